@@ -491,6 +491,7 @@ function createGPUSupportedLimits(limits) {
  * @property {number} maxVertexAttributes
  * @property {number} maxVertexBufferArrayStride
  * @property {number} maxInterStageShaderComponents
+ * @property {number} maxColorAttachmentBytesPerSample
  * @property {number} maxComputeWorkgroupStorageSize
  * @property {number} maxComputeInvocationsPerWorkgroup
  * @property {number} maxComputeWorkgroupSizeX
@@ -594,6 +595,10 @@ class GPUSupportedLimits {
     webidl.assertBranded(this, GPUSupportedLimitsPrototype);
     return this[_limits].maxInterStageShaderComponents;
   }
+  get maxColorAttachmentBytesPerSample() {
+    webidl.assertBranded(this, GPUSupportedLimitsPrototype);
+    return this[_limits].maxColorAttachmentBytesPerSample;
+  }
   get maxComputeWorkgroupStorageSize() {
     webidl.assertBranded(this, GPUSupportedLimitsPrototype);
     return this[_limits].maxComputeWorkgroupStorageSize;
@@ -647,6 +652,7 @@ class GPUSupportedLimits {
           "maxVertexAttributes",
           "maxVertexBufferArrayStride",
           "maxInterStageShaderComponents",
+          "maxColorAttachmentBytesPerSample",
           "maxComputeWorkgroupStorageSize",
           "maxComputeInvocationsPerWorkgroup",
           "maxComputeWorkgroupSizeX",
@@ -247,6 +247,7 @@ impl WgpuContext {
             layout: Some(&pipeline_layout),
             module: &shader,
             entry_point: "main",
+            cache: None,
         });
 
         WgpuContext {
@@ -288,6 +288,7 @@ fn compute_pass(
         layout: None,
         module,
         entry_point: "main_cs",
+        cache: None,
     });
     let bind_group_layout = compute_pipeline.get_bind_group_layout(0);
     let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -353,6 +354,7 @@ fn render_pass(
         depth_stencil: None,
         multisample: wgpu::MultisampleState::default(),
         multiview: None,
+        cache: None,
     });
 
     let render_target = device.create_texture(&wgpu::TextureDescriptor {
@@ -58,7 +58,7 @@ struct Args {
 
     /// the shader model to use if targeting HLSL
     ///
-    /// May be `50`, 51`, or `60`
+    /// May be `50`, `51`, or `60` through `67`
     #[argh(option)]
     shader_model: Option<ShaderModelArg>,
 
@@ -93,10 +93,37 @@ struct Args {
     #[argh(option)]
     before_compaction: Option<String>,
 
+    /// keep only the entry point named by `--entry-point`, and the globals,
+    /// functions, types and constants it uses.
+    ///
+    /// This implies `--compact`.
+    #[argh(switch)]
+    entry_point_only: bool,
+
+    /// optimize the module's IR with all of Naga's optimization passes, and
+    /// revalidate.
+    ///
+    /// Small functions are inlined, constant expressions are evaluated, and
+    /// dead code is removed. Output files will reflect the optimized IR.
+    #[argh(switch)]
+    optimize: bool,
+
     /// bulk validation mode: all filenames are inputs to read and validate.
     #[argh(switch)]
     bulk_validate: bool,
 
+    /// format mode: lay out each WGSL input file in a consistent style, in
+    /// place.
+    ///
+    /// If no files are given, format STDIN and write the result to STDOUT.
+    #[argh(switch)]
+    format: bool,
+
+    /// with `--format`, don't write anything, but fail if any input is not
+    /// already formatted.
+    #[argh(switch)]
+    check: bool,
+
     /// show version
     #[argh(switch)]
     version: bool,
@@ -150,6 +177,13 @@ impl FromStr for ShaderModelArg {
             "50" => ShaderModel::V5_0,
             "51" => ShaderModel::V5_1,
             "60" => ShaderModel::V6_0,
+            "61" => ShaderModel::V6_1,
+            "62" => ShaderModel::V6_2,
+            "63" => ShaderModel::V6_3,
+            "64" => ShaderModel::V6_4,
+            "65" => ShaderModel::V6_5,
+            "66" => ShaderModel::V6_6,
+            "67" => ShaderModel::V6_7,
             _ => return Err(format!("Invalid value for --shader-model: {s}")),
         }))
     }
@@ -301,6 +335,10 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
         return bulk_validate(args, &params);
     }
 
+    if args.format {
+        return format(args);
+    }
+
     let (input_path, input) = if let Some(path) = args.files.first() {
         let path = Path::new(path);
         (path, fs::read(path)?)
@@ -370,7 +408,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     // Compact the module, if requested.
-    let info = if args.compact || args.before_compaction.is_some() {
+    let info = if args.compact || args.before_compaction.is_some() || args.entry_point_only {
         // Compact only if validation succeeded. Otherwise, compaction may panic.
         if info.is_some() {
             // Write out the module state before compaction, if requested.
@@ -378,7 +416,19 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 write_output(&module, &info, &params, before_compaction)?;
             }
 
-            naga::compact::compact(&mut module);
+            if args.entry_point_only {
+                let name = args.entry_point.as_ref().ok_or(CliError(
+                    "The `--entry-point-only` option requires `--entry-point`",
+                ))?;
+                let index = module
+                    .entry_points
+                    .iter()
+                    .position(|ep| ep.name == *name)
+                    .ok_or(CliError("Unable to find the entry point"))?;
+                naga::compact::retain_entry_point(&mut module, index);
+            } else {
+                naga::compact::compact(&mut module);
+            }
 
             // Re-validate the IR after compaction.
             match naga::valid::Validator::new(params.validation_flags, validation_caps)
@@ -404,6 +454,33 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
         info
     };
 
+    // Optimize the module, if requested.
+    let info = if args.optimize {
+        // Optimize only if validation succeeded. Otherwise, the passes may panic.
+        if info.is_some() {
+            let mut validator =
+                naga::valid::Validator::new(params.validation_flags, validation_caps);
+            match naga::opt::optimize(&mut module, &naga::opt::Options::default(), &mut validator) {
+                Ok(info) => Some(info),
+                Err(error) => {
+                    // Validation failure is not fatal. Just report the error.
+                    eprintln!("Error validating optimized module:");
+                    if let Some(input) = &input_text {
+                        let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
+                        emit_annotated_error(&error, filename.unwrap_or("input"), input);
+                    }
+                    print_err(&error);
+                    None
+                }
+            }
+        } else {
+            eprintln!("Skipping optimization due to validation failure.");
+            None
+        }
+    } else {
+        info
+    };
+
     // If no output was requested, then report validation results and stop here.
     //
     // If the user asked for output, don't stop: some output formats (".txt",
@@ -444,13 +521,26 @@ fn parse_input(
         "spv" => naga::front::spv::parse_u8_slice(&input, &params.spv_in).map(|m| (m, None))?,
         "wgsl" => {
             let input = String::from_utf8(input)?;
-            let result = naga::front::wgsl::parse_str(&input);
+            let mut frontend = naga::front::wgsl::Frontend::new();
+            // Imports are looked up relative to the importing file.
+            let mut resolver = |name: &str, importer: Option<&str>| {
+                let importer = importer.map_or(input_path, Path::new);
+                let path = importer.parent().unwrap_or(Path::new("")).join(name);
+                let source = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+                Ok(naga::front::wgsl::ImportedSource {
+                    name: path.to_string_lossy().into_owned(),
+                    source,
+                })
+            };
+            let result = frontend.parse_with_imports(&input, &mut resolver);
+            // Spans refer to the source with its imports appended.
+            let expanded = frontend.source_map().expanded_source().to_string();
             match result {
-                Ok(v) => (v, Some(input)),
+                Ok(v) => (v, Some(expanded)),
                 Err(ref e) => {
                     let message = format!(
                         "Could not parse WGSL:\n{}",
-                        e.emit_to_string_with_path(&input, input_path)
+                        e.emit_to_string_with_path(&expanded, input_path)
                     );
                     return Err(message.into());
                 }
@@ -459,10 +549,19 @@ fn parse_input(
         ext @ ("vert" | "frag" | "comp" | "glsl") => {
             let input = String::from_utf8(input)?;
             let mut parser = naga::front::glsl::Frontend::default();
+            // Includes are looked up relative to the including file.
+            let mut resolver = |name: &str, _, includer: Option<&str>| {
+                let includer = includer.map_or(input_path, Path::new);
+                let path = includer.parent().unwrap_or(Path::new("")).join(name);
+                let source = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+                Ok(naga::front::glsl::IncludedSource {
+                    name: path.to_string_lossy().into_owned(),
+                    source,
+                })
+            };
 
-            (
-                parser
-                    .parse(
+            let module = parser
+                    .parse_with_includes(
                         &naga::front::glsl::Options {
                             stage: match ext {
                                 "vert" => naga::ShaderStage::Vertex,
@@ -487,13 +586,22 @@ fn parse_input(
                             defines: Default::default(),
                         },
                         &input,
+                        &mut resolver,
                     )
                     .unwrap_or_else(|errors| {
                         let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
-                        emit_glsl_parser_error(errors, filename.unwrap_or("glsl"), &input);
+                        emit_glsl_parser_error(
+                            errors,
+                            filename.unwrap_or("glsl"),
+                            &input,
+                            parser.source_map(),
+                        );
                         std::process::exit(1);
-                    }),
-                Some(input),
+                    });
+            // Spans refer to the source with its includes expanded.
+            (
+                module,
+                Some(parser.source_map().expanded_source().to_string()),
             )
         }
         _ => return Err(CliError("Unknown input file extension").into()),
@@ -625,6 +733,12 @@ fn write_output(
             let output = dot::write(module, info.as_ref(), params.dot.clone())?;
             fs::write(output_path, output)?;
         }
+        "rs" => {
+            use naga::back::rust;
+
+            let output = rust::write_string(module, &rust::Options::default())?;
+            fs::write(output_path, output)?;
+        }
         "hlsl" => {
             use naga::back::hlsl;
             let mut buffer = String::new();
@@ -709,9 +823,60 @@ fn bulk_validate(args: Args, params: &Parameters) -> Result<(), Box<dyn std::err
     Ok(())
 }
 
+fn format(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let format_wgsl = |input: &str, path: &Path| {
+        naga::front::wgsl::format_str(input).map_err(|e| {
+            format!(
+                "Could not parse WGSL:\n{}",
+                e.emit_to_string_with_path(input, path)
+            )
+        })
+    };
+
+    if args.files.is_empty() {
+        let mut input = String::new();
+        std::io::stdin().lock().read_to_string(&mut input)?;
+        let path = Path::new(args.stdin_file_path.as_deref().unwrap_or("stdin"));
+        let formatted = format_wgsl(&input, path)?;
+        if !args.check {
+            print!("{formatted}");
+        } else if formatted != input {
+            return Err(CliError("Input is not formatted").into());
+        }
+        return Ok(());
+    }
+
+    let mut unformatted = vec![];
+    for input_path in args.files {
+        let path = Path::new(&input_path);
+        let input = fs::read_to_string(path)?;
+        let formatted = format_wgsl(&input, path)?;
+        if formatted == input {
+            continue;
+        }
+        if args.check {
+            unformatted.push(input_path);
+        } else {
+            fs::write(path, formatted)?;
+        }
+    }
+
+    if !unformatted.is_empty() {
+        use std::fmt::Write;
+        let mut formatted = String::new();
+        writeln!(&mut formatted, "The following inputs are not formatted:").unwrap();
+        for path in unformatted {
+            writeln!(&mut formatted, "  {path}").unwrap();
+        }
+        return Err(formatted.into());
+    }
+
+    Ok(())
+}
+
 use codespan_reporting::{
     diagnostic::{Diagnostic, Label},
-    files::SimpleFile,
+    files::{SimpleFile, SimpleFiles},
     term::{
         self,
         termcolor::{ColorChoice, StandardStream},
@@ -719,16 +884,29 @@ use codespan_reporting::{
 };
 use naga::WithSpan;
 
-pub fn emit_glsl_parser_error(errors: Vec<naga::front::glsl::Error>, filename: &str, source: &str) {
-    let files = SimpleFile::new(filename, source);
+pub fn emit_glsl_parser_error(
+    errors: Vec<naga::front::glsl::Error>,
+    filename: &str,
+    source: &str,
+    source_map: &naga::front::glsl::SourceMap,
+) {
+    let mut files = SimpleFiles::new();
+    let main_file = files.add(filename.to_string(), source.to_string());
+    let included_files: Vec<_> = source_map
+        .files()
+        .iter()
+        .map(|file| files.add(file.name.clone(), file.source.clone()))
+        .collect();
     let config = codespan_reporting::term::Config::default();
     let writer = StandardStream::stderr(ColorChoice::Auto);
 
     for err in errors {
         let mut diagnostic = Diagnostic::error().with_message(err.kind.to_string());
 
-        if let Some(range) = err.meta.to_range() {
-            diagnostic = diagnostic.with_labels(vec![Label::primary((), range)]);
+        let (file, span) = source_map.locate(err.meta);
+        if let Some(range) = span.to_range() {
+            let file_id = file.map_or(main_file, |index| included_files[index]);
+            diagnostic = diagnostic.with_labels(vec![Label::primary(file_id, range)]);
         }
 
         term::emit(&mut writer.lock(), &config, &files, &diagnostic).expect("cannot write error");
@@ -746,6 +924,9 @@ pub fn emit_annotated_error<E: Error>(ann_err: &WithSpan<E>, filename: &str, sou
             .map(|(span, desc)| {
                 Label::primary((), span.to_range().unwrap()).with_message(desc.to_owned())
             })
+            .chain(ann_err.notes().map(|(span, desc)| {
+                Label::secondary((), span.to_range().unwrap()).with_message(desc.to_owned())
+            }))
             .collect(),
     );
 
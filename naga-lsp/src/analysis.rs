@@ -0,0 +1,282 @@
+//! Answers to language server queries about a WGSL document.
+//!
+//! Each query parses the document afresh. Naga's front end is fast enough
+//! that this is not noticeable for shaders of any reasonable size.
+
+use crate::line_index::LineIndex;
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticRelatedInformation,
+    DiagnosticSeverity, Hover, HoverContents, Location, MarkupContent, MarkupKind, Position, Range,
+    Url,
+};
+use naga::front::wgsl;
+use naga::valid::{Capabilities, ModuleInfo, ValidationFlags, Validator};
+use std::error::Error;
+
+const SOURCE: &str = "naga";
+
+/// Parse and validate `text`, and return all the errors found.
+///
+/// `uri` is used to refer to other places in the document that the errors
+/// mention.
+pub fn diagnostics(uri: &Url, text: &str) -> Vec<Diagnostic> {
+    let index = LineIndex::new(text);
+    let module = match wgsl::parse_str(text) {
+        Ok(module) => module,
+        Err(error) => {
+            return error
+                .errors()
+                .map(|error| {
+                    let labels = error
+                        .labels()
+                        .map(|(span, label)| (span, label.to_string()));
+                    diagnostic(uri, &index, error.message().to_string(), labels)
+                })
+                .collect();
+        }
+    };
+
+    match validate(&module) {
+        Ok(_) => Vec::new(),
+        Err(error) => {
+            // Validation errors nest, so include the whole chain of causes.
+            let mut message = error.to_string();
+            let mut source = error.source();
+            while let Some(cause) = source {
+                message.push_str(": ");
+                message.push_str(&cause.to_string());
+                source = cause.source();
+            }
+            let labels = error.spans().chain(error.notes()).cloned();
+            vec![diagnostic(uri, &index, message, labels)]
+        }
+    }
+}
+
+/// Build a diagnostic from an error message and its labelled spans.
+///
+/// The diagnostic's range is the first label's span. The other labels become
+/// related information, so that editors can show them alongside.
+fn diagnostic(
+    uri: &Url,
+    index: &LineIndex,
+    message: String,
+    labels: impl Iterator<Item = (naga::Span, String)>,
+) -> Diagnostic {
+    let mut labels = labels.filter(|&(span, _)| span.is_defined());
+    let range = labels
+        .next()
+        .map_or(Range::default(), |(span, _)| index.range(span));
+    let related_information = labels
+        .map(|(span, label)| DiagnosticRelatedInformation {
+            location: Location::new(uri.clone(), index.range(span)),
+            message: label,
+        })
+        .collect::<Vec<_>>();
+
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some(SOURCE.to_string()),
+        message,
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        ..Default::default()
+    }
+}
+
+fn validate(
+    module: &naga::Module,
+) -> Result<ModuleInfo, naga::WithSpan<naga::valid::ValidationError>> {
+    Validator::new(ValidationFlags::all(), Capabilities::all()).validate(module)
+}
+
+/// Describe the type of the innermost expression at `position`.
+///
+/// This requires `text` to parse and validate, since the types come from
+/// validation.
+pub fn hover(text: &str, position: Position) -> Option<Hover> {
+    let index = LineIndex::new(text);
+    let offset = index.offset(position)?;
+    let module = wgsl::parse_str(text).ok()?;
+    let info = validate(&module).ok()?;
+
+    // Naga doesn't make a new expression for each use of a function
+    // argument, so look up names at their definition first.
+    let (range, ty) = reference_at(text, offset)
+        .and_then(|reference| {
+            let definition = reference.definition.to_range()?.start;
+            let (_, ty) = innermost_expression(&module, &info, definition)?;
+            Some((reference.usage.to_range()?, ty))
+        })
+        .or_else(|| innermost_expression(&module, &info, offset))?;
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("```wgsl\n{ty}\n```"),
+        }),
+        range: Some(index.range(naga::Span::from(range))),
+    })
+}
+
+/// Return the span and type of the innermost expression at `offset`.
+///
+/// The innermost expression is the one with the shortest span. When spans
+/// are equal, prefer the later expression: a load of a variable has the
+/// same span as the variable itself, and its type is the more useful one.
+fn innermost_expression(
+    module: &naga::Module,
+    info: &ModuleInfo,
+    offset: usize,
+) -> Option<(std::ops::Range<usize>, String)> {
+    let gctx = module.to_ctx();
+    let mut best: Option<(std::ops::Range<usize>, &naga::proc::TypeResolution)> = None;
+    let mut consider = |span: naga::Span, ty| {
+        let Some(range) = span.to_range() else {
+            return;
+        };
+        if !range.contains(&offset) {
+            return;
+        }
+        let innermost = match best {
+            Some((ref best, _)) => range.len() <= best.len(),
+            None => true,
+        };
+        if innermost {
+            best = Some((range, ty));
+        }
+    };
+
+    let exprs = &module.const_expressions;
+    for (handle, _) in exprs.iter() {
+        consider(exprs.get_span(handle), &info[handle]);
+    }
+    for (handle, function) in module.functions.iter() {
+        let function_info = &info[handle];
+        for (expr, _) in function.expressions.iter() {
+            consider(function.expressions.get_span(expr), &function_info[expr].ty);
+        }
+    }
+    for (index, entry_point) in module.entry_points.iter().enumerate() {
+        let function_info = info.get_entry_point(index);
+        let exprs = &entry_point.function.expressions;
+        for (expr, _) in exprs.iter() {
+            consider(exprs.get_span(expr), &function_info[expr].ty);
+        }
+    }
+
+    best.map(|(range, ty)| (range, ty.to_wgsl(&gctx)))
+}
+
+/// Find the declaration of the name at `position`.
+///
+/// This only requires `text` to parse.
+pub fn definition(text: &str, position: Position) -> Option<Range> {
+    let index = LineIndex::new(text);
+    let offset = index.offset(position)?;
+    let reference = reference_at(text, offset)?;
+    Some(index.range(reference.definition))
+}
+
+/// Return the reference to a name at `offset`, if any.
+///
+/// An offset just past the end of a name counts as being on it, since
+/// that's where the cursor sits after typing it.
+fn reference_at(text: &str, offset: usize) -> Option<wgsl::Reference> {
+    let references = wgsl::Frontend::new().references(text).ok()?;
+    references.into_iter().find(|reference| {
+        reference
+            .usage
+            .to_range()
+            .is_some_and(|range| range.start <= offset && offset <= range.end)
+    })
+}
+
+/// Return completions for WGSL's builtin functions.
+pub fn completions() -> Vec<CompletionItem> {
+    wgsl::BUILTIN_FUNCTIONS
+        .iter()
+        .map(|&name| CompletionItem {
+            label: name.to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADER: &str = "\
+struct Light {
+    color: vec3<f32>,
+}
+
+fn shade(light: Light, scale: f32) -> vec3<f32> {
+    let color = light.color * scale;
+    return color;
+}
+";
+
+    fn uri() -> Url {
+        Url::parse("file:///shader.wgsl").unwrap()
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let text = "fn f() {\n    let a = ;\n    let b = ;\n}\n";
+        let found = diagnostics(&uri(), text);
+        let lines: Vec<_> = found.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, [1, 2]);
+    }
+
+    #[test]
+    fn validation_error() {
+        let text = "fn f() -> i32 {\n    return 1u;\n}\n";
+        let found = diagnostics(&uri(), text);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].message.contains("return"));
+        assert!(diagnostics(&uri(), SHADER).is_empty());
+    }
+
+    #[test]
+    fn hover_type() {
+        // `scale` in `light.color * scale`.
+        let found = hover(SHADER, Position::new(5, 32)).unwrap();
+        let HoverContents::Markup(contents) = found.contents else {
+            panic!("unexpected hover contents");
+        };
+        assert_eq!(contents.value, "```wgsl\nf32\n```");
+        assert_eq!(
+            found.range,
+            Some(Range::new(Position::new(5, 30), Position::new(5, 35)))
+        );
+
+        // `light.color`.
+        let found = hover(SHADER, Position::new(5, 22)).unwrap();
+        let HoverContents::Markup(contents) = found.contents else {
+            panic!("unexpected hover contents");
+        };
+        assert_eq!(contents.value, "```wgsl\nvec3<f32>\n```");
+    }
+
+    #[test]
+    fn go_to_definition() {
+        // `Light` in the parameter list goes to the struct.
+        let range = definition(SHADER, Position::new(4, 18)).unwrap();
+        assert_eq!(range, Range::new(Position::new(0, 7), Position::new(0, 12)));
+        // `color` in the return statement goes to the `let`.
+        let range = definition(SHADER, Position::new(6, 11)).unwrap();
+        assert_eq!(range, Range::new(Position::new(5, 8), Position::new(5, 13)));
+    }
+
+    #[test]
+    fn utf16_positions() {
+        let index = LineIndex::new("// é😀\nx");
+        assert_eq!(index.position(9), Position::new(0, 6));
+        assert_eq!(index.offset(Position::new(0, 6)), Some(9));
+        assert_eq!(index.offset(Position::new(1, 0)), Some(10));
+        assert_eq!(index.offset(Position::new(0, 99)), Some(9));
+    }
+}
@@ -0,0 +1,63 @@
+//! Conversion between byte offsets in a document and LSP positions.
+
+use lsp_types::{Position, Range};
+
+/// The start of each line of a document.
+///
+/// Naga's [`Span`]s are byte offsets, but LSP [`Position`]s are a line number
+/// and a column counted in UTF-16 code units.
+///
+/// [`Span`]: naga::Span
+pub struct LineIndex<'a> {
+    text: &'a str,
+    /// The byte offset of the start of each line.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    /// Return the position of the byte at `offset`.
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let character = self.text[line_start..offset].encode_utf16().count();
+        Position::new(line as u32, character as u32)
+    }
+
+    /// Return the byte offset of `position`, or `None` if it's past the end
+    /// of the document.
+    ///
+    /// A position past the end of a line is taken to be at its end.
+    pub fn offset(&self, position: Position) -> Option<usize> {
+        let line = position.line as usize;
+        let line_start = *self.line_starts.get(line)?;
+        let line_end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.text.len(), |&next| next - 1);
+
+        let mut units = 0;
+        for (offset, ch) in self.text[line_start..line_end].char_indices() {
+            if units >= position.character as usize {
+                return Some(line_start + offset);
+            }
+            units += ch.len_utf16();
+        }
+        Some(line_end)
+    }
+
+    /// Return the range covered by `span`.
+    ///
+    /// Undefined spans are placed at the start of the document.
+    pub fn range(&self, span: naga::Span) -> Range {
+        let range = span.to_range().unwrap_or(0..0);
+        Range::new(self.position(range.start), self.position(range.end))
+    }
+}
@@ -0,0 +1,196 @@
+//! A WGSL language server built on naga.
+//!
+//! The server speaks the Language Server Protocol over stdin and stdout, and
+//! needs nothing beyond the documents the editor sends it. It offers:
+//!
+//! - diagnostics from naga's WGSL front end and validator,
+//! - hover showing the type of the expression under the cursor,
+//! - go-to-definition for names, and
+//! - completion of WGSL's builtin functions.
+
+mod analysis;
+mod line_index;
+
+use lsp_server::{Connection, ExtractError, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest},
+    CompletionOptions, CompletionResponse, GotoDefinitionResponse, HoverProviderCapability,
+    InitializeParams, Location, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use std::{collections::HashMap, error::Error};
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+fn main() -> Result<(), BoxError> {
+    // stdout carries the protocol, so logs go to stderr, env_logger's default.
+    env_logger::init();
+
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        ..Default::default()
+    };
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    log::info!(
+        "initialized by {}",
+        params
+            .client_info
+            .as_ref()
+            .map_or("unknown client", |info| info.name.as_str())
+    );
+
+    // The server owns the connection, so that it's dropped before we wait
+    // for the I/O threads to finish.
+    Server {
+        connection,
+        documents: HashMap::new(),
+    }
+    .run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
+
+struct Server {
+    connection: Connection,
+    /// The text of each open document.
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    fn run(mut self) -> Result<(), BoxError> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.request(request)?;
+                }
+                Message::Notification(notification) => self.notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn request(&self, request: Request) -> Result<(), BoxError> {
+        let request = match request.extract::<lsp_types::HoverParams>(
+            <HoverRequest as lsp_types::request::Request>::METHOD,
+        ) {
+            Ok((id, params)) => {
+                let position = params.text_document_position_params;
+                let result = self
+                    .document(&position.text_document.uri)
+                    .and_then(|text| analysis::hover(text, position.position));
+                return self.respond(id, result);
+            }
+            Err(ExtractError::MethodMismatch(request)) => request,
+            Err(error) => return Err(error.into()),
+        };
+
+        let request = match request.extract::<lsp_types::GotoDefinitionParams>(
+            <GotoDefinition as lsp_types::request::Request>::METHOD,
+        ) {
+            Ok((id, params)) => {
+                let position = params.text_document_position_params;
+                let uri = position.text_document.uri;
+                let result = self
+                    .document(&uri)
+                    .and_then(|text| analysis::definition(text, position.position))
+                    .map(|range| GotoDefinitionResponse::Scalar(Location::new(uri, range)));
+                return self.respond(id, result);
+            }
+            Err(ExtractError::MethodMismatch(request)) => request,
+            Err(error) => return Err(error.into()),
+        };
+
+        let request = match request.extract::<lsp_types::CompletionParams>(
+            <Completion as lsp_types::request::Request>::METHOD,
+        ) {
+            Ok((id, _)) => {
+                let result = CompletionResponse::Array(analysis::completions());
+                return self.respond(id, Some(result));
+            }
+            Err(ExtractError::MethodMismatch(request)) => request,
+            Err(error) => return Err(error.into()),
+        };
+
+        log::warn!("unhandled request: {}", request.method);
+        let response = Response::new_err(
+            request.id,
+            lsp_server::ErrorCode::MethodNotFound as i32,
+            format!("unhandled method {}", request.method),
+        );
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn notification(&mut self, notification: Notification) -> Result<(), BoxError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                self.publish_diagnostics(&document.uri, &document.text)?;
+                self.documents.insert(document.uri, document.text);
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                // We only ask for full document sync, so the last change
+                // holds the whole text.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    let uri = params.text_document.uri;
+                    self.publish_diagnostics(&uri, &change.text)?;
+                    self.documents.insert(uri, change.text);
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                // Clear the closed document's diagnostics.
+                self.send_diagnostics(uri, Vec::new())?;
+            }
+            _ => log::debug!("ignored notification: {}", notification.method),
+        }
+        Ok(())
+    }
+
+    fn document(&self, uri: &Url) -> Option<&str> {
+        self.documents.get(uri).map(String::as_str)
+    }
+
+    fn respond<T: serde::Serialize>(&self, id: RequestId, result: T) -> Result<(), BoxError> {
+        let response = Response::new_ok(id, result);
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn publish_diagnostics(&self, uri: &Url, text: &str) -> Result<(), BoxError> {
+        self.send_diagnostics(uri.clone(), analysis::diagnostics(uri, text))
+    }
+
+    fn send_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+    ) -> Result<(), BoxError> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+}
@@ -278,6 +278,12 @@ impl<T> Arena<T> {
         self.data
     }
 
+    /// Extracts the inner vector, along with the span of each item.
+    #[cfg(any(feature = "link", feature = "opt"))]
+    pub(crate) fn into_inner_with_spans(self) -> impl Iterator<Item = (T, Span)> {
+        self.data.into_iter().zip(self.span_info)
+    }
+
     /// Returns the current number of items stored in this arena.
     pub fn len(&self) -> usize {
         self.data.len()
@@ -279,6 +279,27 @@ impl StatementGraph {
                         crate::RayQueryFunction::Terminate => "RayQueryTerminate",
                     }
                 }
+                S::MeshFunction(ref fun) => match *fun {
+                    crate::MeshFunction::SetMeshOutputs {
+                        vertex_count,
+                        primitive_count,
+                    } => {
+                        self.dependencies.push((id, vertex_count, "vertex_count"));
+                        self.dependencies
+                            .push((id, primitive_count, "primitive_count"));
+                        "SetMeshOutputs"
+                    }
+                    crate::MeshFunction::SetVertex { index, value } => {
+                        self.dependencies.push((id, index, "index"));
+                        self.dependencies.push((id, value, "value"));
+                        "SetVertex"
+                    }
+                    crate::MeshFunction::SetPrimitive { index, value } => {
+                        self.dependencies.push((id, index, "index"));
+                        self.dependencies.push((id, value, "value"));
+                        "SetPrimitive"
+                    }
+                },
             };
             // Set the last node to the merge node
             last_node = merge_id;
@@ -452,6 +473,7 @@ fn write_function_expressions(
                 offset: _,
                 level,
                 depth_ref,
+                clamp_to_edge,
             } => {
                 edges.insert("image", image);
                 edges.insert("sampler", sampler);
@@ -478,6 +500,7 @@ fn write_function_expressions(
                 }
                 let string = match gather {
                     Some(component) => Cow::Owned(format!("ImageGather{component:?}")),
+                    _ if clamp_to_edge => Cow::Borrowed("ImageSampleClampToEdge"),
                     _ => Cow::Borrowed("ImageSample"),
                 };
                 (string, 5)
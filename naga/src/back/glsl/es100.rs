@@ -0,0 +1,869 @@
+/*!
+Support for GLSL ES 1.00, the shading language of OpenGL ES 2.0 and WebGL 1.
+
+GLSL ES 1.00 predates most of what the rest of this backend relies on: it has
+no unsigned integers, no integer vertex inputs or varyings, no interface
+blocks, no `switch` statement, no bitwise operators and, per Appendix A of its
+specification, only guarantees `for` loops whose iteration count is known at
+compile time. The writer lowers what it can:
+
+- `u32` values are written as `int`, since literals must fit in an `int`.
+  Comparisons, divisions and remainders of them are rejected, as they would
+  treat values of 2<sup>31</sup> and up as negative.
+- Integer vertex inputs and varyings are declared as floats and rounded back
+  to integers when read. This is only exact for values that are the same at
+  every vertex of a primitive, which is what `@interpolate(flat)` integers
+  usually carry.
+- Fragment outputs are written to `gl_FragColor`, padded to a `vec4`.
+- Uniform buffers are declared as plain `uniform` variables.
+- Loops that the WGSL front end produces for counted `for` statements are
+  wrapped in a `for` loop with a constant bound; see [`loop_iteration_bound`].
+- Appendix A only guarantees indexing with constants and `for` loop indices,
+  apart from uniforms in vertex shaders. Loads of the counter of an enclosing
+  counted loop are written in terms of the `for` loop's index, as
+  `start + index * step`; any other dynamic index is rejected.
+
+Everything else is reported by [`Writer::check_es100`] as an
+[`Error::Es100Unsupported`] naming the [`Es100Construct`] and where it was
+found.
+*/
+
+use super::{BackendResult, Error, Writer};
+use crate::{
+    valid, BinaryOperator as Bo, Expression, Handle, ImageClass, ImageDimension, ScalarKind,
+    ShaderStage, Statement, TypeInner,
+};
+use std::fmt::Write;
+
+/// An IR construct that can't be written as GLSL ES 1.00.
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum Es100Construct {
+    #[error("the unsigned integer {0}, which doesn't fit in an `int`")]
+    UnsignedOverflow(u32),
+    #[error("the {0:?} operator on integers")]
+    BinaryOperator(crate::BinaryOperator),
+    #[error("the {0:?} operator on unsigned integers, which are written as `int`")]
+    UnsignedOperator(crate::BinaryOperator),
+    #[error("the {0:?} operator")]
+    UnaryOperator(crate::UnaryOperator),
+    #[error("the {fun:?} function on {kind:?} values")]
+    MathFunction {
+        fun: crate::MathFunction,
+        kind: ScalarKind,
+    },
+    #[error("the {0:?} function")]
+    RelationalFunction(crate::RelationalFunction),
+    #[error("a bit cast from {from:?} to {to:?}")]
+    Bitcast { from: ScalarKind, to: ScalarKind },
+    #[error("a {columns:?}x{rows:?} matrix, as only square matrices are available")]
+    NonSquareMatrix {
+        columns: crate::VectorSize,
+        rows: crate::VectorSize,
+    },
+    #[error("an array constructor")]
+    ArrayConstructor,
+    #[error("an array assignment")]
+    ArrayAssignment,
+    #[error("a function returning an array")]
+    ArrayReturn,
+    #[error("{}{dim:?} images of class {class:?}", if *arrayed { "arrayed " } else { "" })]
+    Image {
+        dim: ImageDimension,
+        arrayed: bool,
+        class: ImageClass,
+    },
+    #[error("{0}")]
+    ImageOperation(&'static str),
+    #[error("a `switch` statement")]
+    Switch,
+    #[error("a loop whose iteration count isn't a compile-time constant")]
+    DynamicLoop,
+    #[error("an index that is neither a constant nor the counter of an enclosing loop")]
+    DynamicIndex,
+    #[error("the {0:?} built-in")]
+    BuiltIn(crate::BuiltIn),
+    #[error("a fragment output at location {0}, as only `gl_FragColor` is available")]
+    FragmentOutput(u32),
+    #[error("{0:?} interpolation of floats")]
+    Interpolation(crate::Interpolation),
+    #[error("{0:?} sampling")]
+    Sampling(crate::Sampling),
+}
+
+impl<'a, W: Write> Writer<'a, W> {
+    /// Checks that everything written for the selected entry point can be
+    /// represented in GLSL ES 1.00.
+    ///
+    /// # Errors
+    /// Returns an [`Error::Es100Unsupported`] for the first construct that
+    /// can't be written.
+    pub(super) fn check_es100(&self) -> BackendResult {
+        let module = self.module;
+        let ep_info = self.info.get_entry_point(self.entry_point_idx as usize);
+        let stage = self.entry_point.stage;
+
+        let unsupported = |location: String| {
+            move |construct| Error::Es100Unsupported {
+                location,
+                construct,
+            }
+        };
+
+        // Every struct type is written, whether it's used or not.
+        for (handle, ty) in module.types.iter() {
+            if let TypeInner::Struct { ref members, .. } = ty.inner {
+                for member in members {
+                    check_type(module, member.ty).map_err(unsupported(format!(
+                        "struct `{}`",
+                        self.names[&crate::proc::NameKey::Type(handle)]
+                    )))?;
+                }
+            }
+        }
+
+        for (handle, constant) in module.constants.iter() {
+            if constant.name.is_some() && contains_array(module, constant.ty) {
+                return Err(unsupported(format!(
+                    "constant `{}`",
+                    self.names[&crate::proc::NameKey::Constant(handle)]
+                ))(Es100Construct::ArrayConstructor));
+            }
+        }
+        for (_, expression) in module.const_expressions.iter() {
+            if let Expression::Literal(literal) = *expression {
+                check_literal(literal).map_err(unsupported("module scope".to_string()))?;
+            }
+        }
+
+        for (handle, global) in module.global_variables.iter() {
+            if ep_info[handle].is_empty() {
+                continue;
+            }
+            let error = unsupported(format!(
+                "global `{}`",
+                self.names[&crate::proc::NameKey::GlobalVariable(handle)]
+            ));
+            check_type(module, global.ty).map_err(error.clone())?;
+            if global.init.is_some() && contains_array(module, global.ty) {
+                return Err(error(Es100Construct::ArrayConstructor));
+            }
+        }
+
+        for arg in self.entry_point.function.arguments.iter() {
+            check_varying(module, stage, arg.binding.as_ref(), arg.ty, false).map_err(
+                unsupported(format!("entry point `{}`", self.entry_point.name)),
+            )?;
+        }
+        if let Some(ref result) = self.entry_point.function.result {
+            check_varying(module, stage, result.binding.as_ref(), result.ty, true).map_err(
+                unsupported(format!("entry point `{}`", self.entry_point.name)),
+            )?;
+        }
+
+        for (handle, function) in module.functions.iter() {
+            if !self.is_function_written(handle) {
+                continue;
+            }
+            let error = unsupported(format!(
+                "function `{}`",
+                self.names[&crate::proc::NameKey::Function(handle)]
+            ));
+            if let Some(ref result) = function.result {
+                if contains_array(module, result.ty) {
+                    return Err(error(Es100Construct::ArrayReturn));
+                }
+            }
+            check_function(module, function, &self.info[handle], stage).map_err(error)?;
+        }
+
+        check_function(module, &self.entry_point.function, ep_info, stage).map_err(unsupported(
+            format!("entry point `{}`", self.entry_point.name),
+        ))
+    }
+}
+
+/// Returns true if values of type `ty` contain an array, which GLSL ES 1.00
+/// can neither construct nor assign.
+pub(super) fn contains_array(module: &crate::Module, ty: Handle<crate::Type>) -> bool {
+    match module.types[ty].inner {
+        TypeInner::Array { .. } => true,
+        TypeInner::Struct { ref members, .. } => members
+            .iter()
+            .any(|member| contains_array(module, member.ty)),
+        _ => false,
+    }
+}
+
+fn check_type(module: &crate::Module, ty: Handle<crate::Type>) -> Result<(), Es100Construct> {
+    check_type_inner(module, &module.types[ty].inner)
+}
+
+fn check_type_inner(module: &crate::Module, inner: &TypeInner) -> Result<(), Es100Construct> {
+    match *inner {
+        TypeInner::Matrix { columns, rows, .. } if columns != rows => {
+            Err(Es100Construct::NonSquareMatrix { columns, rows })
+        }
+        TypeInner::Pointer { base, .. } | TypeInner::Array { base, .. } => check_type(module, base),
+        TypeInner::Image {
+            dim,
+            arrayed,
+            class,
+        } => match (dim, arrayed, class) {
+            // One-dimensional images are written as two-dimensional ones.
+            (
+                ImageDimension::D1 | ImageDimension::D2 | ImageDimension::Cube,
+                false,
+                ImageClass::Sampled {
+                    kind: ScalarKind::Float,
+                    multi: false,
+                },
+            ) => Ok(()),
+            _ => Err(Es100Construct::Image {
+                dim,
+                arrayed,
+                class,
+            }),
+        },
+        _ => Ok(()),
+    }
+}
+
+fn check_literal(literal: crate::Literal) -> Result<(), Es100Construct> {
+    match literal {
+        crate::Literal::U32(value) if i32::try_from(value).is_err() => {
+            Err(Es100Construct::UnsignedOverflow(value))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_varying(
+    module: &crate::Module,
+    stage: ShaderStage,
+    binding: Option<&crate::Binding>,
+    ty: Handle<crate::Type>,
+    output: bool,
+) -> Result<(), Es100Construct> {
+    if let TypeInner::Struct { ref members, .. } = module.types[ty].inner {
+        for member in members {
+            check_varying(module, stage, member.binding.as_ref(), member.ty, output)?;
+        }
+        return Ok(());
+    }
+
+    match binding {
+        None => Ok(()),
+        Some(&crate::Binding::BuiltIn(built_in)) => match built_in {
+            crate::BuiltIn::Position { .. }
+            | crate::BuiltIn::PointSize
+            | crate::BuiltIn::PointCoord
+            | crate::BuiltIn::FrontFacing => Ok(()),
+            _ => Err(Es100Construct::BuiltIn(built_in)),
+        },
+        Some(&crate::Binding::Location {
+            location,
+            interpolation,
+            sampling,
+            second_blend_source,
+        }) => {
+            if stage == ShaderStage::Fragment && output && (location != 0 || second_blend_source) {
+                return Err(Es100Construct::FragmentOutput(location));
+            }
+
+            // Only varyings between the vertex and fragment stages are interpolated.
+            if (stage == ShaderStage::Vertex) == output {
+                let is_float = module.types[ty].inner.scalar_kind() == Some(ScalarKind::Float);
+                if let Some(crate::Interpolation::Flat) = interpolation {
+                    if is_float {
+                        return Err(Es100Construct::Interpolation(crate::Interpolation::Flat));
+                    }
+                }
+                match sampling {
+                    Some(sampling @ (crate::Sampling::Centroid | crate::Sampling::Sample)) => {
+                        return Err(Es100Construct::Sampling(sampling));
+                    }
+                    Some(crate::Sampling::Center) | None => {}
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn check_function(
+    module: &crate::Module,
+    function: &crate::Function,
+    info: &valid::FunctionInfo,
+    stage: ShaderStage,
+) -> Result<(), Es100Construct> {
+    for argument in function.arguments.iter() {
+        check_type(module, argument.ty)?;
+    }
+    for (_, local) in function.local_variables.iter() {
+        check_type(module, local.ty)?;
+    }
+
+    for (handle, expression) in function.expressions.iter() {
+        check_type_inner(module, info[handle].ty.inner_with(&module.types))?;
+
+        let scalar_kind = |expr: Handle<Expression>| {
+            info[expr]
+                .ty
+                .inner_with(&module.types)
+                .scalar_kind()
+                .unwrap_or(ScalarKind::Float)
+        };
+
+        match *expression {
+            Expression::Literal(literal) => check_literal(literal)?,
+            Expression::Compose { ty, .. } | Expression::ZeroValue(ty) => {
+                if let TypeInner::Array { .. } = module.types[ty].inner {
+                    return Err(Es100Construct::ArrayConstructor);
+                }
+            }
+            Expression::Unary {
+                op: op @ crate::UnaryOperator::BitwiseNot,
+                ..
+            } => return Err(Es100Construct::UnaryOperator(op)),
+            Expression::Binary { op, left, .. } => match op {
+                Bo::And | Bo::ExclusiveOr | Bo::InclusiveOr
+                    if scalar_kind(left) == ScalarKind::Bool && op != Bo::ExclusiveOr => {}
+                Bo::And | Bo::ExclusiveOr | Bo::InclusiveOr | Bo::ShiftLeft | Bo::ShiftRight => {
+                    return Err(Es100Construct::BinaryOperator(op))
+                }
+                Bo::Less
+                | Bo::LessEqual
+                | Bo::Greater
+                | Bo::GreaterEqual
+                | Bo::Divide
+                | Bo::Modulo
+                    if scalar_kind(left) == ScalarKind::Uint =>
+                {
+                    return Err(Es100Construct::UnsignedOperator(op))
+                }
+                _ => {}
+            },
+            Expression::Math { fun, arg, .. } => check_math_function(fun, scalar_kind(arg))?,
+            Expression::Relational { fun, .. } => match fun {
+                crate::RelationalFunction::All | crate::RelationalFunction::Any => {}
+                crate::RelationalFunction::IsNan | crate::RelationalFunction::IsInf => {
+                    return Err(Es100Construct::RelationalFunction(fun))
+                }
+            },
+            Expression::As {
+                expr,
+                kind,
+                convert: None,
+            } => {
+                let from = scalar_kind(expr);
+                // Signed and unsigned integers are both written as `int`.
+                if from != kind && (from == ScalarKind::Float || kind == ScalarKind::Float) {
+                    return Err(Es100Construct::Bitcast { from, to: kind });
+                }
+            }
+            Expression::ImageSample {
+                gather,
+                offset,
+                level,
+                depth_ref,
+                clamp_to_edge,
+                ..
+            } => {
+                let unsupported = if clamp_to_edge {
+                    Some("sampling clamped to the edge texels")
+                } else if gather.is_some() {
+                    Some("texture gathers")
+                } else if depth_ref.is_some() {
+                    Some("depth comparisons")
+                } else if offset.is_some() {
+                    Some("texel offsets")
+                } else {
+                    match level {
+                        crate::SampleLevel::Gradient { .. } => Some("explicit gradients"),
+                        crate::SampleLevel::Zero | crate::SampleLevel::Exact(_)
+                            if stage == ShaderStage::Fragment =>
+                        {
+                            Some("explicit levels of detail in fragment shaders")
+                        }
+                        _ => None,
+                    }
+                };
+                if let Some(operation) = unsupported {
+                    return Err(Es100Construct::ImageOperation(operation));
+                }
+            }
+            Expression::ImageLoad { .. } => {
+                return Err(Es100Construct::ImageOperation("texel loads"))
+            }
+            Expression::ImageQuery { .. } => {
+                return Err(Es100Construct::ImageOperation("image queries"))
+            }
+            _ => {}
+        }
+    }
+
+    check_block(
+        module,
+        function,
+        info,
+        stage,
+        &mut Vec::new(),
+        &function.body,
+    )
+}
+
+fn check_math_function(fun: crate::MathFunction, kind: ScalarKind) -> Result<(), Es100Construct> {
+    use crate::MathFunction as Mf;
+
+    let supported = match kind {
+        ScalarKind::Float => matches!(
+            fun,
+            Mf::Abs
+                | Mf::Min
+                | Mf::Max
+                | Mf::Clamp
+                | Mf::Saturate
+                | Mf::Cos
+                | Mf::Sin
+                | Mf::Tan
+                | Mf::Acos
+                | Mf::Asin
+                | Mf::Atan
+                | Mf::Atan2
+                | Mf::Radians
+                | Mf::Degrees
+                | Mf::Ceil
+                | Mf::Floor
+                | Mf::Fract
+                | Mf::Exp
+                | Mf::Exp2
+                | Mf::Log
+                | Mf::Log2
+                | Mf::Pow
+                | Mf::Dot
+                | Mf::Cross
+                | Mf::Distance
+                | Mf::Length
+                | Mf::Normalize
+                | Mf::FaceForward
+                | Mf::Reflect
+                | Mf::Refract
+                | Mf::Sign
+                | Mf::Fma
+                | Mf::Mix
+                | Mf::Step
+                | Mf::SmoothStep
+                | Mf::Sqrt
+                | Mf::InverseSqrt
+        ),
+        // Integer dot products are expanded into arithmetic.
+        _ => fun == Mf::Dot,
+    };
+
+    if supported {
+        Ok(())
+    } else {
+        Err(Es100Construct::MathFunction { fun, kind })
+    }
+}
+
+/// Checks the statements of `block`.
+///
+/// `counters` holds the counters of the enclosing loops that indices may be
+/// written in terms of; see [`CountedLoop::counter`].
+fn check_block(
+    module: &crate::Module,
+    function: &crate::Function,
+    info: &valid::FunctionInfo,
+    stage: ShaderStage,
+    counters: &mut Vec<Handle<crate::LocalVariable>>,
+    block: &crate::Block,
+) -> Result<(), Es100Construct> {
+    for statement in block.iter() {
+        match *statement {
+            Statement::Emit(ref range) => {
+                for handle in range.clone() {
+                    if let Expression::Access { base, index } = function.expressions[handle] {
+                        check_index(module, function, stage, counters, base, index)?;
+                    }
+                }
+            }
+            Statement::Block(ref block) => {
+                check_block(module, function, info, stage, counters, block)?
+            }
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => {
+                check_block(module, function, info, stage, counters, accept)?;
+                check_block(module, function, info, stage, counters, reject)?;
+            }
+            Statement::Switch { .. } => return Err(Es100Construct::Switch),
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                break_if,
+            } => {
+                let counted = loop_iteration_bound(module, function, body, continuing, break_if)
+                    .ok_or(Es100Construct::DynamicLoop)?;
+                let depth = counters.len();
+                counters.extend(counted.counter.map(|counter| counter.local));
+                check_block(module, function, info, stage, counters, body)?;
+                counters.truncate(depth);
+                check_block(module, function, info, stage, counters, continuing)?;
+            }
+            Statement::Store { value, .. } => {
+                if let TypeInner::Array { .. } = *info[value].ty.inner_with(&module.types) {
+                    return Err(Es100Construct::ArrayAssignment);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `index` is one Appendix A guarantees for indexing `base`: a
+/// constant, a load of one of `counters`, or anything at all when indexing a
+/// uniform in a vertex shader.
+fn check_index(
+    module: &crate::Module,
+    function: &crate::Function,
+    stage: ShaderStage,
+    counters: &[Handle<crate::LocalVariable>],
+    base: Handle<Expression>,
+    index: Handle<Expression>,
+) -> Result<(), Es100Construct> {
+    let expressions = &function.expressions;
+    if constant_value(module, expressions, index).is_some() {
+        return Ok(());
+    }
+    if let Some(local) = loop_counter(expressions, index) {
+        if counters.contains(&local) {
+            return Ok(());
+        }
+    }
+
+    if stage == ShaderStage::Vertex {
+        let mut root = base;
+        while let Expression::Access { base, .. } | Expression::AccessIndex { base, .. } =
+            expressions[root]
+        {
+            root = base;
+        }
+        if let Expression::GlobalVariable(global) = expressions[root] {
+            if module.global_variables[global].space == crate::AddressSpace::Uniform {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(Es100Construct::DynamicIndex)
+}
+
+/// A Naga [`Loop`] that can be written as a `for` loop with a constant bound.
+///
+/// [`Loop`]: crate::Statement::Loop
+pub(super) struct CountedLoop {
+    /// The bound of the `for` loop.
+    pub bound: u32,
+    /// The loop's counter, if its value in the body can be computed from the
+    /// `for` loop's index.
+    ///
+    /// That is the case when the only stores to it are its step, so it always
+    /// starts from its initializer: either the body runs with the counter at
+    /// `start + index * step`, or the counter kept the value that ended the
+    /// loop the last time and the exit test breaks out right away.
+    pub counter: Option<LoopCounter>,
+}
+
+/// The counter of a [`CountedLoop`].
+#[derive(Clone, Copy)]
+pub(super) struct LoopCounter {
+    pub local: Handle<crate::LocalVariable>,
+    pub start: i64,
+    pub step: i64,
+}
+
+/// Returns how GLSL ES 1.00 can run a Naga [`Loop`] as a `for` loop with a
+/// constant bound, or `None` if the loop's iteration count isn't a
+/// compile-time constant.
+///
+/// We recognize the shape the WGSL front end gives
+/// `for (var i = a; i < b; i += c)`: a body that starts by breaking out once
+/// `i` fails a comparison with a constant, a `continuing` block that only steps
+/// `i` by a constant, and no other stores to `i` except of constants. The
+/// bound is one more than the largest trip count from any of those starting
+/// values, so the last `continuing` block still runs before the original exit
+/// test breaks out of the loop.
+///
+/// [`Loop`]: crate::Statement::Loop
+pub(super) fn loop_iteration_bound(
+    module: &crate::Module,
+    function: &crate::Function,
+    body: &crate::Block,
+    continuing: &crate::Block,
+    break_if: Option<Handle<Expression>>,
+) -> Option<CountedLoop> {
+    if break_if.is_some() {
+        return None;
+    }
+    let expressions = &function.expressions;
+
+    // The exit test must come before anything with side effects.
+    let (condition, exit_if_true) = match *body
+        .iter()
+        .find(|statement| !matches!(**statement, Statement::Emit(_)))?
+    {
+        Statement::If {
+            condition,
+            ref accept,
+            ref reject,
+        } => match (&accept[..], &reject[..]) {
+            (&[Statement::Break], &[]) => (condition, true),
+            (&[], &[Statement::Break]) => (condition, false),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let Expression::Binary { op, left, right } = expressions[condition] else {
+        return None;
+    };
+    let (counter, op, limit) = match (
+        loop_counter(expressions, left),
+        constant_value(module, expressions, right),
+    ) {
+        (Some(counter), Some(limit)) => (counter, op, limit),
+        _ => {
+            let counter = loop_counter(expressions, right)?;
+            let limit = constant_value(module, expressions, left)?;
+            let op = match op {
+                Bo::Less => Bo::Greater,
+                Bo::LessEqual => Bo::GreaterEqual,
+                Bo::Greater => Bo::Less,
+                Bo::GreaterEqual => Bo::LessEqual,
+                other => other,
+            };
+            (counter, op, limit)
+        }
+    };
+    // Turn the exit test into the condition under which the loop keeps running.
+    let op = if exit_if_true {
+        match op {
+            Bo::Less => Bo::GreaterEqual,
+            Bo::LessEqual => Bo::Greater,
+            Bo::Greater => Bo::LessEqual,
+            Bo::GreaterEqual => Bo::Less,
+            Bo::Equal => Bo::NotEqual,
+            Bo::NotEqual => Bo::Equal,
+            _ => return None,
+        }
+    } else {
+        op
+    };
+
+    // `continuing` must do nothing but step the counter.
+    let mut steps = continuing
+        .iter()
+        .filter(|statement| !matches!(**statement, Statement::Emit(_)));
+    let (Some(&Statement::Store { pointer, value: step_value }), None) =
+        (steps.next(), steps.next())
+    else {
+        return None;
+    };
+    if expressions[pointer] != Expression::LocalVariable(counter) {
+        return None;
+    }
+    let step = match expressions[step_value] {
+        Expression::Binary {
+            op: op @ (Bo::Add | Bo::Subtract),
+            left,
+            right,
+        } if loop_counter(expressions, left) == Some(counter) => {
+            let step = constant_value(module, expressions, right)?;
+            if op == Bo::Subtract {
+                -step
+            } else {
+                step
+            }
+        }
+        Expression::Binary {
+            op: Bo::Add,
+            left,
+            right,
+        } if loop_counter(expressions, right) == Some(counter) => {
+            constant_value(module, expressions, left)?
+        }
+        _ => return None,
+    };
+    if step == 0 {
+        return None;
+    }
+
+    // Locals without an initializer start out zeroed.
+    let mut starts = vec![match function.local_variables[counter].init {
+        Some(init) => constant_value(module, expressions, init)?,
+        None => 0,
+    }];
+    if !collect_counter_stores(
+        module,
+        expressions,
+        &function.body,
+        counter,
+        step_value,
+        &mut starts,
+    ) {
+        return None;
+    }
+
+    let mut bound = 0;
+    for &start in starts.iter() {
+        bound = bound.max(trip_count(start, op, limit, step)?);
+    }
+    Some(CountedLoop {
+        bound: u32::try_from(bound + 1).ok()?,
+        counter: match starts[..] {
+            [start] => Some(LoopCounter {
+                local: counter,
+                start,
+                step,
+            }),
+            _ => None,
+        },
+    })
+}
+
+/// Returns the local variable `expr` loads from, if any.
+fn loop_counter(
+    expressions: &crate::Arena<Expression>,
+    expr: Handle<Expression>,
+) -> Option<Handle<crate::LocalVariable>> {
+    match expressions[expr] {
+        Expression::Load { pointer } => match expressions[pointer] {
+            Expression::LocalVariable(local) => Some(local),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the value of an integer literal or constant.
+fn constant_value(
+    module: &crate::Module,
+    expressions: &crate::Arena<Expression>,
+    expr: Handle<Expression>,
+) -> Option<i64> {
+    let (expressions, expr) = match expressions[expr] {
+        Expression::Constant(constant) => {
+            (&module.const_expressions, module.constants[constant].init)
+        }
+        _ => (expressions, expr),
+    };
+    match expressions[expr] {
+        Expression::Literal(crate::Literal::I32(value)) => Some(value.into()),
+        Expression::Literal(crate::Literal::U32(value)) => Some(value.into()),
+        _ => None,
+    }
+}
+
+/// Adds the values of all stores to `counter` in `block`, other than the one
+/// of `step_value`, to `starts`.
+///
+/// Returns false if any of them isn't a constant, or if a pointer to
+/// `counter` escapes into a function call.
+fn collect_counter_stores(
+    module: &crate::Module,
+    expressions: &crate::Arena<Expression>,
+    block: &crate::Block,
+    counter: Handle<crate::LocalVariable>,
+    step_value: Handle<Expression>,
+    starts: &mut Vec<i64>,
+) -> bool {
+    let is_counter =
+        |pointer: Handle<Expression>| expressions[pointer] == Expression::LocalVariable(counter);
+    let collect = |block: &crate::Block, starts: &mut Vec<i64>| {
+        collect_counter_stores(module, expressions, block, counter, step_value, starts)
+    };
+
+    for statement in block.iter() {
+        let valid = match *statement {
+            Statement::Store { pointer, value } if is_counter(pointer) => {
+                if value == step_value {
+                    true
+                } else if let Some(start) = constant_value(module, expressions, value) {
+                    starts.push(start);
+                    true
+                } else {
+                    false
+                }
+            }
+            Statement::Call { ref arguments, .. } => !arguments.iter().any(|&arg| is_counter(arg)),
+            Statement::Block(ref block) => collect(block, starts),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => collect(accept, starts) && collect(reject, starts),
+            Statement::Switch { ref cases, .. } => {
+                cases.iter().all(|case| collect(&case.body, starts))
+            }
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => collect(body, starts) && collect(continuing, starts),
+            _ => true,
+        };
+        if !valid {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns how many times a loop runs while `counter op limit` holds, with
+/// the counter starting at `start` and advancing by `step`.
+const fn trip_count(start: i64, op: Bo, limit: i64, step: i64) -> Option<i64> {
+    let runs = match op {
+        Bo::Less => start < limit,
+        Bo::LessEqual => start <= limit,
+        Bo::Greater => start > limit,
+        Bo::GreaterEqual => start >= limit,
+        Bo::Equal => start == limit,
+        Bo::NotEqual => start != limit,
+        _ => return None,
+    };
+    if !runs {
+        return Some(0);
+    }
+
+    let distance = limit - start;
+    match op {
+        Bo::Less if step > 0 => Some((distance + step - 1) / step),
+        Bo::LessEqual if step > 0 => Some(distance / step + 1),
+        Bo::Greater if step < 0 => Some((distance + step + 1) / step),
+        Bo::GreaterEqual if step < 0 => Some(distance / step + 1),
+        // The counter moves away from the limit after the first iteration.
+        Bo::Equal => Some(1),
+        Bo::NotEqual if distance % step == 0 && distance / step > 0 => Some(distance / step),
+        _ => None,
+    }
+}
+
+#[test]
+fn counted_loop_trip_counts() {
+    assert_eq!(trip_count(0, Bo::Less, 4, 1), Some(4));
+    assert_eq!(trip_count(0, Bo::Less, 4, 3), Some(2));
+    assert_eq!(trip_count(1, Bo::LessEqual, 3, 1), Some(3));
+    assert_eq!(trip_count(10, Bo::Greater, 0, -2), Some(5));
+    assert_eq!(trip_count(10, Bo::GreaterEqual, 0, -2), Some(6));
+    assert_eq!(trip_count(0, Bo::NotEqual, 4, 2), Some(2));
+    assert_eq!(trip_count(5, Bo::Less, 4, 1), Some(0));
+    assert_eq!(trip_count(0, Bo::Less, 4, -1), None);
+    assert_eq!(trip_count(0, Bo::NotEqual, 5, 2), None);
+}
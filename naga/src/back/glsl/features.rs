@@ -48,6 +48,8 @@ bitflags::bitflags! {
         ///
         /// We can always support this, either through the language or a polyfill
         const INSTANCE_INDEX = 1 << 22;
+        /// Derivatives in GLSL ES 1.00
+        const STANDARD_DERIVATIVES = 1 << 23;
     }
 }
 
@@ -244,6 +246,11 @@ impl FeaturesManager {
             writeln!(out, "#extension GL_EXT_blend_func_extended : require")?;
         }
 
+        if self.0.contains(Features::STANDARD_DERIVATIVES) {
+            // https://registry.khronos.org/OpenGL/extensions/OES/OES_standard_derivatives.txt
+            writeln!(out, "#extension GL_OES_standard_derivatives : require")?;
+        }
+
         if self.0.contains(Features::INSTANCE_INDEX) {
             if options.writer_flags.contains(WriterFlags::DRAW_PARAMETERS) {
                 // https://registry.khronos.org/OpenGL/extensions/ARB/ARB_shader_draw_parameters.txt
@@ -381,7 +388,8 @@ impl<'a, W> Writer<'a, W> {
                             _ => {}
                         },
                         ImageClass::Sampled { multi: false, .. }
-                        | ImageClass::Depth { multi: false } => {}
+                        | ImageClass::Depth { multi: false }
+                        | ImageClass::External => {}
                     }
                 }
                 _ => {}
@@ -417,6 +425,7 @@ impl<'a, W> Writer<'a, W> {
             entry_point,
             entry_point_idx,
             ref policies,
+            options,
             ..
         } = self;
 
@@ -454,6 +463,10 @@ impl<'a, W> Writer<'a, W> {
                     crate::ImageQuery::NumSamples => features.request(Features::TEXTURE_SAMPLES),
                 }
                 ,
+                // Derivatives are an extension in GLSL ES 1.00
+                Expression::Derivative { .. } if options.version.is_es100() => {
+                    features.request(Features::STANDARD_DERIVATIVES)
+                }
                 // Check for image loads that needs bound checking on the sample
                 // or level argument since this requires a feature
                 Expression::ImageLoad {
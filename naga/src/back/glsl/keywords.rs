@@ -480,5 +480,7 @@ pub const RESERVED_KEYWORDS: &[&str] = &[
     // Naga utilities:
     super::MODF_FUNCTION,
     super::FREXP_FUNCTION,
+    super::SAMPLE_EXTERNAL_FUNCTION,
+    super::LOAD_EXTERNAL_FUNCTION,
     super::FIRST_INSTANCE_BINDING,
 ];
@@ -14,9 +14,13 @@ to output a [`Module`](crate::Module) into glsl
 - 450
 
 ### ES
+- 100
 - 300
 - 310
 
+GLSL ES 1.00 can't represent a lot of Naga IR, see the [`es100`] module for
+what is lowered and what is rejected.
+
 [glsl]: https://www.khronos.org/registry/OpenGL/index_gl.php
 */
 
@@ -43,6 +47,7 @@ to output a [`Module`](crate::Module) into glsl
 // Additions that are relevant for the backend are the discard keyword, the introduction of
 // vector, matrices, samplers, image types and functions that provide common shader operations
 
+pub use es100::Es100Construct;
 pub use features::Features;
 
 use crate::{
@@ -59,6 +64,7 @@ use std::{
 };
 use thiserror::Error;
 
+pub mod es100;
 /// Contains the features related code and the features querying method
 mod features;
 /// Contains a constant with a slice of all the reserved keywords RESERVED_KEYWORDS
@@ -67,7 +73,7 @@ mod keywords;
 /// List of supported `core` GLSL versions.
 pub const SUPPORTED_CORE_VERSIONS: &[u16] = &[140, 150, 330, 400, 410, 420, 430, 440, 450, 460];
 /// List of supported `es` GLSL versions.
-pub const SUPPORTED_ES_VERSIONS: &[u16] = &[300, 310, 320];
+pub const SUPPORTED_ES_VERSIONS: &[u16] = &[100, 300, 310, 320];
 
 /// The suffix of the variable that will hold the calculated clamped level
 /// of detail for bounds checking in `ImageLoad`
@@ -75,6 +81,8 @@ const CLAMPED_LOD_SUFFIX: &str = "_clamped_lod";
 
 pub(crate) const MODF_FUNCTION: &str = "naga_modf";
 pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
+pub(crate) const SAMPLE_EXTERNAL_FUNCTION: &str = "nagaTextureSampleExternal";
+pub(crate) const LOAD_EXTERNAL_FUNCTION: &str = "nagaTextureLoadExternal";
 
 // Must match code in glsl_built_in
 pub const FIRST_INSTANCE_BINDING: &str = "naga_vs_first_instance";
@@ -113,7 +121,8 @@ impl crate::AddressSpace {
             | crate::AddressSpace::Uniform
             | crate::AddressSpace::Storage { .. }
             | crate::AddressSpace::Handle
-            | crate::AddressSpace::PushConstant => false,
+            | crate::AddressSpace::PushConstant
+            | crate::AddressSpace::TaskPayload => false,
         }
     }
 }
@@ -154,6 +163,11 @@ impl Version {
         }
     }
 
+    /// Returns true if targetting GLSL ES 1.00 (i.e. OpenGL ES 2.0 or WebGL 1)
+    const fn is_es100(&self) -> bool {
+        matches!(*self, Version::Embedded { version: 100, .. })
+    }
+
     /// Checks the list of currently supported versions and returns true if it contains the
     /// specified version
     ///
@@ -222,6 +236,8 @@ impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Version::Desktop(v) => write!(f, "{v} core"),
+            // GLSL ES 1.00 predates profiles
+            Version::Embedded { version: 100, .. } => write!(f, "100"),
             Version::Embedded { version: v, .. } => write!(f, "{v} es"),
         }
     }
@@ -312,6 +328,9 @@ pub struct ReflectionInfo {
     /// Mapping between texture names and variables/samplers.
     pub texture_mapping: crate::FastHashMap<String, TextureMapping>,
     /// Mapping between uniform variables and names.
+    ///
+    /// For [`External`](crate::ImageClass::External) images, this holds the
+    /// name of the uniform block of their parameters.
     pub uniforms: crate::FastHashMap<Handle<crate::GlobalVariable>, String>,
     /// Mapping between names and attribute locations.
     pub varying: crate::FastHashMap<String, VaryingLocation>,
@@ -334,6 +353,11 @@ pub struct TextureMapping {
     pub texture: Handle<crate::GlobalVariable>,
     /// Handle to the associated sampler global variable, if it exists.
     pub sampler: Option<Handle<crate::GlobalVariable>>,
+    /// The plane of an [`External`](crate::ImageClass::External) image that
+    /// this texture holds, whose binding is the image's binding plus `plane`.
+    ///
+    /// This is `0` for all other images.
+    pub plane: u32,
 }
 
 /// All information to bind a single uniform value to the shader.
@@ -399,6 +423,7 @@ impl IdGenerator {
 struct VaryingOptions {
     output: bool,
     targetting_webgl: bool,
+    targetting_es100: bool,
     draw_parameters: bool,
 }
 
@@ -407,6 +432,7 @@ impl VaryingOptions {
         Self {
             output,
             targetting_webgl: options.version.is_webgl(),
+            targetting_es100: options.version.is_es100(),
             draw_parameters: options.writer_flags.contains(WriterFlags::DRAW_PARAMETERS),
         }
     }
@@ -419,6 +445,7 @@ impl VaryingOptions {
 /// - Varyings with location bindings are named `_S_location_X` where `S` is a
 ///   prefix identifying which pipeline stage the varying connects, and `X` is
 ///   the location.
+/// - On GLSL ES 1.00 the fragment output is always `gl_FragColor`.
 struct VaryingName<'a> {
     binding: &'a crate::Binding,
     stage: ShaderStage,
@@ -433,9 +460,18 @@ impl fmt::Display for VaryingName<'_> {
             } => {
                 write!(f, "_fs2p_location1",)
             }
+            crate::Binding::Location { .. }
+                if self.options.targetting_es100
+                    && self.stage == ShaderStage::Fragment
+                    && self.options.output =>
+            {
+                write!(f, "gl_FragColor")
+            }
             crate::Binding::Location { location, .. } => {
                 let prefix = match (self.stage, self.options.output) {
-                    (ShaderStage::Compute, _) => unreachable!(),
+                    (ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh, _) => {
+                        unreachable!()
+                    }
                     // pipeline to vertex
                     (ShaderStage::Vertex, false) => "p2vs",
                     // vertex to fragment
@@ -458,6 +494,8 @@ impl ShaderStage {
             ShaderStage::Compute => "cs",
             ShaderStage::Fragment => "fs",
             ShaderStage::Vertex => "vs",
+            ShaderStage::Task => "ts",
+            ShaderStage::Mesh => "ms",
         }
     }
 }
@@ -495,6 +533,13 @@ pub enum Error {
     /// A image was used with multiple samplers, which isn't supported.
     #[error("A image was used with multiple samplers")]
     ImageMultipleSamplers,
+    /// The entry point uses a construct that GLSL ES 1.00 can't represent.
+    #[error("GLSL ES 1.00 can't represent {construct}, used in {location}")]
+    Es100Unsupported {
+        /// Where the construct was found, for example ``function `foo` ``.
+        location: String,
+        construct: Es100Construct,
+    },
     #[error("{0}")]
     Custom(String),
 }
@@ -519,7 +564,7 @@ pub struct Writer<'a, W> {
     /// The module analysis.
     info: &'a valid::ModuleInfo,
     /// The output writer.
-    out: W,
+    out: back::SourceMapWriter<W>,
     /// User defined configuration to be used.
     options: &'a Options,
     /// The bound checking policies to be used
@@ -534,6 +579,9 @@ pub struct Writer<'a, W> {
     names: crate::FastHashMap<NameKey, String>,
     /// A map with the names of global variables needed for reflections.
     reflection_names_globals: crate::FastHashMap<Handle<crate::GlobalVariable>, String>,
+    /// A map with the names of the parameter blocks of external textures,
+    /// needed for reflections.
+    reflection_names_external_params: crate::FastHashMap<Handle<crate::GlobalVariable>, String>,
     /// The selected entry point.
     entry_point: &'a crate::EntryPoint,
     /// The index of the selected entry point.
@@ -548,6 +596,9 @@ pub struct Writer<'a, W> {
     multiview: Option<std::num::NonZeroU32>,
     /// Mapping of varying variables to their location. Needed for reflections.
     varying: crate::FastHashMap<String, VaryingLocation>,
+    /// The counters of the GLSL ES 1.00 loops whose body is being written,
+    /// with the name of the `for` loop index they're computed from.
+    es100_loop_counters: Vec<(es100::LoopCounter, String)>,
 }
 
 impl<'a, W: Write> Writer<'a, W> {
@@ -579,6 +630,11 @@ impl<'a, W: Write> Writer<'a, W> {
                 pipeline_options.shader_stage == ep.stage && pipeline_options.entry_point == ep.name
             })
             .ok_or(Error::EntryPointNotFound)?;
+        if let ShaderStage::Task | ShaderStage::Mesh = module.entry_points[ep_idx].stage {
+            return Err(Error::Custom(
+                "task and mesh shaders are not supported".to_string(),
+            ));
+        }
 
         // Generate a map with names required to write the module
         let mut names = crate::FastHashMap::default();
@@ -600,7 +656,7 @@ impl<'a, W: Write> Writer<'a, W> {
         let mut this = Self {
             module,
             info,
-            out,
+            out: back::SourceMapWriter::new(out),
             options,
             policies,
 
@@ -608,6 +664,7 @@ impl<'a, W: Write> Writer<'a, W> {
             features: FeaturesManager::new(),
             names,
             reflection_names_globals: crate::FastHashMap::default(),
+            reflection_names_external_params: crate::FastHashMap::default(),
             entry_point: &module.entry_points[ep_idx],
             entry_point_idx: ep_idx as u16,
             multiview: pipeline_options.multiview,
@@ -615,14 +672,26 @@ impl<'a, W: Write> Writer<'a, W> {
             named_expressions: Default::default(),
             need_bake_expressions: Default::default(),
             varying: Default::default(),
+            es100_loop_counters: Vec::new(),
         };
 
         // Find all features required to print this module
         this.collect_required_features()?;
 
+        if options.version.is_es100() {
+            this.check_es100()?;
+        }
+
         Ok(this)
     }
 
+    /// Return the [`SourceMap`] for the output written so far.
+    ///
+    /// [`SourceMap`]: back::SourceMap
+    pub const fn source_map(&self) -> &back::SourceMap {
+        self.out.source_map()
+    }
+
     /// Writes the [`Module`](crate::Module) as glsl to the output
     ///
     /// # Notes
@@ -658,7 +727,18 @@ impl<'a, W: Write> Writer<'a, W> {
 
         // glsl es requires a precision to be specified for floats and ints
         // TODO: Should this be user configurable?
-        if es {
+        if self.options.version.is_es100() && self.entry_point.stage == ShaderStage::Fragment {
+            // Fragment shaders aren't required to support `highp` in GLSL ES 1.00
+            writeln!(self.out)?;
+            writeln!(self.out, "#ifdef GL_FRAGMENT_PRECISION_HIGH")?;
+            writeln!(self.out, "precision highp float;")?;
+            writeln!(self.out, "precision highp int;")?;
+            writeln!(self.out, "#else")?;
+            writeln!(self.out, "precision mediump float;")?;
+            writeln!(self.out, "precision mediump int;")?;
+            writeln!(self.out, "#endif")?;
+            writeln!(self.out)?;
+        } else if es {
             writeln!(self.out)?;
             writeln!(self.out, "precision highp float;")?;
             writeln!(self.out, "precision highp int;")?;
@@ -825,6 +905,7 @@ impl<'a, W: Write> Writer<'a, W> {
             .options
             .writer_flags
             .contains(WriterFlags::INCLUDE_UNUSED_ITEMS);
+        let mut external_textures = false;
         for (handle, global) in self.module.global_variables.iter() {
             let is_unused = ep_info[handle].is_empty();
             if !include_unused && is_unused {
@@ -832,6 +913,14 @@ impl<'a, W: Write> Writer<'a, W> {
             }
 
             match self.module.types[global.ty].inner {
+                TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } => {
+                    self.write_external_texture_global(handle, global)?;
+                    writeln!(self.out)?;
+                    external_textures = true;
+                }
                 // We treat images separately because they might require
                 // writing the storage format
                 TypeInner::Image {
@@ -910,6 +999,10 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         }
 
+        if external_textures {
+            self.write_external_texture_functions()?;
+        }
+
         for arg in self.entry_point.function.arguments.iter() {
             self.write_varying(arg.binding.as_ref(), arg.ty, false)?;
         }
@@ -920,28 +1013,14 @@ impl<'a, W: Write> Writer<'a, W> {
 
         // Write all regular functions
         for (handle, function) in self.module.functions.iter() {
-            // Check that the function doesn't use globals that aren't supported
-            // by the current entry point
-            if !include_unused && !ep_info.dominates_global_use(&self.info[handle]) {
+            if !self.is_function_written(handle) {
                 continue;
             }
 
             let fun_info = &self.info[handle];
 
-            // Skip functions that that are not compatible with this entry point's stage.
-            //
-            // When validation is enabled, it rejects modules whose entry points try to call
-            // incompatible functions, so if we got this far, then any functions incompatible
-            // with our selected entry point must not be used.
-            //
-            // When validation is disabled, `fun_info.available_stages` is always just
-            // `ShaderStages::all()`, so this will write all functions in the module, and
-            // the downstream GLSL compiler will catch any problems.
-            if !fun_info.available_stages.contains(ep_info.available_stages) {
-                continue;
-            }
-
             // Write the function
+            self.out.mark(self.module.functions.get_span(handle));
             self.write_function(back::FunctionType::Function(handle), function, fun_info)?;
 
             writeln!(self.out)?;
@@ -960,6 +1039,34 @@ impl<'a, W: Write> Writer<'a, W> {
         self.collect_reflection_info()
     }
 
+    /// Returns true if the function should be written for the selected entry point
+    fn is_function_written(&self, handle: Handle<crate::Function>) -> bool {
+        let ep_info = self.info.get_entry_point(self.entry_point_idx as usize);
+        let fun_info = &self.info[handle];
+
+        // Check that the function doesn't use globals that aren't supported
+        // by the current entry point
+        if !self
+            .options
+            .writer_flags
+            .contains(WriterFlags::INCLUDE_UNUSED_ITEMS)
+            && !ep_info.dominates_global_use(fun_info)
+        {
+            return false;
+        }
+
+        // Skip functions that that are not compatible with this entry point's stage.
+        //
+        // When validation is enabled, it rejects modules whose entry points try to call
+        // incompatible functions, so if we got this far, then any functions incompatible
+        // with our selected entry point must not be used.
+        //
+        // When validation is disabled, `fun_info.available_stages` is always just
+        // `ShaderStages::all()`, so this will write all functions in the module, and
+        // the downstream GLSL compiler will catch any problems.
+        fun_info.available_stages.contains(ep_info.available_stages)
+    }
+
     fn write_array_size(
         &mut self,
         base: Handle<crate::Type>,
@@ -1003,19 +1110,35 @@ impl<'a, W: Write> Writer<'a, W> {
                 size: None,
                 scalar,
                 space: _,
-            } => write!(self.out, "{}", glsl_scalar(scalar)?.full)?,
+            } => write!(self.out, "{}", self.scalar_string(scalar)?.full)?,
             // Vectors are just `gvecN` where `g` is the scalar prefix and `N` is the vector size
             TypeInner::Vector { size, scalar }
             | TypeInner::ValuePointer {
                 size: Some(size),
                 scalar,
                 space: _,
-            } => write!(self.out, "{}vec{}", glsl_scalar(scalar)?.prefix, size as u8)?,
+            } => write!(
+                self.out,
+                "{}vec{}",
+                self.scalar_string(scalar)?.prefix,
+                size as u8
+            )?,
             // Matrices are written with `gmatMxN` where `g` is the scalar prefix (only floats and
             // doubles are allowed), `M` is the columns count and `N` is the rows count
             //
             // glsl supports a matrix shorthand `gmatN` where `N` = `M` but it doesn't justify the
-            // extra branch to write matrices this way
+            // extra branch to write matrices this way, except for GLSL ES 1.00 which only has
+            // the shorthand
+            TypeInner::Matrix {
+                columns,
+                rows: _,
+                scalar,
+            } if self.options.version.is_es100() => write!(
+                self.out,
+                "{}mat{}",
+                glsl_scalar(scalar)?.prefix,
+                columns as u8
+            )?,
             TypeInner::Matrix {
                 columns,
                 rows,
@@ -1047,6 +1170,21 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method used to get the strings for a scalar
+    ///
+    /// GLSL ES 1.00 has no unsigned integers so they are written as `int`s.
+    const fn scalar_string(&self, scalar: crate::Scalar) -> Result<ScalarString<'static>, Error> {
+        match scalar.kind {
+            crate::ScalarKind::Uint if self.options.version.is_es100() => {
+                glsl_scalar(crate::Scalar {
+                    kind: crate::ScalarKind::Sint,
+                    ..scalar
+                })
+            }
+            _ => glsl_scalar(scalar),
+        }
+    }
+
     /// Helper method used to write non image/sampler types
     ///
     /// # Notes
@@ -1098,9 +1236,17 @@ impl<'a, W: Write> Writer<'a, W> {
             Ic::Depth { multi: true } => ("sampler", crate::ScalarKind::Float, "MS", ""),
             Ic::Depth { multi: false } => ("sampler", crate::ScalarKind::Float, "", "Shadow"),
             Ic::Storage { format, .. } => ("image", format.into(), "", ""),
+            // External texture globals are written by
+            // `write_external_texture_global`.
+            Ic::External => {
+                return Err(Error::Custom(
+                    "external texture function arguments are not supported".to_string(),
+                ))
+            }
         };
 
-        let precision = if self.options.version.is_es() {
+        // GLSL ES 1.00 samplers default to `lowp`, which is all fragment shaders must support
+        let precision = if self.options.version.is_es() && !self.options.version.is_es100() {
             "highp "
         } else {
             ""
@@ -1128,6 +1274,168 @@ impl<'a, W: Write> Writer<'a, W> {
     ///
     /// # Panics
     /// If the global has type sampler
+    /// Write the declarations of the planes and the parameters that the
+    /// [`External`] image global `handle` is lowered into.
+    ///
+    /// The planes are `sampler2D`s and the parameters are a uniform block,
+    /// bound as given by [`ResourceBinding::external_texture_bindings`]. The
+    /// first plane takes the name of the global, and the others and the
+    /// parameters add a suffix to it.
+    ///
+    /// [`External`]: crate::ImageClass::External
+    /// [`ResourceBinding::external_texture_bindings`]: crate::ResourceBinding::external_texture_bindings
+    fn write_external_texture_global(
+        &mut self,
+        handle: Handle<crate::GlobalVariable>,
+        global: &crate::GlobalVariable,
+    ) -> BackendResult {
+        let params_ty = self.external_texture_params()?;
+        let binding = global.binding.as_ref().unwrap();
+        let [plane1, plane2, params] = binding.external_texture_bindings();
+        let name = self.get_global_name(handle, global);
+
+        let plane_class = crate::ImageClass::Sampled {
+            kind: crate::ScalarKind::Float,
+            multi: false,
+        };
+        for (plane, suffix) in [(binding, ""), (&plane1, "_plane1"), (&plane2, "_plane2")] {
+            self.write_layout_binding(plane, "")?;
+            write!(self.out, "uniform ")?;
+            self.write_image_type(crate::ImageDimension::D2, false, plane_class)?;
+            writeln!(self.out, " {name}{suffix};")?;
+        }
+
+        // Write the parameters as the sole member of an interface block, like
+        // `write_interface_block` does.
+        self.write_layout_binding(&params, "std140, ")?;
+        let ty_name = &self.names[&NameKey::Type(params_ty)];
+        let block_name = format!(
+            "{}_block_{}{:?}",
+            // avoid double underscores as they are reserved in GLSL
+            ty_name.trim_end_matches('_'),
+            self.block_id.generate(),
+            self.entry_point.stage,
+        );
+        writeln!(
+            self.out,
+            "uniform {block_name} {{ {ty_name} {name}_params; }};"
+        )?;
+
+        self.reflection_names_globals.insert(handle, name);
+        self.reflection_names_external_params
+            .insert(handle, block_name);
+        Ok(())
+    }
+
+    /// Write the `layout` qualifier giving the binding of `binding`, if the
+    /// version supports it and the binding is mapped, preceded by `layout`.
+    fn write_layout_binding(
+        &mut self,
+        binding: &crate::ResourceBinding,
+        layout: &str,
+    ) -> BackendResult {
+        if self.options.version.supports_explicit_locations() {
+            if let Some(binding) = self.options.binding_map.get(binding) {
+                write!(self.out, "layout({layout}binding = {binding}) ")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the functions used for reading external textures.
+    ///
+    /// They take the planes and the parameters that an [`External`] image is
+    /// lowered into, and convert the planes' contents to RGBA.
+    ///
+    /// [`External`]: crate::ImageClass::External
+    fn write_external_texture_functions(&mut self) -> BackendResult {
+        let params_ty = self.external_texture_params()?;
+        let params_ty_name = &self.names[&NameKey::Type(params_ty)];
+        let yuv_conversion_matrix = &self.names[&NameKey::StructMember(params_ty, 0)];
+        let num_planes = &self.names[&NameKey::StructMember(params_ty, 2)];
+        let precision = if self.options.version.is_es() {
+            "highp "
+        } else {
+            ""
+        };
+        let planes = format!(
+            "{precision}sampler2D plane0, {precision}sampler2D plane1, \
+             {precision}sampler2D plane2, {params_ty_name} params"
+        );
+        let to_rgba = format!(
+            "    if (params.{num_planes} == 1u) {{
+        return texel0;
+    }}
+    float v = params.{num_planes} == 2u ? texel1.y : texel2.x;
+    return params.{yuv_conversion_matrix} * vec4(texel0.x, texel1.x, v, 1.0);"
+        );
+
+        // Each plane is sampled with the coordinates clamped to half a texel
+        // inside its edges.
+        writeln!(
+            self.out,
+            "vec4 {SAMPLE_EXTERNAL_FUNCTION}({planes}, vec2 coords) {{"
+        )?;
+        for i in 0..3 {
+            writeln!(
+                self.out,
+                "    vec2 half_texel{i} = vec2(0.5) / vec2(textureSize(plane{i}, 0));"
+            )?;
+            writeln!(
+                self.out,
+                "    vec4 texel{i} = textureLod(plane{i}, \
+                 clamp(coords, half_texel{i}, vec2(1.0) - half_texel{i}), 0.0);"
+            )?;
+        }
+        writeln!(self.out, "{to_rgba}")?;
+        writeln!(self.out, "}}")?;
+        writeln!(self.out)?;
+
+        // The coordinates are clamped to the first plane, and scaled to the
+        // others, which may be subsampled.
+        writeln!(
+            self.out,
+            "vec4 {LOAD_EXTERNAL_FUNCTION}({planes}, uvec2 coords) {{
+    uvec2 size0 = uvec2(textureSize(plane0, 0));
+    uvec2 size1 = uvec2(textureSize(plane1, 0));
+    uvec2 size2 = uvec2(textureSize(plane2, 0));
+    uvec2 coords0 = min(coords, size0 - 1u);
+    vec4 texel0 = texelFetch(plane0, ivec2(coords0), 0);
+    vec4 texel1 = texelFetch(plane1, ivec2(coords0 * size1 / size0), 0);
+    vec4 texel2 = texelFetch(plane2, ivec2(coords0 * size2 / size0), 0);
+{to_rgba}
+}}"
+        )?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    /// Return the type of the parameters of external textures, which the
+    /// module must have if it uses them.
+    fn external_texture_params(&self) -> Result<Handle<crate::Type>, Error> {
+        self.module
+            .special_types
+            .external_texture_params
+            .ok_or_else(|| Error::Custom("missing external texture parameters type".to_string()))
+    }
+
+    /// Return the name of the external texture global `image`, which the
+    /// names of its other planes and its parameters are derived from.
+    fn external_texture_name(
+        &self,
+        image: Handle<crate::Expression>,
+        ctx: &back::FunctionCtx,
+    ) -> Result<String, Error> {
+        match ctx.expressions[image] {
+            crate::Expression::GlobalVariable(handle) => {
+                Ok(self.get_global_name(handle, &self.module.global_variables[handle]))
+            }
+            _ => Err(Error::Custom(
+                "external texture function arguments are not supported".to_string(),
+            )),
+        }
+    }
+
     fn write_global(
         &mut self,
         handle: Handle<crate::GlobalVariable>,
@@ -1180,6 +1488,12 @@ impl<'a, W: Write> Writer<'a, W> {
             crate::AddressSpace::PushConstant => {
                 self.write_simple_global(handle, global)?;
             }
+            // Task and mesh shaders are rejected in `Writer::new`.
+            crate::AddressSpace::TaskPayload => unreachable!(),
+            // GLSL ES 1.00 has no interface blocks
+            crate::AddressSpace::Uniform if self.options.version.is_es100() => {
+                self.write_simple_global(handle, global)?;
+            }
             crate::AddressSpace::Uniform => {
                 self.write_interface_block(handle, global)?;
             }
@@ -1209,7 +1523,9 @@ impl<'a, W: Write> Writer<'a, W> {
             self.write_array_size(base, size)?;
         }
 
-        if global.space.initializable() && is_value_init_supported(self.module, global.ty) {
+        if global.space.initializable()
+            && is_value_init_supported(self.module, global.ty, self.options.version)
+        {
             write!(self.out, " = ")?;
             if let Some(init) = global.init {
                 self.write_const_expr(init)?;
@@ -1220,7 +1536,7 @@ impl<'a, W: Write> Writer<'a, W> {
 
         writeln!(self.out, ";")?;
 
-        if let crate::AddressSpace::PushConstant = global.space {
+        if let crate::AddressSpace::PushConstant | crate::AddressSpace::Uniform = global.space {
             let global_name = self.get_global_name(handle, global);
             self.reflection_names_globals.insert(handle, global_name);
         }
@@ -1320,6 +1636,23 @@ impl<'a, W: Write> Writer<'a, W> {
                             self.need_bake_expressions.insert(arg);
                         }
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed => {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
+                    crate::MathFunction::Pack4xI8
+                    | crate::MathFunction::Pack4xU8
+                    | crate::MathFunction::Pack4xI8Clamp
+                    | crate::MathFunction::Pack4xU8Clamp => {
+                        self.need_bake_expressions.insert(arg);
+                    }
+                    crate::MathFunction::QuantizeToF16 => {
+                        if let TypeInner::Vector { size, .. } = *inner {
+                            if size as u8 > 2 {
+                                self.need_bake_expressions.insert(arg);
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -1452,6 +1785,9 @@ impl<'a, W: Write> Writer<'a, W> {
                             //
                             // See https://github.com/KhronosGroup/WebGL/issues/3518
                         }
+                        (Version::Embedded { version: 100, .. }, ShaderStage::Fragment) => {
+                            // Only vertex shader outputs can be invariant in GLSL ES 1.00
+                        }
                         _ => {
                             writeln!(
                                 self.out,
@@ -1468,6 +1804,13 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         };
 
+        let es100 = self.options.version.is_es100();
+
+        // GLSL ES 1.00 fragment shaders write their output to `gl_FragColor`
+        if es100 && self.entry_point.stage == ShaderStage::Fragment && output {
+            return Ok(());
+        }
+
         // Write the interpolation modifier if needed
         //
         // We ignore all interpolation and auxiliary modifiers that aren't used in fragment
@@ -1475,7 +1818,7 @@ impl<'a, W: Write> Writer<'a, W> {
         let emit_interpolation_and_auxiliary = match self.entry_point.stage {
             ShaderStage::Vertex => output,
             ShaderStage::Fragment => !output,
-            ShaderStage::Compute => false,
+            ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh => false,
         };
 
         // Write the I/O locations, if allowed
@@ -1499,6 +1842,10 @@ impl<'a, W: Write> Writer<'a, W> {
             None
         };
 
+        // GLSL ES 1.00 has no interpolation nor auxiliary qualifiers, `check_es100` rejects the
+        // ones we can't do without.
+        let emit_interpolation_and_auxiliary = emit_interpolation_and_auxiliary && !es100;
+
         // Write the interpolation qualifier.
         if let Some(interp) = interpolation {
             if emit_interpolation_and_auxiliary {
@@ -1520,11 +1867,22 @@ impl<'a, W: Write> Writer<'a, W> {
         }
 
         // Write the input/output qualifier.
-        write!(self.out, "{} ", if output { "out" } else { "in" })?;
+        let qualifier = match (es100, self.entry_point.stage, output) {
+            (true, ShaderStage::Vertex, false) => "attribute",
+            (true, _, _) => "varying",
+            (false, _, true) => "out",
+            (false, _, false) => "in",
+        };
+        write!(self.out, "{qualifier} ")?;
 
         // Write the type
         // `write_type` adds no leading or trailing spaces
-        self.write_type(ty)?;
+        //
+        // GLSL ES 1.00 has no integer attributes nor varyings, so we pass them as floats
+        match es100_float_varying_type(&self.module.types[ty].inner) {
+            Some(float_ty) if es100 => write!(self.out, "{float_ty}")?,
+            _ => self.write_type(ty)?,
+        }
 
         // Finally write the global name and end the global with a `;` and a newline
         // Leading space is important
@@ -1680,6 +2038,23 @@ impl<'a, W: Write> Writer<'a, W> {
             self.write_workgroup_variables_initialization(&ctx)?;
         }
 
+        // GLSL ES 1.00 can't zero initialize private globals containing arrays in their
+        // declaration, so the entry point does it element by element
+        if self.options.version.is_es100() {
+            if let back::FunctionType::EntryPoint(_) = ctx.ty {
+                for (handle, global) in self.module.global_variables.iter() {
+                    if global.space == crate::AddressSpace::Private
+                        && global.init.is_none()
+                        && !ctx.info[handle].is_empty()
+                        && es100::contains_array(self.module, global.ty)
+                    {
+                        let name = self.names[&NameKey::GlobalVariable(handle)].clone();
+                        self.write_zero_init_statements(&name, global.ty, back::Level(1))?;
+                    }
+                }
+            }
+        }
+
         // Compose the function arguments from globals, in case of an entry point.
         if let back::FunctionType::EntryPoint(ep_index) = ctx.ty {
             let stage = self.module.entry_points[ep_index as usize].stage;
@@ -1694,25 +2069,20 @@ impl<'a, W: Write> Writer<'a, W> {
                         self.write_type(arg.ty)?;
                         write!(self.out, "(")?;
                         for (index, member) in members.iter().enumerate() {
-                            let varying_name = VaryingName {
-                                binding: member.binding.as_ref().unwrap(),
-                                stage,
-                                options: VaryingOptions::from_writer_options(self.options, false),
-                            };
                             if index != 0 {
                                 write!(self.out, ", ")?;
                             }
-                            write!(self.out, "{varying_name}")?;
+                            self.write_varying_value(
+                                member.binding.as_ref().unwrap(),
+                                member.ty,
+                                stage,
+                            )?;
                         }
                         writeln!(self.out, ");")?;
                     }
                     _ => {
-                        let varying_name = VaryingName {
-                            binding: arg.binding.as_ref().unwrap(),
-                            stage,
-                            options: VaryingOptions::from_writer_options(self.options, false),
-                        };
-                        writeln!(self.out, "{varying_name};")?;
+                        self.write_varying_value(arg.binding.as_ref().unwrap(), arg.ty, stage)?;
+                        writeln!(self.out, ";")?;
                     }
                 }
             }
@@ -1744,7 +2114,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 // Write the constant
                 // `write_constant` adds no trailing or leading space/newline
                 self.write_expr(init, &ctx)?;
-            } else if is_value_init_supported(self.module, local.ty) {
+            } else if is_value_init_supported(self.module, local.ty, self.options.version) {
                 write!(self.out, " = ")?;
                 self.write_zero_init_value(local.ty)?;
             }
@@ -1753,8 +2123,19 @@ impl<'a, W: Write> Writer<'a, W> {
             writeln!(self.out, ";")?
         }
 
+        // GLSL ES 1.00 locals containing arrays are zero initialized element by element
+        if self.options.version.is_es100() {
+            for (handle, local) in func.local_variables.iter() {
+                if local.init.is_none() && es100::contains_array(self.module, local.ty) {
+                    let name = self.names[&ctx.name_key(handle)].clone();
+                    self.write_zero_init_statements(&name, local.ty, back::Level(1))?;
+                }
+            }
+        }
+
         // Write the function body (statement list)
-        for sta in func.body.iter() {
+        for (sta, span) in func.body.span_iter() {
+            self.out.mark(*span);
             // Write a statement, the indentation should always be 1 when writing the function body
             // `write_stmt` adds a newline
             self.write_stmt(sta, &ctx, back::Level(1))?;
@@ -1766,6 +2147,80 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method used to write the value of an entry point input
+    ///
+    /// GLSL ES 1.00 passes integers as floats, these are rounded as interpolation might not
+    /// reproduce the exact value.
+    fn write_varying_value(
+        &mut self,
+        binding: &crate::Binding,
+        ty: Handle<crate::Type>,
+        stage: ShaderStage,
+    ) -> BackendResult {
+        let varying_name = VaryingName {
+            binding,
+            stage,
+            options: VaryingOptions::from_writer_options(self.options, false),
+        };
+        if self.options.version.is_es100()
+            && matches!(*binding, crate::Binding::Location { .. })
+            && es100_float_varying_type(&self.module.types[ty].inner).is_some()
+        {
+            self.write_type(ty)?;
+            write!(self.out, "(floor({varying_name} + 0.5))")?;
+        } else {
+            write!(self.out, "{varying_name}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Helper method used to start the conversion GLSL ES 1.00 needs to assign a value of type
+    /// `ty` to an entry point output, returns the text that ends it
+    ///
+    /// Integers are passed as floats, and fragment outputs are padded to the `vec4` of
+    /// `gl_FragColor`.
+    fn write_es100_output_conversion(
+        &mut self,
+        binding: &crate::Binding,
+        ty: Handle<crate::Type>,
+    ) -> Result<&'static str, Error> {
+        if !self.options.version.is_es100() || !matches!(*binding, crate::Binding::Location { .. })
+        {
+            return Ok("");
+        }
+
+        let inner = &self.module.types[ty].inner;
+        if self.entry_point.stage == ShaderStage::Fragment {
+            let padding = match *inner {
+                TypeInner::Vector {
+                    size: crate::VectorSize::Quad,
+                    scalar,
+                } if scalar.kind == crate::ScalarKind::Float => return Ok(""),
+                TypeInner::Vector {
+                    size: crate::VectorSize::Quad,
+                    ..
+                } => ")",
+                TypeInner::Vector {
+                    size: crate::VectorSize::Tri,
+                    ..
+                } => ", 1.0)",
+                TypeInner::Vector {
+                    size: crate::VectorSize::Bi,
+                    ..
+                } => ", 0.0, 1.0)",
+                _ => ", 0.0, 0.0, 1.0)",
+            };
+            write!(self.out, "vec4(")?;
+            Ok(padding)
+        } else if let Some(float_ty) = es100_float_varying_type(inner) {
+            write!(self.out, "{float_ty}(")?;
+            Ok(")")
+        } else {
+            Ok("")
+        }
+    }
+
     fn write_workgroup_variables_initialization(
         &mut self,
         ctx: &back::FunctionCtx,
@@ -1877,6 +2332,80 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method used to write packed 4x8-bit integer dot products
+    ///
+    /// GLSL has no builtin for these, so each byte is extracted with shifts,
+    /// sign-extending it for the signed variant. Like [`write_dot_product`],
+    /// this requires both arguments to be baked.
+    ///
+    /// [`write_dot_product`]: Self::write_dot_product
+    fn write_dot_4x8_packed(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        arg1: Handle<crate::Expression>,
+        signed: bool,
+        ctx: &back::FunctionCtx,
+    ) -> BackendResult {
+        write!(self.out, "(")?;
+        for index in 0..4 {
+            write!(self.out, " + ")?;
+            for (i, operand) in [arg, arg1].into_iter().enumerate() {
+                if i != 0 {
+                    write!(self.out, " * ")?;
+                }
+                if signed {
+                    write!(self.out, "((int(")?;
+                    self.write_expr(operand, ctx)?;
+                    write!(self.out, ") << {}) >> 24)", 24 - index * 8)?;
+                } else {
+                    write!(self.out, "((")?;
+                    self.write_expr(operand, ctx)?;
+                    write!(self.out, " >> {}u) & 0xFFu)", index * 8)?;
+                }
+            }
+        }
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
+    /// Helper method used to write `pack4xI8`, `pack4xU8` and their clamping variants
+    ///
+    /// The argument is accessed once per component, so it must be baked.
+    fn write_pack_4x8(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        signed: bool,
+        clamp: bool,
+        ctx: &back::FunctionCtx,
+    ) -> BackendResult {
+        write!(self.out, "(")?;
+        for (index, component) in back::COMPONENTS[..4].iter().enumerate() {
+            if index != 0 {
+                write!(self.out, " | ")?;
+            }
+            write!(self.out, "{}(", if signed { "uint" } else { "" })?;
+            match (signed, clamp) {
+                (true, true) => write!(self.out, "clamp(")?,
+                (false, true) => write!(self.out, "min(")?,
+                (_, false) => {}
+            }
+            self.write_expr(arg, ctx)?;
+            write!(self.out, ".{component}")?;
+            match (signed, clamp) {
+                (true, true) => write!(self.out, ", -128, 127)")?,
+                (false, true) => write!(self.out, ", 255u)")?,
+                (_, false) => {}
+            }
+            let mask = if signed { "0xFF" } else { "0xFFu" };
+            write!(self.out, " & {mask})")?;
+            if index != 0 {
+                write!(self.out, " << {}", index * 8)?;
+            }
+        }
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
     /// Helper method used to write structs
     ///
     /// # Notes
@@ -2004,7 +2533,8 @@ impl<'a, W: Write> Writer<'a, W> {
             Statement::Block(ref block) => {
                 write!(self.out, "{level}")?;
                 writeln!(self.out, "{{")?;
-                for sta in block.iter() {
+                for (sta, span) in block.span_iter() {
+                    self.out.mark(*span);
                     // Increase the indentation to help with readability
                     self.write_stmt(sta, ctx, level.next())?
                 }
@@ -2028,7 +2558,8 @@ impl<'a, W: Write> Writer<'a, W> {
                 self.write_expr(condition, ctx)?;
                 writeln!(self.out, ") {{")?;
 
-                for sta in accept {
+                for (sta, span) in accept.span_iter() {
+                    self.out.mark(*span);
                     // Increase indentation to help with readability
                     self.write_stmt(sta, ctx, level.next())?;
                 }
@@ -2038,7 +2569,8 @@ impl<'a, W: Write> Writer<'a, W> {
                 if !reject.is_empty() {
                     writeln!(self.out, "{level}}} else {{")?;
 
-                    for sta in reject {
+                    for (sta, span) in reject.span_iter() {
+                        self.out.mark(*span);
                         // Increase indentation to help with readability
                         self.write_stmt(sta, ctx, level.next())?;
                     }
@@ -2088,7 +2620,8 @@ impl<'a, W: Write> Writer<'a, W> {
                         writeln!(self.out)?;
                     }
 
-                    for sta in case.body.iter() {
+                    for (sta, span) in case.body.span_iter() {
+                        self.out.mark(*span);
                         self.write_stmt(sta, ctx, l2.next())?;
                     }
 
@@ -2113,19 +2646,49 @@ impl<'a, W: Write> Writer<'a, W> {
             //  <body>
             // }
             // ```
+            // GLSL ES 1.00 only guarantees `for` loops with a constant bound, so there the
+            // `while` is replaced by one, see `es100::loop_iteration_bound`.
             Statement::Loop {
                 ref body,
                 ref continuing,
                 break_if,
             } => {
+                let mut loop_counter = None;
+                let loop_header = if self.options.version.is_es100() {
+                    let function = match ctx.ty {
+                        back::FunctionType::Function(handle) => &self.module.functions[handle],
+                        back::FunctionType::EntryPoint(index) => {
+                            &self.module.entry_points[index as usize].function
+                        }
+                    };
+                    let counted = es100::loop_iteration_bound(
+                        self.module,
+                        function,
+                        body,
+                        continuing,
+                        break_if,
+                    )
+                    .expect("Loop bound should be checked by `check_es100`");
+                    let index = self.namer.call("loop_iteration");
+                    let header = format!(
+                        "for (int {index} = 0; {index} < {}; {index}++)",
+                        counted.bound
+                    );
+                    loop_counter = counted.counter.map(|counter| (counter, index));
+                    header
+                } else {
+                    "while(true)".to_string()
+                };
+
                 if !continuing.is_empty() || break_if.is_some() {
                     let gate_name = self.namer.call("loop_init");
                     writeln!(self.out, "{level}bool {gate_name} = true;")?;
-                    writeln!(self.out, "{level}while(true) {{")?;
+                    writeln!(self.out, "{level}{loop_header} {{")?;
                     let l2 = level.next();
                     let l3 = l2.next();
                     writeln!(self.out, "{l2}if (!{gate_name}) {{")?;
-                    for sta in continuing {
+                    for (sta, span) in continuing.span_iter() {
+                        self.out.mark(*span);
                         self.write_stmt(sta, ctx, l3)?;
                     }
                     if let Some(condition) = break_if {
@@ -2138,11 +2701,15 @@ impl<'a, W: Write> Writer<'a, W> {
                     writeln!(self.out, "{l2}}}")?;
                     writeln!(self.out, "{}{} = false;", level.next(), gate_name)?;
                 } else {
-                    writeln!(self.out, "{level}while(true) {{")?;
+                    writeln!(self.out, "{level}{loop_header} {{")?;
                 }
-                for sta in body {
+                let depth = self.es100_loop_counters.len();
+                self.es100_loop_counters.extend(loop_counter);
+                for (sta, span) in body.span_iter() {
+                    self.out.mark(*span);
                     self.write_stmt(sta, ctx, level.next())?;
                 }
+                self.es100_loop_counters.truncate(depth);
                 writeln!(self.out, "{level}}}")?
             }
             // Break, continue and return as written as in C
@@ -2210,6 +2777,10 @@ impl<'a, W: Write> Writer<'a, W> {
                                             ),
                                         };
                                         write!(self.out, "{varying_name} = ")?;
+                                        let conversion_end = self.write_es100_output_conversion(
+                                            member.binding.as_ref().unwrap(),
+                                            member.ty,
+                                        )?;
 
                                         if let Some(struct_name) = temp_struct_name {
                                             write!(self.out, "{struct_name}")?;
@@ -2220,7 +2791,7 @@ impl<'a, W: Write> Writer<'a, W> {
                                         // Write field name
                                         writeln!(
                                             self.out,
-                                            ".{};",
+                                            ".{}{conversion_end};",
                                             &self.names
                                                 [&NameKey::StructMember(result.ty, index as u32)]
                                         )?;
@@ -2237,8 +2808,12 @@ impl<'a, W: Write> Writer<'a, W> {
                                         ),
                                     };
                                     write!(self.out, "{name} = ")?;
+                                    let conversion_end = self.write_es100_output_conversion(
+                                        result.binding.as_ref().unwrap(),
+                                        result.ty,
+                                    )?;
                                     self.write_expr(value, ctx)?;
-                                    writeln!(self.out, ";")?;
+                                    writeln!(self.out, "{conversion_end};")?;
                                     write!(self.out, "{level}")?;
                                 }
                             }
@@ -2379,6 +2954,8 @@ impl<'a, W: Write> Writer<'a, W> {
                 writeln!(self.out, ");")?;
             }
             Statement::RayQuery { .. } => unreachable!(),
+            // Task and mesh shaders are rejected in `Writer::new`.
+            Statement::MeshFunction(_) => unreachable!(),
         }
 
         Ok(())
@@ -2444,7 +3021,11 @@ impl<'a, W: Write> Writer<'a, W> {
                     // Unsigned integers need a `u` at the end
                     //
                     // While `core` doesn't necessarily need it, it's allowed and since `es` needs it we
-                    // always write it as the extra branch wouldn't have any benefit in readability
+                    // always write it as the extra branch wouldn't have any benefit in readability,
+                    // except for GLSL ES 1.00 where they are written as `int`s
+                    crate::Literal::U32(value) if self.options.version.is_es100() => {
+                        write!(self.out, "{}", value)?
+                    }
                     crate::Literal::U32(value) => write!(self.out, "{}u", value)?,
                     crate::Literal::I32(value) => write!(self.out, "{}", value)?,
                     crate::Literal::Bool(value) => write!(self.out, "{}", value)?,
@@ -2529,10 +3110,36 @@ impl<'a, W: Write> Writer<'a, W> {
                 )?;
             }
             // `Access` is applied to arrays, vectors and matrices and is written as indexing
+            //
+            // GLSL ES 1.00 only guarantees indexing with loop indices, so loads of a loop
+            // counter are written in terms of the index, see `es100::CountedLoop::counter`.
             Expression::Access { base, index } => {
                 self.write_expr(base, ctx)?;
                 write!(self.out, "[")?;
-                self.write_expr(index, ctx)?;
+                let counter = match ctx.expressions[index] {
+                    Expression::Load { pointer } => match ctx.expressions[pointer] {
+                        Expression::LocalVariable(local) => self
+                            .es100_loop_counters
+                            .iter()
+                            .find(|&&(ref counter, _)| counter.local == local),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match counter {
+                    Some(&(es100::LoopCounter { start, step, .. }, ref index)) => {
+                        let scaled = match step.abs() {
+                            1 => index.clone(),
+                            step => format!("{index} * {step}"),
+                        };
+                        let op = if step < 0 { "-" } else { "+" };
+                        match (start, step) {
+                            (0, 1) => write!(self.out, "{index}")?,
+                            _ => write!(self.out, "({start} {op} {scaled})")?,
+                        }
+                    }
+                    None => self.write_expr(index, ctx)?,
+                }
                 write!(self.out, "]")?
             }
             // `AccessIndex` is the same as `Access` except that the index is a constant and it can
@@ -2610,6 +3217,39 @@ impl<'a, W: Write> Writer<'a, W> {
             // `textureLod(image, coordinate, level)` - Zero or Exact sample level
             //
             // Furthermore if `depth_ref` is some we need to append it to the coordinate vector
+            // `textureSampleBaseClampToEdge` is written as a `textureLod` with
+            // the coordinates clamped to half a texel inside the edges
+            Expression::ImageSample {
+                image,
+                coordinate,
+                clamp_to_edge: true,
+                ..
+            } => {
+                if let TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = *ctx.resolve_type(image, &self.module.types)
+                {
+                    let name = self.external_texture_name(image, ctx)?;
+                    write!(
+                        self.out,
+                        "{SAMPLE_EXTERNAL_FUNCTION}({name}, {name}_plane1, {name}_plane2, {name}_params, "
+                    )?;
+                    self.write_expr(coordinate, ctx)?;
+                    write!(self.out, ")")?;
+                    return Ok(());
+                }
+
+                write!(self.out, "textureLod(")?;
+                self.write_expr(image, ctx)?;
+                write!(self.out, ", clamp(")?;
+                self.write_expr(coordinate, ctx)?;
+                write!(self.out, ", vec2(0.5) / vec2(textureSize(")?;
+                self.write_expr(image, ctx)?;
+                write!(self.out, ", 0)), vec2(1.0) - vec2(0.5) / vec2(textureSize(")?;
+                self.write_expr(image, ctx)?;
+                write!(self.out, ", 0))), 0.0)")?;
+            }
             Expression::ImageSample {
                 image,
                 sampler: _, //TODO?
@@ -2619,6 +3259,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 offset,
                 level,
                 depth_ref,
+                clamp_to_edge: false,
             } => {
                 let dim = match *ctx.resolve_type(image, &self.module.types) {
                     TypeInner::Image { dim, .. } => dim,
@@ -2667,6 +3308,20 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     crate::SampleLevel::Gradient { .. } => "textureGrad",
                 };
+                // GLSL ES 1.00 names the functions after the sampler type
+                let fun_name = match (self.options.version.is_es100(), dim, level) {
+                    (false, _, _) => fun_name,
+                    (
+                        true,
+                        crate::ImageDimension::Cube,
+                        crate::SampleLevel::Zero | crate::SampleLevel::Exact(_),
+                    ) => "textureCubeLod",
+                    (true, crate::ImageDimension::Cube, _) => "textureCube",
+                    (true, _, crate::SampleLevel::Zero | crate::SampleLevel::Exact(_)) => {
+                        "texture2DLod"
+                    }
+                    (true, _, _) => "texture2D",
+                };
                 let offset_name = match offset {
                     Some(_) => "Offset",
                     None => "",
@@ -2816,6 +3471,17 @@ impl<'a, W: Write> Writer<'a, W> {
                     } => (dim, class),
                     _ => unreachable!(),
                 };
+
+                // The size is the only query of external textures, and it is
+                // in their parameters.
+                if class == ImageClass::External {
+                    let name = self.external_texture_name(image, ctx)?;
+                    let params_ty = self.external_texture_params()?;
+                    let size = &self.names[&NameKey::StructMember(params_ty, 1)];
+                    write!(self.out, "{name}_params.{size}")?;
+                    return Ok(());
+                }
+
                 let components = match dim {
                     crate::ImageDimension::D1 => 1,
                     crate::ImageDimension::D2 => 2,
@@ -2868,6 +3534,7 @@ impl<'a, W: Write> Writer<'a, W> {
                                 write!(self.out, "imageSize(")?;
                                 self.write_expr(image, ctx)?;
                             }
+                            ImageClass::External => unreachable!(),
                         }
                         write!(self.out, ")")?;
                         if components != 1 || self.options.version.is_es() {
@@ -2881,7 +3548,9 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     crate::ImageQuery::NumLayers => {
                         let fun_name = match class {
-                            ImageClass::Sampled { .. } | ImageClass::Depth { .. } => "textureSize",
+                            ImageClass::Sampled { .. }
+                            | ImageClass::Depth { .. }
+                            | ImageClass::External => "textureSize",
                             ImageClass::Storage { .. } => "imageSize",
                         };
                         write!(self.out, "{fun_name}(")?;
@@ -2898,9 +3567,9 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     crate::ImageQuery::NumSamples => {
                         let fun_name = match class {
-                            ImageClass::Sampled { .. } | ImageClass::Depth { .. } => {
-                                "textureSamples"
-                            }
+                            ImageClass::Sampled { .. }
+                            | ImageClass::Depth { .. }
+                            | ImageClass::External => "textureSamples",
                             ImageClass::Storage { .. } => "imageSamples",
                         };
                         write!(self.out, "{fun_name}(")?;
@@ -2953,6 +3622,7 @@ impl<'a, W: Write> Writer<'a, W> {
                         | Bo::Equal
                         | Bo::NotEqual => BinaryOperation::VectorCompare,
                         Bo::Modulo if scalar.kind == Sk::Float => BinaryOperation::Modulo,
+                        Bo::Modulo if self.options.version.is_es100() => BinaryOperation::Modulo,
                         Bo::And if scalar.kind == Sk::Bool => {
                             op = crate::BinaryOperator::LogicalAnd;
                             BinaryOperation::VectorComponentWise
@@ -2968,6 +3638,12 @@ impl<'a, W: Write> Writer<'a, W> {
                             Bo::Modulo => BinaryOperation::Modulo,
                             _ => BinaryOperation::Other,
                         },
+                        // `%` is reserved in GLSL ES 1.00
+                        (Some(Sk::Sint | Sk::Uint), _)
+                            if op == Bo::Modulo && self.options.version.is_es100() =>
+                        {
+                            BinaryOperation::Modulo
+                        }
                         (Some(Sk::Bool), Some(Sk::Bool)) => match op {
                             Bo::InclusiveOr => {
                                 op = crate::BinaryOperator::LogicalOr;
@@ -3045,11 +3721,34 @@ impl<'a, W: Write> Writer<'a, W> {
                         write!(self.out, " - ")?;
                         self.write_expr(right, ctx)?;
                         write!(self.out, " * ")?;
-                        write!(self.out, "trunc(")?;
-                        self.write_expr(left, ctx)?;
-                        write!(self.out, " / ")?;
-                        self.write_expr(right, ctx)?;
-                        write!(self.out, ")")?;
+
+                        let is_float = left_inner.scalar_kind() == Some(Sk::Float)
+                            || right_inner.scalar_kind() == Some(Sk::Float);
+                        if !self.options.version.is_es100() {
+                            write!(self.out, "trunc(")?;
+                            self.write_expr(left, ctx)?;
+                            write!(self.out, " / ")?;
+                            self.write_expr(right, ctx)?;
+                            write!(self.out, ")")?;
+                        } else if is_float {
+                            // GLSL ES 1.00 has no `trunc`
+                            write!(self.out, "(sign(")?;
+                            self.write_expr(left, ctx)?;
+                            write!(self.out, " / ")?;
+                            self.write_expr(right, ctx)?;
+                            write!(self.out, ") * floor(abs(")?;
+                            self.write_expr(left, ctx)?;
+                            write!(self.out, " / ")?;
+                            self.write_expr(right, ctx)?;
+                            write!(self.out, ")))")?;
+                        } else {
+                            // Integer division already truncates
+                            write!(self.out, "(")?;
+                            self.write_expr(left, ctx)?;
+                            write!(self.out, " / ")?;
+                            self.write_expr(right, ctx)?;
+                            write!(self.out, ")")?;
+                        }
 
                         write!(self.out, ")")?;
                     }
@@ -3079,7 +3778,28 @@ impl<'a, W: Write> Writer<'a, W> {
                 };
 
                 // TODO: Boolean mix on desktop required GL_EXT_shader_integer_mix
-                if vec_select {
+                if vec_select && self.options.version.is_es100() {
+                    // GLSL ES 1.00 has no boolean `mix`, select each component instead
+                    let size = match *cond_ty {
+                        TypeInner::Vector { size, .. } => size,
+                        _ => unreachable!(),
+                    };
+                    let accept_inner = ctx.resolve_type(accept, &self.module.types);
+                    self.write_value_type(accept_inner)?;
+                    write!(self.out, "(")?;
+                    for i in 0..size as usize {
+                        if i != 0 {
+                            write!(self.out, ", ")?;
+                        }
+                        let component = back::COMPONENTS[i];
+                        self.write_expr(condition, ctx)?;
+                        write!(self.out, ".{component} ? ")?;
+                        self.write_expr(accept, ctx)?;
+                        write!(self.out, ".{component} : ")?;
+                        self.write_expr(reject, ctx)?;
+                        write!(self.out, ".{component}")?;
+                    }
+                } else if vec_select {
                     // Glsl defines that for mix when the condition is a boolean the first element
                     // is picked if condition is false and the second if condition is true
                     write!(self.out, "mix(")?;
@@ -3224,6 +3944,10 @@ impl<'a, W: Write> Writer<'a, W> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let signed = fun == Mf::Dot4I8Packed;
+                        return self.write_dot_4x8_packed(arg, arg1.unwrap(), signed, ctx);
+                    }
                     Mf::Outer => "outerProduct",
                     Mf::Cross => "cross",
                     Mf::Distance => "distance",
@@ -3266,6 +3990,38 @@ impl<'a, W: Write> Writer<'a, W> {
                     Mf::Inverse => "inverse",
                     Mf::Transpose => "transpose",
                     Mf::Determinant => "determinant",
+                    Mf::QuantizeToF16 => {
+                        // Round-trip through `packHalf2x16`, two components at a time.
+                        match *ctx.resolve_type(arg, &self.module.types) {
+                            TypeInner::Scalar(_) => {
+                                write!(self.out, "unpackHalf2x16(packHalf2x16(vec2(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, "))).x")?;
+                            }
+                            TypeInner::Vector {
+                                size: crate::VectorSize::Bi,
+                                ..
+                            } => {
+                                write!(self.out, "unpackHalf2x16(packHalf2x16(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, "))")?;
+                            }
+                            TypeInner::Vector { size, .. } => {
+                                let (last, swizzle) = match size {
+                                    crate::VectorSize::Tri => (".x", "zz"),
+                                    _ => ("", "zw"),
+                                };
+                                write!(self.out, "vec{}(", size as u8)?;
+                                write!(self.out, "unpackHalf2x16(packHalf2x16(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, ".xy)), unpackHalf2x16(packHalf2x16(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, ".{swizzle})){last})")?;
+                            }
+                            _ => unreachable!(),
+                        }
+                        return Ok(());
+                    }
                     // bits
                     Mf::CountTrailingZeros => {
                         match *ctx.resolve_type(arg, &self.module.types) {
@@ -3380,12 +4136,30 @@ impl<'a, W: Write> Writer<'a, W> {
                     Mf::Pack2x16snorm => "packSnorm2x16",
                     Mf::Pack2x16unorm => "packUnorm2x16",
                     Mf::Pack2x16float => "packHalf2x16",
+                    Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp => {
+                        let signed = matches!(fun, Mf::Pack4xI8 | Mf::Pack4xI8Clamp);
+                        let clamp = matches!(fun, Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp);
+                        return self.write_pack_4x8(arg, signed, clamp, ctx);
+                    }
                     // data unpacking
                     Mf::Unpack4x8snorm => "unpackSnorm4x8",
                     Mf::Unpack4x8unorm => "unpackUnorm4x8",
                     Mf::Unpack2x16snorm => "unpackSnorm2x16",
                     Mf::Unpack2x16unorm => "unpackUnorm2x16",
                     Mf::Unpack2x16float => "unpackHalf2x16",
+                    Mf::Unpack4xI8 => {
+                        // Sign-extend each byte by shifting it to the top and back down.
+                        write!(self.out, "((ivec4(int(")?;
+                        self.write_expr(arg, ctx)?;
+                        write!(self.out, ")) << ivec4(24, 16, 8, 0)) >> 24)")?;
+                        return Ok(());
+                    }
+                    Mf::Unpack4xU8 => {
+                        write!(self.out, "((uvec4(")?;
+                        self.write_expr(arg, ctx)?;
+                        write!(self.out, ") >> uvec4(0u, 8u, 16u, 24u)) & 0xFFu)")?;
+                        return Ok(());
+                    }
                 };
 
                 let extract_bits = fun == Mf::ExtractBits;
@@ -3496,7 +4270,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 match convert {
                     Some(width) => {
                         // this is similar to `write_type`, but with the target kind
-                        let scalar = glsl_scalar(crate::Scalar {
+                        let scalar = self.scalar_string(crate::Scalar {
                             kind: target_kind,
                             width,
                         })?;
@@ -3541,6 +4315,13 @@ impl<'a, W: Write> Writer<'a, W> {
                                 self.write_expr(expr, ctx)?;
                                 return Ok(());
                             }
+                            // GLSL ES 1.00 writes both as `int`
+                            (Sk::Sint, Sk::Uint, _) | (Sk::Uint, Sk::Sint, _)
+                                if self.options.version.is_es100() =>
+                            {
+                                self.write_expr(expr, ctx)?;
+                                return Ok(());
+                            }
 
                             // Cast to/from floats
                             (Sk::Float, Sk::Sint, _) => write!(self.out, "floatBitsToInt")?,
@@ -3794,6 +4575,16 @@ impl<'a, W: Write> Writer<'a, W> {
         let (fun_name, policy) = match class {
             // Sampled images inherit the policy from the user passed policies
             crate::ImageClass::Sampled { .. } => ("texelFetch", self.policies.image_load),
+            crate::ImageClass::External => {
+                let name = self.external_texture_name(image, ctx)?;
+                write!(
+                    self.out,
+                    "{LOAD_EXTERNAL_FUNCTION}({name}, {name}_plane1, {name}_plane2, {name}_params, uvec2("
+                )?;
+                self.write_expr(coordinate, ctx)?;
+                write!(self.out, "))")?;
+                return Ok(());
+            }
             crate::ImageClass::Storage { .. } => {
                 // OpenGL ES 3.1 mentiones in Chapter "8.22 Texture Image Loads and Stores" that:
                 // "Invalid image loads will return a vector where the value of R, G, and B components
@@ -4118,10 +4909,58 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method used to zero initialize the variable `name` of type `ty` with statements
+    ///
+    /// Arrays are initialized element by element, which is needed for GLSL ES 1.00 as it has
+    /// no array constructors.
+    fn write_zero_init_statements(
+        &mut self,
+        name: &str,
+        ty: Handle<crate::Type>,
+        level: back::Level,
+    ) -> BackendResult {
+        match self.module.types[ty].inner {
+            _ if is_value_init_supported(self.module, ty, self.options.version) => {
+                write!(self.out, "{level}{name} = ")?;
+                self.write_zero_init_value(ty)?;
+                writeln!(self.out, ";")?;
+            }
+            TypeInner::Array { base, size, .. } => {
+                let count = match size
+                    .to_indexable_length(self.module)
+                    .expect("Bad array size")
+                {
+                    proc::IndexableLength::Known(count) => count,
+                    proc::IndexableLength::Dynamic => return Ok(()),
+                };
+                let index = self.namer.call("i");
+                writeln!(
+                    self.out,
+                    "{level}for (int {index} = 0; {index} < {count}; {index}++) {{"
+                )?;
+                self.write_zero_init_statements(&format!("{name}[{index}]"), base, level.next())?;
+                writeln!(self.out, "{level}}}")?;
+            }
+            TypeInner::Struct { ref members, .. } => {
+                for (index, member) in members.iter().enumerate() {
+                    let member_name = format!(
+                        "{name}.{}",
+                        self.names[&NameKey::StructMember(ty, index as u32)]
+                    );
+                    self.write_zero_init_statements(&member_name, member.ty, level)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     /// Helper function that write string with zero initialization for scalar
     fn write_zero_init_scalar(&mut self, kind: crate::ScalarKind) -> BackendResult {
         match kind {
             crate::ScalarKind::Bool => write!(self.out, "false")?,
+            crate::ScalarKind::Uint if self.options.version.is_es100() => write!(self.out, "0")?,
             crate::ScalarKind::Uint => write!(self.out, "0u")?,
             crate::ScalarKind::Float => write!(self.out, "0.0")?,
             crate::ScalarKind::Sint => write!(self.out, "0")?,
@@ -4144,6 +4983,9 @@ impl<'a, W: Write> Writer<'a, W> {
         if flags.contains(crate::Barrier::WORK_GROUP) {
             writeln!(self.out, "{level}memoryBarrierShared();")?;
         }
+        if flags.contains(crate::Barrier::TEXTURE) {
+            writeln!(self.out, "{level}memoryBarrierImage();")?;
+        }
         writeln!(self.out, "{level}barrier();")?;
         Ok(())
     }
@@ -4173,17 +5015,20 @@ impl<'a, W: Write> Writer<'a, W> {
         for sampling in info.sampling_set.iter() {
             let tex_name = self.reflection_names_globals[&sampling.image].clone();
 
-            match texture_mapping.entry(tex_name) {
-                Entry::Vacant(v) => {
-                    v.insert(TextureMapping {
-                        texture: sampling.image,
-                        sampler: Some(sampling.sampler),
-                    });
-                }
-                Entry::Occupied(e) => {
-                    if e.get().sampler != Some(sampling.sampler) {
-                        log::error!("Conflicting samplers for {}", e.key());
-                        return Err(Error::ImageMultipleSamplers);
+            for (tex_name, plane) in self.texture_names(sampling.image, tex_name) {
+                match texture_mapping.entry(tex_name) {
+                    Entry::Vacant(v) => {
+                        v.insert(TextureMapping {
+                            texture: sampling.image,
+                            sampler: Some(sampling.sampler),
+                            plane,
+                        });
+                    }
+                    Entry::Occupied(e) => {
+                        if e.get().sampler != Some(sampling.sampler) {
+                            log::error!("Conflicting samplers for {}", e.key());
+                            return Err(Error::ImageMultipleSamplers);
+                        }
                     }
                 }
             }
@@ -4197,17 +5042,23 @@ impl<'a, W: Write> Writer<'a, W> {
             match self.module.types[var.ty].inner {
                 crate::TypeInner::Image { .. } => {
                     let tex_name = self.reflection_names_globals[&handle].clone();
-                    match texture_mapping.entry(tex_name) {
-                        Entry::Vacant(v) => {
-                            v.insert(TextureMapping {
-                                texture: handle,
-                                sampler: None,
-                            });
-                        }
-                        Entry::Occupied(_) => {
-                            // already used with a sampler, do nothing
+                    for (tex_name, plane) in self.texture_names(handle, tex_name) {
+                        match texture_mapping.entry(tex_name) {
+                            Entry::Vacant(v) => {
+                                v.insert(TextureMapping {
+                                    texture: handle,
+                                    sampler: None,
+                                    plane,
+                                });
+                            }
+                            Entry::Occupied(_) => {
+                                // already used with a sampler, do nothing
+                            }
                         }
                     }
+                    if let Some(name) = self.reflection_names_external_params.get(&handle) {
+                        uniforms.insert(handle, name.clone());
+                    }
                 }
                 _ => match var.space {
                     crate::AddressSpace::Uniform | crate::AddressSpace::Storage { .. } => {
@@ -4255,6 +5106,24 @@ impl<'a, W: Write> Writer<'a, W> {
         })
     }
 
+    /// Return the names of the textures that the image global `handle`,
+    /// named `name`, is written as, with the plane each of them holds.
+    ///
+    /// This is the global itself, plus the other planes of external textures.
+    fn texture_names(
+        &self,
+        handle: Handle<crate::GlobalVariable>,
+        name: String,
+    ) -> Vec<(String, u32)> {
+        let mut names = Vec::with_capacity(3);
+        if self.reflection_names_external_params.contains_key(&handle) {
+            names.push((format!("{name}_plane1"), 1));
+            names.push((format!("{name}_plane2"), 2));
+        }
+        names.push((name, 0));
+        names
+    }
+
     fn collect_push_constant_items(
         &mut self,
         ty: Handle<crate::Type>,
@@ -4364,6 +5233,20 @@ const fn glsl_scalar(scalar: crate::Scalar) -> Result<ScalarString<'static>, Err
     })
 }
 
+/// Helper function that returns the float type GLSL ES 1.00 uses to pass
+/// integer attributes and varyings of type `inner`
+fn es100_float_varying_type(inner: &TypeInner) -> Option<String> {
+    match *inner {
+        TypeInner::Scalar(scalar) if scalar.kind != crate::ScalarKind::Float => {
+            Some("float".to_string())
+        }
+        TypeInner::Vector { size, scalar } if scalar.kind != crate::ScalarKind::Float => {
+            Some(format!("vec{}", size as u8))
+        }
+        _ => None,
+    }
+}
+
 /// Helper function that returns the glsl variable name for a builtin
 const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'static str {
     use crate::BuiltIn as Bi;
@@ -4413,6 +5296,12 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
         Bi::WorkGroupId => "gl_WorkGroupID",
         Bi::WorkGroupSize => "gl_WorkGroupSize",
         Bi::NumWorkGroups => "gl_NumWorkGroups",
+        // task and mesh shaders are rejected in `Writer::new`
+        Bi::MeshTaskSize
+        | Bi::CullPrimitive
+        | Bi::PointIndex
+        | Bi::LineIndices
+        | Bi::TriangleIndices => unreachable!(),
     }
 }
 
@@ -4428,6 +5317,7 @@ const fn glsl_storage_qualifier(space: crate::AddressSpace) -> Option<&'static s
         As::Handle => Some("uniform"),
         As::WorkGroup => Some("shared"),
         As::PushConstant => Some("uniform"),
+        As::TaskPayload => None,
     }
 }
 
@@ -4518,15 +5408,23 @@ fn glsl_storage_format(format: crate::StorageFormat) -> Result<&'static str, Err
     })
 }
 
-fn is_value_init_supported(module: &crate::Module, ty: Handle<crate::Type>) -> bool {
+/// Types containing arrays can't be initialized with a value in GLSL ES 1.00 as it has no
+/// array constructors.
+fn is_value_init_supported(
+    module: &crate::Module,
+    ty: Handle<crate::Type>,
+    version: Version,
+) -> bool {
     match module.types[ty].inner {
         TypeInner::Scalar { .. } | TypeInner::Vector { .. } | TypeInner::Matrix { .. } => true,
         TypeInner::Array { base, size, .. } => {
-            size != crate::ArraySize::Dynamic && is_value_init_supported(module, base)
+            !version.is_es100()
+                && size != crate::ArraySize::Dynamic
+                && is_value_init_supported(module, base, version)
         }
         TypeInner::Struct { ref members, .. } => members
             .iter()
-            .all(|member| is_value_init_supported(module, member.ty)),
+            .all(|member| is_value_init_supported(module, member.ty, version)),
         _ => false,
     }
 }
@@ -174,7 +174,14 @@ impl crate::BuiltIn {
             Self::BaseInstance | Self::BaseVertex | Self::WorkGroupSize => {
                 return Err(Error::Unimplemented(format!("builtin {self:?}")))
             }
-            Self::PointSize | Self::ViewIndex | Self::PointCoord => {
+            Self::PointSize
+            | Self::ViewIndex
+            | Self::PointCoord
+            | Self::MeshTaskSize
+            | Self::CullPrimitive
+            | Self::PointIndex
+            | Self::LineIndices
+            | Self::TriangleIndices => {
                 return Err(Error::Custom(format!("Unsupported builtin {self:?}")))
             }
         })
@@ -43,6 +43,17 @@ pub(super) struct WrappedImageQuery {
     pub(super) query: ImageQuery,
 }
 
+/// A helper function for sampling with clamp-to-edge, or for reading an
+/// [`External`] image from its lowered planes and parameters.
+///
+/// [`External`]: crate::ImageClass::External
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub(super) enum WrappedTextureFunction {
+    SampleBaseClampToEdge,
+    SampleExternal,
+    LoadExternal,
+}
+
 #[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
 pub(super) struct WrappedConstructor {
     pub(super) ty: Handle<crate::Type>,
@@ -140,6 +151,8 @@ impl<'a, W: Write> super::Writer<'a, W> {
                 let storage_format_str = format.to_hlsl_str();
                 write!(self.out, "<{storage_format_str}>")?
             }
+            // This is the type of each plane.
+            crate::ImageClass::External => write!(self.out, "<float4>")?,
         }
         Ok(())
     }
@@ -209,6 +222,9 @@ impl<'a, W: Write> super::Writer<'a, W> {
             crate::ImageClass::Depth { multi: false } => "Depth",
             crate::ImageClass::Sampled { multi: false, .. } => "",
             crate::ImageClass::Storage { .. } => "RW",
+            crate::ImageClass::External => {
+                unreachable!("external texture sizes are read from their parameters")
+            }
         };
         let arrayed_str = if query.arrayed { "Array" } else { "" };
         let query_str = match query.query {
@@ -268,6 +284,9 @@ impl<'a, W: Write> super::Writer<'a, W> {
         let extra_coords = match wiq.class {
             crate::ImageClass::Storage { .. } => 0,
             crate::ImageClass::Sampled { .. } | crate::ImageClass::Depth { .. } => 1,
+            crate::ImageClass::External => {
+                unreachable!("external texture sizes are read from their parameters")
+            }
         };
 
         // GetDimensions Overloaded Methods
@@ -776,6 +795,115 @@ impl<'a, W: Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper function that writes a wrapped function for sampling with
+    /// clamp-to-edge, or for reading an [`External`] image.
+    ///
+    /// The [`External`] functions take the image's three planes and its
+    /// parameters, as declared by `write_global`, and convert the planes'
+    /// contents to RGBA.
+    ///
+    /// [`External`]: crate::ImageClass::External
+    pub(super) fn write_wrapped_texture_function(
+        &mut self,
+        module: &crate::Module,
+        function: WrappedTextureFunction,
+    ) -> BackendResult {
+        use crate::back::INDENT;
+
+        const PLANES: &str = "Texture2D<float4> plane0, Texture2D<float4> plane1, \
+                              Texture2D<float4> plane2";
+
+        match function {
+            WrappedTextureFunction::SampleBaseClampToEdge => {
+                let name = super::writer::SAMPLE_CLAMP_TO_EDGE_FUNCTION;
+                writeln!(
+                    self.out,
+                    "float4 {name}(Texture2D<float4> tex, SamplerState samp, float2 coords)"
+                )?;
+                writeln!(self.out, "{{")?;
+                writeln!(self.out, "{INDENT}uint2 size;")?;
+                writeln!(self.out, "{INDENT}tex.GetDimensions(size.x, size.y);")?;
+                writeln!(
+                    self.out,
+                    "{INDENT}float2 half_texel = float2(0.5, 0.5) / float2(size);"
+                )?;
+                writeln!(
+                    self.out,
+                    "{INDENT}return tex.SampleLevel(samp, \
+                     clamp(coords, half_texel, float2(1.0, 1.0) - half_texel), 0.0);"
+                )?;
+            }
+            WrappedTextureFunction::SampleExternal | WrappedTextureFunction::LoadExternal => {
+                let params_ty = super::external_texture_params(module)?;
+                let params_ty_name = &self.names[&NameKey::Type(params_ty)];
+
+                if function == WrappedTextureFunction::SampleExternal {
+                    let name = super::writer::SAMPLE_EXTERNAL_FUNCTION;
+                    writeln!(
+                        self.out,
+                        "float4 {name}({PLANES}, {params_ty_name} params, \
+                         SamplerState samp, float2 coords)"
+                    )?;
+                    writeln!(self.out, "{{")?;
+                    let sample = super::writer::SAMPLE_CLAMP_TO_EDGE_FUNCTION;
+                    for i in 0..3 {
+                        writeln!(
+                            self.out,
+                            "{INDENT}float4 texel{i} = {sample}(plane{i}, samp, coords);"
+                        )?;
+                    }
+                } else {
+                    let name = super::writer::LOAD_EXTERNAL_FUNCTION;
+                    writeln!(
+                        self.out,
+                        "float4 {name}({PLANES}, {params_ty_name} params, uint2 coords)"
+                    )?;
+                    writeln!(self.out, "{{")?;
+                    writeln!(self.out, "{INDENT}uint2 size0, size1, size2;")?;
+                    for i in 0..3 {
+                        writeln!(
+                            self.out,
+                            "{INDENT}plane{i}.GetDimensions(size{i}.x, size{i}.y);"
+                        )?;
+                    }
+                    // Clamp to the first plane, and scale to the others,
+                    // which may be subsampled.
+                    writeln!(self.out, "{INDENT}uint2 coords0 = min(coords, size0 - 1u);")?;
+                    writeln!(
+                        self.out,
+                        "{INDENT}float4 texel0 = plane0.Load(int3(coords0, 0));"
+                    )?;
+                    for i in 1..3 {
+                        writeln!(
+                            self.out,
+                            "{INDENT}float4 texel{i} = \
+                             plane{i}.Load(int3(coords0 * size{i} / size0, 0));"
+                        )?;
+                    }
+                }
+
+                let yuv_conversion_matrix = &self.names[&NameKey::StructMember(params_ty, 0)];
+                let num_planes = &self.names[&NameKey::StructMember(params_ty, 2)];
+                writeln!(self.out, "{INDENT}if (params.{num_planes} == 1u) {{")?;
+                writeln!(self.out, "{INDENT}{INDENT}return texel0;")?;
+                writeln!(self.out, "{INDENT}}}")?;
+                writeln!(
+                    self.out,
+                    "{INDENT}float v = params.{num_planes} == 2u ? texel1.y : texel2.x;"
+                )?;
+                writeln!(
+                    self.out,
+                    "{INDENT}return mul(float4(texel0.x, texel1.x, v, 1.0), \
+                     params.{yuv_conversion_matrix});"
+                )?;
+            }
+        }
+        writeln!(self.out, "}}")?;
+        writeln!(self.out)?;
+
+        Ok(())
+    }
+
     /// Write functions to create special types.
     pub(super) fn write_special_functions(&mut self, module: &crate::Module) -> BackendResult {
         for (type_key, struct_ty) in module.special_types.predeclared_types.iter() {
@@ -885,6 +1013,59 @@ impl<'a, W: Write> super::Writer<'a, W> {
                         self.write_wrapped_array_length_function(wal)?;
                     }
                 }
+                crate::Expression::ImageSample {
+                    image,
+                    clamp_to_edge: true,
+                    ..
+                } => {
+                    let external = matches!(
+                        *func_ctx.resolve_type(image, &module.types),
+                        crate::TypeInner::Image {
+                            class: crate::ImageClass::External,
+                            ..
+                        }
+                    );
+                    // Sampling an external texture samples each plane.
+                    if self
+                        .wrapped
+                        .texture_functions
+                        .insert(WrappedTextureFunction::SampleBaseClampToEdge)
+                    {
+                        self.write_wrapped_texture_function(
+                            module,
+                            WrappedTextureFunction::SampleBaseClampToEdge,
+                        )?;
+                    }
+                    if external
+                        && self
+                            .wrapped
+                            .texture_functions
+                            .insert(WrappedTextureFunction::SampleExternal)
+                    {
+                        self.write_wrapped_texture_function(
+                            module,
+                            WrappedTextureFunction::SampleExternal,
+                        )?;
+                    }
+                }
+                crate::Expression::ImageLoad { image, .. } => {
+                    if let crate::TypeInner::Image {
+                        class: crate::ImageClass::External,
+                        ..
+                    } = *func_ctx.resolve_type(image, &module.types)
+                    {
+                        if self
+                            .wrapped
+                            .texture_functions
+                            .insert(WrappedTextureFunction::LoadExternal)
+                        {
+                            self.write_wrapped_texture_function(
+                                module,
+                                WrappedTextureFunction::LoadExternal,
+                            )?;
+                        }
+                    }
+                }
                 crate::Expression::ImageQuery { image, query } => {
                     let wiq = match *func_ctx.resolve_type(image, &module.types) {
                         crate::TypeInner::Image {
@@ -900,7 +1081,10 @@ impl<'a, W: Write> super::Writer<'a, W> {
                         _ => unreachable!("we only query images"),
                     };
 
-                    if self.wrapped.image_queries.insert(wiq) {
+                    // External texture sizes are read from their parameters.
+                    if wiq.class != crate::ImageClass::External
+                        && self.wrapped.image_queries.insert(wiq)
+                    {
                         self.write_wrapped_image_query_function(module, wiq, handle, func_ctx)?;
                     }
                 }
@@ -807,6 +807,7 @@ pub const RESERVED: &[&str] = &[
     "ConstantBuffer",
     "TextureBuffer",
     "RaytracingAccelerationStructure",
+    "RayDesc",
     // DXC templated types, from https://github.com/microsoft/DirectXShaderCompiler/blob/18c9e114f9c314f93e68fbc72ce207d4ed2e65ae/tools/clang/lib/AST/ASTContextHLSL.cpp
     // look for `BuiltinTypeDeclBuilder`
     "matrix",
@@ -817,6 +818,12 @@ pub const RESERVED: &[&str] = &[
     // Naga utilities
     super::writer::MODF_FUNCTION,
     super::writer::FREXP_FUNCTION,
+    super::writer::RAY_DESC_FUNCTION,
+    super::writer::RAY_QUERY_COMMITTED_FUNCTION,
+    super::writer::RAY_QUERY_CANDIDATE_FUNCTION,
+    super::writer::SAMPLE_CLAMP_TO_EDGE_FUNCTION,
+    super::writer::SAMPLE_EXTERNAL_FUNCTION,
+    super::writer::LOAD_EXTERNAL_FUNCTION,
 ];
 
 // DXC scalar types, from https://github.com/microsoft/DirectXShaderCompiler/blob/18c9e114f9c314f93e68fbc72ce207d4ed2e65ae/tools/clang/lib/AST/ASTContextHLSL.cpp#L48-L254
@@ -4,7 +4,21 @@ Backend for [HLSL][hlsl] (High-Level Shading Language).
 # Supported shader model versions:
 - 5.0
 - 5.1
-- 6.0
+- 6.0 through 6.7
+
+Ray queries require shader model 6.5 or later.
+
+# Descriptor heap indexing
+
+Binding arrays in the bind groups listed in [`Options::descriptor_heap_map`]
+are not bound to registers. Instead, their elements are fetched from the
+`ResourceDescriptorHeap` and `SamplerDescriptorHeap` introduced by shader
+model 6.6, so arbitrarily large arrays don't need matching descriptor ranges
+in the root signature. The `register` of such a binding array in
+[`Options::binding_map`] is taken as the offset of its first element from the
+start of its bind group's descriptors. Each such binding array is accessed
+through two functions, the second of which wraps the index into the heap in
+`NonUniformResourceIndex` for non-uniform indices.
 
 # Layout of values in `uniform` buffers
 
@@ -101,6 +115,7 @@ accessing individual columns by dynamic index.
 mod conv;
 mod help;
 mod keywords;
+mod ray;
 mod storage;
 mod writer;
 
@@ -122,6 +137,21 @@ pub struct BindTarget {
 // Using `BTreeMap` instead of `HashMap` so that we can hash itself.
 pub type BindingMap = std::collections::BTreeMap<crate::ResourceBinding, BindTarget>;
 
+/// Where a bind group's descriptors start in the shader-visible descriptor heaps.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+#[cfg_attr(any(feature = "serialize", feature = "deserialize"), serde(default))]
+pub struct DescriptorHeapOffsets {
+    /// Index of the group's first descriptor in the `CBV_SRV_UAV` heap.
+    pub resources: u32,
+    /// Index of the group's first descriptor in the sampler heap.
+    pub samplers: u32,
+}
+
+/// Descriptor heap offsets, indexed by bind group.
+pub type DescriptorHeapMap = std::collections::BTreeMap<u32, DescriptorHeapOffsets>;
+
 /// A HLSL shader model version.
 #[allow(non_snake_case, non_camel_case_types)]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd)]
@@ -131,6 +161,13 @@ pub enum ShaderModel {
     V5_0,
     V5_1,
     V6_0,
+    V6_1,
+    V6_2,
+    V6_3,
+    V6_4,
+    V6_5,
+    V6_6,
+    V6_7,
 }
 
 impl ShaderModel {
@@ -139,6 +176,13 @@ impl ShaderModel {
             Self::V5_0 => "5_0",
             Self::V5_1 => "5_1",
             Self::V6_0 => "6_0",
+            Self::V6_1 => "6_1",
+            Self::V6_2 => "6_2",
+            Self::V6_3 => "6_3",
+            Self::V6_4 => "6_4",
+            Self::V6_5 => "6_5",
+            Self::V6_6 => "6_6",
+            Self::V6_7 => "6_7",
         }
     }
 }
@@ -149,6 +193,8 @@ impl crate::ShaderStage {
             Self::Vertex => "vs",
             Self::Fragment => "ps",
             Self::Compute => "cs",
+            Self::Task => "as",
+            Self::Mesh => "ms",
         }
     }
 }
@@ -193,6 +239,10 @@ pub struct Options {
     pub push_constants_target: Option<BindTarget>,
     /// Should workgroup variables be zero initialized (by polyfilling)?
     pub zero_initialize_workgroup_memory: bool,
+    /// Bind groups whose binding arrays are accessed through the descriptor
+    /// heaps. Requires [`ShaderModel::V6_6`].
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub descriptor_heap_map: DescriptorHeapMap,
 }
 
 impl Default for Options {
@@ -204,6 +254,7 @@ impl Default for Options {
             special_constants_binding: None,
             push_constants_target: None,
             zero_initialize_workgroup_memory: true,
+            descriptor_heap_map: DescriptorHeapMap::default(),
         }
     }
 }
@@ -223,6 +274,46 @@ impl Options {
             None => Err(EntryPointError::MissingBinding(res_binding.clone())),
         }
     }
+
+    /// Resolve the descriptor heap index of the first element of the binding
+    /// array at `res_binding`, if its group is in the [`descriptor_heap_map`].
+    ///
+    /// [`descriptor_heap_map`]: Options::descriptor_heap_map
+    fn resolve_descriptor_heap_index(
+        &self,
+        res_binding: &crate::ResourceBinding,
+        is_sampler: bool,
+    ) -> Option<u32> {
+        let offsets = self.descriptor_heap_map.get(&res_binding.group)?;
+        let target = self.resolve_resource_binding(res_binding).ok()?;
+        let group_offset = if is_sampler {
+            offsets.samplers
+        } else {
+            offsets.resources
+        };
+        Some(group_offset + target.register)
+    }
+
+    /// Resolve `res_binding`, and, if `ty` is an [`External`] image, the
+    /// bindings of the other resources it is lowered into.
+    ///
+    /// [`External`]: crate::ImageClass::External
+    fn resolve_global_binding(
+        &self,
+        res_binding: &crate::ResourceBinding,
+        ty: &crate::TypeInner,
+    ) -> Result<BindTarget, EntryPointError> {
+        if let crate::TypeInner::Image {
+            class: crate::ImageClass::External,
+            ..
+        } = *ty
+        {
+            for binding in res_binding.external_texture_bindings().iter() {
+                self.resolve_resource_binding(binding)?;
+            }
+        }
+        self.resolve_resource_binding(res_binding)
+    }
 }
 
 /// Reflection info for entry point names.
@@ -243,16 +334,35 @@ pub enum Error {
     IoError(#[from] FmtError),
     #[error("A scalar with an unsupported width was requested: {0:?}")]
     UnsupportedScalar(crate::Scalar),
+    #[error("{feature} requires shader model {required:?} or later")]
+    ShaderModelTooLow {
+        feature: &'static str,
+        required: ShaderModel,
+    },
     #[error("{0}")]
     Unimplemented(String), // TODO: Error used only during development
     #[error("{0}")]
     Custom(String),
+    #[error("module is not validated properly: {0}")]
+    Validation(&'static str),
+}
+
+/// Return the type of the parameters of external textures, which the module
+/// must have if it uses them.
+fn external_texture_params(module: &crate::Module) -> Result<crate::Handle<crate::Type>, Error> {
+    module
+        .special_types
+        .external_texture_params
+        .ok_or(Error::Validation(
+            "missing external texture parameters type",
+        ))
 }
 
 #[derive(Default)]
 struct Wrapped {
     array_lengths: crate::FastHashSet<help::WrappedArrayLength>,
     image_queries: crate::FastHashSet<help::WrappedImageQuery>,
+    texture_functions: crate::FastHashSet<help::WrappedTextureFunction>,
     constructors: crate::FastHashSet<help::WrappedConstructor>,
     struct_matrix_access: crate::FastHashSet<help::WrappedStructMatrixAccess>,
     mat_cx2s: crate::FastHashSet<help::WrappedMatCx2>,
@@ -262,6 +372,7 @@ impl Wrapped {
     fn clear(&mut self) {
         self.array_lengths.clear();
         self.image_queries.clear();
+        self.texture_functions.clear();
         self.constructors.clear();
         self.struct_matrix_access.clear();
         self.mat_cx2s.clear();
@@ -269,7 +380,7 @@ impl Wrapped {
 }
 
 pub struct Writer<'a, W> {
-    out: W,
+    out: back::SourceMapWriter<W>,
     names: crate::FastHashMap<proc::NameKey, String>,
     namer: proc::Namer,
     /// HLSL backend options
@@ -279,6 +390,9 @@ pub struct Writer<'a, W> {
     /// Set of expressions that have associated temporary variables
     named_expressions: crate::NamedExpressions,
     wrapped: Wrapped,
+    /// Names of the accessors for binding arrays in descriptor heaps that wrap
+    /// the heap index in `NonUniformResourceIndex`.
+    non_uniform_heap_accessors: crate::FastHashMap<crate::Handle<crate::GlobalVariable>, String>,
 
     /// A reference to some part of a global variable, lowered to a series of
     /// byte offset calculations.
@@ -0,0 +1,265 @@
+/*!
+Generating HLSL for ray query operations.
+
+Ray queries map onto the [inline raytracing] `RayQuery` object of shader
+model 6.5. Naga's own [`RayDesc`] and [`RayIntersection`] structs don't match
+the layout of HLSL's built-in types, so we generate helper functions to
+convert between them:
+
+```hlsl
+RayDesc RayDescFromRayDesc_(RayDesc_ arg0) { ... }
+RayIntersection GetCommittedIntersection(RayQuery<RAY_FLAG_NONE> rq) { ... }
+RayIntersection GetCandidateIntersection(RayQuery<RAY_FLAG_NONE> rq) { ... }
+```
+
+[inline raytracing]: https://microsoft.github.io/DirectX-Specs/d3d/Raytracing.html#inline-raytracing
+[`RayDesc`]: crate::Module::generate_ray_desc_type
+[`RayIntersection`]: crate::Module::generate_ray_intersection_type
+*/
+
+use super::{
+    writer::{
+        RAY_DESC_FUNCTION, RAY_QUERY_CANDIDATE_FUNCTION, RAY_QUERY_COMMITTED_FUNCTION,
+        RAY_QUERY_TYPE,
+    },
+    BackendResult,
+};
+use crate::{arena::Handle, back, proc::NameKey};
+use std::fmt::Write;
+
+impl<'a, W: Write> super::Writer<'a, W> {
+    /// Write the helper functions needed by ray queries, if the module uses them.
+    ///
+    /// # Notes
+    /// Ends in a newline
+    pub(super) fn write_ray_query_functions(&mut self, module: &crate::Module) -> BackendResult {
+        if let Some(ray_desc) = module.special_types.ray_desc {
+            self.write_ray_desc_function(ray_desc)?;
+        }
+        if let Some(ray_intersection) = module.special_types.ray_intersection {
+            self.write_ray_intersection_function(ray_intersection, true)?;
+            self.write_ray_intersection_function(ray_intersection, false)?;
+        }
+        Ok(())
+    }
+
+    /// Write a function converting Naga's `RayDesc` struct to HLSL's built-in one.
+    ///
+    /// The member indices must match `generate_ray_desc_type`.
+    fn write_ray_desc_function(&mut self, ray_desc: Handle<crate::Type>) -> BackendResult {
+        let tab = back::INDENT;
+        let ty_name = &self.names[&NameKey::Type(ray_desc)];
+        let member = |index: u32| &self.names[&NameKey::StructMember(ray_desc, index)];
+
+        writeln!(self.out, "RayDesc {RAY_DESC_FUNCTION}({ty_name} arg0) {{")?;
+        writeln!(self.out, "{tab}RayDesc ret = (RayDesc)0;")?;
+        writeln!(self.out, "{tab}ret.Origin = arg0.{};", member(4))?;
+        writeln!(self.out, "{tab}ret.TMin = arg0.{};", member(2))?;
+        writeln!(self.out, "{tab}ret.Direction = arg0.{};", member(5))?;
+        writeln!(self.out, "{tab}ret.TMax = arg0.{};", member(3))?;
+        writeln!(self.out, "{tab}return ret;")?;
+        writeln!(self.out, "}}")?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    /// Write a function reading the committed or candidate intersection of a
+    /// ray query into Naga's `RayIntersection` struct.
+    ///
+    /// The `kind` member receives the `COMMITTED_STATUS` or `CANDIDATE_TYPE`
+    /// value mapped to [`RayIntersectionType`], like the MSL backend does.
+    ///
+    /// [`RayIntersectionType`]: back::RayIntersectionType
+    ///
+    /// The member indices must match `generate_ray_intersection_type`.
+    fn write_ray_intersection_function(
+        &mut self,
+        ray_intersection: Handle<crate::Type>,
+        committed: bool,
+    ) -> BackendResult {
+        let tab = back::INDENT;
+        let ty_name = &self.names[&NameKey::Type(ray_intersection)];
+        let member = |index: u32| &self.names[&NameKey::StructMember(ray_intersection, index)];
+        let (fun_name, prefix, kind, nothing, triangle, bounding_box) = if committed {
+            (
+                RAY_QUERY_COMMITTED_FUNCTION,
+                "Committed",
+                "CommittedStatus()",
+                Some("COMMITTED_NOTHING"),
+                "COMMITTED_TRIANGLE_HIT",
+                "COMMITTED_PROCEDURAL_PRIMITIVE_HIT",
+            )
+        } else {
+            (
+                RAY_QUERY_CANDIDATE_FUNCTION,
+                "Candidate",
+                "CandidateType()",
+                None,
+                "CANDIDATE_NON_OPAQUE_TRIANGLE",
+                "CANDIDATE_PROCEDURAL_PRIMITIVE",
+            )
+        };
+        let v_triangle = back::RayIntersectionType::Triangle as u32;
+        let v_bbox = back::RayIntersectionType::BoundingBox as u32;
+
+        writeln!(self.out, "{ty_name} {fun_name}({RAY_QUERY_TYPE} rq) {{")?;
+        writeln!(self.out, "{tab}{ty_name} ret = ({ty_name})0;")?;
+        writeln!(
+            self.out,
+            "{tab}ret.{} = rq.{kind} == {triangle} ? {v_triangle} :",
+            member(0)
+        )?;
+        writeln!(
+            self.out,
+            "{tab}{tab}rq.{kind} == {bounding_box} ? {v_bbox} : 0;"
+        )?;
+
+        // There is nothing to read if nothing was committed yet.
+        let level = match nothing {
+            Some(nothing) => {
+                writeln!(self.out, "{tab}if (rq.{kind} != {nothing}) {{")?;
+                back::Level(2)
+            }
+            None => back::Level(1),
+        };
+
+        writeln!(
+            self.out,
+            "{level}ret.{} = rq.{prefix}InstanceID();",
+            member(2)
+        )?;
+        writeln!(
+            self.out,
+            "{level}ret.{} = rq.{prefix}InstanceIndex();",
+            member(3)
+        )?;
+        writeln!(
+            self.out,
+            "{level}ret.{} = rq.{prefix}InstanceContributionToHitGroupIndex();",
+            member(4)
+        )?;
+        writeln!(
+            self.out,
+            "{level}ret.{} = rq.{prefix}GeometryIndex();",
+            member(5)
+        )?;
+        writeln!(
+            self.out,
+            "{level}ret.{} = rq.{prefix}PrimitiveIndex();",
+            member(6)
+        )?;
+        writeln!(
+            self.out,
+            "{level}ret.{} = rq.{prefix}ObjectToWorld4x3();",
+            member(9)
+        )?;
+        writeln!(
+            self.out,
+            "{level}ret.{} = rq.{prefix}WorldToObject4x3();",
+            member(10)
+        )?;
+
+        // The hit distance of a candidate is only known for triangles;
+        // procedural candidates must compute it themselves.
+        if committed {
+            writeln!(self.out, "{level}ret.{} = rq.CommittedRayT();", member(1))?;
+        }
+        writeln!(self.out, "{level}if (rq.{kind} == {triangle}) {{")?;
+        if !committed {
+            writeln!(
+                self.out,
+                "{}ret.{} = rq.CandidateTriangleRayT();",
+                level.next(),
+                member(1)
+            )?;
+        }
+        writeln!(
+            self.out,
+            "{}ret.{} = rq.{prefix}TriangleBarycentrics();",
+            level.next(),
+            member(7)
+        )?;
+        writeln!(
+            self.out,
+            "{}ret.{} = rq.{prefix}TriangleFrontFace();",
+            level.next(),
+            member(8)
+        )?;
+        writeln!(self.out, "{level}}}")?;
+
+        if nothing.is_some() {
+            writeln!(self.out, "{tab}}}")?;
+        }
+        writeln!(self.out, "{tab}return ret;")?;
+        writeln!(self.out, "}}")?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    pub(super) fn write_ray_query_function(
+        &mut self,
+        module: &crate::Module,
+        query: Handle<crate::Expression>,
+        function: &crate::RayQueryFunction,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> BackendResult {
+        match *function {
+            crate::RayQueryFunction::Initialize {
+                acceleration_structure,
+                descriptor,
+            } => {
+                // The member indices must match `generate_ray_desc_type`.
+                let ray_desc = module.special_types.ray_desc.unwrap();
+                let flags = self.names[&NameKey::StructMember(ray_desc, 0)].clone();
+                let cull_mask = self.names[&NameKey::StructMember(ray_desc, 1)].clone();
+
+                write!(self.out, "{level}")?;
+                self.write_expr(module, query, func_ctx)?;
+                write!(self.out, ".TraceRayInline(")?;
+                self.write_expr(module, acceleration_structure, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, descriptor, func_ctx)?;
+                write!(self.out, ".{flags}, ")?;
+                self.write_expr(module, descriptor, func_ctx)?;
+                write!(self.out, ".{cull_mask}, ")?;
+                write!(self.out, "{RAY_DESC_FUNCTION}(")?;
+                self.write_expr(module, descriptor, func_ctx)?;
+                writeln!(self.out, "));")?;
+            }
+            crate::RayQueryFunction::Proceed { result } => {
+                write!(self.out, "{level}")?;
+                let name = format!("{}{}", back::BAKE_PREFIX, result.index());
+                write!(self.out, "const bool {name} = ")?;
+                self.named_expressions.insert(result, name);
+                self.write_expr(module, query, func_ctx)?;
+                writeln!(self.out, ".Proceed();")?;
+            }
+            crate::RayQueryFunction::Terminate => {
+                write!(self.out, "{level}")?;
+                self.write_expr(module, query, func_ctx)?;
+                writeln!(self.out, ".Abort();")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn write_ray_query_get_intersection(
+        &mut self,
+        module: &crate::Module,
+        query: Handle<crate::Expression>,
+        committed: bool,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        let fun_name = if committed {
+            RAY_QUERY_COMMITTED_FUNCTION
+        } else {
+            RAY_QUERY_CANDIDATE_FUNCTION
+        };
+        write!(self.out, "{fun_name}(")?;
+        self.write_expr(module, query, func_ctx)?;
+        write!(self.out, ")")?;
+        Ok(())
+    }
+}
@@ -50,7 +50,10 @@ use crate::{
     Handle,
 };
 
-use std::{fmt, mem};
+use std::{
+    fmt::{self, Write as _},
+    mem,
+};
 
 const STORE_TEMP_NAME: &str = "_value";
 
@@ -1,14 +1,17 @@
 use super::{
     help::{WrappedArrayLength, WrappedConstructor, WrappedImageQuery, WrappedStructMatrixAccess},
     storage::StoreValue,
-    BackendResult, Error, Options,
+    BackendResult, Error, Options, ShaderModel,
 };
 use crate::{
     back,
     proc::{self, NameKey},
     valid, Handle, Module, ScalarKind, ShaderStage, TypeInner,
 };
-use std::{fmt, mem};
+use std::{
+    fmt::{self, Write as _},
+    mem,
+};
 
 const LOCATION_SEMANTIC: &str = "LOC";
 const SPECIAL_CBUF_TYPE: &str = "NagaConstants";
@@ -19,6 +22,13 @@ const SPECIAL_OTHER: &str = "other";
 
 pub(crate) const MODF_FUNCTION: &str = "naga_modf";
 pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
+pub(crate) const RAY_QUERY_TYPE: &str = "RayQuery<RAY_FLAG_NONE>";
+pub(crate) const RAY_DESC_FUNCTION: &str = "RayDescFromRayDesc_";
+pub(crate) const SAMPLE_CLAMP_TO_EDGE_FUNCTION: &str = "nagaTextureSampleBaseClampToEdge";
+pub(crate) const SAMPLE_EXTERNAL_FUNCTION: &str = "nagaTextureSampleExternal";
+pub(crate) const LOAD_EXTERNAL_FUNCTION: &str = "nagaTextureLoadExternal";
+pub(crate) const RAY_QUERY_COMMITTED_FUNCTION: &str = "GetCommittedIntersection";
+pub(crate) const RAY_QUERY_CANDIDATE_FUNCTION: &str = "GetCandidateIntersection";
 
 struct EpStructMember {
     name: String,
@@ -78,13 +88,14 @@ enum Io {
 impl<'a, W: fmt::Write> super::Writer<'a, W> {
     pub fn new(out: W, options: &'a Options) -> Self {
         Self {
-            out,
+            out: back::SourceMapWriter::new(out),
             names: crate::FastHashMap::default(),
             namer: proc::Namer::default(),
             options,
             entry_point_io: Vec::new(),
             named_expressions: crate::NamedExpressions::default(),
             wrapped: super::Wrapped::default(),
+            non_uniform_heap_accessors: crate::FastHashMap::default(),
             temp_access_chain: Vec::new(),
             need_bake_expressions: Default::default(),
         }
@@ -103,6 +114,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         self.entry_point_io.clear();
         self.named_expressions.clear();
         self.wrapped.clear();
+        self.non_uniform_heap_accessors.clear();
         self.need_bake_expressions.clear();
     }
 
@@ -146,8 +158,20 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     | crate::MathFunction::Pack2x16snorm
                     | crate::MathFunction::Pack2x16unorm
                     | crate::MathFunction::Pack4x8snorm
-                    | crate::MathFunction::Pack4x8unorm => {
+                    | crate::MathFunction::Pack4x8unorm
+                    | crate::MathFunction::Pack4xI8
+                    | crate::MathFunction::Pack4xU8
+                    | crate::MathFunction::Pack4xI8Clamp
+                    | crate::MathFunction::Pack4xU8Clamp
+                    | crate::MathFunction::Unpack4xI8
+                    | crate::MathFunction::Unpack4xU8 => {
+                        self.need_bake_expressions.insert(arg);
+                    }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed
+                        if self.options.shader_model < ShaderModel::V6_4 =>
+                    {
                         self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
                     }
                     crate::MathFunction::ExtractBits => {
                         self.need_bake_expressions.insert(arg);
@@ -170,6 +194,13 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 }
             }
 
+            // Ray descriptors are referenced several times by `TraceRayInline`.
+            if let Expression::Compose { ty, .. } = *expr {
+                if module.special_types.ray_desc == Some(ty) {
+                    self.need_bake_expressions.insert(fun_handle);
+                }
+            }
+
             if let Expression::Derivative { axis, ctrl, expr } = *expr {
                 use crate::{DerivativeAxis as Axis, DerivativeControl as Ctrl};
                 if axis == Axis::Width && (ctrl == Ctrl::Coarse || ctrl == Ctrl::Fine) {
@@ -179,11 +210,41 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         }
     }
 
+    /// Return the [`SourceMap`] for the output written so far.
+    ///
+    /// [`SourceMap`]: back::SourceMap
+    pub const fn source_map(&self) -> &back::SourceMap {
+        self.out.source_map()
+    }
+
     pub fn write(
         &mut self,
         module: &Module,
         module_info: &valid::ModuleInfo,
     ) -> Result<super::ReflectionInfo, Error> {
+        if self.options.shader_model < ShaderModel::V6_5
+            && module
+                .types
+                .iter()
+                .any(|(_, ty)| ty.inner == TypeInner::RayQuery)
+        {
+            return Err(Error::ShaderModelTooLow {
+                feature: "Ray queries",
+                required: ShaderModel::V6_5,
+            });
+        }
+
+        if module.entry_points.iter().any(|ep| {
+            matches!(
+                ep.stage,
+                crate::ShaderStage::Task | crate::ShaderStage::Mesh
+            )
+        }) {
+            return Err(Error::Custom(
+                "Task and mesh shaders are not supported".to_string(),
+            ));
+        }
+
         self.reset(module);
 
         // Write special constants, if needed
@@ -249,6 +310,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
         self.write_special_functions(module)?;
 
+        self.write_ray_query_functions(module)?;
+
         self.write_wrapped_compose_functions(module, &module.const_expressions)?;
 
         // Write all named constants
@@ -293,9 +356,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         .global_variables
                         .iter()
                         .find(|&(var_handle, var)| match var.binding {
-                            Some(ref binding) if !info[var_handle].is_empty() => {
-                                self.options.resolve_resource_binding(binding).is_err()
-                            }
+                            Some(ref binding) if !info[var_handle].is_empty() => self
+                                .options
+                                .resolve_global_binding(binding, &module.types[var.ty].inner)
+                                .is_err(),
                             _ => false,
                         })
                 {
@@ -319,6 +383,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
             self.write_wrapped_functions(module, &ctx)?;
 
+            self.out.mark(module.functions.get_span(handle));
             self.write_function(module, name.as_str(), function, &ctx, info)?;
 
             writeln!(self.out)?;
@@ -335,7 +400,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 for (var_handle, var) in module.global_variables.iter() {
                     match var.binding {
                         Some(ref binding) if !info[var_handle].is_empty() => {
-                            if let Err(err) = self.options.resolve_resource_binding(binding) {
+                            if let Err(err) = self
+                                .options
+                                .resolve_global_binding(binding, &module.types[var.ty].inner)
+                            {
                                 ep_error = Some(err);
                                 break;
                             }
@@ -637,6 +705,46 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// If the global `handle` is a binding array accessed through a descriptor
+    /// heap, return the name of the heap and the index of its first element.
+    fn descriptor_heap_binding(
+        &self,
+        module: &Module,
+        handle: Handle<crate::GlobalVariable>,
+    ) -> Option<(&'static str, u32)> {
+        let global = &module.global_variables[handle];
+        let base = match module.types[global.ty].inner {
+            TypeInner::BindingArray { base, .. } => base,
+            _ => return None,
+        };
+        let (heap, is_sampler) = match module.types[base].inner {
+            TypeInner::Sampler { .. } => ("SamplerDescriptorHeap", true),
+            _ => ("ResourceDescriptorHeap", false),
+        };
+        let index = self
+            .options
+            .resolve_descriptor_heap_index(global.binding.as_ref()?, is_sampler)?;
+        Some((heap, index))
+    }
+
+    /// Return the brackets used to index `base`, which are parentheses if it
+    /// is a binding array accessed through a descriptor heap.
+    fn index_brackets(
+        &self,
+        module: &Module,
+        base: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx,
+    ) -> (&'static str, &'static str) {
+        match func_ctx.expressions[base] {
+            crate::Expression::GlobalVariable(handle)
+                if self.descriptor_heap_binding(module, handle).is_some() =>
+            {
+                ("(", ")")
+            }
+            _ => ("[", "]"),
+        }
+    }
+
     /// Helper method used to write global variables
     /// # Notes
     /// Always adds a newline
@@ -649,7 +757,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         let inner = &module.types[global.ty].inner;
 
         if let Some(ref binding) = global.binding {
-            if let Err(err) = self.options.resolve_resource_binding(binding) {
+            if let Err(err) = self.options.resolve_global_binding(binding, inner) {
                 log::info!(
                     "Skipping global {:?} (name {:?}) for being inaccessible: {}",
                     handle,
@@ -660,9 +768,44 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             }
         }
 
+        // Binding arrays in the descriptor heaps aren't declared, but fetched
+        // through an accessor function of the same name.
+        if let Some((heap, first_index)) = self.descriptor_heap_binding(module, handle) {
+            if self.options.shader_model < ShaderModel::V6_6 {
+                return Err(Error::ShaderModelTooLow {
+                    feature: "Descriptor heap indexing",
+                    required: ShaderModel::V6_6,
+                });
+            }
+            let base = match *inner {
+                TypeInner::BindingArray { base, .. } => base,
+                _ => unreachable!(),
+            };
+            let name = self.names[&NameKey::GlobalVariable(handle)].clone();
+            self.write_type(module, base)?;
+            writeln!(
+                self.out,
+                " {name}(uint index) {{ return {heap}[{first_index} + index]; }}"
+            )?;
+
+            // `NonUniformResourceIndex` has to wrap the index into the heap
+            // itself, so non-uniform accesses need an accessor of their own.
+            let non_uniform_name = self.namer.call(&format!("{name}_non_uniform"));
+            self.write_type(module, base)?;
+            writeln!(
+                self.out,
+                " {non_uniform_name}(uint index) {{ \
+                return {heap}[NonUniformResourceIndex({first_index} + index)]; }}"
+            )?;
+            self.non_uniform_heap_accessors
+                .insert(handle, non_uniform_name);
+            return Ok(());
+        }
+
         // https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-variable-register
         let register_ty = match global.space {
             crate::AddressSpace::Function => unreachable!("Function address space"),
+            crate::AddressSpace::TaskPayload => unreachable!("TaskPayload address space"),
             crate::AddressSpace::Private => {
                 write!(self.out, "static ")?;
                 self.write_type(module, global.ty)?;
@@ -799,6 +942,89 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             writeln!(self.out, ";")?;
         }
 
+        if let TypeInner::Image {
+            class: crate::ImageClass::External,
+            ..
+        } = *inner
+        {
+            self.write_external_texture_global(module, handle)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the second and third planes and the parameters of an external
+    /// texture global.
+    ///
+    /// `write_global` writes the global itself as the first plane. See
+    /// [`ImageClass::External`] for how the bindings are laid out.
+    ///
+    /// [`ImageClass::External`]: crate::ImageClass::External
+    fn write_external_texture_global(
+        &mut self,
+        module: &Module,
+        handle: Handle<crate::GlobalVariable>,
+    ) -> BackendResult {
+        let global = &module.global_variables[handle];
+        let binding = global
+            .binding
+            .as_ref()
+            .expect("external texture without a binding");
+        let params_ty = super::external_texture_params(module)?;
+        let name = self.names[&NameKey::GlobalVariable(handle)].clone();
+        let params_ty_name = &self.names[&NameKey::Type(params_ty)];
+
+        let [plane1, plane2, params] = binding.external_texture_bindings();
+        for (i, plane) in [plane1, plane2].iter().enumerate() {
+            // this was already resolved earlier when we started evaluating an entry point.
+            let bt = self.options.resolve_resource_binding(plane).unwrap();
+            write!(
+                self.out,
+                "Texture2D<float4> {name}_plane{} : register(t{}",
+                i + 1,
+                bt.register
+            )?;
+            if bt.space != 0 {
+                write!(self.out, ", space{}", bt.space)?;
+            }
+            writeln!(self.out, ");")?;
+        }
+
+        let bt = self.options.resolve_resource_binding(&params).unwrap();
+        write!(
+            self.out,
+            "cbuffer {name}_params : register(b{}",
+            bt.register
+        )?;
+        if bt.space != 0 {
+            write!(self.out, ", space{}", bt.space)?;
+        }
+        writeln!(self.out, ") {{ {params_ty_name} {name}_params; }}")?;
+
+        Ok(())
+    }
+
+    /// Write the arguments for the planes and parameters of the external
+    /// texture `image`, as declared by `write_external_texture_global`.
+    fn write_external_texture_arguments(
+        &mut self,
+        image: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx,
+    ) -> BackendResult {
+        let handle = match func_ctx.expressions[image] {
+            crate::Expression::GlobalVariable(handle) => handle,
+            _ => {
+                return Err(Error::Unimplemented(
+                    "external texture function arguments".to_string(),
+                ))
+            }
+        };
+        let name = &self.names[&NameKey::GlobalVariable(handle)];
+        write!(
+            self.out,
+            "{name}, {name}_plane1, {name}_plane2, {name}_params"
+        )?;
+
         Ok(())
     }
 
@@ -1063,6 +1289,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 };
                 write!(self.out, "{sampler}")?;
             }
+            TypeInner::AccelerationStructure => {
+                write!(self.out, "RaytracingAccelerationStructure")?;
+            }
+            TypeInner::RayQuery => {
+                write!(self.out, "{RAY_QUERY_TYPE}")?;
+            }
             // HLSL arrays are written as `type name[size]`
             // Current code is written arrays only as `[size]`
             // Base `type` and `name` should be written outside
@@ -1231,13 +1463,16 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 self.write_array_size(module, base, size)?;
             }
 
-            write!(self.out, " = ")?;
-            // Write the local initializer if needed
-            if let Some(init) = local.init {
-                self.write_expr(module, init, func_ctx)?;
-            } else {
-                // Zero initialize local variables
-                self.write_default_init(module, local.ty)?;
+            // Ray query objects can't be initialized, they are set up by `TraceRayInline`
+            if module.types[local.ty].inner != TypeInner::RayQuery {
+                write!(self.out, " = ")?;
+                // Write the local initializer if needed
+                if let Some(init) = local.init {
+                    self.write_expr(module, init, func_ctx)?;
+                } else {
+                    // Zero initialize local variables
+                    self.write_default_init(module, local.ty)?;
+                }
             }
 
             // Finish the local with `;` and add a newline (only for readability)
@@ -1249,7 +1484,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         }
 
         // Write the function body (statement list)
-        for sta in func.body.iter() {
+        for (sta, span) in func.body.span_iter() {
+            self.out.mark(*span);
             // The indentation should always be 1 when writing the function body
             self.write_stmt(module, sta, func_ctx, back::Level(1))?;
         }
@@ -1344,7 +1580,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             Statement::Block(ref block) => {
                 write!(self.out, "{level}")?;
                 writeln!(self.out, "{{")?;
-                for sta in block.iter() {
+                for (sta, span) in block.span_iter() {
+                    self.out.mark(*span);
                     // Increase the indentation to help with readability
                     self.write_stmt(module, sta, func_ctx, level.next())?
                 }
@@ -1362,7 +1599,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 writeln!(self.out, ") {{")?;
 
                 let l2 = level.next();
-                for sta in accept {
+                for (sta, span) in accept.span_iter() {
+                    self.out.mark(*span);
                     // Increase indentation to help with readability
                     self.write_stmt(module, sta, func_ctx, l2)?;
                 }
@@ -1372,7 +1610,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 if !reject.is_empty() {
                     writeln!(self.out, "{level}}} else {{")?;
 
-                    for sta in reject {
+                    for (sta, span) in reject.span_iter() {
+                        self.out.mark(*span);
                         // Increase indentation to help with readability
                         self.write_stmt(module, sta, func_ctx, l2)?;
                     }
@@ -1750,7 +1989,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     writeln!(self.out, "{level}while(true) {{")?;
                     writeln!(self.out, "{l2}if (!{gate_name}) {{")?;
                     let l3 = l2.next();
-                    for sta in continuing.iter() {
+                    for (sta, span) in continuing.span_iter() {
+                        self.out.mark(*span);
                         self.write_stmt(module, sta, func_ctx, l3)?;
                     }
                     if let Some(condition) = break_if {
@@ -1766,7 +2006,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     writeln!(self.out, "{level}while(true) {{")?;
                 }
 
-                for sta in body.iter() {
+                for (sta, span) in body.span_iter() {
+                    self.out.mark(*span);
                     self.write_stmt(module, sta, func_ctx, l2)?;
                 }
                 writeln!(self.out, "{level}}}")?
@@ -1969,7 +2210,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         for case in &cases[i..=end_case_idx] {
                             writeln!(self.out, "{indent_level_2}{{")?;
                             let prev_len = self.named_expressions.len();
-                            for sta in case.body.iter() {
+                            for (sta, span) in case.body.span_iter() {
+                                self.out.mark(*span);
                                 self.write_stmt(module, sta, func_ctx, indent_level_3)?;
                             }
                             // Clear all named expressions that were previously inserted by the statements in the block
@@ -1982,7 +2224,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                             writeln!(self.out, "{indent_level_2}break;")?;
                         }
                     } else {
-                        for sta in case.body.iter() {
+                        for (sta, span) in case.body.span_iter() {
+                            self.out.mark(*span);
                             self.write_stmt(module, sta, func_ctx, indent_level_2)?;
                         }
                         if !case.fall_through
@@ -1999,7 +2242,11 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
                 writeln!(self.out, "{level}}}")?
             }
-            Statement::RayQuery { .. } => unreachable!(),
+            Statement::RayQuery { query, ref fun } => {
+                self.write_ray_query_function(module, query, fun, func_ctx, level)?;
+            }
+            // Task and mesh shaders are rejected in `Writer::write`.
+            Statement::MeshFunction(_) => unreachable!(),
         }
 
         Ok(())
@@ -2244,8 +2491,22 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         _ => false,
                     };
 
+                    let non_uniform_heap_accessor = match func_ctx.expressions[base] {
+                        Expression::GlobalVariable(handle) if non_uniform_qualifier => {
+                            self.non_uniform_heap_accessors.get(&handle).cloned()
+                        }
+                        _ => None,
+                    };
+                    if let Some(name) = non_uniform_heap_accessor {
+                        write!(self.out, "{name}(")?;
+                        self.write_expr(module, index, func_ctx)?;
+                        write!(self.out, ")")?;
+                        return Ok(());
+                    }
+
+                    let (open, close) = self.index_brackets(module, base, func_ctx);
                     self.write_expr(module, base, func_ctx)?;
-                    write!(self.out, "[")?;
+                    write!(self.out, "{open}")?;
                     if non_uniform_qualifier {
                         write!(self.out, "NonUniformResourceIndex(")?;
                     }
@@ -2253,7 +2514,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     if non_uniform_qualifier {
                         write!(self.out, ")")?;
                     }
-                    write!(self.out, "]")?;
+                    write!(self.out, "{close}")?;
                 }
             }
             Expression::AccessIndex { base, index } => {
@@ -2349,6 +2610,11 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     }
 
                     self.write_expr(module, base, func_ctx)?;
+                    if let TypeInner::BindingArray { .. } = *resolved {
+                        let (open, close) = self.index_brackets(module, base, func_ctx);
+                        write!(self.out, "{open}{index}{close}")?;
+                        return Ok(());
+                    }
                     write_access(self, resolved, base_ty_handle, index)?;
                 }
             }
@@ -2357,6 +2623,30 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 let name = &self.names[&key];
                 write!(self.out, "{name}")?;
             }
+            Expression::ImageSample {
+                image,
+                sampler,
+                coordinate,
+                clamp_to_edge: true,
+                ..
+            } => {
+                if let TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = *func_ctx.resolve_type(image, &module.types)
+                {
+                    write!(self.out, "{SAMPLE_EXTERNAL_FUNCTION}(")?;
+                    self.write_external_texture_arguments(image, func_ctx)?;
+                } else {
+                    write!(self.out, "{SAMPLE_CLAMP_TO_EDGE_FUNCTION}(")?;
+                    self.write_expr(module, image, func_ctx)?;
+                }
+                write!(self.out, ", ")?;
+                self.write_expr(module, sampler, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, coordinate, func_ctx)?;
+                write!(self.out, ")")?;
+            }
             Expression::ImageSample {
                 image,
                 sampler,
@@ -2366,6 +2656,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 offset,
                 level,
                 depth_ref,
+                clamp_to_edge: false,
             } => {
                 use crate::SampleLevel as Sl;
                 const COMPONENTS: [&str; 4] = ["", "Green", "Blue", "Alpha"];
@@ -2432,6 +2723,27 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 write!(self.out, ")")?;
             }
             Expression::ImageQuery { image, query } => {
+                if let TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = *func_ctx.resolve_type(image, &module.types)
+                {
+                    // The only valid query is the size, which is in the parameters.
+                    let handle = match func_ctx.expressions[image] {
+                        Expression::GlobalVariable(handle) => handle,
+                        _ => {
+                            return Err(Error::Unimplemented(
+                                "external texture function arguments".to_string(),
+                            ))
+                        }
+                    };
+                    let params_ty = super::external_texture_params(module)?;
+                    let name = &self.names[&NameKey::GlobalVariable(handle)];
+                    let size = &self.names[&NameKey::StructMember(params_ty, 1)];
+                    write!(self.out, "{name}_params.{size}")?;
+                    return Ok(());
+                }
+
                 // use wrapped image query function
                 if let TypeInner::Image {
                     dim,
@@ -2464,6 +2776,19 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 sample,
                 level,
             } => {
+                if let TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = *func_ctx.resolve_type(image, &module.types)
+                {
+                    write!(self.out, "{LOAD_EXTERNAL_FUNCTION}(")?;
+                    self.write_external_texture_arguments(image, func_ctx)?;
+                    write!(self.out, ", uint2(")?;
+                    self.write_expr(module, coordinate, func_ctx)?;
+                    write!(self.out, "))")?;
+                    return Ok(());
+                }
+
                 // https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-to-load
                 self.write_expr(module, image, func_ctx)?;
                 write!(self.out, ".Load(")?;
@@ -2632,6 +2957,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Unpack2x16unorm,
                     Unpack4x8snorm,
                     Unpack4x8unorm,
+                    Dot4x8Packed { signed: bool },
+                    QuantizeToF16,
+                    Pack4x8 { signed: bool, clamp: bool },
+                    Unpack4x8 { signed: bool },
                     Regular(&'static str),
                     MissingIntOverload(&'static str),
                     MissingIntReturnType(&'static str),
@@ -2679,6 +3008,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Mf::Pow => Function::Regular("pow"),
                     // geometry
                     Mf::Dot => Function::Regular("dot"),
+                    Mf::Dot4I8Packed => Function::Dot4x8Packed { signed: true },
+                    Mf::Dot4U8Packed => Function::Dot4x8Packed { signed: false },
                     //Mf::Outer => ,
                     Mf::Cross => Function::Regular("cross"),
                     Mf::Distance => Function::Regular("distance"),
@@ -2698,6 +3029,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     //Mf::Inverse =>,
                     Mf::Transpose => Function::Regular("transpose"),
                     Mf::Determinant => Function::Regular("determinant"),
+                    Mf::QuantizeToF16 => Function::QuantizeToF16,
                     // bits
                     Mf::CountTrailingZeros => Function::CountTrailingZeros,
                     Mf::CountLeadingZeros => Function::CountLeadingZeros,
@@ -2713,12 +3045,30 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Mf::Pack2x16unorm => Function::Pack2x16unorm,
                     Mf::Pack4x8snorm => Function::Pack4x8snorm,
                     Mf::Pack4x8unorm => Function::Pack4x8unorm,
+                    Mf::Pack4xI8 => Function::Pack4x8 {
+                        signed: true,
+                        clamp: false,
+                    },
+                    Mf::Pack4xU8 => Function::Pack4x8 {
+                        signed: false,
+                        clamp: false,
+                    },
+                    Mf::Pack4xI8Clamp => Function::Pack4x8 {
+                        signed: true,
+                        clamp: true,
+                    },
+                    Mf::Pack4xU8Clamp => Function::Pack4x8 {
+                        signed: false,
+                        clamp: true,
+                    },
                     // Data Unpacking
                     Mf::Unpack2x16float => Function::Unpack2x16float,
                     Mf::Unpack2x16snorm => Function::Unpack2x16snorm,
                     Mf::Unpack2x16unorm => Function::Unpack2x16unorm,
                     Mf::Unpack4x8snorm => Function::Unpack4x8snorm,
                     Mf::Unpack4x8unorm => Function::Unpack4x8unorm,
+                    Mf::Unpack4xI8 => Function::Unpack4x8 { signed: true },
+                    Mf::Unpack4xU8 => Function::Unpack4x8 { signed: false },
                     _ => return Err(Error::Unimplemented(format!("write_expr_math {fun:?}"))),
                 };
 
@@ -2806,6 +3156,91 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                             write!(self.out, "))")?;
                         }
                     }
+                    Function::Dot4x8Packed { signed } => {
+                        if self.options.shader_model >= ShaderModel::V6_4 {
+                            let (fun_name, zero) = match signed {
+                                true => ("dot4add_i8packed", "0"),
+                                false => ("dot4add_u8packed", "0u"),
+                            };
+                            write!(self.out, "{fun_name}(")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, ", ")?;
+                            self.write_expr(module, arg1.unwrap(), func_ctx)?;
+                            write!(self.out, ", {zero})")?;
+                        } else {
+                            // Extract each byte with shifts, sign-extending
+                            // it for the signed variant.
+                            write!(self.out, "(")?;
+                            for index in 0..4 {
+                                if index != 0 {
+                                    write!(self.out, " + ")?;
+                                }
+                                for (i, operand) in [arg, arg1.unwrap()].into_iter().enumerate() {
+                                    if i != 0 {
+                                        write!(self.out, " * ")?;
+                                    }
+                                    if signed {
+                                        write!(self.out, "((int(")?;
+                                        self.write_expr(module, operand, func_ctx)?;
+                                        write!(self.out, ") << {}) >> 24)", 24 - index * 8)?;
+                                    } else {
+                                        write!(self.out, "((")?;
+                                        self.write_expr(module, operand, func_ctx)?;
+                                        write!(self.out, " >> {}) & 0xFF)", index * 8)?;
+                                    }
+                                }
+                            }
+                            write!(self.out, ")")?;
+                        }
+                    }
+                    Function::QuantizeToF16 => {
+                        write!(self.out, "f16tof32(f32tof16(")?;
+                        self.write_expr(module, arg, func_ctx)?;
+                        write!(self.out, "))")?;
+                    }
+                    Function::Pack4x8 { signed, clamp } => {
+                        let (clamp_start, clamp_end) = match (signed, clamp) {
+                            (true, true) => ("clamp(", ", -128, 127)"),
+                            (false, true) => ("min(", ", 255u)"),
+                            (_, false) => ("", ""),
+                        };
+                        write!(self.out, "uint(")?;
+                        for index in 0..4 {
+                            if index != 0 {
+                                write!(self.out, " | ")?;
+                            }
+                            write!(self.out, "({clamp_start}")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, "[{index}]{clamp_end} & 0xFF)")?;
+                            if index != 0 {
+                                write!(self.out, " << {}", index * 8)?;
+                            }
+                        }
+                        write!(self.out, ")")?;
+                    }
+                    Function::Unpack4x8 { signed } => {
+                        if signed {
+                            write!(self.out, "(int4(")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, " << 24, ")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, " << 16, ")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, " << 8, ")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, ") >> 24)")?;
+                        } else {
+                            write!(self.out, "(uint4(")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, ", ")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, " >> 8, ")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, " >> 16, ")?;
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, " >> 24) & 0xFF)")?;
+                        }
+                    }
                     Function::Pack2x16float => {
                         write!(self.out, "(f32tof16(")?;
                         self.write_expr(module, arg, func_ctx)?;
@@ -3152,8 +3587,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 self.write_expr(module, reject, func_ctx)?;
                 write!(self.out, ")")?
             }
-            // Not supported yet
-            Expression::RayQueryGetIntersection { .. } => unreachable!(),
+            Expression::RayQueryGetIntersection { query, committed } => {
+                self.write_ray_query_get_intersection(module, query, committed, func_ctx)?;
+            }
             // Nothing to do here, since call expression already cached
             Expression::CallResult(_)
             | Expression::AtomicResult { .. }
@@ -3219,7 +3655,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
     }
 
     fn write_barrier(&mut self, barrier: crate::Barrier, level: back::Level) -> BackendResult {
-        if barrier.contains(crate::Barrier::STORAGE) {
+        // HLSL has no barrier specific to textures; storage textures are
+        // UAVs in device memory, just like storage buffers.
+        if barrier.intersects(crate::Barrier::STORAGE | crate::Barrier::TEXTURE) {
             writeln!(self.out, "{level}DeviceMemoryBarrierWithGroupSync();")?;
         }
         if barrier.contains(crate::Barrier::WORK_GROUP) {
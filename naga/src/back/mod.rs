@@ -11,6 +11,8 @@ pub mod glsl;
 pub mod hlsl;
 #[cfg(feature = "msl-out")]
 pub mod msl;
+#[cfg(feature = "rust-out")]
+pub mod rust;
 #[cfg(feature = "spv-out")]
 pub mod spv;
 #[cfg(feature = "wgsl-out")]
@@ -271,3 +273,110 @@ enum RayIntersectionType {
     Triangle = 1,
     BoundingBox = 4,
 }
+
+/// A mapping from positions in a text backend's output to the [`Span`]s of
+/// the source code they were generated from.
+///
+/// The text backends record the span of each function and statement they
+/// write at the position in the output where its code begins. This can be
+/// used to relate a driver compiler's error messages, or a debugger's view of
+/// the generated code, back to the original shader source.
+///
+/// Spans are only recorded if the front end provided them.
+///
+/// [`Span`]: crate::Span
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourceMap {
+    entries: Vec<SourceMapEntry>,
+}
+
+/// The start of the code generated for one [`Span`] of source code.
+///
+/// [`Span`]: crate::Span
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceMapEntry {
+    /// 1-based line number in the output.
+    pub line: u32,
+    /// 1-based column in the output, counted in characters.
+    pub column: u32,
+    /// The span of the source code that the output starting here was
+    /// generated from.
+    pub span: crate::Span,
+}
+
+impl SourceMap {
+    /// Return the entries in this map, in the order they appear in the output.
+    pub fn entries(&self) -> &[SourceMapEntry] {
+        &self.entries
+    }
+
+    /// Return the span of the source code that the output at `line` and
+    /// `column` was generated from.
+    ///
+    /// This is the span of the last entry at or before that position, or
+    /// `None` if there is no such entry.
+    pub fn lookup(&self, line: u32, column: u32) -> Option<crate::Span> {
+        let index = self
+            .entries
+            .partition_point(|entry| (entry.line, entry.column) <= (line, column));
+        index.checked_sub(1).map(|index| self.entries[index].span)
+    }
+}
+
+/// A [`Write`] adapter that keeps track of the output position, to build a
+/// [`SourceMap`].
+///
+/// [`Write`]: std::fmt::Write
+struct SourceMapWriter<W> {
+    inner: W,
+    /// The 1-based line number of the next character written.
+    line: u32,
+    /// The 1-based column of the next character written.
+    column: u32,
+    map: SourceMap,
+}
+
+impl<W> SourceMapWriter<W> {
+    const fn new(inner: W) -> Self {
+        SourceMapWriter {
+            inner,
+            line: 1,
+            column: 1,
+            map: SourceMap {
+                entries: Vec::new(),
+            },
+        }
+    }
+
+    /// Record that the code written next was generated from `span`.
+    fn mark(&mut self, span: crate::Span) {
+        if span.is_defined() {
+            self.map.entries.push(SourceMapEntry {
+                line: self.line,
+                column: self.column,
+                span,
+            });
+        }
+    }
+
+    const fn source_map(&self) -> &SourceMap {
+        &self.map
+    }
+
+    fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: std::fmt::Write> std::fmt::Write for SourceMapWriter<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        match s.rfind('\n') {
+            Some(last) => {
+                self.line += s.matches('\n').count() as u32;
+                self.column = s[last + 1..].chars().count() as u32 + 1;
+            }
+            None => self.column += s.chars().count() as u32,
+        }
+        self.inner.write_str(s)
+    }
+}
@@ -339,4 +339,7 @@ pub const RESERVED: &[&str] = &[
     "DefaultConstructible",
     super::writer::FREXP_FUNCTION,
     super::writer::MODF_FUNCTION,
+    super::writer::SAMPLE_CLAMP_TO_EDGE_FUNCTION,
+    super::writer::SAMPLE_EXTERNAL_FUNCTION,
+    super::writer::LOAD_EXTERNAL_FUNCTION,
 ];
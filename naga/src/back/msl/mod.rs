@@ -8,6 +8,14 @@ from SPIR-V's descriptor sets, we require a separate mapping provided in the opt
 This mapping may have one or more resource end points for each descriptor set + index
 pair.
 
+## Argument buffers
+
+Alternatively, whole bind groups can be passed as [argument buffers][ab] by
+listing them in [`EntryPointResources::argument_buffers`]. Each such group
+becomes a struct whose members are addressed by `[[id(n)]]` rather than by
+slot, so the group is bound with a single buffer regardless of how many
+resources it holds. Argument buffers require MSL 2.0.
+
 ## Entry points
 
 Even though MSL and our IR appear to be similar in that the entry points in both can
@@ -24,6 +32,7 @@ For the result type, if it's a structure, we re-compose it with a temporary valu
 holding the result.
 
 [msl]: https://developer.apple.com/metal/Metal-Shading-Language-Specification.pdf
+[ab]: https://developer.apple.com/documentation/metal/buffers/about_argument_buffers
 */
 
 use crate::{arena::Handle, proc::index, valid::ModuleInfo};
@@ -62,6 +71,41 @@ pub struct BindTarget {
 // Using `BTreeMap` instead of `HashMap` so that we can hash itself.
 pub type BindingMap = std::collections::BTreeMap<crate::ResourceBinding, BindTarget>;
 
+/// Where a resource lives within an [`ArgumentBuffer`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+#[cfg_attr(any(feature = "serialize", feature = "deserialize"), serde(default))]
+pub struct ArgumentBufferTarget {
+    /// The `[[id(n)]]` of the resource. A binding array of size `n` occupies
+    /// the `n` consecutive ids starting here.
+    pub id: u32,
+    /// If the binding is an unsized binding array, this overrides the size.
+    pub binding_array_size: Option<u32>,
+    /// For buffers bound with a dynamic offset, the index of that offset in
+    /// the group's [`ArgumentBuffer::dynamic_offsets_buffer`].
+    pub dynamic_offset: Option<u32>,
+}
+
+/// A bind group passed to the entry point as a Metal argument buffer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+#[cfg_attr(any(feature = "serialize", feature = "deserialize"), serde(default))]
+pub struct ArgumentBuffer {
+    /// The slot of the argument buffer itself.
+    pub buffer: Slot,
+    /// The resources of the group, indexed by binding.
+    pub resources: std::collections::BTreeMap<u32, ArgumentBufferTarget>,
+    /// The slot of a buffer that contains an array of `u32`, holding the
+    /// byte offsets to apply to buffers with a
+    /// [`dynamic_offset`](ArgumentBufferTarget::dynamic_offset).
+    pub dynamic_offsets_buffer: Option<Slot>,
+}
+
+// Using `BTreeMap` instead of `HashMap` so that we can hash itself.
+pub type ArgumentBufferMap = std::collections::BTreeMap<u32, ArgumentBuffer>;
+
 #[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -69,6 +113,12 @@ pub type BindingMap = std::collections::BTreeMap<crate::ResourceBinding, BindTar
 pub struct EntryPointResources {
     pub resources: BindingMap,
 
+    /// Bind groups to pass as argument buffers, indexed by group.
+    ///
+    /// Resources in these groups are looked up here rather than in
+    /// [`resources`](Self::resources).
+    pub argument_buffers: ArgumentBufferMap,
+
     pub push_constant_buffer: Option<Slot>,
 
     /// The slot of a buffer that contains an array of `u32`,
@@ -143,6 +193,8 @@ pub enum Error {
     UnsupportedArrayOfType(Handle<crate::Type>),
     #[error("ray tracing is not supported prior to MSL 2.3")]
     UnsupportedRayTracing,
+    #[error("argument buffers are not supported prior to MSL 2.0")]
+    UnsupportedArgumentBuffers,
 }
 
 #[derive(Clone, Debug, PartialEq, thiserror::Error)]
@@ -157,6 +209,10 @@ pub enum EntryPointError {
     MissingPushConstants,
     #[error("mapping for sizes buffer is missing")]
     MissingSizesBuffer,
+    #[error("mapping for the dynamic offsets buffer of group {0} is missing")]
+    MissingDynamicOffsetsBuffer(u32),
+    #[error("{0:?} shaders are not supported")]
+    UnsupportedStage(crate::ShaderStage),
 }
 
 /// Points in the MSL code where we might emit a pipeline input or output.
@@ -330,6 +386,11 @@ impl Options {
             .and_then(|res| res.resources.get(res_binding))
     }
 
+    fn get_argument_buffer(&self, ep: &crate::EntryPoint, group: u32) -> Option<&ArgumentBuffer> {
+        self.get_entry_point_resources(ep)
+            .and_then(|res| res.argument_buffers.get(&group))
+    }
+
     fn resolve_resource_binding(
         &self,
         ep: &crate::EntryPoint,
@@ -437,9 +498,13 @@ impl ResolvedBinding {
                     Bi::WorkGroupId => "threadgroup_position_in_grid",
                     Bi::WorkGroupSize => "dispatch_threads_per_threadgroup",
                     Bi::NumWorkGroups => "threadgroups_per_grid",
-                    Bi::CullDistance | Bi::ViewIndex => {
-                        return Err(Error::UnsupportedBuiltIn(built_in))
-                    }
+                    Bi::CullDistance
+                    | Bi::ViewIndex
+                    | Bi::MeshTaskSize
+                    | Bi::CullPrimitive
+                    | Bi::PointIndex
+                    | Bi::LineIndices
+                    | Bi::TriangleIndices => return Err(Error::UnsupportedBuiltIn(built_in)),
                 };
                 write!(out, "{name}")?;
             }
@@ -34,6 +34,9 @@ const RAY_QUERY_FUN_MAP_INTERSECTION: &str = "_map_intersection_type";
 
 pub(crate) const MODF_FUNCTION: &str = "naga_modf";
 pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
+pub(crate) const SAMPLE_CLAMP_TO_EDGE_FUNCTION: &str = "nagaTextureSampleBaseClampToEdge";
+pub(crate) const SAMPLE_EXTERNAL_FUNCTION: &str = "nagaTextureSampleExternal";
+pub(crate) const LOAD_EXTERNAL_FUNCTION: &str = "nagaTextureLoadExternal";
 
 /// Write the Metal name for a Naga numeric type: scalar, vector, or matrix.
 ///
@@ -192,6 +195,10 @@ impl<'a> Display for TypeContext<'a> {
                         };
                         ("texture", "", format.into(), access)
                     }
+                    // This is the type of each plane.
+                    crate::ImageClass::External => {
+                        ("texture", "", crate::ScalarKind::Float, "sample")
+                    }
                 };
                 let base_name = crate::Scalar { kind, width: 4 }.to_msl_name();
                 let array_str = if arrayed { "_array" } else { "" };
@@ -243,8 +250,15 @@ struct TypedGlobalVariable<'a> {
 
 impl<'a> TypedGlobalVariable<'a> {
     fn try_fmt<W: Write>(&self, out: &mut W) -> BackendResult {
-        let var = &self.module.global_variables[self.handle];
         let name = &self.names[&NameKey::GlobalVariable(self.handle)];
+        self.try_fmt_type(out, "&")?;
+        Ok(write!(out, " {name}")?)
+    }
+
+    /// Write the type of the variable, followed by `indirection` if the
+    /// variable is accessed through a reference or pointer.
+    fn try_fmt_type<W: Write>(&self, out: &mut W, indirection: &str) -> BackendResult {
+        let var = &self.module.global_variables[self.handle];
 
         let storage_access = match var.space {
             crate::AddressSpace::Storage { access } => access,
@@ -274,36 +288,45 @@ impl<'a> TypedGlobalVariable<'a> {
             first_time: false,
         };
 
-        let (space, access, reference) = match var.space.to_msl_name() {
-            Some(space) if self.reference => {
-                let access = if var.space.needs_access_qualifier()
-                    && !self.usage.contains(valid::GlobalUse::WRITE)
-                {
-                    "const"
-                } else {
-                    ""
-                };
-                (space, access, "&")
-            }
-            _ => ("", "", ""),
+        let (space, access, indirection) = match self.address_space() {
+            Some((space, access)) => (space, access, indirection),
+            None => ("", "", ""),
         };
 
         Ok(write!(
             out,
-            "{}{}{}{}{}{} {}",
+            "{}{}{}{}{}{}",
             space,
             if space.is_empty() { "" } else { " " },
             ty_name,
             if access.is_empty() { "" } else { " " },
             access,
-            reference,
-            name,
+            indirection,
         )?)
     }
+
+    /// The address space and access qualifier of the variable, if it is
+    /// accessed through a reference.
+    fn address_space(&self) -> Option<(&'static str, &'static str)> {
+        let var = &self.module.global_variables[self.handle];
+        match var.space.to_msl_name() {
+            Some(space) if self.reference => {
+                let access = if var.space.needs_access_qualifier()
+                    && !self.usage.contains(valid::GlobalUse::WRITE)
+                {
+                    "const"
+                } else {
+                    ""
+                };
+                Some((space, access))
+            }
+            _ => None,
+        }
+    }
 }
 
 pub struct Writer<W> {
-    out: W,
+    out: back::SourceMapWriter<W>,
     names: FastHashMap<NameKey, String>,
     named_expressions: crate::NamedExpressions,
     /// Set of expressions that need to be baked to avoid unnecessary repetition in output
@@ -379,6 +402,19 @@ fn should_pack_struct_member(
     }
 }
 
+/// Returns true if `ty` is an [`External`] image.
+///
+/// [`External`]: crate::ImageClass::External
+fn is_external_texture(ty: Handle<crate::Type>, arena: &crate::UniqueArena<crate::Type>) -> bool {
+    matches!(
+        arena[ty].inner,
+        crate::TypeInner::Image {
+            class: crate::ImageClass::External,
+            ..
+        }
+    )
+}
+
 fn needs_array_length(ty: Handle<crate::Type>, arena: &crate::UniqueArena<crate::Type>) -> bool {
     match arena[ty].inner {
         crate::TypeInner::Struct { ref members, .. } => {
@@ -412,7 +448,8 @@ impl crate::AddressSpace {
             | Self::Private
             | Self::WorkGroup
             | Self::PushConstant
-            | Self::Handle => true,
+            | Self::Handle
+            | Self::TaskPayload => true,
             Self::Function => false,
         }
     }
@@ -426,7 +463,7 @@ impl crate::AddressSpace {
             // and that should be OK.
             Self::Storage { .. } => true,
             // These should always be read-write.
-            Self::Private | Self::WorkGroup => false,
+            Self::Private | Self::WorkGroup | Self::TaskPayload => false,
             // These translate to `constant` address space, no need for qualifiers.
             Self::Uniform | Self::PushConstant => false,
             // Not applicable.
@@ -441,6 +478,7 @@ impl crate::AddressSpace {
             Self::Storage { .. } => Some("device"),
             Self::Private | Self::Function => Some("thread"),
             Self::WorkGroup => Some("threadgroup"),
+            Self::TaskPayload => Some("object_data"),
         }
     }
 }
@@ -587,7 +625,7 @@ impl<W: Write> Writer<W> {
     /// Creates a new `Writer` instance.
     pub fn new(out: W) -> Self {
         Writer {
-            out,
+            out: back::SourceMapWriter::new(out),
             names: FastHashMap::default(),
             named_expressions: Default::default(),
             need_bake_expressions: Default::default(),
@@ -604,7 +642,14 @@ impl<W: Write> Writer<W> {
     // See https://github.com/rust-lang/rust-clippy/issues/4979.
     #[allow(clippy::missing_const_for_fn)]
     pub fn finish(self) -> W {
-        self.out
+        self.out.into_inner()
+    }
+
+    /// Return the [`SourceMap`] for the output written so far.
+    ///
+    /// [`SourceMap`]: back::SourceMap
+    pub const fn source_map(&self) -> &back::SourceMap {
+        self.out.source_map()
     }
 
     fn put_call_parameters(
@@ -744,6 +789,84 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Return the name of the external texture global `image`.
+    fn external_texture_name(
+        &self,
+        image: Handle<crate::Expression>,
+        context: &ExpressionContext,
+    ) -> Result<String, Error> {
+        match context.function.expressions[image] {
+            crate::Expression::GlobalVariable(handle) => {
+                Ok(self.names[&NameKey::GlobalVariable(handle)].clone())
+            }
+            _ => Err(Error::FeatureNotImplemented(
+                "external texture function arguments".to_string(),
+            )),
+        }
+    }
+
+    /// Write the declarations of the second and third planes and the
+    /// parameters of the external texture global `handle`, which are passed
+    /// alongside the global itself, separated by `separator`.
+    ///
+    /// For entry points, `ep` supplies the binding of each argument.
+    fn put_external_texture_extra_arguments(
+        &mut self,
+        module: &crate::Module,
+        handle: Handle<crate::GlobalVariable>,
+        ep: Option<(&Options, &crate::EntryPoint)>,
+        separator: &str,
+    ) -> BackendResult {
+        let var = &module.global_variables[handle];
+        let params_ty = module
+            .special_types
+            .external_texture_params
+            .ok_or(Error::Validation)?;
+        let name = &self.names[&NameKey::GlobalVariable(handle)];
+        let params_ty_name = &self.names[&NameKey::Type(params_ty)];
+        let bindings = var
+            .binding
+            .as_ref()
+            .map(|br| br.external_texture_bindings());
+
+        for i in 0..3 {
+            if i != 0 {
+                write!(self.out, "{separator}")?;
+            }
+            if i < 2 {
+                write!(
+                    self.out,
+                    "{NAMESPACE}::texture2d<float, {NAMESPACE}::access::sample> {name}_plane{}",
+                    i + 1
+                )?;
+            } else {
+                write!(self.out, "constant {params_ty_name}& {name}_params")?;
+            }
+            if let (Some((options, ep)), Some(bindings)) = (ep, bindings.as_ref()) {
+                // the resolves have already been checked for `!fake_missing_bindings` case
+                if let Ok(resolved) = options.resolve_resource_binding(ep, &bindings[i]) {
+                    resolved.try_fmt(&mut self.out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the planes and parameters that the external texture `image` is
+    /// lowered into, as arguments to one of the external texture functions.
+    fn put_external_texture_arguments(
+        &mut self,
+        image: Handle<crate::Expression>,
+        context: &ExpressionContext,
+    ) -> BackendResult {
+        let name = self.external_texture_name(image, context)?;
+        write!(
+            self.out,
+            "{name}, {name}_plane1, {name}_plane2, {name}_params"
+        )?;
+        Ok(())
+    }
+
     fn put_image_sample_level(
         &mut self,
         image: Handle<crate::Expression>,
@@ -1194,6 +1317,41 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Emit code for a dot product of two `u32`s holding four 8-bit
+    /// components each.
+    ///
+    /// Like [`put_dot_product`], this uses each argument once per component,
+    /// so the arguments must be baked.
+    ///
+    /// [`put_dot_product`]: Writer::put_dot_product
+    fn put_dot_4x8_packed(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        arg1: Handle<crate::Expression>,
+        signed: bool,
+        context: &ExpressionContext,
+    ) -> BackendResult {
+        let (scalar, packed) = if signed {
+            ("int", "char4")
+        } else {
+            ("uint", "uchar4")
+        };
+
+        write!(self.out, "(")?;
+        for component in back::COMPONENTS {
+            write!(self.out, " + {scalar}(as_type<{NAMESPACE}::{packed}>(")?;
+            self.put_expression(arg, context, true)?;
+            write!(
+                self.out,
+                ").{component}) * {scalar}(as_type<{NAMESPACE}::{packed}>("
+            )?;
+            self.put_expression(arg1, context, true)?;
+            write!(self.out, ").{component})")?;
+        }
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
     /// Emit code for the sign(i32) expression.
     ///
     fn put_isign(
@@ -1466,6 +1624,30 @@ impl<W: Write> Writer<W> {
                 write!(self.out, "{name}")?;
             }
             crate::Expression::Load { pointer } => self.put_load(pointer, context, is_scoped)?,
+            crate::Expression::ImageSample {
+                image,
+                sampler,
+                coordinate,
+                clamp_to_edge: true,
+                ..
+            } => {
+                if let crate::TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = *context.resolve_type(image)
+                {
+                    write!(self.out, "{SAMPLE_EXTERNAL_FUNCTION}(")?;
+                    self.put_external_texture_arguments(image, context)?;
+                } else {
+                    write!(self.out, "{SAMPLE_CLAMP_TO_EDGE_FUNCTION}(")?;
+                    self.put_expression(image, context, true)?;
+                }
+                write!(self.out, ", ")?;
+                self.put_expression(sampler, context, true)?;
+                write!(self.out, ", ")?;
+                self.put_expression(coordinate, context, true)?;
+                write!(self.out, ")")?;
+            }
             crate::Expression::ImageSample {
                 image,
                 sampler,
@@ -1475,6 +1657,7 @@ impl<W: Write> Writer<W> {
                 offset,
                 level,
                 depth_ref,
+                clamp_to_edge: false,
             } => {
                 let main_op = match gather {
                     Some(_) => "gather",
@@ -1533,6 +1716,19 @@ impl<W: Write> Writer<W> {
                 sample,
                 level,
             } => {
+                if let crate::TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = *context.resolve_type(image)
+                {
+                    write!(self.out, "{LOAD_EXTERNAL_FUNCTION}(")?;
+                    self.put_external_texture_arguments(image, context)?;
+                    write!(self.out, ", {NAMESPACE}::uint2(")?;
+                    self.put_expression(coordinate, context, true)?;
+                    write!(self.out, "))")?;
+                    return Ok(());
+                }
+
                 let address = TexelAddress {
                     coordinate,
                     array_index,
@@ -1544,6 +1740,25 @@ impl<W: Write> Writer<W> {
             //Note: for all the queries, the signed integers are expected,
             // so a conversion is needed.
             crate::Expression::ImageQuery { image, query } => match query {
+                // The size of an external texture is in its parameters.
+                crate::ImageQuery::Size { level: None }
+                    if matches!(
+                        *context.resolve_type(image),
+                        crate::TypeInner::Image {
+                            class: crate::ImageClass::External,
+                            ..
+                        }
+                    ) =>
+                {
+                    let name = self.external_texture_name(image, context)?;
+                    let params_ty = context
+                        .module
+                        .special_types
+                        .external_texture_params
+                        .ok_or(Error::Validation)?;
+                    let size = &self.names[&NameKey::StructMember(params_ty, 1)];
+                    write!(self.out, "{name}_params.{size}")?;
+                }
                 crate::ImageQuery::Size { level } => {
                     self.put_image_size_query(
                         image,
@@ -1764,6 +1979,10 @@ impl<W: Write> Writer<W> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let signed = fun == Mf::Dot4I8Packed;
+                        return self.put_dot_4x8_packed(arg, arg1.unwrap(), signed, context);
+                    }
                     Mf::Outer => return Err(Error::UnsupportedCall(format!("{fun:?}"))),
                     Mf::Cross => "cross",
                     Mf::Distance => "distance",
@@ -1789,6 +2008,7 @@ impl<W: Write> Writer<W> {
                     Mf::Inverse => return Err(Error::UnsupportedCall(format!("{fun:?}"))),
                     Mf::Transpose => "transpose",
                     Mf::Determinant => "determinant",
+                    Mf::QuantizeToF16 => "",
                     // bits
                     Mf::CountTrailingZeros => "ctz",
                     Mf::CountLeadingZeros => "clz",
@@ -1804,12 +2024,14 @@ impl<W: Write> Writer<W> {
                     Mf::Pack2x16snorm => "pack_float_to_snorm2x16",
                     Mf::Pack2x16unorm => "pack_float_to_unorm2x16",
                     Mf::Pack2x16float => "",
+                    Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp => "",
                     // data unpacking
                     Mf::Unpack4x8snorm => "unpack_snorm4x8_to_float",
                     Mf::Unpack4x8unorm => "unpack_unorm4x8_to_float",
                     Mf::Unpack2x16snorm => "unpack_snorm2x16_to_float",
                     Mf::Unpack2x16unorm => "unpack_unorm2x16_to_float",
                     Mf::Unpack2x16float => "",
+                    Mf::Unpack4xI8 | Mf::Unpack4xU8 => "",
                 };
 
                 match fun {
@@ -1891,6 +2113,55 @@ impl<W: Write> Writer<W> {
                     write!(self.out, "as_type<uint>(half2(")?;
                     self.put_expression(arg, context, false)?;
                     write!(self.out, "))")?;
+                } else if fun == Mf::QuantizeToF16 {
+                    match *context.resolve_type(arg) {
+                        crate::TypeInner::Vector { size, .. } => {
+                            let size = back::vector_size_str(size);
+                            write!(
+                                self.out,
+                                "{NAMESPACE}::float{size}({NAMESPACE}::half{size}("
+                            )?;
+                        }
+                        _ => write!(self.out, "float(half(")?,
+                    }
+                    self.put_expression(arg, context, false)?;
+                    write!(self.out, "))")?;
+                } else if let Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp =
+                    fun
+                {
+                    let packed = match fun {
+                        Mf::Pack4xI8 | Mf::Pack4xI8Clamp => "char4",
+                        _ => "uchar4",
+                    };
+                    write!(self.out, "as_type<uint>({NAMESPACE}::{packed}(")?;
+                    match fun {
+                        Mf::Pack4xI8Clamp => {
+                            write!(self.out, "{NAMESPACE}::clamp(")?;
+                            self.put_expression(arg, context, false)?;
+                            write!(
+                                self.out,
+                                ", {NAMESPACE}::int4(-128), {NAMESPACE}::int4(127))"
+                            )?;
+                        }
+                        Mf::Pack4xU8Clamp => {
+                            write!(self.out, "{NAMESPACE}::min(")?;
+                            self.put_expression(arg, context, false)?;
+                            write!(self.out, ", {NAMESPACE}::uint4(255u))")?;
+                        }
+                        _ => self.put_expression(arg, context, false)?,
+                    }
+                    write!(self.out, "))")?;
+                } else if let Mf::Unpack4xI8 | Mf::Unpack4xU8 = fun {
+                    let (unpacked, packed) = match fun {
+                        Mf::Unpack4xI8 => ("int4", "char4"),
+                        _ => ("uint4", "uchar4"),
+                    };
+                    write!(
+                        self.out,
+                        "{NAMESPACE}::{unpacked}(as_type<{NAMESPACE}::{packed}>("
+                    )?;
+                    self.put_expression(arg, context, false)?;
+                    write!(self.out, "))")?;
                 } else if fun == Mf::Radians {
                     write!(self.out, "((")?;
                     self.put_expression(arg, context, false)?;
@@ -2511,6 +2782,11 @@ impl<W: Write> Writer<W> {
                             }
                         }
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed => {
+                        // See `put_dot_4x8_packed`.
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
                     crate::MathFunction::FindMsb => {
                         self.need_bake_expressions.insert(arg);
                     }
@@ -2627,7 +2903,7 @@ impl<W: Write> Writer<W> {
     fn put_block(
         &mut self,
         level: back::Level,
-        statements: &[crate::Statement],
+        statements: &crate::Block,
         context: &StatementContext,
     ) -> BackendResult {
         // Add to the set in order to track the stack size.
@@ -2636,8 +2912,9 @@ impl<W: Write> Writer<W> {
         self.put_block_stack_pointers
             .insert(&level as *const _ as *const ());
 
-        for statement in statements {
+        for (statement, span) in statements.span_iter() {
             log::trace!("statement[{}] {:?}", level.0, statement);
+            self.out.mark(*span);
             match *statement {
                 crate::Statement::Emit(ref range) => {
                     for handle in range.clone() {
@@ -2866,6 +3143,9 @@ impl<W: Write> Writer<W> {
                                 separate = true;
                             }
                             write!(self.out, "{name}")?;
+                            if is_external_texture(var.ty, &context.expression.module.types) {
+                                write!(self.out, ", {name}_plane1, {name}_plane2, {name}_params")?;
+                            }
                         }
                         supports_array_length |=
                             needs_array_length(var.ty, &context.expression.module.types);
@@ -2942,6 +3222,9 @@ impl<W: Write> Writer<W> {
                     writeln!(self.out, ";")?;
                     self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
                 }
+                crate::Statement::MeshFunction(_) => {
+                    return Err(Error::FeatureNotImplemented("mesh shaders".to_string()));
+                }
                 crate::Statement::RayQuery { query, ref fun } => {
                     if context.expression.lang_version < (2, 4) {
                         return Err(Error::UnsupportedRayTracing);
@@ -3425,6 +3708,92 @@ impl<W: Write> Writer<W> {
             }
         }
 
+        self.write_texture_functions(module)
+    }
+
+    /// Write the functions used for sampling with clamp-to-edge and for
+    /// reading external textures, if the module needs them.
+    ///
+    /// The external texture functions take the three planes and the
+    /// parameters that an [`External`] image is lowered into, and convert
+    /// the planes' contents to RGBA.
+    ///
+    /// [`External`]: crate::ImageClass::External
+    fn write_texture_functions(&mut self, module: &crate::Module) -> BackendResult {
+        let sample_clamp_to_edge = module
+            .functions
+            .iter()
+            .map(|(_, fun)| fun)
+            .chain(module.entry_points.iter().map(|ep| &ep.function))
+            .flat_map(|fun| fun.expressions.iter())
+            .any(|(_, expr)| {
+                matches!(
+                    *expr,
+                    crate::Expression::ImageSample {
+                        clamp_to_edge: true,
+                        ..
+                    }
+                )
+            });
+        let params_ty = module.special_types.external_texture_params;
+        if !sample_clamp_to_edge && params_ty.is_none() {
+            return Ok(());
+        }
+
+        let texture = format!("{NAMESPACE}::texture2d<float, {NAMESPACE}::access::sample>");
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "{NAMESPACE}::float4 {SAMPLE_CLAMP_TO_EDGE_FUNCTION}({texture} tex, {NAMESPACE}::sampler samp, {NAMESPACE}::float2 coords) {{
+    {NAMESPACE}::float2 half_texel = 0.5 / {NAMESPACE}::float2(tex.get_width(), tex.get_height());
+    return tex.sample(samp, {NAMESPACE}::clamp(coords, half_texel, 1.0 - half_texel), {NAMESPACE}::level(0.0));
+}}"
+        )?;
+
+        let params_ty = match params_ty {
+            Some(params_ty) => params_ty,
+            None => return Ok(()),
+        };
+        let params_ty_name = &self.names[&NameKey::Type(params_ty)];
+        let yuv_conversion_matrix = &self.names[&NameKey::StructMember(params_ty, 0)];
+        let num_planes = &self.names[&NameKey::StructMember(params_ty, 2)];
+        let planes = format!("{texture} plane0, {texture} plane1, {texture} plane2");
+        let to_rgba = format!(
+            "    if (params.{num_planes} == 1u) {{
+        return texel0;
+    }}
+    float v = params.{num_planes} == 2u ? texel1.y : texel2.x;
+    return params.{yuv_conversion_matrix} * {NAMESPACE}::float4(texel0.x, texel1.x, v, 1.0);"
+        );
+
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "{NAMESPACE}::float4 {SAMPLE_EXTERNAL_FUNCTION}({planes}, constant {params_ty_name}& params, {NAMESPACE}::sampler samp, {NAMESPACE}::float2 coords) {{
+    {NAMESPACE}::float4 texel0 = {SAMPLE_CLAMP_TO_EDGE_FUNCTION}(plane0, samp, coords);
+    {NAMESPACE}::float4 texel1 = {SAMPLE_CLAMP_TO_EDGE_FUNCTION}(plane1, samp, coords);
+    {NAMESPACE}::float4 texel2 = {SAMPLE_CLAMP_TO_EDGE_FUNCTION}(plane2, samp, coords);
+{to_rgba}
+}}"
+        )?;
+
+        // The coordinates are clamped to the first plane, and scaled to the
+        // others, which may be subsampled.
+        writeln!(self.out)?;
+        writeln!(
+            self.out,
+            "{NAMESPACE}::float4 {LOAD_EXTERNAL_FUNCTION}({planes}, constant {params_ty_name}& params, {NAMESPACE}::uint2 coords) {{
+    {NAMESPACE}::uint2 size0 = {NAMESPACE}::uint2(plane0.get_width(), plane0.get_height());
+    {NAMESPACE}::uint2 size1 = {NAMESPACE}::uint2(plane1.get_width(), plane1.get_height());
+    {NAMESPACE}::uint2 size2 = {NAMESPACE}::uint2(plane2.get_width(), plane2.get_height());
+    {NAMESPACE}::uint2 coords0 = {NAMESPACE}::min(coords, size0 - 1u);
+    {NAMESPACE}::float4 texel0 = plane0.read(coords0);
+    {NAMESPACE}::float4 texel1 = plane1.read(coords0 * size1 / size0);
+    {NAMESPACE}::float4 texel2 = plane2.read(coords0 * size2 / size0);
+{to_rgba}
+}}"
+        )?;
+
         Ok(())
     }
 
@@ -3561,6 +3930,7 @@ impl<W: Write> Writer<W> {
             }
 
             writeln!(self.out)?;
+            self.out.mark(module.functions.get_span(fun_handle));
             let fun_name = &self.names[&NameKey::Function(fun_handle)];
             match fun.result {
                 Some(ref result) => {
@@ -3617,6 +3987,11 @@ impl<W: Write> Writer<W> {
                     separate(index + 1 != pass_through_globals.len() || supports_array_length);
                 write!(self.out, "{}", back::INDENT)?;
                 tyvar.try_fmt(&mut self.out)?;
+                if is_external_texture(module.global_variables[handle].ty, &module.types) {
+                    let separator = format!(",\n{}", back::INDENT);
+                    write!(self.out, "{separator}")?;
+                    self.put_external_texture_extra_arguments(module, handle, None, &separator)?;
+                }
                 writeln!(self.out, "{separator}")?;
             }
 
@@ -3683,7 +4058,12 @@ impl<W: Write> Writer<W> {
         for (ep_index, ep) in module.entry_points.iter().enumerate() {
             let fun = &ep.function;
             let fun_info = mod_info.get_entry_point(ep_index);
-            let mut ep_error = None;
+            let mut ep_error = match ep.stage {
+                crate::ShaderStage::Task | crate::ShaderStage::Mesh => {
+                    Some(super::EntryPointError::UnsupportedStage(ep.stage))
+                }
+                _ => None,
+            };
 
             log::trace!(
                 "entry point {:?}, index {:?}",
@@ -3700,7 +4080,7 @@ impl<W: Write> Writer<W> {
 
             // skip this entry point if any global bindings are missing,
             // or their types are incompatible.
-            if !options.fake_missing_bindings {
+            if !options.fake_missing_bindings && ep_error.is_none() {
                 for (var_handle, var) in module.global_variables.iter() {
                     if fun_info[var_handle].is_empty() {
                         continue;
@@ -3718,6 +4098,28 @@ impl<W: Write> Writer<W> {
                                     break;
                                 }
                             };
+                            if let Some(ab) = options.get_argument_buffer(ep, br.group) {
+                                match ab.resources.get(&br.binding) {
+                                    Some(target)
+                                        if target.dynamic_offset.is_some()
+                                            && ab.dynamic_offsets_buffer.is_none() =>
+                                    {
+                                        ep_error = Some(
+                                            super::EntryPointError::MissingDynamicOffsetsBuffer(
+                                                br.group,
+                                            ),
+                                        );
+                                        break;
+                                    }
+                                    Some(_) => continue,
+                                    None => {
+                                        ep_error = Some(super::EntryPointError::MissingBindTarget(
+                                            br.clone(),
+                                        ));
+                                        break;
+                                    }
+                                }
+                            }
                             let target = options.get_resource_binding_target(ep, br);
                             let good = match target {
                                 Some(target) => {
@@ -3742,6 +4144,22 @@ impl<W: Write> Writer<W> {
                                     Some(super::EntryPointError::MissingBindTarget(br.clone()));
                                 break;
                             }
+                            if is_external_texture(var.ty, &module.types) {
+                                let [plane1, plane2, params] = br.external_texture_bindings();
+                                let missing = [(plane1, true), (plane2, true), (params, false)]
+                                    .into_iter()
+                                    .find(|&(ref br, is_texture)| {
+                                        match options.get_resource_binding_target(ep, br) {
+                                            Some(target) if is_texture => target.texture.is_none(),
+                                            Some(target) => target.buffer.is_none(),
+                                            None => true,
+                                        }
+                                    });
+                                if let Some((br, _)) = missing {
+                                    ep_error = Some(super::EntryPointError::MissingBindTarget(br));
+                                    break;
+                                }
+                            }
                         }
                         crate::AddressSpace::PushConstant => {
                             if let Err(e) = options.resolve_push_constants(ep) {
@@ -3751,7 +4169,8 @@ impl<W: Write> Writer<W> {
                         }
                         crate::AddressSpace::Function
                         | crate::AddressSpace::Private
-                        | crate::AddressSpace::WorkGroup => {}
+                        | crate::AddressSpace::WorkGroup
+                        | crate::AddressSpace::TaskPayload => {}
                     }
                 }
                 if supports_array_length {
@@ -3784,6 +4203,7 @@ impl<W: Write> Writer<W> {
                 crate::ShaderStage::Compute { .. } => {
                     ("kernel", LocationMode::Uniform, LocationMode::Uniform)
                 }
+                crate::ShaderStage::Task | crate::ShaderStage::Mesh => unreachable!(),
             };
 
             // Since `Namer.reset` wasn't expecting struct members to be
@@ -3939,6 +4359,62 @@ impl<W: Write> Writer<W> {
                 None => "void",
             };
 
+            // Gather the globals living in argument buffers by group, and
+            // emit a struct type named `<fun>Group<n>` for each group, holding
+            // the members this entry point uses.
+            let mut argument_buffers = std::collections::BTreeMap::new();
+            for (handle, var) in module.global_variables.iter() {
+                if fun_info[handle].is_empty() {
+                    continue;
+                }
+                let Some(ref br) = var.binding else {
+                    continue;
+                };
+                if let Some(ab) = options.get_argument_buffer(ep, br.group) {
+                    if is_external_texture(var.ty, &module.types) {
+                        return Err(Error::FeatureNotImplemented(
+                            "external textures in argument buffers".to_string(),
+                        ));
+                    }
+                    argument_buffers
+                        .entry(br.group)
+                        .or_insert_with(|| (ab, Vec::new(), format!("{fun_name}Group{}", br.group)))
+                        .1
+                        .push((br.binding, handle));
+                }
+            }
+            if !argument_buffers.is_empty() && options.lang_version < (2, 0) {
+                return Err(Error::UnsupportedArgumentBuffers);
+            }
+            for &mut (ab, ref mut members, ref struct_name) in argument_buffers.values_mut() {
+                members.sort();
+                writeln!(self.out, "struct {struct_name} {{")?;
+                for &(binding, handle) in members.iter() {
+                    let target = ab.resources.get(&binding);
+                    let resolved = super::ResolvedBinding::Resource(super::BindTarget {
+                        binding_array_size: target.and_then(|target| target.binding_array_size),
+                        ..Default::default()
+                    });
+                    let tyvar = TypedGlobalVariable {
+                        module,
+                        names: &self.names,
+                        handle,
+                        usage: fun_info[handle],
+                        binding: Some(&resolved),
+                        reference: true,
+                    };
+                    let name = &self.names[&NameKey::GlobalVariable(handle)];
+                    write!(self.out, "{}", back::INDENT)?;
+                    tyvar.try_fmt_type(&mut self.out, "*")?;
+                    write!(self.out, " {name}")?;
+                    if let Some(target) = target {
+                        write!(self.out, " [[id({})]]", target.id)?;
+                    }
+                    writeln!(self.out, ";")?;
+                }
+                writeln!(self.out, "}};")?;
+            }
+
             // Write the entry point function's name, and begin its argument list.
             writeln!(self.out, "{em_str} {result_type_name} {fun_name}(")?;
             let mut is_first_argument = true;
@@ -4119,6 +4595,11 @@ impl<W: Write> Writer<W> {
                                             "read-write textures".to_string(),
                                         ));
                                     }
+                                    crate::ImageClass::External => {
+                                        return Err(Error::UnsupportedArrayOf(
+                                            "external textures".to_string(),
+                                        ));
+                                    }
                                 },
                                 _ => {
                                     return Err(Error::UnsupportedArrayOfType(base));
@@ -4130,6 +4611,13 @@ impl<W: Write> Writer<W> {
                     _ => {}
                 }
 
+                // Globals in argument buffers are passed with their group below.
+                if let Some(ref br) = var.binding {
+                    if argument_buffers.contains_key(&br.group) {
+                        continue;
+                    }
+                }
+
                 // the resolves have already been checked for `!fake_missing_bindings` case
                 let resolved = match var.space {
                     crate::AddressSpace::PushConstant => options.resolve_push_constants(ep).ok(),
@@ -4168,7 +4656,65 @@ impl<W: Write> Writer<W> {
                     write!(self.out, " = ")?;
                     self.put_const_expression(value, module, mod_info)?;
                 }
+                if is_external_texture(var.ty, &module.types) {
+                    write!(self.out, "\n, ")?;
+                    self.put_external_texture_extra_arguments(
+                        module,
+                        handle,
+                        Some((options, ep)),
+                        "\n, ",
+                    )?;
+                }
+                writeln!(self.out)?;
+            }
+
+            // Then pass each argument buffer, followed by its dynamic offsets
+            // if any of the members we use need them.
+            let mut argument_buffer_names = FastHashMap::default();
+            for (&group, &(ab, ref members, ref struct_name)) in argument_buffers.iter() {
+                let separator = if is_first_argument {
+                    is_first_argument = false;
+                    ' '
+                } else {
+                    ','
+                };
+                let name = self.namer.call(&format!("group{group}"));
+                write!(self.out, "{separator} constant {struct_name}& {name}")?;
+                super::ResolvedBinding::Resource(super::BindTarget {
+                    buffer: Some(ab.buffer),
+                    ..Default::default()
+                })
+                .try_fmt(&mut self.out)?;
                 writeln!(self.out)?;
+
+                let has_dynamic_offsets = members.iter().any(|&(binding, _)| {
+                    ab.resources
+                        .get(&binding)
+                        .and_then(|target| target.dynamic_offset)
+                        .is_some()
+                });
+                let offsets_name = if has_dynamic_offsets {
+                    let offsets_name = self.namer.call(&format!("group{group}_dynamic_offsets"));
+                    write!(self.out, ", constant uint* {offsets_name}")?;
+                    let resolved = match ab.dynamic_offsets_buffer {
+                        Some(slot) => super::ResolvedBinding::Resource(super::BindTarget {
+                            buffer: Some(slot),
+                            ..Default::default()
+                        }),
+                        // this is checked earlier for the `!fake_missing_bindings` case
+                        None => super::ResolvedBinding::User {
+                            prefix: "fake",
+                            index: 0,
+                            interpolation: None,
+                        },
+                    };
+                    resolved.try_fmt(&mut self.out)?;
+                    writeln!(self.out)?;
+                    Some(offsets_name)
+                } else {
+                    None
+                };
+                argument_buffer_names.insert(group, (name, offsets_name));
             }
 
             // If this entry uses any variable-length arrays, their sizes are
@@ -4229,6 +4775,65 @@ impl<W: Write> Writer<W> {
                             writeln!(self.out, " = {{}};")?;
                         }
                     };
+                } else if let Some((ab, group_name, offsets_name)) =
+                    var.binding.as_ref().and_then(|br| {
+                        let &(ab, ..) = argument_buffers.get(&br.group)?;
+                        let (ref name, ref offsets_name) = argument_buffer_names[&br.group];
+                        Some((ab, name, offsets_name))
+                    })
+                {
+                    // take the global out of its argument buffer
+                    let target = ab.resources.get(&var.binding.as_ref().unwrap().binding);
+                    let resolved = super::ResolvedBinding::Resource(super::BindTarget {
+                        binding_array_size: target.and_then(|target| target.binding_array_size),
+                        ..Default::default()
+                    });
+                    let tyvar = TypedGlobalVariable {
+                        module,
+                        names: &self.names,
+                        handle,
+                        usage,
+                        binding: Some(&resolved),
+                        reference: true,
+                    };
+                    let name = &self.names[&NameKey::GlobalVariable(handle)];
+                    write!(self.out, "{}", back::INDENT)?;
+                    match tyvar.address_space() {
+                        Some((space, access)) => {
+                            tyvar.try_fmt(&mut self.out)?;
+                            let dynamic_offset = target.and_then(|target| target.dynamic_offset);
+                            match (dynamic_offset, offsets_name.as_ref()) {
+                                (Some(index), Some(offsets_name)) => {
+                                    let access = if access.is_empty() {
+                                        String::new()
+                                    } else {
+                                        format!(" {access}")
+                                    };
+                                    write!(self.out, " = *reinterpret_cast<")?;
+                                    tyvar.try_fmt_type(&mut self.out, "*")?;
+                                    writeln!(
+                                        self.out,
+                                        ">(reinterpret_cast<{space} char{access}*>({group_name}.{name}) + {offsets_name}[{index}]);"
+                                    )?;
+                                }
+                                _ => writeln!(self.out, " = *{group_name}.{name};")?,
+                            }
+                        }
+                        None => {
+                            // Don't copy binding arrays out of the argument
+                            // buffer, they may be large.
+                            if let crate::TypeInner::BindingArray { .. } =
+                                module.types[var.ty].inner
+                            {
+                                write!(self.out, "constant ")?;
+                                tyvar.try_fmt_type(&mut self.out, "")?;
+                                write!(self.out, "&")?;
+                            } else {
+                                tyvar.try_fmt_type(&mut self.out, "")?;
+                            }
+                            writeln!(self.out, " {name} = {group_name}.{name};")?;
+                        }
+                    }
                 } else if let Some(ref binding) = var.binding {
                     // write an inline sampler
                     let resolved = options.resolve_resource_binding(ep, binding).unwrap();
@@ -4381,6 +4986,12 @@ impl<W: Write> Writer<W> {
                 "{level}{NAMESPACE}::threadgroup_barrier({NAMESPACE}::mem_flags::mem_threadgroup);",
             )?;
         }
+        if flags.contains(crate::Barrier::TEXTURE) {
+            writeln!(
+                self.out,
+                "{level}{NAMESPACE}::threadgroup_barrier({NAMESPACE}::mem_flags::mem_texture);",
+            )?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,520 @@
+/*!
+Backend for host-side [Rust][rust] type definitions.
+
+This backend doesn't translate shader code. Instead, it writes a Rust
+`#[repr(C)]` struct for each host-shareable struct type in the module,
+laid out exactly as [`Layouter`] lays out the corresponding WGSL type in
+uniform and storage buffers, so that host code can fill buffers without
+its types drifting out of sync with the shader's.
+
+For each struct, the generated code includes:
+
+- explicit `_padN: [u8; N]` fields wherever the shader's layout has a gap
+  between members or at the end of the struct, so the struct has no
+  implicit padding and can derive [`bytemuck::Pod`];
+
+- `const` assertions checking the struct's size and each member's offset
+  against the shader's layout.
+
+For each global variable with a resource binding, it writes `NAME_GROUP` and
+`NAME_BINDING` constants.
+
+Shader types are represented as follows:
+
+- Scalars are the corresponding Rust primitive types. `f16` is represented
+  by its bits, as a `u16`.
+
+- Vectors are arrays, like `[f32; 3]`.
+
+- Matrices are arrays of columns. Three-row matrices have four-element
+  columns, since that is their stride in memory: `mat2x3<f32>` is
+  `[[f32; 4]; 2]`, and the fourth element of each column is padding.
+
+- Atomics are their underlying scalar type.
+
+- Fixed-size arrays are Rust arrays. If an array's stride is larger than
+  its element type's size, the elements are wrapped in a generated struct,
+  named like `Padded`, that adds the necessary padding.
+
+- A runtime-sized array at the end of a struct is omitted; the struct
+  describes only the fixed-size header before it.
+
+Struct types containing `bool`s, pointers, or other types with no host
+representation are skipped, as are the types they are used in.
+
+[rust]: https://www.rust-lang.org/
+[`Layouter`]: crate::proc::Layouter
+[`bytemuck::Pod`]: https://docs.rs/bytemuck/latest/bytemuck/trait.Pod.html
+*/
+
+use crate::{
+    arena::Handle,
+    back,
+    proc::{self, NameKey},
+};
+use std::fmt::Write;
+
+/// Configuration options for the Rust backend.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+#[cfg_attr(feature = "deserialize", serde(default))]
+pub struct Options {
+    /// Derive `bytemuck::Pod` and `bytemuck::Zeroable` for generated types.
+    pub derive_bytemuck: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            derive_bytemuck: true,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    FmtError(#[from] std::fmt::Error),
+    #[error(transparent)]
+    Layout(#[from] proc::LayoutError),
+}
+
+/// Shorthand result used internally by the backend
+type BackendResult = Result<(), Error>;
+
+/// Write Rust definitions for the host-shareable types in `module`.
+pub fn write_string(module: &crate::Module, options: &Options) -> Result<String, Error> {
+    let mut w = Writer::new(String::new(), options.clone());
+    w.write(module)?;
+    Ok(w.finish())
+}
+
+pub struct Writer<W> {
+    out: W,
+    options: Options,
+    names: crate::FastHashMap<NameKey, String>,
+    layouter: proc::Layouter,
+    /// The Rust type for each type in the module, or `None` if it has no
+    /// host representation.
+    rust_types: Vec<Option<String>>,
+    /// The names of the generated types.
+    type_names: crate::FastHashSet<String>,
+    /// The structs wrapping the elements of arrays whose stride is larger
+    /// than their element type.
+    padded_types: Vec<PaddedType>,
+}
+
+/// A struct wrapping an array element, followed by padding.
+struct PaddedType {
+    name: String,
+    element: String,
+    padding: u32,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(out: W, options: Options) -> Self {
+        Writer {
+            out,
+            options,
+            names: crate::FastHashMap::default(),
+            layouter: proc::Layouter::default(),
+            rust_types: Vec::new(),
+            type_names: crate::FastHashSet::default(),
+            padded_types: Vec::new(),
+        }
+    }
+
+    fn reset(&mut self, module: &crate::Module) -> BackendResult {
+        self.names.clear();
+        // Names the generated code itself refers to.
+        self.type_names.clear();
+        self.type_names
+            .extend(["bytemuck", "core"].iter().map(|name| name.to_string()));
+        for (handle, ty) in module.types.iter() {
+            let name = unique(&mut self.type_names, ident(ty.name.as_deref(), "Type"));
+            self.names.insert(NameKey::Type(handle), name);
+            if let crate::TypeInner::Struct { ref members, .. } = ty.inner {
+                let mut used_members = crate::FastHashSet::default();
+                for (index, member) in members.iter().enumerate() {
+                    let mut name = ident(member.name.as_deref(), "member");
+                    // Keep clear of the names of padding fields.
+                    let padding_suffix = name.strip_prefix("_pad");
+                    if padding_suffix.is_some_and(|n| n.chars().all(|c| c.is_ascii_digit())) {
+                        name.push('_');
+                    }
+                    let name = unique(&mut used_members, name);
+                    self.names
+                        .insert(NameKey::StructMember(handle, index as u32), name);
+                }
+            }
+        }
+        let mut used_constants = crate::FastHashSet::default();
+        for (handle, global) in module.global_variables.iter() {
+            let base = ident(global.name.as_deref(), "global");
+            let name = unique(&mut used_constants, screaming_snake_case(&base));
+            self.names.insert(NameKey::GlobalVariable(handle), name);
+        }
+        self.layouter.clear();
+        self.layouter.update(module.to_ctx())?;
+        self.rust_types.clear();
+        self.padded_types.clear();
+        Ok(())
+    }
+
+    pub fn write(&mut self, module: &crate::Module) -> BackendResult {
+        self.reset(module)?;
+
+        // Types appear in the arena after the types they refer to, so a
+        // single pass suffices.
+        for (handle, ty) in module.types.iter() {
+            let rust_type = self.rust_type(module, handle, &ty.inner);
+            self.rust_types.push(rust_type);
+        }
+
+        writeln!(
+            self.out,
+            "// Host-side types generated by naga. Do not edit by hand."
+        )?;
+        writeln!(self.out)?;
+
+        for padded in std::mem::take(&mut self.padded_types) {
+            self.write_padded(&padded)?;
+        }
+
+        for (handle, ty) in module.types.iter() {
+            if let crate::TypeInner::Struct {
+                ref members, span, ..
+            } = ty.inner
+            {
+                if self.rust_types[handle.index()].is_some() {
+                    self.write_struct(module, handle, members, span)?;
+                }
+            }
+        }
+
+        for (handle, global) in module.global_variables.iter() {
+            if let Some(ref binding) = global.binding {
+                let name = &self.names[&NameKey::GlobalVariable(handle)];
+                writeln!(self.out, "pub const {name}_GROUP: u32 = {};", binding.group)?;
+                writeln!(
+                    self.out,
+                    "pub const {name}_BINDING: u32 = {};",
+                    binding.binding
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(self) -> W {
+        self.out
+    }
+
+    /// Return the Rust type for `inner`, or `None` if it has no host
+    /// representation.
+    ///
+    /// The types `inner` refers to must already be in `self.rust_types`.
+    fn rust_type(
+        &mut self,
+        module: &crate::Module,
+        handle: Handle<crate::Type>,
+        inner: &crate::TypeInner,
+    ) -> Option<String> {
+        match *inner {
+            crate::TypeInner::Scalar(scalar) | crate::TypeInner::Atomic(scalar) => {
+                scalar_type(scalar).map(str::to_string)
+            }
+            crate::TypeInner::Vector { size, scalar } => {
+                Some(format!("[{}; {}]", scalar_type(scalar)?, size as u8))
+            }
+            crate::TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => {
+                // Three-row columns are padded to four elements.
+                let column_len = match rows {
+                    crate::VectorSize::Tri => 4,
+                    rows => rows as u8,
+                };
+                Some(format!(
+                    "[[{}; {column_len}]; {}]",
+                    scalar_type(scalar)?,
+                    columns as u8
+                ))
+            }
+            crate::TypeInner::Array {
+                base,
+                size: crate::ArraySize::Constant(count),
+                stride,
+            } => {
+                let element = self.rust_types[base.index()].clone()?;
+                let padding = stride - self.layouter[base].size;
+                if padding == 0 {
+                    Some(format!("[{element}; {count}]"))
+                } else {
+                    let padded = self.padded_type(element, padding);
+                    Some(format!("[{padded}; {count}]"))
+                }
+            }
+            crate::TypeInner::Struct { ref members, .. } => {
+                // Predeclared types can't be spelled in WGSL, so they're
+                // never part of a shader's interface.
+                if module
+                    .special_types
+                    .predeclared_types
+                    .values()
+                    .any(|&ty| ty == handle)
+                {
+                    return None;
+                }
+                for (index, member) in members.iter().enumerate() {
+                    let is_last = index + 1 == members.len();
+                    match module.types[member.ty].inner {
+                        crate::TypeInner::Array {
+                            base,
+                            size: crate::ArraySize::Dynamic,
+                            ..
+                        } if is_last => {
+                            self.rust_types[base.index()].as_ref()?;
+                        }
+                        _ => {
+                            self.rust_types[member.ty.index()].as_ref()?;
+                        }
+                    }
+                }
+                Some(self.names[&NameKey::Type(handle)].clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn write_derives(&mut self) -> BackendResult {
+        write!(self.out, "#[derive(Clone, Copy, Debug, PartialEq")?;
+        if self.options.derive_bytemuck {
+            write!(self.out, ", bytemuck::Pod, bytemuck::Zeroable")?;
+        }
+        writeln!(self.out, ")]")?;
+        Ok(())
+    }
+
+    /// Return the name of the struct wrapping an array element of type
+    /// `element`, followed by `padding` bytes of padding.
+    ///
+    /// Each element type and padding gets its own struct, rather than one
+    /// generic struct, so that `bytemuck` can check that it has no implicit
+    /// padding when deriving `Pod`.
+    fn padded_type(&mut self, element: String, padding: u32) -> String {
+        if let Some(padded) = self
+            .padded_types
+            .iter()
+            .find(|padded| padded.element == element && padded.padding == padding)
+        {
+            return padded.name.clone();
+        }
+        let name = unique(&mut self.type_names, "Padded".to_string());
+        self.padded_types.push(PaddedType {
+            name: name.clone(),
+            element,
+            padding,
+        });
+        name
+    }
+
+    /// Write a struct used for the elements of arrays whose stride is larger
+    /// than their element type.
+    fn write_padded(&mut self, padded: &PaddedType) -> BackendResult {
+        let PaddedType {
+            ref name,
+            ref element,
+            padding,
+        } = *padded;
+        writeln!(
+            self.out,
+            "/// An array element of type `{element}`, followed by {padding} bytes of padding."
+        )?;
+        writeln!(self.out, "#[repr(C)]")?;
+        self.write_derives()?;
+        writeln!(self.out, "pub struct {name} {{")?;
+        writeln!(self.out, "{}pub value: {element},", back::INDENT)?;
+        writeln!(self.out, "{}pub _pad: [u8; {padding}],", back::INDENT)?;
+        writeln!(self.out, "}}")?;
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    fn write_struct(
+        &mut self,
+        module: &crate::Module,
+        handle: Handle<crate::Type>,
+        members: &[crate::StructMember],
+        span: u32,
+    ) -> BackendResult {
+        let name = self.names[&NameKey::Type(handle)].clone();
+        let mut offsets = Vec::with_capacity(members.len());
+        let mut padding_count = 0;
+        let mut end = 0;
+
+        writeln!(self.out, "#[repr(C)]")?;
+        self.write_derives()?;
+        // Member names follow the shader's, which may be `camelCase`.
+        if (0..members.len()).any(|index| {
+            self.names[&NameKey::StructMember(handle, index as u32)]
+                .contains(|c: char| c.is_ascii_uppercase())
+        }) {
+            writeln!(self.out, "#[allow(non_snake_case)]")?;
+        }
+        writeln!(self.out, "pub struct {name} {{")?;
+        for (index, member) in members.iter().enumerate() {
+            let member_name = self.names[&NameKey::StructMember(handle, index as u32)].clone();
+            if let crate::TypeInner::Array {
+                base,
+                size: crate::ArraySize::Dynamic,
+                stride,
+            } = module.types[member.ty].inner
+            {
+                writeln!(
+                    self.out,
+                    "{}// `{member_name}`, a runtime-sized array of `{}` with stride {stride}, \
+                     follows at offset {}.",
+                    back::INDENT,
+                    self.rust_types[base.index()].as_ref().unwrap(),
+                    member.offset,
+                )?;
+                // The struct ends where the runtime-sized array starts.
+                end = self.write_padding(end, member.offset, &mut padding_count)?;
+                break;
+            }
+
+            end = self.write_padding(end, member.offset, &mut padding_count)?;
+            writeln!(
+                self.out,
+                "{}pub {member_name}: {},",
+                back::INDENT,
+                self.rust_types[member.ty.index()].as_ref().unwrap()
+            )?;
+            offsets.push((member_name, member.offset));
+            end += self.layouter[member.ty].size;
+        }
+        if offsets.len() == members.len() {
+            end = self.write_padding(end, span, &mut padding_count)?;
+        }
+        writeln!(self.out, "}}")?;
+        writeln!(self.out)?;
+
+        writeln!(
+            self.out,
+            "const _: () = assert!(core::mem::size_of::<{name}>() == {end});"
+        )?;
+        if !offsets.is_empty() {
+            // Compute the offsets by hand, since `core::mem::offset_of!`
+            // needs Rust 1.77.
+            writeln!(self.out, "const _: () = {{")?;
+            writeln!(
+                self.out,
+                "{}let value = core::mem::MaybeUninit::<{name}>::uninit();",
+                back::INDENT
+            )?;
+            writeln!(self.out, "{}let base = value.as_ptr();", back::INDENT)?;
+            for (member_name, offset) in offsets {
+                writeln!(
+                    self.out,
+                    "{}assert!(unsafe {{ core::ptr::addr_of!((*base).{member_name}).cast::<u8>().offset_from(base.cast::<u8>()) }} == {offset});",
+                    back::INDENT
+                )?;
+            }
+            writeln!(self.out, "}};")?;
+        }
+        writeln!(self.out)?;
+        Ok(())
+    }
+
+    /// Write a padding field to advance from offset `from` to offset `to`,
+    /// if necessary, and return `to`.
+    fn write_padding(&mut self, from: u32, to: u32, count: &mut u32) -> Result<u32, Error> {
+        if to > from {
+            writeln!(
+                self.out,
+                "{}pub _pad{}: [u8; {}],",
+                back::INDENT,
+                *count,
+                to - from
+            )?;
+            *count += 1;
+        }
+        Ok(to)
+    }
+}
+
+/// Return the Rust type for `scalar`, or `None` if it has no host
+/// representation.
+const fn scalar_type(scalar: crate::Scalar) -> Option<&'static str> {
+    use crate::ScalarKind as Sk;
+
+    Some(match (scalar.kind, scalar.width) {
+        (Sk::Float, 2) => "u16",
+        (Sk::Float, 4) => "f32",
+        (Sk::Float, 8) => "f64",
+        (Sk::Sint, 4) => "i32",
+        (Sk::Sint, 8) => "i64",
+        (Sk::Uint, 4) => "u32",
+        (Sk::Uint, 8) => "u64",
+        _ => return None,
+    })
+}
+
+/// Return `name` as a Rust identifier, or `fallback` if `name` is missing or
+/// is not a valid identifier.
+///
+/// Keywords are turned into raw identifiers where Rust allows it.
+fn ident(name: Option<&str>, fallback: &str) -> String {
+    let name = match name {
+        Some(name)
+            if !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && name != "_" =>
+        {
+            name
+        }
+        _ => return fallback.to_string(),
+    };
+    if crate::keywords::rust::RESERVED.contains(&name) {
+        match name {
+            // These can't be raw identifiers.
+            "crate" | "self" | "Self" | "super" => format!("{name}_"),
+            _ => format!("r#{name}"),
+        }
+    } else {
+        name.to_string()
+    }
+}
+
+/// Return `name`, with a numeric suffix if necessary to make it distinct from
+/// every name in `used`, and add it to `used`.
+fn unique(used: &mut crate::FastHashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut suffix = 1;
+    while used.contains(&candidate) {
+        candidate = format!("{name}_{suffix}");
+        suffix += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+/// Convert `name`, in `snake_case` or `camelCase`, to `SCREAMING_SNAKE_CASE`.
+fn screaming_snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    let mut previous_lowercase = false;
+    for c in name.trim_start_matches("r#").chars() {
+        if c.is_ascii_uppercase() && previous_lowercase {
+            result.push('_');
+        }
+        previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        result.push(c.to_ascii_uppercase());
+    }
+    result
+}
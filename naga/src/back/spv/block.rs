@@ -4,8 +4,8 @@ Implementations for `BlockContext` methods.
 
 use super::{
     helpers, index::BoundsCheckResult, make_local, selection::Selection, Block, BlockContext,
-    Dimension, Error, Instruction, LocalType, LookupType, LoopContext, ResultMember, Writer,
-    WriterFlags,
+    Dimension, EntryPointContext, Error, Instruction, LocalType, LookupType, LoopContext,
+    ResultMember, Writer, WriterFlags,
 };
 use crate::{arena::Handle, proc::TypeResolution, Statement};
 use spirv::Word;
@@ -71,7 +71,7 @@ impl Writer {
     // between SPIR-V and our IR.
     // The `position_id` argument is a pointer to a `vecN<f32>`,
     // whose `y` component we will negate.
-    fn write_epilogue_position_y_flip(
+    pub(super) fn write_epilogue_position_y_flip(
         &mut self,
         position_id: Word,
         body: &mut Vec<Instruction>,
@@ -831,6 +831,44 @@ impl<'w> BlockContext<'w> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let signed = fun == Mf::Dot4I8Packed;
+                        const CAPABILITIES: &[spirv::Capability] = &[
+                            spirv::Capability::DotProduct,
+                            spirv::Capability::DotProductInput4x8BitPacked,
+                        ];
+                        if self.writer.lang_version() >= (1, 6)
+                            && self.writer.capabilities_allowed(CAPABILITIES)
+                        {
+                            for &capability in CAPABILITIES {
+                                self.writer
+                                    .require_any("4x8 packed dot products", &[capability])?;
+                            }
+                            let op = if signed {
+                                spirv::Op::SDot
+                            } else {
+                                spirv::Op::UDot
+                            };
+                            MathOp::Custom(Instruction::dot_packed_4x8(
+                                op,
+                                result_type_id,
+                                id,
+                                arg0_id,
+                                arg1_id,
+                            ))
+                        } else {
+                            self.write_dot_4x8_packed_polyfill(
+                                id,
+                                result_type_id,
+                                arg0_id,
+                                arg1_id,
+                                signed,
+                                block,
+                            );
+                            self.cached[expr_handle] = id;
+                            return Ok(());
+                        }
+                    }
                     Mf::Outer => MathOp::Custom(Instruction::binary(
                         spirv::Op::OuterProduct,
                         result_type_id,
@@ -907,6 +945,12 @@ impl<'w> BlockContext<'w> {
                         arg0_id,
                     )),
                     Mf::Determinant => MathOp::Ext(spirv::GLOp::Determinant),
+                    Mf::QuantizeToF16 => MathOp::Custom(Instruction::unary(
+                        spirv::Op::QuantizeToF16,
+                        result_type_id,
+                        id,
+                        arg0_id,
+                    )),
                     Mf::ReverseBits => MathOp::Custom(Instruction::unary(
                         spirv::Op::BitReverse,
                         result_type_id,
@@ -1055,6 +1099,19 @@ impl<'w> BlockContext<'w> {
                     Mf::Unpack2x16float => MathOp::Ext(spirv::GLOp::UnpackHalf2x16),
                     Mf::Unpack2x16unorm => MathOp::Ext(spirv::GLOp::UnpackUnorm2x16),
                     Mf::Unpack2x16snorm => MathOp::Ext(spirv::GLOp::UnpackSnorm2x16),
+                    Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp => {
+                        let signed = matches!(fun, Mf::Pack4xI8 | Mf::Pack4xI8Clamp);
+                        let clamp = matches!(fun, Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp);
+                        self.write_pack_4x8(id, result_type_id, arg0_id, signed, clamp, block);
+                        self.cached[expr_handle] = id;
+                        return Ok(());
+                    }
+                    Mf::Unpack4xI8 | Mf::Unpack4xU8 => {
+                        let signed = fun == Mf::Unpack4xI8;
+                        self.write_unpack_4x8(id, result_type_id, arg0_id, signed, block);
+                        self.cached[expr_handle] = id;
+                        return Ok(());
+                    }
                 };
 
                 block.body.push(match math_op {
@@ -1085,7 +1142,8 @@ impl<'w> BlockContext<'w> {
                                 _ => None,
                             };
                         let instruction = if let Some(space) = atomic_space {
-                            let (semantics, scope) = space.to_spirv_semantics_and_scope();
+                            let (semantics, scope) =
+                                self.writer.atomic_semantics_and_scope(space)?;
                             let scope_constant_id = self.get_scope_constant(scope as u32);
                             let semantics_id = self.get_index_constant(semantics.bits());
                             Instruction::atomic_load(
@@ -1096,13 +1154,15 @@ impl<'w> BlockContext<'w> {
                                 semantics_id,
                             )
                         } else {
-                            Instruction::load(result_type_id, id, pointer_id, None)
+                            let memory_access = self.pointer_memory_access(pointer);
+                            Instruction::load(result_type_id, id, pointer_id, memory_access)
                         };
                         block.body.push(instruction);
                         id
                     }
                     ExpressionPointer::Conditional { condition, access } => {
                         //TODO: support atomics?
+                        let memory_access = self.pointer_memory_access(pointer);
                         self.write_conditional_indexed_load(
                             result_type_id,
                             condition,
@@ -1116,7 +1176,7 @@ impl<'w> BlockContext<'w> {
                                     result_type_id,
                                     value_id,
                                     pointer_id,
-                                    None,
+                                    memory_access,
                                 ));
                                 value_id
                             },
@@ -1297,6 +1357,13 @@ impl<'w> BlockContext<'w> {
                 sample,
                 block,
             )?,
+            crate::Expression::ImageSample {
+                image,
+                sampler,
+                coordinate,
+                clamp_to_edge: true,
+                ..
+            } => self.write_image_sample_clamp_to_edge(image, sampler, coordinate, block)?,
             crate::Expression::ImageSample {
                 image,
                 sampler,
@@ -1306,6 +1373,7 @@ impl<'w> BlockContext<'w> {
                 offset,
                 level,
                 depth_ref,
+                clamp_to_edge: false,
             } => self.write_image_sample(
                 result_type_id,
                 image,
@@ -1731,6 +1799,229 @@ impl<'w> BlockContext<'w> {
         }
     }
 
+    /// Write a dot product of two 32-bit integers as vectors of four 8-bit
+    /// components, for when `OpSDot` and `OpUDot` aren't available.
+    fn write_dot_4x8_packed_polyfill(
+        &mut self,
+        result_id: Word,
+        result_type_id: Word,
+        arg0_id: Word,
+        arg1_id: Word,
+        signed: bool,
+        block: &mut Block,
+    ) {
+        // Do the arithmetic in the result type, so that signed components are
+        // sign-extended.
+        let (extract_op, arg0_id, arg1_id) = if signed {
+            let a_id = self.gen_id();
+            block.body.push(Instruction::unary(
+                spirv::Op::Bitcast,
+                result_type_id,
+                a_id,
+                arg0_id,
+            ));
+            let b_id = self.gen_id();
+            block.body.push(Instruction::unary(
+                spirv::Op::Bitcast,
+                result_type_id,
+                b_id,
+                arg1_id,
+            ));
+            (spirv::Op::BitFieldSExtract, a_id, b_id)
+        } else {
+            (spirv::Op::BitFieldUExtract, arg0_id, arg1_id)
+        };
+
+        let eight = self.writer.get_index_constant(8);
+        let mut partial_sum = self.writer.get_constant_null(result_type_id);
+        for index in 0..4 {
+            let offset = self.writer.get_index_constant(index * 8);
+
+            // compute the product of the current components
+            let a_id = self.gen_id();
+            block.body.push(Instruction::ternary(
+                extract_op,
+                result_type_id,
+                a_id,
+                arg0_id,
+                offset,
+                eight,
+            ));
+            let b_id = self.gen_id();
+            block.body.push(Instruction::ternary(
+                extract_op,
+                result_type_id,
+                b_id,
+                arg1_id,
+                offset,
+                eight,
+            ));
+            let prod_id = self.gen_id();
+            block.body.push(Instruction::binary(
+                spirv::Op::IMul,
+                result_type_id,
+                prod_id,
+                a_id,
+                b_id,
+            ));
+
+            let id = if index == 3 { result_id } else { self.gen_id() };
+            block.body.push(Instruction::binary(
+                spirv::Op::IAdd,
+                result_type_id,
+                id,
+                partial_sum,
+                prod_id,
+            ));
+            partial_sum = id;
+        }
+    }
+
+    /// Pack the low eight bits of each component of a four-component integer
+    /// vector into a `u32`, clamping the components to the 8-bit range first
+    /// if `clamp` is set.
+    fn write_pack_4x8(
+        &mut self,
+        result_id: Word,
+        result_type_id: Word,
+        arg0_id: Word,
+        signed: bool,
+        clamp: bool,
+        block: &mut Block,
+    ) {
+        let scalar = if signed {
+            crate::Scalar::I32
+        } else {
+            crate::Scalar::U32
+        };
+        let scalar_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            scalar,
+            pointer_space: None,
+        }));
+
+        let mut vector_id = arg0_id;
+        if clamp {
+            let vector_type = LookupType::Local(LocalType::Value {
+                vector_size: Some(crate::VectorSize::Quad),
+                scalar,
+                pointer_space: None,
+            });
+            let vector_type_id = self.get_type_id(vector_type);
+            let mut splat = |value| {
+                let scalar_id = self.writer.get_constant_scalar(value);
+                self.writer
+                    .get_constant_composite(vector_type, &[scalar_id; 4])
+            };
+            let (op, operands) = if signed {
+                let min = splat(crate::Literal::I32(i8::MIN as i32));
+                let max = splat(crate::Literal::I32(i8::MAX as i32));
+                (spirv::GLOp::SClamp, vec![arg0_id, min, max])
+            } else {
+                let max = splat(crate::Literal::U32(u8::MAX as u32));
+                (spirv::GLOp::UMin, vec![arg0_id, max])
+            };
+            vector_id = self.gen_id();
+            block.body.push(Instruction::ext_inst(
+                self.writer.gl450_ext_inst_id,
+                op,
+                vector_type_id,
+                vector_id,
+                &operands,
+            ));
+        }
+
+        let eight = self.writer.get_index_constant(8);
+        let mut packed = self.writer.get_index_constant(0);
+        for index in 0..4 {
+            let mut component_id = self.gen_id();
+            block.body.push(Instruction::composite_extract(
+                scalar_type_id,
+                component_id,
+                vector_id,
+                &[index],
+            ));
+            if signed {
+                let unsigned_id = self.gen_id();
+                block.body.push(Instruction::unary(
+                    spirv::Op::Bitcast,
+                    result_type_id,
+                    unsigned_id,
+                    component_id,
+                ));
+                component_id = unsigned_id;
+            }
+
+            let id = if index == 3 { result_id } else { self.gen_id() };
+            let offset = self.writer.get_index_constant(index * 8);
+            block.body.push(Instruction::quaternary(
+                spirv::Op::BitFieldInsert,
+                result_type_id,
+                id,
+                packed,
+                component_id,
+                offset,
+                eight,
+            ));
+            packed = id;
+        }
+    }
+
+    /// Unpack a `u32` into a four-component integer vector, one 8-bit
+    /// component at a time, sign-extending them if `signed` is set.
+    fn write_unpack_4x8(
+        &mut self,
+        result_id: Word,
+        result_type_id: Word,
+        arg0_id: Word,
+        signed: bool,
+        block: &mut Block,
+    ) {
+        let scalar = if signed {
+            crate::Scalar::I32
+        } else {
+            crate::Scalar::U32
+        };
+        let scalar_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            scalar,
+            pointer_space: None,
+        }));
+
+        let (extract_op, packed_id) = if signed {
+            let id = self.gen_id();
+            block.body.push(Instruction::unary(
+                spirv::Op::Bitcast,
+                scalar_type_id,
+                id,
+                arg0_id,
+            ));
+            (spirv::Op::BitFieldSExtract, id)
+        } else {
+            (spirv::Op::BitFieldUExtract, arg0_id)
+        };
+
+        let eight = self.writer.get_index_constant(8);
+        let mut components = [0; 4];
+        for (index, component) in components.iter_mut().enumerate() {
+            *component = self.gen_id();
+            let offset = self.writer.get_index_constant(index as u32 * 8);
+            block.body.push(Instruction::ternary(
+                extract_op,
+                scalar_type_id,
+                *component,
+                packed_id,
+                offset,
+                eight,
+            ));
+        }
+        block.body.push(Instruction::composite_construct(
+            result_type_id,
+            result_id,
+            &components,
+        ));
+    }
+
     pub(super) fn write_block(
         &mut self,
         label_id: Word,
@@ -2002,6 +2293,11 @@ impl<'w> BlockContext<'w> {
                 crate::Statement::Return { value: Some(value) } => {
                     let value_id = self.cached[value];
                     let instruction = match self.function.entry_point_context {
+                        // Task shaders end by launching the mesh shader grid.
+                        Some(EntryPointContext {
+                            task_payload: Some(payload_id),
+                            ..
+                        }) => self.write_task_return(Some(value_id), payload_id, &mut block),
                         // If this is an entry point, and we need to return anything,
                         // let's instead store the output variables and return `void`.
                         Some(ref context) => {
@@ -2023,11 +2319,30 @@ impl<'w> BlockContext<'w> {
                     return Ok(());
                 }
                 crate::Statement::Kill => {
-                    self.function.consume(block, Instruction::kill());
-                    return Ok(());
+                    if self
+                        .writer
+                        .flags
+                        .contains(WriterFlags::DEMOTE_TO_HELPER_INVOCATION)
+                    {
+                        // Demoted invocations keep running, so carry on to
+                        // the end of this block like WGSL's `discard` does.
+                        // `Kill` is the last statement in a block.
+                        self.writer.require_any(
+                            "demoting to helper invocations",
+                            &[spirv::Capability::DemoteToHelperInvocation],
+                        )?;
+                        if self.writer.lang_version() < (1, 6) {
+                            self.writer
+                                .use_extension("SPV_EXT_demote_to_helper_invocation");
+                        }
+                        block.body.push(Instruction::demote_to_helper_invocation());
+                    } else {
+                        self.function.consume(block, Instruction::kill());
+                        return Ok(());
+                    }
                 }
                 crate::Statement::Barrier(flags) => {
-                    self.writer.write_barrier(flags, &mut block);
+                    self.writer.write_barrier(flags, &mut block)?;
                 }
                 crate::Statement::Store { pointer, value } => {
                     let value_id = self.cached[value];
@@ -2046,7 +2361,8 @@ impl<'w> BlockContext<'w> {
                                 _ => None,
                             };
                             let instruction = if let Some(space) = atomic_space {
-                                let (semantics, scope) = space.to_spirv_semantics_and_scope();
+                                let (semantics, scope) =
+                                    self.writer.atomic_semantics_and_scope(space)?;
                                 let scope_constant_id = self.get_scope_constant(scope as u32);
                                 let semantics_id = self.get_index_constant(semantics.bits());
                                 Instruction::atomic_store(
@@ -2056,7 +2372,8 @@ impl<'w> BlockContext<'w> {
                                     value_id,
                                 )
                             } else {
-                                Instruction::store(pointer_id, value_id, None)
+                                let memory_access = self.pointer_memory_access(pointer);
+                                Instruction::store(pointer_id, value_id, memory_access)
                             };
                             block.body.push(instruction);
                         }
@@ -2066,11 +2383,13 @@ impl<'w> BlockContext<'w> {
 
                             // The in-bounds path. Perform the access and the store.
                             let pointer_id = access.result_id.unwrap();
+                            let memory_access = self.pointer_memory_access(pointer);
                             selection.block().body.push(access);
-                            selection
-                                .block()
-                                .body
-                                .push(Instruction::store(pointer_id, value_id, None));
+                            selection.block().body.push(Instruction::store(
+                                pointer_id,
+                                value_id,
+                                memory_access,
+                            ));
 
                             // Finish the in-bounds block and start the merge block. This
                             // is the block we'll leave current on return.
@@ -2136,7 +2455,7 @@ impl<'w> BlockContext<'w> {
                         .inner_with(&self.ir_module.types)
                         .pointer_space()
                         .unwrap();
-                    let (semantics, scope) = space.to_spirv_semantics_and_scope();
+                    let (semantics, scope) = self.writer.atomic_semantics_and_scope(space)?;
                     let scope_constant_id = self.get_scope_constant(scope as u32);
                     let semantics_id = self.get_index_constant(semantics.bits());
                     let value_id = self.cached[value];
@@ -2292,8 +2611,9 @@ impl<'w> BlockContext<'w> {
                 }
                 crate::Statement::WorkGroupUniformLoad { pointer, result } => {
                     self.writer
-                        .write_barrier(crate::Barrier::WORK_GROUP, &mut block);
+                        .write_barrier(crate::Barrier::WORK_GROUP, &mut block)?;
                     let result_type_id = self.get_expression_type_id(&self.fun_info[result].ty);
+                    let memory_access = self.pointer_memory_access(pointer);
                     // Embed the body of
                     match self.write_expression_pointer(pointer, &mut block, None)? {
                         ExpressionPointer::Ready { pointer_id } => {
@@ -2302,7 +2622,7 @@ impl<'w> BlockContext<'w> {
                                 result_type_id,
                                 id,
                                 pointer_id,
-                                None,
+                                memory_access,
                             ));
                             self.cached[result] = id;
                         }
@@ -2320,7 +2640,7 @@ impl<'w> BlockContext<'w> {
                                         result_type_id,
                                         value_id,
                                         pointer_id,
-                                        None,
+                                        memory_access,
                                     ));
                                     value_id
                                 },
@@ -2328,11 +2648,14 @@ impl<'w> BlockContext<'w> {
                         }
                     }
                     self.writer
-                        .write_barrier(crate::Barrier::WORK_GROUP, &mut block);
+                        .write_barrier(crate::Barrier::WORK_GROUP, &mut block)?;
                 }
                 crate::Statement::RayQuery { query, ref fun } => {
                     self.write_ray_query_function(query, fun, &mut block);
                 }
+                crate::Statement::MeshFunction(ref fun) => {
+                    self.write_mesh_function(fun, &mut block)?;
+                }
             }
         }
 
@@ -2345,6 +2668,13 @@ impl<'w> BlockContext<'w> {
                     let null_id = self.writer.get_constant_null(type_id);
                     Instruction::return_value(null_id)
                 }
+                Some(_) => match self.function.entry_point_context {
+                    Some(EntryPointContext {
+                        task_payload: Some(payload_id),
+                        ..
+                    }) => self.write_task_return(None, payload_id, &mut block),
+                    _ => Instruction::return_void(),
+                },
                 _ => Instruction::return_void(),
             },
             BlockExit::Branch { target } => Instruction::branch(target),
@@ -0,0 +1,382 @@
+/*!
+Generating SPIR-V for [`External`] images.
+
+An external texture is lowered into three plane textures and a uniform buffer
+of [`NagaExternalTextureParams`]. The first plane uses the image global's own
+`OpVariable`; the others are described by [`ExternalTextureGlobal`].
+
+[`External`]: crate::ImageClass::External
+[`NagaExternalTextureParams`]: crate::Module::generate_external_texture_params_type
+*/
+
+use super::{
+    Block, BlockContext, Error, ExternalTextureGlobal, Instruction, LocalType, LookupType,
+};
+use crate::arena::Handle;
+use spirv::Word;
+
+/// Index of `yuv_conversion_matrix` in `NagaExternalTextureParams`.
+const PARAMS_MATRIX: u32 = 0;
+/// Index of `size` in `NagaExternalTextureParams`.
+const PARAMS_SIZE: u32 = 1;
+/// Index of `num_planes` in `NagaExternalTextureParams`.
+const PARAMS_NUM_PLANES: u32 = 2;
+
+impl<'w> BlockContext<'w> {
+    /// Return the lowered resources of the external texture `image`.
+    fn get_external_texture(
+        &self,
+        image: Handle<crate::Expression>,
+    ) -> Result<ExternalTextureGlobal, Error> {
+        match self.ir_function.expressions[image] {
+            crate::Expression::GlobalVariable(handle) => self.writer.global_variables
+                [handle.index()]
+            .external_texture
+            .ok_or(Error::Validation("external texture global")),
+            _ => Err(Error::FeatureNotImplemented(
+                "external texture function arguments",
+            )),
+        }
+    }
+
+    /// Load member `index` of the parameters of `external`.
+    fn write_external_texture_param(
+        &mut self,
+        external: &ExternalTextureGlobal,
+        index: u32,
+        block: &mut Block,
+    ) -> Result<Word, Error> {
+        let params_ty =
+            self.ir_module
+                .special_types
+                .external_texture_params
+                .ok_or(Error::Validation(
+                    "missing external texture parameters type",
+                ))?;
+        let member_ty = match self.ir_module.types[params_ty].inner {
+            crate::TypeInner::Struct { ref members, .. } => members[index as usize].ty,
+            _ => return Err(Error::Validation("external texture parameters type")),
+        };
+
+        let pointer_type_id = self.writer.get_pointer_id(
+            &self.ir_module.types,
+            member_ty,
+            spirv::StorageClass::Uniform,
+        )?;
+        let index_id = self.get_index_constant(index);
+        let pointer_id = self.gen_id();
+        block.body.push(Instruction::access_chain(
+            pointer_type_id,
+            pointer_id,
+            external.params_access_id,
+            &[index_id],
+        ));
+
+        let type_id = self.get_type_id(LookupType::Handle(member_ty));
+        let id = self.gen_id();
+        block
+            .body
+            .push(Instruction::load(type_id, id, pointer_id, None));
+        Ok(id)
+    }
+
+    /// Generate code for sampling an external texture.
+    ///
+    /// All three planes are sampled, and then combined by
+    /// [`write_external_texture_to_rgba`].
+    ///
+    /// [`write_external_texture_to_rgba`]: BlockContext::write_external_texture_to_rgba
+    pub(super) fn write_external_texture_sample(
+        &mut self,
+        image: Handle<crate::Expression>,
+        image_type_id: Word,
+        sampler_id: Word,
+        coordinate_id: Word,
+        block: &mut Block,
+    ) -> Result<Word, Error> {
+        let external = self.get_external_texture(image)?;
+        let plane_ids = [
+            self.get_handle_id(image),
+            external.plane_handle_ids[0],
+            external.plane_handle_ids[1],
+        ];
+
+        let mut texel_ids = [0; 3];
+        for (texel_id, &plane_id) in texel_ids.iter_mut().zip(plane_ids.iter()) {
+            *texel_id = self.write_sample_clamped_to_edge(
+                image_type_id,
+                plane_id,
+                sampler_id,
+                coordinate_id,
+                block,
+            )?;
+        }
+
+        self.write_external_texture_to_rgba(&external, texel_ids, block)
+    }
+
+    /// Generate code for loading a texel of an external texture.
+    ///
+    /// The coordinates are clamped to the size of the first plane, and scaled
+    /// to the sizes of the other planes, which may be subsampled.
+    pub(super) fn write_external_texture_load(
+        &mut self,
+        image: Handle<crate::Expression>,
+        coordinate: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<Word, Error> {
+        self.writer
+            .require_any("image queries", &[spirv::Capability::ImageQuery])?;
+
+        let external = self.get_external_texture(image)?;
+        let plane_ids = [
+            self.get_handle_id(image),
+            external.plane_handle_ids[0],
+            external.plane_handle_ids[1],
+        ];
+
+        let vec2_u32 = LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Bi),
+            scalar: crate::Scalar::U32,
+            pointer_space: None,
+        });
+        let vec2_u32_type_id = self.get_type_id(vec2_u32);
+        let vec4_f32_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Quad),
+            scalar: crate::Scalar::F32,
+            pointer_space: None,
+        }));
+        let zero_id = self.get_index_constant(0);
+
+        let mut coordinate_id = self.cached[coordinate];
+        if self.fun_info[coordinate]
+            .ty
+            .inner_with(&self.ir_module.types)
+            .scalar_kind()
+            == Some(crate::ScalarKind::Sint)
+        {
+            // Negative coordinates become huge, and are clamped below.
+            let bitcast_id = self.gen_id();
+            block.body.push(Instruction::unary(
+                spirv::Op::Bitcast,
+                vec2_u32_type_id,
+                bitcast_id,
+                coordinate_id,
+            ));
+            coordinate_id = bitcast_id;
+        }
+
+        let mut size_ids = [0; 3];
+        for (size_id, &plane_id) in size_ids.iter_mut().zip(plane_ids.iter()) {
+            *size_id = self.gen_id();
+            let mut inst = Instruction::image_query(
+                spirv::Op::ImageQuerySizeLod,
+                vec2_u32_type_id,
+                *size_id,
+                plane_id,
+            );
+            inst.add_operand(zero_id);
+            block.body.push(inst);
+        }
+
+        let one_id = self.get_index_constant(1);
+        let one_vec_id = self.writer.get_constant_composite(vec2_u32, &[one_id; 2]);
+        let max_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::ISub,
+            vec2_u32_type_id,
+            max_id,
+            size_ids[0],
+            one_vec_id,
+        ));
+        let clamped_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::UMin,
+            vec2_u32_type_id,
+            clamped_id,
+            &[coordinate_id, max_id],
+        ));
+
+        let mut texel_ids = [0; 3];
+        for (i, texel_id) in texel_ids.iter_mut().enumerate() {
+            let plane_coordinate_id = if i == 0 {
+                clamped_id
+            } else {
+                let scaled_id = self.gen_id();
+                block.body.push(Instruction::binary(
+                    spirv::Op::IMul,
+                    vec2_u32_type_id,
+                    scaled_id,
+                    clamped_id,
+                    size_ids[i],
+                ));
+                let id = self.gen_id();
+                block.body.push(Instruction::binary(
+                    spirv::Op::UDiv,
+                    vec2_u32_type_id,
+                    id,
+                    scaled_id,
+                    size_ids[0],
+                ));
+                id
+            };
+
+            *texel_id = self.gen_id();
+            let mut inst = Instruction::image_fetch_or_read(
+                spirv::Op::ImageFetch,
+                vec4_f32_type_id,
+                *texel_id,
+                plane_ids[i],
+                plane_coordinate_id,
+            );
+            inst.add_operand(spirv::ImageOperands::LOD.bits());
+            inst.add_operand(zero_id);
+            block.body.push(inst);
+        }
+
+        self.write_external_texture_to_rgba(&external, texel_ids, block)
+    }
+
+    /// Generate code for querying the size of an external texture.
+    ///
+    /// This is the `size` field of the parameters, rather than the size of
+    /// any of the planes.
+    pub(super) fn write_external_texture_size(
+        &mut self,
+        image: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<Word, Error> {
+        let external = self.get_external_texture(image)?;
+        self.write_external_texture_param(&external, PARAMS_SIZE, block)
+    }
+
+    /// Combine the texels read from each plane of `external` into RGBA.
+    ///
+    /// With one plane, its texel is already RGBA. Otherwise, the first plane
+    /// holds Y, and either the second holds interleaved UV or the second and
+    /// third hold U and V. The YUV values are converted with the parameters'
+    /// conversion matrix.
+    fn write_external_texture_to_rgba(
+        &mut self,
+        external: &ExternalTextureGlobal,
+        texel_ids: [Word; 3],
+        block: &mut Block,
+    ) -> Result<Word, Error> {
+        let f32_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            scalar: crate::Scalar::F32,
+            pointer_space: None,
+        }));
+        let vec4_f32_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Quad),
+            scalar: crate::Scalar::F32,
+            pointer_space: None,
+        }));
+        let bool_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            scalar: crate::Scalar::BOOL,
+            pointer_space: None,
+        }));
+        let vec4_bool_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Quad),
+            scalar: crate::Scalar::BOOL,
+            pointer_space: None,
+        }));
+
+        let num_planes_id =
+            self.write_external_texture_param(external, PARAMS_NUM_PLANES, block)?;
+        let matrix_id = self.write_external_texture_param(external, PARAMS_MATRIX, block)?;
+
+        let y_id = self.gen_id();
+        block.body.push(Instruction::composite_extract(
+            f32_type_id,
+            y_id,
+            texel_ids[0],
+            &[0],
+        ));
+        let u_id = self.gen_id();
+        block.body.push(Instruction::composite_extract(
+            f32_type_id,
+            u_id,
+            texel_ids[1],
+            &[0],
+        ));
+        let interleaved_v_id = self.gen_id();
+        block.body.push(Instruction::composite_extract(
+            f32_type_id,
+            interleaved_v_id,
+            texel_ids[1],
+            &[1],
+        ));
+        let separate_v_id = self.gen_id();
+        block.body.push(Instruction::composite_extract(
+            f32_type_id,
+            separate_v_id,
+            texel_ids[2],
+            &[0],
+        ));
+
+        let two_id = self.get_index_constant(2);
+        let is_interleaved_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IEqual,
+            bool_type_id,
+            is_interleaved_id,
+            num_planes_id,
+            two_id,
+        ));
+        let v_id = self.gen_id();
+        block.body.push(Instruction::select(
+            f32_type_id,
+            v_id,
+            is_interleaved_id,
+            interleaved_v_id,
+            separate_v_id,
+        ));
+
+        let one_id = self.writer.get_constant_scalar(crate::Literal::F32(1.0));
+        let yuv_id = self.gen_id();
+        block.body.push(Instruction::composite_construct(
+            vec4_f32_type_id,
+            yuv_id,
+            &[y_id, u_id, v_id, one_id],
+        ));
+        let converted_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::MatrixTimesVector,
+            vec4_f32_type_id,
+            converted_id,
+            matrix_id,
+            yuv_id,
+        ));
+
+        // `OpSelect` only accepts a scalar condition for vector operands
+        // since SPIR-V 1.4, so splat it.
+        let one_plane_id = self.get_index_constant(1);
+        let is_rgba_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::IEqual,
+            bool_type_id,
+            is_rgba_id,
+            num_planes_id,
+            one_plane_id,
+        ));
+        let is_rgba_vec_id = self.gen_id();
+        block.body.push(Instruction::composite_construct(
+            vec4_bool_type_id,
+            is_rgba_vec_id,
+            &[is_rgba_id; 4],
+        ));
+        let id = self.gen_id();
+        block.body.push(Instruction::select(
+            vec4_f32_type_id,
+            id,
+            is_rgba_vec_id,
+            texel_ids[0],
+            converted_id,
+        ));
+
+        Ok(id)
+    }
+}
@@ -103,6 +103,10 @@ struct Load {
 
     /// The id of the image being accessed.
     image_id: Word,
+
+    /// The id of the scope the texel is made visible to, for storage images
+    /// under the Vulkan memory model.
+    texel_scope_id: Option<Word>,
 }
 
 impl Load {
@@ -114,9 +118,9 @@ impl Load {
     ) -> Result<Load, Error> {
         let opcode = match image_class {
             crate::ImageClass::Storage { .. } => spirv::Op::ImageRead,
-            crate::ImageClass::Depth { .. } | crate::ImageClass::Sampled { .. } => {
-                spirv::Op::ImageFetch
-            }
+            crate::ImageClass::Depth { .. }
+            | crate::ImageClass::Sampled { .. }
+            | crate::ImageClass::External => spirv::Op::ImageFetch,
         };
 
         // `OpImageRead` and `OpImageFetch` instructions produce vec4<f32>
@@ -135,10 +139,16 @@ impl Load {
             _ => result_type_id,
         };
 
+        let texel_scope_id = match image_class {
+            crate::ImageClass::Storage { .. } => ctx.writer.storage_texel_scope(),
+            _ => None,
+        };
+
         Ok(Load {
             opcode,
             type_id,
             image_id,
+            texel_scope_id,
         })
     }
 }
@@ -164,19 +174,32 @@ impl Access for Load {
             coordinates_id,
         );
 
+        let mut mask = spirv::ImageOperands::empty();
+        let mut operands = Vec::new();
         match (level_id, sample_id) {
             (None, None) => {}
             (Some(level_id), None) => {
-                instruction.add_operand(spirv::ImageOperands::LOD.bits());
-                instruction.add_operand(level_id);
+                mask |= spirv::ImageOperands::LOD;
+                operands.push(level_id);
             }
             (None, Some(sample_id)) => {
-                instruction.add_operand(spirv::ImageOperands::SAMPLE.bits());
-                instruction.add_operand(sample_id);
+                mask |= spirv::ImageOperands::SAMPLE;
+                operands.push(sample_id);
             }
             // There's no such thing as a multi-sampled mipmap.
             (Some(_), Some(_)) => unreachable!(),
         }
+        if let Some(scope_id) = self.texel_scope_id {
+            mask |=
+                spirv::ImageOperands::MAKE_TEXEL_VISIBLE | spirv::ImageOperands::NON_PRIVATE_TEXEL;
+            operands.push(scope_id);
+        }
+        if !mask.is_empty() {
+            instruction.add_operand(mask.bits());
+            for operand in operands {
+                instruction.add_operand(operand);
+            }
+        }
 
         block.body.push(instruction);
 
@@ -201,6 +224,10 @@ struct Store {
 
     /// The value we're going to write to the texel.
     value_id: Word,
+
+    /// The id of the scope the texel is made available to, under the Vulkan
+    /// memory model.
+    texel_scope_id: Option<Word>,
 }
 
 impl Access for Store {
@@ -215,11 +242,15 @@ impl Access for Store {
         _sample_id: Option<Word>,
         block: &mut Block,
     ) {
-        block.body.push(Instruction::image_write(
-            self.image_id,
-            coordinates_id,
-            self.value_id,
-        ));
+        let mut instruction =
+            Instruction::image_write(self.image_id, coordinates_id, self.value_id);
+        if let Some(scope_id) = self.texel_scope_id {
+            let mask = spirv::ImageOperands::MAKE_TEXEL_AVAILABLE
+                | spirv::ImageOperands::NON_PRIVATE_TEXEL;
+            instruction.add_operand(mask.bits());
+            instruction.add_operand(scope_id);
+        }
+        block.body.push(instruction);
     }
 
     /// Stores don't generate any value, so this just returns `()`.
@@ -747,6 +778,9 @@ impl<'w> BlockContext<'w> {
             crate::TypeInner::Image { class, .. } => class,
             _ => return Err(Error::Validation("image type")),
         };
+        if image_class == crate::ImageClass::External {
+            return self.write_external_texture_load(image, coordinate, block);
+        }
 
         let access = Load::from_image_expr(self, image_id, image_class, result_type_id)?;
         let coordinates = self.write_image_coordinates(coordinate, array_index, block)?;
@@ -996,6 +1030,147 @@ impl<'w> BlockContext<'w> {
         Ok(id)
     }
 
+    /// Generate code for an `ImageSample` expression with `clamp_to_edge` set.
+    ///
+    /// WGSL's `textureSampleBaseClampToEdge` samples the base level, with the
+    /// coordinates clamped to half a texel inside the edges of the image.
+    pub(super) fn write_image_sample_clamp_to_edge(
+        &mut self,
+        image: Handle<crate::Expression>,
+        sampler: Handle<crate::Expression>,
+        coordinate: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<Word, Error> {
+        let image_type = self.fun_info[image].ty.handle().unwrap();
+        let image_type_id = self.get_type_id(LookupType::Handle(image_type));
+        let sampler_id = self.get_handle_id(sampler);
+        let coordinate_id = self.cached[coordinate];
+
+        if let crate::TypeInner::Image {
+            class: crate::ImageClass::External,
+            ..
+        } = self.ir_module.types[image_type].inner
+        {
+            return self.write_external_texture_sample(
+                image,
+                image_type_id,
+                sampler_id,
+                coordinate_id,
+                block,
+            );
+        }
+
+        let image_id = self.get_handle_id(image);
+        self.write_sample_clamped_to_edge(image_type_id, image_id, sampler_id, coordinate_id, block)
+    }
+
+    /// Sample the base level of the 2D float image `image_id`, whose type is
+    /// `image_type_id`, at `coordinate_id` clamped to half a texel inside its
+    /// edges.
+    ///
+    /// Return the id of the `vec4<f32>` result.
+    pub(super) fn write_sample_clamped_to_edge(
+        &mut self,
+        image_type_id: Word,
+        image_id: Word,
+        sampler_id: Word,
+        coordinate_id: Word,
+        block: &mut Block,
+    ) -> Result<Word, Error> {
+        use super::instructions::SampleLod;
+
+        self.writer
+            .require_any("image queries", &[spirv::Capability::ImageQuery])?;
+
+        let vec2_f32 = LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Bi),
+            scalar: crate::Scalar::F32,
+            pointer_space: None,
+        });
+        let vec2_f32_type_id = self.get_type_id(vec2_f32);
+        let vec2_u32_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Bi),
+            scalar: crate::Scalar::U32,
+            pointer_space: None,
+        }));
+        let vec4_f32_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: Some(crate::VectorSize::Quad),
+            scalar: crate::Scalar::F32,
+            pointer_space: None,
+        }));
+
+        // half_texel = vec2(0.5) / vec2<f32>(textureDimensions(image, 0))
+        let size_id = self.gen_id();
+        let mut inst = Instruction::image_query(
+            spirv::Op::ImageQuerySizeLod,
+            vec2_u32_type_id,
+            size_id,
+            image_id,
+        );
+        inst.add_operand(self.get_index_constant(0));
+        block.body.push(inst);
+        let float_size_id = self.gen_id();
+        block.body.push(Instruction::unary(
+            spirv::Op::ConvertUToF,
+            vec2_f32_type_id,
+            float_size_id,
+            size_id,
+        ));
+        let half_id = self.writer.get_constant_scalar(crate::Literal::F32(0.5));
+        let half_vec_id = self.writer.get_constant_composite(vec2_f32, &[half_id; 2]);
+        let one_id = self.writer.get_constant_scalar(crate::Literal::F32(1.0));
+        let one_vec_id = self.writer.get_constant_composite(vec2_f32, &[one_id; 2]);
+        let min_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::FDiv,
+            vec2_f32_type_id,
+            min_id,
+            half_vec_id,
+            float_size_id,
+        ));
+        let max_id = self.gen_id();
+        block.body.push(Instruction::binary(
+            spirv::Op::FSub,
+            vec2_f32_type_id,
+            max_id,
+            one_vec_id,
+            min_id,
+        ));
+        let clamped_id = self.gen_id();
+        block.body.push(Instruction::ext_inst(
+            self.writer.gl450_ext_inst_id,
+            spirv::GLOp::FClamp,
+            vec2_f32_type_id,
+            clamped_id,
+            &[coordinate_id, min_id, max_id],
+        ));
+
+        let sampled_image_type_id =
+            self.get_type_id(LookupType::Local(LocalType::SampledImage { image_type_id }));
+        let sampled_image_id = self.gen_id();
+        block.body.push(Instruction::sampled_image(
+            sampled_image_type_id,
+            sampled_image_id,
+            image_id,
+            sampler_id,
+        ));
+
+        let id = self.gen_id();
+        let mut inst = Instruction::image_sample(
+            vec4_f32_type_id,
+            id,
+            SampleLod::Explicit,
+            sampled_image_id,
+            clamped_id,
+            None,
+        );
+        inst.add_operand(spirv::ImageOperands::LOD.bits());
+        inst.add_operand(self.writer.get_constant_scalar(crate::Literal::F32(0.0)));
+        block.body.push(inst);
+
+        Ok(id)
+    }
+
     /// Generate code for an `ImageQuery` expression.
     ///
     /// The arguments are the components of an `Expression::ImageQuery` variant.
@@ -1021,6 +1196,10 @@ impl<'w> BlockContext<'w> {
             }
         };
 
+        if class == Ic::External {
+            return self.write_external_texture_size(image, block);
+        }
+
         self.writer
             .require_any("image queries", &[spirv::Capability::ImageQuery])?;
 
@@ -1161,7 +1340,11 @@ impl<'w> BlockContext<'w> {
         let coordinates = self.write_image_coordinates(coordinate, array_index, block)?;
         let value_id = self.cached[value];
 
-        let write = Store { image_id, value_id };
+        let write = Store {
+            image_id,
+            value_id,
+            texel_scope_id: self.writer.storage_texel_scope(),
+        };
 
         match *self.fun_info[image].ty.inner_with(&self.ir_module.types) {
             crate::TypeInner::Image {
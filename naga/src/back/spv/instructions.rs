@@ -826,6 +826,24 @@ impl super::Instruction {
         instruction
     }
 
+    /// An `OpSDot` or `OpUDot` on two 32-bit integers holding four packed
+    /// 8-bit components each.
+    pub(super) fn dot_packed_4x8(
+        op: Op,
+        result_type_id: Word,
+        id: Word,
+        operand_1: Word,
+        operand_2: Word,
+    ) -> Self {
+        let mut instruction = Self::new(op);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(operand_1);
+        instruction.add_operand(operand_2);
+        instruction.add_operand(spirv::PackedVectorFormat::PackedVectorFormat4x8Bit as u32);
+        instruction
+    }
+
     pub(super) fn ternary(
         op: Op,
         result_type_id: Word,
@@ -1006,6 +1024,10 @@ impl super::Instruction {
         Self::new(Op::Kill)
     }
 
+    pub(super) const fn demote_to_helper_invocation() -> Self {
+        Self::new(Op::DemoteToHelperInvocation)
+    }
+
     pub(super) const fn return_void() -> Self {
         Self::new(Op::Return)
     }
@@ -1037,6 +1059,33 @@ impl super::Instruction {
         instruction.add_operand(semantics_id);
         instruction
     }
+
+    //
+    //  Mesh Shading Instructions
+    //
+
+    pub(super) fn emit_mesh_tasks(
+        group_count_x_id: Word,
+        group_count_y_id: Word,
+        group_count_z_id: Word,
+        payload_id: Option<Word>,
+    ) -> Self {
+        let mut instruction = Self::new(Op::EmitMeshTasksEXT);
+        instruction.add_operand(group_count_x_id);
+        instruction.add_operand(group_count_y_id);
+        instruction.add_operand(group_count_z_id);
+        if let Some(payload_id) = payload_id {
+            instruction.add_operand(payload_id);
+        }
+        instruction
+    }
+
+    pub(super) fn set_mesh_outputs(vertex_count_id: Word, primitive_count_id: Word) -> Self {
+        let mut instruction = Self::new(Op::SetMeshOutputsEXT);
+        instruction.add_operand(vertex_count_id);
+        instruction.add_operand(primitive_count_id);
+        instruction
+    }
 }
 
 impl From<crate::StorageFormat> for spirv::ImageFormat {
@@ -0,0 +1,122 @@
+/*!
+Generating SPIR-V for task and mesh shader operations.
+*/
+
+use super::{Block, BlockContext, Error, Instruction, WriterFlags};
+
+impl<'w> BlockContext<'w> {
+    pub(super) fn write_mesh_function(
+        &mut self,
+        function: &crate::MeshFunction,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        let (index, value, vertex) = match *function {
+            crate::MeshFunction::SetMeshOutputs {
+                vertex_count,
+                primitive_count,
+            } => {
+                block.body.push(Instruction::set_mesh_outputs(
+                    self.cached[vertex_count],
+                    self.cached[primitive_count],
+                ));
+                return Ok(());
+            }
+            crate::MeshFunction::SetVertex { index, value } => (index, value, true),
+            crate::MeshFunction::SetPrimitive { index, value } => (index, value, false),
+        };
+
+        let outputs = self
+            .function
+            .entry_point_context
+            .as_ref()
+            .and_then(|context| context.mesh_outputs.as_ref())
+            .ok_or(Error::FeatureNotImplemented(
+                "mesh shader outputs written outside of the entry point",
+            ))?;
+        let members = if vertex {
+            &outputs.vertex_members
+        } else {
+            &outputs.primitive_members
+        };
+        let members: Vec<_> = members
+            .iter()
+            .map(|member| {
+                (
+                    member.id,
+                    member.type_id,
+                    member.pointer_type_id,
+                    member.built_in,
+                )
+            })
+            .collect();
+
+        // Scatter the members of `value` into the per-member output arrays.
+        let index_id = self.cached[index];
+        let value_id = self.cached[value];
+        for (member_index, (array_id, type_id, pointer_type_id, built_in)) in
+            members.into_iter().enumerate()
+        {
+            let member_value_id = self.gen_id();
+            block.body.push(Instruction::composite_extract(
+                type_id,
+                member_value_id,
+                value_id,
+                &[member_index as u32],
+            ));
+            let pointer_id = self.gen_id();
+            block.body.push(Instruction::access_chain(
+                pointer_type_id,
+                pointer_id,
+                array_id,
+                &[index_id],
+            ));
+            block
+                .body
+                .push(Instruction::store(pointer_id, member_value_id, None));
+
+            if let Some(crate::BuiltIn::Position { .. }) = built_in {
+                if self
+                    .writer
+                    .flags
+                    .contains(WriterFlags::ADJUST_COORDINATE_SPACE)
+                {
+                    self.writer
+                        .write_epilogue_position_y_flip(pointer_id, &mut block.body)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generate the `OpEmitMeshTasksEXT` instruction that ends a task shader.
+    ///
+    /// The `value_id` is the grid size the entry point returned, or `None` to
+    /// launch no mesh shader workgroups.
+    pub(super) fn write_task_return(
+        &mut self,
+        value_id: Option<spirv::Word>,
+        payload_id: Option<spirv::Word>,
+        block: &mut Block,
+    ) -> Instruction {
+        let uint_type_id = self.writer.get_uint_type_id();
+        let mut group_count_ids = [0; 3];
+        for (component, group_count_id) in group_count_ids.iter_mut().enumerate() {
+            *group_count_id = match value_id {
+                Some(value_id) => {
+                    let id = self.gen_id();
+                    block.body.push(Instruction::composite_extract(
+                        uint_type_id,
+                        id,
+                        value_id,
+                        &[component as u32],
+                    ));
+                    id
+                }
+                None => self.get_index_constant(0),
+            };
+        }
+        let [x, y, z] = group_count_ids;
+        Instruction::emit_mesh_tasks(x, y, z, payload_id)
+    }
+}
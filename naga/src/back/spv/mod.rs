@@ -5,11 +5,13 @@ Backend for [SPIR-V][spv] (Standard Portable Intermediate Representation).
 */
 
 mod block;
+mod external_texture;
 mod helpers;
 mod image;
 mod index;
 mod instructions;
 mod layout;
+mod mesh;
 mod ray;
 mod recyclable;
 mod selection;
@@ -130,9 +132,34 @@ struct ResultMember {
     built_in: Option<crate::BuiltIn>,
 }
 
+/// One member of a mesh shader's vertex or primitive output type.
+///
+/// Each member is written to its own `Output` array variable, indexed by
+/// vertex or primitive.
+struct MeshOutputMember {
+    /// The id of the `Output` array variable.
+    id: Word,
+    /// The type id of the member.
+    type_id: Word,
+    /// The id of the pointer type to one element of the array.
+    pointer_type_id: Word,
+    built_in: Option<crate::BuiltIn>,
+}
+
+struct MeshOutputs {
+    vertex_members: Vec<MeshOutputMember>,
+    primitive_members: Vec<MeshOutputMember>,
+}
+
 struct EntryPointContext {
     argument_ids: Vec<Word>,
     results: Vec<ResultMember>,
+    /// For task shaders, the id of the payload variable passed to
+    /// `OpEmitMeshTasksEXT`, if there is one.
+    task_payload: Option<Option<Word>>,
+    /// For mesh shaders, the variables the vertices and primitives are
+    /// written to.
+    mesh_outputs: Option<MeshOutputs>,
 }
 
 #[derive(Default)]
@@ -222,6 +249,13 @@ impl LocalImageType {
                 flags: make_flags(false, ImageTypeFlags::empty()),
                 image_format: format.into(),
             },
+            // Each plane of an external texture is an ordinary 2D float texture.
+            crate::ImageClass::External => LocalImageType {
+                sampled_type: crate::ScalarKind::Float,
+                dim,
+                flags: make_flags(false, ImageTypeFlags::SAMPLED),
+                image_format: spirv::ImageFormat::Unknown,
+            },
         }
     }
 }
@@ -308,6 +342,18 @@ enum LocalType {
         base: Handle<crate::Type>,
         size: u32,
     },
+    /// An array holding one member of a mesh shader's output type for each
+    /// vertex or primitive.
+    MeshOutputArray {
+        base: Handle<crate::Type>,
+        size: u32,
+    },
+    /// A pointer to a [`LocalType::MeshOutputArray`] in the `Output` storage
+    /// class.
+    PointerToMeshOutputArray {
+        base: Handle<crate::Type>,
+        size: u32,
+    },
     AccelerationStructure,
     RayQuery,
 }
@@ -495,6 +541,11 @@ struct GlobalVariable {
     /// to refer to the global in the function body. This is the id of that access,
     /// updated for each function in `write_function`.
     access_id: Word,
+
+    /// The additional resources backing an [`External`] image global.
+    ///
+    /// [`External`]: crate::ImageClass::External
+    external_texture: Option<ExternalTextureGlobal>,
 }
 
 impl GlobalVariable {
@@ -503,6 +554,7 @@ impl GlobalVariable {
             var_id: 0,
             handle_id: 0,
             access_id: 0,
+            external_texture: None,
         }
     }
 
@@ -511,6 +563,7 @@ impl GlobalVariable {
             var_id: id,
             handle_id: 0,
             access_id: 0,
+            external_texture: None,
         }
     }
 
@@ -518,9 +571,33 @@ impl GlobalVariable {
     fn reset_for_function(&mut self) {
         self.handle_id = 0;
         self.access_id = 0;
+        if let Some(ref mut external) = self.external_texture {
+            external.plane_handle_ids = [0; 2];
+            external.params_access_id = 0;
+        }
     }
 }
 
+/// The SPIR-V variables that an [`External`] image global is lowered into,
+/// besides the first plane, which uses the [`GlobalVariable`] itself.
+///
+/// [`External`]: crate::ImageClass::External
+#[derive(Clone, Copy)]
+struct ExternalTextureGlobal {
+    /// IDs of the `OpVariable`s for the second and third planes.
+    plane_var_ids: [Word; 2],
+
+    /// ID of the `OpVariable` for the `Block`-wrapped parameters uniform.
+    params_var_id: Word,
+
+    /// `OpLoad`s of `plane_var_ids`, recorded in the function prelude.
+    plane_handle_ids: [Word; 2],
+
+    /// `OpAccessChain` to the parameters struct inside its wrapper,
+    /// recorded in the function prelude.
+    params_access_id: Word,
+}
+
 struct FunctionArgument {
     /// Actual instruction of the argument.
     instruction: Instruction,
@@ -576,6 +653,19 @@ impl BlockContext<'_> {
         self.writer
             .get_constant_scalar(crate::Literal::I32(scope as _))
     }
+
+    /// Return the memory access operands for non-atomic loads and stores
+    /// through `pointer`.
+    fn pointer_memory_access(
+        &self,
+        pointer: Handle<crate::Expression>,
+    ) -> Option<spirv::MemoryAccess> {
+        let space = self.fun_info[pointer]
+            .ty
+            .inner_with(&self.ir_module.types)
+            .pointer_space()?;
+        self.writer.memory_access(space)
+    }
 }
 
 #[derive(Clone, Copy, Default)]
@@ -645,6 +735,21 @@ bitflags::bitflags! {
         const FORCE_POINT_SIZE = 0x8;
         /// Clamp `BuiltIn::FragDepth` output between 0 and 1.
         const CLAMP_FRAG_DEPTH = 0x10;
+        /// Declare the Vulkan memory model instead of `GLSL450`.
+        ///
+        /// Barriers make memory available and visible explicitly, loads and
+        /// stores of workgroup and storage memory are `NonPrivatePointer`
+        /// accesses so they take part in that, and storage image accesses
+        /// are `NonPrivateTexel` ones that make their texel visible or
+        /// available. This is core in SPIR-V 1.5;
+        /// earlier versions use `SPV_KHR_vulkan_memory_model`.
+        const VULKAN_MEMORY_MODEL = 0x20;
+        /// Write `discard` as `OpDemoteToHelperInvocation` rather than
+        /// `OpKill`, so the rest of the quad keeps valid derivatives.
+        ///
+        /// This is core in SPIR-V 1.6; earlier versions use
+        /// `SPV_EXT_demote_to_helper_invocation`.
+        const DEMOTE_TO_HELPER_INVOCATION = 0x40;
     }
 }
 
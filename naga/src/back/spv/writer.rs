@@ -2,9 +2,10 @@ use super::{
     block::DebugInfoInner,
     helpers::{contains_builtin, global_needs_wrapper, map_storage_class},
     make_local, Block, BlockContext, CachedConstant, CachedExpressions, DebugInfo,
-    EntryPointContext, Error, Function, FunctionArgument, GlobalVariable, IdGenerator, Instruction,
-    LocalType, LocalVariable, LogicalLayout, LookupFunctionType, LookupType, LoopContext, Options,
-    PhysicalLayout, PipelineOptions, ResultMember, Writer, WriterFlags, BITS_PER_BYTE,
+    EntryPointContext, Error, ExternalTextureGlobal, Function, FunctionArgument, GlobalVariable,
+    IdGenerator, Instruction, LocalType, LocalVariable, LogicalLayout, LookupFunctionType,
+    LookupType, LoopContext, Options, PhysicalLayout, PipelineOptions, ResultMember, Writer,
+    WriterFlags, BITS_PER_BYTE,
 };
 use crate::{
     arena::{Handle, UniqueArena},
@@ -18,6 +19,8 @@ use std::collections::hash_map::Entry;
 struct FunctionInterface<'a> {
     varying_ids: &'a mut Vec<Word>,
     stage: crate::ShaderStage,
+    mesh_info: Option<&'a crate::MeshStageInfo>,
+    task_payload: Option<Handle<crate::GlobalVariable>>,
 }
 
 impl Function {
@@ -176,6 +179,23 @@ impl Writer {
         }
     }
 
+    /// Return true if all of `capabilities` may be used, without requiring
+    /// them.
+    ///
+    /// Use this to choose between a native instruction and a polyfill.
+    pub(super) fn capabilities_allowed(&self, capabilities: &[spirv::Capability]) -> bool {
+        match self.capabilities_available {
+            None => true,
+            Some(ref available) => capabilities.iter().all(|cap| available.contains(cap)),
+        }
+    }
+
+    /// Return the SPIR-V version we're generating, as `(major, minor)`.
+    pub(super) const fn lang_version(&self) -> (u8, u8) {
+        let version = self.physical_layout.version;
+        ((version >> 16) as u8, (version >> 8) as u8)
+    }
+
     /// Indicate that the code uses the given extension.
     pub(super) fn use_extension(&mut self, extension: &'static str) {
         self.extensions_used.insert(extension);
@@ -336,6 +356,8 @@ impl Writer {
         let mut ep_context = EntryPointContext {
             argument_ids: Vec::new(),
             results: Vec::new(),
+            task_payload: None,
+            mesh_outputs: None,
         };
 
         let mut local_invocation_id = None;
@@ -343,6 +365,15 @@ impl Writer {
         let mut parameter_type_ids = Vec::with_capacity(ir_function.arguments.len());
         for argument in ir_function.arguments.iter() {
             let class = spirv::StorageClass::Input;
+            if let crate::TypeInner::Image {
+                class: crate::ImageClass::External,
+                ..
+            } = ir_module.types[argument.ty].inner
+            {
+                return Err(Error::FeatureNotImplemented(
+                    "external texture function arguments",
+                ));
+            }
             let handle_ty = ir_module.types[argument.ty].inner.is_handle();
             let argument_type_id = match handle_ty {
                 true => self.get_pointer_id(
@@ -446,7 +477,15 @@ impl Writer {
                 if let Some(ref mut iface) = interface {
                     let mut has_point_size = false;
                     let class = spirv::StorageClass::Output;
-                    if let Some(ref binding) = result.binding {
+                    if iface.stage == crate::ShaderStage::Task {
+                        // Task shaders return the size of the mesh shader
+                        // grid to launch, and pass it to
+                        // `OpEmitMeshTasksEXT` rather than an output variable.
+                        let payload_id = iface
+                            .task_payload
+                            .map(|handle| self.global_variables[handle.index()].var_id);
+                        ep_context.task_payload = Some(payload_id);
+                    } else if let Some(ref binding) = result.binding {
                         has_point_size |=
                             *binding == crate::Binding::BuiltIn(crate::BuiltIn::PointSize);
                         let type_id = self.get_type_id(LookupType::Handle(result.ty));
@@ -521,6 +560,13 @@ impl Writer {
             None => self.void_type,
         };
 
+        if let Some(ref mut iface) = interface {
+            if let Some(mesh_info) = iface.mesh_info {
+                ep_context.mesh_outputs =
+                    Some(self.write_mesh_outputs(ir_module, mesh_info, iface.varying_ids)?);
+            }
+        }
+
         let lookup_function_type = LookupFunctionType {
             parameter_type_ids,
             return_type_id,
@@ -559,6 +605,10 @@ impl Writer {
                 // Have to include global variables in the interface
                 if self.physical_layout.version >= 0x10400 {
                     iface.varying_ids.push(gv.var_id);
+                    if let Some(ref external) = gv.external_texture {
+                        iface.varying_ids.extend_from_slice(&external.plane_var_ids);
+                        iface.varying_ids.push(external.params_var_id);
+                    }
                 }
             }
 
@@ -578,6 +628,36 @@ impl Writer {
                     .push(Instruction::load(var_type_id, id, gv.var_id, None));
                 gv.access_id = gv.var_id;
                 gv.handle_id = id;
+
+                if let Some(ref mut external) = gv.external_texture {
+                    for (handle_id, &var_id) in external
+                        .plane_handle_ids
+                        .iter_mut()
+                        .zip(external.plane_var_ids.iter())
+                    {
+                        *handle_id = self.id_gen.next();
+                        prelude
+                            .body
+                            .push(Instruction::load(var_type_id, *handle_id, var_id, None));
+                    }
+
+                    let params_ty = ir_module.special_types.external_texture_params.ok_or(
+                        Error::Validation("missing external texture parameters type"),
+                    )?;
+                    let pointer_type_id = self.get_pointer_id(
+                        &ir_module.types,
+                        params_ty,
+                        spirv::StorageClass::Uniform,
+                    )?;
+                    let index_id = self.get_index_constant(0);
+                    external.params_access_id = self.id_gen.next();
+                    prelude.body.push(Instruction::access_chain(
+                        pointer_type_id,
+                        external.params_access_id,
+                        external.params_var_id,
+                        &[index_id],
+                    ));
+                }
             } else if global_needs_wrapper(ir_module, var) {
                 let class = map_storage_class(var.space);
                 let pointer_type_id = self.get_pointer_id(&ir_module.types, var.ty, class)?;
@@ -680,7 +760,10 @@ impl Writer {
                     super::ZeroInitializeWorkgroupMemoryMode::Polyfill,
                     Some(
                         ref mut interface @ FunctionInterface {
-                            stage: crate::ShaderStage::Compute,
+                            stage:
+                                crate::ShaderStage::Compute
+                                | crate::ShaderStage::Task
+                                | crate::ShaderStage::Mesh,
                             ..
                         },
                     ),
@@ -691,7 +774,7 @@ impl Writer {
                     local_invocation_id,
                     interface,
                     context.function,
-                ),
+                )?,
                 _ => None,
             };
 
@@ -742,6 +825,10 @@ impl Writer {
         ir_module: &crate::Module,
         debug_info: &Option<DebugInfoInner>,
     ) -> Result<Instruction, Error> {
+        if let crate::ShaderStage::Task | crate::ShaderStage::Mesh = entry_point.stage {
+            self.require_mesh_shading()?;
+        }
+
         let mut interface_ids = Vec::new();
         let function_id = self.write_function(
             &entry_point.function,
@@ -750,6 +837,8 @@ impl Writer {
             Some(FunctionInterface {
                 varying_ids: &mut interface_ids,
                 stage: entry_point.stage,
+                mesh_info: entry_point.mesh_info.as_ref(),
+                task_payload: entry_point.task_payload,
             }),
             debug_info,
         )?;
@@ -784,6 +873,41 @@ impl Writer {
                 .to_words(&mut self.logical_layout.execution_modes);
                 spirv::ExecutionModel::GLCompute
             }
+            crate::ShaderStage::Task | crate::ShaderStage::Mesh => {
+                Instruction::execution_mode(
+                    function_id,
+                    spirv::ExecutionMode::LocalSize,
+                    &entry_point.workgroup_size,
+                )
+                .to_words(&mut self.logical_layout.execution_modes);
+                match entry_point.mesh_info {
+                    Some(ref mesh_info) => {
+                        let topology_mode = match mesh_info.topology {
+                            crate::MeshOutputTopology::Points => spirv::ExecutionMode::OutputPoints,
+                            crate::MeshOutputTopology::Lines => {
+                                spirv::ExecutionMode::OutputLinesEXT
+                            }
+                            crate::MeshOutputTopology::Triangles => {
+                                spirv::ExecutionMode::OutputTrianglesEXT
+                            }
+                        };
+                        let modes = [
+                            (spirv::ExecutionMode::OutputVertices, mesh_info.max_vertices),
+                            (
+                                spirv::ExecutionMode::OutputPrimitivesEXT,
+                                mesh_info.max_primitives,
+                            ),
+                        ];
+                        for (mode, count) in modes {
+                            Instruction::execution_mode(function_id, mode, &[count])
+                                .to_words(&mut self.logical_layout.execution_modes);
+                        }
+                        self.write_execution_mode(function_id, topology_mode)?;
+                        spirv::ExecutionModel::MeshEXT
+                    }
+                    None => spirv::ExecutionModel::TaskEXT,
+                }
+            }
         };
         //self.check(exec_model.required_capabilities())?;
 
@@ -839,7 +963,7 @@ impl Writer {
             } => {
                 let sampled = match class {
                     crate::ImageClass::Sampled { .. } => true,
-                    crate::ImageClass::Depth { .. } => true,
+                    crate::ImageClass::Depth { .. } | crate::ImageClass::External => true,
                     crate::ImageClass::Storage { format, .. } => {
                         self.request_image_format_capabilities(format.into())?;
                         false
@@ -955,6 +1079,16 @@ impl Writer {
                 let class = map_storage_class(space);
                 Instruction::type_pointer(id, class, inner_ty)
             }
+            LocalType::MeshOutputArray { base, size } => {
+                let inner_ty = self.get_type_id(LookupType::Handle(base));
+                let scalar_id = self.get_constant_scalar(crate::Literal::U32(size));
+                Instruction::type_array(id, inner_ty, scalar_id)
+            }
+            LocalType::PointerToMeshOutputArray { base, size } => {
+                let inner_ty =
+                    self.get_type_id(LookupType::Local(LocalType::MeshOutputArray { base, size }));
+                Instruction::type_pointer(id, spirv::StorageClass::Output, inner_ty)
+            }
             LocalType::AccelerationStructure => Instruction::type_acceleration_structure(id),
             LocalType::RayQuery => Instruction::type_ray_query(id),
         };
@@ -1290,12 +1424,78 @@ impl Writer {
         Ok(id)
     }
 
-    pub(super) fn write_barrier(&mut self, flags: crate::Barrier, block: &mut Block) {
+    /// Return the memory access operands for non-atomic loads and stores
+    /// through a pointer into `space`.
+    ///
+    /// Under the Vulkan memory model, only `NonPrivatePointer` accesses are
+    /// made available and visible by barriers.
+    pub(super) const fn memory_access(
+        &self,
+        space: crate::AddressSpace,
+    ) -> Option<spirv::MemoryAccess> {
+        match space {
+            crate::AddressSpace::Storage { .. } | crate::AddressSpace::WorkGroup
+                if self.flags.contains(WriterFlags::VULKAN_MEMORY_MODEL) =>
+            {
+                Some(spirv::MemoryAccess::NON_PRIVATE_POINTER)
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the id of the scope storage image reads and writes make texels
+    /// visible and available to, if they need to.
+    ///
+    /// Under the Vulkan memory model, storage image accesses are
+    /// `NonPrivateTexel` so barriers order them, and make texels visible and
+    /// available at the `Workgroup` scope that `textureBarrier` uses.
+    pub(super) fn storage_texel_scope(&mut self) -> Option<Word> {
+        if self.flags.contains(WriterFlags::VULKAN_MEMORY_MODEL) {
+            Some(self.get_index_constant(spirv::Scope::Workgroup as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Check that memory operations can use `scope`.
+    ///
+    /// The Vulkan memory model needs an extra capability for `Device` scope.
+    pub(super) fn require_memory_scope(&mut self, scope: spirv::Scope) -> Result<(), Error> {
+        if scope == spirv::Scope::Device && self.flags.contains(WriterFlags::VULKAN_MEMORY_MODEL) {
+            self.require_any(
+                "device scope memory operations in the Vulkan memory model",
+                &[spirv::Capability::VulkanMemoryModelDeviceScope],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the memory semantics and scope for atomic operations on `space`.
+    pub(super) fn atomic_semantics_and_scope(
+        &mut self,
+        space: crate::AddressSpace,
+    ) -> Result<(spirv::MemorySemantics, spirv::Scope), Error> {
+        let (mut semantics, scope) = space.to_spirv_semantics_and_scope();
+        if self.flags.contains(WriterFlags::VULKAN_MEMORY_MODEL) {
+            // WGSL atomics are relaxed, and storage class semantics only
+            // mean something alongside an ordering.
+            semantics = spirv::MemorySemantics::NONE;
+            self.require_memory_scope(scope)?;
+        }
+        Ok((semantics, scope))
+    }
+
+    pub(super) fn write_barrier(
+        &mut self,
+        flags: crate::Barrier,
+        block: &mut Block,
+    ) -> Result<(), Error> {
         let memory_scope = if flags.contains(crate::Barrier::STORAGE) {
             spirv::Scope::Device
         } else {
             spirv::Scope::Workgroup
         };
+        self.require_memory_scope(memory_scope)?;
         let mut semantics = spirv::MemorySemantics::ACQUIRE_RELEASE;
         semantics.set(
             spirv::MemorySemantics::UNIFORM_MEMORY,
@@ -1305,6 +1505,20 @@ impl Writer {
             spirv::MemorySemantics::WORKGROUP_MEMORY,
             flags.contains(crate::Barrier::WORK_GROUP),
         );
+        semantics.set(
+            spirv::MemorySemantics::IMAGE_MEMORY,
+            flags.contains(crate::Barrier::TEXTURE),
+        );
+        if self.flags.contains(WriterFlags::VULKAN_MEMORY_MODEL) {
+            if flags.is_empty() {
+                // Ordering semantics need a storage class in the Vulkan
+                // memory model, so this is only an execution barrier.
+                semantics = spirv::MemorySemantics::NONE;
+            } else {
+                semantics |=
+                    spirv::MemorySemantics::MAKE_AVAILABLE | spirv::MemorySemantics::MAKE_VISIBLE;
+            }
+        }
         let exec_scope_id = self.get_index_constant(spirv::Scope::Workgroup as u32);
         let mem_scope_id = self.get_index_constant(memory_scope as u32);
         let semantics_id = self.get_index_constant(semantics.bits());
@@ -1313,6 +1527,7 @@ impl Writer {
             mem_scope_id,
             semantics_id,
         ));
+        Ok(())
     }
 
     fn generate_workgroup_vars_init_block(
@@ -1323,7 +1538,7 @@ impl Writer {
         local_invocation_id: Option<Word>,
         interface: &mut FunctionInterface,
         function: &mut Function,
-    ) -> Option<Word> {
+    ) -> Result<Option<Word>, Error> {
         let body = ir_module
             .global_variables
             .iter()
@@ -1337,12 +1552,13 @@ impl Writer {
                 let var_id = self.global_variables[handle.index()].var_id;
                 let var_type_id = self.get_type_id(LookupType::Handle(var.ty));
                 let init_word = self.get_constant_null(var_type_id);
-                Instruction::store(var_id, init_word, None)
+                let memory_access = self.memory_access(var.space);
+                Instruction::store(var_id, init_word, memory_access)
             })
             .collect::<Vec<_>>();
 
         if body.is_empty() {
-            return None;
+            return Ok(None);
         }
 
         let uint3_type_id = self.get_uint3_type_id();
@@ -1415,11 +1631,11 @@ impl Writer {
 
         let mut post_if_block = Block::new(merge_id);
 
-        self.write_barrier(crate::Barrier::WORK_GROUP, &mut post_if_block);
+        self.write_barrier(crate::Barrier::WORK_GROUP, &mut post_if_block)?;
 
         let next_id = self.id_gen.next();
         function.consume(post_if_block, Instruction::branch(next_id));
-        Some(next_id)
+        Ok(Some(next_id))
     }
 
     /// Generate an `OpVariable` for one value in an [`EntryPoint`]'s IO interface.
@@ -1464,6 +1680,106 @@ impl Writer {
             }
         }
 
+        self.decorate_varying(ir_module, stage, class, id, ty, binding)?;
+        Ok(id)
+    }
+
+    /// Generate the `Output` array variables a mesh shader writes its
+    /// vertices and primitives to, adding them to `varying_ids`.
+    fn write_mesh_outputs(
+        &mut self,
+        ir_module: &crate::Module,
+        mesh_info: &crate::MeshStageInfo,
+        varying_ids: &mut Vec<Word>,
+    ) -> Result<super::MeshOutputs, Error> {
+        let class = spirv::StorageClass::Output;
+        let mut outputs = [Vec::new(), Vec::new()];
+        let types = [
+            (mesh_info.vertex_output_type, mesh_info.max_vertices),
+            (mesh_info.primitive_output_type, mesh_info.max_primitives),
+        ];
+        for (output, (ty, size)) in outputs.iter_mut().zip(types) {
+            let members = match ir_module.types[ty].inner {
+                crate::TypeInner::Struct { ref members, .. } => members,
+                _ => return Err(Error::Validation("mesh output type is not a struct")),
+            };
+            for member in members {
+                let binding = member
+                    .binding
+                    .as_ref()
+                    .ok_or(Error::Validation("mesh output member without a binding"))?;
+                let id = self.id_gen.next();
+                let array_pointer_type_id =
+                    self.get_type_id(LookupType::Local(LocalType::PointerToMeshOutputArray {
+                        base: member.ty,
+                        size,
+                    }));
+                Instruction::variable(array_pointer_type_id, id, class, None)
+                    .to_words(&mut self.logical_layout.declarations);
+
+                if self
+                    .flags
+                    .contains(WriterFlags::DEBUG | WriterFlags::LABEL_VARYINGS)
+                {
+                    if let Some(ref name) = member.name {
+                        self.debugs.push(Instruction::name(id, name));
+                    }
+                }
+
+                self.decorate_varying(
+                    ir_module,
+                    crate::ShaderStage::Mesh,
+                    class,
+                    id,
+                    member.ty,
+                    binding,
+                )?;
+                varying_ids.push(id);
+                output.push(super::MeshOutputMember {
+                    id,
+                    type_id: self.get_type_id(LookupType::Handle(member.ty)),
+                    pointer_type_id: self.get_pointer_id(&ir_module.types, member.ty, class)?,
+                    built_in: binding.to_built_in(),
+                });
+            }
+        }
+
+        let [vertex_members, primitive_members] = outputs;
+        Ok(super::MeshOutputs {
+            vertex_members,
+            primitive_members,
+        })
+    }
+
+    /// Indicate that the code uses mesh shading.
+    fn require_mesh_shading(&mut self) -> Result<(), Error> {
+        // `SPV_EXT_mesh_shader` requires SPIR-V 1.4.
+        if self.physical_layout.version < 0x10400 {
+            return Err(Error::FeatureNotImplemented(
+                "task and mesh shaders before SPIR-V 1.4",
+            ));
+        }
+        self.require_any(
+            "task and mesh shaders",
+            &[spirv::Capability::MeshShadingEXT],
+        )?;
+        self.use_extension("SPV_EXT_mesh_shader");
+        Ok(())
+    }
+
+    /// Decorate the interface variable `id`, holding values of type `ty`, to
+    /// represent `binding`.
+    ///
+    /// For mesh shader outputs, `ty` is the type of the array's elements.
+    fn decorate_varying(
+        &mut self,
+        ir_module: &crate::Module,
+        stage: crate::ShaderStage,
+        class: spirv::StorageClass,
+        id: Word,
+        ty: Handle<crate::Type>,
+        binding: &crate::Binding,
+    ) -> Result<(), Error> {
         use spirv::{BuiltIn, Decoration};
 
         match *binding {
@@ -1557,6 +1873,10 @@ impl Writer {
                     Bi::FragDepth => BuiltIn::FragDepth,
                     Bi::PointCoord => BuiltIn::PointCoord,
                     Bi::FrontFacing => BuiltIn::FrontFacing,
+                    Bi::PrimitiveIndex if stage == crate::ShaderStage::Mesh => {
+                        self.decorate(id, Decoration::PerPrimitiveEXT, &[]);
+                        BuiltIn::PrimitiveId
+                    }
                     Bi::PrimitiveIndex => {
                         self.require_any(
                             "`primitive_index` built-in",
@@ -1580,6 +1900,18 @@ impl Writer {
                     Bi::WorkGroupId => BuiltIn::WorkgroupId,
                     Bi::WorkGroupSize => BuiltIn::WorkgroupSize,
                     Bi::NumWorkGroups => BuiltIn::NumWorkgroups,
+                    // task shaders pass it to `OpEmitMeshTasksEXT` instead
+                    Bi::MeshTaskSize => {
+                        return Err(Error::Validation("`mesh_task_size` is not a varying"))
+                    }
+                    // mesh
+                    Bi::CullPrimitive => {
+                        self.decorate(id, Decoration::PerPrimitiveEXT, &[]);
+                        BuiltIn::CullPrimitiveEXT
+                    }
+                    Bi::PointIndex => BuiltIn::PrimitivePointIndicesEXT,
+                    Bi::LineIndices => BuiltIn::PrimitiveLineIndicesEXT,
+                    Bi::TriangleIndices => BuiltIn::PrimitiveTriangleIndicesEXT,
                 };
 
                 self.decorate(id, Decoration::BuiltIn, &[built_in as u32]);
@@ -1613,7 +1945,7 @@ impl Writer {
             }
         }
 
-        Ok(id)
+        Ok(())
     }
 
     fn write_global_variable(
@@ -1625,6 +1957,9 @@ impl Writer {
 
         let id = self.id_gen.next();
         let class = map_storage_class(global_variable.space);
+        if global_variable.space == crate::AddressSpace::TaskPayload {
+            self.require_mesh_shading()?;
+        }
 
         //self.check(class.required_capabilities())?;
 
@@ -1741,6 +2076,121 @@ impl Writer {
         Ok(id)
     }
 
+    /// Write the variables for the second and third planes and the parameters
+    /// uniform of an [`External`] image global.
+    ///
+    /// The first plane is the variable written by [`write_global_variable`].
+    ///
+    /// [`External`]: crate::ImageClass::External
+    /// [`write_global_variable`]: Writer::write_global_variable
+    fn write_external_texture_variables(
+        &mut self,
+        ir_module: &crate::Module,
+        global_variable: &crate::GlobalVariable,
+    ) -> Result<ExternalTextureGlobal, Error> {
+        use spirv::Decoration;
+
+        let res_binding = global_variable
+            .binding
+            .as_ref()
+            .ok_or(Error::Validation("external texture without a binding"))?;
+        let params_ty =
+            ir_module
+                .special_types
+                .external_texture_params
+                .ok_or(Error::Validation(
+                    "missing external texture parameters type",
+                ))?;
+
+        let plane_pointer_type_id = self.get_pointer_id(
+            &ir_module.types,
+            global_variable.ty,
+            spirv::StorageClass::UniformConstant,
+        )?;
+        let [plane1_binding, plane2_binding, params_binding] =
+            res_binding.external_texture_bindings();
+        let mut plane_var_ids = [0; 2];
+        for (i, (var_id, binding)) in plane_var_ids
+            .iter_mut()
+            .zip([plane1_binding, plane2_binding])
+            .enumerate()
+        {
+            *var_id = self.id_gen.next();
+            if self.flags.contains(WriterFlags::DEBUG) {
+                if let Some(ref name) = global_variable.name {
+                    self.debugs.push(Instruction::name(
+                        *var_id,
+                        &format!("{name}_plane{}", i + 1),
+                    ));
+                }
+            }
+            self.decorate(*var_id, Decoration::DescriptorSet, &[binding.group]);
+            self.decorate(*var_id, Decoration::Binding, &[binding.binding]);
+            Instruction::variable(
+                plane_pointer_type_id,
+                *var_id,
+                spirv::StorageClass::UniformConstant,
+                None,
+            )
+            .to_words(&mut self.logical_layout.declarations);
+        }
+
+        // Vulkan requires uniform buffers to be `Block`-decorated structs, so
+        // wrap the parameters the same way `write_global_variable` does.
+        let params_type_id = self.get_type_id(LookupType::Handle(params_ty));
+        let wrapper_type_id = self.id_gen.next();
+        self.decorate(wrapper_type_id, Decoration::Block, &[]);
+        let member = crate::StructMember {
+            name: None,
+            ty: params_ty,
+            binding: None,
+            offset: 0,
+        };
+        self.decorate_struct_member(wrapper_type_id, 0, &member, &ir_module.types)?;
+        Instruction::type_struct(wrapper_type_id, &[params_type_id])
+            .to_words(&mut self.logical_layout.declarations);
+
+        let pointer_type_id = self.id_gen.next();
+        Instruction::type_pointer(
+            pointer_type_id,
+            spirv::StorageClass::Uniform,
+            wrapper_type_id,
+        )
+        .to_words(&mut self.logical_layout.declarations);
+
+        let params_var_id = self.id_gen.next();
+        if self.flags.contains(WriterFlags::DEBUG) {
+            if let Some(ref name) = global_variable.name {
+                self.debugs
+                    .push(Instruction::name(params_var_id, &format!("{name}_params")));
+            }
+        }
+        self.decorate(
+            params_var_id,
+            Decoration::DescriptorSet,
+            &[params_binding.group],
+        );
+        self.decorate(
+            params_var_id,
+            Decoration::Binding,
+            &[params_binding.binding],
+        );
+        Instruction::variable(
+            pointer_type_id,
+            params_var_id,
+            spirv::StorageClass::Uniform,
+            None,
+        )
+        .to_words(&mut self.logical_layout.declarations);
+
+        Ok(ExternalTextureGlobal {
+            plane_var_ids,
+            params_var_id,
+            plane_handle_ids: [0; 2],
+            params_access_id: 0,
+        })
+    }
+
     /// Write the necessary decorations for a struct member.
     ///
     /// Emit decorations for the `index`'th member of the struct type
@@ -1930,7 +2380,16 @@ impl Writer {
                 }
                 _ => {
                     let id = self.write_global_variable(ir_module, var)?;
-                    GlobalVariable::new(id)
+                    let mut gvar = GlobalVariable::new(id);
+                    if let crate::TypeInner::Image {
+                        class: crate::ImageClass::External,
+                        ..
+                    } = ir_module.types[var.ty].inner
+                    {
+                        gvar.external_texture =
+                            Some(self.write_external_texture_variables(ir_module, var)?);
+                    }
+                    gvar
                 }
             };
             self.global_variables.push(gvar);
@@ -1977,6 +2436,20 @@ impl Writer {
             ep_instruction.to_words(&mut self.logical_layout.entry_points);
         }
 
+        let addressing_model = spirv::AddressingModel::Logical;
+        let memory_model = if self.flags.contains(WriterFlags::VULKAN_MEMORY_MODEL) {
+            self.require_any(
+                "the Vulkan memory model",
+                &[spirv::Capability::VulkanMemoryModel],
+            )?;
+            if self.lang_version() < (1, 5) {
+                self.use_extension("SPV_KHR_vulkan_memory_model");
+            }
+            spirv::MemoryModel::Vulkan
+        } else {
+            spirv::MemoryModel::GLSL450
+        };
+
         for capability in self.capabilities_used.iter() {
             Instruction::capability(*capability).to_words(&mut self.logical_layout.capabilities);
         }
@@ -1989,8 +2462,6 @@ impl Writer {
                 .to_words(&mut self.logical_layout.capabilities);
         }
 
-        let addressing_model = spirv::AddressingModel::Logical;
-        let memory_model = spirv::MemoryModel::GLSL450;
         //self.check(addressing_model.required_capabilities())?;
         //self.check(memory_model.required_capabilities())?;
 
@@ -20,6 +20,7 @@ enum Attribute {
     SecondBlendSource,
     Stage(ShaderStage),
     WorkGroupSize([u32; 3]),
+    OutputTopology(crate::MeshOutputTopology),
 }
 
 /// The WGSL form that `write_expr_with_indirection` should use to render a Naga
@@ -62,7 +63,7 @@ bitflags::bitflags! {
 }
 
 pub struct Writer<W> {
-    out: W,
+    out: back::SourceMapWriter<W>,
     flags: WriterFlags,
     names: crate::FastHashMap<NameKey, String>,
     namer: proc::Namer,
@@ -73,7 +74,7 @@ pub struct Writer<W> {
 impl<W: Write> Writer<W> {
     pub fn new(out: W, flags: WriterFlags) -> Self {
         Writer {
-            out,
+            out: back::SourceMapWriter::new(out),
             flags,
             names: crate::FastHashMap::default(),
             namer: proc::Namer::default(),
@@ -98,11 +99,21 @@ impl<W: Write> Writer<W> {
     }
 
     fn is_builtin_wgsl_struct(&self, module: &Module, handle: Handle<crate::Type>) -> bool {
-        module
-            .special_types
-            .predeclared_types
-            .values()
-            .any(|t| *t == handle)
+        // The external texture parameters only exist for the other backends'
+        // sake; `texture_external` is a WGSL type in its own right.
+        module.special_types.external_texture_params == Some(handle)
+            || module
+                .special_types
+                .predeclared_types
+                .values()
+                .any(|t| *t == handle)
+    }
+
+    /// Return the [`SourceMap`] for the output written so far.
+    ///
+    /// [`SourceMap`]: back::SourceMap
+    pub const fn source_map(&self) -> &back::SourceMap {
+        self.out.source_map()
     }
 
     pub fn write(&mut self, module: &Module, info: &valid::ModuleInfo) -> BackendResult {
@@ -163,6 +174,7 @@ impl<W: Write> Writer<W> {
             };
 
             // Write the function
+            self.out.mark(module.functions.get_span(handle));
             self.write_function(module, function, &func_ctx)?;
 
             writeln!(self.out)?;
@@ -172,13 +184,25 @@ impl<W: Write> Writer<W> {
         for (index, ep) in module.entry_points.iter().enumerate() {
             let attributes = match ep.stage {
                 ShaderStage::Vertex | ShaderStage::Fragment => vec![Attribute::Stage(ep.stage)],
-                ShaderStage::Compute => vec![
-                    Attribute::Stage(ShaderStage::Compute),
+                ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh => vec![
+                    Attribute::Stage(ep.stage),
                     Attribute::WorkGroupSize(ep.workgroup_size),
                 ],
             };
 
             self.write_attributes(&attributes)?;
+            if let Some(ref mesh_info) = ep.mesh_info {
+                self.write_attributes(&[Attribute::OutputTopology(mesh_info.topology)])?;
+                write!(self.out, "@vertex_output(")?;
+                self.write_type(module, mesh_info.vertex_output_type)?;
+                write!(self.out, ", {}) @primitive_output(", mesh_info.max_vertices)?;
+                self.write_type(module, mesh_info.primitive_output_type)?;
+                write!(self.out, ", {}) ", mesh_info.max_primitives)?;
+            }
+            if let Some(payload) = ep.task_payload {
+                let name = &self.names[&NameKey::GlobalVariable(payload)];
+                write!(self.out, "@payload({name}) ")?;
+            }
             // Add a newline after attribute
             writeln!(self.out)?;
 
@@ -207,6 +231,8 @@ impl<W: Write> Writer<W> {
             if let Some(&(stage, _)) = self.ep_results.iter().find(|&&(_, ty)| ty == handle) {
                 let name = match stage {
                     ShaderStage::Compute => "ComputeOutput",
+                    ShaderStage::Task => "TaskOutput",
+                    ShaderStage::Mesh => "MeshOutput",
                     ShaderStage::Fragment => "FragmentOutput",
                     ShaderStage::Vertex => "VertexOutput",
                 };
@@ -304,7 +330,8 @@ impl<W: Write> Writer<W> {
         }
 
         // Write the function body (statement list)
-        for sta in func.body.iter() {
+        for (sta, span) in func.body.span_iter() {
+            self.out.mark(*span);
             // The indentation should always be 1 when writing the function body
             self.write_stmt(module, sta, func_ctx, back::Level(1))?;
         }
@@ -331,6 +358,8 @@ impl<W: Write> Writer<W> {
                         ShaderStage::Vertex => "vertex",
                         ShaderStage::Fragment => "fragment",
                         ShaderStage::Compute => "compute",
+                        ShaderStage::Task => "task",
+                        ShaderStage::Mesh => "mesh",
                     };
                     write!(self.out, "@{stage_str} ")?;
                 }
@@ -341,6 +370,14 @@ impl<W: Write> Writer<W> {
                         size[0], size[1], size[2]
                     )?;
                 }
+                Attribute::OutputTopology(topology) => {
+                    let topology_str = match topology {
+                        crate::MeshOutputTopology::Points => "points",
+                        crate::MeshOutputTopology::Lines => "lines",
+                        crate::MeshOutputTopology::Triangles => "triangles",
+                    };
+                    write!(self.out, "@output_topology({topology_str}) ")?;
+                }
                 Attribute::Binding(id) => write!(self.out, "@binding({id}) ")?,
                 Attribute::Group(id) => write!(self.out, "@group({id}) ")?,
                 Attribute::Invariant => write!(self.out, "@invariant ")?,
@@ -438,6 +475,12 @@ impl<W: Write> Writer<W> {
             TypeInner::Sampler { comparison: true } => {
                 write!(self.out, "sampler_comparison")?;
             }
+            TypeInner::Image {
+                class: crate::ImageClass::External,
+                ..
+            } => {
+                write!(self.out, "texture_external")?;
+            }
             TypeInner::Image {
                 dim,
                 arrayed,
@@ -458,6 +501,8 @@ impl<W: Write> Writer<W> {
                     Ic::Depth { multi } => {
                         ("depth_", if multi { "multisampled_" } else { "" }, "", "")
                     }
+                    // Handled above.
+                    Ic::External => unreachable!(),
                     Ic::Storage { format, access } => (
                         "storage_",
                         "",
@@ -661,7 +706,8 @@ impl<W: Write> Writer<W> {
                 writeln!(self.out, " {{")?;
 
                 let l2 = level.next();
-                for sta in accept {
+                for (sta, span) in accept.span_iter() {
+                    self.out.mark(*span);
                     // Increase indentation to help with readability
                     self.write_stmt(module, sta, func_ctx, l2)?;
                 }
@@ -671,7 +717,8 @@ impl<W: Write> Writer<W> {
                 if !reject.is_empty() {
                     writeln!(self.out, "{level}}} else {{")?;
 
-                    for sta in reject {
+                    for (sta, span) in reject.span_iter() {
+                        self.out.mark(*span);
                         // Increase indentation to help with readability
                         self.write_stmt(module, sta, func_ctx, l2)?;
                     }
@@ -795,7 +842,8 @@ impl<W: Write> Writer<W> {
             Statement::Block(ref block) => {
                 write!(self.out, "{level}")?;
                 writeln!(self.out, "{{")?;
-                for sta in block.iter() {
+                for (sta, span) in block.span_iter() {
+                    self.out.mark(*span);
                     // Increase the indentation to help with readability
                     self.write_stmt(module, sta, func_ctx, level.next())?
                 }
@@ -854,7 +902,8 @@ impl<W: Write> Writer<W> {
                         writeln!(self.out, ": {{")?;
                     }
 
-                    for sta in case.body.iter() {
+                    for (sta, span) in case.body.span_iter() {
+                        self.out.mark(*span);
                         self.write_stmt(module, sta, func_ctx, l2.next())?;
                     }
 
@@ -874,7 +923,8 @@ impl<W: Write> Writer<W> {
                 writeln!(self.out, "loop {{")?;
 
                 let l2 = level.next();
-                for sta in body.iter() {
+                for (sta, span) in body.span_iter() {
+                    self.out.mark(*span);
                     self.write_stmt(module, sta, func_ctx, l2)?;
                 }
 
@@ -884,7 +934,8 @@ impl<W: Write> Writer<W> {
                 // `break if` exists
                 if !continuing.is_empty() || break_if.is_some() {
                     writeln!(self.out, "{l2}continuing {{")?;
-                    for sta in continuing.iter() {
+                    for (sta, span) in continuing.span_iter() {
+                        self.out.mark(*span);
                         self.write_stmt(module, sta, func_ctx, l2.next())?;
                     }
 
@@ -917,8 +968,29 @@ impl<W: Write> Writer<W> {
                 if barrier.contains(crate::Barrier::WORK_GROUP) {
                     writeln!(self.out, "{level}workgroupBarrier();")?;
                 }
+
+                if barrier.contains(crate::Barrier::TEXTURE) {
+                    writeln!(self.out, "{level}textureBarrier();")?;
+                }
             }
             Statement::RayQuery { .. } => unreachable!(),
+            Statement::MeshFunction(ref fun) => {
+                let (name, first, second) = match *fun {
+                    crate::MeshFunction::SetMeshOutputs {
+                        vertex_count,
+                        primitive_count,
+                    } => ("setMeshOutputs", vertex_count, primitive_count),
+                    crate::MeshFunction::SetVertex { index, value } => ("setVertex", index, value),
+                    crate::MeshFunction::SetPrimitive { index, value } => {
+                        ("setPrimitive", index, value)
+                    }
+                };
+                write!(self.out, "{level}{name}(")?;
+                self.write_expr(module, first, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, second, func_ctx)?;
+                writeln!(self.out, ");")?;
+            }
         }
 
         Ok(())
@@ -1245,6 +1317,22 @@ impl<W: Write> Writer<W> {
                     ref other => return Err(Error::Custom(format!("Cannot index {other:?}"))),
                 }
             }
+            Expression::ImageSample {
+                image,
+                sampler,
+                gather: None,
+                coordinate,
+                clamp_to_edge: true,
+                ..
+            } => {
+                write!(self.out, "textureSampleBaseClampToEdge(")?;
+                self.write_expr(module, image, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, sampler, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, coordinate, func_ctx)?;
+                write!(self.out, ")")?;
+            }
             Expression::ImageSample {
                 image,
                 sampler,
@@ -1254,6 +1342,7 @@ impl<W: Write> Writer<W> {
                 offset,
                 level,
                 depth_ref,
+                clamp_to_edge: false,
             } => {
                 use crate::SampleLevel as Sl;
 
@@ -1326,6 +1415,7 @@ impl<W: Write> Writer<W> {
                 offset,
                 level: _,
                 depth_ref,
+                clamp_to_edge: _,
             } => {
                 let suffix_cmp = match depth_ref {
                     Some(_) => "Compare",
@@ -1552,6 +1642,8 @@ impl<W: Write> Writer<W> {
                     Mf::Pow => Function::Regular("pow"),
                     // geometry
                     Mf::Dot => Function::Regular("dot"),
+                    Mf::Dot4I8Packed => Function::Regular("dot4I8Packed"),
+                    Mf::Dot4U8Packed => Function::Regular("dot4U8Packed"),
                     Mf::Cross => Function::Regular("cross"),
                     Mf::Distance => Function::Regular("distance"),
                     Mf::Length => Function::Regular("length"),
@@ -1569,6 +1661,7 @@ impl<W: Write> Writer<W> {
                     Mf::InverseSqrt => Function::Regular("inverseSqrt"),
                     Mf::Transpose => Function::Regular("transpose"),
                     Mf::Determinant => Function::Regular("determinant"),
+                    Mf::QuantizeToF16 => Function::Regular("quantizeToF16"),
                     // bits
                     Mf::CountTrailingZeros => Function::Regular("countTrailingZeros"),
                     Mf::CountLeadingZeros => Function::Regular("countLeadingZeros"),
@@ -1584,12 +1677,18 @@ impl<W: Write> Writer<W> {
                     Mf::Pack2x16snorm => Function::Regular("pack2x16snorm"),
                     Mf::Pack2x16unorm => Function::Regular("pack2x16unorm"),
                     Mf::Pack2x16float => Function::Regular("pack2x16float"),
+                    Mf::Pack4xI8 => Function::Regular("pack4xI8"),
+                    Mf::Pack4xU8 => Function::Regular("pack4xU8"),
+                    Mf::Pack4xI8Clamp => Function::Regular("pack4xI8Clamp"),
+                    Mf::Pack4xU8Clamp => Function::Regular("pack4xU8Clamp"),
                     // data unpacking
                     Mf::Unpack4x8snorm => Function::Regular("unpack4x8snorm"),
                     Mf::Unpack4x8unorm => Function::Regular("unpack4x8unorm"),
                     Mf::Unpack2x16snorm => Function::Regular("unpack2x16snorm"),
                     Mf::Unpack2x16unorm => Function::Regular("unpack2x16unorm"),
                     Mf::Unpack2x16float => Function::Regular("unpack2x16float"),
+                    Mf::Unpack4xI8 => Function::Regular("unpack4xI8"),
+                    Mf::Unpack4xU8 => Function::Regular("unpack4xU8"),
                     Mf::Inverse | Mf::Outer => {
                         return Err(Error::UnsupportedMathFunction(fun));
                     }
@@ -1761,7 +1860,7 @@ impl<W: Write> Writer<W> {
     // See https://github.com/rust-lang/rust-clippy/issues/4979.
     #[allow(clippy::missing_const_for_fn)]
     pub fn finish(self) -> W {
-        self.out
+        self.out.into_inner()
     }
 }
 
@@ -1783,6 +1882,11 @@ fn builtin_str(built_in: crate::BuiltIn) -> Result<&'static str, Error> {
         Bi::SampleMask => "sample_mask",
         Bi::PrimitiveIndex => "primitive_index",
         Bi::ViewIndex => "view_index",
+        Bi::MeshTaskSize => "mesh_task_size",
+        Bi::CullPrimitive => "cull_primitive",
+        Bi::PointIndex => "point_index",
+        Bi::LineIndices => "line_indices",
+        Bi::TriangleIndices => "triangle_indices",
         Bi::BaseInstance
         | Bi::BaseVertex
         | Bi::ClipDistance
@@ -1922,6 +2026,7 @@ const fn address_space_str(
             }
             As::PushConstant => "push_constant",
             As::WorkGroup => "workgroup",
+            As::TaskPayload => "task_payload",
             As::Handle => return (None, None),
             As::Function => "function",
         }),
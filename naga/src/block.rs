@@ -75,6 +75,10 @@ impl Block {
         self.body.iter_mut().zip(span_iter)
     }
 
+    pub fn span_into_iter(self) -> impl Iterator<Item = (Statement, Span)> {
+        self.body.into_iter().zip(self.span_info)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.body.is_empty()
     }
@@ -112,6 +112,7 @@ impl<'tracer> ExpressionTracer<'tracer> {
                     offset,
                     ref level,
                     depth_ref,
+                    clamp_to_edge: _,
                 } => {
                     self.expressions_used
                         .insert_iter([image, sampler, coordinate]);
@@ -214,12 +215,12 @@ impl ModuleMap {
             // Expressions that do not contain handles that need to be adjusted.
             Ex::Literal(_)
             | Ex::FunctionArgument(_)
-            | Ex::GlobalVariable(_)
             | Ex::LocalVariable(_)
-            | Ex::CallResult(_)
             | Ex::RayQueryProceedResult => {}
 
             // Expressions that contain handles that need to be adjusted.
+            Ex::GlobalVariable(ref mut global) => self.global_variables.adjust(global),
+            Ex::CallResult(ref mut function) => self.functions.adjust(function),
             Ex::Constant(ref mut constant) => self.constants.adjust(constant),
             Ex::ZeroValue(ref mut ty) => self.types.adjust(ty),
             Ex::Compose {
@@ -261,6 +262,7 @@ impl ModuleMap {
                 ref mut offset,
                 ref mut level,
                 ref mut depth_ref,
+                clamp_to_edge: _,
             } => {
                 adjust(image);
                 adjust(sampler);
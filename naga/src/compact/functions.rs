@@ -8,6 +8,8 @@ pub struct FunctionTracer<'a> {
     pub types_used: &'a mut HandleSet<crate::Type>,
     pub constants_used: &'a mut HandleSet<crate::Constant>,
     pub const_expressions_used: &'a mut HandleSet<crate::Expression>,
+    pub global_variables_used: &'a mut HandleSet<crate::GlobalVariable>,
+    pub functions_used: &'a mut HandleSet<crate::Function>,
 
     /// Function-local expressions used.
     pub expressions_used: HandleSet<crate::Expression>,
@@ -42,6 +44,16 @@ impl<'a> FunctionTracer<'a> {
         // directly by statements, walk the arena to find all
         // expressions used, directly or indirectly.
         self.as_expression().trace_expressions();
+
+        // Note the global variables the used expressions refer to. Called
+        // functions were noted by `trace_block`.
+        for (handle, expr) in self.function.expressions.iter() {
+            if let crate::Expression::GlobalVariable(global) = *expr {
+                if self.expressions_used.contains(handle) {
+                    self.global_variables_used.insert(global);
+                }
+            }
+        }
     }
 
     fn as_expression(&mut self) -> super::expressions::ExpressionTracer {
@@ -101,6 +113,6 @@ impl FunctionMap {
         assert!(reuse.is_empty());
 
         // Adjust statements.
-        self.adjust_body(function);
+        self.adjust_body(function, module_map);
     }
 }
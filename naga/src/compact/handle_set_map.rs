@@ -98,6 +98,35 @@ impl<T: 'static> HandleMap<T> {
         }
     }
 
+    /// Return a map with no entries, to be extended with [`push`].
+    ///
+    /// [`push`]: HandleMap::push
+    #[cfg(any(feature = "link", feature = "opt"))]
+    pub const fn new() -> Self {
+        Self {
+            new_index: Vec::new(),
+            as_keys: std::marker::PhantomData,
+        }
+    }
+
+    /// Return a map taking each of the first `len` handles to the handle
+    /// `offset` places after it.
+    #[cfg(any(feature = "link", feature = "opt"))]
+    pub fn offset(len: usize, offset: usize) -> Self {
+        Self {
+            new_index: (0..len)
+                .map(|zero_based_index| Index::new((offset + zero_based_index + 1) as u32))
+                .collect(),
+            as_keys: std::marker::PhantomData,
+        }
+    }
+
+    /// Map the next handle to `new`.
+    #[cfg(any(feature = "link", feature = "opt"))]
+    pub fn push(&mut self, new: Handle<T>) {
+        self.new_index.push(Index::new(new.index() as u32 + 1));
+    }
+
     /// Return true if `old` is used in the compacted module.
     pub fn used(&self, old: Handle<T>) -> bool {
         self.new_index[old.index()].is_some()
@@ -5,7 +5,8 @@ mod statements;
 mod types;
 
 use crate::{arena, compact::functions::FunctionTracer};
-use handle_set_map::{HandleMap, HandleSet};
+pub(crate) use handle_set_map::HandleMap;
+pub(crate) use handle_set_map::HandleSet;
 
 /// Remove unused types, expressions, and constants from `module`.
 ///
@@ -29,61 +30,154 @@ use handle_set_map::{HandleMap, HandleSet};
 ///
 /// If `module` has not passed validation, this may panic.
 pub fn compact(module: &mut crate::Module) {
+    compact_impl(module, None);
+}
+
+/// Remove everything from `module` that its entry point at index
+/// `entry_point` doesn't use.
+///
+/// The result contains only that entry point, and the global variables,
+/// functions, types, constants and constant expressions it uses, directly or
+/// indirectly. Unlike [`compact`], this drops named types and constants and
+/// unused globals. The handles of the remaining items are adjusted, so the
+/// module must be validated again to get its [`ModuleInfo`].
+///
+/// This is useful for emitting a separate module for each entry point, or for
+/// identifying exactly the code a pipeline uses.
+///
+/// # Panics
+///
+/// If `module` has not passed validation, or `entry_point` is out of range,
+/// this may panic.
+///
+/// [`ModuleInfo`]: crate::valid::ModuleInfo
+pub fn retain_entry_point(module: &mut crate::Module, entry_point: usize) {
+    assert!(entry_point < module.entry_points.len());
+    compact_impl(module, Some(entry_point));
+}
+
+/// Return a new module containing only the entry point at index
+/// `entry_point` of `module`, and what it uses, validated by `validator`.
+///
+/// See [`retain_entry_point`] for details.
+#[cfg(feature = "clone")]
+pub fn extract_entry_point(
+    module: &crate::Module,
+    entry_point: usize,
+    validator: &mut crate::valid::Validator,
+) -> Result<(crate::Module, crate::valid::ModuleInfo), crate::WithSpan<crate::valid::ValidationError>>
+{
+    let mut module = module.clone();
+    retain_entry_point(&mut module, entry_point);
+    let info = validator.validate(&module)?;
+    Ok((module, info))
+}
+
+/// Return the expressions in `function` that its statements use, directly or
+/// indirectly, along with its named expressions and local initializers.
+#[cfg(feature = "opt")]
+pub(crate) fn used_expressions(
+    module: &crate::Module,
+    function: &crate::Function,
+) -> HandleSet<crate::Expression> {
     let mut module_tracer = ModuleTracer::new(module);
+    let mut function_tracer = module_tracer.as_function(function);
+    function_tracer.trace();
+    function_tracer.expressions_used
+}
 
-    // We treat all globals as used by definition.
-    log::trace!("tracing global variables");
-    {
-        for (_, global) in module.global_variables.iter() {
-            log::trace!("tracing global {:?}", global.name);
-            module_tracer.types_used.insert(global.ty);
-            if let Some(init) = global.init {
-                module_tracer.const_expressions_used.insert(init);
+/// Compact `module`, keeping only the entry point at index `only_entry_point`
+/// if it's not `None`.
+fn compact_impl(module: &mut crate::Module, only_entry_point: Option<usize>) {
+    let mut module_tracer = ModuleTracer::new(module);
+
+    if only_entry_point.is_none() {
+        // We treat all globals as used by definition.
+        module_tracer
+            .global_variables_used
+            .insert_iter(module.global_variables.iter().map(|(handle, _)| handle));
+
+        // We treat all named constants as used by definition.
+        for (handle, constant) in module.constants.iter() {
+            if constant.name.is_some() {
+                module_tracer.constants_used.insert(handle);
+                module_tracer.const_expressions_used.insert(constant.init);
             }
         }
+
+        // We assume that all functions are used.
+        module_tracer
+            .functions_used
+            .insert_iter(module.functions.iter().map(|(handle, _)| handle));
     }
 
     // We treat all special types as used by definition.
     module_tracer.trace_special_types(&module.special_types);
 
-    // We treat all named constants as used by definition.
-    for (handle, constant) in module.constants.iter() {
-        if constant.name.is_some() {
-            module_tracer.constants_used.insert(handle);
-            module_tracer.const_expressions_used.insert(constant.init);
-        }
-    }
+    // Observe what each entry point we keep actually uses. This includes
+    // the functions it calls.
+    log::trace!("tracing entry points");
+    let entry_point_maps: Vec<Option<FunctionMap>> = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .map(|(index, e)| {
+            if matches!(only_entry_point, Some(only) if only != index) {
+                return None;
+            }
+            log::trace!("tracing entry point {:?}", e.function.name);
+            if let Some(ref mesh_info) = e.mesh_info {
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.vertex_output_type);
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.primitive_output_type);
+            }
+            if let Some(task_payload) = e.task_payload {
+                module_tracer.global_variables_used.insert(task_payload);
+            }
+            let mut used = module_tracer.as_function(&e.function);
+            used.trace();
+            Some(FunctionMap::from(used))
+        })
+        .collect();
 
-    // We assume that all functions are used.
+    // Observe which types, constant expressions, constants, global
+    // variables, expressions and functions each used function uses, and
+    // produce maps for each function from pre-compaction to
+    // post-compaction expression handles.
     //
-    // Observe which types, constant expressions, constants, and
-    // expressions each function uses, and produce maps for each
-    // function from pre-compaction to post-compaction expression
-    // handles.
+    // Functions may only call functions that precede them in the arena, so
+    // a single pass from back to front finds all the functions used.
     log::trace!("tracing functions");
-    let function_maps: Vec<FunctionMap> = module
+    let mut function_maps: Vec<Option<FunctionMap>> = module
         .functions
         .iter()
-        .map(|(_, f)| {
+        .rev()
+        .map(|(handle, f)| {
+            if !module_tracer.functions_used.contains(handle) {
+                return None;
+            }
             log::trace!("tracing function {:?}", f.name);
             let mut function_tracer = module_tracer.as_function(f);
             function_tracer.trace();
-            FunctionMap::from(function_tracer)
+            Some(FunctionMap::from(function_tracer))
         })
         .collect();
+    function_maps.reverse();
 
-    // Similiarly, observe what each entry point actually uses.
-    log::trace!("tracing entry points");
-    let entry_point_maps: Vec<FunctionMap> = module
-        .entry_points
-        .iter()
-        .map(|e| {
-            log::trace!("tracing entry point {:?}", e.function.name);
-            let mut used = module_tracer.as_function(&e.function);
-            used.trace();
-            FunctionMap::from(used)
-        })
-        .collect();
+    // Note what the used globals refer to.
+    log::trace!("tracing global variables");
+    for (handle, global) in module.global_variables.iter() {
+        if module_tracer.global_variables_used.contains(handle) {
+            log::trace!("tracing global {:?}", global.name);
+            module_tracer.types_used.insert(global.ty);
+            if let Some(init) = global.init {
+                module_tracer.const_expressions_used.insert(init);
+            }
+        }
+    }
 
     // Given that the above steps have marked all the constant
     // expressions used directly by globals, constants, functions, and
@@ -101,10 +195,12 @@ pub fn compact(module: &mut crate::Module) {
     }
 
     // Treat all named types as used.
-    for (handle, ty) in module.types.iter() {
-        log::trace!("tracing type {:?}, name {:?}", handle, ty.name);
-        if ty.name.is_some() {
-            module_tracer.types_used.insert(handle);
+    if only_entry_point.is_none() {
+        for (handle, ty) in module.types.iter() {
+            log::trace!("tracing type {:?}, name {:?}", handle, ty.name);
+            if ty.name.is_some() {
+                module_tracer.types_used.insert(handle);
+            }
         }
     }
 
@@ -117,6 +213,103 @@ pub fn compact(module: &mut crate::Module) {
     // in the compacted module.
     let module_map = ModuleMap::from(module_tracer);
 
+    apply_maps(module, module_map, function_maps, entry_point_maps);
+}
+
+/// Remove the types in `types` from `module`, along with the types built
+/// from them, unless something else in it still refers to them.
+///
+/// Unlike [`compact`], this keeps everything else in `module`, including
+/// unused types and expressions, and only adjusts their handles. Frontends
+/// can use this to drop types they have replaced without otherwise changing
+/// their output.
+#[cfg(feature = "spv-in")]
+pub(crate) fn remove_types(module: &mut crate::Module, types: &[arena::Handle<crate::Type>]) {
+    // Types refer only to earlier types, so one pass finds everything built
+    // from `types`, like pointers to them.
+    let mut removable = HandleSet::for_arena(&module.types);
+    removable.insert_iter(types.iter().copied());
+    for (handle, ty) in module.types.iter() {
+        let built_from_removable = match ty.inner {
+            crate::TypeInner::Pointer { base, .. }
+            | crate::TypeInner::Array { base, .. }
+            | crate::TypeInner::BindingArray { base, .. } => removable.contains(base),
+            crate::TypeInner::Struct { ref members, .. } => {
+                members.iter().any(|member| removable.contains(member.ty))
+            }
+            _ => false,
+        };
+        if built_from_removable {
+            removable.insert(handle);
+        }
+    }
+
+    let mut module_tracer = ModuleTracer::new(module);
+
+    module_tracer
+        .global_variables_used
+        .insert_iter(module.global_variables.iter().map(|(handle, _)| handle));
+    module_tracer
+        .constants_used
+        .insert_iter(module.constants.iter().map(|(handle, _)| handle));
+    module_tracer
+        .const_expressions_used
+        .insert_iter(module.const_expressions.iter().map(|(handle, _)| handle));
+    module_tracer
+        .functions_used
+        .insert_iter(module.functions.iter().map(|(handle, _)| handle));
+    module_tracer.trace_special_types(&module.special_types);
+
+    let entry_point_maps = module
+        .entry_points
+        .iter()
+        .map(|e| {
+            if let Some(ref mesh_info) = e.mesh_info {
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.vertex_output_type);
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.primitive_output_type);
+            }
+            Some(module_tracer.trace_whole_function(&e.function))
+        })
+        .collect();
+    let function_maps = module
+        .functions
+        .iter()
+        .map(|(_, f)| Some(module_tracer.trace_whole_function(f)))
+        .collect();
+
+    for (_, global) in module.global_variables.iter() {
+        module_tracer.types_used.insert(global.ty);
+    }
+    module_tracer.as_const_expression().trace_expressions();
+    for (_, constant) in module.constants.iter() {
+        module_tracer.types_used.insert(constant.ty);
+    }
+
+    // Everything else is used by definition. The removable types are only
+    // kept if something still refers to them.
+    for (handle, _) in module.types.iter() {
+        if !removable.contains(handle) {
+            module_tracer.types_used.insert(handle);
+        }
+    }
+    module_tracer.as_type().trace_types();
+
+    let module_map = ModuleMap::from(module_tracer);
+    apply_maps(module, module_map, function_maps, entry_point_maps);
+}
+
+/// Drop everything `module_map`, `function_maps` and `entry_point_maps` don't
+/// keep from `module`, and adjust the handles of what remains.
+fn apply_maps(
+    module: &mut crate::Module,
+    module_map: ModuleMap,
+    function_maps: Vec<Option<FunctionMap>>,
+    entry_point_maps: Vec<Option<FunctionMap>>,
+) {
     // Drop unused types from the type arena.
     //
     // `FastIndexSet`s don't have an underlying Vec<T> that we can
@@ -158,35 +351,58 @@ pub fn compact(module: &mut crate::Module) {
         }
     });
 
-    // Adjust global variables' types and initializers.
+    // Drop unused global variables, and adjust the types and initializers
+    // of the others.
     log::trace!("adjusting global variables");
-    for (_, global) in module.global_variables.iter_mut() {
+    module.global_variables.retain_mut(|handle, global| {
+        if !module_map.global_variables.used(handle) {
+            return false;
+        }
         log::trace!("adjusting global {:?}", global.name);
         module_map.types.adjust(&mut global.ty);
         if let Some(ref mut init) = global.init {
             module_map.const_expressions.adjust(init);
         }
-    }
+        true
+    });
 
     // Temporary storage to help us reuse allocations of existing
     // named expression tables.
     let mut reused_named_expressions = crate::NamedExpressions::default();
 
-    // Compact each function.
-    for ((_, function), map) in module.functions.iter_mut().zip(function_maps.iter()) {
+    // Drop unused functions, and compact the others.
+    module.functions.retain_mut(|handle, function| {
+        let Some(ref map) = function_maps[handle.index()] else {
+            return false;
+        };
         log::trace!("compacting function {:?}", function.name);
         map.compact(function, &module_map, &mut reused_named_expressions);
-    }
+        true
+    });
 
-    // Compact each entry point.
-    for (entry, map) in module.entry_points.iter_mut().zip(entry_point_maps.iter()) {
+    // Drop unused entry points, and compact the others.
+    let mut entry_point_maps = entry_point_maps.into_iter();
+    module.entry_points.retain_mut(|entry| {
+        let Some(map) = entry_point_maps.next().flatten() else {
+            return false;
+        };
         log::trace!("compacting entry point {:?}", entry.function.name);
+        if let Some(ref mut mesh_info) = entry.mesh_info {
+            module_map.types.adjust(&mut mesh_info.vertex_output_type);
+            module_map
+                .types
+                .adjust(&mut mesh_info.primitive_output_type);
+        }
+        if let Some(ref mut task_payload) = entry.task_payload {
+            module_map.global_variables.adjust(task_payload);
+        }
         map.compact(
             &mut entry.function,
             &module_map,
             &mut reused_named_expressions,
         );
-    }
+        true
+    });
 }
 
 struct ModuleTracer<'module> {
@@ -194,6 +410,8 @@ struct ModuleTracer<'module> {
     types_used: HandleSet<crate::Type>,
     constants_used: HandleSet<crate::Constant>,
     const_expressions_used: HandleSet<crate::Expression>,
+    global_variables_used: HandleSet<crate::GlobalVariable>,
+    functions_used: HandleSet<crate::Function>,
 }
 
 impl<'module> ModuleTracer<'module> {
@@ -203,6 +421,8 @@ impl<'module> ModuleTracer<'module> {
             types_used: HandleSet::for_arena(&module.types),
             constants_used: HandleSet::for_arena(&module.constants),
             const_expressions_used: HandleSet::for_arena(&module.const_expressions),
+            global_variables_used: HandleSet::for_arena(&module.global_variables),
+            functions_used: HandleSet::for_arena(&module.functions),
         }
     }
 
@@ -210,6 +430,7 @@ impl<'module> ModuleTracer<'module> {
         let crate::SpecialTypes {
             ref ray_desc,
             ref ray_intersection,
+            ref external_texture_params,
             ref predeclared_types,
         } = *special_types;
 
@@ -219,6 +440,9 @@ impl<'module> ModuleTracer<'module> {
         if let Some(ray_intersection) = *ray_intersection {
             self.types_used.insert(ray_intersection);
         }
+        if let Some(external_texture_params) = *external_texture_params {
+            self.types_used.insert(external_texture_params);
+        }
         for (_, &handle) in predeclared_types {
             self.types_used.insert(handle);
         }
@@ -242,6 +466,17 @@ impl<'module> ModuleTracer<'module> {
         }
     }
 
+    /// Trace `function`, keeping all of its expressions, used or not.
+    #[cfg(feature = "spv-in")]
+    fn trace_whole_function(&mut self, function: &crate::Function) -> FunctionMap {
+        let mut function_tracer = self.as_function(function);
+        function_tracer
+            .expressions_used
+            .insert_iter(function.expressions.iter().map(|(handle, _)| handle));
+        function_tracer.trace();
+        FunctionMap::from(function_tracer)
+    }
+
     pub fn as_function<'tracer>(
         &'tracer mut self,
         function: &'tracer crate::Function,
@@ -252,15 +487,19 @@ impl<'module> ModuleTracer<'module> {
             types_used: &mut self.types_used,
             constants_used: &mut self.constants_used,
             const_expressions_used: &mut self.const_expressions_used,
+            global_variables_used: &mut self.global_variables_used,
+            functions_used: &mut self.functions_used,
             expressions_used: HandleSet::for_arena(&function.expressions),
         }
     }
 }
 
-struct ModuleMap {
-    types: HandleMap<crate::Type>,
-    constants: HandleMap<crate::Constant>,
-    const_expressions: HandleMap<crate::Expression>,
+pub(crate) struct ModuleMap {
+    pub(crate) types: HandleMap<crate::Type>,
+    pub(crate) constants: HandleMap<crate::Constant>,
+    pub(crate) const_expressions: HandleMap<crate::Expression>,
+    pub(crate) global_variables: HandleMap<crate::GlobalVariable>,
+    pub(crate) functions: HandleMap<crate::Function>,
 }
 
 impl From<ModuleTracer<'_>> for ModuleMap {
@@ -269,15 +508,18 @@ impl From<ModuleTracer<'_>> for ModuleMap {
             types: HandleMap::from_set(used.types_used),
             constants: HandleMap::from_set(used.constants_used),
             const_expressions: HandleMap::from_set(used.const_expressions_used),
+            global_variables: HandleMap::from_set(used.global_variables_used),
+            functions: HandleMap::from_set(used.functions_used),
         }
     }
 }
 
 impl ModuleMap {
-    fn adjust_special_types(&self, special: &mut crate::SpecialTypes) {
+    pub(crate) fn adjust_special_types(&self, special: &mut crate::SpecialTypes) {
         let crate::SpecialTypes {
             ref mut ray_desc,
             ref mut ray_intersection,
+            ref mut external_texture_params,
             ref mut predeclared_types,
         } = *special;
 
@@ -287,6 +529,9 @@ impl ModuleMap {
         if let Some(ref mut ray_intersection) = *ray_intersection {
             self.types.adjust(ray_intersection);
         }
+        if let Some(ref mut external_texture_params) = *external_texture_params {
+            self.types.adjust(external_texture_params);
+        }
 
         for handle in predeclared_types.values_mut() {
             self.types.adjust(handle);
@@ -294,8 +539,8 @@ impl ModuleMap {
     }
 }
 
-struct FunctionMap {
-    expressions: HandleMap<crate::Expression>,
+pub(crate) struct FunctionMap {
+    pub(crate) expressions: HandleMap<crate::Expression>,
 }
 
 impl From<FunctionTracer<'_>> for FunctionMap {
@@ -1,5 +1,5 @@
 use super::functions::FunctionTracer;
-use super::FunctionMap;
+use super::{FunctionMap, ModuleMap};
 use crate::arena::Handle;
 
 impl FunctionTracer<'_> {
@@ -82,10 +82,11 @@ impl FunctionTracer<'_> {
                         self.expressions_used.insert(result);
                     }
                     St::Call {
-                        function: _,
+                        function,
                         ref arguments,
                         result,
                     } => {
+                        self.functions_used.insert(function);
                         for expr in arguments {
                             self.expressions_used.insert(*expr);
                         }
@@ -97,6 +98,7 @@ impl FunctionTracer<'_> {
                         self.expressions_used.insert(query);
                         self.trace_ray_query_function(fun);
                     }
+                    St::MeshFunction(ref fun) => self.trace_mesh_function(fun),
 
                     // Trivial statements.
                     St::Break
@@ -144,10 +146,27 @@ impl FunctionTracer<'_> {
             Qf::Terminate => {}
         }
     }
+
+    fn trace_mesh_function(&mut self, fun: &crate::MeshFunction) {
+        use crate::MeshFunction as Mf;
+        match *fun {
+            Mf::SetMeshOutputs {
+                vertex_count,
+                primitive_count,
+            } => {
+                self.expressions_used.insert(vertex_count);
+                self.expressions_used.insert(primitive_count);
+            }
+            Mf::SetVertex { index, value } | Mf::SetPrimitive { index, value } => {
+                self.expressions_used.insert(index);
+                self.expressions_used.insert(value);
+            }
+        }
+    }
 }
 
 impl FunctionMap {
-    pub fn adjust_body(&self, function: &mut crate::Function) {
+    pub fn adjust_body(&self, function: &mut crate::Function, module_map: &ModuleMap) {
         let block = &mut function.body;
         let mut worklist: Vec<&mut [crate::Statement]> = vec![block];
         let adjust = |handle: &mut Handle<crate::Expression>| {
@@ -232,10 +251,11 @@ impl FunctionMap {
                         adjust(result);
                     }
                     St::Call {
-                        function: _,
+                        ref mut function,
                         ref mut arguments,
                         ref mut result,
                     } => {
+                        module_map.functions.adjust(function);
                         for expr in arguments {
                             adjust(expr);
                         }
@@ -250,6 +270,7 @@ impl FunctionMap {
                         adjust(query);
                         self.adjust_ray_query_function(fun);
                     }
+                    St::MeshFunction(ref mut fun) => self.adjust_mesh_function(fun),
 
                     // Trivial statements.
                     St::Break
@@ -297,4 +318,28 @@ impl FunctionMap {
             Qf::Terminate => {}
         }
     }
+
+    fn adjust_mesh_function(&self, fun: &mut crate::MeshFunction) {
+        use crate::MeshFunction as Mf;
+        match *fun {
+            Mf::SetMeshOutputs {
+                ref mut vertex_count,
+                ref mut primitive_count,
+            } => {
+                self.expressions.adjust(vertex_count);
+                self.expressions.adjust(primitive_count);
+            }
+            Mf::SetVertex {
+                ref mut index,
+                ref mut value,
+            }
+            | Mf::SetPrimitive {
+                ref mut index,
+                ref mut value,
+            } => {
+                self.expressions.adjust(index);
+                self.expressions.adjust(value);
+            }
+        }
+    }
 }
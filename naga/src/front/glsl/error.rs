@@ -109,6 +109,14 @@ pub enum ErrorKind {
     /// An error was returned by the preprocessor.
     #[error("{0:?}")]
     PreprocessorError(PreprocessorError),
+    /// A malformed `#include` directive.
+    #[error("Invalid include: {0}")]
+    InvalidInclude(Cow<'static, str>),
+    /// The [`IncludeResolver`] failed to find an included file.
+    ///
+    /// [`IncludeResolver`]: super::IncludeResolver
+    #[error("Can't include {0:?}: {1}")]
+    UnresolvedInclude(String, String),
     /// The parser entered an illegal state and exited
     ///
     /// This obviously is a bug and as such should be reported in the github issue tracker
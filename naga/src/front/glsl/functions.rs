@@ -1352,6 +1352,8 @@ impl Frontend {
             early_depth_test: Some(crate::EarlyDepthTest { conservative: None })
                 .filter(|_| self.meta.early_fragment_tests),
             workgroup_size: self.meta.workgroup_size,
+            mesh_info: None,
+            task_payload: None,
             function: Function {
                 arguments,
                 expressions,
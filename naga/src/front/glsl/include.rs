@@ -0,0 +1,411 @@
+/*!
+Expansion of `#include` directives.
+
+The preprocessor doesn't know about `#include`, but it reports the directives
+it doesn't skip as unknown. So the source is preprocessed repeatedly, each time
+splicing in the file named by the first such directive, until none are left, as
+described by `GL_GOOGLE_include_directive`. This way `#if` and `#ifdef` blocks
+are honored, even when they depend on macros defined by included files, and so
+is `#pragma once`.
+
+Every span produced while parsing the expanded source can be mapped back to
+the file it came from with [`SourceMap::locate`].
+*/
+
+use super::{Error, ErrorKind};
+use crate::{FastHashMap, Span};
+use pp_rs::{
+    pp::Preprocessor,
+    token::{PreprocessorError, Token, TokenValue},
+};
+
+/// The maximum depth of nested `#include` directives.
+///
+/// This mostly serves to catch files that include themselves.
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// How the file of an `#include` directive was named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IncludeKind {
+    /// `#include "name"`, usually relative to the including file.
+    Relative,
+    /// `#include <name>`, usually looked up in a list of include directories.
+    Standard,
+}
+
+/// A file returned by an [`IncludeResolver`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IncludedSource {
+    /// The name of the file.
+    ///
+    /// This is passed back to the resolver as the including file for the
+    /// directives in `source`, so it's usually the resolved path.
+    pub name: String,
+    /// The contents of the file.
+    pub source: String,
+}
+
+/// Provides the contents of the files named by `#include` directives.
+///
+/// This is implemented for closures taking the same arguments as
+/// [`resolve`](IncludeResolver::resolve).
+pub trait IncludeResolver {
+    /// Find the file named `name`.
+    ///
+    /// `includer` is the [`name`](IncludedSource::name) of the file containing
+    /// the directive, or `None` for the main source. On failure, return a
+    /// message to report in [`ErrorKind::UnresolvedInclude`].
+    fn resolve(
+        &mut self,
+        name: &str,
+        kind: IncludeKind,
+        includer: Option<&str>,
+    ) -> Result<IncludedSource, String>;
+}
+
+impl<F> IncludeResolver for F
+where
+    F: FnMut(&str, IncludeKind, Option<&str>) -> Result<IncludedSource, String>,
+{
+    fn resolve(
+        &mut self,
+        name: &str,
+        kind: IncludeKind,
+        includer: Option<&str>,
+    ) -> Result<IncludedSource, String> {
+        self(name, kind, includer)
+    }
+}
+
+/// A contiguous part of the expanded source coming from a single file.
+#[derive(Clone, Copy, Debug)]
+struct Chunk {
+    /// The offset of the chunk in the expanded source.
+    start: u32,
+    /// The index of the file in [`SourceMap::files`], or `None` for the
+    /// main source.
+    file: Option<usize>,
+    /// The offset of the chunk in its file.
+    offset: u32,
+}
+
+/// Maps spans in the source with its `#include` directives expanded back to
+/// the files they came from.
+///
+/// Spans reported by [`Frontend::parse_with_includes`], both in errors and in
+/// the [`Module`]'s span information, refer to the expanded source. With
+/// [`Frontend::parse`], the expanded source is the original source.
+///
+/// [`Frontend::parse_with_includes`]: super::Frontend::parse_with_includes
+/// [`Frontend::parse`]: super::Frontend::parse
+/// [`Module`]: crate::Module
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    pub(super) expanded: String,
+    files: Vec<IncludedSource>,
+    chunks: Vec<Chunk>,
+}
+
+impl SourceMap {
+    /// Returns the source with all `#include` directives expanded.
+    pub fn expanded_source(&self) -> &str {
+        &self.expanded
+    }
+
+    /// Returns the included files, in the order they were first included.
+    ///
+    /// A file included several times appears once for each time.
+    pub fn files(&self) -> &[IncludedSource] {
+        &self.files
+    }
+
+    /// Returns the file `span` of the expanded source came from, and the
+    /// corresponding span in that file.
+    ///
+    /// The file is an index into [`files`](SourceMap::files), or `None` for the
+    /// main source. Spans crossing the end of a file are truncated.
+    pub fn locate(&self, span: Span) -> (Option<usize>, Span) {
+        let Some(range) = span.to_range() else {
+            return (None, span);
+        };
+        let index = self
+            .chunks
+            .partition_point(|chunk| chunk.start as usize <= range.start);
+        let Some(chunk) = index.checked_sub(1).map(|index| self.chunks[index]) else {
+            return (None, span);
+        };
+        let end = match self.chunks.get(index) {
+            Some(next) => range.end.min(next.start as usize),
+            None => range.end,
+        };
+
+        let start = chunk.offset + (range.start as u32 - chunk.start);
+        let end = chunk.offset + (end as u32 - chunk.start);
+        (chunk.file, Span::new(start, end))
+    }
+}
+
+/// Expand the `#include` directives of `source` into `map`.
+///
+/// Only the directives that the preprocessor doesn't skip, given `defines`,
+/// are expanded.
+///
+/// On failure, `map` still describes the source expanded so far, so that the
+/// error's span can be located.
+pub fn expand(
+    source: &str,
+    defines: &FastHashMap<String, String>,
+    resolver: &mut dyn IncludeResolver,
+    map: &mut SourceMap,
+) -> Result<(), Error> {
+    *map = SourceMap::default();
+    let mut expander = Expander {
+        resolver,
+        map,
+        source,
+        depths: Vec::new(),
+        includes: FastHashMap::default(),
+    };
+    loop {
+        expander.render(false);
+        match expander.scan(defines) {
+            Some((start, once)) => expander.include(start, &once)?,
+            None => break,
+        }
+    }
+    // The remaining directives are skipped, but the preprocessor can't lex
+    // their file names.
+    expander.render(true);
+    Ok(())
+}
+
+/// Returns a [`SourceMap`] for a source without any includes.
+pub fn identity(source: &str) -> SourceMap {
+    SourceMap {
+        expanded: source.to_string(),
+        files: Vec::new(),
+        chunks: Vec::new(),
+    }
+}
+
+struct Expander<'a> {
+    resolver: &'a mut dyn IncludeResolver,
+    map: &'a mut SourceMap,
+    /// The main source.
+    source: &'a str,
+    /// The depth of the `#include` directive of each file in
+    /// [`SourceMap::files`].
+    depths: Vec<usize>,
+    /// The expanded directives, keyed by the file containing them and the
+    /// offset of their line in it.
+    ///
+    /// The value is the index of the included file in [`SourceMap::files`],
+    /// or `None` if it was left out because of `#pragma once`.
+    includes: FastHashMap<(Option<usize>, usize), Option<usize>>,
+}
+
+impl Expander<'_> {
+    /// Returns the source of `file`.
+    fn source(&self, file: Option<usize>) -> &str {
+        match file {
+            Some(index) => &self.map.files[index].source,
+            None => self.source,
+        }
+    }
+
+    fn push_chunk(&mut self, file: Option<usize>, offset: usize) {
+        self.map.chunks.push(Chunk {
+            start: self.map.expanded.len() as u32,
+            file,
+            offset: offset as u32,
+        });
+    }
+
+    /// Write the main source into the map, with the directives expanded so
+    /// far replaced by the files they include.
+    ///
+    /// The other directives are blanked out if `blank_skipped` is set, and
+    /// only keep their name otherwise, for [`scan`](Expander::scan) to find.
+    fn render(&mut self, blank_skipped: bool) {
+        self.map.expanded.clear();
+        self.map.chunks.clear();
+        self.render_file(None, blank_skipped);
+    }
+
+    fn render_file(&mut self, file: Option<usize>, blank_skipped: bool) {
+        self.push_chunk(file, 0);
+
+        let source = self.source(file).to_string();
+        let mut line_start = 0;
+        while line_start < source.len() {
+            let line_end = source[line_start..]
+                .find('\n')
+                .map_or(source.len(), |end| line_start + end);
+            let line = &source[line_start..line_end];
+
+            let included = self.includes.get(&(file, line_start)).copied();
+            let kept = match included {
+                Some(_) => "",
+                // The preprocessor can't lex the file names of the other
+                // directives, so only their name is kept, unless they're
+                // blanked out too.
+                None => match parse_include(line) {
+                    Some(_) if blank_skipped => "",
+                    Some(_) => {
+                        let end = line.find("include").unwrap() + "include".len();
+                        &line[..end]
+                    }
+                    None => line,
+                },
+            };
+            self.map.expanded.push_str(kept);
+
+            // Blank out the rest, so that spans after it stay put.
+            for c in line[kept.len()..].chars() {
+                match c {
+                    '\t' => self.map.expanded.push(c),
+                    _ => (0..c.len_utf8()).for_each(|_| self.map.expanded.push(' ')),
+                }
+            }
+            if line_end < source.len() || included.is_some() {
+                self.map.expanded.push('\n');
+            }
+
+            line_start = line_end + 1;
+            if let Some(Some(index)) = included {
+                self.render_file(Some(index), blank_skipped);
+                if !self.map.files[index].source.ends_with('\n') {
+                    self.map.expanded.push('\n');
+                }
+                self.push_chunk(file, line_start);
+            }
+        }
+    }
+
+    /// Preprocess the expanded source to find the first `#include` directive
+    /// that isn't skipped.
+    ///
+    /// Returns the offset of the directive in the expanded source, and the
+    /// files that have a `#pragma once` directive before it.
+    fn scan(&self, defines: &FastHashMap<String, String>) -> Option<(u32, Vec<Option<usize>>)> {
+        let mut pp = Preprocessor::new(&self.map.expanded);
+        for (define, value) in defines {
+            // Invalid defines are reported by the lexer.
+            let _ = pp.add_define(define, value);
+        }
+
+        let mut once = Vec::new();
+        for item in pp {
+            match item {
+                // Directives the preprocessor doesn't know are only reported
+                // when they aren't skipped.
+                Err((PreprocessorError::UnknownDirective, location)) => {
+                    let start = location.start as usize;
+                    let line_start = self.map.expanded[..start].rfind('\n').map_or(0, |i| i + 1);
+                    let line_end = self.map.expanded[start..]
+                        .find('\n')
+                        .map_or(self.map.expanded.len(), |end| start + end);
+                    if parse_include(&self.map.expanded[line_start..line_end]).is_some() {
+                        return Some((location.start, once));
+                    }
+                }
+                Ok(Token {
+                    value: TokenValue::Pragma(ref pragma),
+                    location,
+                }) if matches!(
+                    pragma.tokens[..],
+                    [Token { value: TokenValue::Ident(ref name), .. }] if name == "once"
+                ) =>
+                {
+                    let (file, _) = self.map.locate(Span::new(location.start, location.end));
+                    once.push(file);
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Expand the `#include` directive at `start` in the expanded source.
+    ///
+    /// The file isn't included again if one of the same name in `once` was.
+    fn include(&mut self, start: u32, once: &[Option<usize>]) -> Result<(), Error> {
+        let (file, span) = self.map.locate(Span::new(start, start + 1));
+        let source = self.source(file);
+        let offset = span.to_range().unwrap().start;
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |end| offset + end);
+        let line = source[line_start..line_end].to_string();
+
+        let meta_start = start as usize - (offset - line_start);
+        let meta = Span::new(
+            meta_start as u32,
+            (meta_start + line.trim_end().len()) as u32,
+        );
+        let (name, kind) = parse_include(&line).unwrap().map_err(|reason| Error {
+            kind: ErrorKind::InvalidInclude(reason.into()),
+            meta,
+        })?;
+        let depth = file.map_or(0, |index| self.depths[index]);
+        if depth == MAX_INCLUDE_DEPTH {
+            return Err(Error {
+                kind: ErrorKind::InvalidInclude("includes are nested too deeply".into()),
+                meta,
+            });
+        }
+        let includer = file.map(|index| self.map.files[index].name.as_str());
+        let included = self
+            .resolver
+            .resolve(name, kind, includer)
+            .map_err(|reason| Error {
+                kind: ErrorKind::UnresolvedInclude(name.to_string(), reason),
+                meta,
+            })?;
+
+        let included_once = once
+            .iter()
+            .flatten()
+            .any(|&index| self.map.files[index].name == included.name);
+        let index = if included_once {
+            None
+        } else {
+            self.map.files.push(included);
+            self.depths.push(depth + 1);
+            Some(self.map.files.len() - 1)
+        };
+        self.includes.insert((file, line_start), index);
+        Ok(())
+    }
+}
+
+/// Parse `line` as an `#include` directive.
+///
+/// Returns `None` if `line` isn't one, and an error message if it's malformed.
+fn parse_include(line: &str) -> Option<Result<(&str, IncludeKind), &'static str>> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start();
+    let rest = rest.strip_prefix("include")?;
+    if rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+    let (close, kind) = match rest.chars().next() {
+        Some('"') => ('"', IncludeKind::Relative),
+        Some('<') => ('>', IncludeKind::Standard),
+        _ => return Some(Err("expected a file name")),
+    };
+    let rest = &rest[1..];
+    Some(match rest.find(close) {
+        Some(end) if is_blank(&rest[end + 1..]) => Ok((&rest[..end], kind)),
+        Some(_) => Err("unexpected tokens after the file name"),
+        None => Err("unterminated file name"),
+    })
+}
+
+/// Returns true if `text` only contains whitespace and comments.
+fn is_blank(text: &str) -> bool {
+    let text = text.trim();
+    text.is_empty() || text.starts_with("//") || text.starts_with("/*")
+}
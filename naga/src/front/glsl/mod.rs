@@ -9,11 +9,17 @@ To begin, take a look at the documentation for the [`Frontend`].
 - 450
 - 460
 
+# Includes
+`#include` directives are resolved by [`Frontend::parse_with_includes`], with
+a user-provided [`IncludeResolver`], honoring conditional blocks and
+`#pragma once`. The [`SourceMap`] maps spans back to the file they came from.
+
 [glsl]: https://www.khronos.org/registry/OpenGL/index_gl.php
 */
 
 pub use ast::{Precision, Profile};
 pub use error::{Error, ErrorKind, ExpectedToken};
+pub use include::{IncludeKind, IncludeResolver, IncludedSource, SourceMap};
 pub use token::TokenValue;
 
 use crate::{proc::Layouter, FastHashMap, FastHashSet, Handle, Module, ShaderStage, Span, Type};
@@ -25,6 +31,7 @@ mod builtins;
 mod context;
 mod error;
 mod functions;
+mod include;
 mod lex;
 mod offset;
 mod parser;
@@ -175,6 +182,8 @@ pub struct Frontend {
     layouter: Layouter,
 
     errors: Vec<Error>,
+
+    source_map: SourceMap,
 }
 
 impl Frontend {
@@ -197,12 +206,39 @@ impl Frontend {
         options: &Options,
         source: &str,
     ) -> std::result::Result<Module, Vec<Error>> {
+        self.source_map = include::identity(source);
+        self.parse_expanded(options)
+    }
+
+    /// Parses a shader like [`parse`](Frontend::parse), resolving `#include`
+    /// directives with `resolver`.
+    ///
+    /// Included files are spliced into the source where the preprocessor
+    /// doesn't skip their directives, and the reported spans refer to the
+    /// resulting source. Files with a `#pragma once` directive are only
+    /// included once. Use
+    /// [`source_map`](Frontend::source_map) to find the file they came from.
+    pub fn parse_with_includes(
+        &mut self,
+        options: &Options,
+        source: &str,
+        resolver: &mut dyn IncludeResolver,
+    ) -> std::result::Result<Module, Vec<Error>> {
+        if let Err(e) = include::expand(source, &options.defines, resolver, &mut self.source_map) {
+            return Err(vec![e]);
+        }
+        self.parse_expanded(options)
+    }
+
+    fn parse_expanded(&mut self, options: &Options) -> std::result::Result<Module, Vec<Error>> {
         self.reset(options.stage);
 
-        let lexer = lex::Lexer::new(source, &options.defines);
+        // The lexer borrows the source while parsing borrows `self`.
+        let source = std::mem::take(&mut self.source_map.expanded);
+        let lexer = lex::Lexer::new(&source, &options.defines);
         let mut ctx = ParsingContext::new(lexer);
 
-        match ctx.parse(self) {
+        let result = match ctx.parse(self) {
             Ok(module) => {
                 if self.errors.is_empty() {
                     Ok(module)
@@ -214,7 +250,18 @@ impl Frontend {
                 self.errors.push(e);
                 Err(std::mem::take(&mut self.errors))
             }
-        }
+        };
+        self.source_map.expanded = source;
+        result
+    }
+
+    /// Returns the mapping of the spans reported by the last parse back to
+    /// the files they came from.
+    ///
+    /// Unlike [`metadata`](Frontend::metadata), this is also valid after an
+    /// unsuccessful parse.
+    pub const fn source_map(&self) -> &SourceMap {
+        &self.source_map
     }
 
     /// Returns additional information about the parsed shader which might not
@@ -3,7 +3,7 @@ use super::{
     error::ExpectedToken,
     error::{Error, ErrorKind},
     token::TokenValue,
-    Frontend, Options, Span,
+    Frontend, IncludeKind, IncludedSource, Options, Span,
 };
 use crate::ShaderStage;
 use pp_rs::token::PreprocessorError;
@@ -842,3 +842,113 @@ fn expressions() {
         )
         .unwrap();
 }
+
+#[test]
+fn includes() {
+    let mut frontend = Frontend::default();
+    let mut resolver = |name: &str, kind: IncludeKind, includer: Option<&str>| {
+        let source = match (name, kind, includer) {
+            ("common.glsl", IncludeKind::Standard, None) => {
+                "#ifndef COMMON\n#define COMMON\nconst float PI = 3.14;\n#endif\n"
+            }
+            ("lighting.glsl", IncludeKind::Relative, None) => {
+                "#include <common.glsl>\nfloat light(float x) { return x * PI; }"
+            }
+            ("common.glsl", IncludeKind::Standard, Some("lighting.glsl")) => {
+                "#ifndef COMMON\n#define COMMON\nconst float PI = 3.14;\n#endif\n"
+            }
+            ("broken.glsl", IncludeKind::Relative, _) => "float broken() { return undefined; }\n",
+            ("once.glsl", IncludeKind::Relative, _) => {
+                "#pragma once\n#define ONCE\nfloat once() { return 1.0; }\n"
+            }
+            _ => return Err("not found".to_string()),
+        };
+        Ok(IncludedSource {
+            name: name.to_string(),
+            source: source.to_string(),
+        })
+    };
+
+    let source = "#version 450\n#include <common.glsl>\n#include \"lighting.glsl\"\nvoid main() { float x = light(PI); }\n";
+    frontend
+        .parse_with_includes(&Options::from(ShaderStage::Vertex), source, &mut resolver)
+        .unwrap();
+    let source_map = frontend.source_map();
+    assert_eq!(source_map.files().len(), 3);
+    let main_offset = source_map.expanded_source().find("void main").unwrap() as u32;
+    assert_eq!(
+        source_map.locate(Span::new(main_offset, main_offset + 4)),
+        (None, Span::new(61, 65)),
+    );
+    let light_offset = source_map.expanded_source().find("float light").unwrap() as u32;
+    assert_eq!(
+        source_map.locate(Span::new(light_offset, light_offset + 5)),
+        (Some(1), Span::new(23, 28)),
+    );
+
+    // Errors in included files can be located
+    let source = "#version 450\n#include \"broken.glsl\"\nvoid main() {}\n";
+    let errors = frontend
+        .parse_with_includes(&Options::from(ShaderStage::Vertex), source, &mut resolver)
+        .unwrap_err();
+    assert_eq!(
+        errors[0].kind,
+        ErrorKind::UnknownVariable("undefined".into())
+    );
+    assert_eq!(
+        frontend.source_map().locate(errors[0].meta),
+        (Some(0), Span::new(24, 33)),
+    );
+
+    // Unresolved includes are reported at the directive
+    let source = "#version 450\n#include \"missing.glsl\"\nvoid main() {}\n";
+    assert_eq!(
+        frontend
+            .parse_with_includes(&Options::from(ShaderStage::Vertex), source, &mut resolver)
+            .unwrap_err(),
+        vec![Error {
+            kind: ErrorKind::UnresolvedInclude("missing.glsl".into(), "not found".into()),
+            meta: Span::new(13, 36),
+        }],
+    );
+
+    // Malformed includes
+    let source = "#version 450\n#include missing.glsl\nvoid main() {}\n";
+    assert_eq!(
+        frontend
+            .parse_with_includes(&Options::from(ShaderStage::Vertex), source, &mut resolver)
+            .unwrap_err(),
+        vec![Error {
+            kind: ErrorKind::InvalidInclude("expected a file name".into()),
+            meta: Span::new(13, 34),
+        }],
+    );
+
+    // Includes in comments are ignored
+    let source = "#version 450\n/*\n#include \"missing.glsl\"\n*/\nvoid main() {}\n";
+    frontend
+        .parse_with_includes(&Options::from(ShaderStage::Vertex), source, &mut resolver)
+        .unwrap();
+    // Includes in skipped blocks are ignored, also when the block depends on
+    // an included file or on the options
+    let source = "#version 450\n#include \"once.glsl\"\n#ifndef ONCE\n#include \"missing.glsl\"\n#endif\n#ifdef EXTRA\n#include <missing.glsl>\n#endif\nvoid main() {}\n";
+    frontend
+        .parse_with_includes(&Options::from(ShaderStage::Vertex), source, &mut resolver)
+        .unwrap();
+    let mut options = Options::from(ShaderStage::Vertex);
+    options.defines.insert("EXTRA".to_string(), String::new());
+    assert_eq!(
+        frontend
+            .parse_with_includes(&options, source, &mut resolver)
+            .unwrap_err()[0]
+            .kind,
+        ErrorKind::UnresolvedInclude("missing.glsl".into(), "not found".into()),
+    );
+
+    // Files with `#pragma once` are only included once
+    let source = "#version 450\n#include \"once.glsl\"\n#include \"once.glsl\"\nvoid main() {}\n";
+    frontend
+        .parse_with_includes(&Options::from(ShaderStage::Vertex), source, &mut resolver)
+        .unwrap();
+    assert_eq!(frontend.source_map().files().len(), 1);
+}
@@ -256,6 +256,30 @@ impl<Name, Var> SymbolTable<Name, Var> {
 
         self.cursor -= 1;
     }
+
+    /// Returns the number of active lexical scopes, including the root scope.
+    pub const fn depth(&self) -> usize {
+        self.cursor
+    }
+
+    /// Removes lexical scopes until only `depth` of them remain.
+    ///
+    /// This is like calling [`pop_scope`] until [`depth`] returns `depth`,
+    /// which is useful when recovering from an error left scopes unpopped.
+    ///
+    /// # PANICS
+    /// - If `depth` would remove the root scope or is greater than the current depth
+    ///
+    /// [`pop_scope`]: Self::pop_scope
+    /// [`depth`]: Self::depth
+    pub fn truncate(&mut self, depth: usize) {
+        assert!(
+            depth >= 1 && depth <= self.cursor,
+            "Tried to truncate to an invalid scope depth"
+        );
+
+        self.cursor = depth;
+    }
 }
 
 impl<Name, Var> SymbolTable<Name, Var>
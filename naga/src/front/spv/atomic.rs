@@ -0,0 +1,341 @@
+use super::{BlockContext, Error, LookupExpression, LookupHelper as _};
+use crate::arena::{Arena, Handle, UniqueArena};
+
+/// The path from a global variable to the value an atomic operation accesses.
+///
+/// Each step is the member index for a struct, or `None` for an array or
+/// binding array element.
+pub(super) type AtomicPath = Vec<Option<u32>>;
+
+impl<I: Iterator<Item = u32>> super::Frontend<I> {
+    /// Parse one of the `OpAtomic*` instructions.
+    ///
+    /// Loads and stores become plain [`Load`] expressions and [`Store`]
+    /// statements, like WGSL's `atomicLoad` and `atomicStore`. Everything
+    /// else becomes a [`Statement::Atomic`].
+    ///
+    /// SPIR-V has no atomic types, so we record the pointer's path here and
+    /// upgrade the global variable's type once the whole module is parsed.
+    ///
+    /// The memory scope and semantics operands are ignored, since Naga
+    /// atomics are always relaxed at device scope.
+    ///
+    /// [`Load`]: crate::Expression::Load
+    /// [`Store`]: crate::Statement::Store
+    /// [`Statement::Atomic`]: crate::Statement::Atomic
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn parse_atomic(
+        &mut self,
+        inst: super::Instruction,
+        ctx: &mut BlockContext,
+        emitter: &mut crate::proc::Emitter,
+        block: &mut crate::Block,
+        block_id: spirv::Word,
+        body_idx: usize,
+        span: crate::Span,
+    ) -> Result<(), Error> {
+        use spirv::Op;
+
+        if inst.op == Op::AtomicStore {
+            inst.expect(5)?;
+            let pointer_id = self.next()?;
+            let _scope_id = self.next()?;
+            let _semantics_id = self.next()?;
+            let value_id = self.next()?;
+
+            let pointer = self.atomic_pointer(pointer_id, ctx, emitter, block, body_idx)?;
+            let value_lexp = self.lookup_expression.lookup(value_id)?;
+            let value = self.get_expr_handle(value_id, value_lexp, ctx, emitter, block, body_idx);
+
+            block.extend(emitter.finish(ctx.expressions));
+            block.push(crate::Statement::Store { pointer, value }, span);
+            emitter.start(ctx.expressions);
+            return Ok(());
+        }
+
+        inst.expect_at_least(6)?;
+        let result_type_id = self.next()?;
+        let result_id = self.next()?;
+        let pointer_id = self.next()?;
+        let _scope_id = self.next()?;
+        let _semantics_id = self.next()?;
+
+        let pointer = self.atomic_pointer(pointer_id, ctx, emitter, block, body_idx)?;
+        let result_ty = self.lookup_type.lookup(result_type_id)?.handle;
+
+        let (fun, value_id) = match inst.op {
+            Op::AtomicLoad => {
+                inst.expect(6)?;
+                let handle = ctx
+                    .expressions
+                    .append(crate::Expression::Load { pointer }, span);
+                self.lookup_expression.insert(
+                    result_id,
+                    LookupExpression {
+                        handle,
+                        type_id: result_type_id,
+                        block_id,
+                    },
+                );
+                return Ok(());
+            }
+            Op::AtomicIIncrement | Op::AtomicIDecrement => {
+                inst.expect(6)?;
+                let fun = if inst.op == Op::AtomicIIncrement {
+                    crate::AtomicFunction::Add
+                } else {
+                    crate::AtomicFunction::Subtract
+                };
+                (fun, None)
+            }
+            Op::AtomicCompareExchange | Op::AtomicCompareExchangeWeak => {
+                inst.expect(9)?;
+                let _unequal_semantics_id = self.next()?;
+                let value_id = self.next()?;
+                let comparator_id = self.next()?;
+                let comparator_lexp = self.lookup_expression.lookup(comparator_id)?;
+                let comparator = self.get_expr_handle(
+                    comparator_id,
+                    comparator_lexp,
+                    ctx,
+                    emitter,
+                    block,
+                    body_idx,
+                );
+                let fun = crate::AtomicFunction::Exchange {
+                    compare: Some(comparator),
+                };
+                (fun, Some(value_id))
+            }
+            _ => {
+                inst.expect(7)?;
+                let fun = match inst.op {
+                    Op::AtomicExchange => crate::AtomicFunction::Exchange { compare: None },
+                    Op::AtomicIAdd => crate::AtomicFunction::Add,
+                    Op::AtomicISub => crate::AtomicFunction::Subtract,
+                    Op::AtomicSMin | Op::AtomicUMin => crate::AtomicFunction::Min,
+                    Op::AtomicSMax | Op::AtomicUMax => crate::AtomicFunction::Max,
+                    Op::AtomicAnd => crate::AtomicFunction::And,
+                    Op::AtomicOr => crate::AtomicFunction::InclusiveOr,
+                    Op::AtomicXor => crate::AtomicFunction::ExclusiveOr,
+                    _ => return Err(Error::UnsupportedInstruction(self.state, inst.op)),
+                };
+                (fun, Some(self.next()?))
+            }
+        };
+
+        let value = match value_id {
+            Some(value_id) => {
+                let value_lexp = self.lookup_expression.lookup(value_id)?;
+                self.get_expr_handle(value_id, value_lexp, ctx, emitter, block, body_idx)
+            }
+            None => {
+                let literal = match ctx.type_arena[result_ty].inner.scalar() {
+                    Some(crate::Scalar::U32) => crate::Literal::U32(1),
+                    Some(crate::Scalar::I32) => crate::Literal::I32(1),
+                    Some(crate::Scalar {
+                        kind: crate::ScalarKind::Sint,
+                        width: 8,
+                    }) => crate::Literal::I64(1),
+                    _ => return Err(Error::UnsupportedType(result_ty)),
+                };
+                // Literals must not be covered by an `Emit`.
+                block.extend(emitter.finish(ctx.expressions));
+                let handle = ctx
+                    .expressions
+                    .append(crate::Expression::Literal(literal), span);
+                emitter.start(ctx.expressions);
+                handle
+            }
+        };
+
+        // The result of a compare-exchange is a struct holding the old value,
+        // but only `Module::generate_predeclared_type` can build that struct
+        // and it needs the whole module. Use the scalar type for now and let
+        // `patch_compare_exchange_results` fix it up once the function is
+        // done.
+        let comparison = matches!(fun, crate::AtomicFunction::Exchange { compare: Some(_) });
+
+        block.extend(emitter.finish(ctx.expressions));
+        let result = ctx.expressions.append(
+            crate::Expression::AtomicResult {
+                ty: result_ty,
+                comparison,
+            },
+            span,
+        );
+        block.push(
+            crate::Statement::Atomic {
+                pointer,
+                fun,
+                value,
+                result,
+            },
+            span,
+        );
+        emitter.start(ctx.expressions);
+
+        let handle = if comparison {
+            ctx.expressions.append(
+                crate::Expression::AccessIndex {
+                    base: result,
+                    index: 0,
+                },
+                span,
+            )
+        } else {
+            result
+        };
+        self.lookup_expression.insert(
+            result_id,
+            LookupExpression {
+                handle,
+                type_id: result_type_id,
+                block_id,
+            },
+        );
+        Ok(())
+    }
+
+    /// Look up the pointer operand of an atomic instruction, and remember that
+    /// the value it points to must be given an atomic type.
+    ///
+    /// The pointer must be based on a global variable. Pointers passed as
+    /// function arguments are rejected with
+    /// [`Error::UnsupportedAtomicFunctionArgument`].
+    fn atomic_pointer(
+        &mut self,
+        pointer_id: spirv::Word,
+        ctx: &mut BlockContext,
+        emitter: &mut crate::proc::Emitter,
+        block: &mut crate::Block,
+        body_idx: usize,
+    ) -> Result<Handle<crate::Expression>, Error> {
+        let pointer_lexp = self.lookup_expression.lookup(pointer_id)?;
+        let pointer = self.get_expr_handle(pointer_id, pointer_lexp, ctx, emitter, block, body_idx);
+
+        let mut path = Vec::new();
+        let mut current = pointer;
+        let global = loop {
+            match ctx.expressions[current] {
+                crate::Expression::AccessIndex { base, index } => {
+                    path.push(Some(index));
+                    current = base;
+                }
+                crate::Expression::Access { base, .. } => {
+                    path.push(None);
+                    current = base;
+                }
+                crate::Expression::GlobalVariable(handle) => break handle,
+                crate::Expression::FunctionArgument(_) => {
+                    return Err(Error::UnsupportedAtomicFunctionArgument)
+                }
+                ref other => return Err(Error::InvalidAtomicPointer(other.clone())),
+            }
+        };
+        path.reverse();
+        self.upgrade_atomics.entry(global).or_default().insert(path);
+
+        Ok(pointer)
+    }
+}
+
+/// Give the results of `OpAtomicCompareExchange` in `expressions` the type
+/// Naga expects, now that `module` is no longer borrowed by the parser.
+pub(super) fn patch_compare_exchange_results(
+    expressions: &mut Arena<crate::Expression>,
+    module: &mut crate::Module,
+) {
+    for (_, expr) in expressions.iter_mut() {
+        if let crate::Expression::AtomicResult {
+            ref mut ty,
+            comparison: true,
+        } = *expr
+        {
+            if let Some(scalar) = module.types[*ty].inner.scalar() {
+                *ty = module.generate_predeclared_type(
+                    crate::PredeclaredType::AtomicCompareExchangeWeakResult(scalar),
+                );
+            }
+        }
+    }
+}
+
+/// Return a version of `ty` in which the values reached by following each of
+/// `paths` have atomic types.
+///
+/// All the paths are applied at once, so that we don't leave types that are
+/// only partially upgraded behind in the arena. The composite types that were
+/// replaced by an upgraded copy are added to `replaced`, so they can be
+/// removed if nothing refers to them anymore.
+pub(super) fn upgrade_atomic_type(
+    types: &mut UniqueArena<crate::Type>,
+    ty: Handle<crate::Type>,
+    paths: &[&[Option<u32>]],
+    replaced: &mut Vec<Handle<crate::Type>>,
+) -> Result<Handle<crate::Type>, Error> {
+    // Split off the first step of every path. They must either all end here,
+    // or all continue into a member or element of `ty`.
+    let mut steps = Vec::with_capacity(paths.len());
+    let mut rests = Vec::with_capacity(paths.len());
+    for path in paths {
+        if let Some((&step, rest)) = path.split_first() {
+            steps.push(step);
+            rests.push(rest);
+        }
+    }
+    if !steps.is_empty() && steps.len() != paths.len() {
+        return Err(Error::UnsupportedType(ty));
+    }
+
+    let inner = match types[ty].inner {
+        crate::TypeInner::Atomic(_) if steps.is_empty() => return Ok(ty),
+        crate::TypeInner::Scalar(scalar) if steps.is_empty() => crate::TypeInner::Atomic(scalar),
+        crate::TypeInner::Struct { ref members, span } if !steps.is_empty() => {
+            let mut members = members.clone();
+            for &step in steps.iter() {
+                match step {
+                    Some(index) if (index as usize) < members.len() => {}
+                    Some(index) => return Err(Error::InvalidAccessIndex(index)),
+                    None => return Err(Error::UnsupportedType(ty)),
+                }
+            }
+            for (index, member) in members.iter_mut().enumerate() {
+                let member_rests = steps
+                    .iter()
+                    .zip(rests.iter())
+                    .filter(|&(&step, _)| step == Some(index as u32))
+                    .map(|(_, &rest)| rest)
+                    .collect::<Vec<_>>();
+                if !member_rests.is_empty() {
+                    member.ty = upgrade_atomic_type(types, member.ty, &member_rests, replaced)?;
+                }
+            }
+            crate::TypeInner::Struct { members, span }
+        }
+        crate::TypeInner::Array { base, size, stride } if !steps.is_empty() => {
+            crate::TypeInner::Array {
+                base: upgrade_atomic_type(types, base, &rests, replaced)?,
+                size,
+                stride,
+            }
+        }
+        crate::TypeInner::BindingArray { base, size } if !steps.is_empty() => {
+            crate::TypeInner::BindingArray {
+                base: upgrade_atomic_type(types, base, &rests, replaced)?,
+                size,
+            }
+        }
+        _ => return Err(Error::UnsupportedType(ty)),
+    };
+
+    let span = types.get_span(ty);
+    let name = types[ty].name.clone();
+    let upgraded = types.insert(crate::Type { name, inner }, span);
+    // Scalars are shared by everything, so only composite types are worth
+    // removing.
+    if upgraded != ty && !matches!(types[ty].inner, crate::TypeInner::Scalar(_)) {
+        replaced.push(ty);
+    }
+    Ok(upgraded)
+}
@@ -39,10 +39,16 @@ pub enum Error {
     UnsupportedBuiltIn(spirv::Word),
     #[error("unsupported control flow %{0}")]
     UnsupportedControlFlow(spirv::Word),
+    #[error("unsupported spec constant operation {0:?}")]
+    UnsupportedSpecConstantOp(spirv::Op),
     #[error("unsupported binary operator %{0}")]
     UnsupportedBinaryOperator(spirv::Word),
     #[error("Naga supports OpTypeRuntimeArray in the StorageBuffer storage class only")]
     UnsupportedRuntimeArrayStorageClass,
+    #[error(
+        "Naga supports atomic operations on global variables only, not through function arguments"
+    )]
+    UnsupportedAtomicFunctionArgument,
     #[error("unsupported matrix stride {stride} for a {columns}x{rows} matrix with scalar width={width}")]
     UnsupportedMatrixStride {
         stride: u32,
@@ -84,6 +90,8 @@ pub enum Error {
     InvalidBinding(spirv::Word),
     #[error("invalid global var {0:?}")]
     InvalidGlobalVar(crate::Expression),
+    #[error("invalid atomic pointer {0:?}")]
+    InvalidAtomicPointer(crate::Expression),
     #[error("invalid image/sampler expression {0:?}")]
     InvalidImageExpression(crate::Expression),
     #[error("invalid image base type {0:?}")]
@@ -126,4 +134,6 @@ pub enum Error {
          come from a binding)"
     )]
     NonBindingArrayOfImageOrSamplers,
+    #[error(transparent)]
+    ConstantEvaluator(#[from] crate::proc::ConstantEvaluatorError),
 }
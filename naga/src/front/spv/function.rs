@@ -280,6 +280,7 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
         }
 
         fun.body = block_ctx.lower();
+        super::atomic::patch_compare_exchange_results(&mut fun.expressions, module);
 
         // done
         let fun_handle = module.functions.append(fun, self.span_from_with_op(start));
@@ -560,6 +561,8 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
                 stage: ep.stage,
                 early_depth_test: ep.early_depth_test,
                 workgroup_size: ep.workgroup_size,
+                mesh_info: None,
+                task_payload: None,
                 function,
             });
         }
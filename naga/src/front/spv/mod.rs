@@ -24,9 +24,28 @@ Instead, we detect when such matrix is accessed in the `OpAccessChain`,
 and we generate a parallel expression that loads the value, but transposed.
 This value then gets used instead of `OpLoad` result later on.
 
+## Atomics
+
+SPIR-V performs atomic operations on pointers to ordinary integers, while our
+IR requires the pointee to have an atomic type. We remember which parts of
+which global variables are accessed atomically, and upgrade their types once
+the whole module has been parsed.
+
+Atomic operations on pointers passed as function arguments are valid SPIR-V,
+but aren't supported yet: the type of the argument, and of the global variable
+passed at each call site, would have to be upgraded as well.
+
+## Specialization constants
+
+`OpSpecConstant`, `OpSpecConstantTrue`, `OpSpecConstantFalse` and
+`OpSpecConstantComposite` become overridable constants, identified by their
+`SpecId`. `OpSpecConstantOp` is evaluated using the default values of its
+operands, since our IR can't express override expressions in constants yet.
+
 [spv]: https://www.khronos.org/registry/SPIR-V/
 */
 
+mod atomic;
 mod convert;
 mod error;
 mod function;
@@ -40,7 +59,7 @@ use function::*;
 use crate::{
     arena::{Arena, Handle, UniqueArena},
     proc::{Alignment, Layouter},
-    FastHashMap, FastHashSet, FastIndexMap,
+    FastHashMap, FastHashSet, FastIndexMap, FastIndexSet,
 };
 
 use petgraph::graphmap::GraphMap;
@@ -602,6 +621,10 @@ pub struct Frontend<I> {
     /// glslang declares those by default even though they are never written to
     /// (see <https://github.com/KhronosGroup/glslang/issues/1868>)
     gl_per_vertex_builtin_access: FastHashSet<crate::BuiltIn>,
+
+    /// Global variables accessed by atomic instructions, along with the path
+    /// to the accessed value, whose type needs to be made atomic.
+    upgrade_atomics: FastIndexMap<Handle<crate::GlobalVariable>, FastIndexSet<atomic::AtomicPath>>,
 }
 
 impl<I: Iterator<Item = u32>> Frontend<I> {
@@ -634,6 +657,7 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             options: options.clone(),
             switch_cases: FastIndexMap::default(),
             gl_per_vertex_builtin_access: FastHashSet::default(),
+            upgrade_atomics: FastIndexMap::default(),
         }
     }
 
@@ -3672,6 +3696,32 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                         log::warn!("Unsupported barrier execution scope: {}", exec_scope);
                     }
                 }
+                Op::AtomicLoad
+                | Op::AtomicStore
+                | Op::AtomicExchange
+                | Op::AtomicCompareExchange
+                | Op::AtomicCompareExchangeWeak
+                | Op::AtomicIIncrement
+                | Op::AtomicIDecrement
+                | Op::AtomicIAdd
+                | Op::AtomicISub
+                | Op::AtomicSMin
+                | Op::AtomicUMin
+                | Op::AtomicSMax
+                | Op::AtomicUMax
+                | Op::AtomicAnd
+                | Op::AtomicOr
+                | Op::AtomicXor => {
+                    self.parse_atomic(
+                        inst,
+                        ctx,
+                        &mut emitter,
+                        &mut block,
+                        block_id,
+                        body_idx,
+                        span,
+                    )?;
+                }
                 Op::CopyObject => {
                     inst.expect(4)?;
                     let result_type_id = self.next()?;
@@ -3810,7 +3860,8 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 | S::Store { .. }
                 | S::ImageStore { .. }
                 | S::Atomic { .. }
-                | S::RayQuery { .. } => {}
+                | S::RayQuery { .. }
+                | S::MeshFunction(_) => {}
                 S::Call {
                     function: ref mut callee,
                     ref arguments,
@@ -3942,10 +3993,17 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 Op::TypeSampledImage => self.parse_type_sampled_image(inst),
                 Op::TypeSampler => self.parse_type_sampler(inst, &mut module),
                 Op::Constant | Op::SpecConstant => self.parse_constant(inst, &mut module),
-                Op::ConstantComposite => self.parse_composite_constant(inst, &mut module),
+                Op::ConstantComposite | Op::SpecConstantComposite => {
+                    self.parse_composite_constant(inst, &mut module)
+                }
                 Op::ConstantNull | Op::Undef => self.parse_null_constant(inst, &mut module),
-                Op::ConstantTrue => self.parse_bool_constant(inst, true, &mut module),
-                Op::ConstantFalse => self.parse_bool_constant(inst, false, &mut module),
+                Op::ConstantTrue | Op::SpecConstantTrue => {
+                    self.parse_bool_constant(inst, true, &mut module)
+                }
+                Op::ConstantFalse | Op::SpecConstantFalse => {
+                    self.parse_bool_constant(inst, false, &mut module)
+                }
+                Op::SpecConstantOp => self.parse_spec_constant_op(inst, &mut module),
                 Op::Variable => self.parse_global_variable(inst, &mut module),
                 Op::Function => {
                     self.switch(ModuleState::Function, inst.op)?;
@@ -3995,6 +4053,19 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             }
         }
 
+        // Give everything accessed by atomic instructions an atomic type, and
+        // drop the original types if nothing else uses them, so the upgraded
+        // ones keep their names.
+        let mut replaced = Vec::new();
+        for (handle, paths) in self.upgrade_atomics.drain(..) {
+            let paths = paths.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let var = module.global_variables.get_mut(handle);
+            var.ty = atomic::upgrade_atomic_type(&mut module.types, var.ty, &paths, &mut replaced)?;
+        }
+        if !replaced.is_empty() {
+            crate::compact::remove_types(&mut module, &replaced);
+        }
+
         if !self.future_decor.is_empty() {
             log::warn!("Unused item decorations: {:?}", self.future_decor);
             self.future_decor.clear();
@@ -5056,6 +5127,180 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
         Ok(())
     }
 
+    /// Parse `OpSpecConstantOp`.
+    ///
+    /// Our IR can't hold override expressions in constant initializers yet, so
+    /// the operation is evaluated using the current values of its operands.
+    /// The result is an ordinary constant: specializing the operands later on
+    /// won't affect it.
+    fn parse_spec_constant_op(
+        &mut self,
+        inst: Instruction,
+        module: &mut crate::Module,
+    ) -> Result<(), Error> {
+        use spirv::Op;
+
+        let start = self.data_offset;
+        self.switch(ModuleState::Type, inst.op)?;
+        let operand_count = inst.expect_at_least(4)?;
+        let type_id = self.next()?;
+        let id = self.next()?;
+        let opcode = self.next()?;
+        let op = Op::from_u32(opcode).ok_or(Error::UnknownInstruction(opcode as u16))?;
+        let mut operands = Vec::with_capacity(operand_count as usize);
+        for _ in 0..operand_count {
+            operands.push(self.next()?);
+        }
+        let span = self.span_from_with_op(start);
+
+        let ty = self.lookup_type.lookup(type_id)?.handle;
+        let operand = |index: usize| -> Result<Handle<crate::Expression>, Error> {
+            let id = *operands
+                .get(index)
+                .ok_or(Error::InvalidOperandCount(inst.op, inst.wc))?;
+            Ok(module.constants[self.lookup_constant.lookup(id)?.handle].init)
+        };
+
+        let expr = match op {
+            Op::SNegate | Op::FNegate | Op::Not | Op::LogicalNot => crate::Expression::Unary {
+                op: match op {
+                    Op::Not => crate::UnaryOperator::BitwiseNot,
+                    Op::LogicalNot => crate::UnaryOperator::LogicalNot,
+                    _ => crate::UnaryOperator::Negate,
+                },
+                expr: operand(0)?,
+            },
+            Op::Select => {
+                // The constant evaluator doesn't support `Select`, but with a
+                // constant scalar condition we can just pick the operand.
+                let condition = operand(0)?;
+                let (accept, reject) = (operand(1)?, operand(2)?);
+                let init = match module.const_expressions[condition] {
+                    crate::Expression::Literal(crate::Literal::Bool(true)) => accept,
+                    crate::Expression::Literal(crate::Literal::Bool(false)) => reject,
+                    _ => return Err(Error::UnsupportedSpecConstantOp(op)),
+                };
+                self.insert_spec_constant_op_result(id, type_id, ty, init, span, module);
+                return Ok(());
+            }
+            Op::CompositeExtract => {
+                let mut init = operand(0)?;
+                for &index in operands.get(1..).unwrap_or_default() {
+                    init = crate::proc::ConstantEvaluator::for_glsl_module(module)
+                        .try_eval_and_append(
+                            &crate::Expression::AccessIndex { base: init, index },
+                            span,
+                        )?;
+                }
+                self.insert_spec_constant_op_result(id, type_id, ty, init, span, module);
+                return Ok(());
+            }
+            Op::SConvert
+            | Op::UConvert
+            | Op::FConvert
+            | Op::ConvertFToS
+            | Op::ConvertFToU
+            | Op::ConvertSToF
+            | Op::ConvertUToF => {
+                let scalar = module.types[ty]
+                    .inner
+                    .scalar()
+                    .ok_or(Error::UnsupportedType(ty))?;
+                crate::Expression::As {
+                    expr: operand(0)?,
+                    kind: scalar.kind,
+                    convert: Some(scalar.width),
+                }
+            }
+            Op::ShiftLeftLogical | Op::ShiftRightLogical | Op::ShiftRightArithmetic => {
+                let scalar = module.types[ty]
+                    .inner
+                    .scalar()
+                    .ok_or(Error::UnsupportedType(ty))?;
+                // Our `ShiftRight` is arithmetic for signed operands and
+                // logical for unsigned ones, so convert `Base` to the
+                // signedness the opcode asks for, and the result back to the
+                // result type. Same-width integer conversions keep the bits.
+                let (op, kind) = match op {
+                    Op::ShiftLeftLogical => (crate::BinaryOperator::ShiftLeft, scalar.kind),
+                    Op::ShiftRightLogical => {
+                        (crate::BinaryOperator::ShiftRight, crate::ScalarKind::Uint)
+                    }
+                    _ => (crate::BinaryOperator::ShiftRight, crate::ScalarKind::Sint),
+                };
+                let (base, shift) = (operand(0)?, operand(1)?);
+                let mut evaluator = crate::proc::ConstantEvaluator::for_glsl_module(module);
+                let left = evaluator.try_eval_and_append(
+                    &crate::Expression::As {
+                        expr: base,
+                        kind,
+                        convert: Some(scalar.width),
+                    },
+                    span,
+                )?;
+                let right = evaluator.try_eval_and_append(
+                    &crate::Expression::As {
+                        expr: shift,
+                        kind: crate::ScalarKind::Uint,
+                        convert: Some(4),
+                    },
+                    span,
+                )?;
+                let shifted = evaluator
+                    .try_eval_and_append(&crate::Expression::Binary { op, left, right }, span)?;
+                crate::Expression::As {
+                    expr: shifted,
+                    kind: scalar.kind,
+                    convert: Some(scalar.width),
+                }
+            }
+            _ => crate::Expression::Binary {
+                op: match op {
+                    Op::UMod => crate::BinaryOperator::Modulo,
+                    Op::LogicalAnd => crate::BinaryOperator::LogicalAnd,
+                    Op::LogicalOr => crate::BinaryOperator::LogicalOr,
+                    _ => {
+                        map_binary_operator(op).map_err(|_| Error::UnsupportedSpecConstantOp(op))?
+                    }
+                },
+                left: operand(0)?,
+                right: operand(1)?,
+            },
+        };
+
+        let init = crate::proc::ConstantEvaluator::for_glsl_module(module)
+            .try_eval_and_append(&expr, span)?;
+        self.insert_spec_constant_op_result(id, type_id, ty, init, span, module);
+        Ok(())
+    }
+
+    fn insert_spec_constant_op_result(
+        &mut self,
+        id: spirv::Word,
+        type_id: spirv::Word,
+        ty: Handle<crate::Type>,
+        init: Handle<crate::Expression>,
+        span: crate::Span,
+        module: &mut crate::Module,
+    ) {
+        let decor = self.future_decor.remove(&id).unwrap_or_default();
+        self.lookup_constant.insert(
+            id,
+            LookupConstant {
+                handle: module.constants.append(
+                    crate::Constant {
+                        r#override: crate::Override::None,
+                        name: decor.name,
+                        ty,
+                        init,
+                    },
+                    span,
+                ),
+                type_id,
+            },
+        );
+    }
+
     fn parse_global_variable(
         &mut self,
         inst: Instruction,
@@ -5338,6 +5583,49 @@ mod test {
         ];
         let _ = super::parse_u8_slice(&bin, &Default::default()).unwrap();
     }
+
+    #[test]
+    fn spec_constant_op_shift_right() {
+        #[rustfmt::skip]
+        let words = [
+            // Magic number, version 1.0, generator, bound, reserved.
+            0x07230203, 0x00010000, 0, 8, 0,
+            // OpCapability Shader
+            0x00020011, 1,
+            // OpMemoryModel Logical GLSL450
+            0x0003000e, 0, 1,
+            // %1 = OpTypeInt 32 1
+            0x00040015, 1, 32, 1,
+            // %2 = OpTypeInt 32 0
+            0x00040015, 2, 32, 0,
+            // %3 = OpConstant %1 -8
+            0x0004002b, 1, 3, 0xfffffff8,
+            // %4 = OpConstant %2 1
+            0x0004002b, 2, 4, 1,
+            // %6 = OpConstant %2 0xfffffff8
+            0x0004002b, 2, 6, 0xfffffff8,
+            // %5 = OpSpecConstantOp %1 ShiftRightLogical %3 %4
+            0x00060034, 1, 5, 194, 3, 4,
+            // %7 = OpSpecConstantOp %2 ShiftRightArithmetic %6 %4
+            0x00060034, 2, 7, 195, 6, 4,
+        ];
+        let module = super::Frontend::new(words.into_iter(), &Default::default())
+            .parse()
+            .unwrap();
+        let inits = module
+            .constants
+            .iter()
+            .map(|(_, constant)| &module.const_expressions[constant.init])
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            inits[3],
+            crate::Expression::Literal(crate::Literal::I32(0x7ffffffc))
+        ));
+        assert!(matches!(
+            inits[4],
+            crate::Expression::Literal(crate::Literal::U32(0xfffffffc))
+        ));
+    }
 }
 
 /// Helper function to check if `child` is in the scope of `parent`
@@ -244,6 +244,99 @@ impl crate::Module {
         handle
     }
 
+    /// Populate this module's [`SpecialTypes::external_texture_params`] type.
+    ///
+    /// Backends lower each [`External`] image into up to three planes and a
+    /// uniform buffer of this type, which the host fills in when it binds the
+    /// texture. In WGSL syntax, it is:
+    ///
+    /// ```ignore
+    /// struct NagaExternalTextureParams {
+    ///     // Converts `vec4(y, u, v, 1.0)` to RGBA.
+    ///     yuv_conversion_matrix: mat4x4<f32>,
+    ///     // The size of the texture, which is also the size of the first plane.
+    ///     size: vec2<u32>,
+    ///     // 1 for a single RGBA plane, 2 for Y and interleaved UV planes,
+    ///     // or 3 for separate Y, U and V planes.
+    ///     num_planes: u32,
+    /// }
+    /// ```
+    ///
+    /// Backends generate code that reads these fields by index, so if you
+    /// change them, you must update the backends to match. Look for backend
+    /// code dealing with [`ImageClass::External`].
+    ///
+    /// [`SpecialTypes::external_texture_params`]: crate::SpecialTypes::external_texture_params
+    /// [`External`]: crate::ImageClass::External
+    /// [`ImageClass::External`]: crate::ImageClass::External
+    pub fn generate_external_texture_params_type(&mut self) -> Handle<crate::Type> {
+        if let Some(handle) = self.special_types.external_texture_params {
+            return handle;
+        }
+
+        let ty_matrix = self.types.insert(
+            crate::Type {
+                name: None,
+                inner: crate::TypeInner::Matrix {
+                    columns: crate::VectorSize::Quad,
+                    rows: crate::VectorSize::Quad,
+                    scalar: crate::Scalar::F32,
+                },
+            },
+            Span::UNDEFINED,
+        );
+        let ty_size = self.types.insert(
+            crate::Type {
+                name: None,
+                inner: crate::TypeInner::Vector {
+                    size: crate::VectorSize::Bi,
+                    scalar: crate::Scalar::U32,
+                },
+            },
+            Span::UNDEFINED,
+        );
+        let ty_count = self.types.insert(
+            crate::Type {
+                name: None,
+                inner: crate::TypeInner::Scalar(crate::Scalar::U32),
+            },
+            Span::UNDEFINED,
+        );
+
+        let handle = self.types.insert(
+            crate::Type {
+                name: Some("NagaExternalTextureParams".to_string()),
+                inner: crate::TypeInner::Struct {
+                    members: vec![
+                        crate::StructMember {
+                            name: Some("yuv_conversion_matrix".to_string()),
+                            ty: ty_matrix,
+                            binding: None,
+                            offset: 0,
+                        },
+                        crate::StructMember {
+                            name: Some("size".to_string()),
+                            ty: ty_size,
+                            binding: None,
+                            offset: 64,
+                        },
+                        crate::StructMember {
+                            name: Some("num_planes".to_string()),
+                            ty: ty_count,
+                            binding: None,
+                            offset: 72,
+                        },
+                    ],
+                    span: 80,
+                },
+            },
+            Span::UNDEFINED,
+        );
+
+        self.special_types.external_texture_params = Some(handle);
+        handle
+    }
+
     /// Populate this module's [`SpecialTypes::predeclared_types`] type and return the handle.
     ///
     /// [`SpecialTypes::predeclared_types`]: crate::SpecialTypes::predeclared_types
@@ -10,14 +10,47 @@ use std::ops::Range;
 use termcolor::{ColorChoice, NoColor, StandardStream};
 use thiserror::Error;
 
+/// An error encountered while parsing WGSL.
+///
+/// The parser recovers from syntax errors at statement and declaration
+/// boundaries, and lowering continues past declarations with errors, so a
+/// single `ParseError` may describe several independent errors. Methods like
+/// [`message`] and [`labels`] describe the first; [`errors`] iterates over all
+/// of them, and the `emit_*` methods report all of them.
+///
+/// [`message`]: ParseError::message
+/// [`labels`]: ParseError::labels
+/// [`errors`]: ParseError::errors
 #[derive(Clone, Debug)]
 pub struct ParseError {
     message: String,
     labels: Vec<(Span, Cow<'static, str>)>,
     notes: Vec<String>,
+    /// The errors found after this one, if any.
+    subsequent: Vec<ParseError>,
 }
 
 impl ParseError {
+    /// Combine `errors` into a single `ParseError`.
+    ///
+    /// # Panics
+    ///
+    /// If `errors` is empty.
+    pub(crate) fn from_errors(errors: impl IntoIterator<Item = ParseError>) -> Self {
+        let mut errors = errors.into_iter();
+        let mut first = errors.next().expect("no errors to report");
+        first.subsequent.extend(errors);
+        first
+    }
+
+    /// Return all the errors found, starting with this one.
+    pub fn errors(&self) -> impl ExactSizeIterator<Item = &ParseError> + '_ {
+        std::iter::once(self)
+            .chain(self.subsequent.iter())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     pub fn labels(&self) -> impl ExactSizeIterator<Item = (Span, &str)> + '_ {
         self.labels
             .iter()
@@ -63,8 +96,10 @@ impl ParseError {
         let files = SimpleFile::new(path, source);
         let config = codespan_reporting::term::Config::default();
         let writer = StandardStream::stderr(ColorChoice::Auto);
-        term::emit(&mut writer.lock(), &config, &files, &self.diagnostic())
-            .expect("cannot write error");
+        for error in self.errors() {
+            term::emit(&mut writer.lock(), &config, &files, &error.diagnostic())
+                .expect("cannot write error");
+        }
     }
 
     /// Emits a summary of the error to a string.
@@ -81,7 +116,10 @@ impl ParseError {
         let files = SimpleFile::new(path, source);
         let config = codespan_reporting::term::Config::default();
         let mut writer = NoColor::new(Vec::new());
-        term::emit(&mut writer, &config, &files, &self.diagnostic()).expect("cannot write error");
+        for error in self.errors() {
+            term::emit(&mut writer, &config, &files, &error.diagnostic())
+                .expect("cannot write error");
+        }
         String::from_utf8(writer.into_inner()).unwrap()
     }
 
@@ -123,6 +161,8 @@ pub enum ExpectedToken<'a> {
     Variable,
     /// Access of a function
     Function,
+    /// Expected a string literal, as in an `import` directive.
+    String,
 }
 
 #[derive(Clone, Copy, Debug, Error, PartialEq)]
@@ -233,6 +273,16 @@ pub enum Error<'a> {
         /// the same identifier as `ident`, above.
         path: Vec<(Span, Span)>,
     },
+    /// The resolver couldn't find the file named by an `import` directive.
+    UnresolvedImport {
+        /// The span of the file name.
+        span: Span,
+        /// Why the resolver failed.
+        reason: String,
+    },
+    /// A file imports itself, directly or through other files.
+    #[cfg(feature = "link")]
+    CyclicImport(Span),
     InvalidSwitchValue {
         uint: bool,
         span: Span,
@@ -271,8 +321,33 @@ pub enum Error<'a> {
     },
 }
 
+/// A single error, before it is turned into a [`ParseError`] with no
+/// subsequent errors.
+struct ParseErrorEntry {
+    message: String,
+    labels: Vec<(Span, Cow<'static, str>)>,
+    notes: Vec<String>,
+}
+
 impl<'a> Error<'a> {
     pub(crate) fn as_parse_error(&self, source: &'a str) -> ParseError {
+        let ParseErrorEntry {
+            message,
+            labels,
+            notes,
+        } = self.as_parse_error_entry(source);
+        ParseError {
+            message,
+            labels,
+            notes,
+            subsequent: Vec::new(),
+        }
+    }
+
+    fn as_parse_error_entry(&self, source: &'a str) -> ParseErrorEntry {
+        // Each arm describes a single error.
+        use ParseErrorEntry as ParseError;
+
         match *self {
             Error::Unexpected(unexpected_span, expected) => {
                 let expected_str = match expected {
@@ -305,6 +380,7 @@ impl<'a> Error<'a> {
                     ExpectedToken::Type => "type".to_string(),
                     ExpectedToken::Variable => "variable access".to_string(),
                     ExpectedToken::Function => "function name".to_string(),
+                    ExpectedToken::String => "string literal".to_string(),
                 };
                 ParseError {
                     message: format!(
@@ -642,6 +718,17 @@ impl<'a> Error<'a> {
                     .collect(),
                 notes: vec![],
             },
+            Error::UnresolvedImport { span, ref reason } => ParseError {
+                message: format!("failed to resolve import {}", &source[span]),
+                labels: vec![(span, reason.clone().into())],
+                notes: vec![],
+            },
+            #[cfg(feature = "link")]
+            Error::CyclicImport(span) => ParseError {
+                message: format!("cyclic import of {}", &source[span]),
+                labels: vec![(span, "this file is already being imported".into())],
+                notes: vec![],
+            },
             Error::InvalidSwitchValue { uint, span } => ParseError {
                 message: "invalid switch value".to_string(),
                 labels: vec![(
@@ -0,0 +1,452 @@
+/*!
+Laying out WGSL source code in a consistent style.
+
+This does not regenerate WGSL from Naga IR, the way the WGSL backend does:
+that would lose comments, the spelling of literals, the names of inlined
+expressions, and the order of declarations. Instead, we keep every token of
+the original source, comments included, and only decide where the whitespace
+between them goes. This makes formatting lossless by construction: the
+formatted text always has the same tokens as the original.
+
+The brackets and separators determine most of the layout. The parser's
+[`TranslationUnit`] tells us the rest: where each module-scope declaration
+starts, and which operators are unary, like the `-` in `a * -b`.
+
+Line breaks are placed as follows:
+
+- Each statement and module-scope declaration starts on a new line, as does
+  each member of a struct. Module-scope attributes get a line of their own.
+
+- Blank lines in the source between statements, declarations or members are
+  kept, but runs of them are collapsed into one, and blank lines at the start
+  or end of a block are removed.
+
+- Line breaks within a statement or declaration, say in a long argument list,
+  are kept, and the continuation lines are indented by one level for each
+  bracket left open.
+
+[`TranslationUnit`]: super::parse::ast::TranslationUnit
+*/
+
+use super::parse::ast;
+use super::parse::lexer::{Lexer, Token};
+use crate::FastHashSet;
+
+const INDENT: &str = "    ";
+
+/// What a token is, as far as layout is concerned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Class {
+    /// Identifiers, keywords and literals.
+    Word,
+    Open(Bracket),
+    Close(Bracket),
+    Comma,
+    Semicolon,
+    Colon,
+    Dot,
+    /// The `@` that starts an attribute.
+    Attribute,
+    /// Binary and assignment operators, and `->`.
+    Binary,
+    /// Unary operators, like `-`, `!`, `&` or `*`.
+    Prefix,
+    /// `++` and `--`.
+    Postfix,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bracket {
+    Paren,
+    Square,
+    /// The angle brackets around a template list, like `vec3<f32>`.
+    Template,
+    /// The braces around a struct's members.
+    Struct,
+    /// Any other braces: function bodies, statement blocks and switch bodies.
+    Block,
+}
+
+impl Bracket {
+    const fn is_brace(self) -> bool {
+        matches!(self, Self::Struct | Self::Block)
+    }
+}
+
+/// Return `true` if `word` is followed by a template list when used as a
+/// type, like `vec3` in `vec3<f32>`.
+fn takes_template_list(word: &str) -> bool {
+    matches!(
+        word,
+        "array"
+            | "atomic"
+            | "binding_array"
+            | "bitcast"
+            | "ptr"
+            | "var"
+            | "vec2"
+            | "vec3"
+            | "vec4"
+            | "mat2x2"
+            | "mat2x3"
+            | "mat2x4"
+            | "mat3x2"
+            | "mat3x3"
+            | "mat3x4"
+            | "mat4x2"
+            | "mat4x3"
+            | "mat4x4"
+            | "texture_1d"
+            | "texture_2d"
+            | "texture_2d_array"
+            | "texture_3d"
+            | "texture_cube"
+            | "texture_cube_array"
+            | "texture_multisampled_2d"
+            | "texture_storage_1d"
+            | "texture_storage_2d"
+            | "texture_storage_2d_array"
+            | "texture_storage_3d"
+    )
+}
+
+/// Keywords that are separated from a following `(` by a space.
+const KEYWORDS_BEFORE_PAREN: &[&str] = &["case", "for", "if", "return", "switch", "while"];
+
+/// Return `source`, laid out in a consistent style.
+///
+/// The `tu` argument must be the result of parsing `source`.
+pub fn format(source: &str, tu: &ast::TranslationUnit) -> String {
+    let decl_starts = tu
+        .decls
+        .iter()
+        .filter_map(|(handle, _)| tu.decls.get_span(handle).to_range())
+        .map(|range| range.start)
+        .collect();
+    let prefix_operators = tu
+        .expressions
+        .iter()
+        .filter(|&(_, expr)| {
+            matches!(
+                *expr,
+                ast::Expression::Unary { .. }
+                    | ast::Expression::AddrOf(_)
+                    | ast::Expression::Deref(_)
+            )
+        })
+        .filter_map(|(handle, _)| tu.expressions.get_span(handle).to_range())
+        .map(|range| range.start)
+        .collect();
+
+    let mut formatter = Formatter {
+        out: String::with_capacity(source.len()),
+        decl_starts,
+        prefix_operators,
+        brackets: Vec::new(),
+        prev: None,
+        prev_text: "",
+        struct_pending: false,
+        newlines: 0,
+        after_comment: None,
+    };
+    formatter.run(source);
+    formatter.out
+}
+
+struct Formatter<'a> {
+    out: String,
+
+    /// The start offsets of the module-scope declarations.
+    decl_starts: FastHashSet<usize>,
+
+    /// The start offsets of the unary operators.
+    prefix_operators: FastHashSet<usize>,
+
+    /// The brackets enclosing the current token.
+    brackets: Vec<Bracket>,
+
+    /// The class of the previous token, or `None` at the start of the source.
+    prev: Option<Class>,
+
+    /// The text of the previous token.
+    prev_text: &'a str,
+
+    /// True if we have seen the `struct` keyword, but not the brace after it.
+    struct_pending: bool,
+
+    /// The number of line breaks in the source since the last token or comment.
+    newlines: usize,
+
+    /// The comment we just wrote, if any.
+    after_comment: Option<Comment>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Comment {
+    Line,
+    /// A block comment, and whether it started its own line.
+    Block {
+        own_line: bool,
+    },
+}
+
+impl<'a> Formatter<'a> {
+    fn run(&mut self, source: &'a str) {
+        let mut lexer = Lexer::new(source);
+        loop {
+            let generic = takes_template_list(self.prev_text)
+                || self.brackets.last() == Some(&Bracket::Template);
+            let restart = lexer.clone();
+            let (mut token, mut span) = lexer.next_with_trivia(generic);
+            if token == Token::Unknown('"') {
+                // The file names of `import` directives are string literals,
+                // which the lexer leaves to the parser.
+                let mut string = restart;
+                if let Ok((contents, string_span)) = string.next_string() {
+                    lexer = string;
+                    token = Token::Word(contents);
+                    span = string_span;
+                }
+            }
+            let Some(range) = span.to_range() else {
+                break;
+            };
+            let start = range.start;
+            let text = &source[range];
+
+            let class = match token {
+                Token::End => break,
+                Token::Trivia if text.starts_with("//") => {
+                    // Line comments include the line break that ends them.
+                    let comment = text.trim_end();
+                    self.comment(comment, true);
+                    self.newlines = text[comment.len()..].matches('\n').count();
+                    continue;
+                }
+                Token::Trivia if text.starts_with("/*") => {
+                    self.comment(text, false);
+                    continue;
+                }
+                Token::Trivia => {
+                    self.newlines += text.matches('\n').count();
+                    continue;
+                }
+                Token::Separator(',') => Class::Comma,
+                Token::Separator(';') => Class::Semicolon,
+                Token::Separator(':') => Class::Colon,
+                Token::Separator(_) => Class::Dot,
+                Token::Paren('(') => Class::Open(Bracket::Paren),
+                Token::Paren('[') => Class::Open(Bracket::Square),
+                Token::Paren('{') if self.struct_pending => Class::Open(Bracket::Struct),
+                Token::Paren('{') => Class::Open(Bracket::Block),
+                Token::Paren(')') => Class::Close(self.close(Bracket::Paren)),
+                Token::Paren(']') => Class::Close(self.close(Bracket::Square)),
+                Token::Paren('}') => Class::Close(self.close(Bracket::Block)),
+                Token::Paren('<') if takes_template_list(self.prev_text) => {
+                    Class::Open(Bracket::Template)
+                }
+                Token::Paren('>') if self.brackets.last() == Some(&Bracket::Template) => {
+                    self.brackets.pop();
+                    Class::Close(Bracket::Template)
+                }
+                Token::Operation(_) if self.prefix_operators.contains(&start) => Class::Prefix,
+                Token::Paren(_)
+                | Token::Operation(_)
+                | Token::LogicalOperation(_)
+                | Token::ShiftOperation(_)
+                | Token::AssignmentOperation(_)
+                | Token::Arrow => Class::Binary,
+                Token::IncrementOperation | Token::DecrementOperation => Class::Postfix,
+                Token::Attribute => Class::Attribute,
+                Token::Number(_) | Token::Word(_) | Token::Unknown(_) => Class::Word,
+            };
+
+            self.token(class, text, start);
+
+            match class {
+                Class::Open(bracket) => {
+                    if bracket == Bracket::Struct {
+                        self.struct_pending = false;
+                    }
+                    self.brackets.push(bracket);
+                }
+                Class::Word if text == "struct" => self.struct_pending = true,
+                _ => {}
+            }
+            self.prev = Some(class);
+            self.prev_text = text;
+        }
+
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+    }
+
+    /// Pop the innermost `bracket` from the bracket stack, and return it.
+    ///
+    /// All braces match each other, so a `bracket` of [`Bracket::Block`]
+    /// matches [`Bracket::Struct`] as well.
+    fn close(&mut self, bracket: Bracket) -> Bracket {
+        // Skip over any unclosed template lists. These can only arise from
+        // `<` comparisons that we took for the start of a template list.
+        while self.brackets.last() == Some(&Bracket::Template) {
+            self.brackets.pop();
+        }
+        self.brackets.pop().unwrap_or(bracket)
+    }
+
+    /// The number of braces enclosing the current token.
+    fn block_depth(&self) -> usize {
+        self.brackets
+            .iter()
+            .filter(|bracket| bracket.is_brace())
+            .count()
+    }
+
+    /// The number of parentheses, square brackets and template lists enclosing
+    /// the current token.
+    fn bracket_depth(&self) -> usize {
+        self.brackets.len() - self.block_depth()
+    }
+
+    fn comment(&mut self, text: &str, line: bool) {
+        let own_line = self.out.is_empty() || self.newlines > 0;
+        if own_line {
+            let blank =
+                self.newlines > 1 && !matches!(self.prev, Some(Class::Open(b)) if b.is_brace());
+            self.line_break(self.block_depth() + self.bracket_depth(), blank);
+        } else if !self.out.is_empty() {
+            self.out.push(' ');
+        }
+        self.out.push_str(text);
+        self.newlines = 0;
+        self.after_comment = Some(if line {
+            Comment::Line
+        } else {
+            Comment::Block { own_line }
+        });
+    }
+
+    fn token(&mut self, class: Class, text: &str, start: usize) {
+        let forced = match (self.prev, class) {
+            (None, _) => false,
+            (Some(Class::Open(open)), Class::Close(close))
+                if open.is_brace() && close.is_brace() =>
+            {
+                false
+            }
+            (Some(Class::Open(open)), _) if open.is_brace() => true,
+            (_, Class::Close(close)) if close.is_brace() => true,
+            (Some(Class::Close(close)), Class::Word) if close.is_brace() && text == "else" => false,
+            (Some(Class::Close(close)), Class::Semicolon | Class::Comma) if close.is_brace() => {
+                false
+            }
+            (Some(Class::Close(close)), _) if close.is_brace() => true,
+            (Some(Class::Semicolon), _) => self.bracket_depth() == 0,
+            (Some(Class::Comma), _) => self.brackets.last() == Some(&Bracket::Struct),
+            _ => self.brackets.is_empty() && self.decl_starts.contains(&start),
+        };
+        let after_comment = self.after_comment.take();
+        let forced = match after_comment {
+            None => forced,
+            Some(Comment::Line) => true,
+            // A block comment on a line of its own stands in for the line
+            // break before whatever follows it on that line.
+            Some(Comment::Block { own_line }) if self.newlines == 0 => forced && !own_line,
+            Some(Comment::Block { .. }) => true,
+        };
+
+        let keep = self.prev.is_some()
+            && self.newlines > 0
+            && text != "else"
+            && self.prev != Some(Class::Attribute)
+            && !matches!(
+                class,
+                Class::Comma
+                    | Class::Semicolon
+                    | Class::Colon
+                    | Class::Postfix
+                    | Class::Open(Bracket::Template | Bracket::Struct | Bracket::Block)
+                    | Class::Close(Bracket::Template | Bracket::Struct | Bracket::Block)
+            );
+
+        if forced {
+            let blank = self.newlines > 1
+                && !matches!(self.prev, Some(Class::Open(b)) if b.is_brace())
+                && !matches!(class, Class::Close(b) if b.is_brace());
+            let indent = self.block_depth() + self.bracket_depth();
+            self.line_break(indent, blank);
+        } else if keep {
+            // A line break the author chose. Indent the continuation by one
+            // level for each bracket still open, or one level if there are
+            // none: we are in the middle of a statement either way.
+            let depth = self.bracket_depth();
+            let extra = match class {
+                Class::Close(_) => depth,
+                _ => depth.max(1),
+            };
+            self.line_break(self.block_depth() + extra, false);
+        } else if let Some(prev) = self.prev {
+            let space = match after_comment {
+                Some(_) => !matches!(
+                    class,
+                    Class::Comma | Class::Semicolon | Class::Colon | Class::Dot | Class::Close(_)
+                ),
+                None => space_between(prev, self.prev_text, class, text),
+            };
+            if space {
+                self.out.push(' ');
+            }
+        }
+
+        self.out.push_str(text);
+        self.newlines = 0;
+    }
+
+    /// Start a new line, indented `indent` levels.
+    fn line_break(&mut self, indent: usize, blank: bool) {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+            if blank {
+                self.out.push('\n');
+            }
+        }
+        for _ in 0..indent {
+            self.out.push_str(INDENT);
+        }
+    }
+}
+
+/// Return `true` if adjacent tokens on the same line should be separated by a
+/// space.
+fn space_between(prev: Class, prev_text: &str, next: Class, next_text: &str) -> bool {
+    match (prev, next) {
+        // Keep `- -x` from becoming `--x`.
+        (Class::Prefix, Class::Prefix) => prev_text == "-" && next_text == "-",
+        (
+            _,
+            Class::Comma
+            | Class::Semicolon
+            | Class::Colon
+            | Class::Dot
+            | Class::Postfix
+            | Class::Open(Bracket::Template)
+            | Class::Close(Bracket::Paren | Bracket::Square | Bracket::Template),
+        ) => false,
+        (
+            Class::Open(Bracket::Paren | Bracket::Square | Bracket::Template)
+            | Class::Dot
+            | Class::Prefix
+            | Class::Attribute,
+            _,
+        ) => false,
+        (Class::Open(open), Class::Close(close)) if open.is_brace() && close.is_brace() => false,
+        (Class::Word, Class::Open(Bracket::Paren)) => KEYWORDS_BEFORE_PAREN.contains(&prev_text),
+        (Class::Close(Bracket::Template), Class::Open(Bracket::Paren)) => false,
+        (
+            Class::Word | Class::Close(Bracket::Paren | Bracket::Square | Bracket::Template),
+            Class::Open(Bracket::Square),
+        ) => false,
+        _ => true,
+    }
+}
@@ -0,0 +1,221 @@
+/*!
+Resolution of `import` directives.
+
+An `import "name";` directive makes the module-scope declarations of the
+named file visible in the file containing it. The files are appended to the
+main source, each once however many files import it, and spans refer to the
+result. [`SourceMap::locate`] maps them back to the files they came from.
+*/
+
+#[cfg(feature = "link")]
+use super::error::{Error, ParseError};
+#[cfg(feature = "link")]
+use super::parse::Parser;
+use crate::Span;
+#[cfg(feature = "link")]
+use std::ops::Range;
+
+/// A file returned by an [`ImportResolver`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportedSource {
+    /// The name of the file.
+    ///
+    /// Files with the same name are only loaded once, and this is passed back
+    /// to the resolver as the importer for the directives in `source`, so it's
+    /// usually the resolved path.
+    pub name: String,
+    /// The contents of the file.
+    pub source: String,
+}
+
+/// Provides the contents of the files named by `import` directives.
+///
+/// This is implemented for closures taking the same arguments as
+/// [`resolve`](ImportResolver::resolve).
+pub trait ImportResolver {
+    /// Find the file named `name`.
+    ///
+    /// `importer` is the [`name`](ImportedSource::name) of the file containing
+    /// the directive, or `None` for the main source. On failure, return a
+    /// message to report in the resulting error.
+    fn resolve(&mut self, name: &str, importer: Option<&str>) -> Result<ImportedSource, String>;
+}
+
+impl<F> ImportResolver for F
+where
+    F: FnMut(&str, Option<&str>) -> Result<ImportedSource, String>,
+{
+    fn resolve(&mut self, name: &str, importer: Option<&str>) -> Result<ImportedSource, String> {
+        self(name, importer)
+    }
+}
+
+/// Maps spans in the source with its imported files appended back to the
+/// files they came from.
+///
+/// Spans reported by [`Frontend::parse_with_imports`], both in errors and in
+/// the [`Module`]'s span information, refer to the expanded source. With
+/// [`Frontend::parse`], the expanded source is the original source.
+///
+/// [`Frontend::parse_with_imports`]: super::Frontend::parse_with_imports
+/// [`Frontend::parse`]: super::Frontend::parse
+/// [`Module`]: crate::Module
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    pub(super) expanded: String,
+    files: Vec<ImportedSource>,
+    /// The offset of each file in `expanded`.
+    starts: Vec<usize>,
+}
+
+impl SourceMap {
+    pub(super) const fn new() -> Self {
+        Self {
+            expanded: String::new(),
+            files: Vec::new(),
+            starts: Vec::new(),
+        }
+    }
+
+    /// Returns the main source, followed by each imported file.
+    pub fn expanded_source(&self) -> &str {
+        &self.expanded
+    }
+
+    /// Returns the imported files, in the order they were first imported.
+    pub fn files(&self) -> &[ImportedSource] {
+        &self.files
+    }
+
+    /// Returns the file `span` of the expanded source came from, and the
+    /// corresponding span in that file.
+    ///
+    /// The file is an index into [`files`](SourceMap::files), or `None` for the
+    /// main source.
+    pub fn locate(&self, span: Span) -> (Option<usize>, Span) {
+        let Some(range) = span.to_range() else {
+            return (None, span);
+        };
+        let Some(file) = self
+            .starts
+            .partition_point(|&start| start <= range.start)
+            .checked_sub(1)
+        else {
+            return (None, span);
+        };
+        let start = self.starts[file];
+        (
+            Some(file),
+            Span::from(range.start - start..range.end - start),
+        )
+    }
+
+    /// Make `source` the main source, with no files imported.
+    pub(super) fn reset(&mut self, source: &str) {
+        self.expanded.clear();
+        self.expanded.push_str(source);
+        self.files.clear();
+        self.starts.clear();
+    }
+}
+
+/// A file of the expanded source, and the files it imports.
+#[cfg(feature = "link")]
+pub(super) struct Unit {
+    /// The file's part of the expanded source.
+    pub range: Range<usize>,
+    /// The indices in the unit list of the files it imports, in order.
+    pub imports: Vec<usize>,
+}
+
+#[cfg(feature = "link")]
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Loading,
+    Loaded,
+}
+
+/// Load `source` and all the files it imports, directly or indirectly, into
+/// `map`.
+///
+/// Return the units, starting with the main source, and the order in which
+/// to lower them so that each comes after the files it imports. On failure,
+/// `map` still describes the files loaded so far, so that the error's span
+/// can be located.
+#[cfg(feature = "link")]
+pub(super) fn load(
+    source: &str,
+    resolver: &mut dyn ImportResolver,
+    map: &mut SourceMap,
+) -> Result<(Vec<Unit>, Vec<usize>), ParseError> {
+    map.reset(source);
+    let mut loader = Loader {
+        resolver,
+        map,
+        units: vec![Unit {
+            range: 0..source.len(),
+            imports: Vec::new(),
+        }],
+        states: vec![State::Loading],
+        order: Vec::new(),
+    };
+    loader.load(0)?;
+    Ok((loader.units, loader.order))
+}
+
+#[cfg(feature = "link")]
+struct Loader<'a> {
+    resolver: &'a mut dyn ImportResolver,
+    map: &'a mut SourceMap,
+    units: Vec<Unit>,
+    states: Vec<State>,
+    order: Vec<usize>,
+}
+
+#[cfg(feature = "link")]
+impl Loader<'_> {
+    fn load(&mut self, unit: usize) -> Result<(), ParseError> {
+        let range = self.units[unit].range.clone();
+        let directives: Vec<(String, Span)> = Parser::imports(&self.map.expanded, range)
+            .map_err(|error| error.as_parse_error(&self.map.expanded))?
+            .into_iter()
+            .map(|import| (import.name.to_string(), import.span))
+            .collect();
+
+        for (name, span) in directives {
+            let importer = unit
+                .checked_sub(1)
+                .map(|file| self.map.files[file].name.as_str());
+            let file = self.resolver.resolve(&name, importer).map_err(|reason| {
+                Error::UnresolvedImport { span, reason }.as_parse_error(&self.map.expanded)
+            })?;
+
+            let imported = match self.map.files.iter().position(|f| f.name == file.name) {
+                Some(file) => file + 1,
+                None => {
+                    self.map.expanded.push('\n');
+                    let start = self.map.expanded.len();
+                    self.map.expanded.push_str(&file.source);
+                    self.map.starts.push(start);
+                    self.map.files.push(file);
+                    self.units.push(Unit {
+                        range: start..self.map.expanded.len(),
+                        imports: Vec::new(),
+                    });
+                    self.states.push(State::Loading);
+                    let imported = self.units.len() - 1;
+                    self.load(imported)?;
+                    imported
+                }
+            };
+            if self.states[imported] == State::Loading {
+                return Err(Error::CyclicImport(span).as_parse_error(&self.map.expanded));
+            }
+            self.units[unit].imports.push(imported);
+        }
+
+        self.states[unit] = State::Loaded;
+        self.order.push(unit);
+        Ok(())
+    }
+}
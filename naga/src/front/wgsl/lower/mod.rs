@@ -1,18 +1,163 @@
 use std::num::NonZeroU32;
 
 use crate::front::wgsl::error::{Error, ExpectedToken, InvalidAssignmentType};
-use crate::front::wgsl::index::Index;
+use crate::front::wgsl::index::{self, Index};
 use crate::front::wgsl::parse::number::Number;
 use crate::front::wgsl::parse::{ast, conv};
 use crate::front::Typifier;
 use crate::proc::{
     ensure_block_returns, Alignment, ConstantEvaluator, Emitter, Layouter, ResolveContext,
 };
-use crate::{Arena, FastHashMap, FastIndexMap, Handle, Span};
+use crate::{Arena, FastHashMap, FastHashSet, FastIndexMap, Handle, Span};
 
 mod construction;
 mod conversion;
 
+/// The names of the builtin functions that WGSL source may call.
+///
+/// These are the functions [`Lowerer`] recognizes when a call's name isn't
+/// defined by the module itself.
+pub const BUILTIN_FUNCTIONS: &[&str] = &[
+    // Operators that look like function calls
+    "select",
+    "arrayLength",
+    "bitcast",
+    // Atomics
+    "atomicLoad",
+    "atomicStore",
+    "atomicAdd",
+    "atomicSub",
+    "atomicAnd",
+    "atomicOr",
+    "atomicXor",
+    "atomicMin",
+    "atomicMax",
+    "atomicExchange",
+    "atomicCompareExchangeWeak",
+    // Barriers and synchronization
+    "storageBarrier",
+    "workgroupBarrier",
+    "textureBarrier",
+    "workgroupUniformLoad",
+    // Texture queries and stores
+    "textureStore",
+    "textureLoad",
+    "textureDimensions",
+    "textureNumLevels",
+    "textureNumLayers",
+    "textureNumSamples",
+    // Texture sampling
+    "textureGather",
+    "textureGatherCompare",
+    "textureSample",
+    "textureSampleBias",
+    "textureSampleCompare",
+    "textureSampleCompareLevel",
+    "textureSampleGrad",
+    "textureSampleLevel",
+    "textureSampleBaseClampToEdge",
+    // Ray queries
+    "rayQueryInitialize",
+    "rayQueryProceed",
+    "rayQueryGetCommittedIntersection",
+    // Mesh shaders
+    "setMeshOutputs",
+    "setVertex",
+    "setPrimitive",
+    // Derivatives
+    "dpdxCoarse",
+    "dpdyCoarse",
+    "fwidthCoarse",
+    "dpdxFine",
+    "dpdyFine",
+    "fwidthFine",
+    "dpdx",
+    "dpdy",
+    "fwidth",
+    // Relational functions
+    "any",
+    "all",
+    // Math functions
+    "abs",
+    "min",
+    "max",
+    "clamp",
+    "saturate",
+    "cos",
+    "cosh",
+    "sin",
+    "sinh",
+    "tan",
+    "tanh",
+    "acos",
+    "acosh",
+    "asin",
+    "asinh",
+    "atan",
+    "atanh",
+    "atan2",
+    "radians",
+    "degrees",
+    "ceil",
+    "floor",
+    "round",
+    "fract",
+    "trunc",
+    "modf",
+    "frexp",
+    "ldexp",
+    "exp",
+    "exp2",
+    "log",
+    "log2",
+    "pow",
+    "dot",
+    "dot4I8Packed",
+    "dot4U8Packed",
+    "cross",
+    "distance",
+    "length",
+    "normalize",
+    "faceForward",
+    "reflect",
+    "refract",
+    "sign",
+    "fma",
+    "mix",
+    "step",
+    "smoothstep",
+    "sqrt",
+    "inverseSqrt",
+    "transpose",
+    "determinant",
+    "quantizeToF16",
+    "countTrailingZeros",
+    "countLeadingZeros",
+    "countOneBits",
+    "reverseBits",
+    "extractBits",
+    "insertBits",
+    "firstTrailingBit",
+    "firstLeadingBit",
+    // Packing
+    "pack4x8snorm",
+    "pack4x8unorm",
+    "pack2x16snorm",
+    "pack2x16unorm",
+    "pack2x16float",
+    "pack4xI8",
+    "pack4xU8",
+    "pack4xI8Clamp",
+    "pack4xU8Clamp",
+    "unpack4x8snorm",
+    "unpack4x8unorm",
+    "unpack2x16snorm",
+    "unpack2x16unorm",
+    "unpack2x16float",
+    "unpack4xI8",
+    "unpack4xU8",
+];
+
 /// Resolves the inner type of a given expression.
 ///
 /// Expects a &mut [`ExpressionContext`] and a [`Handle<Expression>`].
@@ -782,6 +927,7 @@ impl Components {
 }
 
 /// An `ast::GlobalDecl` for which we have built the Naga IR equivalent.
+#[derive(Clone, Copy)]
 enum LoweredGlobalDecl {
     Function(Handle<crate::Function>),
     Var(Handle<crate::GlobalVariable>),
@@ -800,7 +946,7 @@ enum Texture {
     SampleCompareLevel,
     SampleGrad,
     SampleLevel,
-    // SampleBaseClampToEdge,
+    SampleBaseClampToEdge,
 }
 
 impl Texture {
@@ -815,7 +961,7 @@ impl Texture {
             "textureSampleCompareLevel" => Self::SampleCompareLevel,
             "textureSampleGrad" => Self::SampleGrad,
             "textureSampleLevel" => Self::SampleLevel,
-            // "textureSampleBaseClampToEdge" => Some(Self::SampleBaseClampToEdge),
+            "textureSampleBaseClampToEdge" => Self::SampleBaseClampToEdge,
             _ => return None,
         })
     }
@@ -831,7 +977,26 @@ impl Texture {
             Self::SampleCompareLevel => 5,
             Self::SampleGrad => 6,
             Self::SampleLevel => 5,
-            // Self::SampleBaseClampToEdge => 3,
+            Self::SampleBaseClampToEdge => 3,
+        }
+    }
+}
+
+/// The module-scope declarations of a translation unit, by name.
+///
+/// These are returned by [`Lowerer::lower_into`], so that other translation
+/// units lowered into the same module can import them.
+#[derive(Default)]
+pub struct Declarations {
+    decls: FastHashMap<String, LoweredGlobalDecl>,
+}
+
+impl Declarations {
+    /// Add the declarations in `other` whose names aren't already present.
+    #[cfg(feature = "link")]
+    pub fn extend(&mut self, other: &Self) {
+        for (name, &decl) in other.decls.iter() {
+            self.decls.entry(name.clone()).or_insert(decl);
         }
     }
 }
@@ -849,146 +1014,224 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         }
     }
 
+    /// Lower `tu` to a Naga [`Module`].
+    ///
+    /// If a global declaration has errors, carry on with the others, so that
+    /// we can report errors in independent declarations together. Return all
+    /// the errors found, in dependency order.
+    ///
+    /// [`Module`]: crate::Module
     pub fn lower(
         &mut self,
         tu: &'temp ast::TranslationUnit<'source>,
-    ) -> Result<crate::Module, Error<'source>> {
+    ) -> Result<crate::Module, Vec<Error<'source>>> {
         let mut module = crate::Module::default();
+        self.lower_into(tu, &mut module, &Declarations::default())?;
+
+        // Constant evaluation may leave abstract-typed literals and
+        // compositions in expression arenas, so we need to compact the module
+        // to remove unused expressions and types.
+        crate::compact::compact(&mut module);
+
+        Ok(module)
+    }
+
+    /// Lower `tu` into `module`, after the items already there.
+    ///
+    /// Names that `tu` uses but doesn't declare are looked up in `imports`,
+    /// whose declarations must be in `module` already. Return `tu`'s own
+    /// declarations. Errors are handled as for [`lower`], and, as there, the
+    /// caller should compact `module` afterwards.
+    ///
+    /// [`lower`]: Lowerer::lower
+    pub fn lower_into(
+        &mut self,
+        tu: &'temp ast::TranslationUnit<'source>,
+        module: &mut crate::Module,
+        imports: &Declarations,
+    ) -> Result<Declarations, Vec<Error<'source>>> {
+        // Declarations in `tu` shadow imported ones, since lowering them
+        // replaces these entries.
+        let mut globals = FastHashMap::default();
+        for (_, decl) in tu.decls.iter() {
+            for dep in decl.dependencies.iter() {
+                if let Some(&imported) = imports.decls.get(dep.ident) {
+                    globals.insert(dep.ident, imported);
+                }
+            }
+        }
 
         let mut ctx = GlobalContext {
             ast_expressions: &tu.expressions,
-            globals: &mut FastHashMap::default(),
+            globals: &mut globals,
             types: &tu.types,
-            module: &mut module,
+            module,
             const_typifier: &mut Typifier::new(),
         };
 
+        let mut errors = Vec::new();
+        // Names of global declarations we couldn't lower. Declarations that
+        // use them are skipped, since any errors we found in them would likely
+        // just be consequences of the first.
+        let mut failed = FastHashSet::default();
+
         for decl_handle in self.index.visit_ordered() {
             let span = tu.decls.get_span(decl_handle);
             let decl = &tu.decls[decl_handle];
+            let name = index::decl_ident(decl).name;
+
+            if decl
+                .dependencies
+                .iter()
+                .any(|dep| failed.contains(dep.ident))
+            {
+                failed.insert(name);
+                continue;
+            }
 
-            match decl.kind {
-                ast::GlobalDeclKind::Fn(ref f) => {
-                    let lowered_decl = self.function(f, span, &mut ctx)?;
-                    ctx.globals.insert(f.name.name, lowered_decl);
-                }
-                ast::GlobalDeclKind::Var(ref v) => {
-                    let ty = self.resolve_ast_type(v.ty, &mut ctx)?;
-
-                    let init;
-                    if let Some(init_ast) = v.init {
-                        let mut ectx = ctx.as_const();
-                        let lowered = self.expression_for_abstract(init_ast, &mut ectx)?;
-                        let ty_res = crate::proc::TypeResolution::Handle(ty);
-                        let converted = ectx
-                            .try_automatic_conversions(lowered, &ty_res, v.name.span)
-                            .map_err(|error| match error {
-                                Error::AutoConversion {
-                                    dest_span: _,
-                                    dest_type,
-                                    source_span: _,
-                                    source_type,
-                                } => Error::InitializationTypeMismatch {
-                                    name: v.name.span,
-                                    expected: dest_type,
-                                    got: source_type,
-                                },
-                                other => other,
-                            })?;
-                        init = Some(converted);
-                    } else {
-                        init = None;
-                    }
-
-                    let binding = if let Some(ref binding) = v.binding {
-                        Some(crate::ResourceBinding {
-                            group: self.const_u32(binding.group, &mut ctx.as_const())?.0,
-                            binding: self.const_u32(binding.binding, &mut ctx.as_const())?.0,
-                        })
-                    } else {
-                        None
-                    };
+            if let Err(error) = self.global_decl(decl, span, &mut ctx) {
+                errors.push(error);
+                failed.insert(name);
+            }
+        }
 
-                    let handle = ctx.module.global_variables.append(
-                        crate::GlobalVariable {
-                            name: Some(v.name.name.to_string()),
-                            space: v.space,
-                            binding,
-                            ty,
-                            init,
-                        },
-                        span,
-                    );
+        if !errors.is_empty() {
+            return Err(errors);
+        }
 
-                    ctx.globals
-                        .insert(v.name.name, LoweredGlobalDecl::Var(handle));
+        let decls = tu
+            .decls
+            .iter()
+            .filter_map(|(_, decl)| {
+                let name = index::decl_ident(decl).name;
+                match globals[name] {
+                    LoweredGlobalDecl::EntryPoint => None,
+                    decl => Some((name.to_string(), decl)),
                 }
-                ast::GlobalDeclKind::Const(ref c) => {
+            })
+            .collect();
+        Ok(Declarations { decls })
+    }
+
+    /// Lower a single global declaration, adding it to `ctx.globals`.
+    fn global_decl(
+        &mut self,
+        decl: &ast::GlobalDecl<'source>,
+        span: Span,
+        ctx: &mut GlobalContext<'source, '_, '_>,
+    ) -> Result<(), Error<'source>> {
+        match decl.kind {
+            ast::GlobalDeclKind::Fn(ref f) => {
+                let lowered_decl = self.function(f, span, ctx)?;
+                ctx.globals.insert(f.name.name, lowered_decl);
+            }
+            ast::GlobalDeclKind::Var(ref v) => {
+                let ty = self.resolve_ast_type(v.ty, ctx)?;
+
+                let init;
+                if let Some(init_ast) = v.init {
                     let mut ectx = ctx.as_const();
-                    let mut init = self.expression_for_abstract(c.init, &mut ectx)?;
+                    let lowered = self.expression_for_abstract(init_ast, &mut ectx)?;
+                    let ty_res = crate::proc::TypeResolution::Handle(ty);
+                    let converted = ectx
+                        .try_automatic_conversions(lowered, &ty_res, v.name.span)
+                        .map_err(|error| match error {
+                            Error::AutoConversion {
+                                dest_span: _,
+                                dest_type,
+                                source_span: _,
+                                source_type,
+                            } => Error::InitializationTypeMismatch {
+                                name: v.name.span,
+                                expected: dest_type,
+                                got: source_type,
+                            },
+                            other => other,
+                        })?;
+                    init = Some(converted);
+                } else {
+                    init = None;
+                }
 
-                    let ty;
-                    if let Some(explicit_ty) = c.ty {
-                        let explicit_ty =
-                            self.resolve_ast_type(explicit_ty, &mut ectx.as_global())?;
-                        let explicit_ty_res = crate::proc::TypeResolution::Handle(explicit_ty);
-                        init = ectx
-                            .try_automatic_conversions(init, &explicit_ty_res, c.name.span)
-                            .map_err(|error| match error {
-                                Error::AutoConversion {
-                                    dest_span: _,
-                                    dest_type,
-                                    source_span: _,
-                                    source_type,
-                                } => Error::InitializationTypeMismatch {
-                                    name: c.name.span,
-                                    expected: dest_type,
-                                    got: source_type,
-                                },
-                                other => other,
-                            })?;
-                        ty = explicit_ty;
-                    } else {
-                        init = ectx.concretize(init)?;
-                        ty = ectx.register_type(init)?;
-                    }
+                let binding = if let Some(ref binding) = v.binding {
+                    Some(crate::ResourceBinding {
+                        group: self.const_u32(binding.group, &mut ctx.as_const())?.0,
+                        binding: self.const_u32(binding.binding, &mut ctx.as_const())?.0,
+                    })
+                } else {
+                    None
+                };
 
-                    let handle = ctx.module.constants.append(
-                        crate::Constant {
-                            name: Some(c.name.name.to_string()),
-                            r#override: crate::Override::None,
-                            ty,
-                            init,
-                        },
-                        span,
-                    );
+                let handle = ctx.module.global_variables.append(
+                    crate::GlobalVariable {
+                        name: Some(v.name.name.to_string()),
+                        space: v.space,
+                        binding,
+                        ty,
+                        init,
+                    },
+                    span,
+                );
 
-                    ctx.globals
-                        .insert(c.name.name, LoweredGlobalDecl::Const(handle));
-                }
-                ast::GlobalDeclKind::Struct(ref s) => {
-                    let handle = self.r#struct(s, span, &mut ctx)?;
-                    ctx.globals
-                        .insert(s.name.name, LoweredGlobalDecl::Type(handle));
-                }
-                ast::GlobalDeclKind::Type(ref alias) => {
-                    let ty = self.resolve_named_ast_type(
-                        alias.ty,
-                        Some(alias.name.name.to_string()),
-                        &mut ctx,
-                    )?;
-                    ctx.globals
-                        .insert(alias.name.name, LoweredGlobalDecl::Type(ty));
+                ctx.globals
+                    .insert(v.name.name, LoweredGlobalDecl::Var(handle));
+            }
+            ast::GlobalDeclKind::Const(ref c) => {
+                let mut ectx = ctx.as_const();
+                let mut init = self.expression_for_abstract(c.init, &mut ectx)?;
+
+                let ty;
+                if let Some(explicit_ty) = c.ty {
+                    let explicit_ty = self.resolve_ast_type(explicit_ty, &mut ectx.as_global())?;
+                    let explicit_ty_res = crate::proc::TypeResolution::Handle(explicit_ty);
+                    init = ectx
+                        .try_automatic_conversions(init, &explicit_ty_res, c.name.span)
+                        .map_err(|error| match error {
+                            Error::AutoConversion {
+                                dest_span: _,
+                                dest_type,
+                                source_span: _,
+                                source_type,
+                            } => Error::InitializationTypeMismatch {
+                                name: c.name.span,
+                                expected: dest_type,
+                                got: source_type,
+                            },
+                            other => other,
+                        })?;
+                    ty = explicit_ty;
+                } else {
+                    init = ectx.concretize(init)?;
+                    ty = ectx.register_type(init)?;
                 }
+
+                let handle = ctx.module.constants.append(
+                    crate::Constant {
+                        name: Some(c.name.name.to_string()),
+                        r#override: crate::Override::None,
+                        ty,
+                        init,
+                    },
+                    span,
+                );
+
+                ctx.globals
+                    .insert(c.name.name, LoweredGlobalDecl::Const(handle));
+            }
+            ast::GlobalDeclKind::Struct(ref s) => {
+                let handle = self.r#struct(s, span, ctx)?;
+                ctx.globals
+                    .insert(s.name.name, LoweredGlobalDecl::Type(handle));
+            }
+            ast::GlobalDeclKind::Type(ref alias) => {
+                let ty =
+                    self.resolve_named_ast_type(alias.ty, Some(alias.name.name.to_string()), ctx)?;
+                ctx.globals
+                    .insert(alias.name.name, LoweredGlobalDecl::Type(ty));
             }
         }
 
-        // Constant evaluation may leave abstract-typed literals and
-        // compositions in expression arenas, so we need to compact the module
-        // to remove unused expressions and types.
-        crate::compact::compact(&mut module);
-
-        Ok(module)
+        Ok(())
     }
 
     fn function(
@@ -1078,11 +1321,37 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 [0; 3]
             };
 
+            let mesh_info = if let Some(ref output) = entry.mesh_output {
+                Some(crate::MeshStageInfo {
+                    topology: output.topology,
+                    max_vertices: self.const_u32(output.max_vertices, &mut ctx.as_const())?.0,
+                    max_primitives: self
+                        .const_u32(output.max_primitives, &mut ctx.as_const())?
+                        .0,
+                    vertex_output_type: self.resolve_ast_type(output.vertex_type, ctx)?,
+                    primitive_output_type: self.resolve_ast_type(output.primitive_type, ctx)?,
+                })
+            } else {
+                None
+            };
+
+            let task_payload = if let Some(ident) = entry.task_payload {
+                match ctx.globals.get(ident.name) {
+                    Some(&LoweredGlobalDecl::Var(handle)) => Some(handle),
+                    Some(_) => return Err(Error::Unexpected(ident.span, ExpectedToken::Variable)),
+                    None => return Err(Error::UnknownIdent(ident.span, ident.name)),
+                }
+            } else {
+                None
+            };
+
             ctx.module.entry_points.push(crate::EntryPoint {
                 name: f.name.name.to_string(),
                 stage: entry.stage,
                 early_depth_test: entry.early_depth_test,
                 workgroup_size,
+                mesh_info,
+                task_payload,
                 function,
             });
             Ok(LoweredGlobalDecl::EntryPoint)
@@ -2102,6 +2371,14 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 .push(crate::Statement::Barrier(crate::Barrier::WORK_GROUP), span);
                             return Ok(None);
                         }
+                        "textureBarrier" => {
+                            ctx.prepare_args(arguments, 0, span).finish()?;
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .push(crate::Statement::Barrier(crate::Barrier::TEXTURE), span);
+                            return Ok(None);
+                        }
                         "workgroupUniformLoad" => {
                             let mut args = ctx.prepare_args(arguments, 1, span);
                             let expr = args.next()?;
@@ -2298,6 +2575,41 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 committed: true,
                             }
                         }
+                        "setMeshOutputs" => {
+                            let mut args = ctx.prepare_args(arguments, 2, span);
+                            let vertex_count = self.expression(args.next()?, ctx)?;
+                            let primitive_count = self.expression(args.next()?, ctx)?;
+                            args.finish()?;
+
+                            let fun = crate::MeshFunction::SetMeshOutputs {
+                                vertex_count,
+                                primitive_count,
+                            };
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .extend(rctx.emitter.finish(&rctx.function.expressions));
+                            rctx.emitter.start(&rctx.function.expressions);
+                            rctx.block.push(crate::Statement::MeshFunction(fun), span);
+                            return Ok(None);
+                        }
+                        "setVertex" | "setPrimitive" => {
+                            let mut args = ctx.prepare_args(arguments, 2, span);
+                            let index = self.expression(args.next()?, ctx)?;
+                            let value = self.expression(args.next()?, ctx)?;
+                            args.finish()?;
+
+                            let fun = if function.name == "setVertex" {
+                                crate::MeshFunction::SetVertex { index, value }
+                            } else {
+                                crate::MeshFunction::SetPrimitive { index, value }
+                            };
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .extend(rctx.emitter.finish(&rctx.function.expressions));
+                            rctx.emitter.start(&rctx.function.expressions);
+                            rctx.block.push(crate::Statement::MeshFunction(fun), span);
+                            return Ok(None);
+                        }
                         "RayDesc" => {
                             let ty = ctx.module.generate_ray_desc_type();
                             let handle = self.construct(
@@ -2478,13 +2790,18 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 let level = self.expression(args.next()?, ctx)?;
                 (crate::SampleLevel::Exact(level), None)
             }
+            Texture::SampleBaseClampToEdge => (crate::SampleLevel::Zero, None),
         };
 
-        let offset = args
-            .next()
-            .map(|arg| self.expression(arg, &mut ctx.as_const()))
-            .ok()
-            .transpose()?;
+        let clamp_to_edge = matches!(fun, Texture::SampleBaseClampToEdge);
+        let offset = match clamp_to_edge {
+            true => None,
+            false => args
+                .next()
+                .map(|arg| self.expression(arg, &mut ctx.as_const()))
+                .ok()
+                .transpose()?,
+        };
 
         args.finish()?;
 
@@ -2497,6 +2814,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             offset,
             level,
             depth_ref,
+            clamp_to_edge,
         })
     }
 
@@ -2668,11 +2986,17 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 dim,
                 arrayed,
                 class,
-            } => crate::TypeInner::Image {
-                dim,
-                arrayed,
-                class,
-            },
+            } => {
+                if class == crate::ImageClass::External {
+                    // Backends need this to lower the texture.
+                    ctx.module.generate_external_texture_params_type();
+                }
+                crate::TypeInner::Image {
+                    dim,
+                    arrayed,
+                    class,
+                }
+            }
             ast::Type::Sampler { comparison } => crate::TypeInner::Sampler { comparison },
             ast::Type::AccelerationStructure => crate::TypeInner::AccelerationStructure,
             ast::Type::RayQuery => crate::TypeInner::RayQuery,
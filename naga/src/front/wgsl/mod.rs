@@ -5,6 +5,8 @@ Frontend for [WGSL][wgsl] (WebGPU Shading Language).
 */
 
 mod error;
+mod format;
+mod import;
 mod index;
 mod lower;
 mod parse;
@@ -17,33 +19,185 @@ use crate::front::wgsl::parse::Parser;
 use thiserror::Error;
 
 pub use crate::front::wgsl::error::ParseError;
+pub use crate::front::wgsl::import::{ImportResolver, ImportedSource, SourceMap};
+pub use crate::front::wgsl::lower::BUILTIN_FUNCTIONS;
+#[cfg(feature = "link")]
+use crate::front::wgsl::lower::Declarations;
 use crate::front::wgsl::lower::Lowerer;
-use crate::Scalar;
+use crate::front::wgsl::parse::ast;
+use crate::{FastHashMap, Scalar, Span};
 
 pub struct Frontend {
     parser: Parser,
+    source_map: SourceMap,
+}
+
+/// A use of a name in WGSL source code, and the declaration it refers to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reference {
+    /// The span of the name where it is used.
+    pub usage: Span,
+    /// The span of the name in the declaration it refers to.
+    pub definition: Span,
 }
 
 impl Frontend {
     pub const fn new() -> Self {
         Self {
             parser: Parser::new(),
+            source_map: SourceMap::new(),
         }
     }
 
     pub fn parse(&mut self, source: &str) -> Result<crate::Module, ParseError> {
-        self.inner(source).map_err(|x| x.as_parse_error(source))
+        self.source_map.reset(source);
+        self.inner(source).map_err(|errors| {
+            ParseError::from_errors(errors.iter().map(|error| error.as_parse_error(source)))
+        })
+    }
+
+    /// Parse `source` like [`parse`](Frontend::parse), resolving its `import`
+    /// directives with `resolver`.
+    ///
+    /// An `import "name";` directive, which must come before any
+    /// declarations, makes the module-scope declarations of the named file
+    /// visible in the file containing it. Imports aren't transitive. A file's
+    /// own declarations shadow imported ones, and earlier imports shadow later
+    /// ones.
+    ///
+    /// Each file is lowered once, however many files import it, and they are
+    /// all combined into one module with a [`Linker`], which renames
+    /// declarations whose names conflict with those of files lowered earlier.
+    ///
+    /// Spans, both in errors and in the module, refer to the source with the
+    /// imported files appended. Use [`source_map`](Frontend::source_map) to
+    /// find the file they came from.
+    ///
+    /// Like [`parse`](Frontend::parse), the result is compacted once all the
+    /// files are lowered, which removes the expressions and unnamed types
+    /// nothing uses. Named declarations are kept, whether they are used or
+    /// not, so for a source without imports this returns the same module as
+    /// `parse`.
+    ///
+    /// [`Linker`]: crate::link::Linker
+    #[cfg(feature = "link")]
+    pub fn parse_with_imports(
+        &mut self,
+        source: &str,
+        resolver: &mut dyn ImportResolver,
+    ) -> Result<crate::Module, ParseError> {
+        let (units, order) = import::load(source, resolver, &mut self.source_map)?;
+
+        let expanded = self.source_map.expanded.as_str();
+        let as_parse_error = |errors: Vec<Error>| {
+            ParseError::from_errors(errors.iter().map(|error| error.as_parse_error(expanded)))
+        };
+
+        let mut linker = crate::link::Linker::new();
+        let mut declarations: Vec<_> = units.iter().map(|_| Declarations::default()).collect();
+        for unit in order {
+            let tu = self
+                .parser
+                .parse_part(expanded, units[unit].range.clone())
+                .map_err(as_parse_error)?;
+            let index = index::Index::generate(&tu).map_err(|error| as_parse_error(vec![error]))?;
+
+            let mut imports = Declarations::default();
+            for &imported in units[unit].imports.iter() {
+                imports.extend(&declarations[imported]);
+            }
+            declarations[unit] = linker
+                .extend(|module| Lowerer::new(&index).lower_into(&tu, module, &imports))
+                .map_err(as_parse_error)?;
+        }
+
+        let (mut module, _) = linker.finish();
+        crate::compact::compact(&mut module);
+        Ok(module)
+    }
+
+    /// Returns the mapping of the spans reported by the last parse back to
+    /// the files they came from.
+    ///
+    /// This is also valid after a parse fails, so it can be used to report
+    /// errors.
+    pub const fn source_map(&self) -> &SourceMap {
+        &self.source_map
     }
 
-    fn inner<'a>(&mut self, source: &'a str) -> Result<crate::Module, Error<'a>> {
+    fn inner<'a>(&mut self, source: &'a str) -> Result<crate::Module, Vec<Error<'a>>> {
         let tu = self.parser.parse(source)?;
-        let index = index::Index::generate(&tu)?;
+        if let Some(import) = tu.imports.first() {
+            return Err(vec![Error::UnresolvedImport {
+                span: import.span,
+                reason: "imports need `Frontend::parse_with_imports`".to_string(),
+            }]);
+        }
+        let index = index::Index::generate(&tu).map_err(|error| vec![error])?;
         let module = Lowerer::new(&index).lower(&tu)?;
 
         Ok(module)
     }
+
+    /// Parse `source`, and find the declaration each use of a name refers to.
+    ///
+    /// This only needs `source` to parse; it need not lower or validate
+    /// successfully. The references are returned in source order. Uses of
+    /// predeclared names, like `vec4` or `textureSample`, are not included.
+    pub fn references(&mut self, source: &str) -> Result<Vec<Reference>, ParseError> {
+        let tu = self.parser.parse(source).map_err(|errors| {
+            ParseError::from_errors(errors.iter().map(|error| error.as_parse_error(source)))
+        })?;
+
+        let mut globals = FastHashMap::default();
+        for (_, decl) in tu.decls.iter() {
+            let ident = index::decl_ident(decl);
+            globals.entry(ident.name).or_insert(ident.span);
+        }
+
+        let mut references: Vec<_> = tu
+            .references
+            .iter()
+            .filter_map(|reference| {
+                let definition = match reference.referent {
+                    ast::Referent::Local(span) => span,
+                    ast::Referent::Global(name) => *globals.get(name)?,
+                };
+                Some(Reference {
+                    usage: reference.usage,
+                    definition,
+                })
+            })
+            .collect();
+        references.sort_by_key(|reference| reference.usage.to_range().map(|range| range.start));
+        Ok(references)
+    }
+
+    /// Parse `source`, and return it laid out in a consistent style.
+    ///
+    /// Only whitespace changes: comments, literals, names and the order of
+    /// declarations are all kept as written. Statements, declarations and
+    /// struct members each start on a new line, indented four spaces per
+    /// level of nesting. Blank lines between them are kept, and line breaks
+    /// within them are kept as continuation lines. Formatting is idempotent.
+    ///
+    /// This only needs `source` to parse; it need not lower or validate
+    /// successfully.
+    pub fn format(&mut self, source: &str) -> Result<String, ParseError> {
+        let tu = self.parser.parse(source).map_err(|errors| {
+            ParseError::from_errors(errors.iter().map(|error| error.as_parse_error(source)))
+        })?;
+        Ok(format::format(source, &tu))
+    }
 }
 
 pub fn parse_str(source: &str) -> Result<crate::Module, ParseError> {
     Frontend::new().parse(source)
 }
+
+/// Return the WGSL in `source`, laid out in a consistent style.
+///
+/// See [`Frontend::format`] for details.
+pub fn format_str(source: &str) -> Result<String, ParseError> {
+    Frontend::new().format(source)
+}
@@ -24,6 +24,42 @@ pub struct TranslationUnit<'a> {
     /// These are referred to by `Handle<ast::Type<'a>>` values.
     /// User-defined types are referred to by name until lowering.
     pub types: Arena<Type<'a>>,
+
+    /// Every use of a name as an identifier, type or function.
+    ///
+    /// Lowering doesn't need these; they are for tools that want to find
+    /// what a name refers to.
+    pub references: Vec<Reference<'a>>,
+
+    /// The files named by `import` directives, in order.
+    pub imports: Vec<Import<'a>>,
+}
+
+/// An `import "name";` directive.
+#[derive(Debug, Clone, Copy)]
+pub struct Import<'a> {
+    /// The name of the file, without the quotes.
+    #[cfg_attr(not(feature = "link"), allow(dead_code))]
+    pub name: &'a str,
+    /// The span of the name, including the quotes.
+    pub span: Span,
+}
+
+/// A use of a name, and what it refers to.
+#[derive(Debug, Clone, Copy)]
+pub struct Reference<'a> {
+    /// Where the name is used.
+    pub usage: Span,
+    pub referent: Referent<'a>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Referent<'a> {
+    /// A function argument or local, whose name is declared at the given span.
+    Local(Span),
+    /// A module-scope or predeclared name, which we can't resolve until we've
+    /// seen the entire translation unit.
+    Global(&'a str),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -105,6 +141,19 @@ pub struct EntryPoint<'a> {
     pub stage: crate::ShaderStage,
     pub early_depth_test: Option<crate::EarlyDepthTest>,
     pub workgroup_size: Option<[Option<Handle<Expression<'a>>>; 3]>,
+    pub mesh_output: Option<MeshOutput<'a>>,
+    pub task_payload: Option<Ident<'a>>,
+}
+
+/// The `@output_topology`, `@vertex_output` and `@primitive_output`
+/// attributes of a mesh shader.
+#[derive(Debug)]
+pub struct MeshOutput<'a> {
+    pub topology: crate::MeshOutputTopology,
+    pub vertex_type: Handle<Type<'a>>,
+    pub max_vertices: Handle<Expression<'a>>,
+    pub primitive_type: Handle<Type<'a>>,
+    pub max_primitives: Handle<Expression<'a>>,
 }
 
 #[cfg(doc)]
@@ -11,6 +11,7 @@ pub fn map_address_space(word: &str, span: Span) -> Result<crate::AddressSpace,
             access: crate::StorageAccess::default(),
         }),
         "push_constant" => Ok(crate::AddressSpace::PushConstant),
+        "task_payload" => Ok(crate::AddressSpace::TaskPayload),
         "function" => Ok(crate::AddressSpace::Function),
         _ => Err(Error::UnknownAddressSpace(span)),
     }
@@ -35,6 +36,13 @@ pub fn map_built_in(word: &str, span: Span) -> Result<crate::BuiltIn, Error<'_>>
         "local_invocation_index" => crate::BuiltIn::LocalInvocationIndex,
         "workgroup_id" => crate::BuiltIn::WorkGroupId,
         "num_workgroups" => crate::BuiltIn::NumWorkGroups,
+        // task
+        "mesh_task_size" => crate::BuiltIn::MeshTaskSize,
+        // mesh
+        "cull_primitive" => crate::BuiltIn::CullPrimitive,
+        "point_index" => crate::BuiltIn::PointIndex,
+        "line_indices" => crate::BuiltIn::LineIndices,
+        "triangle_indices" => crate::BuiltIn::TriangleIndices,
         _ => return Err(Error::UnknownBuiltin(span)),
     })
 }
@@ -48,6 +56,18 @@ pub fn map_interpolation(word: &str, span: Span) -> Result<crate::Interpolation,
     }
 }
 
+pub fn map_mesh_output_topology(
+    word: &str,
+    span: Span,
+) -> Result<crate::MeshOutputTopology, Error<'_>> {
+    match word {
+        "points" => Ok(crate::MeshOutputTopology::Points),
+        "lines" => Ok(crate::MeshOutputTopology::Lines),
+        "triangles" => Ok(crate::MeshOutputTopology::Triangles),
+        _ => Err(Error::UnknownAttribute(span)),
+    }
+}
+
 pub fn map_sampling(word: &str, span: Span) -> Result<crate::Sampling, Error<'_>> {
     match word {
         "center" => Ok(crate::Sampling::Center),
@@ -198,6 +218,8 @@ pub fn map_standard_fun(word: &str) -> Option<crate::MathFunction> {
         "pow" => Mf::Pow,
         // geometry
         "dot" => Mf::Dot,
+        "dot4I8Packed" => Mf::Dot4I8Packed,
+        "dot4U8Packed" => Mf::Dot4U8Packed,
         "cross" => Mf::Cross,
         "distance" => Mf::Distance,
         "length" => Mf::Length,
@@ -215,6 +237,7 @@ pub fn map_standard_fun(word: &str) -> Option<crate::MathFunction> {
         "inverseSqrt" => Mf::InverseSqrt,
         "transpose" => Mf::Transpose,
         "determinant" => Mf::Determinant,
+        "quantizeToF16" => Mf::QuantizeToF16,
         // bits
         "countTrailingZeros" => Mf::CountTrailingZeros,
         "countLeadingZeros" => Mf::CountLeadingZeros,
@@ -230,12 +253,18 @@ pub fn map_standard_fun(word: &str) -> Option<crate::MathFunction> {
         "pack2x16snorm" => Mf::Pack2x16snorm,
         "pack2x16unorm" => Mf::Pack2x16unorm,
         "pack2x16float" => Mf::Pack2x16float,
+        "pack4xI8" => Mf::Pack4xI8,
+        "pack4xU8" => Mf::Pack4xU8,
+        "pack4xI8Clamp" => Mf::Pack4xI8Clamp,
+        "pack4xU8Clamp" => Mf::Pack4xU8Clamp,
         // data unpacking
         "unpack4x8snorm" => Mf::Unpack4x8snorm,
         "unpack4x8unorm" => Mf::Unpack4x8unorm,
         "unpack2x16snorm" => Mf::Unpack2x16snorm,
         "unpack2x16unorm" => Mf::Unpack2x16unorm,
         "unpack2x16float" => Mf::Unpack2x16float,
+        "unpack4xI8" => Mf::Unpack4xI8,
+        "unpack4xU8" => Mf::Unpack4xU8,
         _ => return None,
     })
 }
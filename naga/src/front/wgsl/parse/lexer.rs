@@ -215,6 +215,17 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Return a lexer for the part of `source` in `range`.
+    ///
+    /// Spans are still offsets in all of `source`.
+    pub(in crate::front::wgsl) fn new_part(source: &'a str, range: std::ops::Range<usize>) -> Self {
+        Lexer {
+            input: &source[range.clone()],
+            source: &source[..range.end],
+            last_end_offset: range.start,
+        }
+    }
+
     /// Calls the function with a lexer and returns the result of the function as well as the span for everything the function parsed
     ///
     /// # Examples
@@ -253,6 +264,11 @@ impl<'a> Lexer<'a> {
         (token, rest)
     }
 
+    /// Return the byte offset of the end of the last token consumed.
+    pub(in crate::front::wgsl) const fn last_end_offset(&self) -> usize {
+        self.last_end_offset
+    }
+
     const fn current_byte_offset(&self) -> usize {
         self.source.len() - self.input.len()
     }
@@ -297,6 +313,43 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Return the next token from `self`, including trivia.
+    ///
+    /// Unlike [`next`], this doesn't skip whitespace and comments, so the
+    /// formatter can preserve them. See [`consume_token`] for the meaning of
+    /// `generic`.
+    ///
+    /// [`next`]: Lexer::next
+    #[must_use]
+    pub(in crate::front::wgsl) fn next_with_trivia(&mut self, generic: bool) -> TokenSpan<'a> {
+        let start_byte_offset = self.current_byte_offset();
+        let (token, rest) = consume_token(self.input, generic);
+        self.input = rest;
+        (
+            token,
+            Span::from(start_byte_offset..self.current_byte_offset()),
+        )
+    }
+
+    /// Return the contents and span of the string literal next in `self`.
+    ///
+    /// WGSL has no string literals, but directives like `import` use them
+    /// to name files. They can't contain escapes or line breaks.
+    pub(in crate::front::wgsl) fn next_string(&mut self) -> Result<(&'a str, Span), Error<'a>> {
+        let start_byte_offset = self.start_byte_offset();
+        let contents = self.input.strip_prefix('"').and_then(|rest| {
+            let end = rest.find(['"', '\n'])?;
+            rest[end..].starts_with('"').then_some(&rest[..end])
+        });
+        let Some(contents) = contents else {
+            let (_, span) = self.next();
+            return Err(Error::Unexpected(span, ExpectedToken::String));
+        };
+        self.input = &self.input[contents.len() + 2..];
+        self.last_end_offset = self.current_byte_offset();
+        Ok((contents, self.span_from(start_byte_offset)))
+    }
+
     #[must_use]
     pub(in crate::front::wgsl) fn peek(&mut self) -> TokenSpan<'a> {
         let (token, _) = self.peek_token_and_rest();
@@ -53,6 +53,17 @@ struct ExpressionContext<'input, 'temp, 'out> {
     /// [`GlobalDecl`]: ast::GlobalDecl
     /// [`dependencies`]: ast::GlobalDecl::dependencies
     unresolved: &'out mut FastIndexSet<ast::Dependency<'input>>,
+
+    /// The [`TranslationUnit::references`] list to which we should add uses
+    /// of names.
+    ///
+    /// [`TranslationUnit::references`]: ast::TranslationUnit::references
+    references: &'out mut Vec<ast::Reference<'input>>,
+
+    /// Syntax errors in statements that we have recovered from so far.
+    ///
+    /// See [`Parser::statement_with_recovery`].
+    errors: &'temp mut Vec<Error<'input>>,
 }
 
 impl<'a> ExpressionContext<'a, '_, '_> {
@@ -90,6 +101,15 @@ impl<'a> ExpressionContext<'a, '_, '_> {
             Ok(handle)
         }
     }
+
+    /// Record a use of the module-scope or predeclared name `ident` at `usage`.
+    fn add_dependency(&mut self, ident: &'a str, usage: Span) {
+        self.unresolved.insert(ast::Dependency { ident, usage });
+        self.references.push(ast::Reference {
+            usage,
+            referent: ast::Referent::Global(ident),
+        });
+    }
 }
 
 /// Which grammar rule we are in the midst of parsing.
@@ -568,10 +588,7 @@ impl Parser {
             // everything else must be handled later, since they can be hidden by user-defined functions.
             _ => {
                 let arguments = self.arguments(lexer, ctx)?;
-                ctx.unresolved.insert(ast::Dependency {
-                    ident: name,
-                    usage: name_span,
-                });
+                ctx.add_dependency(name, name_span);
                 ast::Expression::Call {
                     function: ast::Ident {
                         name,
@@ -594,12 +611,15 @@ impl Parser {
         ctx: &mut ExpressionContext<'a, '_, '_>,
     ) -> ast::IdentExpr<'a> {
         match ctx.local_table.lookup(name) {
-            Some(&local) => ast::IdentExpr::Local(local),
-            None => {
-                ctx.unresolved.insert(ast::Dependency {
-                    ident: name,
+            Some(&local) => {
+                ctx.references.push(ast::Reference {
                     usage: name_span,
+                    referent: ast::Referent::Local(ctx.locals.get_span(local)),
                 });
+                ast::IdentExpr::Local(local)
+            }
+            None => {
+                ctx.add_dependency(name, name_span);
                 ast::IdentExpr::Unresolved(name)
             }
         }
@@ -1433,6 +1453,11 @@ impl Parser {
                     class: crate::ImageClass::Storage { format, access },
                 }
             }
+            "texture_external" => ast::Type::Image {
+                dim: crate::ImageDimension::D2,
+                arrayed: false,
+                class: crate::ImageClass::External,
+            },
             "acceleration_structure" => ast::Type::AccelerationStructure,
             "ray_query" => ast::Type::RayQuery,
             "RayDesc" => ast::Type::RayDesc,
@@ -1466,10 +1491,7 @@ impl Parser {
         let ty = match self.type_decl_impl(lexer, name, ctx)? {
             Some(ty) => ty,
             None => {
-                ctx.unresolved.insert(ast::Dependency {
-                    ident: name,
-                    usage: span,
-                });
+                ctx.add_dependency(name, span);
                 ast::Type::User(ast::Ident { name, span })
             }
         };
@@ -1565,10 +1587,7 @@ impl Parser {
     ) -> Result<(), Error<'a>> {
         self.push_rule_span(Rule::SingularExpr, lexer);
 
-        context.unresolved.insert(ast::Dependency {
-            ident,
-            usage: ident_span,
-        });
+        context.add_dependency(ident, ident_span);
         let arguments = self.arguments(lexer, context)?;
         let span = lexer.span_from(span_start);
 
@@ -2009,7 +2028,7 @@ impl Parser {
                         break;
                     } else {
                         // Otherwise try to parse a statement
-                        self.statement(lexer, ctx, &mut continuing)?;
+                        self.statement_with_recovery(lexer, ctx, &mut continuing)?;
                     }
                 }
                 // Since the continuing block must be the last part of the loop body,
@@ -2023,7 +2042,7 @@ impl Parser {
                 break;
             }
             // Otherwise try to parse a statement
-            self.statement(lexer, ctx, &mut body)?;
+            self.statement_with_recovery(lexer, ctx, &mut body)?;
         }
 
         ctx.local_table.pop_scope();
@@ -2048,7 +2067,7 @@ impl Parser {
         lexer.expect(Token::Paren('{'))?;
         let mut block = ast::Block::default();
         while !lexer.skip(Token::Paren('}')) {
-            self.statement(lexer, ctx, &mut block)?;
+            self.statement_with_recovery(lexer, ctx, &mut block)?;
         }
 
         ctx.local_table.pop_scope();
@@ -2079,6 +2098,7 @@ impl Parser {
         lexer: &mut Lexer<'a>,
         out: &mut ast::TranslationUnit<'a>,
         dependencies: &mut FastIndexSet<ast::Dependency<'a>>,
+        errors: &mut Vec<Error<'a>>,
     ) -> Result<ast::Function<'a>, Error<'a>> {
         self.push_rule_span(Rule::FunctionDecl, lexer);
         // read function name
@@ -2092,6 +2112,8 @@ impl Parser {
             locals: &mut locals,
             types: &mut out.types,
             unresolved: dependencies,
+            references: &mut out.references,
+            errors,
         };
 
         // start a scope that contains arguments as well as the function body
@@ -2137,7 +2159,7 @@ impl Parser {
         lexer.expect(Token::Paren('{'))?;
         let mut body = ast::Block::default();
         while !lexer.skip(Token::Paren('}')) {
-            self.statement(lexer, &mut ctx, &mut body)?;
+            self.statement_with_recovery(lexer, &mut ctx, &mut body)?;
         }
 
         ctx.local_table.pop_scope();
@@ -2161,13 +2183,18 @@ impl Parser {
         &mut self,
         lexer: &mut Lexer<'a>,
         out: &mut ast::TranslationUnit<'a>,
+        errors: &mut Vec<Error<'a>>,
     ) -> Result<(), Error<'a>> {
         // read attributes
         let mut binding = None;
         let mut stage = ParsedAttribute::default();
-        let mut compute_span = Span::new(0, 0);
+        let mut stage_span = Span::new(0, 0);
         let mut workgroup_size = ParsedAttribute::default();
         let mut early_depth_test = ParsedAttribute::default();
+        let mut output_topology = ParsedAttribute::default();
+        let mut vertex_output = ParsedAttribute::default();
+        let mut primitive_output = ParsedAttribute::default();
+        let mut task_payload = ParsedAttribute::default();
         let (mut bind_index, mut bind_group) =
             (ParsedAttribute::default(), ParsedAttribute::default());
 
@@ -2178,6 +2205,8 @@ impl Parser {
             locals: &mut Arena::new(),
             types: &mut out.types,
             unresolved: &mut dependencies,
+            references: &mut out.references,
+            errors,
         };
 
         self.push_rule_span(Rule::Attribute, lexer);
@@ -2201,7 +2230,45 @@ impl Parser {
                 }
                 ("compute", name_span) => {
                     stage.set(crate::ShaderStage::Compute, name_span)?;
-                    compute_span = name_span;
+                    stage_span = name_span;
+                }
+                ("task", name_span) => {
+                    stage.set(crate::ShaderStage::Task, name_span)?;
+                    stage_span = name_span;
+                }
+                ("mesh", name_span) => {
+                    stage.set(crate::ShaderStage::Mesh, name_span)?;
+                    stage_span = name_span;
+                }
+                ("output_topology", name_span) => {
+                    lexer.expect(Token::Paren('('))?;
+                    let (ident, ident_span) = lexer.next_ident_with_span()?;
+                    let value = conv::map_mesh_output_topology(ident, ident_span)?;
+                    lexer.expect(Token::Paren(')'))?;
+                    output_topology.set(value, name_span)?;
+                }
+                ("vertex_output", name_span) => {
+                    lexer.expect(Token::Paren('('))?;
+                    let ty = self.type_decl(lexer, &mut ctx)?;
+                    lexer.expect(Token::Separator(','))?;
+                    let count = self.general_expression(lexer, &mut ctx)?;
+                    lexer.expect(Token::Paren(')'))?;
+                    vertex_output.set((ty, count), name_span)?;
+                }
+                ("primitive_output", name_span) => {
+                    lexer.expect(Token::Paren('('))?;
+                    let ty = self.type_decl(lexer, &mut ctx)?;
+                    lexer.expect(Token::Separator(','))?;
+                    let count = self.general_expression(lexer, &mut ctx)?;
+                    lexer.expect(Token::Paren(')'))?;
+                    primitive_output.set((ty, count), name_span)?;
+                }
+                ("payload", name_span) => {
+                    lexer.expect(Token::Paren('('))?;
+                    let ident = lexer.next_ident()?;
+                    ctx.add_dependency(ident.name, ident.span);
+                    lexer.expect(Token::Paren(')'))?;
+                    task_payload.set(ident, name_span)?;
                 }
                 ("workgroup_size", name_span) => {
                     lexer.expect(Token::Paren('('))?;
@@ -2289,16 +2356,46 @@ impl Parser {
                 Some(ast::GlobalDeclKind::Var(var))
             }
             (Token::Word("fn"), _) => {
-                let function = self.function_decl(lexer, out, &mut dependencies)?;
+                let function = self.function_decl(lexer, out, &mut dependencies, errors)?;
                 Some(ast::GlobalDeclKind::Fn(ast::Function {
                     entry_point: if let Some(stage) = stage.value {
-                        if stage == ShaderStage::Compute && workgroup_size.value.is_none() {
-                            return Err(Error::MissingWorkgroupSize(compute_span));
+                        if matches!(
+                            stage,
+                            ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh
+                        ) && workgroup_size.value.is_none()
+                        {
+                            return Err(Error::MissingWorkgroupSize(stage_span));
                         }
+                        let mesh_output = if stage == ShaderStage::Mesh
+                            || output_topology.value.is_some()
+                            || vertex_output.value.is_some()
+                            || primitive_output.value.is_some()
+                        {
+                            let (vertex_type, max_vertices) = vertex_output
+                                .value
+                                .ok_or(Error::MissingAttribute("vertex_output", stage_span))?;
+                            let (primitive_type, max_primitives) = primitive_output
+                                .value
+                                .ok_or(Error::MissingAttribute("primitive_output", stage_span))?;
+                            Some(ast::MeshOutput {
+                                topology: output_topology.value.ok_or(Error::MissingAttribute(
+                                    "output_topology",
+                                    stage_span,
+                                ))?,
+                                vertex_type,
+                                max_vertices,
+                                primitive_type,
+                                max_primitives,
+                            })
+                        } else {
+                            None
+                        };
                         Some(ast::EntryPoint {
                             stage,
                             early_depth_test: early_depth_test.value,
                             workgroup_size: workgroup_size.value,
+                            mesh_output,
+                            task_payload: task_payload.value,
                         })
                     } else {
                         None
@@ -2329,22 +2426,177 @@ impl Parser {
         }
     }
 
-    pub fn parse<'a>(&mut self, source: &'a str) -> Result<ast::TranslationUnit<'a>, Error<'a>> {
+    /// Parse `source` as a WGSL translation unit.
+    ///
+    /// If there are syntax errors, skip to the next statement or global
+    /// declaration after each one and carry on, so that we can report as many
+    /// of them as possible. Return all the errors, in source order.
+    pub fn parse<'a>(
+        &mut self,
+        source: &'a str,
+    ) -> Result<ast::TranslationUnit<'a>, Vec<Error<'a>>> {
+        self.parse_part(source, 0..source.len())
+    }
+
+    /// Parse the part of `source` in `range`.
+    ///
+    /// This is for sources made of several files concatenated together, so
+    /// spans are still offsets in all of `source`.
+    pub fn parse_part<'a>(
+        &mut self,
+        source: &'a str,
+        range: std::ops::Range<usize>,
+    ) -> Result<ast::TranslationUnit<'a>, Vec<Error<'a>>> {
         self.reset();
 
-        let mut lexer = Lexer::new(source);
-        let mut tu = ast::TranslationUnit::default();
+        let mut lexer = Lexer::new_part(source, range);
+        let mut tu = ast::TranslationUnit {
+            imports: Self::import_directives(&mut lexer).map_err(|error| vec![error])?,
+            ..Default::default()
+        };
+        let mut errors = Vec::new();
         loop {
-            match self.global_decl(&mut lexer, &mut tu) {
-                Err(error) => return Err(error),
-                Ok(()) => {
-                    if lexer.peek().0 == Token::End {
-                        break;
+            let restart = lexer.clone();
+            if let Err(error) = self.global_decl(&mut lexer, &mut tu, &mut errors) {
+                errors.push(error);
+                self.reset();
+                let error_end = lexer.last_end_offset();
+                lexer = restart;
+                Self::skip_after_error(&mut lexer, error_end, Recovery::GlobalDecl);
+            }
+            if lexer.peek().0 == Token::End {
+                break;
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(tu)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse just the `import` directives of the part of `source` in `range`.
+    ///
+    /// See [`parse_part`](Parser::parse_part).
+    #[cfg(feature = "link")]
+    pub fn imports<'a>(
+        source: &'a str,
+        range: std::ops::Range<usize>,
+    ) -> Result<Vec<ast::Import<'a>>, Error<'a>> {
+        Self::import_directives(&mut Lexer::new_part(source, range))
+    }
+
+    /// Parse the `import` directives, which must come before any
+    /// declarations.
+    fn import_directives<'a>(lexer: &mut Lexer<'a>) -> Result<Vec<ast::Import<'a>>, Error<'a>> {
+        let mut imports = Vec::new();
+        while lexer.peek().0 == Token::Word("import") {
+            let _ = lexer.next();
+            let (name, span) = lexer.next_string()?;
+            lexer.expect(Token::Separator(';'))?;
+            imports.push(ast::Import { name, span });
+        }
+        Ok(imports)
+    }
+
+    /// Parse a statement, recovering from syntax errors.
+    ///
+    /// If the statement has a syntax error, record it in `ctx.errors` and skip
+    /// past the rest of the statement, so that the caller can go on to parse
+    /// the next one. If the error ran into the end of the source, return it
+    /// instead, since there is nothing left to recover with.
+    fn statement_with_recovery<'a>(
+        &mut self,
+        lexer: &mut Lexer<'a>,
+        ctx: &mut ExpressionContext<'a, '_, '_>,
+        block: &mut ast::Block<'a>,
+    ) -> Result<(), Error<'a>> {
+        let rules = self.rules.len();
+        let scopes = ctx.local_table.depth();
+        let restart = lexer.clone();
+        if let Err(error) = self.statement(lexer, ctx, block) {
+            self.rules.truncate(rules);
+            ctx.local_table.truncate(scopes);
+            let error_end = lexer.last_end_offset();
+            *lexer = restart;
+            Self::skip_after_error(lexer, error_end, Recovery::Statement);
+            if lexer.peek().0 == Token::End {
+                return Err(error);
+            }
+            ctx.errors.push(error);
+        }
+        Ok(())
+    }
+
+    /// Skip the statement or global declaration that `lexer` is at the start
+    /// of, which has a syntax error in the tokens ending at `error_end`.
+    ///
+    /// We count brackets from the start of the statement, and stop after the
+    /// first `;` or closing `}` outside of them that comes at or after the
+    /// error. We also stop before a `}` that closes the enclosing block, or
+    /// consume it when recovering at global scope, where it must be unmatched.
+    /// Global declarations also end before the keyword or attribute that
+    /// starts the next one.
+    ///
+    /// Only a `for` statement's header can have a `;` inside parentheses, so
+    /// for anything else we ignore parentheses and square brackets when
+    /// looking for the end, so that a missing `)` doesn't hide it.
+    fn skip_after_error(lexer: &mut Lexer<'_>, error_end: usize, recovery: Recovery) {
+        // The number of open parentheses and square brackets outside each open brace.
+        let mut braces = Vec::new();
+        let mut parens = 0usize;
+        let in_for = lexer.peek().0 == Token::Word("for");
+        let mut first = true;
+        loop {
+            match lexer.peek().0 {
+                Token::End => return,
+                Token::Paren('}') if braces.is_empty() => {
+                    if recovery == Recovery::GlobalDecl {
+                        let _ = lexer.next();
                     }
+                    return;
+                }
+                Token::Attribute
+                | Token::Word("fn" | "struct" | "var" | "const" | "override" | "alias")
+                    if braces.is_empty()
+                        && !first
+                        && recovery == Recovery::GlobalDecl
+                        && lexer.last_end_offset() >= error_end =>
+                {
+                    return
                 }
+                _ => {}
             }
-        }
+            first = false;
 
-        Ok(tu)
+            let (token, _) = lexer.next();
+            match token {
+                Token::Paren('(' | '[') => parens += 1,
+                Token::Paren(')' | ']') => parens = parens.saturating_sub(1),
+                Token::Paren('{') => {
+                    braces.push(parens);
+                    parens = 0;
+                }
+                Token::Paren('}') => parens = braces.pop().unwrap_or(0),
+                _ => {}
+            }
+            if braces.is_empty()
+                && (parens == 0 || !in_for)
+                && matches!(token, Token::Separator(';') | Token::Paren('}'))
+                && lexer.last_end_offset() >= error_end
+            {
+                return;
+            }
+        }
     }
 }
+
+/// Where [`Parser::skip_after_error`] should resume parsing.
+#[derive(Clone, Copy, PartialEq)]
+enum Recovery {
+    /// At the start of the next statement in the enclosing block.
+    Statement,
+    /// At the start of the next global declaration.
+    GlobalDecl,
+}
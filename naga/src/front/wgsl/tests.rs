@@ -615,8 +615,8 @@ fn parse_repeated_attributes() {
 
         let result = Frontend::new().inner(&shader);
         assert!(matches!(
-            result.unwrap_err(),
-            Error::RepeatedAttribute(span) if span == expected_span
+            result.unwrap_err()[..],
+            [Error::RepeatedAttribute(span)] if span == expected_span
         ));
     }
 }
@@ -631,7 +631,51 @@ fn parse_missing_workgroup_size() {
     let shader = "@compute fn vs() -> vec4<f32> { return vec4<f32>(0.0); }";
     let result = Frontend::new().inner(shader);
     assert!(matches!(
-        result.unwrap_err(),
-        Error::MissingWorkgroupSize(span) if span == Span::new(1, 8)
+        result.unwrap_err()[..],
+        [Error::MissingWorkgroupSize(span)] if span == Span::new(1, 8)
     ));
 }
+
+#[test]
+fn builtin_functions_are_known() {
+    use crate::front::wgsl::{error::Error, Frontend, BUILTIN_FUNCTIONS};
+
+    for name in BUILTIN_FUNCTIONS {
+        let shader = format!("fn f() {{ _ = {name}(); }}");
+        let errors = Frontend::new().inner(&shader).unwrap_err();
+        assert!(
+            !errors
+                .iter()
+                .any(|error| matches!(*error, Error::UnknownIdent(..))),
+            "`{name}` is not a builtin function"
+        );
+    }
+}
+
+#[test]
+fn references() {
+    use crate::front::wgsl::Frontend;
+
+    let shader = "
+        struct S { x: f32 }
+        fn fetch(s: S) -> f32 { let y = s.x; return y; }
+        fn main() { _ = fetch(S(1.0)) + abs(2.0); }
+    ";
+    let references = Frontend::new().references(shader).unwrap();
+    let pairs: Vec<_> = references
+        .iter()
+        .map(|reference| (&shader[reference.usage], &shader[reference.definition]))
+        .collect();
+    assert_eq!(
+        pairs,
+        [
+            ("S", "S"),
+            ("s", "s"),
+            ("y", "y"),
+            ("fetch", "fetch"),
+            ("S", "S")
+        ]
+    );
+    // The uses of `S` refer to the struct declaration.
+    assert_eq!(references[0].definition, references[4].definition);
+}
@@ -74,6 +74,10 @@ impl crate::TypeInner {
                 // TODO: Actually output the struct?
                 "struct".to_string()
             }
+            Ti::Image {
+                class: crate::ImageClass::External,
+                ..
+            } => "texture_external".to_string(),
             Ti::Image {
                 dim,
                 arrayed,
@@ -92,7 +96,8 @@ impl crate::TypeInner {
                     crate::ImageClass::Depth { multi: false } => "_depth",
                     crate::ImageClass::Depth { multi: true } => "_depth_multisampled",
                     crate::ImageClass::Sampled { multi: false, .. }
-                    | crate::ImageClass::Storage { .. } => "",
+                    | crate::ImageClass::Storage { .. }
+                    | crate::ImageClass::External => "",
                 };
 
                 let type_in_brackets = match class {
@@ -103,7 +108,9 @@ impl crate::TypeInner {
                         let element_type = crate::Scalar { kind, width: 4 }.to_wgsl();
                         format!("<{element_type}>")
                     }
-                    crate::ImageClass::Depth { multi: _ } => String::new(),
+                    crate::ImageClass::Depth { multi: _ } | crate::ImageClass::External => {
+                        String::new()
+                    }
                     crate::ImageClass::Storage { format, access } => {
                         if access.contains(crate::StorageAccess::STORE) {
                             format!("<{},write>", format.to_wgsl())
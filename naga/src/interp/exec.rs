@@ -0,0 +1,679 @@
+/*!
+Execution of a single invocation.
+
+An invocation's state is an explicit stack of [`Frame`]s, each with a stack of
+[`Cursor`]s into the blocks being executed, so that it can be suspended at a
+barrier and resumed once the rest of its workgroup gets there.
+*/
+
+use super::{
+    memory::{element, Memory, Pointee, Pointer, Region, RegionId},
+    value::{self, Value},
+    Context, Error, InvocationId, OutOfBounds,
+};
+use crate::{
+    proc::BoundsCheckPolicy, valid::FunctionInfo, AtomicFunction, BinaryOperator, Block,
+    Expression, Function, Handle, MathFunction, Statement, SwitchCase, SwitchValue,
+};
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Why an invocation stopped running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The invocation is waiting at a barrier.
+    ///
+    /// `id` identifies the barrier statement, so that we can check that the
+    /// whole workgroup waits at the same one.
+    Barrier {
+        id: usize,
+        flags: crate::Barrier,
+    },
+    Finished,
+}
+
+/// What a finished block was the body of.
+#[derive(Clone, Copy, Debug)]
+enum BlockKind {
+    Plain,
+    LoopBody,
+    LoopContinuing,
+    SwitchCase,
+}
+
+enum Cursor<'m> {
+    Block {
+        statements: &'m [Statement],
+        next: usize,
+        kind: BlockKind,
+    },
+    /// Marks a loop being executed. Always below its body or continuing
+    /// block.
+    Loop {
+        body: &'m Block,
+        continuing: &'m Block,
+        break_if: Option<Handle<Expression>>,
+    },
+    /// Marks a switch being executed. Always below the body of case `index`.
+    Switch {
+        cases: &'m [SwitchCase],
+        index: usize,
+    },
+}
+
+impl<'m> Cursor<'m> {
+    fn block(block: &'m Block, kind: BlockKind) -> Self {
+        Cursor::Block {
+            statements: block,
+            next: 0,
+            kind,
+        }
+    }
+}
+
+struct Frame<'m> {
+    function: &'m Function,
+    info: &'m FunctionInfo,
+    /// The function's handle, or `None` for the entry point.
+    handle: Option<Handle<Function>>,
+    arguments: Vec<Value>,
+    /// The values of the expressions evaluated so far.
+    values: Vec<Option<Value>>,
+    /// The index of the private region of the first local variable.
+    locals: usize,
+    cursors: Vec<Cursor<'m>>,
+    /// The caller's [`CallResult`] expression for the returned value.
+    ///
+    /// [`CallResult`]: Expression::CallResult
+    result: Option<Handle<Expression>>,
+}
+
+/// A [`WorkGroupUniformLoad`] waiting for its first barrier.
+///
+/// [`WorkGroupUniformLoad`]: Statement::WorkGroupUniformLoad
+struct UniformLoad {
+    id: usize,
+    pointer: Pointer,
+    result: Handle<Expression>,
+}
+
+/// The execution state of an invocation.
+pub struct Thread<'m> {
+    pub id: InvocationId,
+    frames: Vec<Frame<'m>>,
+    uniform_load: Option<UniformLoad>,
+}
+
+impl<'m> Thread<'m> {
+    pub const fn new(id: InvocationId) -> Self {
+        Thread {
+            id,
+            frames: Vec::new(),
+            uniform_load: None,
+        }
+    }
+}
+
+/// An invocation running with access to its memory.
+pub struct Exec<'a, 'm> {
+    pub ctx: &'a Context<'m>,
+    pub thread: &'a mut Thread<'m>,
+    pub memory: Memory<'a>,
+    pub out_of_bounds: &'a mut Vec<OutOfBounds>,
+    pub steps: &'a mut u64,
+}
+
+impl<'m> Exec<'_, 'm> {
+    fn frame(&self) -> &Frame<'m> {
+        self.thread.frames.last().unwrap()
+    }
+
+    fn frame_mut(&mut self) -> &mut Frame<'m> {
+        self.thread.frames.last_mut().unwrap()
+    }
+
+    /// Call `function`, allocating and initializing its local variables.
+    pub fn call(
+        &mut self,
+        function: &'m Function,
+        info: &'m FunctionInfo,
+        handle: Option<Handle<Function>>,
+        arguments: Vec<Value>,
+        result: Option<Handle<Expression>>,
+    ) -> Result<()> {
+        let module = self.ctx.module;
+        let locals = self.memory.private.len();
+        for (_, local) in function.local_variables.iter() {
+            let size = module.types[local.ty].inner.size(module.to_ctx());
+            self.memory
+                .private
+                .push(Region::new(vec![0; size as usize], true, false));
+        }
+        self.thread.frames.push(Frame {
+            function,
+            info,
+            handle,
+            arguments,
+            values: vec![None; function.expressions.len()],
+            locals,
+            cursors: vec![Cursor::block(&function.body, BlockKind::Plain)],
+            result,
+        });
+
+        for (handle, local) in function.local_variables.iter() {
+            if let Some(init) = local.init {
+                let value = self.eval(init)?;
+                self.memory.store(self.local(handle), value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn local(&self, local: Handle<crate::LocalVariable>) -> Pointer {
+        let frame = self.frame();
+        Pointer {
+            region: RegionId::Invocation(frame.locals + local.index()),
+            offset: 0,
+            pointee: Pointee::Type(frame.function.local_variables[local].ty),
+            in_bounds: true,
+        }
+    }
+
+    fn return_(&mut self, value: Option<Value>) -> Result<()> {
+        let frame = self.thread.frames.pop().unwrap();
+        self.memory.private.truncate(frame.locals);
+        if let Some(result) = frame.result {
+            let value = value.ok_or(Error::InvalidModule("missing return value"))?;
+            self.frame_mut().values[result.index()] = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Run until the invocation finishes or reaches a barrier.
+    pub fn run(&mut self) -> Result<Status> {
+        if let Some(load) = self.thread.uniform_load.take() {
+            // The first barrier has passed: load, then wait at the second.
+            let value = self.memory.load(load.pointer)?;
+            self.frame_mut().values[load.result.index()] = Some(value);
+            return Ok(Status::Barrier {
+                id: load.id,
+                flags: crate::Barrier::WORK_GROUP,
+            });
+        }
+
+        loop {
+            *self.steps += 1;
+            if *self.steps > self.ctx.options.step_limit {
+                return Err(Error::StepLimit);
+            }
+
+            let Some(frame) = self.thread.frames.last_mut() else {
+                return Ok(Status::Finished);
+            };
+            let Some(&mut Cursor::Block {
+                statements,
+                ref mut next,
+                kind,
+            }) = frame.cursors.last_mut()
+            else {
+                // Falling off the end of the function.
+                self.return_(None)?;
+                continue;
+            };
+
+            if let Some(statement) = statements.get(*next) {
+                *next += 1;
+                if let Some(status) = self.statement(statement)? {
+                    return Ok(status);
+                }
+            } else {
+                frame.cursors.pop();
+                self.block_finished(kind)?;
+            }
+        }
+    }
+
+    fn block_finished(&mut self, kind: BlockKind) -> Result<()> {
+        match kind {
+            BlockKind::Plain => {}
+            BlockKind::LoopBody => {
+                let cursors = &mut self.frame_mut().cursors;
+                if let Some(&Cursor::Loop { continuing, .. }) = cursors.last() {
+                    cursors.push(Cursor::block(continuing, BlockKind::LoopContinuing));
+                }
+            }
+            BlockKind::LoopContinuing => {
+                let Some(&Cursor::Loop { body, break_if, .. }) = self.frame().cursors.last() else {
+                    return Err(Error::InvalidModule("continuing block outside of a loop"));
+                };
+                let done = match break_if {
+                    Some(condition) => self.eval(condition)?.as_bool()?,
+                    None => false,
+                };
+                let cursors = &mut self.frame_mut().cursors;
+                if done {
+                    cursors.pop();
+                } else {
+                    cursors.push(Cursor::block(body, BlockKind::LoopBody));
+                }
+            }
+            BlockKind::SwitchCase => {
+                let cursors = &mut self.frame_mut().cursors;
+                if let Some(&mut Cursor::Switch {
+                    cases,
+                    ref mut index,
+                }) = cursors.last_mut()
+                {
+                    if cases[*index].fall_through && *index + 1 < cases.len() {
+                        *index += 1;
+                        let body = &cases[*index].body;
+                        cursors.push(Cursor::block(body, BlockKind::SwitchCase));
+                    } else {
+                        cursors.pop();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute `statement`, returning a status if the invocation must stop.
+    fn statement(&mut self, statement: &'m Statement) -> Result<Option<Status>> {
+        match *statement {
+            Statement::Emit(ref range) => {
+                for handle in range.clone() {
+                    let value = self.compute(handle)?;
+                    self.frame_mut().values[handle.index()] = Some(value);
+                }
+            }
+            Statement::Block(ref block) => {
+                let cursor = Cursor::block(block, BlockKind::Plain);
+                self.frame_mut().cursors.push(cursor);
+            }
+            Statement::If {
+                condition,
+                ref accept,
+                ref reject,
+            } => {
+                let block = if self.eval(condition)?.as_bool()? {
+                    accept
+                } else {
+                    reject
+                };
+                let cursor = Cursor::block(block, BlockKind::Plain);
+                self.frame_mut().cursors.push(cursor);
+            }
+            Statement::Switch {
+                selector,
+                ref cases,
+            } => {
+                let selector = self.eval(selector)?;
+                let index = cases
+                    .iter()
+                    .position(|case| match (case.value, &selector) {
+                        (SwitchValue::I32(value), &Value::I32(selector)) => value == selector,
+                        (SwitchValue::U32(value), &Value::U32(selector)) => value == selector,
+                        _ => false,
+                    })
+                    .or_else(|| {
+                        cases
+                            .iter()
+                            .position(|case| case.value == SwitchValue::Default)
+                    })
+                    .ok_or(Error::InvalidModule("switch without a default case"))?;
+                let cursors = &mut self.frame_mut().cursors;
+                cursors.push(Cursor::Switch { cases, index });
+                cursors.push(Cursor::block(&cases[index].body, BlockKind::SwitchCase));
+            }
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                break_if,
+            } => {
+                let cursors = &mut self.frame_mut().cursors;
+                cursors.push(Cursor::Loop {
+                    body,
+                    continuing,
+                    break_if,
+                });
+                cursors.push(Cursor::block(body, BlockKind::LoopBody));
+            }
+            Statement::Break => {
+                let cursors = &mut self.frame_mut().cursors;
+                while let Some(cursor) = cursors.pop() {
+                    if let Cursor::Loop { .. } | Cursor::Switch { .. } = cursor {
+                        break;
+                    }
+                }
+            }
+            Statement::Continue => {
+                let cursors = &mut self.frame_mut().cursors;
+                while let Some(cursor) = cursors.last() {
+                    if let Cursor::Loop { continuing, .. } = *cursor {
+                        cursors.push(Cursor::block(continuing, BlockKind::LoopContinuing));
+                        break;
+                    }
+                    cursors.pop();
+                }
+            }
+            Statement::Return { value } => {
+                let value = value.map(|value| self.eval(value)).transpose()?;
+                self.return_(value)?;
+            }
+            Statement::Barrier(flags) => {
+                return Ok(Some(Status::Barrier {
+                    id: barrier_id(statement),
+                    flags,
+                }));
+            }
+            Statement::Store { pointer, value } => {
+                let pointer = self.eval(pointer)?.as_pointer()?;
+                let value = self.eval(value)?;
+                self.memory.store(pointer, value)?;
+            }
+            Statement::Atomic {
+                pointer,
+                ref fun,
+                value,
+                result,
+            } => {
+                let pointer = self.eval(pointer)?.as_pointer()?;
+                let value = self.eval(value)?;
+                let result_value = self.atomic(pointer, fun, value)?;
+                self.frame_mut().values[result.index()] = Some(result_value);
+            }
+            Statement::WorkGroupUniformLoad { pointer, result } => {
+                let id = barrier_id(statement);
+                self.thread.uniform_load = Some(UniformLoad {
+                    id,
+                    pointer: self.eval(pointer)?.as_pointer()?,
+                    result,
+                });
+                return Ok(Some(Status::Barrier {
+                    id,
+                    flags: crate::Barrier::WORK_GROUP,
+                }));
+            }
+            Statement::Call {
+                function,
+                ref arguments,
+                result,
+            } => {
+                let arguments = arguments
+                    .iter()
+                    .map(|&argument| self.eval(argument))
+                    .collect::<Result<_>>()?;
+                let module = self.ctx.module;
+                let info = &self.ctx.info[function];
+                self.call(
+                    &module.functions[function],
+                    info,
+                    Some(function),
+                    arguments,
+                    result,
+                )?;
+            }
+            Statement::Kill => return Err(Error::Unsupported("discard")),
+            Statement::ImageStore { .. } => return Err(Error::Unsupported("images")),
+            Statement::RayQuery { .. } => return Err(Error::Unsupported("ray queries")),
+            Statement::MeshFunction(_) => return Err(Error::Unsupported("mesh shaders")),
+        }
+        Ok(None)
+    }
+
+    fn atomic(&mut self, pointer: Pointer, fun: &AtomicFunction, value: Value) -> Result<Value> {
+        let comparison = match *fun {
+            AtomicFunction::Exchange {
+                compare: Some(compare),
+            } => Some(self.eval(compare)?),
+            _ => None,
+        };
+        let mut exchanged = false;
+        let old = self.memory.atomic(pointer, |old| {
+            let old = old.clone();
+            Ok(Some(match *fun {
+                AtomicFunction::Add => value::scalar_binary(BinaryOperator::Add, old, value)?,
+                AtomicFunction::Subtract => {
+                    value::scalar_binary(BinaryOperator::Subtract, old, value)?
+                }
+                AtomicFunction::And => value::scalar_binary(BinaryOperator::And, old, value)?,
+                AtomicFunction::ExclusiveOr => {
+                    value::scalar_binary(BinaryOperator::ExclusiveOr, old, value)?
+                }
+                AtomicFunction::InclusiveOr => {
+                    value::scalar_binary(BinaryOperator::InclusiveOr, old, value)?
+                }
+                AtomicFunction::Min => value::math(MathFunction::Min, vec![old, value])?,
+                AtomicFunction::Max => value::math(MathFunction::Max, vec![old, value])?,
+                AtomicFunction::Exchange { compare: None } => value,
+                AtomicFunction::Exchange { compare: Some(_) } => {
+                    exchanged = comparison.as_ref() == Some(&old);
+                    if !exchanged {
+                        return Ok(None);
+                    }
+                    value
+                }
+            }))
+        })?;
+        Ok(match *fun {
+            AtomicFunction::Exchange {
+                compare: Some(_), ..
+            } => Value::Composite(vec![old, Value::Bool(exchanged)]),
+            _ => old,
+        })
+    }
+
+    /// Returns the value of `handle`, evaluating it if it wasn't emitted.
+    fn eval(&mut self, handle: Handle<Expression>) -> Result<Value> {
+        if let Some(ref value) = self.frame().values[handle.index()] {
+            return Ok(value.clone());
+        }
+        let value = self.compute(handle)?;
+        self.frame_mut().values[handle.index()] = Some(value.clone());
+        Ok(value)
+    }
+
+    /// Evaluate the expression `handle`.
+    fn compute(&mut self, handle: Handle<Expression>) -> Result<Value> {
+        let module = self.ctx.module;
+        let types = &module.types;
+        let function = self.frame().function;
+        let info = self.frame().info;
+
+        Ok(match function.expressions[handle] {
+            Expression::Literal(literal) => literal.into(),
+            Expression::Constant(constant) => self.ctx.constant(constant)?,
+            Expression::ZeroValue(ty) => Value::zero(types, &types[ty].inner)?,
+            Expression::Compose { ty, ref components } => {
+                let components = components
+                    .iter()
+                    .map(|&component| self.eval(component))
+                    .collect::<Result<_>>()?;
+                super::compose(&types[ty].inner, components)
+            }
+            Expression::Access { base, index } => {
+                let index = self.eval(index)?.as_index()?;
+                self.access(handle, base, index)?
+            }
+            Expression::AccessIndex { base, index } => self.access(handle, base, index as i64)?,
+            Expression::Splat { size, value } => {
+                Value::Composite(vec![self.eval(value)?; size as usize])
+            }
+            Expression::Swizzle {
+                size,
+                vector,
+                pattern,
+            } => {
+                let vector = self.eval(vector)?;
+                let components = vector.components()?;
+                Value::Composite(
+                    pattern[..size as usize]
+                        .iter()
+                        .map(|&component| components[component as usize].clone())
+                        .collect(),
+                )
+            }
+            Expression::FunctionArgument(index) => self.frame().arguments[index as usize].clone(),
+            Expression::GlobalVariable(global) => {
+                Value::Pointer(self.ctx.globals[global.index()].ok_or(Error::Unsupported(
+                    "global variables outside of the storage, uniform, workgroup and private address spaces",
+                ))?)
+            }
+            Expression::LocalVariable(local) => Value::Pointer(self.local(local)),
+            Expression::Load { pointer } => {
+                let pointer = self.eval(pointer)?.as_pointer()?;
+                self.memory.load(pointer)?
+            }
+            Expression::Unary { op, expr } => value::unary(op, self.eval(expr)?)?,
+            Expression::Binary { op, left, right } => {
+                let left_value = self.eval(left)?;
+                let right_value = self.eval(right)?;
+                value::binary(
+                    op,
+                    left_value,
+                    right_value,
+                    info[left].ty.inner_with(types),
+                    info[right].ty.inner_with(types),
+                )?
+            }
+            Expression::Select {
+                condition,
+                accept,
+                reject,
+            } => value::select(
+                self.eval(condition)?,
+                self.eval(accept)?,
+                self.eval(reject)?,
+            )?,
+            Expression::Relational { fun, argument } => {
+                value::relational(fun, self.eval(argument)?)?
+            }
+            Expression::Math {
+                fun,
+                arg,
+                arg1,
+                arg2,
+                arg3,
+            } => {
+                let arguments = [Some(arg), arg1, arg2, arg3]
+                    .into_iter()
+                    .flatten()
+                    .map(|argument| self.eval(argument))
+                    .collect::<Result<_>>()?;
+                value::math(fun, arguments)?
+            }
+            Expression::As {
+                expr,
+                kind,
+                convert,
+            } => value::cast(self.eval(expr)?, kind, convert)?,
+            Expression::ArrayLength(array) => {
+                let pointer = self.eval(array)?.as_pointer()?;
+                Value::U32(self.dynamic_length(pointer)?)
+            }
+            Expression::CallResult(_)
+            | Expression::AtomicResult { .. }
+            | Expression::WorkGroupUniformLoadResult { .. } => {
+                return Err(Error::InvalidModule("result used before it was produced"))
+            }
+            Expression::ImageSample { .. }
+            | Expression::ImageLoad { .. }
+            | Expression::ImageQuery { .. } => return Err(Error::Unsupported("images")),
+            Expression::Derivative { .. } => return Err(Error::Unsupported("derivatives")),
+            Expression::RayQueryProceedResult | Expression::RayQueryGetIntersection { .. } => {
+                return Err(Error::Unsupported("ray queries"))
+            }
+        })
+    }
+
+    /// Returns the number of elements of the runtime-sized array `pointer`
+    /// points to.
+    fn dynamic_length(&self, pointer: Pointer) -> Result<u32> {
+        let stride = element(&self.ctx.module.types, pointer.pointee, 1)?.offset;
+        let len = self.memory.region_len(pointer) as u32;
+        Ok(len.saturating_sub(pointer.offset) / stride)
+    }
+
+    /// Evaluate an [`Access`] or [`AccessIndex`] expression, applying the
+    /// bounds check policy.
+    ///
+    /// [`Access`]: Expression::Access
+    /// [`AccessIndex`]: Expression::AccessIndex
+    fn access(
+        &mut self,
+        handle: Handle<Expression>,
+        base: Handle<Expression>,
+        index: i64,
+    ) -> Result<Value> {
+        let types = &self.ctx.module.types;
+        let base_value = self.eval(base)?;
+
+        let length = match base_value {
+            Value::Pointer(pointer) => match element(types, pointer.pointee, 0)?.count {
+                Some(count) => count,
+                None => self.dynamic_length(pointer)?,
+            },
+            Value::Composite(ref components) => components.len() as u32,
+            _ => return Err(Error::InvalidModule("indexing a scalar")),
+        };
+
+        let mut in_bounds = true;
+        let index = if (0..length as i64).contains(&index) {
+            index as u32
+        } else {
+            let frame = self.frame();
+            let policy = self
+                .ctx
+                .options
+                .bounds_check_policies
+                .choose_policy(base, types, frame.info);
+            let out_of_bounds = OutOfBounds {
+                invocation: self.thread.id,
+                function: frame.handle,
+                expression: handle,
+                index,
+                length,
+                policy,
+            };
+            match policy {
+                BoundsCheckPolicy::Unchecked => return Err(Error::OutOfBounds(out_of_bounds)),
+                BoundsCheckPolicy::Restrict => {}
+                BoundsCheckPolicy::ReadZeroSkipWrite => in_bounds = false,
+            }
+            if !self.out_of_bounds.iter().any(|previous| {
+                previous.function == out_of_bounds.function
+                    && previous.expression == out_of_bounds.expression
+            }) {
+                self.out_of_bounds.push(out_of_bounds);
+            }
+            if length == 0 || !in_bounds {
+                0
+            } else {
+                index.clamp(0, length as i64 - 1) as u32
+            }
+        };
+
+        Ok(match base_value {
+            Value::Pointer(pointer) => {
+                let element = element(types, pointer.pointee, index)?;
+                Value::Pointer(Pointer {
+                    offset: pointer.offset + element.offset,
+                    pointee: element.pointee,
+                    in_bounds: pointer.in_bounds && in_bounds && length != 0,
+                    ..pointer
+                })
+            }
+            Value::Composite(mut components) if in_bounds => components.swap_remove(index as usize),
+            _ => {
+                let ty = self.frame().info[handle].ty.inner_with(types);
+                Value::zero(types, ty)?
+            }
+        })
+    }
+}
+
+/// Identifies a barrier by the address of its statement.
+fn barrier_id(statement: &Statement) -> usize {
+    let pointer: *const Statement = statement;
+    pointer as usize
+}
@@ -0,0 +1,538 @@
+/*!
+Byte-addressed memory for the interpreter, and data race detection.
+
+Every variable lives in a [`Region`] of bytes laid out as the Naga IR
+describes: struct members at their `offset`, array elements at multiples of
+their `stride`, and matrix columns aligned like vectors. This lets buffers be
+passed in and out of the interpreter without any conversion.
+*/
+
+use super::{value::Value, Error};
+use crate::{
+    proc::Alignment, Handle, Scalar, ScalarKind, Type, TypeInner, UniqueArena, VectorSize,
+};
+
+/// Identifies a [`Region`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionId {
+    /// A region shared by the invocations of a dispatch or a workgroup.
+    Shared(usize),
+    /// A region private to an invocation: a private global or a local
+    /// variable.
+    Invocation(usize),
+}
+
+/// The type a [`Pointer`] points to.
+///
+/// Pointers to the components of vectors and matrices have no type handle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pointee {
+    Type(Handle<Type>),
+    Vector { size: VectorSize, scalar: Scalar },
+    Scalar(Scalar),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pointer {
+    pub region: RegionId,
+    pub offset: u32,
+    pub pointee: Pointee,
+    /// False if this pointer was produced by an out-of-bounds access under
+    /// [`ReadZeroSkipWrite`]: loads through it produce zero, and stores do
+    /// nothing.
+    ///
+    /// [`ReadZeroSkipWrite`]: crate::proc::BoundsCheckPolicy::ReadZeroSkipWrite
+    pub in_bounds: bool,
+}
+
+/// The element of an indexable pointee, as returned by [`element`].
+pub struct Element {
+    pub offset: u32,
+    pub pointee: Pointee,
+    /// The number of elements, or `None` for runtime-sized arrays.
+    pub count: Option<u32>,
+}
+
+/// Returns the `index`th element or member of `pointee`, without checking
+/// `index` against the element count.
+pub fn element(types: &UniqueArena<Type>, pointee: Pointee, index: u32) -> Result<Element, Error> {
+    let (offset, pointee, count) = match pointee {
+        Pointee::Scalar(_) => return Err(Error::InvalidModule("indexing a scalar")),
+        Pointee::Vector { size, scalar } => (
+            index * scalar.width as u32,
+            Pointee::Scalar(scalar),
+            Some(size as u32),
+        ),
+        Pointee::Type(ty) => match types[ty].inner {
+            TypeInner::Vector { size, scalar } => (
+                index * scalar.width as u32,
+                Pointee::Scalar(scalar),
+                Some(size as u32),
+            ),
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => (
+                index * column_stride(rows, scalar),
+                Pointee::Vector { size: rows, scalar },
+                Some(columns as u32),
+            ),
+            TypeInner::Array { base, size, stride } => (
+                index * stride,
+                Pointee::Type(base),
+                match size {
+                    crate::ArraySize::Constant(count) => Some(count.get()),
+                    crate::ArraySize::Dynamic => None,
+                },
+            ),
+            TypeInner::Struct { ref members, .. } => {
+                let member = members
+                    .get(index as usize)
+                    .ok_or(Error::InvalidModule("struct member index"))?;
+                (
+                    member.offset,
+                    Pointee::Type(member.ty),
+                    Some(members.len() as u32),
+                )
+            }
+            _ => return Err(Error::InvalidModule("indexing a non-composite")),
+        },
+    };
+    Ok(Element {
+        offset,
+        pointee,
+        count,
+    })
+}
+
+fn column_stride(rows: VectorSize, scalar: Scalar) -> u32 {
+    Alignment::from(rows) * scalar.width as u32
+}
+
+/// Who performed a memory access, and when.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Access {
+    /// The index of the workgroup in the dispatch.
+    pub workgroup: u32,
+    /// The local index of the invocation in its workgroup.
+    pub invocation: u32,
+    /// The number of barriers affecting the accessed memory that the
+    /// workgroup has passed.
+    pub epoch: u32,
+    pub atomic: bool,
+}
+
+impl Access {
+    /// Returns true if `self` and `other` aren't ordered by a barrier.
+    const fn concurrent(&self, other: &Access) -> bool {
+        self.workgroup != other.workgroup
+            || (self.invocation != other.invocation && self.epoch == other.epoch)
+    }
+
+    const fn conflicts(&self, other: &Access) -> bool {
+        self.concurrent(other) && !(self.atomic && other.atomic)
+    }
+}
+
+/// The accesses to a word of a tracked region.
+#[derive(Clone, Debug, Default)]
+struct Word {
+    last_write: Option<Access>,
+    /// The reads since `last_write`.
+    ///
+    /// At most two reads by different invocations are kept for each
+    /// workgroup and epoch, which is enough to find a conflict with any
+    /// later write.
+    reads: Vec<Access>,
+    /// Whether a race on this word was already reported.
+    reported: bool,
+}
+
+/// Two conflicting accesses to the same word.
+#[derive(Clone, Copy, Debug)]
+pub struct Conflict {
+    /// The index of the shared region.
+    pub region: usize,
+    pub offset: u32,
+    pub first: Access,
+    pub second: Access,
+}
+
+#[derive(Clone, Debug)]
+pub struct Region {
+    pub bytes: Vec<u8>,
+    /// Access history for each 4-byte word, if this region is shared.
+    words: Option<Vec<Word>>,
+    /// Whether stores are allowed.
+    pub writable: bool,
+}
+
+impl Region {
+    pub fn new(bytes: Vec<u8>, writable: bool, tracked: bool) -> Self {
+        let words = tracked.then(|| vec![Word::default(); (bytes.len() + 3) / 4]);
+        Region {
+            bytes,
+            words,
+            writable,
+        }
+    }
+
+    /// Clear the contents and access history of the region.
+    pub fn reset(&mut self) {
+        self.bytes.fill(0);
+        if let Some(ref mut words) = self.words {
+            words.fill(Word::default());
+        }
+    }
+
+    /// Record an access to `width` bytes at `offset`, and add any conflicts
+    /// it causes to `conflicts`.
+    fn track(
+        &mut self,
+        region: usize,
+        offset: u32,
+        width: u32,
+        access: Access,
+        write: bool,
+        conflicts: &mut Vec<Conflict>,
+    ) {
+        let Some(ref mut words) = self.words else {
+            return;
+        };
+        let first = offset / 4;
+        let last = (offset + width.max(1) - 1) / 4;
+        for (index, word) in words
+            .iter_mut()
+            .enumerate()
+            .take(last as usize + 1)
+            .skip(first as usize)
+        {
+            let conflict = word
+                .last_write
+                .filter(|previous| previous.conflicts(&access))
+                .or_else(|| {
+                    if write {
+                        word.reads
+                            .iter()
+                            .find(|previous| previous.conflicts(&access))
+                            .copied()
+                    } else {
+                        None
+                    }
+                });
+            if let Some(previous) = conflict {
+                if !word.reported {
+                    word.reported = true;
+                    conflicts.push(Conflict {
+                        region,
+                        offset: index as u32 * 4,
+                        first: previous,
+                        second: access,
+                    });
+                }
+            }
+
+            if write {
+                word.last_write = Some(access);
+                word.reads.clear();
+            } else {
+                let mut same_epoch = word.reads.iter().filter(|previous| {
+                    previous.workgroup == access.workgroup && previous.epoch == access.epoch
+                });
+                let known = match (same_epoch.next(), same_epoch.next()) {
+                    (Some(_), Some(_)) => true,
+                    (Some(previous), None) => previous.invocation == access.invocation,
+                    _ => false,
+                };
+                if !known {
+                    word.reads.push(access);
+                }
+            }
+        }
+    }
+
+    fn read_scalar(&self, offset: u32, scalar: Scalar) -> Result<Value, Error> {
+        let start = offset as usize;
+        let bytes = self
+            .bytes
+            .get(start..start + scalar.width as usize)
+            .ok_or(Error::InvalidModule("access outside of a variable"))?;
+        let word = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let double = |bytes: &[u8]| {
+            let mut array = [0; 8];
+            array.copy_from_slice(bytes);
+            u64::from_le_bytes(array)
+        };
+        Ok(match (scalar.kind, scalar.width) {
+            (ScalarKind::Bool, _) => Value::Bool(bytes.iter().any(|&byte| byte != 0)),
+            (ScalarKind::Sint, 4) => Value::I32(word(bytes) as i32),
+            (ScalarKind::Uint, 4) => Value::U32(word(bytes)),
+            (ScalarKind::Float, 4) => Value::F32(f32::from_bits(word(bytes))),
+            (ScalarKind::Sint, 8) => Value::I64(double(bytes) as i64),
+            (ScalarKind::Float, 8) => Value::F64(f64::from_bits(double(bytes))),
+            _ => return Err(Error::Unsupported("scalar type in memory")),
+        })
+    }
+
+    fn write_scalar(&mut self, offset: u32, value: &Value) -> Result<(), Error> {
+        let bytes = match *value {
+            Value::Bool(v) => vec![v as u8],
+            Value::I32(v) => v.to_le_bytes().to_vec(),
+            Value::U32(v) => v.to_le_bytes().to_vec(),
+            Value::F32(v) => v.to_le_bytes().to_vec(),
+            Value::I64(v) => v.to_le_bytes().to_vec(),
+            Value::F64(v) => v.to_le_bytes().to_vec(),
+            Value::Composite(_) | Value::Pointer(_) => {
+                return Err(Error::InvalidModule("storing a non-scalar"))
+            }
+        };
+        let start = offset as usize;
+        self.bytes
+            .get_mut(start..start + bytes.len())
+            .ok_or(Error::InvalidModule("access outside of a variable"))?
+            .copy_from_slice(&bytes);
+        Ok(())
+    }
+}
+
+/// The memory an invocation can reach: the shared regions, and its own.
+pub struct Memory<'a> {
+    pub types: &'a UniqueArena<Type>,
+    pub shared: &'a mut [Region],
+    pub private: &'a mut Vec<Region>,
+    /// Identifies the accesses made through this `Memory`, except for the
+    /// epoch, which depends on the region.
+    pub access: Access,
+    /// The epochs of each shared region.
+    pub epochs: &'a [u32],
+    pub conflicts: &'a mut Vec<Conflict>,
+}
+
+/// Which kind of access [`Memory::visit`] is performing.
+enum Visit<'v> {
+    Load(&'v mut Vec<Value>),
+    Store(&'v mut dyn Iterator<Item = Value>),
+}
+
+impl Memory<'_> {
+    /// Returns the size in bytes of the region `pointer` points into.
+    pub fn region_len(&self, pointer: Pointer) -> usize {
+        match pointer.region {
+            RegionId::Shared(index) => self.shared[index].bytes.len(),
+            RegionId::Invocation(index) => self.private[index].bytes.len(),
+        }
+    }
+
+    /// Returns the region `id`, with the index and current access for
+    /// tracking if it's shared.
+    fn region(
+        &mut self,
+        id: RegionId,
+    ) -> (&mut Region, Option<(usize, Access)>, &mut Vec<Conflict>) {
+        let (region, tracking) = match id {
+            RegionId::Shared(index) => (
+                &mut self.shared[index],
+                Some((
+                    index,
+                    Access {
+                        epoch: self.epochs[index],
+                        ..self.access
+                    },
+                )),
+            ),
+            RegionId::Invocation(index) => (&mut self.private[index], None),
+        };
+        (region, tracking, self.conflicts)
+    }
+
+    /// Load the value `pointer` points to.
+    pub fn load(&mut self, pointer: Pointer) -> Result<Value, Error> {
+        if !pointer.in_bounds {
+            return match pointer.pointee {
+                Pointee::Type(ty) => Value::zero(self.types, &self.types[ty].inner),
+                Pointee::Vector { size, scalar } => {
+                    Ok(Value::Composite(vec![
+                        Value::zero_scalar(scalar)?;
+                        size as usize
+                    ]))
+                }
+                Pointee::Scalar(scalar) => Value::zero_scalar(scalar),
+            };
+        }
+        let mut scalars = Vec::new();
+        self.visit(pointer, &mut Visit::Load(&mut scalars))?;
+        let mut scalars = scalars.into_iter();
+        self.assemble(pointer.pointee, &mut scalars)
+    }
+
+    /// Store `value` where `pointer` points.
+    pub fn store(&mut self, pointer: Pointer, value: Value) -> Result<(), Error> {
+        if !pointer.in_bounds {
+            return Ok(());
+        }
+        let mut scalars = Vec::new();
+        flatten(value, &mut scalars);
+        self.visit(pointer, &mut Visit::Store(&mut scalars.into_iter()))
+    }
+
+    /// Apply `fun` to the atomic scalar `pointer` points to, storing its
+    /// result and returning the previous value.
+    ///
+    /// If `fun` returns `None`, nothing is stored.
+    pub fn atomic(
+        &mut self,
+        pointer: Pointer,
+        fun: impl FnOnce(&Value) -> Result<Option<Value>, Error>,
+    ) -> Result<Value, Error> {
+        let scalar = match pointer.pointee {
+            Pointee::Type(ty) => match self.types[ty].inner {
+                TypeInner::Atomic(scalar) | TypeInner::Scalar(scalar) => scalar,
+                _ => return Err(Error::InvalidModule("atomic on a non-scalar")),
+            },
+            Pointee::Scalar(scalar) => scalar,
+            Pointee::Vector { .. } => return Err(Error::InvalidModule("atomic on a vector")),
+        };
+        if !pointer.in_bounds {
+            return Value::zero_scalar(scalar);
+        }
+
+        let width = scalar.width as u32;
+        let (region, access, conflicts) = self.region(pointer.region);
+        let old = region.read_scalar(pointer.offset, scalar)?;
+        let new = fun(&old)?;
+        if let Some((index, access)) = access {
+            let access = Access {
+                atomic: true,
+                ..access
+            };
+            region.track(
+                index,
+                pointer.offset,
+                width,
+                access,
+                new.is_some(),
+                conflicts,
+            );
+        }
+        if let Some(new) = new {
+            if !region.writable {
+                return Err(Error::InvalidModule("store to a read-only variable"));
+            }
+            region.write_scalar(pointer.offset, &new)?;
+        }
+        Ok(old)
+    }
+
+    /// Visit the scalars `pointer` points to, in order.
+    fn visit(&mut self, pointer: Pointer, visit: &mut Visit) -> Result<(), Error> {
+        let types = self.types;
+        let (count, scalar) = match pointer.pointee {
+            Pointee::Scalar(scalar) => (None, scalar),
+            Pointee::Vector { size, scalar } => (Some(size as u32), scalar),
+            Pointee::Type(ty) => {
+                let inner = &types[ty].inner;
+                match *inner {
+                    TypeInner::Scalar(scalar) | TypeInner::Atomic(scalar) => (None, scalar),
+                    TypeInner::Vector { size, scalar } => (Some(size as u32), scalar),
+                    _ => {
+                        let count = match *inner {
+                            TypeInner::Matrix { columns, .. } => columns as u32,
+                            TypeInner::Array {
+                                size: crate::ArraySize::Constant(size),
+                                ..
+                            } => size.get(),
+                            TypeInner::Struct { ref members, .. } => members.len() as u32,
+                            _ => return Err(Error::Unsupported("loading or storing this type")),
+                        };
+                        for index in 0..count {
+                            let element = element(types, pointer.pointee, index)?;
+                            let pointer = Pointer {
+                                offset: pointer.offset + element.offset,
+                                pointee: element.pointee,
+                                ..pointer
+                            };
+                            self.visit(pointer, visit)?;
+                        }
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        let width = scalar.width as u32;
+        let (region, access, conflicts) = self.region(pointer.region);
+        for index in 0..count.unwrap_or(1) {
+            let offset = pointer.offset + index * width;
+            match *visit {
+                Visit::Load(ref mut scalars) => {
+                    if let Some((region_index, access)) = access {
+                        region.track(region_index, offset, width, access, false, conflicts);
+                    }
+                    scalars.push(region.read_scalar(offset, scalar)?);
+                }
+                Visit::Store(ref mut scalars) => {
+                    if !region.writable {
+                        return Err(Error::InvalidModule("store to a read-only variable"));
+                    }
+                    if let Some((region_index, access)) = access {
+                        region.track(region_index, offset, width, access, true, conflicts);
+                    }
+                    let value = scalars
+                        .next()
+                        .ok_or(Error::InvalidModule("stored value doesn't match its type"))?;
+                    region.write_scalar(offset, &value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a value of type `pointee` from `scalars`.
+    fn assemble(
+        &self,
+        pointee: Pointee,
+        scalars: &mut impl Iterator<Item = Value>,
+    ) -> Result<Value, Error> {
+        let mut next = || {
+            scalars
+                .next()
+                .ok_or(Error::InvalidModule("loaded value doesn't match its type"))
+        };
+        let count = match pointee {
+            Pointee::Scalar(_) => return next(),
+            Pointee::Vector { size, .. } => {
+                return Ok(Value::Composite(
+                    (0..size as u32).map(|_| next()).collect::<Result<_, _>>()?,
+                ))
+            }
+            Pointee::Type(ty) => match self.types[ty].inner {
+                TypeInner::Scalar(_) | TypeInner::Atomic(_) => return next(),
+                TypeInner::Vector { size, .. } => size as u32,
+                TypeInner::Matrix { columns, .. } => columns as u32,
+                TypeInner::Array {
+                    size: crate::ArraySize::Constant(size),
+                    ..
+                } => size.get(),
+                TypeInner::Struct { ref members, .. } => members.len() as u32,
+                _ => return Err(Error::Unsupported("loading or storing this type")),
+            },
+        };
+        Ok(Value::Composite(
+            (0..count)
+                .map(|index| self.assemble(element(self.types, pointee, index)?.pointee, scalars))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+fn flatten(value: Value, scalars: &mut Vec<Value>) {
+    match value {
+        Value::Composite(components) => {
+            for component in components {
+                flatten(component, scalars);
+            }
+        }
+        scalar => scalars.push(scalar),
+    }
+}
@@ -0,0 +1,509 @@
+/*!
+An interpreter for compute shaders.
+
+[`Interpreter::dispatch`] runs a compute entry point of a validated
+[`Module`] on the CPU, over buffers provided as bytes, much like a dispatch
+would on a GPU. This is meant for testing shaders without a GPU, and as a
+reference to compare the output of the backends against.
+
+Workgroups are run one after the other, and the invocations of a workgroup
+run one at a time until they reach a barrier, where they wait for the rest of
+the workgroup. Atomic operations therefore always observe one particular
+ordering of the invocations.
+
+Besides running the shader, the interpreter reports:
+
+- Out-of-bounds indexing, according to the [`BoundsCheckPolicies`] in the
+  [`Options`]. Under [`Unchecked`], out-of-bounds accesses are an error;
+  under the other policies they behave as the backends implement them, and
+  are listed in the [`Report`].
+
+- Data races: accesses to the same word of a storage or workgroup variable
+  by different invocations, at least one of them a non-atomic write, that
+  aren't ordered by a barrier. Invocations in different workgroups are never
+  ordered.
+
+The interpreter doesn't support images, samplers, push constants or ray
+queries, and fails with [`Error::Unsupported`] when the entry point uses them.
+
+[`Unchecked`]: BoundsCheckPolicy::Unchecked
+*/
+
+mod exec;
+mod memory;
+mod value;
+
+use crate::{
+    proc::{BoundsCheckPolicies, BoundsCheckPolicy},
+    valid::ModuleInfo,
+    AddressSpace, Binding, BuiltIn, Expression, FastHashMap, Function, GlobalVariable, Handle,
+    Module, ResourceBinding, ShaderStage, StorageAccess, TypeInner,
+};
+use exec::{Exec, Status, Thread};
+use memory::{Access, Memory, Pointee, Pointer, Region, RegionId};
+use value::Value;
+
+/// Options for the interpreter.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// How to handle out-of-bounds indexing.
+    pub bounds_check_policies: BoundsCheckPolicies,
+    /// The maximum number of statements to execute over the whole dispatch,
+    /// after which [`Error::StepLimit`] is returned.
+    ///
+    /// This catches shaders that never terminate.
+    pub step_limit: u64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            bounds_check_policies: BoundsCheckPolicies::default(),
+            step_limit: 1 << 24,
+        }
+    }
+}
+
+/// Identifies an invocation in a dispatch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InvocationId {
+    /// The workgroup's `workgroup_id`.
+    pub workgroup: [u32; 3],
+    /// The invocation's `local_invocation_id`.
+    pub local: [u32; 3],
+}
+
+/// An out-of-bounds index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutOfBounds {
+    /// The first invocation that used the index.
+    pub invocation: InvocationId,
+    /// The function containing the [`Access`] or [`AccessIndex`] expression,
+    /// or `None` for the entry point.
+    ///
+    /// [`Access`]: Expression::Access
+    /// [`AccessIndex`]: Expression::AccessIndex
+    pub function: Option<Handle<Function>>,
+    pub expression: Handle<Expression>,
+    pub index: i64,
+    /// The number of elements of the indexed value.
+    pub length: u32,
+    /// The policy applied to the access.
+    pub policy: BoundsCheckPolicy,
+}
+
+/// Conflicting accesses to a storage or workgroup variable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataRace {
+    pub global: Handle<GlobalVariable>,
+    /// The offset in bytes of the 4-byte word accessed.
+    pub offset: u32,
+    /// The invocations that made the accesses, in order.
+    pub invocations: [InvocationId; 2],
+}
+
+/// The problems found while running a dispatch.
+///
+/// Each out-of-bounds expression and each raced word is only reported once.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub out_of_bounds: Vec<OutOfBounds>,
+    pub data_races: Vec<DataRace>,
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum Error {
+    #[error("The module has no compute entry point named {0:?}")]
+    EntryPointNotFound(String),
+    #[error("No buffer was provided for {0:?}")]
+    MissingBuffer(ResourceBinding),
+    #[error("The buffer for {binding:?} is {size} bytes, but at least {required} are needed")]
+    BufferTooSmall {
+        binding: ResourceBinding,
+        size: usize,
+        required: u32,
+    },
+    #[error("Index {} is out of bounds for a length of {}", .0.index, .0.length)]
+    OutOfBounds(OutOfBounds),
+    #[error("Invocations of workgroup {0:?} reached different barriers")]
+    BarrierDivergence([u32; 3]),
+    #[error("The step limit was exceeded")]
+    StepLimit,
+    #[error("Not supported by the interpreter: {0}")]
+    Unsupported(&'static str),
+    #[error("The module is invalid: {0}")]
+    InvalidModule(&'static str),
+}
+
+/// What is shared by all invocations of a dispatch.
+struct Context<'m> {
+    module: &'m Module,
+    info: &'m ModuleInfo,
+    options: &'m Options,
+    /// The values of the module's constant expressions.
+    constants: Vec<Result<Value, Error>>,
+    /// Pointers to the global variables, where supported.
+    globals: Vec<Option<Pointer>>,
+}
+
+impl Context<'_> {
+    fn constant(&self, constant: Handle<crate::Constant>) -> Result<Value, Error> {
+        let init = self.module.constants[constant].init;
+        self.constants[init.index()].clone()
+    }
+}
+
+/// Build a value of type `ty` from `components`, as in a [`Compose`]
+/// expression.
+///
+/// [`Compose`]: Expression::Compose
+fn compose(ty: &TypeInner, components: Vec<Value>) -> Value {
+    match *ty {
+        // Vectors can be composed from smaller vectors.
+        TypeInner::Vector { .. } => Value::Composite(
+            components
+                .into_iter()
+                .flat_map(|component| match component {
+                    Value::Composite(components) => components,
+                    scalar => vec![scalar],
+                })
+                .collect(),
+        ),
+        _ => Value::Composite(components),
+    }
+}
+
+/// Evaluate the constant expressions of `module`.
+fn evaluate_constants(module: &Module) -> Vec<Result<Value, Error>> {
+    let mut values: Vec<Result<Value, Error>> = Vec::with_capacity(module.const_expressions.len());
+    for (_, expression) in module.const_expressions.iter() {
+        let get = |handle: Handle<Expression>| values[handle.index()].clone();
+        let value = match *expression {
+            Expression::Literal(literal) => Ok(literal.into()),
+            Expression::Constant(constant) => get(module.constants[constant].init),
+            Expression::ZeroValue(ty) => Value::zero(&module.types, &module.types[ty].inner),
+            Expression::Compose { ty, ref components } => components
+                .iter()
+                .map(|&component| get(component))
+                .collect::<Result<_, _>>()
+                .map(|components| compose(&module.types[ty].inner, components)),
+            Expression::Splat { size, value } => {
+                get(value).map(|value| Value::Composite(vec![value; size as usize]))
+            }
+            _ => Err(Error::Unsupported("constant expression")),
+        };
+        values.push(value);
+    }
+    values
+}
+
+/// Runs the compute entry points of a module.
+pub struct Interpreter<'m> {
+    module: &'m Module,
+    info: &'m ModuleInfo,
+    options: Options,
+}
+
+impl<'m> Interpreter<'m> {
+    /// Create an interpreter for `module`, which must have been validated,
+    /// producing `info`.
+    pub const fn new(module: &'m Module, info: &'m ModuleInfo, options: Options) -> Self {
+        Interpreter {
+            module,
+            info,
+            options,
+        }
+    }
+
+    /// Run the compute entry point named `entry_point` over
+    /// `workgroup_count` workgroups.
+    ///
+    /// `buffers` holds the contents of the storage and uniform buffers used
+    /// by the entry point. Writable storage buffers are updated only if the
+    /// dispatch succeeds.
+    pub fn dispatch(
+        &self,
+        entry_point: &str,
+        workgroup_count: [u32; 3],
+        buffers: &mut FastHashMap<ResourceBinding, Vec<u8>>,
+    ) -> Result<Report, Error> {
+        let module = self.module;
+        let (index, ep) = module
+            .entry_points
+            .iter()
+            .enumerate()
+            .find(|&(_, ep)| ep.stage == ShaderStage::Compute && ep.name == entry_point)
+            .ok_or_else(|| Error::EntryPointNotFound(entry_point.to_string()))?;
+        let ep_info = self.info.get_entry_point(index);
+
+        let mut shared = Vec::new();
+        // The globals of the shared regions, and the bindings of the
+        // writable buffers among them.
+        let mut shared_globals = Vec::new();
+        let mut outputs = Vec::new();
+        let mut workgroup_regions = Vec::new();
+        let mut private = Vec::new();
+        let mut globals = Vec::new();
+        for (handle, var) in module.global_variables.iter() {
+            let size = module.types[var.ty].inner.size(module.to_ctx());
+            let region = match var.space {
+                _ if ep_info[handle].is_empty() => None,
+                AddressSpace::Storage { .. } | AddressSpace::Uniform => {
+                    let binding = var
+                        .binding
+                        .clone()
+                        .ok_or(Error::InvalidModule("buffer without a binding"))?;
+                    let buffer = buffers
+                        .get(&binding)
+                        .ok_or_else(|| Error::MissingBuffer(binding.clone()))?;
+                    if buffer.len() < size as usize {
+                        return Err(Error::BufferTooSmall {
+                            binding,
+                            size: buffer.len(),
+                            required: size,
+                        });
+                    }
+                    let writable = match var.space {
+                        AddressSpace::Storage { access } => access.contains(StorageAccess::STORE),
+                        _ => false,
+                    };
+                    if writable {
+                        outputs.push((shared.len(), binding));
+                    }
+                    shared.push(Region::new(buffer.clone(), writable, true));
+                    shared_globals.push(handle);
+                    Some(RegionId::Shared(shared.len() - 1))
+                }
+                AddressSpace::WorkGroup => {
+                    workgroup_regions.push(shared.len());
+                    shared.push(Region::new(vec![0; size as usize], true, true));
+                    shared_globals.push(handle);
+                    Some(RegionId::Shared(shared.len() - 1))
+                }
+                AddressSpace::Private => {
+                    private.push(Region::new(vec![0; size as usize], true, false));
+                    Some(RegionId::Invocation(private.len() - 1))
+                }
+                AddressSpace::Function
+                | AddressSpace::Handle
+                | AddressSpace::PushConstant
+                | AddressSpace::TaskPayload => None,
+            };
+            globals.push(region.map(|region| Pointer {
+                region,
+                offset: 0,
+                pointee: Pointee::Type(var.ty),
+                in_bounds: true,
+            }));
+        }
+
+        let ctx = Context {
+            module,
+            info: self.info,
+            options: &self.options,
+            constants: evaluate_constants(module),
+            globals,
+        };
+        let mut epochs = vec![0; shared.len()];
+        let mut conflicts = Vec::new();
+        let mut report = Report::default();
+        let mut steps = 0;
+
+        // Initialize the private variables once, and copy them into each
+        // invocation.
+        for (handle, var) in module.global_variables.iter() {
+            if let (Some(pointer), Some(init)) = (ctx.globals[handle.index()], var.init) {
+                if let RegionId::Invocation(_) = pointer.region {
+                    let mut memory = Memory {
+                        types: &module.types,
+                        shared: &mut [],
+                        private: &mut private,
+                        access: Access::default(),
+                        epochs: &[],
+                        conflicts: &mut conflicts,
+                    };
+                    memory.store(pointer, ctx.constants[init.index()].clone()?)?;
+                }
+            }
+        }
+
+        let [size_x, size_y, size_z] = ep.workgroup_size;
+        let mut workgroup_index = 0;
+        for z in 0..workgroup_count[2] {
+            for y in 0..workgroup_count[1] {
+                for x in 0..workgroup_count[0] {
+                    let workgroup = [x, y, z];
+                    for &region in workgroup_regions.iter() {
+                        shared[region].reset();
+                        epochs[region] = 0;
+                    }
+
+                    let mut invocations = Vec::new();
+                    for z in 0..size_z {
+                        for y in 0..size_y {
+                            for x in 0..size_x {
+                                let id = InvocationId {
+                                    workgroup,
+                                    local: [x, y, z],
+                                };
+                                let arguments = ep
+                                    .function
+                                    .arguments
+                                    .iter()
+                                    .map(|argument| {
+                                        self.builtin_argument(
+                                            argument,
+                                            id,
+                                            ep.workgroup_size,
+                                            workgroup_count,
+                                        )
+                                    })
+                                    .collect::<Result<_, _>>()?;
+                                let mut thread = Thread::new(id);
+                                let mut regions = private.clone();
+                                Exec {
+                                    ctx: &ctx,
+                                    thread: &mut thread,
+                                    memory: Memory {
+                                        types: &module.types,
+                                        shared: &mut shared,
+                                        private: &mut regions,
+                                        access: Access::default(),
+                                        epochs: &epochs,
+                                        conflicts: &mut conflicts,
+                                    },
+                                    out_of_bounds: &mut report.out_of_bounds,
+                                    steps: &mut steps,
+                                }
+                                .call(
+                                    &ep.function,
+                                    ep_info,
+                                    None,
+                                    arguments,
+                                    None,
+                                )?;
+                                invocations.push((thread, regions));
+                            }
+                        }
+                    }
+
+                    loop {
+                        let mut statuses = Vec::with_capacity(invocations.len());
+                        for (local_index, &mut (ref mut thread, ref mut regions)) in
+                            invocations.iter_mut().enumerate()
+                        {
+                            let status = Exec {
+                                ctx: &ctx,
+                                thread,
+                                memory: Memory {
+                                    types: &module.types,
+                                    shared: &mut shared,
+                                    private: regions,
+                                    access: Access {
+                                        workgroup: workgroup_index,
+                                        invocation: local_index as u32,
+                                        epoch: 0,
+                                        atomic: false,
+                                    },
+                                    epochs: &epochs,
+                                    conflicts: &mut conflicts,
+                                },
+                                out_of_bounds: &mut report.out_of_bounds,
+                                steps: &mut steps,
+                            }
+                            .run()?;
+                            statuses.push(status);
+                        }
+
+                        let Some(&first) = statuses.first() else {
+                            break;
+                        };
+                        if statuses.iter().any(|&status| status != first) {
+                            return Err(Error::BarrierDivergence(workgroup));
+                        }
+                        let Status::Barrier { flags, .. } = first else {
+                            break;
+                        };
+                        for (region, epoch) in epochs.iter_mut().enumerate() {
+                            let space = module.global_variables[shared_globals[region]].space;
+                            let flag = match space {
+                                AddressSpace::WorkGroup => crate::Barrier::WORK_GROUP,
+                                _ => crate::Barrier::STORAGE,
+                            };
+                            if flags.contains(flag) {
+                                *epoch += 1;
+                            }
+                        }
+                    }
+
+                    workgroup_index += 1;
+                }
+            }
+        }
+
+        let invocation = |access: Access| {
+            let [size_x, size_y, _] = ep.workgroup_size;
+            let [count_x, count_y, _] = workgroup_count;
+            let unflatten = |index: u32, x: u32, y: u32| [index % x, index / x % y, index / x / y];
+            InvocationId {
+                workgroup: unflatten(access.workgroup, count_x, count_y),
+                local: unflatten(access.invocation, size_x, size_y),
+            }
+        };
+        report.data_races = conflicts
+            .iter()
+            .map(|conflict| DataRace {
+                global: shared_globals[conflict.region],
+                offset: conflict.offset,
+                invocations: [invocation(conflict.first), invocation(conflict.second)],
+            })
+            .collect();
+
+        for (region, binding) in outputs {
+            buffers.insert(binding, std::mem::take(&mut shared[region].bytes));
+        }
+        Ok(report)
+    }
+
+    /// Returns the value of an entry point argument, which must be a compute
+    /// built-in or a struct of them.
+    fn builtin_argument(
+        &self,
+        argument: &crate::FunctionArgument,
+        id: InvocationId,
+        workgroup_size: [u32; 3],
+        workgroup_count: [u32; 3],
+    ) -> Result<Value, Error> {
+        let builtin = |binding: Option<&Binding>| -> Result<Value, Error> {
+            let vector = |v: [u32; 3]| Value::Composite(v.iter().map(|&v| Value::U32(v)).collect());
+            Ok(match binding {
+                Some(&Binding::BuiltIn(BuiltIn::GlobalInvocationId)) => vector([
+                    id.workgroup[0] * workgroup_size[0] + id.local[0],
+                    id.workgroup[1] * workgroup_size[1] + id.local[1],
+                    id.workgroup[2] * workgroup_size[2] + id.local[2],
+                ]),
+                Some(&Binding::BuiltIn(BuiltIn::LocalInvocationId)) => vector(id.local),
+                Some(&Binding::BuiltIn(BuiltIn::LocalInvocationIndex)) => Value::U32(
+                    (id.local[2] * workgroup_size[1] + id.local[1]) * workgroup_size[0]
+                        + id.local[0],
+                ),
+                Some(&Binding::BuiltIn(BuiltIn::WorkGroupId)) => vector(id.workgroup),
+                Some(&Binding::BuiltIn(BuiltIn::WorkGroupSize)) => vector(workgroup_size),
+                Some(&Binding::BuiltIn(BuiltIn::NumWorkGroups)) => vector(workgroup_count),
+                _ => return Err(Error::Unsupported("entry point argument")),
+            })
+        };
+
+        match self.module.types[argument.ty].inner {
+            TypeInner::Struct { ref members, .. } if argument.binding.is_none() => {
+                Ok(Value::Composite(
+                    members
+                        .iter()
+                        .map(|member| builtin(member.binding.as_ref()))
+                        .collect::<Result<_, _>>()?,
+                ))
+            }
+            _ => builtin(argument.binding.as_ref()),
+        }
+    }
+}
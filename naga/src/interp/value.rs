@@ -0,0 +1,1005 @@
+/*!
+Values computed by the interpreter, and the operations on them.
+
+Composite values are represented uniformly as lists of their components: a
+matrix is a list of column vectors, and a struct a list of its members'
+values. Operations that need to tell these apart take the Naga IR type of
+their operands.
+*/
+
+use super::{memory::Pointer, Error};
+use crate::{
+    BinaryOperator, Literal, MathFunction, RelationalFunction, Scalar, ScalarKind, Type, TypeInner,
+    UnaryOperator, UniqueArena,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    /// A vector, matrix, array or struct.
+    Composite(Vec<Value>),
+    Pointer(Pointer),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+impl From<Literal> for Value {
+    fn from(literal: Literal) -> Self {
+        match literal {
+            Literal::F64(v) | Literal::AbstractFloat(v) => Value::F64(v),
+            Literal::F32(v) => Value::F32(v),
+            Literal::U32(v) => Value::U32(v),
+            Literal::I32(v) => Value::I32(v),
+            Literal::I64(v) | Literal::AbstractInt(v) => Value::I64(v),
+            Literal::Bool(v) => Value::Bool(v),
+        }
+    }
+}
+
+impl Value {
+    /// Returns the zero value of `scalar`.
+    pub const fn zero_scalar(scalar: Scalar) -> Result<Self> {
+        Ok(match (scalar.kind, scalar.width) {
+            (ScalarKind::Bool, _) => Value::Bool(false),
+            (ScalarKind::Sint, 4) => Value::I32(0),
+            (ScalarKind::Uint, 4) => Value::U32(0),
+            (ScalarKind::Sint | ScalarKind::AbstractInt, 8) => Value::I64(0),
+            (ScalarKind::Float, 4) => Value::F32(0.0),
+            (ScalarKind::Float | ScalarKind::AbstractFloat, 8) => Value::F64(0.0),
+            _ => return Err(Error::Unsupported("scalar type")),
+        })
+    }
+
+    /// Returns the zero value of a type.
+    pub fn zero(types: &UniqueArena<Type>, inner: &TypeInner) -> Result<Self> {
+        Ok(match *inner {
+            TypeInner::Scalar(scalar) | TypeInner::Atomic(scalar) => Self::zero_scalar(scalar)?,
+            TypeInner::Vector { size, scalar } => {
+                Value::Composite(vec![Self::zero_scalar(scalar)?; size as usize])
+            }
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => {
+                let column = Value::Composite(vec![Self::zero_scalar(scalar)?; rows as usize]);
+                Value::Composite(vec![column; columns as usize])
+            }
+            TypeInner::Array {
+                base,
+                size: crate::ArraySize::Constant(size),
+                ..
+            } => Value::Composite(vec![
+                Self::zero(types, &types[base].inner)?;
+                size.get() as usize
+            ]),
+            TypeInner::Struct { ref members, .. } => Value::Composite(
+                members
+                    .iter()
+                    .map(|member| Self::zero(types, &types[member.ty].inner))
+                    .collect::<Result<_>>()?,
+            ),
+            _ => return Err(Error::Unsupported("zero value of this type")),
+        })
+    }
+
+    pub const fn as_bool(&self) -> Result<bool> {
+        match *self {
+            Value::Bool(v) => Ok(v),
+            _ => Err(Error::InvalidModule("expected a boolean")),
+        }
+    }
+
+    pub const fn as_u32(&self) -> Result<u32> {
+        match *self {
+            Value::U32(v) => Ok(v),
+            Value::I32(v) => Ok(v as u32),
+            _ => Err(Error::InvalidModule("expected an integer")),
+        }
+    }
+
+    /// Returns the value of an integer used as an index.
+    pub const fn as_index(&self) -> Result<i64> {
+        match *self {
+            Value::I32(v) => Ok(v as i64),
+            Value::U32(v) => Ok(v as i64),
+            Value::I64(v) => Ok(v),
+            _ => Err(Error::InvalidModule("expected an index")),
+        }
+    }
+
+    pub const fn as_pointer(&self) -> Result<Pointer> {
+        match *self {
+            Value::Pointer(pointer) => Ok(pointer),
+            _ => Err(Error::InvalidModule("expected a pointer")),
+        }
+    }
+
+    pub fn components(&self) -> Result<&[Value]> {
+        match *self {
+            Value::Composite(ref components) => Ok(components),
+            _ => Err(Error::InvalidModule("expected a composite")),
+        }
+    }
+
+    fn into_components(self) -> Result<Vec<Value>> {
+        match self {
+            Value::Composite(components) => Ok(components),
+            _ => Err(Error::InvalidModule("expected a composite")),
+        }
+    }
+
+    const fn as_f64(&self) -> Result<f64> {
+        match *self {
+            Value::F32(v) => Ok(v as f64),
+            Value::F64(v) => Ok(v),
+            _ => Err(Error::InvalidModule("expected a float")),
+        }
+    }
+
+    /// Returns a float of the same width as `self`.
+    const fn float_like(&self, value: f64) -> Value {
+        match *self {
+            Value::F32(_) => Value::F32(value as f32),
+            _ => Value::F64(value),
+        }
+    }
+
+    /// Applies `f` to each scalar component of `self`.
+    pub fn map<F>(self, f: &mut F) -> Result<Value>
+    where
+        F: FnMut(Value) -> Result<Value>,
+    {
+        match self {
+            Value::Composite(components) => Ok(Value::Composite(
+                components
+                    .into_iter()
+                    .map(|component| component.map(f))
+                    .collect::<Result<_>>()?,
+            )),
+            scalar => f(scalar),
+        }
+    }
+
+    /// Applies `f` to pairs of scalar components of `self` and `other`.
+    ///
+    /// A scalar operand is paired with every component of the other.
+    pub fn zip<F>(self, other: Value, f: &mut F) -> Result<Value>
+    where
+        F: FnMut(Value, Value) -> Result<Value>,
+    {
+        match (self, other) {
+            (Value::Composite(left), Value::Composite(right)) => {
+                if left.len() != right.len() {
+                    return Err(Error::InvalidModule("mismatched composite sizes"));
+                }
+                Ok(Value::Composite(
+                    left.into_iter()
+                        .zip(right)
+                        .map(|(left, right)| left.zip(right, f))
+                        .collect::<Result<_>>()?,
+                ))
+            }
+            (Value::Composite(left), right) => Ok(Value::Composite(
+                left.into_iter()
+                    .map(|left| left.zip(right.clone(), f))
+                    .collect::<Result<_>>()?,
+            )),
+            (left, Value::Composite(right)) => Ok(Value::Composite(
+                right
+                    .into_iter()
+                    .map(|right| left.clone().zip(right, f))
+                    .collect::<Result<_>>()?,
+            )),
+            (left, right) => f(left, right),
+        }
+    }
+
+    /// Applies `f` to triples of scalar components, like [`zip`](Value::zip).
+    fn zip3<F>(self, second: Value, third: Value, f: &mut F) -> Result<Value>
+    where
+        F: FnMut(Value, Value, Value) -> Result<Value>,
+    {
+        let len = [&self, &second, &third]
+            .iter()
+            .find_map(|value| value.components().ok().map(<[_]>::len));
+        let Some(len) = len else {
+            return f(self, second, third);
+        };
+        let split = |value: Value| match value {
+            Value::Composite(components) => components,
+            scalar => vec![scalar; len],
+        };
+        Ok(Value::Composite(
+            split(self)
+                .into_iter()
+                .zip(split(second))
+                .zip(split(third))
+                .map(|((first, second), third)| first.zip3(second, third, f))
+                .collect::<Result<_>>()?,
+        ))
+    }
+}
+
+pub fn unary(op: UnaryOperator, value: Value) -> Result<Value> {
+    value.map(&mut |value| {
+        Ok(match (op, value) {
+            (UnaryOperator::Negate, Value::I32(v)) => Value::I32(v.wrapping_neg()),
+            (UnaryOperator::Negate, Value::I64(v)) => Value::I64(v.wrapping_neg()),
+            (UnaryOperator::Negate, Value::F32(v)) => Value::F32(-v),
+            (UnaryOperator::Negate, Value::F64(v)) => Value::F64(-v),
+            (UnaryOperator::LogicalNot, Value::Bool(v)) => Value::Bool(!v),
+            (UnaryOperator::BitwiseNot, Value::I32(v)) => Value::I32(!v),
+            (UnaryOperator::BitwiseNot, Value::U32(v)) => Value::U32(!v),
+            (UnaryOperator::BitwiseNot, Value::I64(v)) => Value::I64(!v),
+            (UnaryOperator::BitwiseNot, Value::Bool(v)) => Value::Bool(!v),
+            _ => return Err(Error::InvalidModule("invalid unary operand")),
+        })
+    })
+}
+
+macro_rules! int_binary {
+    ($op:expr, $left:expr, $right:expr, $variant:path, $overflow:expr) => {{
+        let (left, right) = ($left, $right);
+        match $op {
+            BinaryOperator::Add => $variant(left.wrapping_add(right)),
+            BinaryOperator::Subtract => $variant(left.wrapping_sub(right)),
+            BinaryOperator::Multiply => $variant(left.wrapping_mul(right)),
+            // Division by zero and overflowing division yield the dividend.
+            BinaryOperator::Divide if right == 0 || $overflow => $variant(left),
+            BinaryOperator::Divide => $variant(left / right),
+            BinaryOperator::Modulo if right == 0 || $overflow => $variant(0),
+            BinaryOperator::Modulo => $variant(left % right),
+            BinaryOperator::Equal => Value::Bool(left == right),
+            BinaryOperator::NotEqual => Value::Bool(left != right),
+            BinaryOperator::Less => Value::Bool(left < right),
+            BinaryOperator::LessEqual => Value::Bool(left <= right),
+            BinaryOperator::Greater => Value::Bool(left > right),
+            BinaryOperator::GreaterEqual => Value::Bool(left >= right),
+            BinaryOperator::And => $variant(left & right),
+            BinaryOperator::ExclusiveOr => $variant(left ^ right),
+            BinaryOperator::InclusiveOr => $variant(left | right),
+            _ => return Err(Error::InvalidModule("invalid integer operation")),
+        }
+    }};
+}
+
+macro_rules! float_binary {
+    ($op:expr, $left:expr, $right:expr, $variant:path) => {{
+        let (left, right) = ($left, $right);
+        match $op {
+            BinaryOperator::Add => $variant(left + right),
+            BinaryOperator::Subtract => $variant(left - right),
+            BinaryOperator::Multiply => $variant(left * right),
+            BinaryOperator::Divide => $variant(left / right),
+            BinaryOperator::Modulo => $variant(left % right),
+            BinaryOperator::Equal => Value::Bool(left == right),
+            BinaryOperator::NotEqual => Value::Bool(left != right),
+            BinaryOperator::Less => Value::Bool(left < right),
+            BinaryOperator::LessEqual => Value::Bool(left <= right),
+            BinaryOperator::Greater => Value::Bool(left > right),
+            BinaryOperator::GreaterEqual => Value::Bool(left >= right),
+            _ => return Err(Error::InvalidModule("invalid float operation")),
+        }
+    }};
+}
+
+/// Apply `op` to scalars.
+pub fn scalar_binary(op: BinaryOperator, left: Value, right: Value) -> Result<Value> {
+    use BinaryOperator as Bo;
+
+    Ok(match (left, right) {
+        // Shifts only use the low bits of the shift amount.
+        (Value::I32(left), Value::U32(right)) if op == Bo::ShiftLeft => {
+            Value::I32(left.wrapping_shl(right))
+        }
+        (Value::I32(left), Value::U32(right)) if op == Bo::ShiftRight => {
+            Value::I32(left.wrapping_shr(right))
+        }
+        (Value::U32(left), Value::U32(right)) if op == Bo::ShiftLeft => {
+            Value::U32(left.wrapping_shl(right))
+        }
+        (Value::U32(left), Value::U32(right)) if op == Bo::ShiftRight => {
+            Value::U32(left.wrapping_shr(right))
+        }
+        (Value::I64(left), Value::U32(right)) if op == Bo::ShiftLeft => {
+            Value::I64(left.wrapping_shl(right))
+        }
+        (Value::I64(left), Value::U32(right)) if op == Bo::ShiftRight => {
+            Value::I64(left.wrapping_shr(right))
+        }
+        (Value::I32(left), Value::I32(right)) => {
+            int_binary!(op, left, right, Value::I32, left == i32::MIN && right == -1)
+        }
+        (Value::U32(left), Value::U32(right)) => int_binary!(op, left, right, Value::U32, false),
+        (Value::I64(left), Value::I64(right)) => {
+            int_binary!(op, left, right, Value::I64, left == i64::MIN && right == -1)
+        }
+        (Value::F32(left), Value::F32(right)) => float_binary!(op, left, right, Value::F32),
+        (Value::F64(left), Value::F64(right)) => float_binary!(op, left, right, Value::F64),
+        (Value::Bool(left), Value::Bool(right)) => Value::Bool(match op {
+            Bo::Equal => left == right,
+            Bo::NotEqual => left != right,
+            Bo::And | Bo::LogicalAnd => left & right,
+            Bo::InclusiveOr | Bo::LogicalOr => left | right,
+            Bo::ExclusiveOr => left ^ right,
+            _ => return Err(Error::InvalidModule("invalid boolean operation")),
+        }),
+        _ => return Err(Error::InvalidModule("mismatched binary operands")),
+    })
+}
+
+fn add(left: Value, right: Value) -> Result<Value> {
+    left.zip(right, &mut |left, right| {
+        scalar_binary(BinaryOperator::Add, left, right)
+    })
+}
+
+fn multiply(left: Value, right: Value) -> Result<Value> {
+    left.zip(right, &mut |left, right| {
+        scalar_binary(BinaryOperator::Multiply, left, right)
+    })
+}
+
+fn subtract(left: Value, right: Value) -> Result<Value> {
+    left.zip(right, &mut |left, right| {
+        scalar_binary(BinaryOperator::Subtract, left, right)
+    })
+}
+
+fn sum(values: Vec<Value>) -> Result<Value> {
+    let mut values = values.into_iter();
+    let first = values
+        .next()
+        .ok_or(Error::InvalidModule("empty composite"))?;
+    values.try_fold(first, add)
+}
+
+fn dot(left: Value, right: Value) -> Result<Value> {
+    sum(multiply(left, right)?.into_components()?)
+}
+
+/// Multiply a matrix by a column vector.
+fn matrix_times_vector(matrix: Value, vector: Value) -> Result<Value> {
+    let columns = matrix
+        .into_components()?
+        .into_iter()
+        .zip(vector.into_components()?)
+        .map(|(column, scale)| multiply(column, scale))
+        .collect::<Result<Vec<_>>>()?;
+    sum(columns)
+}
+
+pub fn binary(
+    op: BinaryOperator,
+    left: Value,
+    right: Value,
+    left_ty: &TypeInner,
+    right_ty: &TypeInner,
+) -> Result<Value> {
+    if op == BinaryOperator::Multiply {
+        match (left_ty, right_ty) {
+            (&TypeInner::Matrix { .. }, &TypeInner::Vector { .. }) => {
+                return matrix_times_vector(left, right);
+            }
+            (&TypeInner::Vector { .. }, &TypeInner::Matrix { .. }) => {
+                return Ok(Value::Composite(
+                    right
+                        .into_components()?
+                        .into_iter()
+                        .map(|column| dot(left.clone(), column))
+                        .collect::<Result<_>>()?,
+                ));
+            }
+            (&TypeInner::Matrix { .. }, &TypeInner::Matrix { .. }) => {
+                return Ok(Value::Composite(
+                    right
+                        .into_components()?
+                        .into_iter()
+                        .map(|column| matrix_times_vector(left.clone(), column))
+                        .collect::<Result<_>>()?,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    left.zip(right, &mut |left, right| scalar_binary(op, left, right))
+}
+
+pub fn select(condition: Value, accept: Value, reject: Value) -> Result<Value> {
+    match condition {
+        Value::Bool(true) => Ok(accept),
+        Value::Bool(false) => Ok(reject),
+        condition => condition.zip3(accept, reject, &mut |condition, accept, reject| {
+            Ok(if condition.as_bool()? { accept } else { reject })
+        }),
+    }
+}
+
+pub fn relational(fun: RelationalFunction, argument: Value) -> Result<Value> {
+    match fun {
+        RelationalFunction::All | RelationalFunction::Any => {
+            let components = match argument {
+                Value::Composite(components) => components,
+                scalar => vec![scalar],
+            };
+            let mut values = components.iter().map(Value::as_bool);
+            Ok(Value::Bool(if fun == RelationalFunction::All {
+                values.try_fold(true, |all, value| value.map(|value| all && value))?
+            } else {
+                values.try_fold(false, |any, value| value.map(|value| any || value))?
+            }))
+        }
+        RelationalFunction::IsNan | RelationalFunction::IsInf => argument.map(&mut |value| {
+            let value = value.as_f64()?;
+            Ok(Value::Bool(if fun == RelationalFunction::IsNan {
+                value.is_nan()
+            } else {
+                value.is_infinite()
+            }))
+        }),
+    }
+}
+
+/// Convert or bitcast `value` to `kind`.
+///
+/// If `convert` is `None`, reinterpret the bits of `value`.
+pub fn cast(value: Value, kind: ScalarKind, convert: Option<crate::Bytes>) -> Result<Value> {
+    value.map(&mut |value| {
+        Ok(match (convert, kind, value) {
+            (None, ScalarKind::Sint, Value::U32(v)) => Value::I32(v as i32),
+            (None, ScalarKind::Sint, Value::F32(v)) => Value::I32(v.to_bits() as i32),
+            (None, ScalarKind::Uint, Value::I32(v)) => Value::U32(v as u32),
+            (None, ScalarKind::Uint, Value::F32(v)) => Value::U32(v.to_bits()),
+            (None, ScalarKind::Float, Value::I32(v)) => Value::F32(f32::from_bits(v as u32)),
+            (None, ScalarKind::Float, Value::U32(v)) => Value::F32(f32::from_bits(v)),
+            (None, ScalarKind::Sint, Value::F64(v)) => Value::I64(v.to_bits() as i64),
+            (None, ScalarKind::Float, Value::I64(v)) => Value::F64(f64::from_bits(v as u64)),
+            (None, _, value) => value,
+            (Some(_), ScalarKind::Bool, value) => Value::Bool(match value {
+                Value::Bool(v) => v,
+                Value::I32(v) => v != 0,
+                Value::U32(v) => v != 0,
+                Value::I64(v) => v != 0,
+                Value::F32(v) => v != 0.0,
+                Value::F64(v) => v != 0.0,
+                _ => return Err(Error::InvalidModule("invalid conversion")),
+            }),
+            (Some(width), kind, value) => {
+                // Go through the widest type of the source's kind. Float to
+                // integer conversions saturate.
+                let (int, float) = match value {
+                    Value::Bool(v) => (v as i64, v as u8 as f64),
+                    Value::I32(v) => (v as i64, v as f64),
+                    Value::U32(v) => (v as i64, v as f64),
+                    Value::I64(v) => (v, v as f64),
+                    Value::F32(v) => (v as i64, v as f64),
+                    Value::F64(v) => (v as i64, v),
+                    _ => return Err(Error::InvalidModule("invalid conversion")),
+                };
+                let float_source = matches!(value, Value::F32(_) | Value::F64(_));
+                match (kind, width) {
+                    (ScalarKind::Sint, 4) if float_source => Value::I32(float as i32),
+                    (ScalarKind::Sint, 4) => Value::I32(int as i32),
+                    (ScalarKind::Uint, 4) if float_source => Value::U32(float as u32),
+                    (ScalarKind::Uint, 4) => Value::U32(int as u32),
+                    (ScalarKind::Sint, 8) => Value::I64(int),
+                    (ScalarKind::Float, 4) => Value::F32(match value {
+                        Value::F32(v) => v,
+                        _ => float as f32,
+                    }),
+                    (ScalarKind::Float, 8) => Value::F64(float),
+                    _ => return Err(Error::Unsupported("conversion")),
+                }
+            }
+        })
+    })
+}
+
+/// Apply a float function to each component of `value`.
+fn float_map(value: Value, f: impl Fn(f64) -> f64) -> Result<Value> {
+    value.map(&mut |value| Ok(value.float_like(f(value.as_f64()?))))
+}
+
+/// Apply a float function to pairs of components of `left` and `right`.
+fn float_zip(left: Value, right: Value, f: impl Fn(f64, f64) -> f64) -> Result<Value> {
+    left.zip(right, &mut |left, right| {
+        Ok(left.float_like(f(left.as_f64()?, right.as_f64()?)))
+    })
+}
+
+/// Apply an integer function to each component of `value`.
+fn int_map(
+    value: Value,
+    i32_fn: impl Fn(i32) -> i32,
+    u32_fn: impl Fn(u32) -> u32,
+) -> Result<Value> {
+    value.map(&mut |value| match value {
+        Value::I32(v) => Ok(Value::I32(i32_fn(v))),
+        Value::U32(v) => Ok(Value::U32(u32_fn(v))),
+        _ => Err(Error::InvalidModule("expected a 32-bit integer")),
+    })
+}
+
+/// Round to the nearest integer, with ties to even.
+fn round_ties_even(x: f64) -> f64 {
+    let rounded = x.round();
+    if (x - x.trunc()).abs() == 0.5 {
+        2.0 * (x / 2.0).round()
+    } else {
+        rounded
+    }
+}
+
+fn length(value: Value) -> Result<Value> {
+    match value {
+        Value::Composite(_) => float_map(dot(value.clone(), value)?, f64::sqrt),
+        scalar => float_map(scalar, f64::abs),
+    }
+}
+
+fn min_max(left: Value, right: Value, max: bool) -> Result<Value> {
+    left.zip(right, &mut |left, right| {
+        Ok(match (left, right) {
+            (Value::I32(l), Value::I32(r)) => Value::I32(if max { l.max(r) } else { l.min(r) }),
+            (Value::U32(l), Value::U32(r)) => Value::U32(if max { l.max(r) } else { l.min(r) }),
+            (Value::I64(l), Value::I64(r)) => Value::I64(if max { l.max(r) } else { l.min(r) }),
+            (left, right) => {
+                let (l, r) = (left.as_f64()?, right.as_f64()?);
+                left.float_like(if max { l.max(r) } else { l.min(r) })
+            }
+        })
+    })
+}
+
+/// Returns the bits of `count` bits at `offset`, clamped as in WGSL.
+fn bit_range(offset: u32, count: u32) -> (u32, u32) {
+    let offset = offset.min(32);
+    (offset, count.min(32 - offset))
+}
+
+const fn mask(count: u32) -> u32 {
+    if count == 32 {
+        !0
+    } else {
+        (1 << count) - 1
+    }
+}
+
+fn determinant(columns: &[Vec<f64>]) -> f64 {
+    match columns.len() {
+        1 => columns[0][0],
+        2 => columns[0][0] * columns[1][1] - columns[1][0] * columns[0][1],
+        n => (0..n)
+            .map(|i| {
+                let minor = columns
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, column)| column[1..].to_vec())
+                    .collect::<Vec<_>>();
+                let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                sign * columns[i][0] * determinant(&minor)
+            })
+            .sum(),
+    }
+}
+
+/// Convert `value` to the bits of the nearest half-precision float.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    // Round the discarded bits to nearest, ties to even.
+    let round = |kept: u32, rest: u32, halfway: u32| {
+        if rest > halfway || (rest == halfway && kept & 1 == 1) {
+            kept + 1
+        } else {
+            kept
+        }
+    };
+    if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - exponent) as u32;
+        let kept = mantissa >> shift;
+        let rest = mantissa & ((1 << shift) - 1);
+        return sign | round(kept, rest, 1 << (shift - 1)) as u16;
+    }
+    let kept = ((exponent as u32) << 10) | (mantissa >> 13);
+    sign | round(kept, mantissa & 0x1fff, 0x1000) as u16
+}
+
+/// Convert the bits of a half-precision float to a float.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let negative = bits & 0x8000 != 0;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+    let sign = if negative { 0x8000_0000 } else { 0 };
+    match exponent {
+        0 => {
+            let value = mantissa as f32 * 2f32.powi(-24);
+            if negative {
+                -value
+            } else {
+                value
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (mantissa << 13)),
+    }
+}
+
+fn floats(value: &Value) -> Result<Vec<f64>> {
+    value.components()?.iter().map(Value::as_f64).collect()
+}
+
+fn f32_vector(values: impl IntoIterator<Item = f32>) -> Value {
+    Value::Composite(values.into_iter().map(Value::F32).collect())
+}
+
+fn int_components(value: &Value) -> Result<Vec<i64>> {
+    value.components()?.iter().map(Value::as_index).collect()
+}
+
+pub fn math(fun: MathFunction, mut args: Vec<Value>) -> Result<Value> {
+    use MathFunction as Mf;
+
+    let mut arg = |index: usize| -> Result<Value> {
+        args.get_mut(index)
+            .map(|arg| std::mem::replace(arg, Value::Bool(false)))
+            .ok_or(Error::InvalidModule("missing math function argument"))
+    };
+
+    Ok(match fun {
+        Mf::Abs => arg(0)?.map(&mut |value| {
+            Ok(match value {
+                Value::I32(v) => Value::I32(v.wrapping_abs()),
+                Value::I64(v) => Value::I64(v.wrapping_abs()),
+                Value::U32(v) => Value::U32(v),
+                value => value.float_like(value.as_f64()?.abs()),
+            })
+        })?,
+        Mf::Min => min_max(arg(0)?, arg(1)?, false)?,
+        Mf::Max => min_max(arg(0)?, arg(1)?, true)?,
+        Mf::Clamp => {
+            let (value, low, high) = (arg(0)?, arg(1)?, arg(2)?);
+            min_max(min_max(value, low, true)?, high, false)?
+        }
+        Mf::Saturate => float_map(arg(0)?, |x| x.clamp(0.0, 1.0))?,
+        Mf::Cos => float_map(arg(0)?, f64::cos)?,
+        Mf::Cosh => float_map(arg(0)?, f64::cosh)?,
+        Mf::Sin => float_map(arg(0)?, f64::sin)?,
+        Mf::Sinh => float_map(arg(0)?, f64::sinh)?,
+        Mf::Tan => float_map(arg(0)?, f64::tan)?,
+        Mf::Tanh => float_map(arg(0)?, f64::tanh)?,
+        Mf::Acos => float_map(arg(0)?, f64::acos)?,
+        Mf::Asin => float_map(arg(0)?, f64::asin)?,
+        Mf::Atan => float_map(arg(0)?, f64::atan)?,
+        Mf::Atan2 => float_zip(arg(0)?, arg(1)?, f64::atan2)?,
+        Mf::Asinh => float_map(arg(0)?, f64::asinh)?,
+        Mf::Acosh => float_map(arg(0)?, f64::acosh)?,
+        Mf::Atanh => float_map(arg(0)?, f64::atanh)?,
+        Mf::Radians => float_map(arg(0)?, f64::to_radians)?,
+        Mf::Degrees => float_map(arg(0)?, f64::to_degrees)?,
+        Mf::Ceil => float_map(arg(0)?, f64::ceil)?,
+        Mf::Floor => float_map(arg(0)?, f64::floor)?,
+        Mf::Round => float_map(arg(0)?, round_ties_even)?,
+        Mf::Fract => float_map(arg(0)?, |x| x - x.floor())?,
+        Mf::Trunc => float_map(arg(0)?, f64::trunc)?,
+        Mf::Modf => {
+            let value = arg(0)?;
+            Value::Composite(vec![
+                float_map(value.clone(), |x| x - x.trunc())?,
+                float_map(value, f64::trunc)?,
+            ])
+        }
+        Mf::Frexp => {
+            let value = arg(0)?;
+            let frexp = |x: f64| {
+                if x == 0.0 || !x.is_finite() {
+                    (x, 0)
+                } else {
+                    let exponent = x.abs().log2().floor() as i32 + 1;
+                    (x / 2f64.powi(exponent), exponent)
+                }
+            };
+            Value::Composite(vec![
+                float_map(value.clone(), |x| frexp(x).0)?,
+                value.map(&mut |value| Ok(Value::I32(frexp(value.as_f64()?).1)))?,
+            ])
+        }
+        Mf::Ldexp => arg(0)?.zip(arg(1)?, &mut |value, exponent| {
+            let exponent = exponent.as_index()? as i32;
+            Ok(value.float_like(value.as_f64()? * 2f64.powi(exponent)))
+        })?,
+        Mf::Exp => float_map(arg(0)?, f64::exp)?,
+        Mf::Exp2 => float_map(arg(0)?, f64::exp2)?,
+        Mf::Log => float_map(arg(0)?, f64::ln)?,
+        Mf::Log2 => float_map(arg(0)?, f64::log2)?,
+        Mf::Pow => float_zip(arg(0)?, arg(1)?, f64::powf)?,
+        Mf::Dot => dot(arg(0)?, arg(1)?)?,
+        Mf::Outer => {
+            let (left, right) = (arg(0)?, arg(1)?);
+            Value::Composite(
+                right
+                    .into_components()?
+                    .into_iter()
+                    .map(|scale| multiply(left.clone(), scale))
+                    .collect::<Result<_>>()?,
+            )
+        }
+        Mf::Cross => {
+            let (left, right) = (arg(0)?, arg(1)?);
+            let (a, b) = (floats(&left)?, floats(&right)?);
+            let first = &left.components()?[0];
+            Value::Composite(vec![
+                first.float_like(a[1] * b[2] - a[2] * b[1]),
+                first.float_like(a[2] * b[0] - a[0] * b[2]),
+                first.float_like(a[0] * b[1] - a[1] * b[0]),
+            ])
+        }
+        Mf::Distance => length(subtract(arg(0)?, arg(1)?)?)?,
+        Mf::Length => length(arg(0)?)?,
+        Mf::Normalize => {
+            let value = arg(0)?;
+            let length = length(value.clone())?;
+            float_zip(value, length, |x, length| x / length)?
+        }
+        Mf::FaceForward => {
+            let (n, i, n_ref) = (arg(0)?, arg(1)?, arg(2)?);
+            if dot(n_ref, i)?.as_f64()? < 0.0 {
+                n
+            } else {
+                float_map(n, |x| -x)?
+            }
+        }
+        Mf::Reflect => {
+            let (i, n) = (arg(0)?, arg(1)?);
+            let scale = float_map(dot(n.clone(), i.clone())?, |d| 2.0 * d)?;
+            subtract(i, multiply(n, scale)?)?
+        }
+        Mf::Refract => {
+            let (i, n, eta) = (arg(0)?, arg(1)?, arg(2)?);
+            let d = dot(n.clone(), i.clone())?.as_f64()?;
+            let e = eta.as_f64()?;
+            let k = 1.0 - e * e * (1.0 - d * d);
+            if k < 0.0 {
+                float_map(i, |_| 0.0)?
+            } else {
+                let i = float_map(i, |x| e * x)?;
+                let scale = eta.float_like(e * d + k.sqrt());
+                subtract(i, multiply(n, scale)?)?
+            }
+        }
+        Mf::Sign => arg(0)?.map(&mut |value| {
+            Ok(match value {
+                Value::I32(v) => Value::I32(v.signum()),
+                Value::I64(v) => Value::I64(v.signum()),
+                value => {
+                    let x = value.as_f64()?;
+                    value.float_like(if x == 0.0 { 0.0 } else { x.signum() })
+                }
+            })
+        })?,
+        Mf::Fma => arg(0)?.zip3(arg(1)?, arg(2)?, &mut |a, b, c| {
+            Ok(a.float_like(a.as_f64()?.mul_add(b.as_f64()?, c.as_f64()?)))
+        })?,
+        Mf::Mix => arg(0)?.zip3(arg(1)?, arg(2)?, &mut |a, b, t| {
+            let (x, y, t) = (a.as_f64()?, b.as_f64()?, t.as_f64()?);
+            Ok(a.float_like(x * (1.0 - t) + y * t))
+        })?,
+        Mf::Step => float_zip(arg(0)?, arg(1)?, |edge, x| if x < edge { 0.0 } else { 1.0 })?,
+        Mf::SmoothStep => arg(0)?.zip3(arg(1)?, arg(2)?, &mut |low, high, x| {
+            let (l, h, x) = (low.as_f64()?, high.as_f64()?, x.as_f64()?);
+            let t = ((x - l) / (h - l)).clamp(0.0, 1.0);
+            Ok(low.float_like(t * t * (3.0 - 2.0 * t)))
+        })?,
+        Mf::Sqrt => float_map(arg(0)?, f64::sqrt)?,
+        Mf::InverseSqrt => float_map(arg(0)?, |x| 1.0 / x.sqrt())?,
+        Mf::Inverse => return Err(Error::Unsupported("matrix inverse")),
+        Mf::Transpose => {
+            let columns = arg(0)?.into_components()?;
+            let rows = columns
+                .first()
+                .map_or(Ok(0), |column| column.components().map(<[_]>::len))?;
+            Value::Composite(
+                (0..rows)
+                    .map(|row| {
+                        Ok(Value::Composite(
+                            columns
+                                .iter()
+                                .map(|column| Ok(column.components()?[row].clone()))
+                                .collect::<Result<_>>()?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+            )
+        }
+        Mf::Determinant => {
+            let matrix = arg(0)?;
+            let columns = matrix
+                .components()?
+                .iter()
+                .map(floats)
+                .collect::<Result<Vec<_>>>()?;
+            let first = &matrix.components()?[0].components()?[0];
+            first.float_like(determinant(&columns))
+        }
+        Mf::QuantizeToF16 => arg(0)?.map(&mut |value| match value {
+            Value::F32(v) => Ok(Value::F32(f16_to_f32(f32_to_f16(v)))),
+            _ => Err(Error::InvalidModule("expected a 32-bit float")),
+        })?,
+        Mf::CountTrailingZeros => {
+            int_map(arg(0)?, |v| v.trailing_zeros() as i32, u32::trailing_zeros)?
+        }
+        Mf::CountLeadingZeros => {
+            int_map(arg(0)?, |v| v.leading_zeros() as i32, u32::leading_zeros)?
+        }
+        Mf::CountOneBits => int_map(arg(0)?, |v| v.count_ones() as i32, u32::count_ones)?,
+        Mf::ReverseBits => int_map(arg(0)?, i32::reverse_bits, u32::reverse_bits)?,
+        Mf::FindLsb => int_map(
+            arg(0)?,
+            |v| {
+                if v == 0 {
+                    -1
+                } else {
+                    v.trailing_zeros() as i32
+                }
+            },
+            |v| if v == 0 { !0 } else { v.trailing_zeros() },
+        )?,
+        Mf::FindMsb => int_map(
+            arg(0)?,
+            |v| {
+                let v = if v < 0 { !v } else { v };
+                if v == 0 {
+                    -1
+                } else {
+                    31 - v.leading_zeros() as i32
+                }
+            },
+            |v| if v == 0 { !0 } else { 31 - v.leading_zeros() },
+        )?,
+        Mf::ExtractBits => {
+            let (value, offset, count) = (arg(0)?, arg(1)?.as_u32()?, arg(2)?.as_u32()?);
+            let (offset, count) = bit_range(offset, count);
+            int_map(
+                value,
+                |v| match count {
+                    0 => 0,
+                    _ => (v << (32 - offset - count)).wrapping_shr(32 - count),
+                },
+                |v| match count {
+                    0 => 0,
+                    _ => (v >> offset) & mask(count),
+                },
+            )?
+        }
+        Mf::InsertBits => {
+            let (value, new_bits) = (arg(0)?, arg(1)?);
+            let (offset, count) = bit_range(arg(2)?.as_u32()?, arg(3)?.as_u32()?);
+            let mask = mask(count).checked_shl(offset).unwrap_or(0);
+            value.zip(new_bits, &mut |value, new_bits| {
+                let bits = (value.as_u32()? & !mask)
+                    | (new_bits.as_u32()?.checked_shl(offset).unwrap_or(0) & mask);
+                Ok(match value {
+                    Value::I32(_) => Value::I32(bits as i32),
+                    _ => Value::U32(bits),
+                })
+            })?
+        }
+        Mf::Pack4x8snorm | Mf::Pack4x8unorm | Mf::Pack2x16snorm | Mf::Pack2x16unorm => {
+            let (bits, signed) = match fun {
+                Mf::Pack4x8snorm => (8, true),
+                Mf::Pack4x8unorm => (8, false),
+                Mf::Pack2x16snorm => (16, true),
+                _ => (16, false),
+            };
+            let max = ((1u32 << (bits - signed as u32)) - 1) as f64;
+            let packed = floats(&arg(0)?)?
+                .into_iter()
+                .enumerate()
+                .fold(0, |packed, (i, x)| {
+                    let x = if signed {
+                        round_ties_even(x.clamp(-1.0, 1.0) * max) as i32 as u32
+                    } else {
+                        round_ties_even(x.clamp(0.0, 1.0) * max) as u32
+                    };
+                    packed | ((x & mask(bits)) << (i as u32 * bits))
+                });
+            Value::U32(packed)
+        }
+        Mf::Pack2x16float => {
+            let values = floats(&arg(0)?)?;
+            let half = |x: f64| f32_to_f16(x as f32) as u32;
+            Value::U32(half(values[0]) | (half(values[1]) << 16))
+        }
+        Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp => {
+            let packed =
+                int_components(&arg(0)?)?
+                    .into_iter()
+                    .enumerate()
+                    .fold(0, |packed, (i, x)| {
+                        let x = match fun {
+                            Mf::Pack4xI8Clamp => x.clamp(-128, 127),
+                            Mf::Pack4xU8Clamp => x.clamp(0, 255),
+                            _ => x,
+                        };
+                        packed | ((x as u32 & 0xff) << (i * 8))
+                    });
+            Value::U32(packed)
+        }
+        Mf::Unpack4x8snorm => {
+            let v = arg(0)?.as_u32()?;
+            f32_vector((0..4).map(|i| ((v >> (i * 8)) as u8 as i8 as f32 / 127.0).max(-1.0)))
+        }
+        Mf::Unpack4x8unorm => {
+            let v = arg(0)?.as_u32()?;
+            f32_vector((0..4).map(|i| (v >> (i * 8)) as u8 as f32 / 255.0))
+        }
+        Mf::Unpack2x16snorm => {
+            let v = arg(0)?.as_u32()?;
+            f32_vector((0..2).map(|i| ((v >> (i * 16)) as u16 as i16 as f32 / 32767.0).max(-1.0)))
+        }
+        Mf::Unpack2x16unorm => {
+            let v = arg(0)?.as_u32()?;
+            f32_vector((0..2).map(|i| (v >> (i * 16)) as u16 as f32 / 65535.0))
+        }
+        Mf::Unpack2x16float => {
+            let v = arg(0)?.as_u32()?;
+            f32_vector((0..2).map(|i| f16_to_f32((v >> (i * 16)) as u16)))
+        }
+        Mf::Unpack4xI8 => {
+            let v = arg(0)?.as_u32()?;
+            Value::Composite(
+                (0..4)
+                    .map(|i| Value::I32((v >> (i * 8)) as u8 as i8 as i32))
+                    .collect(),
+            )
+        }
+        Mf::Unpack4xU8 => {
+            let v = arg(0)?.as_u32()?;
+            Value::Composite((0..4).map(|i| Value::U32((v >> (i * 8)) & 0xff)).collect())
+        }
+        Mf::Dot4I8Packed => {
+            let (a, b) = (arg(0)?.as_u32()?, arg(1)?.as_u32()?);
+            let byte = |v: u32, i: u32| (v >> (i * 8)) as u8 as i8 as i32;
+            Value::I32((0..4).map(|i| byte(a, i) * byte(b, i)).sum())
+        }
+        Mf::Dot4U8Packed => {
+            let (a, b) = (arg(0)?.as_u32()?, arg(1)?.as_u32()?);
+            let byte = |v: u32, i: u32| (v >> (i * 8)) & 0xff;
+            Value::U32((0..4).map(|i| byte(a, i) * byte(b, i)).sum())
+        }
+    })
+}
+
+#[test]
+fn half_float_conversion() {
+    for value in [0.0, -0.0, 1.0, -2.5, 65504.0, 6.1035156e-5, 5.9604645e-8] {
+        assert_eq!(f16_to_f32(f32_to_f16(value)), value);
+    }
+    assert_eq!(f32_to_f16(65520.0), 0x7c00);
+    assert_eq!(f32_to_f16(1.0 + 1.0 / 4096.0), 0x3c00);
+    assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+}
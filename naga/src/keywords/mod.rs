@@ -2,5 +2,7 @@
 Lists of reserved keywords for each shading language with a [frontend][crate::front] or [backend][crate::back].
 */
 
+#[cfg(feature = "rust-out")]
+pub mod rust;
 #[cfg(any(feature = "wgsl-in", feature = "wgsl-out"))]
 pub mod wgsl;
@@ -0,0 +1,65 @@
+/*!
+Keywords for [Rust][rust].
+
+[rust]: https://doc.rust-lang.org/reference/keywords.html
+*/
+
+// https://doc.rust-lang.org/reference/keywords.html
+pub const RESERVED: &[&str] = &[
+    // Strict keywords
+    "as",
+    "async",
+    "await",
+    "break",
+    "const",
+    "continue",
+    "crate",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "Self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "type",
+    "unsafe",
+    "use",
+    "where",
+    "while",
+    // Reserved keywords
+    "abstract",
+    "become",
+    "box",
+    "do",
+    "final",
+    "macro",
+    "override",
+    "priv",
+    "try",
+    "typeof",
+    "unsized",
+    "virtual",
+    "yield",
+    // Weak keywords
+    "macro_rules",
+    "union",
+];
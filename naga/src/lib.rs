@@ -282,8 +282,15 @@ mod block;
 #[cfg(feature = "compact")]
 pub mod compact;
 pub mod front;
+#[cfg(feature = "interp")]
+pub mod interp;
 pub mod keywords;
+#[cfg(feature = "link")]
+pub mod link;
+#[cfg(feature = "opt")]
+pub mod opt;
 pub mod proc;
+pub mod reflect;
 mod span;
 pub mod valid;
 
@@ -297,6 +304,12 @@ use serde::Deserialize;
 #[cfg(feature = "serialize")]
 use serde::Serialize;
 
+/// The version of this crate.
+///
+/// Shader translations can change from one version to the next, so caches of
+/// translated shaders should be keyed on this.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /// Width of a boolean type, in bytes.
 pub const BOOL_WIDTH: Bytes = 1;
 
@@ -384,6 +397,8 @@ pub enum ShaderStage {
     Vertex,
     Fragment,
     Compute,
+    Task,
+    Mesh,
 }
 
 /// Addressing space of variables.
@@ -406,6 +421,9 @@ pub enum AddressSpace {
     Handle,
     /// Push constants.
     PushConstant,
+    /// Data a task shader workgroup passes to the mesh shader workgroups it
+    /// launches. Mutable in task shaders, read-only in mesh shaders.
+    TaskPayload,
 }
 
 /// Built-in inputs and outputs.
@@ -438,6 +456,13 @@ pub enum BuiltIn {
     WorkGroupId,
     WorkGroupSize,
     NumWorkGroups,
+    // task
+    MeshTaskSize,
+    // mesh
+    CullPrimitive,
+    PointIndex,
+    LineIndices,
+    TriangleIndices,
 }
 
 /// Number of bytes per scalar.
@@ -680,6 +705,28 @@ pub enum ImageClass {
         format: StorageFormat,
         access: StorageAccess,
     },
+    /// External texture, such as a decoded video frame.
+    ///
+    /// External textures are always two-dimensional, non-arrayed and
+    /// single-sampled, and are read as `vec4<f32>` RGBA values. They may only
+    /// be sampled with [`clamp_to_edge`] set, loaded from, and have their size
+    /// queried.
+    ///
+    /// The contents may be stored in one, two or three planes (RGBA,
+    /// Y + interleaved UV, or Y + U + V), so backends don't translate an
+    /// external texture into a single resource. A global variable of this
+    /// class bound at `@group(G) @binding(N)` is lowered into four resources
+    /// in group `G`:
+    ///
+    /// - the planes, as 2D float textures at bindings `N`, `N + 1` and `N + 2`,
+    /// - a uniform buffer holding an [`ExternalTextureParams`] value at
+    ///   binding `N + 3`.
+    ///
+    /// Unused planes must still be bound; their contents are ignored.
+    ///
+    /// [`clamp_to_edge`]: Expression::ImageSample::clamp_to_edge
+    /// [`ExternalTextureParams`]: Module::generate_external_texture_params_type
+    External,
 }
 
 /// A data type declared in the module.
@@ -972,6 +1019,62 @@ pub struct ResourceBinding {
     pub binding: u32,
 }
 
+/// The kind of primitive a [`Mesh`] shader stage outputs.
+///
+/// [`Mesh`]: ShaderStage::Mesh
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[allow(missing_docs)] // The names are self evident
+pub enum MeshOutputTopology {
+    Points,
+    Lines,
+    Triangles,
+}
+
+/// The output declaration of a [`Mesh`] shader stage.
+///
+/// A mesh shader workgroup outputs up to [`max_vertices`] vertices and up to
+/// [`max_primitives`] primitives, built from them. It declares how many it
+/// actually outputs with [`MeshFunction::SetMeshOutputs`], and then writes
+/// each one with [`MeshFunction::SetVertex`] and [`MeshFunction::SetPrimitive`].
+///
+/// [`Mesh`]: ShaderStage::Mesh
+/// [`max_vertices`]: MeshStageInfo::max_vertices
+/// [`max_primitives`]: MeshStageInfo::max_primitives
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct MeshStageInfo {
+    /// The kind of primitives output.
+    pub topology: MeshOutputTopology,
+    /// The maximum number of vertices output.
+    pub max_vertices: u32,
+    /// The maximum number of primitives output.
+    pub max_primitives: u32,
+    /// The struct type of the vertices output.
+    ///
+    /// Its members are bound like the result of a [`Vertex`] stage.
+    ///
+    /// [`Vertex`]: ShaderStage::Vertex
+    pub vertex_output_type: Handle<Type>,
+    /// The struct type of the primitives output.
+    ///
+    /// Its members must all be built-ins: the [`PointIndex`], [`LineIndices`]
+    /// or [`TriangleIndices`] matching the [`topology`], and optionally
+    /// [`CullPrimitive`] and [`PrimitiveIndex`].
+    ///
+    /// [`PointIndex`]: BuiltIn::PointIndex
+    /// [`LineIndices`]: BuiltIn::LineIndices
+    /// [`TriangleIndices`]: BuiltIn::TriangleIndices
+    /// [`topology`]: MeshStageInfo::topology
+    /// [`CullPrimitive`]: BuiltIn::CullPrimitive
+    /// [`PrimitiveIndex`]: BuiltIn::PrimitiveIndex
+    pub primitive_output_type: Handle<Type>,
+}
+
 /// Variable defined at module level.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
@@ -1188,6 +1291,8 @@ pub enum MathFunction {
     Pow,
     // geometry
     Dot,
+    Dot4I8Packed,
+    Dot4U8Packed,
     Outer,
     Cross,
     Distance,
@@ -1207,6 +1312,7 @@ pub enum MathFunction {
     Inverse,
     Transpose,
     Determinant,
+    QuantizeToF16,
     // bits
     CountTrailingZeros,
     CountLeadingZeros,
@@ -1222,12 +1328,18 @@ pub enum MathFunction {
     Pack2x16snorm,
     Pack2x16unorm,
     Pack2x16float,
+    Pack4xI8,
+    Pack4xU8,
+    Pack4xI8Clamp,
+    Pack4xU8Clamp,
     // data unpacking
     Unpack4x8snorm,
     Unpack4x8unorm,
     Unpack2x16snorm,
     Unpack2x16unorm,
     Unpack2x16float,
+    Unpack4xI8,
+    Unpack4xU8,
 }
 
 /// Sampling modifier to control the level of detail.
@@ -1293,6 +1405,8 @@ bitflags::bitflags! {
         const STORAGE = 0x1;
         /// Barrier affects all `AddressSpace::WorkGroup` accesses.
         const WORK_GROUP = 0x2;
+        /// Barrier affects all storage texture accesses.
+        const TEXTURE = 0x4;
     }
 }
 
@@ -1437,6 +1551,15 @@ pub enum Expression {
         offset: Option<Handle<Expression>>,
         level: SampleLevel,
         depth_ref: Option<Handle<Expression>>,
+        /// Clamp the coordinate to half a texel from the edge of the image,
+        /// as WGSL's `textureSampleBaseClampToEdge` does.
+        ///
+        /// This may only be set for non-arrayed 2D float or [`External`]
+        /// images sampled at [`SampleLevel::Zero`] without an offset, and it
+        /// must be set when sampling an [`External`] image.
+        ///
+        /// [`External`]: ImageClass::External
+        clamp_to_edge: bool,
     },
 
     /// Load a texel from an image.
@@ -1664,6 +1787,36 @@ pub enum RayQueryFunction {
     Terminate,
 }
 
+/// An operation of a [`Mesh`] shader stage on its outputs.
+///
+/// [`Mesh`]: ShaderStage::Mesh
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum MeshFunction {
+    /// Declare the number of vertices and primitives the workgroup outputs.
+    ///
+    /// Both operands must be `u32` scalars, uniform across the workgroup, and
+    /// no greater than the maximums in the entry point's [`MeshStageInfo`].
+    SetMeshOutputs {
+        vertex_count: Handle<Expression>,
+        primitive_count: Handle<Expression>,
+    },
+    /// Write the vertex at `index`, a `u32` scalar, to be `value`, of type
+    /// [`MeshStageInfo::vertex_output_type`].
+    SetVertex {
+        index: Handle<Expression>,
+        value: Handle<Expression>,
+    },
+    /// Write the primitive at `index`, a `u32` scalar, to be `value`, of
+    /// type [`MeshStageInfo::primitive_output_type`].
+    SetPrimitive {
+        index: Handle<Expression>,
+        value: Handle<Expression>,
+    },
+}
+
 //TODO: consider removing `Clone`. It's not valid to clone `Statement::Emit` anyway.
 /// Instructions which make up an executable block.
 // Clone is used only for error reporting and is not intended for end users
@@ -1863,6 +2016,10 @@ pub enum Statement {
         /// The specific operation we're performing on `query`.
         fun: RayQueryFunction,
     },
+    /// Output vertices or primitives from a [`Mesh`] shader stage.
+    ///
+    /// [`Mesh`]: ShaderStage::Mesh
+    MeshFunction(MeshFunction),
 }
 
 /// A function argument.
@@ -1973,8 +2130,15 @@ pub struct EntryPoint {
     pub stage: ShaderStage,
     /// Early depth test for fragment stages.
     pub early_depth_test: Option<EarlyDepthTest>,
-    /// Workgroup size for compute stages
+    /// Workgroup size for compute, task and mesh stages
     pub workgroup_size: [u32; 3],
+    /// The outputs of mesh stages.
+    pub mesh_info: Option<MeshStageInfo>,
+    /// The [`TaskPayload`] global that task stages write and mesh stages read,
+    /// if any.
+    ///
+    /// [`TaskPayload`]: AddressSpace::TaskPayload
+    pub task_payload: Option<Handle<GlobalVariable>>,
     /// The entrance function.
     pub function: Function,
 }
@@ -2020,6 +2184,13 @@ pub struct SpecialTypes {
     /// this if needed and return the handle.
     pub ray_intersection: Option<Handle<Type>>,
 
+    /// Type of the uniform buffer backends lower [`ImageClass::External`]
+    /// textures' parameters into.
+    ///
+    /// Call [`Module::generate_external_texture_params_type`] to populate
+    /// this if needed and return the handle.
+    pub external_texture_params: Option<Handle<Type>>,
+
     /// Types for predeclared wgsl types instantiated on demand.
     ///
     /// Call [`Module::generate_predeclared_type`] to populate this if
@@ -0,0 +1,413 @@
+/*!
+Combining several [`Module`]s into one.
+
+A [`Linker`] merges modules compiled separately, like a library of shared
+utility functions and the shaders that use it, into a single module. Types
+that are identical in several modules are stored only once. All other
+module-scope items are kept, and those whose names are already used in the
+linked module are renamed with a [`Namer`].
+
+The spans of each module added are moved past the spans of the modules
+before it, so that [`SourceMap::locate`] can tell which module a span in the
+linked module came from.
+*/
+
+use crate::arena::{Arena, Handle};
+use crate::compact::{FunctionMap, HandleMap, ModuleMap};
+use crate::proc::Namer;
+use crate::{Block, FastHashSet, Module, Span, Statement};
+
+/// Merges [`Module`]s into one.
+#[derive(Default)]
+pub struct Linker {
+    module: Module,
+    /// The names of the module-scope items in `module`.
+    ///
+    /// Types, constants, global variables, functions and entry points all
+    /// share this namespace, as they do in WGSL.
+    names: FastHashSet<String>,
+    /// Generates replacements for names that are already used.
+    namer: Namer,
+    source_map: SourceMap,
+}
+
+/// Maps spans in a linked module back to the modules they came from.
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    /// The offset of the spans of each module, in the order they were added.
+    starts: Vec<u32>,
+    /// The offset for the spans of the next module added.
+    end: u32,
+}
+
+impl SourceMap {
+    /// Return the module `span` came from, and the span in that module.
+    ///
+    /// The module is identified by the order in which it was added to the
+    /// [`Linker`], starting from zero. Return `None` if `span` is undefined.
+    pub fn locate(&self, span: Span) -> Option<(usize, Span)> {
+        let range = span.to_range()?;
+        let index = self
+            .starts
+            .partition_point(|&start| start as usize <= range.start)
+            .checked_sub(1)?;
+        let start = self.starts[index];
+        let span = Span::new(range.start as u32 - start, range.end as u32 - start);
+        Some((index, span))
+    }
+}
+
+/// Where the items of a module given to [`Linker::add`] are in the linked
+/// module.
+///
+/// Each method panics if given a handle that was not in the module added.
+pub struct Mapping {
+    map: ModuleMap,
+    /// The index of the module's first entry point in the linked module.
+    first_entry_point: usize,
+}
+
+impl Mapping {
+    pub fn ty(&self, old: Handle<crate::Type>) -> Handle<crate::Type> {
+        self.map.types.try_adjust(old).unwrap()
+    }
+
+    pub fn constant(&self, old: Handle<crate::Constant>) -> Handle<crate::Constant> {
+        self.map.constants.try_adjust(old).unwrap()
+    }
+
+    pub fn const_expression(&self, old: Handle<crate::Expression>) -> Handle<crate::Expression> {
+        self.map.const_expressions.try_adjust(old).unwrap()
+    }
+
+    pub fn global_variable(
+        &self,
+        old: Handle<crate::GlobalVariable>,
+    ) -> Handle<crate::GlobalVariable> {
+        self.map.global_variables.try_adjust(old).unwrap()
+    }
+
+    pub fn function(&self, old: Handle<crate::Function>) -> Handle<crate::Function> {
+        self.map.functions.try_adjust(old).unwrap()
+    }
+
+    /// Return the index in the linked module of the entry point at `index`.
+    pub const fn entry_point(&self, index: usize) -> usize {
+        self.first_entry_point + index
+    }
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add all the items of `module` to the linked module.
+    ///
+    /// Return where they ended up, since handles change, and items whose
+    /// names are already used are renamed.
+    pub fn add(&mut self, module: Module) -> Mapping {
+        let Module {
+            mut types,
+            special_types,
+            constants,
+            global_variables,
+            const_expressions,
+            functions,
+            entry_points,
+        } = module;
+
+        let mut spans = SpanOffset {
+            offset: self.source_map.end,
+            end: self.source_map.end,
+        };
+        let mut map = ModuleMap {
+            types: HandleMap::new(),
+            constants: HandleMap::offset(constants.len(), self.module.constants.len()),
+            const_expressions: HandleMap::offset(
+                const_expressions.len(),
+                self.module.const_expressions.len(),
+            ),
+            global_variables: HandleMap::offset(
+                global_variables.len(),
+                self.module.global_variables.len(),
+            ),
+            functions: HandleMap::offset(functions.len(), self.module.functions.len()),
+        };
+
+        // Types only refer to the types before them, so we can build the map
+        // as we go.
+        for (_, mut ty, span) in types.drain_all() {
+            map.adjust_type(&mut ty);
+            let handle = match self.module.types.get(&ty) {
+                Some(handle) => handle,
+                None => {
+                    if let Some(ref mut name) = ty.name {
+                        self.claim(name);
+                    }
+                    self.module.types.insert(ty, spans.apply(span))
+                }
+            };
+            map.types.push(handle);
+        }
+
+        let mut special_types = special_types;
+        map.adjust_special_types(&mut special_types);
+        let special = &mut self.module.special_types;
+        special.ray_desc = special.ray_desc.or(special_types.ray_desc);
+        special.ray_intersection = special.ray_intersection.or(special_types.ray_intersection);
+        special.external_texture_params = special
+            .external_texture_params
+            .or(special_types.external_texture_params);
+        for (kind, handle) in special_types.predeclared_types {
+            special.predeclared_types.entry(kind).or_insert(handle);
+        }
+
+        for (mut expression, span) in const_expressions.into_inner_with_spans() {
+            map.adjust_expression(&mut expression, &map.const_expressions);
+            self.module
+                .const_expressions
+                .append(expression, spans.apply(span));
+        }
+
+        for (mut constant, span) in constants.into_inner_with_spans() {
+            map.types.adjust(&mut constant.ty);
+            map.const_expressions.adjust(&mut constant.init);
+            if let Some(ref mut name) = constant.name {
+                self.claim(name);
+            }
+            self.module.constants.append(constant, spans.apply(span));
+        }
+
+        for (mut global, span) in global_variables.into_inner_with_spans() {
+            map.types.adjust(&mut global.ty);
+            map.const_expressions.adjust_option(&mut global.init);
+            if let Some(ref mut name) = global.name {
+                self.claim(name);
+            }
+            self.module
+                .global_variables
+                .append(global, spans.apply(span));
+        }
+
+        let mut reused_named_expressions = crate::NamedExpressions::default();
+        for (mut function, span) in functions.into_inner_with_spans() {
+            adjust_function(
+                &mut function,
+                &map,
+                &mut spans,
+                &mut reused_named_expressions,
+            );
+            if let Some(ref mut name) = function.name {
+                self.claim(name);
+            }
+            self.module.functions.append(function, spans.apply(span));
+        }
+
+        let first_entry_point = self.module.entry_points.len();
+        for mut entry_point in entry_points {
+            adjust_function(
+                &mut entry_point.function,
+                &map,
+                &mut spans,
+                &mut reused_named_expressions,
+            );
+            self.claim(&mut entry_point.name);
+            self.module.entry_points.push(entry_point);
+        }
+
+        self.source_map.starts.push(spans.offset);
+        self.source_map.end = spans.end;
+
+        Mapping {
+            map,
+            first_entry_point,
+        }
+    }
+
+    /// Let `add_items` add items to the linked module directly, and then
+    /// rename those whose names were already used.
+    ///
+    /// Unlike [`add`], this leaves the new items' spans as they are.
+    ///
+    /// [`add`]: Linker::add
+    #[cfg(feature = "wgsl-in")]
+    pub(crate) fn extend<R>(&mut self, add_items: impl FnOnce(&mut Module) -> R) -> R {
+        let types = self.module.types.len();
+        let constants = self.module.constants.len();
+        let global_variables = self.module.global_variables.len();
+        let functions = self.module.functions.len();
+        let entry_points = self.module.entry_points.len();
+
+        let result = add_items(&mut self.module);
+
+        // Renaming a type changes its hash, so rebuild the arena if any need
+        // it. Since the new names are unique, this doesn't merge any types,
+        // and handles stay the same.
+        let renamed = self.module.types.iter().skip(types).any(|(_, ty)| {
+            ty.name
+                .as_ref()
+                .is_some_and(|name| self.names.contains(name))
+        });
+        if renamed {
+            let mut new_types = crate::UniqueArena::new();
+            for (handle, mut ty, span) in self.module.types.drain_all() {
+                if handle.index() >= types {
+                    if let Some(ref mut name) = ty.name {
+                        claim(&mut self.names, &mut self.namer, name);
+                    }
+                }
+                new_types.insert(ty, span);
+            }
+            self.module.types = new_types;
+        } else {
+            for (_, ty) in self.module.types.iter().skip(types) {
+                if let Some(ref name) = ty.name {
+                    self.names.insert(name.clone());
+                }
+            }
+        }
+
+        let module = &mut self.module;
+        let names = module
+            .constants
+            .iter_mut()
+            .skip(constants)
+            .filter_map(|(_, constant)| constant.name.as_mut())
+            .chain(
+                module
+                    .global_variables
+                    .iter_mut()
+                    .skip(global_variables)
+                    .filter_map(|(_, global)| global.name.as_mut()),
+            )
+            .chain(
+                module
+                    .functions
+                    .iter_mut()
+                    .skip(functions)
+                    .filter_map(|(_, function)| function.name.as_mut()),
+            )
+            .chain(
+                module.entry_points[entry_points..]
+                    .iter_mut()
+                    .map(|entry_point| &mut entry_point.name),
+            );
+        for name in names {
+            claim(&mut self.names, &mut self.namer, name);
+        }
+
+        result
+    }
+
+    /// Return the linked module so far.
+    pub const fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Return the linked module, and the map from its spans to the modules
+    /// they came from.
+    pub fn finish(self) -> (Module, SourceMap) {
+        (self.module, self.source_map)
+    }
+
+    /// Rename `name` if it is already used, and note that it is used now.
+    fn claim(&mut self, name: &mut String) {
+        claim(&mut self.names, &mut self.namer, name);
+    }
+}
+
+fn claim(names: &mut FastHashSet<String>, namer: &mut Namer, name: &mut String) {
+    if names.contains(name.as_str()) {
+        let base = std::mem::take(name);
+        *name = loop {
+            let candidate = namer.call(&base);
+            if !names.contains(&candidate) {
+                break candidate;
+            }
+        };
+    }
+    names.insert(name.clone());
+}
+
+/// Adjust the handles of module-scope items in `function` according to
+/// `map`, and move its spans past those of the modules already linked.
+fn adjust_function(
+    function: &mut crate::Function,
+    map: &ModuleMap,
+    spans: &mut SpanOffset,
+    reused_named_expressions: &mut crate::NamedExpressions,
+) {
+    // The function's own expressions stay where they are.
+    let function_map = FunctionMap {
+        expressions: HandleMap::offset(function.expressions.len(), 0),
+    };
+    function_map.compact(function, map, reused_named_expressions);
+
+    spans.apply_arena(&mut function.expressions);
+    spans.apply_arena(&mut function.local_variables);
+    spans.apply_block(&mut function.body);
+}
+
+/// Moves spans past those of the modules already linked.
+struct SpanOffset {
+    /// The amount to move spans by.
+    offset: u32,
+    /// The end of the furthest span moved so far.
+    end: u32,
+}
+
+impl SpanOffset {
+    fn apply(&mut self, span: Span) -> Span {
+        let Some(range) = span.to_range() else {
+            return span;
+        };
+        let span = Span::new(
+            range.start as u32 + self.offset,
+            range.end as u32 + self.offset,
+        );
+        self.end = self.end.max(range.end as u32 + self.offset);
+        span
+    }
+
+    fn apply_arena<T>(&mut self, arena: &mut Arena<T>) {
+        let mut moved = Arena::new();
+        for (value, span) in std::mem::take(arena).into_inner_with_spans() {
+            moved.append(value, self.apply(span));
+        }
+        *arena = moved;
+    }
+
+    fn apply_block(&mut self, block: &mut Block) {
+        for (statement, span) in block.span_iter_mut() {
+            if let Some(span) = span {
+                *span = self.apply(*span);
+            }
+            match *statement {
+                Statement::Block(ref mut block) => self.apply_block(block),
+                Statement::If {
+                    ref mut accept,
+                    ref mut reject,
+                    ..
+                } => {
+                    self.apply_block(accept);
+                    self.apply_block(reject);
+                }
+                Statement::Switch { ref mut cases, .. } => {
+                    for case in cases {
+                        self.apply_block(&mut case.body);
+                    }
+                }
+                Statement::Loop {
+                    ref mut body,
+                    ref mut continuing,
+                    ..
+                } => {
+                    self.apply_block(body);
+                    self.apply_block(continuing);
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+/*!
+Dead code elimination.
+
+Statements after a `return`, `break`, `continue` or `kill` are removed, and
+`if` statements whose conditions are boolean literals or constants are
+replaced by the statements of the branch taken.
+
+A store is dead if it is to a local variable nothing reads. A variable is
+read if any of its pointers is used by anything but a dead store: loaded,
+passed to a function, named, and so on. Since removing a store may leave
+another variable unread, we start by assuming every store is dead, and put
+back the stores to variables found to be read until there are no more.
+*/
+
+use crate::arena::{Arena, Handle};
+use crate::compact::{HandleMap, HandleSet};
+use crate::{Block, Expression, Function, Literal, LocalVariable, Module, Statement};
+
+pub(super) fn eliminate(module: &mut Module) {
+    let handles: Vec<_> = module.functions.iter().map(|(handle, _)| handle).collect();
+    for handle in handles {
+        let mut function = std::mem::take(&mut module.functions[handle]);
+        eliminate_in_function(&mut function, module);
+        module.functions[handle] = function;
+    }
+    for index in 0..module.entry_points.len() {
+        let mut function = std::mem::take(&mut module.entry_points[index].function);
+        eliminate_in_function(&mut function, module);
+        module.entry_points[index].function = function;
+    }
+}
+
+fn eliminate_in_function(function: &mut Function, module: &Module) {
+    remove_unreachable(&mut function.body, &function.expressions, module);
+
+    // The local variable each pointer expression points into.
+    let mut roots: Vec<Option<Handle<LocalVariable>>> =
+        Vec::with_capacity(function.expressions.len());
+    for (_, expression) in function.expressions.iter() {
+        let root = match *expression {
+            Expression::LocalVariable(local) => Some(local),
+            Expression::Access { base, .. } | Expression::AccessIndex { base, .. } => {
+                roots[base.index()]
+            }
+            _ => None,
+        };
+        roots.push(root);
+    }
+    if roots.iter().all(Option::is_none) {
+        return;
+    }
+
+    let body = std::mem::take(&mut function.body);
+    let mut read = HandleSet::for_arena(&function.local_variables);
+    loop {
+        function.body = body.clone();
+        remove_dead_stores(&mut function.body, &roots, &read);
+
+        let used = crate::compact::used_expressions(module, function);
+        let mut changed = false;
+        for (handle, _) in function.expressions.iter() {
+            if let Some(local) = roots[handle.index()] {
+                if used.contains(handle) && !read.contains(local) {
+                    read.insert(local);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Remove statements that can't be reached from `block` and the blocks
+/// nested in it.
+fn remove_unreachable(block: &mut Block, expressions: &Arena<Expression>, module: &Module) {
+    for (statement, span) in std::mem::take(block).span_into_iter() {
+        let mut statement = statement;
+        match statement {
+            Statement::Block(ref mut inner) => {
+                remove_unreachable(inner, expressions, module);
+                if inner.is_empty() {
+                    continue;
+                }
+            }
+            Statement::If {
+                condition,
+                ref mut accept,
+                ref mut reject,
+            } => {
+                if let Some(value) = constant_bool(condition, expressions, module) {
+                    let mut taken = std::mem::take(if value { accept } else { reject });
+                    remove_unreachable(&mut taken, expressions, module);
+                    let terminated = taken.last().is_some_and(is_terminator);
+                    block.append(&mut taken);
+                    if terminated {
+                        break;
+                    }
+                    continue;
+                }
+                remove_unreachable(accept, expressions, module);
+                remove_unreachable(reject, expressions, module);
+                if accept.is_empty() && reject.is_empty() {
+                    continue;
+                }
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    remove_unreachable(&mut case.body, expressions, module);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                remove_unreachable(body, expressions, module);
+                remove_unreachable(continuing, expressions, module);
+            }
+            _ => {}
+        }
+        let terminated = is_terminator(&statement);
+        block.push(statement, span);
+        if terminated {
+            break;
+        }
+    }
+}
+
+/// Return the value of `expression` if it is a boolean literal, or a constant
+/// initialized with one.
+fn constant_bool(
+    expression: Handle<Expression>,
+    expressions: &Arena<Expression>,
+    module: &Module,
+) -> Option<bool> {
+    let literal = match expressions[expression] {
+        Expression::Literal(literal) => literal,
+        Expression::Constant(constant) => {
+            match module.const_expressions[module.constants[constant].init] {
+                Expression::Literal(literal) => literal,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    match literal {
+        Literal::Bool(value) => Some(value),
+        _ => None,
+    }
+}
+
+const fn is_terminator(statement: &Statement) -> bool {
+    matches!(
+        *statement,
+        Statement::Return { .. } | Statement::Break | Statement::Continue | Statement::Kill
+    )
+}
+
+/// Remove the stores in `block` to local variables not in `read`.
+fn remove_dead_stores(
+    block: &mut Block,
+    roots: &[Option<Handle<LocalVariable>>],
+    read: &HandleSet<LocalVariable>,
+) {
+    super::for_each_block(block, false, &mut |block, _| {
+        let dead = |statement: &Statement| match *statement {
+            Statement::Store { pointer, .. } => {
+                roots[pointer.index()].is_some_and(|local| !read.contains(local))
+            }
+            _ => false,
+        };
+        if !block.iter().any(dead) {
+            return;
+        }
+        for (statement, span) in std::mem::take(block).span_into_iter() {
+            if !dead(&statement) {
+                block.push(statement, span);
+            }
+        }
+    });
+}
+
+/// Remove local variables that no expression refers to.
+pub(super) fn remove_unused_locals(module: &mut Module) {
+    let functions = module
+        .functions
+        .iter_mut()
+        .map(|(_, function)| function)
+        .chain(
+            module
+                .entry_points
+                .iter_mut()
+                .map(|entry_point| &mut entry_point.function),
+        );
+    for function in functions {
+        let mut used = HandleSet::for_arena(&function.local_variables);
+        for (_, expression) in function.expressions.iter() {
+            if let Expression::LocalVariable(local) = *expression {
+                used.insert(local);
+            }
+        }
+        if function
+            .local_variables
+            .iter()
+            .all(|(handle, _)| used.contains(handle))
+        {
+            continue;
+        }
+
+        let map = HandleMap::from_set(used);
+        function
+            .local_variables
+            .retain_mut(|handle, _| map.used(handle));
+        for (_, expression) in function.expressions.iter_mut() {
+            if let Expression::LocalVariable(ref mut local) = *expression {
+                map.adjust(local);
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/*!
+Constant folding.
+
+Each function's expression arena is rebuilt in order, offering every
+expression to the [`ConstantEvaluator`] once its operands have been rebuilt.
+Expressions it can evaluate are replaced by their values; the rest are copied
+as they are.
+*/
+
+use super::for_each_block;
+use crate::arena::{Arena, Range};
+use crate::compact::{FunctionMap, HandleMap, ModuleMap};
+use crate::proc::{ConstantEvaluator, Emitter, ExpressionConstnessTracker};
+use crate::{Block, Function, Module, Statement};
+
+pub(super) fn fold(module: &mut Module) {
+    let module_map = super::identity_map(module);
+
+    let mut functions = std::mem::take(&mut module.functions);
+    for (_, function) in functions.iter_mut() {
+        fold_function(function, module, &module_map);
+    }
+    module.functions = functions;
+
+    let mut entry_points = std::mem::take(&mut module.entry_points);
+    for entry_point in entry_points.iter_mut() {
+        fold_function(&mut entry_point.function, module, &module_map);
+    }
+    module.entry_points = entry_points;
+}
+
+fn fold_function(function: &mut Function, module: &mut Module, module_map: &ModuleMap) {
+    let mut expressions = Arena::new();
+    let mut expression_constness = ExpressionConstnessTracker::new();
+    // We don't build any statements as we go, so the evaluator's emitter is
+    // never started.
+    let mut emitter = Emitter::default();
+    let mut block = Block::new();
+
+    let mut map = HandleMap::new();
+    // The indices of the new expressions appended for each old one. An old
+    // expression can become several new ones, since the evaluator appends
+    // the components of the values it produces.
+    let mut appended = Vec::with_capacity(function.expressions.len());
+    for (mut expression, span) in std::mem::take(&mut function.expressions).into_inner_with_spans()
+    {
+        module_map.adjust_expression(&mut expression, &map);
+        let start = expressions.len();
+        let result = ConstantEvaluator::for_wgsl_function(
+            module,
+            &mut expressions,
+            &mut expression_constness,
+            &mut emitter,
+            &mut block,
+        )
+        .try_eval_and_append(&expression, span);
+        let handle = match result {
+            Ok(handle) => handle,
+            Err(_) => expressions.append(expression, span),
+        };
+        map.push(handle);
+        appended.push(start as u32..expressions.len() as u32);
+    }
+    function.expressions = expressions;
+
+    for (_, local) in function.local_variables.iter_mut() {
+        map.adjust_option(&mut local.init);
+    }
+    let named_expressions = std::mem::take(&mut function.named_expressions);
+    for (mut handle, name) in named_expressions {
+        map.adjust(&mut handle);
+        function.named_expressions.entry(handle).or_insert(name);
+    }
+
+    // `adjust_body` can't adjust `Emit` statements, since an expression's
+    // value may now be computed by several new expressions, so note their
+    // ranges first and fix them afterwards.
+    let mut emitted = Vec::new();
+    for_each_block(&mut function.body, false, &mut |block, _| {
+        for statement in block.iter() {
+            if let Statement::Emit(ref range) = *statement {
+                emitted.push(range.clone());
+            }
+        }
+    });
+
+    let function_map = FunctionMap { expressions: map };
+    function_map.adjust_body(function, module_map);
+
+    let expressions = &function.expressions;
+    let mut emitted = emitted.into_iter();
+    for_each_block(&mut function.body, false, &mut |block, _| {
+        if !block.iter().any(|s| matches!(*s, Statement::Emit(_))) {
+            return;
+        }
+        for (statement, span) in std::mem::take(block).span_into_iter() {
+            if !matches!(statement, Statement::Emit(_)) {
+                block.push(statement, span);
+                continue;
+            }
+            let Some((first, last)) = emitted.next().unwrap().first_and_last() else {
+                continue;
+            };
+            let range = appended[first.index()].start..appended[last.index()].end;
+
+            // Literals and other expressions that need no emitting may now
+            // be in the middle of the range, so split it around them.
+            let mut start = range.start;
+            for handle in Range::from_zero_based_index_range(range.clone(), expressions) {
+                if expressions[handle].needs_pre_emit() {
+                    let index = handle.index() as u32;
+                    if start < index {
+                        let range = Range::from_zero_based_index_range(start..index, expressions);
+                        block.push(Statement::Emit(range), span);
+                    }
+                    start = index + 1;
+                }
+            }
+            if start < range.end {
+                let range = Range::from_zero_based_index_range(start..range.end, expressions);
+                block.push(Statement::Emit(range), span);
+            }
+        }
+    });
+}
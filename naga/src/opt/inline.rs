@@ -0,0 +1,407 @@
+/*!
+Inlining small functions.
+
+Functions are visited in arena order, so the functions they call have already
+had their own calls inlined. A call is replaced by a copy of the callee's
+body, and its result by a copy of the callee's return value. The copied
+expressions take the call result's place in the caller's arena, or go at the
+end of it if the callee returns nothing, so that the caller's `Emit` ranges
+stay contiguous.
+*/
+
+use super::for_each_block;
+use crate::arena::{Arena, Handle};
+use crate::compact::{FunctionMap, HandleMap, HandleSet, ModuleMap};
+use crate::{
+    Block, Expression, FastHashMap, Function, LocalVariable, Module, NamedExpressions, Statement,
+};
+
+pub(super) fn inline(module: &mut Module, limit: usize) {
+    let module_map = super::identity_map(module);
+    let mut functions = std::mem::take(&mut module.functions);
+    let called_before = called_functions(&mut functions, &mut module.entry_points, None);
+
+    // The functions whose calls we can replace with their bodies.
+    let mut inlinable = HandleSet::for_arena(&functions);
+    let handles: Vec<_> = functions.iter().map(|(handle, _)| handle).collect();
+    for handle in handles {
+        let mut function = std::mem::take(&mut functions[handle]);
+        inline_calls(&mut function, &functions, &inlinable, &module_map);
+        if is_inlinable(&function, limit) {
+            inlinable.insert(handle);
+        }
+        functions[handle] = function;
+    }
+    for entry_point in module.entry_points.iter_mut() {
+        inline_calls(
+            &mut entry_point.function,
+            &functions,
+            &inlinable,
+            &module_map,
+        );
+    }
+    module.functions = functions;
+
+    remove_uncalled(module, &called_before);
+}
+
+/// Return true if calls to `function` can be replaced by its body.
+///
+/// The body must not return anywhere but at its end, since there is nothing
+/// to break out of once it is inlined, and must end by returning if the
+/// function has a result.
+fn is_inlinable(function: &Function, limit: usize) -> bool {
+    if function.expressions.len() > limit {
+        return false;
+    }
+    let body = match function.body.last() {
+        Some(&Statement::Return { .. }) => &function.body[..function.body.len() - 1],
+        _ if function.result.is_some() => return false,
+        _ => &function.body[..],
+    };
+    !returns(body)
+}
+
+fn returns(block: &[Statement]) -> bool {
+    block.iter().any(|statement| match *statement {
+        Statement::Return { .. } => true,
+        Statement::Block(ref block) => returns(block),
+        Statement::If {
+            ref accept,
+            ref reject,
+            ..
+        } => returns(accept) || returns(reject),
+        Statement::Switch { ref cases, .. } => cases.iter().any(|case| returns(&case.body)),
+        Statement::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => returns(body) || returns(continuing),
+        _ => false,
+    })
+}
+
+/// A call to a function in [`inline`]'s `inlinable` set.
+struct Site {
+    callee: Handle<Function>,
+    arguments: Vec<Handle<Expression>>,
+    in_loop: bool,
+}
+
+fn inline_calls(
+    caller: &mut Function,
+    functions: &Arena<Function>,
+    inlinable: &HandleSet<Function>,
+    module_map: &ModuleMap,
+) {
+    // Find the calls to inline, in the order `for_each_block` visits them.
+    // Calls we can't inline after all are `None`, so that the indices still
+    // match the calls when we replace them.
+    let mut sites = Vec::new();
+    // The index in `sites` of each call that has a result, by the index of
+    // the result expression.
+    let mut sites_by_result = FastHashMap::default();
+    for_each_block(&mut caller.body, false, &mut |block, in_loop| {
+        for statement in block.iter() {
+            let Statement::Call {
+                function: callee,
+                ref arguments,
+                result,
+            } = *statement
+            else {
+                continue;
+            };
+            if !inlinable.contains(callee) {
+                continue;
+            }
+            // The copied expressions take the result's place in the arena,
+            // so they must come after the arguments.
+            let ordered = match result {
+                Some(result) => arguments.iter().all(|&argument| argument < result),
+                None => true,
+            };
+            // Inlined locals must be reset on each iteration of a loop, and
+            // an initializer that needs emitting hasn't been at that point.
+            let reinitializable = !in_loop || {
+                let callee = &functions[callee];
+                callee
+                    .local_variables
+                    .iter()
+                    .all(|(_, local)| match local.init {
+                        Some(init) => callee.expressions[init].needs_pre_emit(),
+                        None => true,
+                    })
+            };
+            if !(ordered && reinitializable) {
+                sites.push(None);
+                continue;
+            }
+            if let Some(result) = result {
+                sites_by_result.insert(result.index(), sites.len());
+            }
+            sites.push(Some(Site {
+                callee,
+                arguments: arguments.clone(),
+                in_loop,
+            }));
+        }
+    });
+    if sites.iter().all(Option::is_none) {
+        return;
+    }
+
+    let mut inliner = Inliner {
+        expressions: Arena::new(),
+        local_variables: std::mem::take(&mut caller.local_variables),
+        named_expressions: NamedExpressions::default(),
+        module_map,
+    };
+    let caller_locals = inliner.local_variables.len();
+    let mut map = HandleMap::new();
+    let mut bodies: Vec<Option<Block>> = sites.iter().map(|_| None).collect();
+    for (index, (mut expression, span)) in std::mem::take(&mut caller.expressions)
+        .into_inner_with_spans()
+        .enumerate()
+    {
+        if let Some(&site_index) = sites_by_result.get(&index) {
+            let site = sites[site_index].as_mut().unwrap();
+            for argument in site.arguments.iter_mut() {
+                map.adjust(argument);
+            }
+            let (body, value) = inliner.copy(&functions[site.callee], site);
+            bodies[site_index] = Some(body);
+            map.push(value.unwrap());
+        } else {
+            module_map.adjust_expression(&mut expression, &map);
+            map.push(inliner.expressions.append(expression, span));
+        }
+    }
+    // Calls without results can go at the end.
+    for (site, body) in sites.iter_mut().zip(bodies.iter_mut()) {
+        let Some(ref mut site) = *site else {
+            continue;
+        };
+        if body.is_none() {
+            for argument in site.arguments.iter_mut() {
+                map.adjust(argument);
+            }
+            *body = Some(inliner.copy(&functions[site.callee], site).0);
+        }
+    }
+
+    caller.expressions = inliner.expressions;
+    caller.local_variables = inliner.local_variables;
+    for (_, local) in caller.local_variables.iter_mut().take(caller_locals) {
+        map.adjust_option(&mut local.init);
+    }
+    let named_expressions = std::mem::take(&mut caller.named_expressions);
+    for (mut handle, name) in named_expressions {
+        map.adjust(&mut handle);
+        caller.named_expressions.entry(handle).or_insert(name);
+    }
+    for (handle, name) in inliner.named_expressions {
+        caller.named_expressions.entry(handle).or_insert(name);
+    }
+
+    let function_map = FunctionMap { expressions: map };
+    function_map.adjust_body(caller, module_map);
+
+    let mut bodies = bodies.into_iter();
+    for_each_block(&mut caller.body, false, &mut |block, _| {
+        let is_site = |statement: &Statement| matches!(*statement, Statement::Call { function, .. } if inlinable.contains(function));
+        if !block.iter().any(is_site) {
+            return;
+        }
+        for (statement, span) in std::mem::take(block).span_into_iter() {
+            if is_site(&statement) {
+                if let Some(mut body) = bodies.next().unwrap() {
+                    block.append(&mut body);
+                    continue;
+                }
+            }
+            block.push(statement, span);
+        }
+    });
+}
+
+/// Copies callees into a caller being rebuilt.
+struct Inliner<'a> {
+    expressions: Arena<Expression>,
+    local_variables: Arena<LocalVariable>,
+    named_expressions: NamedExpressions,
+    module_map: &'a ModuleMap,
+}
+
+impl Inliner<'_> {
+    /// Append copies of `callee`'s expressions and local variables, and return
+    /// its body with the final `return` removed, and the copy of the value it
+    /// returned.
+    fn copy(&mut self, callee: &Function, site: &Site) -> (Block, Option<Handle<Expression>>) {
+        let locals: Vec<_> = callee
+            .local_variables
+            .iter()
+            .map(|(handle, local)| {
+                let local = LocalVariable {
+                    init: None,
+                    ..local.clone()
+                };
+                let span = callee.local_variables.get_span(handle);
+                self.local_variables.append(local, span)
+            })
+            .collect();
+
+        let mut map = HandleMap::new();
+        for (handle, expression) in callee.expressions.iter() {
+            let span = callee.expressions.get_span(handle);
+            let new = match *expression {
+                Expression::FunctionArgument(index) => site.arguments[index as usize],
+                Expression::LocalVariable(local) => self
+                    .expressions
+                    .append(Expression::LocalVariable(locals[local.index()]), span),
+                ref expression => {
+                    let mut expression = expression.clone();
+                    self.module_map.adjust_expression(&mut expression, &map);
+                    self.expressions.append(expression, span)
+                }
+            };
+            map.push(new);
+        }
+        for (&new, (_, local)) in locals.iter().zip(callee.local_variables.iter()) {
+            self.local_variables[new].init = local.init.map(|init| map.try_adjust(init).unwrap());
+        }
+        for (&handle, name) in callee.named_expressions.iter() {
+            let handle = map.try_adjust(handle).unwrap();
+            self.named_expressions
+                .entry(handle)
+                .or_insert_with(|| name.clone());
+        }
+
+        let mut body = callee.body.clone();
+        let mut value = None;
+        if let Some(&Statement::Return { value: returned }) = body.last() {
+            value = returned;
+            body.cull(body.len() - 1..);
+        }
+
+        // `adjust_body` wants a function, so lend it our arena.
+        let mut function = Function {
+            body,
+            expressions: std::mem::take(&mut self.expressions),
+            ..Function::default()
+        };
+        let function_map = FunctionMap { expressions: map };
+        function_map.adjust_body(&mut function, self.module_map);
+        self.expressions = function.expressions;
+        let value = value.map(|value| function_map.expressions.try_adjust(value).unwrap());
+
+        let mut block = Block::new();
+        if site.in_loop {
+            // Each iteration must see freshly initialized locals, as a call
+            // would.
+            for &local in locals.iter() {
+                let span = self.local_variables.get_span(local);
+                let value = match self.local_variables[local].init {
+                    Some(init) => init,
+                    None => {
+                        let ty = self.local_variables[local].ty;
+                        self.expressions.append(Expression::ZeroValue(ty), span)
+                    }
+                };
+                let pointer = self
+                    .expressions
+                    .append(Expression::LocalVariable(local), span);
+                block.push(Statement::Store { pointer, value }, span);
+            }
+        }
+        block.append(&mut function.body);
+        (block, value)
+    }
+}
+
+/// Return the functions called from `functions` and the entry points,
+/// skipping the functions in `skip`.
+fn called_functions(
+    functions: &mut Arena<Function>,
+    entry_points: &mut [crate::EntryPoint],
+    skip: Option<&HandleSet<Function>>,
+) -> HandleSet<Function> {
+    let mut called = HandleSet::for_arena(functions);
+    let mut note_calls = |block: &mut Block, _| {
+        for statement in block.iter() {
+            if let Statement::Call { function, .. } = *statement {
+                called.insert(function);
+            }
+        }
+    };
+    for (handle, function) in functions.iter_mut() {
+        if !skip.is_some_and(|skip| skip.contains(handle)) {
+            for_each_block(&mut function.body, false, &mut note_calls);
+        }
+    }
+    for entry_point in entry_points.iter_mut() {
+        for_each_block(&mut entry_point.function.body, false, &mut note_calls);
+    }
+    called
+}
+
+/// Remove the functions that were called before inlining, but no longer are.
+///
+/// Functions that were never called are kept, since the module may be a
+/// library.
+fn remove_uncalled(module: &mut Module, called_before: &HandleSet<Function>) {
+    let mut removed = HandleSet::for_arena(&module.functions);
+    let mut any_removed = false;
+    // Removing a function may leave the functions it called uncalled too.
+    loop {
+        let called = called_functions(
+            &mut module.functions,
+            &mut module.entry_points,
+            Some(&removed),
+        );
+        let mut changed = false;
+        for (handle, _) in module.functions.iter() {
+            if called_before.contains(handle)
+                && !called.contains(handle)
+                && !removed.contains(handle)
+            {
+                removed.insert(handle);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        any_removed = true;
+    }
+    if !any_removed {
+        return;
+    }
+
+    let mut kept = HandleSet::for_arena(&module.functions);
+    for (handle, _) in module.functions.iter() {
+        if !removed.contains(handle) {
+            kept.insert(handle);
+        }
+    }
+    let module_map = ModuleMap {
+        functions: HandleMap::from_set(kept),
+        ..super::identity_map(module)
+    };
+    let mut reused_named_expressions = NamedExpressions::default();
+    let mut adjust = |function: &mut Function| {
+        let function_map = FunctionMap {
+            expressions: HandleMap::offset(function.expressions.len(), 0),
+        };
+        function_map.compact(function, &module_map, &mut reused_named_expressions);
+    };
+    for entry_point in module.entry_points.iter_mut() {
+        adjust(&mut entry_point.function);
+    }
+    module.functions.retain_mut(|handle, function| {
+        let keep = module_map.functions.used(handle);
+        if keep {
+            adjust(function);
+        }
+        keep
+    });
+}
@@ -0,0 +1,164 @@
+/*!
+Optimization passes over a [`Module`].
+
+Naga's frontends produce IR that follows the source closely, and its backends
+translate that IR more or less statement by statement. [`optimize`] rewrites a
+module to do the same work with less code, which helps drivers whose own
+shader compilers do little optimization, like many GLES drivers.
+
+The passes are selected with [`Passes`]:
+
+- [`INLINE`] replaces calls to small functions with copies of their bodies,
+  and removes functions that are no longer called afterwards.
+
+- [`CONSTANT_FOLDING`] evaluates function-local expressions whose operands are
+  all constant with the [`ConstantEvaluator`], so that the backends write
+  their values instead.
+
+- [`DEAD_CODE`] removes statements that can never run, replaces `if`
+  statements whose conditions are constant with the branch taken, and removes
+  stores to local variables that are never read, along with the variables.
+
+They run in that order, since inlining makes arguments constant and folding
+makes conditions constant. The result is then [compacted] and validated again.
+
+[`INLINE`]: Passes::INLINE
+[`CONSTANT_FOLDING`]: Passes::CONSTANT_FOLDING
+[`DEAD_CODE`]: Passes::DEAD_CODE
+[`ConstantEvaluator`]: crate::proc::ConstantEvaluator
+[compacted]: crate::compact::compact
+*/
+
+mod dead_code;
+mod fold;
+mod inline;
+
+use crate::compact::{HandleMap, ModuleMap};
+use crate::valid::{ModuleInfo, ValidationError, Validator};
+use crate::{Block, Module, Statement, WithSpan};
+
+bitflags::bitflags! {
+    /// Optimization passes to run.
+    ///
+    /// The default value for `Passes` is `Passes::all()`.
+    #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+    #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub struct Passes: u8 {
+        /// Inline calls to functions no larger than
+        /// [`Options::inline_limit`].
+        const INLINE = 0x1;
+        /// Evaluate constant function-local expressions.
+        const CONSTANT_FOLDING = 0x2;
+        /// Remove unreachable statements, and stores that are never read.
+        const DEAD_CODE = 0x4;
+    }
+}
+
+impl Default for Passes {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Options for [`optimize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+#[cfg_attr(feature = "deserialize", serde(default))]
+pub struct Options {
+    /// The passes to run.
+    pub passes: Passes,
+    /// The largest function to inline, in expressions.
+    pub inline_limit: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            passes: Passes::all(),
+            inline_limit: 32,
+        }
+    }
+}
+
+/// Run the passes selected by `options` over `module`, and validate the result
+/// with `validator`.
+///
+/// Return the [`ModuleInfo`] for the optimized module. An error means that a
+/// pass produced invalid IR, and is a bug in Naga.
+///
+/// # Panics
+///
+/// If `module` has not passed validation, this may panic.
+pub fn optimize(
+    module: &mut Module,
+    options: &Options,
+    validator: &mut Validator,
+) -> Result<ModuleInfo, WithSpan<ValidationError>> {
+    if options.passes.contains(Passes::INLINE) {
+        inline::inline(module, options.inline_limit);
+    }
+    if options.passes.contains(Passes::CONSTANT_FOLDING) {
+        fold::fold(module);
+    }
+    if options.passes.contains(Passes::DEAD_CODE) {
+        dead_code::eliminate(module);
+    }
+    crate::compact::compact(module);
+    if options.passes.contains(Passes::DEAD_CODE) {
+        // Compaction drops the expressions referring to the variables whose
+        // stores were removed, so this has to come afterwards.
+        dead_code::remove_unused_locals(module);
+    }
+    validator.validate(module)
+}
+
+/// Return a map taking each handle in `module` to itself.
+fn identity_map(module: &Module) -> ModuleMap {
+    ModuleMap {
+        types: HandleMap::offset(module.types.len(), 0),
+        constants: HandleMap::offset(module.constants.len(), 0),
+        const_expressions: HandleMap::offset(module.const_expressions.len(), 0),
+        global_variables: HandleMap::offset(module.global_variables.len(), 0),
+        functions: HandleMap::offset(module.functions.len(), 0),
+    }
+}
+
+/// Call `f` on every block nested in `block`, and then on `block` itself.
+///
+/// The second argument to `f` is true if the block is inside a loop, taking
+/// `in_loop` to be true if `block` is.
+///
+/// Since nested blocks are visited before the blocks containing them, `f` can
+/// replace a block's statements without the replacements being visited.
+fn for_each_block(block: &mut Block, in_loop: bool, f: &mut impl FnMut(&mut Block, bool)) {
+    for statement in block.iter_mut() {
+        match *statement {
+            Statement::Block(ref mut block) => for_each_block(block, in_loop, f),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                for_each_block(accept, in_loop, f);
+                for_each_block(reject, in_loop, f);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases {
+                    for_each_block(&mut case.body, in_loop, f);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                for_each_block(body, true, f);
+                for_each_block(continuing, true, f);
+            }
+            _ => {}
+        }
+    }
+    f(block, in_loop);
+}
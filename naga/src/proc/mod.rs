@@ -416,7 +416,8 @@ impl super::AddressSpace {
         match self {
             crate::AddressSpace::Function
             | crate::AddressSpace::Private
-            | crate::AddressSpace::WorkGroup => Sa::LOAD | Sa::STORE,
+            | crate::AddressSpace::WorkGroup
+            | crate::AddressSpace::TaskPayload => Sa::LOAD | Sa::STORE,
             crate::AddressSpace::Uniform => Sa::LOAD,
             crate::AddressSpace::Storage { access } => access,
             crate::AddressSpace::Handle => Sa::LOAD,
@@ -467,6 +468,8 @@ impl super::MathFunction {
             Self::Pow => 2,
             // geometry
             Self::Dot => 2,
+            Self::Dot4I8Packed => 2,
+            Self::Dot4U8Packed => 2,
             Self::Outer => 2,
             Self::Cross => 2,
             Self::Distance => 2,
@@ -486,6 +489,7 @@ impl super::MathFunction {
             Self::Inverse => 1,
             Self::Transpose => 1,
             Self::Determinant => 1,
+            Self::QuantizeToF16 => 1,
             // bits
             Self::CountTrailingZeros => 1,
             Self::CountLeadingZeros => 1,
@@ -501,12 +505,18 @@ impl super::MathFunction {
             Self::Pack2x16snorm => 1,
             Self::Pack2x16unorm => 1,
             Self::Pack2x16float => 1,
+            Self::Pack4xI8 => 1,
+            Self::Pack4xU8 => 1,
+            Self::Pack4xI8Clamp => 1,
+            Self::Pack4xU8Clamp => 1,
             // data unpacking
             Self::Unpack4x8snorm => 1,
             Self::Unpack4x8unorm => 1,
             Self::Unpack2x16snorm => 1,
             Self::Unpack2x16unorm => 1,
             Self::Unpack2x16float => 1,
+            Self::Unpack4xI8 => 1,
+            Self::Unpack4xU8 => 1,
         }
     }
 }
@@ -620,18 +630,43 @@ impl super::ImageClass {
     pub const fn is_multisampled(self) -> bool {
         match self {
             crate::ImageClass::Sampled { multi, .. } | crate::ImageClass::Depth { multi } => multi,
-            crate::ImageClass::Storage { .. } => false,
+            crate::ImageClass::Storage { .. } | crate::ImageClass::External => false,
         }
     }
 
     pub const fn is_mipmapped(self) -> bool {
         match self {
             crate::ImageClass::Sampled { multi, .. } | crate::ImageClass::Depth { multi } => !multi,
-            crate::ImageClass::Storage { .. } => false,
+            crate::ImageClass::Storage { .. } | crate::ImageClass::External => false,
         }
     }
 }
 
+impl super::ResourceBinding {
+    /// Return the bindings of the second plane, the third plane and the
+    /// parameters uniform that an [`External`] image bound at `self` is
+    /// lowered into.
+    ///
+    /// [`External`]: crate::ImageClass::External
+    pub const fn external_texture_bindings(&self) -> [Self; 3] {
+        let Self { group, binding } = *self;
+        [
+            Self {
+                group,
+                binding: binding + 1,
+            },
+            Self {
+                group,
+                binding: binding + 2,
+            },
+            Self {
+                group,
+                binding: binding + 3,
+            },
+        ]
+    }
+}
+
 impl crate::Module {
     pub const fn to_ctx(&self) -> GlobalCtx<'_> {
         GlobalCtx {
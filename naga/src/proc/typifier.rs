@@ -490,6 +490,10 @@ impl<'a> ResolveContext<'a> {
                         },
                         size: crate::VectorSize::Quad,
                     },
+                    crate::ImageClass::External => Ti::Vector {
+                        scalar: crate::Scalar::F32,
+                        size: crate::VectorSize::Quad,
+                    },
                 }),
                 ref other => {
                     log::error!("Image type {:?}", other);
@@ -700,6 +704,8 @@ impl<'a> ResolveContext<'a> {
                                 format!("{fun:?}({other:?}, _)")
                             )),
                     },
+                    Mf::Dot4I8Packed => TypeResolution::Value(Ti::Scalar(crate::Scalar::I32)),
+                    Mf::Dot4U8Packed => TypeResolution::Value(Ti::Scalar(crate::Scalar::U32)),
                     Mf::Outer => {
                         let arg1 = arg1.ok_or_else(|| ResolveError::IncompatibleOperands(
                             format!("{fun:?}(_, None)")
@@ -739,7 +745,8 @@ impl<'a> ResolveContext<'a> {
                     Mf::Step |
                     Mf::SmoothStep |
                     Mf::Sqrt |
-                    Mf::InverseSqrt => res_arg.clone(),
+                    Mf::InverseSqrt |
+                    Mf::QuantizeToF16 => res_arg.clone(),
                     Mf::Transpose => match *res_arg.inner_with(types) {
                         Ti::Matrix {
                             columns,
@@ -806,7 +813,11 @@ impl<'a> ResolveContext<'a> {
                     Mf::Pack4x8unorm |
                     Mf::Pack2x16snorm |
                     Mf::Pack2x16unorm |
-                    Mf::Pack2x16float => TypeResolution::Value(Ti::Scalar(crate::Scalar::U32)),
+                    Mf::Pack2x16float |
+                    Mf::Pack4xI8 |
+                    Mf::Pack4xU8 |
+                    Mf::Pack4xI8Clamp |
+                    Mf::Pack4xU8Clamp => TypeResolution::Value(Ti::Scalar(crate::Scalar::U32)),
                     // data unpacking
                     Mf::Unpack4x8snorm |
                     Mf::Unpack4x8unorm => TypeResolution::Value(Ti::Vector {
@@ -819,6 +830,14 @@ impl<'a> ResolveContext<'a> {
                         size: crate::VectorSize::Bi,
                         scalar: crate::Scalar::F32
                     }),
+                    Mf::Unpack4xI8 => TypeResolution::Value(Ti::Vector {
+                        size: crate::VectorSize::Quad,
+                        scalar: crate::Scalar::I32
+                    }),
+                    Mf::Unpack4xU8 => TypeResolution::Value(Ti::Vector {
+                        size: crate::VectorSize::Quad,
+                        scalar: crate::Scalar::U32
+                    }),
                 }
             }
             crate::Expression::As {
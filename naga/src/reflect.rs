@@ -0,0 +1,305 @@
+/*!
+Reflection of a module's entry point interfaces.
+
+Given a validated [`Module`] and its [`ModuleInfo`], [`reflect`] describes
+each entry point's interface with the outside world: the resources it
+binds, its stage inputs and outputs, its workgroup size, and the range of
+push constants it uses. This is what an API needs to build bind group and
+pipeline layouts, or to check them against a shader.
+
+Only resources an entry point actually uses are listed for it, so the
+same global variable may appear in the reflection of some entry points
+and not others.
+
+[`Module`]: crate::Module
+[`ModuleInfo`]: crate::valid::ModuleInfo
+*/
+
+use crate::{
+    arena::{Handle, UniqueArena},
+    valid::{GlobalUse, ModuleInfo},
+};
+use std::{num::NonZeroU32, ops::Range};
+
+#[cfg(feature = "deserialize")]
+use serde::Deserialize;
+#[cfg(feature = "serialize")]
+use serde::Serialize;
+
+/// The interface of one entry point of a module.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+pub struct EntryPoint {
+    /// The entry point's name.
+    pub name: String,
+    /// The entry point's shader stage.
+    pub stage: crate::ShaderStage,
+    /// The workgroup size, for compute entry points.
+    ///
+    /// This is `[0; 3]` for other stages.
+    pub workgroup_size: [u32; 3],
+    /// The bound global variables this entry point uses, in the order they
+    /// appear in the module.
+    pub resources: Vec<Resource>,
+    /// The user-defined stage inputs, sorted by location.
+    ///
+    /// For vertex shaders, these are the vertex attributes.
+    pub inputs: Vec<Varying>,
+    /// The user-defined stage outputs, sorted by location.
+    ///
+    /// For fragment shaders, these are the color attachments.
+    pub outputs: Vec<Varying>,
+    /// The built-in values this entry point receives as inputs.
+    pub input_built_ins: Vec<crate::BuiltIn>,
+    /// The built-in values this entry point produces as outputs.
+    pub output_built_ins: Vec<crate::BuiltIn>,
+    /// The byte range of push constants this entry point uses, if any.
+    pub push_constants: Option<Range<u32>>,
+}
+
+/// A global variable bound to a resource, as used by an entry point.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+pub struct Resource {
+    /// The global variable in the module.
+    pub global: Handle<crate::GlobalVariable>,
+    /// The global variable's name, if it has one.
+    pub name: Option<String>,
+    /// The group and binding of the resource.
+    pub binding: crate::ResourceBinding,
+    /// The kind of resource bound.
+    pub ty: ResourceType,
+    /// The number of elements, if the global is a binding array.
+    ///
+    /// This is `None` for globals that aren't binding arrays, and for binding
+    /// arrays whose size is not fixed in the shader.
+    pub count: Option<NonZeroU32>,
+    /// Whether this is a binding array.
+    pub is_binding_array: bool,
+    /// How the entry point uses the resource.
+    pub usage: GlobalUse,
+}
+
+/// The kind of resource bound to a [`Resource`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+pub enum ResourceType {
+    /// A uniform or storage buffer.
+    Buffer {
+        /// The buffer's address space: [`Uniform`] or [`Storage`], which
+        /// carries the declared access.
+        ///
+        /// [`Uniform`]: crate::AddressSpace::Uniform
+        /// [`Storage`]: crate::AddressSpace::Storage
+        space: crate::AddressSpace,
+        /// The minimum size of the buffer binding, in bytes.
+        ///
+        /// If the buffer's type ends with a runtime-sized array, this includes
+        /// one element of that array.
+        min_size: u32,
+    },
+    /// A texture.
+    Image {
+        dim: crate::ImageDimension,
+        arrayed: bool,
+        class: crate::ImageClass,
+    },
+    /// A sampler.
+    Sampler { comparison: bool },
+    /// A ray tracing acceleration structure.
+    AccelerationStructure,
+}
+
+/// A user-defined stage input or output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+pub struct Varying {
+    /// The name of the argument, result or struct member, if any.
+    pub name: Option<String>,
+    /// The location of the varying.
+    pub location: u32,
+    /// The scalar type of the varying's components.
+    pub scalar: crate::Scalar,
+    /// The number of components, or `None` for a scalar.
+    pub size: Option<crate::VectorSize>,
+    /// The interpolation qualifier, if any.
+    pub interpolation: Option<crate::Interpolation>,
+    /// The sampling qualifier, if any.
+    pub sampling: Option<crate::Sampling>,
+    /// Whether this is the second source of a dual-source blend.
+    pub second_blend_source: bool,
+}
+
+/// Describe the interface of each entry point in `module`.
+///
+/// The result has one element per entry point, in the same order as
+/// [`Module::entry_points`].
+///
+/// `info` must be the result of validating `module`.
+///
+/// [`Module::entry_points`]: crate::Module::entry_points
+pub fn reflect(module: &crate::Module, info: &ModuleInfo) -> Vec<EntryPoint> {
+    module
+        .entry_points
+        .iter()
+        .enumerate()
+        .map(|(index, entry_point)| reflect_entry_point(module, info, index, entry_point))
+        .collect()
+}
+
+fn reflect_entry_point(
+    module: &crate::Module,
+    info: &ModuleInfo,
+    index: usize,
+    entry_point: &crate::EntryPoint,
+) -> EntryPoint {
+    let ep_info = info.get_entry_point(index);
+    let mut resources = Vec::new();
+    let mut push_constants = None;
+    for (handle, var) in module.global_variables.iter() {
+        let usage = ep_info[handle];
+        if usage.is_empty() {
+            continue;
+        }
+        if var.space == crate::AddressSpace::PushConstant {
+            push_constants = Some(0..module.types[var.ty].inner.size(module.to_ctx()));
+            continue;
+        }
+        let Some(ref binding) = var.binding else {
+            continue;
+        };
+
+        let (base, count, is_binding_array) = match module.types[var.ty].inner {
+            crate::TypeInner::BindingArray { base, size } => {
+                let count = match size {
+                    crate::ArraySize::Constant(count) => Some(count),
+                    crate::ArraySize::Dynamic => None,
+                };
+                (base, count, true)
+            }
+            _ => (var.ty, None, false),
+        };
+        let ty = match module.types[base].inner {
+            crate::TypeInner::Image {
+                dim,
+                arrayed,
+                class,
+            } => ResourceType::Image {
+                dim,
+                arrayed,
+                class,
+            },
+            crate::TypeInner::Sampler { comparison } => ResourceType::Sampler { comparison },
+            crate::TypeInner::AccelerationStructure => ResourceType::AccelerationStructure,
+            ref other => ResourceType::Buffer {
+                space: var.space,
+                min_size: other.size(module.to_ctx()),
+            },
+        };
+
+        resources.push(Resource {
+            global: handle,
+            name: var.name.clone(),
+            binding: binding.clone(),
+            ty,
+            count,
+            is_binding_array,
+            usage,
+        });
+    }
+
+    let mut inputs = Vec::new();
+    let mut input_built_ins = Vec::new();
+    for argument in entry_point.function.arguments.iter() {
+        collect_varyings(
+            &mut inputs,
+            &mut input_built_ins,
+            argument.name.as_ref(),
+            argument.binding.as_ref(),
+            argument.ty,
+            &module.types,
+        );
+    }
+    let mut outputs = Vec::new();
+    let mut output_built_ins = Vec::new();
+    if let Some(ref result) = entry_point.function.result {
+        collect_varyings(
+            &mut outputs,
+            &mut output_built_ins,
+            None,
+            result.binding.as_ref(),
+            result.ty,
+            &module.types,
+        );
+    }
+    inputs.sort_by_key(|varying| varying.location);
+    outputs.sort_by_key(|varying| (varying.location, varying.second_blend_source));
+
+    EntryPoint {
+        name: entry_point.name.clone(),
+        stage: entry_point.stage,
+        workgroup_size: entry_point.workgroup_size,
+        resources,
+        inputs,
+        outputs,
+        input_built_ins,
+        output_built_ins,
+        push_constants,
+    }
+}
+
+/// Add the varyings and built-ins for a value of type `ty` with `binding`
+/// to `varyings` and `built_ins`, looking through structs.
+fn collect_varyings(
+    varyings: &mut Vec<Varying>,
+    built_ins: &mut Vec<crate::BuiltIn>,
+    name: Option<&String>,
+    binding: Option<&crate::Binding>,
+    ty: Handle<crate::Type>,
+    types: &UniqueArena<crate::Type>,
+) {
+    let (scalar, size) = match types[ty].inner {
+        crate::TypeInner::Scalar(scalar) => (scalar, None),
+        crate::TypeInner::Vector { size, scalar } => (scalar, Some(size)),
+        crate::TypeInner::Struct { ref members, .. } => {
+            for member in members {
+                collect_varyings(
+                    varyings,
+                    built_ins,
+                    member.name.as_ref(),
+                    member.binding.as_ref(),
+                    member.ty,
+                    types,
+                );
+            }
+            return;
+        }
+        // Built-ins like `clip_distances` are arrays.
+        _ => (crate::Scalar::F32, None),
+    };
+
+    match binding {
+        Some(&crate::Binding::BuiltIn(built_in)) => built_ins.push(built_in),
+        Some(&crate::Binding::Location {
+            location,
+            second_blend_source,
+            interpolation,
+            sampling,
+        }) => varyings.push(Varying {
+            name: name.cloned(),
+            location,
+            scalar,
+            size,
+            interpolation,
+            sampling,
+            second_blend_source,
+        }),
+        // Validation ensures every entry point argument and result has a
+        // binding, or is a struct whose members do.
+        None => {}
+    }
+}
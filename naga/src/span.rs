@@ -125,10 +125,16 @@ pub struct SourceLocation {
 pub type SpanContext = (Span, String);
 
 /// Wrapper class for [`Error`], augmenting it with a list of [`SpanContext`]s.
+///
+/// Besides the spans the error is about, it may carry notes: secondary
+/// [`SpanContext`]s that explain how the error came about.
 #[derive(Debug, Clone)]
 pub struct WithSpan<E> {
     inner: E,
+    /// The spans, followed by the notes.
     spans: Vec<SpanContext>,
+    /// The index in `spans` of the first note.
+    notes_start: usize,
 }
 
 impl<E> fmt::Display for WithSpan<E>
@@ -165,6 +171,7 @@ impl<E> WithSpan<E> {
         Self {
             inner,
             spans: Vec::new(),
+            notes_start: 0,
         }
     }
 
@@ -180,7 +187,24 @@ impl<E> WithSpan<E> {
 
     /// Iterator over stored [`SpanContext`]s.
     pub fn spans(&self) -> impl ExactSizeIterator<Item = &SpanContext> {
-        self.spans.iter()
+        self.spans[..self.notes_start].iter()
+    }
+
+    /// Iterator over stored notes.
+    pub fn notes(&self) -> impl ExactSizeIterator<Item = &SpanContext> {
+        self.spans[self.notes_start..].iter()
+    }
+
+    /// Add a note: a secondary span with description, explaining how the
+    /// error came about.
+    pub fn with_note<S>(mut self, span: Span, description: S) -> Self
+    where
+        S: ToString,
+    {
+        if span.is_defined() {
+            self.spans.push((span, description.to_string()));
+        }
+        self
     }
 
     /// Add a new span with description.
@@ -189,7 +213,9 @@ impl<E> WithSpan<E> {
         S: ToString,
     {
         if span.is_defined() {
-            self.spans.push((span, description.to_string()));
+            self.spans
+                .insert(self.notes_start, (span, description.to_string()));
+            self.notes_start += 1;
         }
         self
     }
@@ -214,6 +240,7 @@ impl<E> WithSpan<E> {
         WithSpan {
             inner: self.inner.into(),
             spans: self.spans,
+            notes_start: self.notes_start,
         }
     }
 
@@ -224,13 +251,19 @@ impl<E> WithSpan<E> {
         F: FnOnce(E) -> WithSpan<E2>,
     {
         let mut res = func(self.inner);
-        res.spans.extend(self.spans);
+        let mut spans = self.spans;
+        let notes = spans.split_off(self.notes_start);
+        let res_notes = res.spans.split_off(res.notes_start);
+        res.spans.extend(spans);
+        res.notes_start = res.spans.len();
+        res.spans.extend(res_notes);
+        res.spans.extend(notes);
         res
     }
 
     /// Return a [`SourceLocation`] for our first span, if we have one.
     pub fn location(&self, source: &str) -> Option<SourceLocation> {
-        if self.spans.is_empty() {
+        if self.notes_start == 0 {
             return None;
         }
 
@@ -249,6 +282,9 @@ impl<E> WithSpan<E> {
                     .map(|&(span, ref desc)| {
                         Label::primary((), span.to_range().unwrap()).with_message(desc.to_owned())
                     })
+                    .chain(self.notes().map(|&(span, ref desc)| {
+                        Label::secondary((), span.to_range().unwrap()).with_message(desc.to_owned())
+                    }))
                     .collect(),
             )
             .with_notes({
@@ -7,7 +7,7 @@ Figures out the following properties:
 !*/
 
 use super::{ExpressionError, FunctionError, ModuleInfo, ShaderStages, ValidationFlags};
-use crate::span::{AddSpan as _, WithSpan};
+use crate::span::{AddSpan as _, Span, SpanContext, WithSpan};
 use crate::{
     arena::{Arena, Handle},
     proc::{ResolveContext, TypeResolution},
@@ -201,6 +201,52 @@ struct Sampling {
     sampler: GlobalOrArgument,
 }
 
+/// The output types a function and its callees pass to [`MeshFunction`]s.
+///
+/// [`MeshFunction`]: crate::MeshFunction
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct MeshShaderInfo {
+    /// The type of the values passed to [`MeshFunction::SetVertex`].
+    ///
+    /// [`MeshFunction::SetVertex`]: crate::MeshFunction::SetVertex
+    pub vertex_type: Option<Handle<crate::Type>>,
+    /// The type of the values passed to [`MeshFunction::SetPrimitive`].
+    ///
+    /// [`MeshFunction::SetPrimitive`]: crate::MeshFunction::SetPrimitive
+    pub primitive_type: Option<Handle<crate::Type>>,
+}
+
+impl MeshShaderInfo {
+    /// Note that `ty` is passed where `slot` expects values of one type.
+    fn note(
+        slot: &mut Option<Handle<crate::Type>>,
+        ty: Handle<crate::Type>,
+    ) -> Result<(), FunctionError> {
+        match *slot {
+            Some(expected) if expected != ty => Err(FunctionError::MeshOutputTypeMismatch {
+                expected,
+                found: ty,
+            }),
+            _ => {
+                *slot = Some(ty);
+                Ok(())
+            }
+        }
+    }
+
+    fn merge(&mut self, other: &Self) -> Result<(), FunctionError> {
+        if let Some(ty) = other.vertex_type {
+            Self::note(&mut self.vertex_type, ty)?;
+        }
+        if let Some(ty) = other.primitive_type {
+            Self::note(&mut self.primitive_type, ty)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -263,6 +309,9 @@ pub struct FunctionInfo {
 
     /// Indicates that the function is using dual source blending.
     pub dual_source_blending: bool,
+
+    /// The mesh shader outputs written by this function and its callees.
+    pub mesh_shader_info: MeshShaderInfo,
 }
 
 impl FunctionInfo {
@@ -310,6 +359,48 @@ pub enum UniformityDisruptor {
     Discard,
 }
 
+/// Work out the disruptor of the blocks nested in the statement at `span`,
+/// given the disruptor of the statement itself and the non-uniform result
+/// its control flow depends on, if any.
+///
+/// Also returns the trail of spans explaining how the nested blocks came to
+/// be non-uniform, starting from the non-uniform value.
+fn nested_disruptor(
+    disruptor: Option<UniformityDisruptor>,
+    trail: &[SpanContext],
+    condition_nur: NonUniformResult,
+    span: Span,
+    expression_arena: &Arena<crate::Expression>,
+) -> (Option<UniformityDisruptor>, Vec<SpanContext>) {
+    if disruptor.is_some() {
+        let mut trail = trail.to_vec();
+        trail.push((span, "which carries it into this block".to_string()));
+        (disruptor, trail)
+    } else if let Some(source) = condition_nur {
+        let trail = vec![
+            (
+                expression_arena.get_span(source),
+                "this value is non-uniform".to_string(),
+            ),
+            (span, "and control flow depends on it here".to_string()),
+        ];
+        (Some(UniformityDisruptor::Expression(source)), trail)
+    } else {
+        (None, Vec::new())
+    }
+}
+
+/// The trail explaining that the control flow after the statement at `span`
+/// is non-uniform because of the early `exit` within it.
+fn exit_trail(exit: UniformityDisruptor, span: Span) -> Vec<SpanContext> {
+    let description = match exit {
+        UniformityDisruptor::Return => "this may return early in non-uniform control flow",
+        UniformityDisruptor::Discard => "this may discard in non-uniform control flow",
+        UniformityDisruptor::Expression(_) => unreachable!(),
+    };
+    vec![(span, description.to_string())]
+}
+
 impl FunctionInfo {
     /// Adds a value-type reference to an expression.
     #[must_use]
@@ -411,6 +502,10 @@ impl FunctionInfo {
             *mine |= *other;
         }
 
+        self.mesh_shader_info
+            .merge(&callee.mesh_shader_info)
+            .map_err(|error| error.with_span())?;
+
         Ok(FunctionUniformity {
             result: callee.uniformity.clone(),
             exit: if callee.may_kill {
@@ -570,8 +665,9 @@ impl FunctionInfo {
                 let uniform = match var.space {
                     // local data is non-uniform
                     As::Function | As::Private => false,
-                    // workgroup memory is exclusively accessed by the group
-                    As::WorkGroup => true,
+                    // workgroup memory is exclusively accessed by the group,
+                    // and the task payload is shared by it
+                    As::WorkGroup | As::TaskPayload => true,
                     // uniform data
                     As::Uniform | As::PushConstant => true,
                     // storage data is only uniform when read-only
@@ -600,6 +696,7 @@ impl FunctionInfo {
                 offset: _,
                 level,
                 depth_ref,
+                clamp_to_edge: _,
             } => {
                 let image_storage = GlobalOrArgument::from_expression(expression_arena, image)?;
                 let sampler_storage = GlobalOrArgument::from_expression(expression_arena, sampler)?;
@@ -760,19 +857,23 @@ impl FunctionInfo {
     /// The parent control flow is uniform if `disruptor.is_none()`.
     ///
     /// Returns a `NonUniformControlFlow` error if any of the expressions in the block
-    /// require uniformity, but the current flow is non-uniform.
+    /// require uniformity, but the current flow is non-uniform. The error is
+    /// labelled with the spans in `trail`, which explain how the control flow
+    /// came to be non-uniform.
     #[allow(clippy::or_fun_call)]
     fn process_block(
         &mut self,
         statements: &crate::Block,
         other_functions: &[FunctionInfo],
         mut disruptor: Option<UniformityDisruptor>,
+        trail: &[SpanContext],
         expression_arena: &Arena<crate::Expression>,
     ) -> Result<FunctionUniformity, WithSpan<FunctionError>> {
         use crate::Statement as S;
 
+        let mut trail = trail.to_vec();
         let mut combined_uniformity = FunctionUniformity::new();
-        for statement in statements {
+        for (statement, &span) in statements.span_iter() {
             let uniformity = match *statement {
                 S::Emit(ref range) => {
                     let mut requirements = UniformityRequirements::empty();
@@ -784,8 +885,18 @@ impl FunctionInfo {
                             && !req.is_empty()
                         {
                             if let Some(cause) = disruptor {
-                                return Err(FunctionError::NonUniformControlFlow(req, expr, cause)
-                                    .with_span_handle(expr, expression_arena));
+                                let error = WithSpan::new(FunctionError::NonUniformControlFlow(
+                                    req, expr, cause,
+                                ))
+                                .with_span(
+                                    expression_arena.get_span(expr),
+                                    "this operation requires uniform control flow",
+                                );
+                                return Err(trail
+                                    .into_iter()
+                                    .fold(error, |error, (span, description)| {
+                                        error.with_note(span, description)
+                                    }));
                             }
                         }
                         requirements |= req;
@@ -844,7 +955,7 @@ impl FunctionInfo {
                     }
                 }
                 S::Block(ref b) => {
-                    self.process_block(b, other_functions, disruptor, expression_arena)?
+                    self.process_block(b, other_functions, disruptor, &trail, expression_arena)?
                 }
                 S::If {
                     condition,
@@ -852,18 +963,20 @@ impl FunctionInfo {
                     ref reject,
                 } => {
                     let condition_nur = self.add_ref(condition);
-                    let branch_disruptor =
-                        disruptor.or(condition_nur.map(UniformityDisruptor::Expression));
+                    let (branch_disruptor, branch_trail) =
+                        nested_disruptor(disruptor, &trail, condition_nur, span, expression_arena);
                     let accept_uniformity = self.process_block(
                         accept,
                         other_functions,
                         branch_disruptor,
+                        &branch_trail,
                         expression_arena,
                     )?;
                     let reject_uniformity = self.process_block(
                         reject,
                         other_functions,
                         branch_disruptor,
+                        &branch_trail,
                         expression_arena,
                     )?;
                     accept_uniformity | reject_uniformity
@@ -873,22 +986,30 @@ impl FunctionInfo {
                     ref cases,
                 } => {
                     let selector_nur = self.add_ref(selector);
-                    let branch_disruptor =
-                        disruptor.or(selector_nur.map(UniformityDisruptor::Expression));
+                    let (branch_disruptor, branch_trail) =
+                        nested_disruptor(disruptor, &trail, selector_nur, span, expression_arena);
                     let mut uniformity = FunctionUniformity::new();
                     let mut case_disruptor = branch_disruptor;
+                    let mut case_trail = branch_trail.clone();
                     for case in cases.iter() {
                         let case_uniformity = self.process_block(
                             &case.body,
                             other_functions,
                             case_disruptor,
+                            &case_trail,
                             expression_arena,
                         )?;
-                        case_disruptor = if case.fall_through {
-                            case_disruptor.or(case_uniformity.exit_disruptor())
+                        if case.fall_through {
+                            if case_disruptor.is_none() {
+                                if let Some(exit) = case_uniformity.exit_disruptor() {
+                                    case_trail = exit_trail(exit, span);
+                                }
+                            }
+                            case_disruptor = case_disruptor.or(case_uniformity.exit_disruptor());
                         } else {
-                            branch_disruptor
-                        };
+                            case_disruptor = branch_disruptor;
+                            case_trail = branch_trail.clone();
+                        }
                         uniformity = uniformity | case_uniformity;
                     }
                     uniformity
@@ -898,13 +1019,26 @@ impl FunctionInfo {
                     ref continuing,
                     break_if,
                 } => {
-                    let body_uniformity =
-                        self.process_block(body, other_functions, disruptor, expression_arena)?;
-                    let continuing_disruptor = disruptor.or(body_uniformity.exit_disruptor());
+                    let (body_disruptor, body_trail) =
+                        nested_disruptor(disruptor, &trail, None, span, expression_arena);
+                    let body_uniformity = self.process_block(
+                        body,
+                        other_functions,
+                        body_disruptor,
+                        &body_trail,
+                        expression_arena,
+                    )?;
+                    let (continuing_disruptor, continuing_trail) = match body_uniformity
+                        .exit_disruptor()
+                    {
+                        Some(exit) if disruptor.is_none() => (Some(exit), exit_trail(exit, span)),
+                        _ => (body_disruptor, body_trail),
+                    };
                     let continuing_uniformity = self.process_block(
                         continuing,
                         other_functions,
                         continuing_disruptor,
+                        &continuing_trail,
                         expression_arena,
                     )?;
                     if let Some(expr) = break_if {
@@ -982,8 +1116,43 @@ impl FunctionInfo {
                     }
                     FunctionUniformity::new()
                 }
+                S::MeshFunction(ref fun) => {
+                    match *fun {
+                        crate::MeshFunction::SetMeshOutputs {
+                            vertex_count,
+                            primitive_count,
+                        } => {
+                            let _ = self.add_ref(vertex_count);
+                            let _ = self.add_ref(primitive_count);
+                        }
+                        crate::MeshFunction::SetVertex { index, value }
+                        | crate::MeshFunction::SetPrimitive { index, value } => {
+                            let _ = self.add_ref(index);
+                            let _ = self.add_ref(value);
+                            // Values of other types are rejected by the
+                            // function validator.
+                            if let Some(ty) = self.expressions[value.index()].ty.handle() {
+                                let slot = match *fun {
+                                    crate::MeshFunction::SetVertex { .. } => {
+                                        &mut self.mesh_shader_info.vertex_type
+                                    }
+                                    _ => &mut self.mesh_shader_info.primitive_type,
+                                };
+                                MeshShaderInfo::note(slot, ty).map_err(|error| {
+                                    error.with_span_handle(value, expression_arena)
+                                })?;
+                            }
+                        }
+                    }
+                    FunctionUniformity::new()
+                }
             };
 
+            if disruptor.is_none() {
+                if let Some(exit) = uniformity.exit_disruptor() {
+                    trail = exit_trail(exit, span);
+                }
+            }
             disruptor = disruptor.or(uniformity.exit_disruptor());
             combined_uniformity = combined_uniformity | uniformity;
         }
@@ -1023,6 +1192,7 @@ impl ModuleInfo {
             expressions: vec![ExpressionInfo::new(); fun.expressions.len()].into_boxed_slice(),
             sampling: crate::FastHashSet::default(),
             dual_source_blending: false,
+            mesh_shader_info: MeshShaderInfo::default(),
         };
         let resolve_context =
             ResolveContext::with_locals(module, &fun.local_variables, &fun.arguments);
@@ -1046,7 +1216,8 @@ impl ModuleInfo {
             }
         }
 
-        let uniformity = info.process_block(&fun.body, &self.functions, None, &fun.expressions)?;
+        let uniformity =
+            info.process_block(&fun.body, &self.functions, None, &[], &fun.expressions)?;
         info.uniformity = uniformity.result;
         info.may_kill = uniformity.exit.contains(ExitFlags::MAY_KILL);
 
@@ -1136,6 +1307,7 @@ fn uniform_control_flow() {
         expressions: vec![ExpressionInfo::new(); expressions.len()].into_boxed_slice(),
         sampling: crate::FastHashSet::default(),
         dual_source_blending: false,
+        mesh_shader_info: MeshShaderInfo::default(),
     };
     let resolve_context = ResolveContext {
         constants: &Arena::new(),
@@ -1181,6 +1353,7 @@ fn uniform_control_flow() {
             &vec![stmt_emit1, stmt_if_uniform].into(),
             &[],
             None,
+            &[],
             &expressions
         ),
         Ok(FunctionUniformity {
@@ -1212,6 +1385,7 @@ fn uniform_control_flow() {
             &vec![stmt_emit2, stmt_if_non_uniform].into(),
             &[],
             None,
+            &[],
             &expressions,
         );
         if DISABLE_UNIFORMITY_REQ_FOR_FRAGMENT_STAGE {
@@ -1240,6 +1414,7 @@ fn uniform_control_flow() {
             &vec![stmt_emit3, stmt_return_non_uniform].into(),
             &[],
             Some(UniformityDisruptor::Return),
+            &[],
             &expressions
         ),
         Ok(FunctionUniformity {
@@ -1267,6 +1442,7 @@ fn uniform_control_flow() {
             &vec![stmt_emit4, stmt_assign, stmt_kill, stmt_return_pointer].into(),
             &[],
             Some(UniformityDisruptor::Discard),
+            &[],
             &expressions
         ),
         Ok(FunctionUniformity {
@@ -1279,3 +1455,159 @@ fn uniform_control_flow() {
     );
     assert_eq!(info[non_uniform_global], GlobalUse::READ | GlobalUse::WRITE);
 }
+
+#[test]
+fn uniformity_error_notes() {
+    use crate::{Expression as E, Statement as S};
+
+    let source = "\
+if flag != 0u {
+    loop {
+        workgroupBarrier();
+    }
+}";
+    let span_of = |text: &str| {
+        let start = source.find(text).unwrap();
+        Span::new(start as u32, (start + text.len()) as u32)
+    };
+    let if_span = span_of(source);
+    let flag_span = span_of("flag");
+    let loop_span = span_of("loop {\n        workgroupBarrier();\n    }");
+    let barrier_span = span_of("workgroupBarrier()");
+
+    let mut global_var_arena = Arena::new();
+    let mut type_arena = crate::UniqueArena::new();
+    let ty = type_arena.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Scalar(crate::Scalar::U32),
+        },
+        Default::default(),
+    );
+    let non_uniform_global = global_var_arena.append(
+        crate::GlobalVariable {
+            name: None,
+            init: None,
+            ty,
+            space: crate::AddressSpace::Private,
+            binding: None,
+        },
+        Default::default(),
+    );
+
+    let mut expressions = Arena::new();
+    let non_uniform_expr = expressions.append(E::GlobalVariable(non_uniform_global), flag_span);
+    let condition_expr = expressions.append(
+        E::Load {
+            pointer: non_uniform_expr,
+        },
+        flag_span,
+    );
+    let emit_range_condition = expressions.range_from(0);
+    let constant_expr = expressions.append(E::Literal(crate::Literal::U32(0)), barrier_span);
+    let emit_range_constant = expressions.range_from(2);
+
+    let mut info = FunctionInfo {
+        flags: ValidationFlags::all(),
+        available_stages: ShaderStages::all(),
+        uniformity: Uniformity::new(),
+        may_kill: false,
+        sampling_set: crate::FastHashSet::default(),
+        global_uses: vec![GlobalUse::empty(); global_var_arena.len()].into_boxed_slice(),
+        expressions: vec![ExpressionInfo::new(); expressions.len()].into_boxed_slice(),
+        sampling: crate::FastHashSet::default(),
+        dual_source_blending: false,
+        mesh_shader_info: MeshShaderInfo::default(),
+    };
+    let resolve_context = ResolveContext {
+        constants: &Arena::new(),
+        types: &type_arena,
+        special_types: &crate::SpecialTypes::default(),
+        global_vars: &global_var_arena,
+        local_vars: &Arena::new(),
+        functions: &Arena::new(),
+        arguments: &[],
+    };
+    for (handle, _) in expressions.iter() {
+        info.process_expression(
+            handle,
+            &expressions,
+            &[],
+            &resolve_context,
+            super::Capabilities::empty(),
+        )
+        .unwrap();
+    }
+    // Stand in for an operation that requires uniform control flow.
+    info.expressions[constant_expr.index()]
+        .uniformity
+        .requirements = UniformityRequirements::WORK_GROUP_BARRIER;
+
+    let mut loop_body = crate::Block::new();
+    loop_body.push(S::Emit(emit_range_constant), barrier_span);
+    let mut accept = crate::Block::new();
+    accept.push(
+        S::Loop {
+            body: loop_body,
+            continuing: crate::Block::new(),
+            break_if: None,
+        },
+        loop_span,
+    );
+    let mut block = crate::Block::new();
+    block.push(S::Emit(emit_range_condition), flag_span);
+    block.push(
+        S::If {
+            condition: condition_expr,
+            accept,
+            reject: crate::Block::new(),
+        },
+        if_span,
+    );
+
+    let error = info
+        .process_block(&block, &[], None, &[], &expressions)
+        .unwrap_err();
+    assert_eq!(
+        *error.as_inner(),
+        FunctionError::NonUniformControlFlow(
+            UniformityRequirements::WORK_GROUP_BARRIER,
+            constant_expr,
+            UniformityDisruptor::Expression(non_uniform_expr),
+        )
+    );
+    assert_eq!(
+        error.spans().cloned().collect::<Vec<_>>(),
+        [(
+            barrier_span,
+            "this operation requires uniform control flow".to_string()
+        )]
+    );
+    assert_eq!(
+        error.notes().cloned().collect::<Vec<_>>(),
+        [
+            (flag_span, "this value is non-uniform".to_string()),
+            (if_span, "and control flow depends on it here".to_string()),
+            (loop_span, "which carries it into this block".to_string()),
+        ]
+    );
+    // Rendered, the notes lead from the non-uniform value to the operation.
+    assert_eq!(
+        error.emit_to_string(source),
+        concat!(
+            "error: Required uniformity of control flow for UniformityRequirements(WORK_GROUP_BARRIER) in [3] is not fulfilled because of Expression([1])\n",
+            "  ┌─ wgsl:3:9\n",
+            "  │    \n",
+            "1 │ ╭   if flag != 0u {\n",
+            "  │        ---- this value is non-uniform\n",
+            "2 │ │ ╭     loop {\n",
+            "3 │ │ │         workgroupBarrier();\n",
+            "  │ │ │         ^^^^^^^^^^^^^^^^^^ this operation requires uniform control flow\n",
+            "4 │ │ │     }\n",
+            "  │ │ ╰─────' which carries it into this block\n",
+            "5 │ │   }\n",
+            "  │ ╰───' and control flow depends on it here\n",
+            "\n",
+        )
+    );
+}
@@ -98,6 +98,10 @@ pub enum ExpressionError {
     InvalidDepthSampleLevel,
     #[error("Gather level can only be Zero")]
     InvalidGatherLevel,
+    #[error("Sampling with clamp-to-edge requires a non-arrayed 2D float image, level Zero, and no offset, depth reference or gather")]
+    InvalidSampleClampToEdge,
+    #[error("External textures can only be sampled with clamp-to-edge")]
+    ExternalTextureNeedsClampToEdge,
     #[error("Gather component {0:?} doesn't exist in the image")]
     InvalidGatherComponent(crate::SwizzleComponent),
     #[error("Gather can't be done for image dimension {0:?}")]
@@ -385,6 +389,7 @@ impl super::Validator {
                 offset,
                 level,
                 depth_ref,
+                clamp_to_edge,
             } => {
                 // check the validity of expressions
                 let image_ty = Self::global_var_ty(module, function, image)?;
@@ -430,8 +435,33 @@ impl super::Validator {
                         multi: false,
                     } if gather.is_some() => false,
                     crate::ImageClass::Depth { multi: false } => true,
+                    crate::ImageClass::External => false,
                     _ => return Err(ExpressionError::InvalidImageClass(class)),
                 };
+
+                // check `textureSampleBaseClampToEdge` properties
+                if clamp_to_edge {
+                    let float_2d = match class {
+                        crate::ImageClass::Sampled {
+                            kind: crate::ScalarKind::Float,
+                            multi: false,
+                        }
+                        | crate::ImageClass::External => {
+                            dim == crate::ImageDimension::D2 && array_index.is_none()
+                        }
+                        _ => false,
+                    };
+                    if !float_2d
+                        || gather.is_some()
+                        || offset.is_some()
+                        || depth_ref.is_some()
+                        || !matches!(level, crate::SampleLevel::Zero)
+                    {
+                        return Err(ExpressionError::InvalidSampleClampToEdge);
+                    }
+                } else if class == crate::ImageClass::External {
+                    return Err(ExpressionError::ExternalTextureNeedsClampToEdge);
+                }
                 if comparison != depth_ref.is_some() || (comparison && !image_depth) {
                     return Err(ExpressionError::ComparisonSamplingMismatch {
                         image: class,
@@ -1039,6 +1069,18 @@ impl super::Validator {
                             _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
                         }
                     }
+                    Mf::QuantizeToF16 => {
+                        if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
+                            return Err(ExpressionError::WrongArgumentCount(fun));
+                        }
+                        match *arg_ty {
+                            Ti::Scalar(Sc::F32)
+                            | Ti::Vector {
+                                scalar: Sc::F32, ..
+                            } => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                    }
                     Mf::Sign => {
                         if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
                             return Err(ExpressionError::WrongArgumentCount(fun));
@@ -1148,6 +1190,26 @@ impl super::Validator {
                             ));
                         }
                     }
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let arg1_ty = match (arg1_ty, arg2_ty, arg3_ty) {
+                            (Some(ty1), None, None) => ty1,
+                            _ => return Err(ExpressionError::WrongArgumentCount(fun)),
+                        };
+                        match *arg_ty {
+                            Ti::Scalar(Sc::U32) => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                        match *arg1_ty {
+                            Ti::Scalar(Sc::U32) => {}
+                            _ => {
+                                return Err(ExpressionError::InvalidArgumentType(
+                                    fun,
+                                    1,
+                                    arg1.unwrap(),
+                                ))
+                            }
+                        }
+                    }
                     Mf::Outer | Mf::Cross | Mf::Reflect => {
                         let arg1_ty = match (arg1_ty, arg2_ty, arg3_ty) {
                             (Some(ty1), None, None) => ty1,
@@ -1461,6 +1523,22 @@ impl super::Validator {
                             _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
                         }
                     }
+                    Mf::Pack4xI8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8 | Mf::Pack4xU8Clamp => {
+                        if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
+                            return Err(ExpressionError::WrongArgumentCount(fun));
+                        }
+                        let scalar = match fun {
+                            Mf::Pack4xI8 | Mf::Pack4xI8Clamp => Sc::I32,
+                            _ => Sc::U32,
+                        };
+                        match *arg_ty {
+                            Ti::Vector {
+                                size: crate::VectorSize::Quad,
+                                scalar: arg_scalar,
+                            } if arg_scalar == scalar => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                    }
                     Mf::Pack4x8snorm | Mf::Pack4x8unorm => {
                         if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
                             return Err(ExpressionError::WrongArgumentCount(fun));
@@ -1480,7 +1558,9 @@ impl super::Validator {
                     | Mf::Unpack2x16snorm
                     | Mf::Unpack2x16unorm
                     | Mf::Unpack4x8snorm
-                    | Mf::Unpack4x8unorm => {
+                    | Mf::Unpack4x8unorm
+                    | Mf::Unpack4xI8
+                    | Mf::Unpack4xU8 => {
                         if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
                             return Err(ExpressionError::WrongArgumentCount(fun));
                         }
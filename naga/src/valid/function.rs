@@ -155,6 +155,15 @@ pub enum FunctionError {
     WorkgroupUniformLoadExpressionMismatch(Handle<crate::Expression>),
     #[error("The expression {0:?} is not valid as a WorkGroupUniformLoad argument. It should be a Pointer in Workgroup address space")]
     WorkgroupUniformLoadInvalidPointer(Handle<crate::Expression>),
+    #[error("Mesh shader operand {0:?} must be a `u32` scalar")]
+    InvalidMeshFunctionOperand(Handle<crate::Expression>),
+    #[error("Mesh shader output value {0:?} must have a named type")]
+    InvalidMeshOutputValue(Handle<crate::Expression>),
+    #[error("Mesh shader outputs are written with {found:?}, but {expected:?} was used elsewhere")]
+    MeshOutputTypeMismatch {
+        expected: Handle<crate::Type>,
+        found: Handle<crate::Type>,
+    },
 }
 
 bitflags::bitflags! {
@@ -614,7 +623,9 @@ impl super::Validator {
                     finished = true;
                 }
                 S::Barrier(_) => {
-                    stages &= super::ShaderStages::COMPUTE;
+                    stages &= super::ShaderStages::COMPUTE
+                        | super::ShaderStages::TASK
+                        | super::ShaderStages::MESH;
                 }
                 S::Store { pointer, value } => {
                     let mut current = pointer;
@@ -818,7 +829,9 @@ impl super::Validator {
                     self.validate_atomic(pointer, fun, value, result, context)?;
                 }
                 S::WorkGroupUniformLoad { pointer, result } => {
-                    stages &= super::ShaderStages::COMPUTE;
+                    stages &= super::ShaderStages::COMPUTE
+                        | super::ShaderStages::TASK
+                        | super::ShaderStages::MESH;
                     let pointer_inner =
                         context.resolve_type(pointer, &self.valid_expression_set)?;
                     match *pointer_inner {
@@ -904,6 +917,36 @@ impl super::Validator {
                         crate::RayQueryFunction::Terminate => {}
                     }
                 }
+                S::MeshFunction(ref fun) => {
+                    stages &= super::ShaderStages::MESH;
+                    let (operands, value) = match *fun {
+                        crate::MeshFunction::SetMeshOutputs {
+                            vertex_count,
+                            primitive_count,
+                        } => ([vertex_count, primitive_count], None),
+                        crate::MeshFunction::SetVertex { index, value }
+                        | crate::MeshFunction::SetPrimitive { index, value } => {
+                            ([index, index], Some(value))
+                        }
+                    };
+                    for operand in operands {
+                        match *context.resolve_type(operand, &self.valid_expression_set)? {
+                            Ti::Scalar(crate::Scalar::U32) => {}
+                            _ => {
+                                return Err(FunctionError::InvalidMeshFunctionOperand(operand)
+                                    .with_span_handle(operand, context.expressions))
+                            }
+                        }
+                    }
+                    if let Some(value) = value {
+                        // Make sure the value is in scope.
+                        context.resolve_type(value, &self.valid_expression_set)?;
+                        if context.info[value].ty.handle().is_none() {
+                            return Err(FunctionError::InvalidMeshOutputValue(value)
+                                .with_span_handle(value, context.expressions));
+                        }
+                    }
+                }
             }
         }
         Ok(BlockInfo { stages, finished })
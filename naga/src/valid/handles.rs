@@ -155,6 +155,13 @@ impl super::Validator {
         };
 
         for entry_point in entry_points.iter() {
+            if let Some(ref mesh_info) = entry_point.mesh_info {
+                validate_type(mesh_info.vertex_output_type)?;
+                validate_type(mesh_info.primitive_output_type)?;
+            }
+            if let Some(task_payload) = entry_point.task_payload {
+                task_payload.check_valid_for(global_variables)?;
+            }
             validate_function(None, &entry_point.function)?;
         }
 
@@ -168,6 +175,9 @@ impl super::Validator {
         if let Some(ty) = special_types.ray_intersection {
             validate_type(ty)?;
         }
+        if let Some(ty) = special_types.external_texture_params {
+            validate_type(ty)?;
+        }
 
         Ok(())
     }
@@ -286,6 +296,7 @@ impl super::Validator {
                 offset,
                 level,
                 depth_ref,
+                clamp_to_edge: _,
             } => {
                 if let Some(offset) = offset {
                     validate_const_expr(offset)?;
@@ -535,6 +546,23 @@ impl super::Validator {
                 }
                 Ok(())
             }
+            crate::Statement::MeshFunction(ref fun) => {
+                match *fun {
+                    crate::MeshFunction::SetMeshOutputs {
+                        vertex_count,
+                        primitive_count,
+                    } => {
+                        validate_expr(vertex_count)?;
+                        validate_expr(primitive_count)?;
+                    }
+                    crate::MeshFunction::SetVertex { index, value }
+                    | crate::MeshFunction::SetPrimitive { index, value } => {
+                        validate_expr(index)?;
+                        validate_expr(value)?;
+                    }
+                }
+                Ok(())
+            }
             crate::Statement::Break
             | crate::Statement::Continue
             | crate::Statement::Kill
@@ -52,6 +52,8 @@ pub enum VaryingError {
     InvalidBuiltInStage(crate::BuiltIn),
     #[error("Built-in type for {0:?} is invalid")]
     InvalidBuiltInType(crate::BuiltIn),
+    #[error("Built-in {0:?} is required but missing")]
+    MissingBuiltIn(crate::BuiltIn),
     #[error("Entry point arguments and return values must all have bindings")]
     MissingBinding,
     #[error("Struct member {0} is missing a binding")]
@@ -107,6 +109,31 @@ pub enum EntryPointError {
         "Invalid locations {location_mask:?} are set while dual source blending. Only location 0 may be set."
     )]
     InvalidLocationsWhileDualSourceBlending { location_mask: BitSet },
+    #[error("Mesh shaders must declare their outputs")]
+    MissingMeshInfo,
+    #[error("Mesh outputs are only applicable to mesh shaders")]
+    UnexpectedMeshInfo,
+    #[error("Mesh output maximums must not be zero")]
+    OutOfRangeMeshOutputs,
+    #[error("Mesh shaders must not return a value")]
+    UnexpectedMeshResult,
+    #[error("Mesh vertex output varying error")]
+    MeshVertexOutput(#[source] VaryingError),
+    #[error("Mesh primitive output varying error")]
+    MeshPrimitiveOutput(#[source] VaryingError),
+    #[error("Mesh outputs are written with type {found:?}, but declared as {expected:?}")]
+    MeshOutputTypeMismatch {
+        expected: Handle<crate::Type>,
+        found: Handle<crate::Type>,
+    },
+    #[error("Task shaders must return a `@builtin(mesh_task_size)` value")]
+    MissingTaskOutputSize,
+    #[error("A task payload is only applicable to task and mesh shaders")]
+    UnexpectedTaskPayload,
+    #[error("Global variable {0:?} is not in the task payload address space")]
+    InvalidTaskPayload(Handle<crate::GlobalVariable>),
+    #[error("Task payload {0:?} is used, but not declared by the entry point")]
+    UndeclaredTaskPayload(Handle<crate::GlobalVariable>),
 }
 
 fn storage_usage(access: crate::StorageAccess) -> GlobalUse {
@@ -174,7 +201,7 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::ClipDistance | Bi::CullDistance => (
-                        self.stage == St::Vertex && self.output,
+                        matches!(self.stage, St::Vertex | St::Mesh) && self.output,
                         match *ty_inner {
                             Ti::Array { base, .. } => {
                                 self.types[base].inner == Ti::Scalar(crate::Scalar::F32)
@@ -183,7 +210,7 @@ impl VaryingContext<'_> {
                         },
                     ),
                     Bi::PointSize => (
-                        self.stage == St::Vertex && self.output,
+                        matches!(self.stage, St::Vertex | St::Mesh) && self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::F32),
                     ),
                     Bi::PointCoord => (
@@ -196,9 +223,9 @@ impl VaryingContext<'_> {
                     ),
                     Bi::Position { .. } => (
                         match self.stage {
-                            St::Vertex => self.output,
+                            St::Vertex | St::Mesh => self.output,
                             St::Fragment => !self.output,
-                            St::Compute => false,
+                            St::Compute | St::Task => false,
                         },
                         *ty_inner
                             == Ti::Vector {
@@ -209,7 +236,7 @@ impl VaryingContext<'_> {
                     Bi::ViewIndex => (
                         match self.stage {
                             St::Vertex | St::Fragment => !self.output,
-                            St::Compute => false,
+                            St::Compute | St::Task | St::Mesh => false,
                         },
                         *ty_inner == Ti::Scalar(crate::Scalar::I32),
                     ),
@@ -222,7 +249,11 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::BOOL),
                     ),
                     Bi::PrimitiveIndex => (
-                        self.stage == St::Fragment && !self.output,
+                        match self.stage {
+                            St::Fragment => !self.output,
+                            St::Mesh => self.output,
+                            St::Vertex | St::Compute | St::Task => false,
+                        },
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::SampleIndex => (
@@ -234,7 +265,7 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::LocalInvocationIndex => (
-                        self.stage == St::Compute && !self.output,
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::GlobalInvocationId
@@ -242,7 +273,39 @@ impl VaryingContext<'_> {
                     | Bi::WorkGroupId
                     | Bi::WorkGroupSize
                     | Bi::NumWorkGroups => (
-                        self.stage == St::Compute && !self.output,
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::MeshTaskSize => (
+                        self.stage == St::Task && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::CullPrimitive => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::BOOL),
+                    ),
+                    Bi::PointIndex => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::LineIndices => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Bi,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::TriangleIndices => (
+                        self.stage == St::Mesh && self.output,
                         *ty_inner
                             == Ti::Vector {
                                 size: Vs::Tri,
@@ -309,9 +372,9 @@ impl VaryingContext<'_> {
                 }
 
                 let needs_interpolation = match self.stage {
-                    crate::ShaderStage::Vertex => self.output,
+                    crate::ShaderStage::Vertex | crate::ShaderStage::Mesh => self.output,
                     crate::ShaderStage::Fragment => !self.output,
-                    crate::ShaderStage::Compute => false,
+                    crate::ShaderStage::Compute | crate::ShaderStage::Task => false,
                 };
 
                 // It doesn't make sense to specify a sampling when `interpolation` is `Flat`, but
@@ -486,6 +549,14 @@ impl super::Validator {
             }
             crate::AddressSpace::Private => (TypeFlags::CONSTRUCTIBLE, false),
             crate::AddressSpace::WorkGroup => (TypeFlags::DATA | TypeFlags::SIZED, false),
+            crate::AddressSpace::TaskPayload => {
+                if !self.capabilities.contains(Capabilities::MESH_SHADER) {
+                    return Err(GlobalVariableError::UnsupportedCapability(
+                        Capabilities::MESH_SHADER,
+                    ));
+                }
+                (TypeFlags::DATA | TypeFlags::SIZED, false)
+            }
             crate::AddressSpace::PushConstant => {
                 if !self.capabilities.contains(Capabilities::PUSH_CONSTANT) {
                     return Err(GlobalVariableError::UnsupportedCapability(
@@ -553,7 +624,47 @@ impl super::Validator {
             }
         }
 
-        if ep.stage == crate::ShaderStage::Compute {
+        if matches!(
+            ep.stage,
+            crate::ShaderStage::Task | crate::ShaderStage::Mesh
+        ) {
+            let required = Capabilities::MESH_SHADER;
+            if !self.capabilities.contains(required) {
+                return Err(
+                    EntryPointError::Result(VaryingError::UnsupportedCapability(required))
+                        .with_span(),
+                );
+            }
+        } else if ep.task_payload.is_some() {
+            return Err(EntryPointError::UnexpectedTaskPayload.with_span());
+        }
+
+        if let Some(payload) = ep.task_payload {
+            if module.global_variables[payload].space != crate::AddressSpace::TaskPayload {
+                return Err(EntryPointError::InvalidTaskPayload(payload)
+                    .with_span_handle(payload, &module.global_variables));
+            }
+        }
+
+        match (ep.stage, &ep.mesh_info) {
+            (crate::ShaderStage::Mesh, &None) => {
+                return Err(EntryPointError::MissingMeshInfo.with_span());
+            }
+            (crate::ShaderStage::Mesh, &Some(ref mesh_info)) => {
+                if mesh_info.max_vertices == 0 || mesh_info.max_primitives == 0 {
+                    return Err(EntryPointError::OutOfRangeMeshOutputs.with_span());
+                }
+            }
+            (_, &Some(_)) => {
+                return Err(EntryPointError::UnexpectedMeshInfo.with_span());
+            }
+            (_, &None) => {}
+        }
+
+        if matches!(
+            ep.stage,
+            crate::ShaderStage::Compute | crate::ShaderStage::Task | crate::ShaderStage::Mesh
+        ) {
             if ep
                 .workgroup_size
                 .iter()
@@ -576,6 +687,8 @@ impl super::Validator {
                 crate::ShaderStage::Vertex => ShaderStages::VERTEX,
                 crate::ShaderStage::Fragment => ShaderStages::FRAGMENT,
                 crate::ShaderStage::Compute => ShaderStages::COMPUTE,
+                crate::ShaderStage::Task => ShaderStages::TASK,
+                crate::ShaderStage::Mesh => ShaderStages::MESH,
             };
 
             if !info.available_stages.contains(stage_bit) {
@@ -635,8 +748,32 @@ impl super::Validator {
             {
                 return Err(EntryPointError::MissingVertexOutputPosition.with_span());
             }
-        } else if ep.stage == crate::ShaderStage::Vertex {
-            return Err(EntryPointError::MissingVertexOutputPosition.with_span());
+            match ep.stage {
+                crate::ShaderStage::Task
+                    if fr.binding
+                        != Some(crate::Binding::BuiltIn(crate::BuiltIn::MeshTaskSize)) =>
+                {
+                    return Err(EntryPointError::MissingTaskOutputSize.with_span());
+                }
+                crate::ShaderStage::Mesh => {
+                    return Err(EntryPointError::UnexpectedMeshResult.with_span());
+                }
+                _ => {}
+            }
+        } else {
+            match ep.stage {
+                crate::ShaderStage::Vertex => {
+                    return Err(EntryPointError::MissingVertexOutputPosition.with_span());
+                }
+                crate::ShaderStage::Task => {
+                    return Err(EntryPointError::MissingTaskOutputSize.with_span());
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(ref mesh_info) = ep.mesh_info {
+            self.validate_mesh_outputs(mesh_info, &info, module)?;
         }
 
         {
@@ -682,6 +819,17 @@ impl super::Validator {
                 },
                 crate::AddressSpace::Private | crate::AddressSpace::WorkGroup => GlobalUse::all(),
                 crate::AddressSpace::PushConstant => GlobalUse::READ,
+                crate::AddressSpace::TaskPayload => {
+                    if ep.task_payload != Some(var_handle) {
+                        return Err(EntryPointError::UndeclaredTaskPayload(var_handle)
+                            .with_span_handle(var_handle, &module.global_variables));
+                    }
+                    match ep.stage {
+                        crate::ShaderStage::Task => GlobalUse::all(),
+                        crate::ShaderStage::Mesh => GlobalUse::READ | GlobalUse::QUERY,
+                        _ => GlobalUse::empty(),
+                    }
+                }
             };
             if !allowed_usage.contains(usage) {
                 log::warn!("\tUsage error for: {:?}", var);
@@ -695,15 +843,138 @@ impl super::Validator {
             }
 
             if let Some(ref bind) = var.binding {
-                if !self.ep_resource_bindings.insert(bind.clone()) {
-                    if self.flags.contains(super::ValidationFlags::BINDINGS) {
-                        return Err(EntryPointError::BindingCollision(var_handle)
-                            .with_span_handle(var_handle, &module.global_variables));
+                let mut collides = !self.ep_resource_bindings.insert(bind.clone());
+                // External textures also take up the bindings of their other
+                // planes and their parameters.
+                if let crate::TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = module.types[var.ty].inner
+                {
+                    for extra in bind.external_texture_bindings() {
+                        collides |= !self.ep_resource_bindings.insert(extra);
                     }
                 }
+                if collides && self.flags.contains(super::ValidationFlags::BINDINGS) {
+                    return Err(EntryPointError::BindingCollision(var_handle)
+                        .with_span_handle(var_handle, &module.global_variables));
+                }
             }
         }
 
         Ok(info)
     }
+
+    /// Validate the vertex and primitive output types of a mesh shader.
+    fn validate_mesh_outputs(
+        &mut self,
+        mesh_info: &crate::MeshStageInfo,
+        info: &FunctionInfo,
+        module: &crate::Module,
+    ) -> Result<(), WithSpan<EntryPointError>> {
+        use crate::BuiltIn as Bi;
+
+        let written = [
+            (
+                mesh_info.vertex_output_type,
+                info.mesh_shader_info.vertex_type,
+            ),
+            (
+                mesh_info.primitive_output_type,
+                info.mesh_shader_info.primitive_type,
+            ),
+        ];
+        for (expected, found) in written {
+            if let Some(found) = found {
+                if found != expected {
+                    return Err(
+                        EntryPointError::MeshOutputTypeMismatch { expected, found }.with_span()
+                    );
+                }
+            }
+        }
+
+        let index_built_in = match mesh_info.topology {
+            crate::MeshOutputTopology::Points => Bi::PointIndex,
+            crate::MeshOutputTopology::Lines => Bi::LineIndices,
+            crate::MeshOutputTopology::Triangles => Bi::TriangleIndices,
+        };
+
+        self.location_mask.clear();
+        let mut vertex_built_ins = crate::FastHashSet::default();
+        let mut ctx = VaryingContext {
+            stage: crate::ShaderStage::Mesh,
+            output: true,
+            second_blend_source: false,
+            types: &module.types,
+            type_info: &self.types,
+            location_mask: &mut self.location_mask,
+            built_ins: &mut vertex_built_ins,
+            capabilities: self.capabilities,
+            flags: self.flags,
+        };
+        ctx.validate(mesh_info.vertex_output_type, None)
+            .map_err_inner(|e| EntryPointError::MeshVertexOutput(e).with_span())?;
+        let position = Bi::Position { invariant: false };
+        if !vertex_built_ins.contains(&position) {
+            return Err(
+                EntryPointError::MeshVertexOutput(VaryingError::MissingBuiltIn(position))
+                    .with_span(),
+            );
+        }
+        if let Some(&built_in) = vertex_built_ins.iter().find(|&&built_in| {
+            matches!(
+                built_in,
+                Bi::PrimitiveIndex
+                    | Bi::CullPrimitive
+                    | Bi::PointIndex
+                    | Bi::LineIndices
+                    | Bi::TriangleIndices
+            )
+        }) {
+            return Err(
+                EntryPointError::MeshVertexOutput(VaryingError::InvalidBuiltInStage(built_in))
+                    .with_span(),
+            );
+        }
+
+        self.location_mask.clear();
+        let mut primitive_built_ins = crate::FastHashSet::default();
+        let mut ctx = VaryingContext {
+            stage: crate::ShaderStage::Mesh,
+            output: true,
+            second_blend_source: false,
+            types: &module.types,
+            type_info: &self.types,
+            location_mask: &mut self.location_mask,
+            built_ins: &mut primitive_built_ins,
+            capabilities: self.capabilities,
+            flags: self.flags,
+        };
+        ctx.validate(mesh_info.primitive_output_type, None)
+            .map_err_inner(|e| EntryPointError::MeshPrimitiveOutput(e).with_span())?;
+        if !self.location_mask.is_empty() {
+            return Err(EntryPointError::MeshPrimitiveOutput(
+                VaryingError::InvalidAttributeInStage("location", crate::ShaderStage::Mesh),
+            )
+            .with_span());
+        }
+        if !primitive_built_ins.contains(&index_built_in) {
+            return Err(
+                EntryPointError::MeshPrimitiveOutput(VaryingError::MissingBuiltIn(index_built_in))
+                    .with_span(),
+            );
+        }
+        if let Some(&built_in) = primitive_built_ins.iter().find(|&&built_in| {
+            built_in != index_built_in
+                && !matches!(built_in, Bi::PrimitiveIndex | Bi::CullPrimitive)
+        }) {
+            return Err(
+                EntryPointError::MeshPrimitiveOutput(VaryingError::InvalidBuiltInStage(built_in))
+                    .with_span(),
+            );
+        }
+
+        Ok(())
+    }
 }
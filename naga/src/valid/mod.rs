@@ -22,7 +22,9 @@ use std::ops;
 // merge the corresponding matches over expressions and statements.
 
 use crate::span::{AddSpan as _, WithSpan};
-pub use analyzer::{ExpressionInfo, FunctionInfo, GlobalUse, Uniformity, UniformityRequirements};
+pub use analyzer::{
+    ExpressionInfo, FunctionInfo, GlobalUse, MeshShaderInfo, Uniformity, UniformityRequirements,
+};
 pub use compose::ComposeError;
 pub use expression::{check_literal_value, LiteralError};
 pub use expression::{ConstExpressionError, ExpressionError};
@@ -108,6 +110,8 @@ bitflags::bitflags! {
         const DUAL_SOURCE_BLENDING = 0x2000;
         /// Support for arrayed cube textures.
         const CUBE_ARRAY_TEXTURES = 0x4000;
+        /// Support for task and mesh shader stages.
+        const MESH_SHADER = 0x8000;
     }
 }
 
@@ -126,6 +130,8 @@ bitflags::bitflags! {
         const VERTEX = 0x1;
         const FRAGMENT = 0x2;
         const COMPUTE = 0x4;
+        const TASK = 0x8;
+        const MESH = 0x10;
     }
 }
 
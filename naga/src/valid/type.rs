@@ -126,6 +126,10 @@ pub enum TypeError {
     },
     #[error("Structure types must have at least one member")]
     EmptyStruct,
+    #[error("External textures must be 2D and not arrayed")]
+    InvalidExternalTexture,
+    #[error("Binding arrays of external textures are not supported")]
+    BindingArrayOfExternalTextures,
     #[error(transparent)]
     WidthError(#[from] WidthError),
 }
@@ -189,9 +193,12 @@ const fn ptr_space_argument_flag(space: crate::AddressSpace) -> TypeFlags {
     use crate::AddressSpace as As;
     match space {
         As::Function | As::Private => TypeFlags::ARGUMENT,
-        As::Uniform | As::Storage { .. } | As::Handle | As::PushConstant | As::WorkGroup => {
-            TypeFlags::empty()
-        }
+        As::Uniform
+        | As::Storage { .. }
+        | As::Handle
+        | As::PushConstant
+        | As::WorkGroup
+        | As::TaskPayload => TypeFlags::empty(),
     }
 }
 
@@ -596,8 +603,13 @@ impl super::Validator {
             Ti::Image {
                 dim,
                 arrayed,
-                class: _,
+                class,
             } => {
+                if class == crate::ImageClass::External
+                    && (arrayed || dim != crate::ImageDimension::D2)
+                {
+                    return Err(TypeError::InvalidExternalTexture);
+                }
                 if arrayed && matches!(dim, crate::ImageDimension::Cube) {
                     self.require_type_capability(Capabilities::CUBE_ARRAY_TEXTURES)?;
                 }
@@ -635,6 +647,13 @@ impl super::Validator {
                         _ => return Err(TypeError::BindingArrayBaseTypeNotStruct(base)),
                     };
                 }
+                if let crate::TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = gctx.types[base].inner
+                {
+                    return Err(TypeError::BindingArrayOfExternalTextures);
+                }
 
                 TypeInfo::new(base_info.flags & type_info_mask, Alignment::ONE)
             }
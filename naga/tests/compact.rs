@@ -0,0 +1,127 @@
+/*!
+Tests for module compaction.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "clone"))]
+
+use naga::valid;
+
+const SOURCE: &str = r#"
+    const SCALE: f32 = 2.0;
+    const UNUSED: i32 = 7;
+
+    struct Light {
+        color: vec3<f32>,
+        intensity: f32,
+    }
+
+    struct Unused {
+        value: u32,
+    }
+
+    @group(0) @binding(0) var<uniform> light: Light;
+    @group(0) @binding(1) var<storage, read_write> counts: array<u32>;
+    var<private> seed: u32 = 12u;
+
+    fn scale(value: f32) -> f32 {
+        return value * SCALE;
+    }
+
+    fn shade() -> vec4<f32> {
+        return vec4<f32>(light.color * scale(light.intensity), 1.0);
+    }
+
+    fn count(index: u32) {
+        counts[index] += seed;
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4<f32> {
+        return shade();
+    }
+
+    @compute @workgroup_size(1)
+    fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+        count(id.x + u32(scale(1.0)));
+    }
+"#;
+
+fn extract(entry_point: &str) -> (naga::Module, valid::ModuleInfo) {
+    let module = naga::front::wgsl::parse_str(SOURCE).expect("source ought to parse");
+    let mut validator = valid::Validator::new(
+        valid::ValidationFlags::all(),
+        valid::Capabilities::default(),
+    );
+    validator.validate(&module).expect("validation failed");
+    let index = module
+        .entry_points
+        .iter()
+        .position(|ep| ep.name == entry_point)
+        .unwrap();
+    naga::compact::extract_entry_point(&module, index, &mut validator)
+        .expect("extracted module should validate")
+}
+
+fn names<'a>(names: impl Iterator<Item = Option<&'a String>>) -> Vec<&'a str> {
+    names.map(|name| name.map_or("", String::as_str)).collect()
+}
+
+#[test]
+fn extract_fragment_entry_point() {
+    let (module, info) = extract("fs_main");
+
+    assert_eq!(module.entry_points.len(), 1);
+    assert_eq!(module.entry_points[0].name, "fs_main");
+    assert_eq!(
+        names(module.functions.iter().map(|(_, f)| f.name.as_ref())),
+        ["scale", "shade"]
+    );
+    assert_eq!(
+        names(module.global_variables.iter().map(|(_, g)| g.name.as_ref())),
+        ["light"]
+    );
+    assert_eq!(
+        names(module.constants.iter().map(|(_, c)| c.name.as_ref())),
+        ["SCALE"]
+    );
+    assert!(module
+        .types
+        .iter()
+        .all(|(_, ty)| ty.name.as_deref() != Some("Unused")));
+    let ep_info = info.get_entry_point(0);
+    assert!(module
+        .global_variables
+        .iter()
+        .all(|(handle, _)| !ep_info[handle].is_empty()));
+}
+
+#[test]
+fn extract_compute_entry_point() {
+    let (module, _) = extract("cs_main");
+
+    assert_eq!(module.entry_points.len(), 1);
+    assert_eq!(module.entry_points[0].name, "cs_main");
+    assert_eq!(
+        names(module.functions.iter().map(|(_, f)| f.name.as_ref())),
+        ["scale", "count"]
+    );
+    assert_eq!(
+        names(module.global_variables.iter().map(|(_, g)| g.name.as_ref())),
+        ["counts", "seed"]
+    );
+    assert!(module
+        .types
+        .iter()
+        .all(|(_, ty)| !matches!(ty.name.as_deref(), Some("Light" | "Unused"))));
+}
+
+#[test]
+fn compact_keeps_everything_named() {
+    let mut module = naga::front::wgsl::parse_str(SOURCE).expect("source ought to parse");
+    naga::compact::compact(&mut module);
+
+    assert_eq!(module.entry_points.len(), 2);
+    assert_eq!(module.functions.len(), 3);
+    assert_eq!(module.global_variables.len(), 3);
+    assert_eq!(module.constants.len(), 2);
+}
@@ -0,0 +1,223 @@
+/*!
+Test GLSL ES 1.00 support checks.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "glsl-out"))]
+
+use naga::back::glsl::{self, Es100Construct};
+
+fn write_es100(source: &str, stage: naga::ShaderStage) -> Result<String, glsl::Error> {
+    use naga::valid;
+
+    let module = naga::front::wgsl::parse_str(source).unwrap_or_else(|e| {
+        panic!(
+            "expected WGSL to parse successfully:\n{}",
+            e.emit_to_string(source)
+        );
+    });
+
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+
+    let options = glsl::Options {
+        version: glsl::Version::new_gles(100),
+        ..Default::default()
+    };
+    let pipeline_options = glsl::PipelineOptions {
+        shader_stage: stage,
+        entry_point: "main".to_string(),
+        multiview: None,
+    };
+
+    let mut buffer = String::new();
+    let mut writer = glsl::Writer::new(
+        &mut buffer,
+        &module,
+        &info,
+        &options,
+        &pipeline_options,
+        naga::proc::BoundsCheckPolicies::default(),
+    )?;
+    writer.write()?;
+    Ok(buffer)
+}
+
+fn unsupported(source: &str) -> (String, Es100Construct) {
+    match write_es100(source, naga::ShaderStage::Fragment) {
+        Err(glsl::Error::Es100Unsupported {
+            location,
+            construct,
+        }) => (location, construct),
+        Err(other) => panic!("expected an ES 1.00 error, got {other:?}"),
+        Ok(output) => panic!("expected an ES 1.00 error, got:\n{output}"),
+    }
+}
+
+#[test]
+fn counted_loop() {
+    let output = write_es100(
+        "
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            var sum = 0.0;
+            for (var i = 2; i <= 8; i += 2) {
+                sum += 0.25;
+            }
+            return vec4(sum);
+        }
+        ",
+        naga::ShaderStage::Fragment,
+    )
+    .unwrap();
+    assert!(output.contains("loop_iteration < 5;"), "{output}");
+    assert!(output.contains("gl_FragColor = "), "{output}");
+}
+
+#[test]
+fn dynamic_loop() {
+    let (location, construct) = unsupported(
+        "
+        @group(0) @binding(0) var<uniform> count: i32;
+
+        fn helper() -> f32 {
+            var sum = 0.0;
+            for (var i = 0; i < count; i++) {
+                sum += 1.0;
+            }
+            return sum;
+        }
+
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return vec4(helper());
+        }
+        ",
+    );
+    assert!(matches!(construct, Es100Construct::DynamicLoop));
+    assert_eq!(location, "function `helper`");
+}
+
+#[test]
+fn switch() {
+    let (location, construct) = unsupported(
+        "
+        @fragment
+        fn main(@location(0) @interpolate(flat) x: i32) -> @location(0) vec4<f32> {
+            var v = 0.0;
+            switch x {
+                case 1: { v = 1.0; }
+                default: {}
+            }
+            return vec4(v);
+        }
+        ",
+    );
+    assert!(matches!(construct, Es100Construct::Switch));
+    assert_eq!(location, "entry point `main`");
+}
+
+#[test]
+fn bitwise_operators() {
+    let (_, construct) = unsupported(
+        "
+        @fragment
+        fn main(@location(0) @interpolate(flat) x: i32) -> @location(0) vec4<f32> {
+            return vec4(f32(x & 3));
+        }
+        ",
+    );
+    assert!(matches!(
+        construct,
+        Es100Construct::BinaryOperator(naga::BinaryOperator::And)
+    ));
+}
+
+#[test]
+fn multiple_render_targets() {
+    let (_, construct) = unsupported(
+        "
+        struct Output {
+            @location(0) color: vec4<f32>,
+            @location(1) normal: vec4<f32>,
+        }
+
+        @fragment
+        fn main() -> Output {
+            return Output(vec4(1.0), vec4(0.0));
+        }
+        ",
+    );
+    assert!(matches!(construct, Es100Construct::FragmentOutput(1)));
+}
+
+#[test]
+fn unsigned_overflow() {
+    let (_, construct) = unsupported(
+        "
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            let big = 4000000000u;
+            return vec4(f32(big));
+        }
+        ",
+    );
+    assert!(matches!(
+        construct,
+        Es100Construct::UnsignedOverflow(4000000000)
+    ));
+}
+
+#[test]
+fn dynamic_index() {
+    let (location, construct) = unsupported(
+        "
+        @fragment
+        fn main(@location(0) @interpolate(flat) x: i32) -> @location(0) vec4<f32> {
+            var weights: array<f32, 4>;
+            return vec4(weights[x]);
+        }
+        ",
+    );
+    assert!(matches!(construct, Es100Construct::DynamicIndex));
+    assert_eq!(location, "entry point `main`");
+}
+
+#[test]
+fn loop_counter_index() {
+    let output = write_es100(
+        "
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            var weights: array<vec4<f32>, 8>;
+            var sum = vec4(0.0);
+            for (var i = 6; i >= 0; i -= 2) {
+                sum += weights[i];
+            }
+            return sum;
+        }
+        ",
+        naga::ShaderStage::Fragment,
+    )
+    .unwrap();
+    assert!(
+        output.contains("weights[(6 - loop_iteration * 2)]"),
+        "{output}"
+    );
+}
+
+#[test]
+fn unsigned_comparison() {
+    let (_, construct) = unsupported(
+        "
+        @fragment
+        fn main(@location(0) @interpolate(flat) x: u32) -> @location(0) vec4<f32> {
+            return select(vec4(0.0), vec4(1.0), x > 3u);
+        }
+        ",
+    );
+    assert!(matches!(
+        construct,
+        Es100Construct::UnsignedOperator(naga::BinaryOperator::Greater)
+    ));
+}
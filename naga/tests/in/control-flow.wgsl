@@ -3,6 +3,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     //TODO: execution-only barrier?
     storageBarrier();
     workgroupBarrier();
+    textureBarrier();
 
     var pos: i32;
     // switch without cases
@@ -0,0 +1,42 @@
+struct Globals {
+    transform: mat4x4<f32>,
+    tint: vec4<f32>,
+    count: i32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) layer: i32,
+}
+
+@group(0) @binding(0)
+var<uniform> globals: Globals;
+@group(0) @binding(1)
+var tex: texture_2d<f32>;
+@group(0) @binding(2)
+var samp: sampler;
+
+@vertex
+fn vs_main(@location(0) pos: vec2<f32>, @location(1) layer: i32) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = globals.transform * vec4(pos, 0.0, 1.0);
+    out.uv = pos * 0.5 + 0.5;
+    out.layer = layer % 4;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var weights: array<f32, 4>;
+    for (var i = 0; i < 4; i++) {
+        weights[i] = f32(i + in.layer) * 0.25;
+    }
+    var color = textureSample(tex, samp, in.uv);
+    var total = 0;
+    for (var j = 3; j >= 0; j--) {
+        color += globals.tint * weights[j];
+        total += 1;
+    }
+    return select(color, vec4(1.0), total > globals.count);
+}
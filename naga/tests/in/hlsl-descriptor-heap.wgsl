@@ -0,0 +1,23 @@
+struct Material {
+    texture_index: u32,
+    sampler_index: u32,
+}
+
+@group(0) @binding(0) var textures: binding_array<texture_2d<f32>>;
+@group(0) @binding(1) var samplers: binding_array<sampler>;
+@group(0) @binding(2) var<uniform> material: Material;
+@group(1) @binding(0) var depth_textures: binding_array<texture_depth_2d, 16>;
+@group(1) @binding(1) var shadow_sampler: sampler_comparison;
+
+struct FragmentIn {
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) index: u32,
+}
+
+@fragment
+fn main(in: FragmentIn) -> @location(0) vec4<f32> {
+    let base = textureSample(textures[material.texture_index], samplers[material.sampler_index], in.uv);
+    let detail = textureSample(textures[in.index], samplers[0], in.uv);
+    let shadow = textureSampleCompare(depth_textures[3], shadow_sampler, in.uv, 0.5);
+    return base * detail * shadow;
+}
@@ -0,0 +1,45 @@
+// Host-side struct generation.
+
+struct Light {
+    position: vec3<f32>,
+    range: f32,
+    color: vec3<f32>,
+}
+
+struct Globals {
+    viewProj: mat4x4<f32>,
+    normal: mat3x3<f32>,
+    time: f32,
+    lights: array<Light, 2>,
+    weights: array<vec3<f32>, 2>,
+    frame: u32,
+}
+
+struct Particles {
+    count: atomic<u32>,
+    bounds: array<vec2<f32>, 3>,
+    particles: array<vec4<f32>>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) visible: u32,
+}
+
+struct Flags {
+    enabled: bool,
+    value: u32,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(1) @binding(3) var<storage, read_write> particleBuffer: Particles;
+
+@vertex
+fn main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var flags = Flags(true, 1u);
+    atomicAdd(&particleBuffer.count, flags.value);
+    let p = particleBuffer.particles[index];
+    let position = globals.viewProj * vec4<f32>(globals.normal * p.xyz * globals.time, 1.0);
+    let weight = globals.weights[1].x + particleBuffer.bounds[2].y;
+    return VertexOutput(position + vec4<f32>(globals.lights[1].color, weight), globals.frame);
+}
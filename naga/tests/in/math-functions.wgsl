@@ -45,4 +45,16 @@ fn main() {
     let frexp_b = frexp(1.5).fract;
     let frexp_c: i32 = frexp(1.5).exp;
     let frexp_d: i32 = frexp(vec4(1.5, 1.5, 1.5, 1.5)).exp.x;
+    let quantize_a = quantizeToF16(f);
+    let quantize_b = quantizeToF16(vec3(f));
+    let quantize_c = quantizeToF16(v);
+    let packed = 0x01020304u;
+    let dot4_a = dot4I8Packed(packed, 0xFFFEFDFCu);
+    let dot4_b = dot4U8Packed(packed, 0xFFFEFDFCu);
+    let pack4_a = pack4xI8(vec4(-1, 2, -3, 4));
+    let pack4_b = pack4xU8(vec4(1u, 2u, 3u, 4u));
+    let pack4_c = pack4xI8Clamp(vec4(-200, 2, -3, 200));
+    let pack4_d = pack4xU8Clamp(vec4(1u, 2u, 3u, 400u));
+    let unpack4_a = unpack4xI8(packed);
+    let unpack4_b = unpack4xU8(packed);
 }
@@ -0,0 +1,44 @@
+// Task and mesh shaders drawing a single triangle per workgroup.
+
+struct TaskPayload {
+    color: vec4<f32>,
+    visible: bool,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+struct PrimitiveOutput {
+    @builtin(triangle_indices) indices: vec3<u32>,
+    @builtin(cull_primitive) cull: bool,
+}
+
+var<task_payload> taskPayload: TaskPayload;
+var<workgroup> workgroupData: f32;
+
+@task
+@payload(taskPayload)
+@workgroup_size(1)
+fn ts_main() -> @builtin(mesh_task_size) vec3<u32> {
+    workgroupData = 1.0;
+    taskPayload.color = vec4(1.0, 1.0, 0.0, 1.0);
+    taskPayload.visible = true;
+    return vec3(3u, 1u, 1u);
+}
+
+@mesh
+@output_topology(triangles)
+@vertex_output(VertexOutput, 3)
+@primitive_output(PrimitiveOutput, 1)
+@payload(taskPayload)
+@workgroup_size(1)
+fn ms_main(@builtin(local_invocation_index) index: u32, @builtin(global_invocation_id) id: vec3<u32>) {
+    setMeshOutputs(3u, 1u);
+    workgroupData = 2.0;
+    setVertex(0u, VertexOutput(vec4(0.0, 1.0, 0.0, 1.0), vec4(0.0, 1.0, 0.0, 1.0) * taskPayload.color));
+    setVertex(1u, VertexOutput(vec4(-1.0, -1.0, 0.0, 1.0), vec4(0.0, 0.0, 1.0, 1.0) * taskPayload.color));
+    setVertex(2u, VertexOutput(vec4(1.0, -1.0, 0.0, 1.0), vec4(1.0, 0.0, 0.0, 1.0) * taskPayload.color));
+    setPrimitive(0u, PrimitiveOutput(vec3(0u, 1u, 2u), !taskPayload.visible));
+}
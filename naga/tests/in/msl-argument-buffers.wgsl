@@ -0,0 +1,38 @@
+struct Globals {
+    scale: vec2<f32>,
+    layer: u32,
+}
+
+struct Particles {
+    count: u32,
+    positions: array<vec2<f32>>,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var<storage, read_write> particles: Particles;
+@group(0) @binding(2) var textures: binding_array<texture_2d<f32>>;
+@group(0) @binding(3) var linear_sampler: sampler;
+
+@group(1) @binding(0) var<uniform> tint: vec4<f32>;
+@group(1) @binding(1) var<storage, read> offsets: array<vec2<f32>>;
+
+@group(2) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;
+
+fn particle_position(index: u32) -> vec2<f32> {
+    return particles.positions[index] * globals.scale + offsets[index];
+}
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>, @location(1) @interpolate(flat) index: u32) -> @location(0) vec4<f32> {
+    let texture = textures[globals.layer];
+    return textureSample(texture, linear_sampler, uv + particle_position(index)) * tint;
+}
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= particles.count {
+        return;
+    }
+    particles.positions[id.x] = particle_position(id.x);
+    textureStore(output, vec2<i32>(particles.positions[id.x]), tint);
+}
@@ -0,0 +1,56 @@
+struct Counters {
+    total: atomic<u32>,
+    values: array<u32, 64>,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> counters: Counters;
+
+var<workgroup> shared_values: array<u32, 64>;
+var<workgroup> shared_max: atomic<u32>;
+var<workgroup> shared_offset: u32;
+
+@compute @workgroup_size(64)
+fn cs_main(@builtin(local_invocation_index) index: u32) {
+    shared_values[index] = counters.values[index];
+    atomicMax(&shared_max, shared_values[index]);
+    workgroupBarrier();
+
+    if index == 0u {
+        shared_offset = atomicLoad(&shared_max);
+    }
+    let offset = workgroupUniformLoad(&shared_offset);
+    counters.values[index] = shared_values[63u - index] + offset;
+    storageBarrier();
+
+    if index == 0u {
+        atomicAdd(&counters.total, counters.values[1]);
+    }
+}
+
+@group(0) @binding(1)
+var tex: texture_2d<f32>;
+@group(0) @binding(2)
+var samp: sampler;
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    if uv.x < 0.0 {
+        discard;
+    }
+    // Derivatives stay defined for the rest of the quad after `discard`.
+    return textureSample(tex, samp, uv) + dpdx(uv).xyxy;
+}
+
+@group(0) @binding(3)
+var src_image: texture_storage_2d<rgba8unorm, read>;
+@group(0) @binding(4)
+var dst_image: texture_storage_2d<rgba8unorm, read_write>;
+
+@compute @workgroup_size(8, 8)
+fn copy_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    textureStore(dst_image, id.xy, textureLoad(src_image, id.xy));
+    textureBarrier();
+    let neighbour = textureLoad(dst_image, id.yx);
+    textureStore(dst_image, id.xy, neighbour * 0.5);
+}
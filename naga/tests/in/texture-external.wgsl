@@ -0,0 +1,21 @@
+@group(0) @binding(0)
+var tex: texture_external;
+@group(0) @binding(4)
+var samp: sampler;
+@group(0) @binding(5)
+var tex_2d: texture_2d<f32>;
+
+fn sample_external(coords: vec2<f32>) -> vec4<f32> {
+    return textureSampleBaseClampToEdge(tex, samp, coords);
+}
+
+@fragment
+fn fragment_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    var a = sample_external(uv);
+    a += textureLoad(tex, vec2<u32>(1u, 2u));
+    a += textureLoad(tex, vec2<i32>(3, 4));
+    a += textureSampleBaseClampToEdge(tex_2d, samp, uv);
+    let size = textureDimensions(tex);
+    a += vec4<f32>(vec2<f32>(size), 0.0, 0.0);
+    return a;
+}
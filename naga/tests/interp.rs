@@ -0,0 +1,378 @@
+/*!
+Tests for the compute shader interpreter.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "interp"))]
+
+use naga::interp::{Error, Interpreter, Options, Report};
+use naga::proc::{BoundsCheckPolicies, BoundsCheckPolicy};
+use naga::{valid, FastHashMap, ResourceBinding};
+
+fn binding(binding: u32) -> ResourceBinding {
+    ResourceBinding { group: 0, binding }
+}
+
+fn words(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks_exact(4)
+        .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+        .collect()
+}
+
+fn bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
+
+/// Run the `main` entry point of `source`, with the buffers at bindings
+/// 0, 1, ... of group 0 initialized from `buffers`.
+fn run(
+    source: &str,
+    options: Options,
+    workgroups: [u32; 3],
+    buffers: &[&[u32]],
+) -> Result<(Report, Vec<Vec<u32>>), Error> {
+    let module = naga::front::wgsl::parse_str(source).unwrap_or_else(|e| {
+        panic!(
+            "expected WGSL to parse successfully:\n{}",
+            e.emit_to_string(source)
+        );
+    });
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+
+    let mut buffer_map = FastHashMap::default();
+    for (index, contents) in buffers.iter().enumerate() {
+        buffer_map.insert(binding(index as u32), bytes(contents));
+    }
+    let report =
+        Interpreter::new(&module, &info, options).dispatch("main", workgroups, &mut buffer_map)?;
+    let outputs = (0..buffers.len() as u32)
+        .map(|index| words(&buffer_map[&binding(index)]))
+        .collect();
+    Ok((report, outputs))
+}
+
+#[test]
+fn arithmetic() {
+    let (report, outputs) = run(
+        r#"
+        struct Params {
+            scale: vec2<f32>,
+            offset: u32,
+        }
+
+        @group(0) @binding(0) var<uniform> params: Params;
+        @group(0) @binding(1) var<storage, read_write> out: array<u32>;
+
+        fn collatz(start: u32) -> u32 {
+            var n = start;
+            var steps = 0u;
+            while n != 1u {
+                if n % 2u == 0u {
+                    n /= 2u;
+                } else {
+                    n = 3u * n + 1u;
+                }
+                steps++;
+            }
+            return steps;
+        }
+
+        @compute @workgroup_size(2, 2)
+        fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+            let index = id.y * 4u + id.x;
+            let m = mat2x2<f32>(params.scale.x, 0.0, 0.0, params.scale.y);
+            let v = m * vec2<f32>(f32(id.x), f32(id.y));
+            var kind: u32;
+            switch index % 3u {
+                case 0u: { kind = 100u; }
+                case 1u, 2u: { kind = 200u; }
+                default: {}
+            }
+            out[index] = collatz(index + params.offset) + u32(v.x + v.y) * 1000u + kind * 1000000u;
+        }
+        "#,
+        Options::default(),
+        [2, 2, 1],
+        &[&[2f32.to_bits(), 3f32.to_bits(), 1, 0], &[0; 16]],
+    )
+    .unwrap();
+
+    assert_eq!(report.out_of_bounds, []);
+    assert_eq!(report.data_races, []);
+    let collatz = |mut n: u32| {
+        let mut steps = 0;
+        while n != 1 {
+            n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+            steps += 1;
+        }
+        steps
+    };
+    let expected: Vec<u32> = (0..16)
+        .map(|index| {
+            let (x, y) = (index % 4, index / 4);
+            let kind = if index % 3 == 0 { 100 } else { 200 };
+            collatz(index + 1) + (2 * x + 3 * y) * 1000 + kind * 1_000_000
+        })
+        .collect();
+    assert_eq!(outputs[1], expected);
+}
+
+#[test]
+fn workgroup_reduction() {
+    let (report, outputs) = run(
+        r#"
+        @group(0) @binding(0) var<storage> input: array<u32>;
+        @group(0) @binding(1) var<storage, read_write> sums: array<u32>;
+
+        var<workgroup> partial: array<u32, 8>;
+
+        @compute @workgroup_size(8)
+        fn main(
+            @builtin(local_invocation_index) local: u32,
+            @builtin(workgroup_id) group: vec3<u32>,
+        ) {
+            partial[local] = input[group.x * 8u + local];
+            for (var stride = 4u; stride > 0u; stride /= 2u) {
+                workgroupBarrier();
+                if local < stride {
+                    partial[local] += partial[local + stride];
+                }
+            }
+            if local == 0u {
+                sums[group.x] = partial[0];
+            }
+        }
+        "#,
+        Options::default(),
+        [2, 1, 1],
+        &[&(1..=16).collect::<Vec<_>>(), &[0; 2]],
+    )
+    .unwrap();
+
+    assert_eq!(report.data_races, []);
+    assert_eq!(outputs[1], [36, 100]);
+}
+
+#[test]
+fn atomics() {
+    let (report, outputs) = run(
+        r#"
+        @group(0) @binding(0) var<storage, read_write> counters: array<atomic<u32>, 3>;
+
+        var<workgroup> first: atomic<u32>;
+
+        @compute @workgroup_size(4)
+        fn main(@builtin(local_invocation_index) local: u32) {
+            atomicAdd(&counters[0], local);
+            atomicMax(&counters[1], local * 10u);
+            let exchanged = atomicCompareExchangeWeak(&first, 0u, local + 1u).exchanged;
+            if exchanged {
+                atomicAdd(&counters[2], 1u);
+            }
+        }
+        "#,
+        Options::default(),
+        [3, 1, 1],
+        &[&[0; 3]],
+    )
+    .unwrap();
+
+    assert_eq!(report.data_races, []);
+    // One invocation per workgroup wins the exchange.
+    assert_eq!(outputs[0], [18, 30, 3]);
+}
+
+#[test]
+fn uniform_load() {
+    let (report, outputs) = run(
+        r#"
+        @group(0) @binding(0) var<storage, read_write> out: array<u32, 4>;
+
+        var<workgroup> shared_value: u32;
+
+        @compute @workgroup_size(4)
+        fn main(@builtin(local_invocation_index) local: u32) {
+            if local == 2u {
+                shared_value = 42u;
+            }
+            out[local] = workgroupUniformLoad(&shared_value) + local;
+        }
+        "#,
+        Options::default(),
+        [1, 1, 1],
+        &[&[0; 4]],
+    )
+    .unwrap();
+
+    assert_eq!(report.data_races, []);
+    assert_eq!(outputs[0], [42, 43, 44, 45]);
+}
+
+#[test]
+fn data_races() {
+    let source = r#"
+        @group(0) @binding(0) var<storage, read_write> out: array<u32>;
+
+        var<workgroup> scratch: array<u32, 2>;
+
+        @compute @workgroup_size(2)
+        fn main(
+            @builtin(local_invocation_index) local: u32,
+            @builtin(global_invocation_id) id: vec3<u32>,
+        ) {
+            scratch[local] = local;
+            // Missing a barrier here.
+            out[id.x] = scratch[1u - local];
+            // Every invocation writes the same word.
+            out[4] = local;
+        }
+    "#;
+    let (report, _) = run(source, Options::default(), [2, 1, 1], &[&[0; 5]]).unwrap();
+
+    let mut races: Vec<_> = report
+        .data_races
+        .iter()
+        .map(|race| {
+            (
+                race.global.index(),
+                race.offset,
+                race.invocations[0].workgroup[0],
+                race.invocations[1].workgroup[0],
+            )
+        })
+        .collect();
+    races.sort();
+    // Each word is reported once, except that workgroup memory is reported
+    // again for each workgroup.
+    assert_eq!(
+        races,
+        [
+            (0, 16, 0, 0),
+            (1, 0, 0, 0),
+            (1, 0, 1, 1),
+            (1, 4, 0, 0),
+            (1, 4, 1, 1)
+        ]
+    );
+}
+
+#[test]
+fn barrier_divergence() {
+    let source = r#"
+        @compute @workgroup_size(2)
+        fn main(@builtin(local_invocation_index) local: u32) {
+            if local == 0u {
+                workgroupBarrier();
+            }
+        }
+    "#;
+    let error = run(source, Options::default(), [1, 1, 1], &[]).unwrap_err();
+    assert!(
+        matches!(error, Error::BarrierDivergence([0, 0, 0])),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn step_limit() {
+    let source = r#"
+        @group(0) @binding(0) var<storage, read_write> out: u32;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            loop {
+                out += 1u;
+            }
+        }
+    "#;
+    let options = Options {
+        step_limit: 1000,
+        ..Options::default()
+    };
+    let error = run(source, options, [1, 1, 1], &[&[0]]).unwrap_err();
+    assert!(matches!(error, Error::StepLimit), "{error:?}");
+}
+
+#[test]
+fn missing_buffer() {
+    let source = r#"
+        @group(0) @binding(0) var<storage, read_write> out: u32;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            out = 1u;
+        }
+    "#;
+    let error = run(source, Options::default(), [1, 1, 1], &[]).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::MissingBuffer(ResourceBinding {
+            group: 0,
+            binding: 0
+        })
+    ));
+}
+
+#[test]
+fn bounds_checks() {
+    let source = r#"
+        @group(0) @binding(0) var<storage, read_write> data: array<u32, 4>;
+        @group(0) @binding(1) var<storage, read_write> out: array<u32, 2>;
+
+        @compute @workgroup_size(1)
+        fn main() {
+            var index = 6;
+            out[0] = data[index];
+            data[index] = 99u;
+            var local = array<u32, 2>(7u, 8u);
+            out[1] = local[index];
+        }
+    "#;
+    let with_policy = |policy| Options {
+        bounds_check_policies: BoundsCheckPolicies {
+            index: policy,
+            buffer: policy,
+            ..BoundsCheckPolicies::default()
+        },
+        ..Options::default()
+    };
+    let data: &[u32] = &[1, 2, 3, 4];
+
+    let error = run(
+        source,
+        with_policy(BoundsCheckPolicy::Unchecked),
+        [1, 1, 1],
+        &[data, &[0; 2]],
+    )
+    .unwrap_err();
+    let Error::OutOfBounds(out_of_bounds) = error else {
+        panic!("expected an out-of-bounds error, got {error:?}");
+    };
+    assert_eq!((out_of_bounds.index, out_of_bounds.length), (6, 4));
+
+    let (report, outputs) = run(
+        source,
+        with_policy(BoundsCheckPolicy::Restrict),
+        [1, 1, 1],
+        &[data, &[0; 2]],
+    )
+    .unwrap();
+    assert_eq!(outputs, [vec![1, 2, 3, 99], vec![4, 8]]);
+    assert_eq!(report.out_of_bounds.len(), 3);
+    assert!(report
+        .out_of_bounds
+        .iter()
+        .all(|oob| oob.index == 6 && oob.policy == BoundsCheckPolicy::Restrict));
+
+    let (report, outputs) = run(
+        source,
+        with_policy(BoundsCheckPolicy::ReadZeroSkipWrite),
+        [1, 1, 1],
+        &[data, &[5; 2]],
+    )
+    .unwrap();
+    assert_eq!(outputs, [vec![1, 2, 3, 4], vec![0, 0]]);
+    assert_eq!(report.out_of_bounds.len(), 3);
+}
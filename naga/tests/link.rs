@@ -0,0 +1,287 @@
+/*!
+Tests for linking modules, and for WGSL `import` directives.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "link"))]
+
+use naga::front::wgsl::{self, ImportedSource};
+use naga::link::Linker;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+const LIGHTING: &str = "
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+fn shade(light: Light, n: vec3<f32>) -> vec3<f32> {
+    return light.color * light.intensity * max(n.z, 0.0);
+}
+";
+
+#[track_caller]
+fn validate(module: &naga::Module) {
+    Validator::new(ValidationFlags::all(), Capabilities::empty())
+        .validate(module)
+        .unwrap_or_else(|error| panic!("{error:?}"));
+}
+
+fn function_names(module: &naga::Module) -> Vec<&str> {
+    module
+        .functions
+        .iter()
+        .filter_map(|(_, function)| function.name.as_deref())
+        .collect()
+}
+
+#[test]
+fn types_are_shared() {
+    let mut linker = Linker::new();
+    linker.add(wgsl::parse_str(LIGHTING).unwrap());
+    let other = wgsl::parse_str(
+        "
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+var<private> sun: Light;
+",
+    )
+    .unwrap();
+    let light = other.global_variables.iter().next().unwrap().1.ty;
+    let mapping = linker.add(other);
+    let (module, _) = linker.finish();
+
+    validate(&module);
+    let lights: Vec<_> = module
+        .types
+        .iter()
+        .filter(|&(_, ty)| ty.name.as_deref() == Some("Light"))
+        .map(|(handle, _)| handle)
+        .collect();
+    assert_eq!(lights, [mapping.ty(light)]);
+}
+
+#[test]
+fn conflicting_names_are_renamed() {
+    let source = "
+const scale = 2.0;
+var<private> counter: u32;
+fn helper() -> f32 { counter += 1u; return scale; }
+@fragment fn main() -> @location(0) vec4<f32> { return vec4(helper()); }
+";
+    let first = wgsl::parse_str(source).unwrap();
+    let second = wgsl::parse_str(source).unwrap();
+    let helper = second.functions.iter().next().unwrap().0;
+
+    let mut linker = Linker::new();
+    linker.add(first);
+    let mapping = linker.add(second);
+    let (module, _) = linker.finish();
+
+    validate(&module);
+    assert_eq!(function_names(&module), ["helper", "helper_1"]);
+    assert_eq!(
+        module.functions[mapping.function(helper)].name.as_deref(),
+        Some("helper_1")
+    );
+    let constants: Vec<_> = module
+        .constants
+        .iter()
+        .filter_map(|(_, constant)| constant.name.as_deref())
+        .collect();
+    assert_eq!(constants, ["scale", "scale_1"]);
+    let entry_points: Vec<_> = module.entry_points.iter().map(|ep| &ep.name).collect();
+    assert_eq!(entry_points, ["main", "main_1"]);
+    assert_eq!(mapping.entry_point(0), 1);
+}
+
+#[test]
+fn spans_locate_their_module() {
+    let first = "fn first() {}\n";
+    let second = "fn second() -> i32 { return 1; }\n";
+
+    let mut linker = Linker::new();
+    linker.add(wgsl::parse_str(first).unwrap());
+    linker.add(wgsl::parse_str(second).unwrap());
+    let (module, source_map) = linker.finish();
+
+    let sources = [first, second];
+    for (handle, function) in module.functions.iter() {
+        let (index, span) = source_map
+            .locate(module.functions.get_span(handle))
+            .unwrap();
+        let text = &sources[index][span.to_range().unwrap()];
+        assert!(
+            text.starts_with(&format!("fn {}", function.name.as_ref().unwrap())),
+            "{text:?}"
+        );
+    }
+}
+
+#[test]
+fn import() {
+    let mut resolver = |name: &str, importer: Option<&str>| match name {
+        "lighting.wgsl" => {
+            assert_eq!(importer, None);
+            Ok(ImportedSource {
+                name: name.to_string(),
+                source: LIGHTING.to_string(),
+            })
+        }
+        _ => Err(format!("no such file: {name}")),
+    };
+    let source = "import \"lighting.wgsl\";
+
+@fragment
+fn main(@location(0) n: vec3<f32>) -> @location(0) vec4<f32> {
+    let light = Light(vec3(1.0), 0.5);
+    return vec4(shade(light, n), 1.0);
+}
+";
+    let mut frontend = wgsl::Frontend::new();
+    let module = frontend.parse_with_imports(source, &mut resolver).unwrap();
+    validate(&module);
+    assert_eq!(function_names(&module), ["shade"]);
+
+    let source_map = frontend.source_map();
+    assert_eq!(source_map.files().len(), 1);
+    let (shade, _) = module.functions.iter().next().unwrap();
+    let (file, span) = source_map.locate(module.functions.get_span(shade));
+    assert_eq!(file, Some(0));
+    assert!(LIGHTING[span.to_range().unwrap()].starts_with("fn shade"));
+}
+
+/// Without imports, `parse_with_imports` returns the same module as `parse`,
+/// including its unused named declarations.
+#[test]
+fn no_imports() {
+    let source = "
+const UNUSED: f32 = 1.0;
+struct Unused { x: f32 }
+fn main() -> f32 { return 2.0 * 3.0; }
+";
+    let mut resolver = |name: &str, _: Option<&str>| Err(format!("no such file: {name}"));
+    let with_imports = wgsl::Frontend::new()
+        .parse_with_imports(source, &mut resolver)
+        .unwrap();
+    let module = wgsl::parse_str(source).unwrap();
+    assert_eq!(format!("{with_imports:?}"), format!("{module:?}"));
+    assert_eq!(module.constants.len(), 1);
+}
+
+/// Files imported several times are only lowered once, declarations with the
+/// same name in different files are renamed, and imports aren't transitive.
+#[test]
+fn import_graph() {
+    let files = [
+        (
+            "a.wgsl",
+            "import \"util.wgsl\"; fn a() -> f32 { return util(); } fn helper() {}",
+        ),
+        (
+            "b.wgsl",
+            "import \"util.wgsl\"; fn b() -> f32 { return util(); } fn helper() {}",
+        ),
+        ("util.wgsl", "fn util() -> f32 { return 1.0; }"),
+    ];
+    let mut resolver = |name: &str, _: Option<&str>| {
+        let &(name, source) = files
+            .iter()
+            .find(|&&(file, _)| file == name)
+            .ok_or_else(|| "not found".to_string())?;
+        Ok(ImportedSource {
+            name: name.to_string(),
+            source: source.to_string(),
+        })
+    };
+
+    let mut frontend = wgsl::Frontend::new();
+    let module = frontend
+        .parse_with_imports(
+            "import \"a.wgsl\"; import \"b.wgsl\"; fn main() -> f32 { return a() + b(); }",
+            &mut resolver,
+        )
+        .unwrap();
+    validate(&module);
+    assert_eq!(
+        function_names(&module),
+        ["util", "a", "helper", "b", "helper_1", "main"]
+    );
+
+    let error = frontend
+        .parse_with_imports(
+            "import \"a.wgsl\"; fn main() -> f32 { return util(); }",
+            &mut resolver,
+        )
+        .unwrap_err();
+    assert_eq!(
+        error.message(),
+        "no definition in scope for identifier: 'util'"
+    );
+}
+
+#[test]
+fn import_errors() {
+    let mut resolver = |name: &str, _: Option<&str>| {
+        Ok(ImportedSource {
+            name: name.to_string(),
+            source: match name {
+                "missing.wgsl" => return Err("not found".to_string()),
+                "cycle.wgsl" => "import \"cycle.wgsl\";",
+                _ => "fn f() { let x: i32 = 1u; }",
+            }
+            .to_string(),
+        })
+    };
+    let mut frontend = wgsl::Frontend::new();
+    let mut check = |source: &str, message: &str, file: Option<usize>, text: &str| {
+        let error = frontend
+            .parse_with_imports(source, &mut resolver)
+            .unwrap_err();
+        assert_eq!(error.message(), message);
+        let source_map = frontend.source_map();
+        let (span, _) = error.labels().next().unwrap();
+        let (located, span) = source_map.locate(span);
+        assert_eq!(located, file);
+        let source = match file {
+            Some(file) => &source_map.files()[file].source,
+            None => source,
+        };
+        assert_eq!(&source[span.to_range().unwrap()], text);
+    };
+
+    check(
+        "import \"missing.wgsl\";",
+        "failed to resolve import \"missing.wgsl\"",
+        None,
+        "\"missing.wgsl\"",
+    );
+    check(
+        "import \"cycle.wgsl\";",
+        "cyclic import of \"cycle.wgsl\"",
+        Some(0),
+        "\"cycle.wgsl\"",
+    );
+    check(
+        "import \"bad.wgsl\";",
+        "the type of `x` is expected to be `i32`, but got `u32`",
+        Some(0),
+        "x",
+    );
+    check("fn f() {} import \"bad.wgsl\";", "expected global item ('struct', 'const', 'var', 'alias', ';', 'fn') or the end of the file, found 'import'", None, "import");
+}
+
+#[test]
+fn import_without_resolver() {
+    let source = "import \"lighting.wgsl\";\nfn f() {}\n";
+    assert_eq!(
+        wgsl::parse_str(source).unwrap_err().message(),
+        "failed to resolve import \"lighting.wgsl\""
+    );
+    assert_eq!(
+        wgsl::format_str("import   \"lighting.wgsl\" ;fn f() {}").unwrap(),
+        source
+    );
+}
@@ -0,0 +1,198 @@
+/*!
+Tests for the optimization passes in `naga::opt`.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "wgsl-out", feature = "opt"))]
+
+use naga::opt::{Options, Passes};
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+/// Optimize `source` with `passes`, and return the result as WGSL.
+#[track_caller]
+fn optimize(source: &str, passes: Passes) -> String {
+    optimize_with(
+        source,
+        &Options {
+            passes,
+            ..Options::default()
+        },
+    )
+}
+
+#[track_caller]
+fn optimize_with(source: &str, options: &Options) -> String {
+    let mut module = naga::front::wgsl::parse_str(source).unwrap();
+    let mut validator = Validator::new(ValidationFlags::all(), Capabilities::all());
+    validator
+        .validate(&module)
+        .unwrap_or_else(|error| panic!("{error:?}"));
+    let info = naga::opt::optimize(&mut module, options, &mut validator)
+        .unwrap_or_else(|error| panic!("{error:?}"));
+    naga::back::wgsl::write_string(&module, &info, naga::back::wgsl::WriterFlags::empty()).unwrap()
+}
+
+#[test]
+fn inline_and_fold() {
+    let source = "
+fn scale(x: f32) -> f32 {
+    return x * 2.0;
+}
+
+@fragment
+fn main() -> @location(0) vec4<f32> {
+    return vec4(scale(1.5));
+}
+";
+    let output = optimize(source, Passes::all());
+    assert!(!output.contains("fn scale"), "{output}");
+    assert!(output.contains("return vec4(3f);"), "{output}");
+
+    // Without folding, the multiplication stays.
+    let output = optimize(source, Passes::INLINE);
+    assert!(!output.contains("fn scale"), "{output}");
+    assert!(output.contains("(1.5f * 2f)"), "{output}");
+
+    // Without inlining, there is nothing to fold.
+    let output = optimize(source, Passes::CONSTANT_FOLDING | Passes::DEAD_CODE);
+    assert!(output.contains("fn scale"), "{output}");
+}
+
+#[test]
+fn inline_locals_and_pointers() {
+    let source = "
+fn bump(p: ptr<function, i32>, by: i32) {
+    var step = by;
+    step += 1;
+    *p += step;
+}
+
+@compute @workgroup_size(1)
+fn main(@builtin(local_invocation_index) index: u32) {
+    var total = 0;
+    for (var i = 0; i < i32(index); i++) {
+        bump(&total, i);
+    }
+    bump(&total, 2);
+    if total > 10 {
+        workgroupBarrier();
+    }
+}
+";
+    let output = optimize(source, Passes::all());
+    assert!(!output.contains("fn bump"), "{output}");
+    // The call inside the loop resets its copy of `step` each iteration, and
+    // the other call gets a copy of its own.
+    assert_eq!(output.matches("var step").count(), 2, "{output}");
+}
+
+#[test]
+fn keep_large_and_uncalled_functions() {
+    let source = "
+fn unused() -> f32 {
+    return 1.0;
+}
+
+fn large(x: f32) -> f32 {
+    var y = x;
+    for (var i = 0; i < 4; i++) {
+        y = y * y + sin(y) - cos(y) / (y + 1.0);
+    }
+    return y;
+}
+
+fn early(x: f32) -> f32 {
+    if x > 0.0 {
+        return x;
+    }
+    return -x;
+}
+
+@fragment
+fn main(@location(0) x: f32) -> @location(0) vec4<f32> {
+    return vec4(large(x), early(x), 0.0, 1.0);
+}
+";
+    let output = optimize(source, Passes::all());
+    assert!(output.contains("fn unused"), "{output}");
+    assert!(!output.contains("fn large"), "{output}");
+    assert!(output.contains("fn early"), "{output}");
+
+    let options = Options {
+        inline_limit: 8,
+        ..Options::default()
+    };
+    let output = optimize_with(source, &options);
+    assert!(output.contains("fn large"), "{output}");
+}
+
+#[test]
+fn dead_code() {
+    let source = "
+const DEBUG = false;
+
+@fragment
+fn main(@location(0) x: f32) -> @location(0) vec4<f32> {
+    var unread = x;
+    unread = unread * 2.0;
+    var color = vec4(x);
+    if DEBUG {
+        color = vec4(1.0, 0.0, 1.0, 1.0);
+    }
+    if x > 0.5 {
+        discard;
+    }
+    if !DEBUG {
+        return color;
+    }
+    return vec4(0.25);
+}
+";
+    let output = optimize(source, Passes::DEAD_CODE);
+    assert!(!output.contains("unread"), "{output}");
+    assert!(!output.contains("vec4<f32>(1f, 0f, 1f, 1f)"), "{output}");
+    assert!(!output.contains("0.25f"), "{output}");
+    assert!(output.contains("discard;"), "{output}");
+    assert!(output.contains("var color"), "{output}");
+}
+
+#[test]
+fn fold_keeps_runtime_values() {
+    let source = "
+@group(0) @binding(0) var<storage, read_write> out: array<vec2<f32>>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let half = vec2(1.0, 2.0) * 0.5;
+    let m = mat2x2(half, half * 2.0);
+    out[id.x] = m * vec2(f32(id.x)) + m[1] + half.yx;
+}
+";
+    let output = optimize(source, Passes::CONSTANT_FOLDING);
+    assert!(output.contains("f32(id.x)"), "{output}");
+    assert!(output.contains("vec2<f32>(0.5f, 1f)"), "{output}");
+    assert!(!output.contains("* 0.5f"), "{output}");
+    assert!(!output.contains("* 2f"), "{output}");
+}
+
+/// All passes together keep every WGSL snapshot input valid.
+#[test]
+fn snapshot_inputs_stay_valid() {
+    let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/in");
+    for entry in directory.read_dir().unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("wgsl") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        let Ok(mut module) = naga::front::wgsl::parse_str(&source) else {
+            continue;
+        };
+        let mut validator = Validator::new(ValidationFlags::all(), Capabilities::all());
+        if validator.validate(&module).is_err() {
+            continue;
+        }
+        if let Err(error) = naga::opt::optimize(&mut module, &Options::default(), &mut validator) {
+            panic!("{}: {error:?}", path.display());
+        }
+    }
+}
@@ -47,6 +47,8 @@ void main() {
     barrier();
     memoryBarrierShared();
     barrier();
+    memoryBarrierImage();
+    barrier();
     switch(1) {
         default: {
             pos = 1;
@@ -0,0 +1,86 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+precision highp float;
+precision highp int;
+#else
+precision mediump float;
+precision mediump int;
+#endif
+
+struct Globals {
+    mat4 transform;
+    vec4 tint;
+    int count;
+};
+struct VertexOutput {
+    vec4 position;
+    vec2 uv;
+    int layer;
+};
+uniform Globals _group_0_binding_0_fs;
+
+uniform sampler2D _group_0_binding_1_fs;
+
+varying vec2 _vs2fs_location0;
+varying float _vs2fs_location1;
+
+void main() {
+    VertexOutput in_ = VertexOutput(gl_FragCoord, _vs2fs_location0, int(floor(_vs2fs_location1 + 0.5)));
+    float weights[4];
+    int i = 0;
+    vec4 color = vec4(0.0);
+    int total = 0;
+    int j = 3;
+    for (int i_1 = 0; i_1 < 4; i_1++) {
+        weights[i_1] = 0.0;
+    }
+    bool loop_init = true;
+    for (int loop_iteration = 0; loop_iteration < 5; loop_iteration++) {
+        if (!loop_init) {
+            int _e16 = i;
+            i = (_e16 + 1);
+        }
+        loop_init = false;
+        int _e4 = i;
+        if ((_e4 < 4)) {
+        } else {
+            break;
+        }
+        {
+            int _e7 = i;
+            int _e9 = i;
+            weights[loop_iteration] = (float((_e9 + in_.layer)) * 0.25);
+        }
+    }
+    vec4 _e21 = texture2D(_group_0_binding_1_fs, vec2(in_.uv));
+    color = _e21;
+    bool loop_init_1 = true;
+    for (int loop_iteration_1 = 0; loop_iteration_1 < 5; loop_iteration_1++) {
+        if (!loop_init_1) {
+            int _e43 = j;
+            j = (_e43 - 1);
+        }
+        loop_init_1 = false;
+        int _e27 = j;
+        if ((_e27 >= 0)) {
+        } else {
+            break;
+        }
+        {
+            vec4 _e32 = _group_0_binding_0_fs.tint;
+            int _e33 = j;
+            float _e35 = weights[(3 - loop_iteration_1)];
+            vec4 _e37 = color;
+            color = (_e37 + (_e32 * _e35));
+            int _e40 = total;
+            total = (_e40 + 1);
+        }
+    }
+    vec4 _e45 = color;
+    int _e48 = total;
+    int _e51 = _group_0_binding_0_fs.count;
+    gl_FragColor = ((_e48 > _e51) ? vec4(1.0) : _e45);
+    return;
+}
+
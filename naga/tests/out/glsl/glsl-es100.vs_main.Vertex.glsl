@@ -0,0 +1,37 @@
+#version 100
+
+precision highp float;
+precision highp int;
+
+struct Globals {
+    mat4 transform;
+    vec4 tint;
+    int count;
+};
+struct VertexOutput {
+    vec4 position;
+    vec2 uv;
+    int layer;
+};
+uniform Globals _group_0_binding_0_vs;
+
+attribute vec2 _p2vs_location0;
+attribute float _p2vs_location1;
+varying vec2 _vs2fs_location0;
+varying float _vs2fs_location1;
+
+void main() {
+    vec2 pos = _p2vs_location0;
+    int layer = int(floor(_p2vs_location1 + 0.5));
+    VertexOutput out_ = VertexOutput(vec4(0.0), vec2(0.0), 0);
+    mat4 _e6 = _group_0_binding_0_vs.transform;
+    out_.position = (_e6 * vec4(pos, 0.0, 1.0));
+    out_.uv = ((pos * 0.5) + vec2(0.5));
+    out_.layer = (layer - 4 * (layer / 4));
+    VertexOutput _e20 = out_;
+    gl_Position = _e20.position;
+    _vs2fs_location0 = _e20.uv;
+    _vs2fs_location1 = float(_e20.layer);
+    return;
+}
+
@@ -100,5 +100,21 @@ void main() {
     float frexp_b = naga_frexp(1.5).fract_;
     int frexp_c = naga_frexp(1.5).exp_;
     int frexp_d = naga_frexp(vec4(1.5, 1.5, 1.5, 1.5)).exp_.x;
+    float quantize_a = unpackHalf2x16(packHalf2x16(vec2(1.0))).x;
+    vec3 _e126 = vec3(1.0);
+    vec3 quantize_b = vec3(unpackHalf2x16(packHalf2x16(_e126.xy)), unpackHalf2x16(packHalf2x16(_e126.zz)).x);
+    vec4 quantize_c = vec4(unpackHalf2x16(packHalf2x16(v.xy)), unpackHalf2x16(packHalf2x16(v.zw)));
+    int dot4_a = ( + ((int(16909060u) << 24) >> 24) * ((int(4294901244u) << 24) >> 24) + ((int(16909060u) << 16) >> 24) * ((int(4294901244u) << 16) >> 24) + ((int(16909060u) << 8) >> 24) * ((int(4294901244u) << 8) >> 24) + ((int(16909060u) << 0) >> 24) * ((int(4294901244u) << 0) >> 24));
+    uint dot4_b = ( + ((16909060u >> 0u) & 0xFFu) * ((4294901244u >> 0u) & 0xFFu) + ((16909060u >> 8u) & 0xFFu) * ((4294901244u >> 8u) & 0xFFu) + ((16909060u >> 16u) & 0xFFu) * ((4294901244u >> 16u) & 0xFFu) + ((16909060u >> 24u) & 0xFFu) * ((4294901244u >> 24u) & 0xFFu));
+    ivec4 _e138 = ivec4(-1, 2, -3, 4);
+    uint pack4_a = (uint(_e138.x & 0xFF) | uint(_e138.y & 0xFF) << 8 | uint(_e138.z & 0xFF) << 16 | uint(_e138.w & 0xFF) << 24);
+    uvec4 _e144 = uvec4(1u, 2u, 3u, 4u);
+    uint pack4_b = ((_e144.x & 0xFFu) | (_e144.y & 0xFFu) << 8 | (_e144.z & 0xFFu) << 16 | (_e144.w & 0xFFu) << 24);
+    ivec4 _e150 = ivec4(-200, 2, -3, 200);
+    uint pack4_c = (uint(clamp(_e150.x, -128, 127) & 0xFF) | uint(clamp(_e150.y, -128, 127) & 0xFF) << 8 | uint(clamp(_e150.z, -128, 127) & 0xFF) << 16 | uint(clamp(_e150.w, -128, 127) & 0xFF) << 24);
+    uvec4 _e156 = uvec4(1u, 2u, 3u, 400u);
+    uint pack4_d = ((min(_e156.x, 255u) & 0xFFu) | (min(_e156.y, 255u) & 0xFFu) << 8 | (min(_e156.z, 255u) & 0xFFu) << 16 | (min(_e156.w, 255u) & 0xFFu) << 24);
+    ivec4 unpack4_a = ((ivec4(int(16909060u)) << ivec4(24, 16, 8, 0)) >> 24);
+    uvec4 unpack4_b = ((uvec4(16909060u) >> uvec4(0u, 8u, 16u, 24u)) & 0xFFu);
 }
 
@@ -0,0 +1,76 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+struct NagaExternalTextureParams {
+    mat4x4 yuv_conversion_matrix;
+    uvec2 size;
+    uint num_planes;
+};
+uniform highp sampler2D _group_0_binding_0_fs;
+uniform highp sampler2D _group_0_binding_0_fs_plane1;
+uniform highp sampler2D _group_0_binding_0_fs_plane2;
+uniform NagaExternalTextureParams_block_0Fragment { NagaExternalTextureParams _group_0_binding_0_fs_params; };
+
+uniform highp sampler2D _group_0_binding_5_fs;
+
+vec4 nagaTextureSampleExternal(highp sampler2D plane0, highp sampler2D plane1, highp sampler2D plane2, NagaExternalTextureParams params, vec2 coords) {
+    vec2 half_texel0 = vec2(0.5) / vec2(textureSize(plane0, 0));
+    vec4 texel0 = textureLod(plane0, clamp(coords, half_texel0, vec2(1.0) - half_texel0), 0.0);
+    vec2 half_texel1 = vec2(0.5) / vec2(textureSize(plane1, 0));
+    vec4 texel1 = textureLod(plane1, clamp(coords, half_texel1, vec2(1.0) - half_texel1), 0.0);
+    vec2 half_texel2 = vec2(0.5) / vec2(textureSize(plane2, 0));
+    vec4 texel2 = textureLod(plane2, clamp(coords, half_texel2, vec2(1.0) - half_texel2), 0.0);
+    if (params.num_planes == 1u) {
+        return texel0;
+    }
+    float v = params.num_planes == 2u ? texel1.y : texel2.x;
+    return params.yuv_conversion_matrix * vec4(texel0.x, texel1.x, v, 1.0);
+}
+
+vec4 nagaTextureLoadExternal(highp sampler2D plane0, highp sampler2D plane1, highp sampler2D plane2, NagaExternalTextureParams params, uvec2 coords) {
+    uvec2 size0 = uvec2(textureSize(plane0, 0));
+    uvec2 size1 = uvec2(textureSize(plane1, 0));
+    uvec2 size2 = uvec2(textureSize(plane2, 0));
+    uvec2 coords0 = min(coords, size0 - 1u);
+    vec4 texel0 = texelFetch(plane0, ivec2(coords0), 0);
+    vec4 texel1 = texelFetch(plane1, ivec2(coords0 * size1 / size0), 0);
+    vec4 texel2 = texelFetch(plane2, ivec2(coords0 * size2 / size0), 0);
+    if (params.num_planes == 1u) {
+        return texel0;
+    }
+    float v = params.num_planes == 2u ? texel1.y : texel2.x;
+    return params.yuv_conversion_matrix * vec4(texel0.x, texel1.x, v, 1.0);
+}
+
+layout(location = 0) smooth in vec2 _vs2fs_location0;
+layout(location = 0) out vec4 _fs2p_location0;
+
+vec4 sample_external(vec2 coords) {
+    vec4 _e3 = nagaTextureSampleExternal(_group_0_binding_0_fs, _group_0_binding_0_fs_plane1, _group_0_binding_0_fs_plane2, _group_0_binding_0_fs_params, coords);
+    return _e3;
+}
+
+void main() {
+    vec2 uv = _vs2fs_location0;
+    vec4 a = vec4(0.0);
+    vec4 _e1 = sample_external(uv);
+    a = _e1;
+    vec4 _e7 = nagaTextureLoadExternal(_group_0_binding_0_fs, _group_0_binding_0_fs_plane1, _group_0_binding_0_fs_plane2, _group_0_binding_0_fs_params, uvec2(uvec2(1u, 2u)));
+    vec4 _e8 = a;
+    a = (_e8 + _e7);
+    vec4 _e14 = nagaTextureLoadExternal(_group_0_binding_0_fs, _group_0_binding_0_fs_plane1, _group_0_binding_0_fs_plane2, _group_0_binding_0_fs_params, uvec2(ivec2(3, 4)));
+    vec4 _e15 = a;
+    a = (_e15 + _e14);
+    vec4 _e19 = textureLod(_group_0_binding_5_fs, clamp(uv, vec2(0.5) / vec2(textureSize(_group_0_binding_5_fs, 0)), vec2(1.0) - vec2(0.5) / vec2(textureSize(_group_0_binding_5_fs, 0))), 0.0);
+    vec4 _e20 = a;
+    a = (_e20 + _e19);
+    uvec2 size = _group_0_binding_0_fs_params.size;
+    vec4 _e28 = a;
+    a = (_e28 + vec4(vec2(size), 0.0, 0.0));
+    vec4 _e30 = a;
+    _fs2p_location0 = _e30;
+    return;
+}
+
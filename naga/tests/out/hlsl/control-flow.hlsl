@@ -42,6 +42,7 @@ void main(uint3 global_id : SV_DispatchThreadID)
 
     DeviceMemoryBarrierWithGroupSync();
     GroupMemoryBarrierWithGroupSync();
+    DeviceMemoryBarrierWithGroupSync();
     switch(1) {
         default: {
             pos = 1;
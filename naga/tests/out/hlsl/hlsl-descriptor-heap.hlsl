@@ -0,0 +1,34 @@
+struct Material {
+    uint texture_index;
+    uint sampler_index;
+};
+
+struct FragmentIn {
+    float2 uv : LOC0;
+    nointerpolation uint index : LOC1;
+};
+
+Texture2D<float4> textures(uint index) { return ResourceDescriptorHeap[1 + index]; }
+Texture2D<float4> textures_non_uniform(uint index) { return ResourceDescriptorHeap[NonUniformResourceIndex(1 + index)]; }
+SamplerState samplers(uint index) { return SamplerDescriptorHeap[0 + index]; }
+SamplerState samplers_non_uniform(uint index) { return SamplerDescriptorHeap[NonUniformResourceIndex(0 + index)]; }
+cbuffer material : register(b0) { Material material; }
+Texture2D<float> depth_textures(uint index) { return ResourceDescriptorHeap[1001 + index]; }
+Texture2D<float> depth_textures_non_uniform(uint index) { return ResourceDescriptorHeap[NonUniformResourceIndex(1001 + index)]; }
+SamplerComparisonState shadow_sampler : register(s0, space1);
+
+struct FragmentInput_main {
+    float2 uv : LOC0;
+    nointerpolation uint index : LOC1;
+};
+
+float4 main(FragmentInput_main fragmentinput_main) : SV_Target0
+{
+    FragmentIn in_ = { fragmentinput_main.uv, fragmentinput_main.index };
+    uint _expr4 = material.texture_index;
+    uint _expr9 = material.sampler_index;
+    float4 base = textures(_expr4).Sample(samplers(_expr9), in_.uv);
+    float4 detail = textures_non_uniform(in_.index).Sample(samplers(0), in_.uv);
+    float shadow = depth_textures(3).SampleCmp(shadow_sampler, in_.uv, 0.5);
+    return ((base * detail) * shadow);
+}
@@ -240,6 +240,14 @@ float4 levels_queries() : SV_Position
     return (float(sum_1)).xxxx;
 }
 
+float4 nagaTextureSampleBaseClampToEdge(Texture2D<float4> tex, SamplerState samp, float2 coords)
+{
+    uint2 size;
+    tex.GetDimensions(size.x, size.y);
+    float2 half_texel = float2(0.5, 0.5) / float2(size);
+    return tex.SampleLevel(samp, clamp(coords, half_texel, float2(1.0, 1.0) - half_texel), 0.0);
+}
+
 float4 texture_sample() : SV_Target0
 {
     float4 a = (float4)0;
@@ -312,8 +320,11 @@ float4 texture_sample() : SV_Target0
     float4 _expr135 = image_cube_array.SampleBias(sampler_reg, float4(tc3_, 0), 2.0);
     float4 _expr136 = a;
     a = (_expr136 + _expr135);
-    float4 _expr138 = a;
-    return _expr138;
+    float4 _expr140 = nagaTextureSampleBaseClampToEdge(image_2d, sampler_reg, tc);
+    float4 _expr141 = a;
+    a = (_expr141 + _expr140);
+    float4 _expr143 = a;
+    return _expr143;
 }
 
 float texture_sample_comparison() : SV_Target0
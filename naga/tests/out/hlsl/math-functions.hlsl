@@ -110,4 +110,19 @@ void main()
     float frexp_b = naga_frexp(1.5).fract;
     int frexp_c = naga_frexp(1.5).exp_;
     int frexp_d = naga_frexp(float4(1.5, 1.5, 1.5, 1.5)).exp_.x;
+    float quantize_a = f16tof32(f32tof16(1.0));
+    float3 quantize_b = f16tof32(f32tof16((1.0).xxx));
+    float4 quantize_c = f16tof32(f32tof16(v));
+    int dot4_a = (((int(16909060u) << 24) >> 24) * ((int(4294901244u) << 24) >> 24) + ((int(16909060u) << 16) >> 24) * ((int(4294901244u) << 16) >> 24) + ((int(16909060u) << 8) >> 24) * ((int(4294901244u) << 8) >> 24) + ((int(16909060u) << 0) >> 24) * ((int(4294901244u) << 0) >> 24));
+    uint dot4_b = (((16909060u >> 0) & 0xFF) * ((4294901244u >> 0) & 0xFF) + ((16909060u >> 8) & 0xFF) * ((4294901244u >> 8) & 0xFF) + ((16909060u >> 16) & 0xFF) * ((4294901244u >> 16) & 0xFF) + ((16909060u >> 24) & 0xFF) * ((4294901244u >> 24) & 0xFF));
+    int4 _expr138 = int4(-1, 2, -3, 4);
+    uint pack4_a = uint((_expr138[0] & 0xFF) | (_expr138[1] & 0xFF) << 8 | (_expr138[2] & 0xFF) << 16 | (_expr138[3] & 0xFF) << 24);
+    uint4 _expr144 = uint4(1u, 2u, 3u, 4u);
+    uint pack4_b = uint((_expr144[0] & 0xFF) | (_expr144[1] & 0xFF) << 8 | (_expr144[2] & 0xFF) << 16 | (_expr144[3] & 0xFF) << 24);
+    int4 _expr150 = int4(-200, 2, -3, 200);
+    uint pack4_c = uint((clamp(_expr150[0], -128, 127) & 0xFF) | (clamp(_expr150[1], -128, 127) & 0xFF) << 8 | (clamp(_expr150[2], -128, 127) & 0xFF) << 16 | (clamp(_expr150[3], -128, 127) & 0xFF) << 24);
+    uint4 _expr156 = uint4(1u, 2u, 3u, 400u);
+    uint pack4_d = uint((min(_expr156[0], 255u) & 0xFF) | (min(_expr156[1], 255u) & 0xFF) << 8 | (min(_expr156[2], 255u) & 0xFF) << 16 | (min(_expr156[3], 255u) & 0xFF) << 24);
+    int4 unpack4_a = (int4(16909060u << 24, 16909060u << 16, 16909060u << 8, 16909060u) >> 24);
+    uint4 unpack4_b = (uint4(16909060u, 16909060u >> 8, 16909060u >> 16, 16909060u >> 24) & 0xFF);
 }
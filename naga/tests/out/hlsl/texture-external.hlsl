@@ -0,0 +1,83 @@
+struct NagaExternalTextureParams {
+    row_major float4x4 yuv_conversion_matrix;
+    uint2 size;
+    uint num_planes;
+    int _end_pad_0;
+};
+
+Texture2D<float4> tex : register(t0);
+Texture2D<float4> tex_plane1 : register(t1);
+Texture2D<float4> tex_plane2 : register(t2);
+cbuffer tex_params : register(b3) { NagaExternalTextureParams tex_params; }
+SamplerState samp : register(s4);
+Texture2D<float4> tex_2d : register(t5);
+
+struct FragmentInput_fragment_main {
+    float2 uv_1 : LOC0;
+};
+
+float4 nagaTextureSampleBaseClampToEdge(Texture2D<float4> tex, SamplerState samp, float2 coords)
+{
+    uint2 size;
+    tex.GetDimensions(size.x, size.y);
+    float2 half_texel = float2(0.5, 0.5) / float2(size);
+    return tex.SampleLevel(samp, clamp(coords, half_texel, float2(1.0, 1.0) - half_texel), 0.0);
+}
+
+float4 nagaTextureSampleExternal(Texture2D<float4> plane0, Texture2D<float4> plane1, Texture2D<float4> plane2, NagaExternalTextureParams params, SamplerState samp, float2 coords)
+{
+    float4 texel0 = nagaTextureSampleBaseClampToEdge(plane0, samp, coords);
+    float4 texel1 = nagaTextureSampleBaseClampToEdge(plane1, samp, coords);
+    float4 texel2 = nagaTextureSampleBaseClampToEdge(plane2, samp, coords);
+    if (params.num_planes == 1u) {
+        return texel0;
+    }
+    float v = params.num_planes == 2u ? texel1.y : texel2.x;
+    return mul(float4(texel0.x, texel1.x, v, 1.0), params.yuv_conversion_matrix);
+}
+
+float4 sample_external(float2 coords)
+{
+    float4 _expr3 = nagaTextureSampleExternal(tex, tex_plane1, tex_plane2, tex_params, samp, coords);
+    return _expr3;
+}
+
+float4 nagaTextureLoadExternal(Texture2D<float4> plane0, Texture2D<float4> plane1, Texture2D<float4> plane2, NagaExternalTextureParams params, uint2 coords)
+{
+    uint2 size0, size1, size2;
+    plane0.GetDimensions(size0.x, size0.y);
+    plane1.GetDimensions(size1.x, size1.y);
+    plane2.GetDimensions(size2.x, size2.y);
+    uint2 coords0 = min(coords, size0 - 1u);
+    float4 texel0 = plane0.Load(int3(coords0, 0));
+    float4 texel1 = plane1.Load(int3(coords0 * size1 / size0, 0));
+    float4 texel2 = plane2.Load(int3(coords0 * size2 / size0, 0));
+    if (params.num_planes == 1u) {
+        return texel0;
+    }
+    float v = params.num_planes == 2u ? texel1.y : texel2.x;
+    return mul(float4(texel0.x, texel1.x, v, 1.0), params.yuv_conversion_matrix);
+}
+
+float4 fragment_main(FragmentInput_fragment_main fragmentinput_fragment_main) : SV_Target0
+{
+    float2 uv = fragmentinput_fragment_main.uv_1;
+    float4 a = (float4)0;
+
+    const float4 _e1 = sample_external(uv);
+    a = _e1;
+    float4 _expr7 = nagaTextureLoadExternal(tex, tex_plane1, tex_plane2, tex_params, uint2(uint2(1u, 2u)));
+    float4 _expr8 = a;
+    a = (_expr8 + _expr7);
+    float4 _expr14 = nagaTextureLoadExternal(tex, tex_plane1, tex_plane2, tex_params, uint2(int2(3, 4)));
+    float4 _expr15 = a;
+    a = (_expr15 + _expr14);
+    float4 _expr19 = nagaTextureSampleBaseClampToEdge(tex_2d, samp, uv);
+    float4 _expr20 = a;
+    a = (_expr20 + _expr19);
+    uint2 size = tex_params.size;
+    float4 _expr28 = a;
+    a = (_expr28 + float4(float2(size), 0.0, 0.0));
+    float4 _expr30 = a;
+    return _expr30;
+}
@@ -0,0 +1,91 @@
+// Host-side types generated by naga. Do not edit by hand.
+
+/// An array element of type `[f32; 3]`, followed by 4 bytes of padding.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Padded {
+    pub value: [f32; 3],
+    pub _pad: [u8; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Light {
+    pub position: [f32; 3],
+    pub range: f32,
+    pub color: [f32; 3],
+    pub _pad0: [u8; 4],
+}
+
+const _: () = assert!(core::mem::size_of::<Light>() == 32);
+const _: () = {
+    let value = core::mem::MaybeUninit::<Light>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).position).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+    assert!(unsafe { core::ptr::addr_of!((*base).range).cast::<u8>().offset_from(base.cast::<u8>()) } == 12);
+    assert!(unsafe { core::ptr::addr_of!((*base).color).cast::<u8>().offset_from(base.cast::<u8>()) } == 16);
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+#[allow(non_snake_case)]
+pub struct Globals {
+    pub viewProj: [[f32; 4]; 4],
+    pub normal: [[f32; 4]; 3],
+    pub time: f32,
+    pub _pad0: [u8; 12],
+    pub lights: [Light; 2],
+    pub weights: [Padded; 2],
+    pub frame: u32,
+    pub _pad1: [u8; 12],
+}
+
+const _: () = assert!(core::mem::size_of::<Globals>() == 240);
+const _: () = {
+    let value = core::mem::MaybeUninit::<Globals>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).viewProj).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+    assert!(unsafe { core::ptr::addr_of!((*base).normal).cast::<u8>().offset_from(base.cast::<u8>()) } == 64);
+    assert!(unsafe { core::ptr::addr_of!((*base).time).cast::<u8>().offset_from(base.cast::<u8>()) } == 112);
+    assert!(unsafe { core::ptr::addr_of!((*base).lights).cast::<u8>().offset_from(base.cast::<u8>()) } == 128);
+    assert!(unsafe { core::ptr::addr_of!((*base).weights).cast::<u8>().offset_from(base.cast::<u8>()) } == 192);
+    assert!(unsafe { core::ptr::addr_of!((*base).frame).cast::<u8>().offset_from(base.cast::<u8>()) } == 224);
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Particles {
+    pub count: u32,
+    pub _pad0: [u8; 4],
+    pub bounds: [[f32; 2]; 3],
+    // `particles`, a runtime-sized array of `[f32; 4]` with stride 16, follows at offset 32.
+}
+
+const _: () = assert!(core::mem::size_of::<Particles>() == 32);
+const _: () = {
+    let value = core::mem::MaybeUninit::<Particles>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).count).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+    assert!(unsafe { core::ptr::addr_of!((*base).bounds).cast::<u8>().offset_from(base.cast::<u8>()) } == 8);
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct VertexOutput {
+    pub position: [f32; 4],
+    pub visible: u32,
+    pub _pad0: [u8; 12],
+}
+
+const _: () = assert!(core::mem::size_of::<VertexOutput>() == 32);
+const _: () = {
+    let value = core::mem::MaybeUninit::<VertexOutput>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).position).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+    assert!(unsafe { core::ptr::addr_of!((*base).visible).cast::<u8>().offset_from(base.cast::<u8>()) } == 16);
+};
+
+pub const GLOBALS_GROUP: u32 = 0;
+pub const GLOBALS_BINDING: u32 = 0;
+pub const PARTICLE_BUFFER_GROUP: u32 = 1;
+pub const PARTICLE_BUFFER_BINDING: u32 = 3;
@@ -0,0 +1,78 @@
+// Host-side types generated by naga. Do not edit by hand.
+
+/// An array element of type `[f32; 3]`, followed by 4 bytes of padding.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Padded {
+    pub value: [f32; 3],
+    pub _pad: [u8; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct S {
+    pub a: [f32; 3],
+    pub _pad0: [u8; 4],
+}
+
+const _: () = assert!(core::mem::size_of::<S>() == 16);
+const _: () = {
+    let value = core::mem::MaybeUninit::<S>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).a).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Test {
+    pub a: S,
+    pub b: f32,
+    pub _pad0: [u8; 12],
+}
+
+const _: () = assert!(core::mem::size_of::<Test>() == 32);
+const _: () = {
+    let value = core::mem::MaybeUninit::<Test>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).a).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+    assert!(unsafe { core::ptr::addr_of!((*base).b).cast::<u8>().offset_from(base.cast::<u8>()) } == 16);
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Test2 {
+    pub a: [Padded; 2],
+    pub b: f32,
+    pub _pad0: [u8; 12],
+}
+
+const _: () = assert!(core::mem::size_of::<Test2>() == 48);
+const _: () = {
+    let value = core::mem::MaybeUninit::<Test2>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).a).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+    assert!(unsafe { core::ptr::addr_of!((*base).b).cast::<u8>().offset_from(base.cast::<u8>()) } == 32);
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Test3 {
+    pub a: [[f32; 4]; 4],
+    pub b: f32,
+    pub _pad0: [u8; 12],
+}
+
+const _: () = assert!(core::mem::size_of::<Test3>() == 80);
+const _: () = {
+    let value = core::mem::MaybeUninit::<Test3>::uninit();
+    let base = value.as_ptr();
+    assert!(unsafe { core::ptr::addr_of!((*base).a).cast::<u8>().offset_from(base.cast::<u8>()) } == 0);
+    assert!(unsafe { core::ptr::addr_of!((*base).b).cast::<u8>().offset_from(base.cast::<u8>()) } == 64);
+};
+
+pub const INPUT1_GROUP: u32 = 0;
+pub const INPUT1_BINDING: u32 = 0;
+pub const INPUT2_GROUP: u32 = 0;
+pub const INPUT2_BINDING: u32 = 1;
+pub const INPUT3_GROUP: u32 = 0;
+pub const INPUT3_BINDING: u32 = 2;
@@ -0,0 +1,30 @@
+struct Counters {
+    counts: array<atomic<u32>, 4>,
+    total: atomic<i32>,
+}
+
+const SIZE: u32 = 2u;
+const FLAG: bool = true;
+const TWICE: u32 = 4u;
+const PICK: u32 = 4u;
+
+@group(0) @binding(0) 
+var<storage, read_write> counters: Counters;
+var<workgroup> flag: atomic<u32>;
+
+fn main_1() {
+    let _e11 = atomicAdd((&counters.counts[1i]), TWICE);
+    let _e13 = atomicMax((&counters.total), 3i);
+    let _e15 = atomicAdd((&counters.total), 1i);
+    let _e18 = atomicCompareExchangeWeak((&counters.counts[0i]), _e11, 5u);
+    atomicStore((&flag), _e18.old_value);
+    let _e20 = atomicLoad((&flag));
+    let _e21 = atomicExchange((&flag), _e20);
+    let _e24 = atomicOr((&counters.counts[2i]), PICK);
+    return;
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    main_1();
+}
@@ -0,0 +1,54 @@
+struct Light {
+    position: vec3<f32>,
+    range: f32,
+    color: vec3<f32>,
+}
+
+struct Globals {
+    viewProj: mat4x4<f32>,
+    normal: mat3x3<f32>,
+    time: f32,
+    lights: array<Light, 2>,
+    weights: array<vec3<f32>, 2>,
+    frame: u32,
+}
+
+struct Particles {
+    count: atomic<u32>,
+    bounds: array<vec2<f32>, 3>,
+    particles: array<vec4<f32>>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) visible: u32,
+}
+
+struct Flags {
+    enabled: bool,
+    value: u32,
+}
+
+@group(0) @binding(0) 
+var<uniform> globals: Globals;
+@group(1) @binding(3) 
+var<storage, read_write> particleBuffer: Particles;
+
+@vertex 
+fn main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var flags: Flags = Flags(true, 1u);
+
+    let _e8 = flags.value;
+    let _e9 = atomicAdd((&particleBuffer.count), _e8);
+    let p = particleBuffer.particles[index];
+    let _e16 = globals.viewProj;
+    let _e19 = globals.normal;
+    let _e24 = globals.time;
+    let position = (_e16 * vec4<f32>(((_e19 * p.xyz) * _e24), 1f));
+    let _e33 = globals.weights[1].x;
+    let _e38 = particleBuffer.bounds[2].y;
+    let weight = (_e33 + _e38);
+    let _e44 = globals.lights[1].color;
+    let _e49 = globals.frame;
+    return VertexOutput((position + vec4<f32>(_e44, weight)), _e49);
+}
@@ -44,4 +44,15 @@ fn main() {
     let frexp_b = frexp(1.5f).fract;
     let frexp_c = frexp(1.5f).exp;
     let frexp_d = frexp(vec4<f32>(1.5f, 1.5f, 1.5f, 1.5f)).exp.x;
+    let quantize_a = quantizeToF16(1f);
+    let quantize_b = quantizeToF16(vec3(1f));
+    let quantize_c = quantizeToF16(v);
+    let dot4_a = dot4I8Packed(16909060u, 4294901244u);
+    let dot4_b = dot4U8Packed(16909060u, 4294901244u);
+    let pack4_a = pack4xI8(vec4<i32>(-1i, 2i, -3i, 4i));
+    let pack4_b = pack4xU8(vec4<u32>(1u, 2u, 3u, 4u));
+    let pack4_c = pack4xI8Clamp(vec4<i32>(-200i, 2i, -3i, 200i));
+    let pack4_d = pack4xU8Clamp(vec4<u32>(1u, 2u, 3u, 400u));
+    let unpack4_a = unpack4xI8(16909060u);
+    let unpack4_b = unpack4xU8(16909060u);
 }
@@ -0,0 +1,40 @@
+struct TaskPayload {
+    color: vec4<f32>,
+    visible: bool,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+struct PrimitiveOutput {
+    @builtin(triangle_indices) indices: vec3<u32>,
+    @builtin(cull_primitive) cull: bool,
+}
+
+var<task_payload> taskPayload: TaskPayload;
+var<workgroup> workgroupData: f32;
+
+@task @workgroup_size(1, 1, 1) @payload(taskPayload) 
+fn ts_main() -> @builtin(mesh_task_size) vec3<u32> {
+    workgroupData = 1f;
+    taskPayload.color = vec4<f32>(1f, 1f, 0f, 1f);
+    taskPayload.visible = true;
+    return vec3<u32>(3u, 1u, 1u);
+}
+
+@mesh @workgroup_size(1, 1, 1) @output_topology(triangles) @vertex_output(VertexOutput, 3) @primitive_output(PrimitiveOutput, 1) @payload(taskPayload) 
+fn ms_main(@builtin(local_invocation_index) index: u32, @builtin(global_invocation_id) id: vec3<u32>) {
+    setMeshOutputs(3u, 1u);
+    workgroupData = 2f;
+    let _e9 = taskPayload.color;
+    setVertex(0u, VertexOutput(vec4<f32>(0f, 1f, 0f, 1f), (vec4<f32>(0f, 1f, 0f, 1f) * _e9)));
+    let _e25 = taskPayload.color;
+    setVertex(1u, VertexOutput(vec4<f32>(-1f, -1f, 0f, 1f), (vec4<f32>(0f, 0f, 1f, 1f) * _e25)));
+    let _e41 = taskPayload.color;
+    setVertex(2u, VertexOutput(vec4<f32>(1f, -1f, 0f, 1f), (vec4<f32>(1f, 0f, 0f, 1f) * _e41)));
+    let _e61 = taskPayload.visible;
+    setPrimitive(0u, PrimitiveOutput(vec3<u32>(0u, 1u, 2u), !(_e61)));
+    return;
+}
@@ -0,0 +1,33 @@
+@group(0) @binding(0) 
+var tex: texture_external;
+@group(0) @binding(4) 
+var samp: sampler;
+@group(0) @binding(5) 
+var tex_2d: texture_2d<f32>;
+
+fn sample_external(coords: vec2<f32>) -> vec4<f32> {
+    let _e3 = textureSampleBaseClampToEdge(tex, samp, coords);
+    return _e3;
+}
+
+@fragment 
+fn fragment_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    var a: vec4<f32>;
+
+    let _e1 = sample_external(uv);
+    a = _e1;
+    let _e7 = textureLoad(tex, vec2<u32>(1u, 2u));
+    let _e8 = a;
+    a = (_e8 + _e7);
+    let _e14 = textureLoad(tex, vec2<i32>(3i, 4i));
+    let _e15 = a;
+    a = (_e15 + _e14);
+    let _e19 = textureSampleBaseClampToEdge(tex_2d, samp, uv);
+    let _e20 = a;
+    a = (_e20 + _e19);
+    let size = textureDimensions(tex);
+    let _e28 = a;
+    a = (_e28 + vec4<f32>(vec2<f32>(size), 0f, 0f));
+    let _e30 = a;
+    return _e30;
+}
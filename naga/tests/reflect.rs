@@ -0,0 +1,181 @@
+/*!
+Tests for entry point reflection.
+*/
+
+#![cfg(feature = "wgsl-in")]
+
+use naga::reflect::{self, ResourceType};
+use naga::{valid, AddressSpace, ResourceBinding, StorageAccess};
+
+fn reflect(source: &str) -> Vec<reflect::EntryPoint> {
+    let module = naga::front::wgsl::parse_str(source).expect("source ought to parse");
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+    reflect::reflect(&module, &info)
+}
+
+#[test]
+fn render_pipeline() {
+    let entry_points = reflect(
+        r#"
+        struct Globals {
+            transform: mat4x4<f32>,
+            tint: vec4<f32>,
+        }
+
+        struct VertexInput {
+            @location(1) uv: vec2<f32>,
+            @location(0) position: vec3<f32>,
+        }
+
+        struct VertexOutput {
+            @builtin(position) position: vec4<f32>,
+            @location(0) @interpolate(flat) layer: u32,
+            @location(1) uv: vec2<f32>,
+        }
+
+        struct PushConstants {
+            scale: f32,
+        }
+
+        @group(0) @binding(0) var<uniform> globals: Globals;
+        @group(1) @binding(0) var color: texture_2d_array<f32>;
+        @group(1) @binding(1) var color_sampler: sampler;
+        var<push_constant> pc: PushConstants;
+
+        @vertex
+        fn vs_main(input: VertexInput, @builtin(instance_index) instance: u32) -> VertexOutput {
+            let position = globals.transform * vec4<f32>(input.position * pc.scale, 1.0);
+            return VertexOutput(position, instance, input.uv);
+        }
+
+        @fragment
+        fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+            return textureSample(color, color_sampler, input.uv, input.layer) * globals.tint;
+        }
+        "#,
+    );
+
+    let [ref vs, ref fs] = entry_points[..] else {
+        panic!("expected two entry points, got {entry_points:#?}");
+    };
+
+    assert_eq!(vs.name, "vs_main");
+    assert_eq!(vs.stage, naga::ShaderStage::Vertex);
+    assert_eq!(vs.push_constants, Some(0..4));
+    assert_eq!(vs.resources.len(), 1);
+    assert_eq!(vs.resources[0].name.as_deref(), Some("globals"));
+    assert_eq!(
+        vs.resources[0].ty,
+        ResourceType::Buffer {
+            space: AddressSpace::Uniform,
+            min_size: 80
+        }
+    );
+    assert_eq!(
+        vs.inputs
+            .iter()
+            .map(|v| (v.name.as_deref(), v.location, v.size))
+            .collect::<Vec<_>>(),
+        [
+            (Some("position"), 0, Some(naga::VectorSize::Tri)),
+            (Some("uv"), 1, Some(naga::VectorSize::Bi)),
+        ]
+    );
+    assert_eq!(vs.input_built_ins, [naga::BuiltIn::InstanceIndex]);
+    assert_eq!(
+        vs.output_built_ins,
+        [naga::BuiltIn::Position { invariant: false }]
+    );
+    assert_eq!(vs.outputs[0].scalar, naga::Scalar::U32);
+    assert_eq!(vs.outputs[0].interpolation, Some(naga::Interpolation::Flat));
+
+    assert_eq!(fs.push_constants, None);
+    assert_eq!(
+        fs.resources
+            .iter()
+            .map(|r| r.binding.clone())
+            .collect::<Vec<_>>(),
+        [
+            ResourceBinding {
+                group: 0,
+                binding: 0
+            },
+            ResourceBinding {
+                group: 1,
+                binding: 0
+            },
+            ResourceBinding {
+                group: 1,
+                binding: 1
+            },
+        ]
+    );
+    assert!(matches!(
+        fs.resources[1].ty,
+        ResourceType::Image {
+            dim: naga::ImageDimension::D2,
+            arrayed: true,
+            class: naga::ImageClass::Sampled { .. },
+        }
+    ));
+    assert_eq!(
+        fs.resources[2].ty,
+        ResourceType::Sampler { comparison: false }
+    );
+    assert_eq!(fs.outputs.len(), 1);
+    assert_eq!(fs.outputs[0].size, Some(naga::VectorSize::Quad));
+}
+
+#[test]
+fn compute_pipeline() {
+    let entry_points = reflect(
+        r#"
+        @group(0) @binding(0) var<storage> input: array<vec2<f32>>;
+        @group(0) @binding(1) var<storage, read_write> output: array<f32>;
+        @group(0) @binding(2) var<storage, read_write> unused: array<f32>;
+        @group(0) @binding(3) var images: binding_array<texture_storage_2d<rgba8unorm, write>, 4>;
+
+        @compute @workgroup_size(64, 2)
+        fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+            output[id.x] = length(input[id.x]);
+            textureStore(images[id.y], vec2<u32>(id.xy), vec4<f32>(1.0));
+        }
+        "#,
+    );
+
+    let main = &entry_points[0];
+    assert_eq!(main.workgroup_size, [64, 2, 1]);
+    assert_eq!(
+        main.resources
+            .iter()
+            .map(|r| (r.name.as_deref().unwrap(), r.ty))
+            .collect::<Vec<_>>()[..2],
+        [
+            (
+                "input",
+                ResourceType::Buffer {
+                    space: AddressSpace::Storage {
+                        access: StorageAccess::LOAD
+                    },
+                    min_size: 8
+                }
+            ),
+            (
+                "output",
+                ResourceType::Buffer {
+                    space: AddressSpace::Storage {
+                        access: StorageAccess::LOAD | StorageAccess::STORE
+                    },
+                    min_size: 4
+                }
+            ),
+        ]
+    );
+    assert_eq!(main.resources.len(), 3);
+    let images = &main.resources[2];
+    assert!(images.is_binding_array);
+    assert_eq!(images.count.map(|count| count.get()), Some(4));
+    assert!(images.usage.contains(valid::GlobalUse::WRITE));
+}
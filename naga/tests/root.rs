@@ -1,4 +1,13 @@
+mod compact;
 mod example_wgsl;
+mod glsl_es100;
+mod interp;
+mod link;
+mod opt;
+mod reflect;
+mod rust_backend;
 mod snapshots;
+mod source_map;
 mod spirv_capabilities;
 mod wgsl_errors;
+mod wgsl_format;
@@ -0,0 +1,30 @@
+/*!
+Test that the Rust backend's output compiles.
+
+The generated code asserts its own layout at compile time, so compiling it
+also checks that the types are laid out as the shader expects.
+*/
+
+#![allow(dead_code)]
+
+mod host_structs {
+    include!("out/rust/host-structs.rs");
+}
+
+mod padding {
+    include!("out/rust/padding.rs");
+}
+
+#[test]
+fn pod() {
+    let mut test = padding::Test2::zeroed();
+    test.a[1].value = [1.0, 2.0, 3.0];
+    test.b = 4.0;
+
+    let bytes = bytemuck::bytes_of(&test);
+    assert_eq!(bytes.len(), 48);
+    assert_eq!(&bytes[16..20], &1.0f32.to_ne_bytes());
+    assert_eq!(&bytes[32..36], &4.0f32.to_ne_bytes());
+}
+
+use bytemuck::Zeroable;
@@ -22,6 +22,7 @@ bitflags::bitflags! {
         const DOT = 0x20;
         const HLSL = 0x40;
         const WGSL = 0x80;
+        const RUST = 0x100;
     }
 }
 
@@ -49,6 +50,10 @@ struct SpirvOutParameters {
     #[serde(default)]
     separate_entry_points: bool,
     #[serde(default)]
+    vulkan_memory_model: bool,
+    #[serde(default)]
+    demote_to_helper_invocation: bool,
+    #[serde(default)]
     #[cfg(all(feature = "deserialize", feature = "spv-out"))]
     binding_map: naga::back::spv::BindingMap,
 }
@@ -386,6 +391,14 @@ fn check_targets(
             write_output_wgsl(input, module, &info, &params.wgsl);
         }
     }
+    #[cfg(feature = "rust-out")]
+    {
+        if targets.contains(Targets::RUST) {
+            let string = naga::back::rust::write_string(module, &Default::default())
+                .expect("Rust write failed");
+            input.write_output_file("rust", "rs", string);
+        }
+    }
 }
 
 #[cfg(feature = "spv-out")]
@@ -408,6 +421,14 @@ fn write_output_spv(
     );
     flags.set(spv::WriterFlags::FORCE_POINT_SIZE, params.force_point_size);
     flags.set(spv::WriterFlags::CLAMP_FRAG_DEPTH, params.clamp_frag_depth);
+    flags.set(
+        spv::WriterFlags::VULKAN_MEMORY_MODEL,
+        params.vulkan_memory_model,
+    );
+    flags.set(
+        spv::WriterFlags::DEMOTE_TO_HELPER_INVOCATION,
+        params.demote_to_helper_invocation,
+    );
 
     let options = spv::Options {
         lang_version: (params.version.0, params.version.1),
@@ -565,6 +586,9 @@ fn write_output_hlsl(
             naga::ShaderStage::Vertex => &mut config.vertex,
             naga::ShaderStage::Fragment => &mut config.fragment,
             naga::ShaderStage::Compute => &mut config.compute,
+            naga::ShaderStage::Task | naga::ShaderStage::Mesh => {
+                unreachable!("the HLSL backend does not support task and mesh shaders")
+            }
         }
         .push(hlsl_snapshots::ConfigItem {
             entry_point: name.clone(),
@@ -699,8 +723,14 @@ fn convert_wgsl() {
         ("atomicCompareExchange", Targets::SPIRV | Targets::WGSL),
         (
             "padding",
-            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+            Targets::SPIRV
+                | Targets::METAL
+                | Targets::GLSL
+                | Targets::HLSL
+                | Targets::WGSL
+                | Targets::RUST,
         ),
+        ("host-structs", Targets::WGSL | Targets::RUST),
         ("pointers", Targets::SPIRV | Targets::WGSL),
         (
             "control-flow",
@@ -735,6 +765,10 @@ fn convert_wgsl() {
             "texture-arg",
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
+        (
+            "texture-external",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
         ("cubeArrayShadow", Targets::GLSL),
         (
             "math-functions",
@@ -749,6 +783,8 @@ fn convert_wgsl() {
             Targets::WGSL | Targets::SPIRV, //TODO: more backends, eventually merge into "binding-arrays"
         ),
         ("resource-binding-map", Targets::METAL),
+        ("msl-argument-buffers", Targets::METAL),
+        ("hlsl-descriptor-heap", Targets::HLSL),
         ("multiview", Targets::SPIRV | Targets::GLSL | Targets::WGSL),
         ("multiview_webgl", Targets::GLSL),
         (
@@ -770,7 +806,10 @@ fn convert_wgsl() {
         ("sprite", Targets::SPIRV),
         ("force_point_size_vertex_shader_webgl", Targets::GLSL),
         ("invariant", Targets::GLSL),
-        ("ray-query", Targets::SPIRV | Targets::METAL),
+        ("glsl-es100", Targets::GLSL),
+        ("spv-vulkan-memory-model", Targets::SPIRV),
+        ("ray-query", Targets::SPIRV | Targets::METAL | Targets::HLSL),
+        ("mesh-shader", Targets::WGSL),
         ("hlsl-keyword", Targets::HLSL),
         (
             "constructors",
@@ -881,6 +920,7 @@ fn convert_spv_all() {
         true,
         Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
     );
+    convert_spv("atomics", true, Targets::IR | Targets::WGSL);
 }
 
 #[cfg(feature = "glsl-in")]
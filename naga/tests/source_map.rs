@@ -0,0 +1,123 @@
+/*!
+Tests for the source maps produced by the text backends.
+*/
+
+#![cfg(feature = "wgsl-in")]
+
+use naga::{back::SourceMap, valid};
+
+const SOURCE: &str = "
+fn double(x: i32) -> i32 {
+    return x * 2;
+}
+
+@compute @workgroup_size(1)
+fn main() {
+    var y = double(21);
+    if y > 40 {
+        y = 0;
+    }
+}
+";
+
+/// Check that each line of `output` that mentions `needle` maps back to a
+/// span of [`SOURCE`] that starts with `expected`.
+#[allow(dead_code)]
+fn check(output: &str, source_map: &SourceMap, needle: &str, expected: &str) {
+    let mut found = false;
+    for (index, line) in output.lines().enumerate() {
+        let Some(column) = line.find(needle) else {
+            continue;
+        };
+        found = true;
+        let span = source_map
+            .lookup(index as u32 + 1, column as u32 + 1)
+            .unwrap_or_else(|| panic!("no span for line {}: {line}", index + 1));
+        let text = &SOURCE[span];
+        assert!(
+            text.starts_with(expected),
+            "line {} `{line}` maps to `{text}`, expected `{expected}`",
+            index + 1,
+        );
+    }
+    assert!(found, "`{needle}` not found in output:\n{output}");
+}
+
+#[allow(dead_code)]
+fn parse() -> (naga::Module, valid::ModuleInfo) {
+    let module = naga::front::wgsl::parse_str(SOURCE).expect("source ought to parse");
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+    (module, info)
+}
+
+#[cfg(feature = "wgsl-out")]
+#[test]
+fn wgsl() {
+    let (module, info) = parse();
+    let mut writer =
+        naga::back::wgsl::Writer::new(String::new(), naga::back::wgsl::WriterFlags::empty());
+    writer.write(&module, &info).unwrap();
+    let source_map = writer.source_map().clone();
+    let output = writer.finish();
+    assert_eq!(source_map.lookup(0, 0), None);
+    check(&output, &source_map, "fn double", "fn double");
+    check(&output, &source_map, "* 2", "return x * 2");
+    check(&output, &source_map, "if ", "if y > 40");
+    check(&output, &source_map, "y = 0", "y = 0");
+}
+
+#[cfg(feature = "hlsl-out")]
+#[test]
+fn hlsl() {
+    let (module, info) = parse();
+    let mut output = String::new();
+    let options = naga::back::hlsl::Options::default();
+    let mut writer = naga::back::hlsl::Writer::new(&mut output, &options);
+    writer.write(&module, &info).unwrap();
+    let source_map = writer.source_map().clone();
+    check(&output, &source_map, "* 2", "return x * 2");
+    check(&output, &source_map, "if ", "if y > 40");
+}
+
+#[cfg(feature = "msl-out")]
+#[test]
+fn msl() {
+    let (module, info) = parse();
+    let pipeline_options = naga::back::msl::PipelineOptions::default();
+    let mut writer = naga::back::msl::Writer::new(String::new());
+    writer
+        .write(&module, &info, &Default::default(), &pipeline_options)
+        .unwrap();
+    let source_map = writer.source_map().clone();
+    let output = writer.finish();
+    check(&output, &source_map, "* 2", "return x * 2");
+    check(&output, &source_map, "if ", "if y > 40");
+}
+
+#[cfg(feature = "glsl-out")]
+#[test]
+fn glsl() {
+    let (module, info) = parse();
+    let mut output = String::new();
+    let options = naga::back::glsl::Options::default();
+    let pipeline_options = naga::back::glsl::PipelineOptions {
+        shader_stage: naga::ShaderStage::Compute,
+        entry_point: "main".to_string(),
+        multiview: None,
+    };
+    let mut writer = naga::back::glsl::Writer::new(
+        &mut output,
+        &module,
+        &info,
+        &options,
+        &pipeline_options,
+        naga::proc::BoundsCheckPolicies::default(),
+    )
+    .unwrap();
+    writer.write().unwrap();
+    let source_map = writer.source_map().clone();
+    check(&output, &source_map, "* 2", "return x * 2");
+    check(&output, &source_map, "if ", "if y > 40");
+}
@@ -176,3 +176,50 @@ fn storage_image_formats() {
         "#,
     );
 }
+
+#[test]
+fn mesh_shader() {
+    use naga::back::spv;
+    use naga::valid;
+
+    let source = r#"
+        struct VertexOutput {
+            @builtin(position) position: vec4<f32>,
+        }
+        struct PrimitiveOutput {
+            @builtin(point_index) index: u32,
+        }
+
+        @mesh
+        @output_topology(points)
+        @vertex_output(VertexOutput, 1)
+        @primitive_output(PrimitiveOutput, 1)
+        @workgroup_size(1)
+        fn m() {
+            setMeshOutputs(1u, 1u);
+            setVertex(0u, VertexOutput(vec4(0.0)));
+            setPrimitive(0u, PrimitiveOutput(0u));
+        }
+    "#;
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+
+    // `SPV_EXT_mesh_shader` is not available before SPIR-V 1.4.
+    let mut words = vec![];
+    let mut writer = spv::Writer::new(&spv::Options::default()).unwrap();
+    assert!(writer
+        .write(&module, &info, None, &None, &mut words)
+        .is_err());
+
+    let options = spv::Options {
+        lang_version: (1, 4),
+        ..Default::default()
+    };
+    let mut writer = spv::Writer::new(&options).unwrap();
+    writer
+        .write(&module, &info, None, &None, &mut words)
+        .unwrap();
+    assert!(writer.get_capabilities_used().contains(&Ca::MeshShadingEXT));
+}
@@ -1303,6 +1303,39 @@ fn missing_bindings2() {
     }
 }
 
+#[test]
+fn external_texture_binding_collision() {
+    check_validation! {
+        "
+        @group(0) @binding(0) var tex: texture_external;
+        @group(0) @binding(2) var samp: sampler;
+
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return textureSampleBaseClampToEdge(tex, samp, vec2<f32>());
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            stage: naga::ShaderStage::Fragment,
+            source: naga::valid::EntryPointError::BindingCollision(_),
+            ..
+        })
+    }
+
+    check_validation! {
+        "
+        @group(0) @binding(0) var tex: texture_external;
+        @group(0) @binding(4) var samp: sampler;
+
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return textureSampleBaseClampToEdge(tex, samp, vec2<f32>());
+        }
+        ":
+        Ok(_)
+    }
+}
+
 #[test]
 fn invalid_access() {
     check_validation! {
@@ -2098,3 +2131,101 @@ fn compaction_preserves_spans() {
         panic!("Error message has wrong span:\n\n{err:#?}");
     }
 }
+
+#[test]
+fn recover_from_syntax_errors() {
+    check(
+        r#"
+            fn f() {
+                let a = 1 +;
+                let b = vec2(1, 2;
+                var c = 3;
+                if (c > 1 {
+                    c = 4;
+                }
+            }
+
+            var<private> x: i32 = ;
+
+            fn g() -> i32 {
+                return );
+            }
+        "#,
+        r#"error: expected expression, found ';'
+  ┌─ wgsl:3:28
+  │
+3 │                 let a = 1 +;
+  │                            ^ expected expression
+
+error: expected ')', found ';'
+  ┌─ wgsl:4:34
+  │
+4 │                 let b = vec2(1, 2;
+  │                                  ^ expected ')'
+
+error: expected ')', found '{'
+  ┌─ wgsl:6:27
+  │
+6 │                 if (c > 1 {
+  │                           ^ expected ')'
+
+error: expected expression, found ';'
+   ┌─ wgsl:11:35
+   │
+11 │             var<private> x: i32 = ;
+   │                                   ^ expected expression
+
+error: expected expression, found ')'
+   ┌─ wgsl:14:24
+   │
+14 │                 return );
+   │                        ^ expected expression
+
+"#,
+    );
+}
+
+#[test]
+fn recover_from_lowering_errors() {
+    // `h` uses `f`, so any errors in it are not reported.
+    check(
+        r#"
+            fn f() -> i32 {
+                let x: i32 = 1u;
+                return x;
+            }
+
+            fn g() {
+                let a: f32 = true;
+            }
+
+            fn h() -> i32 {
+                return f() + undefined;
+            }
+        "#,
+        r#"error: the type of `x` is expected to be `i32`, but got `u32`
+  ┌─ wgsl:3:21
+  │
+3 │                 let x: i32 = 1u;
+  │                     ^ definition of `x`
+
+error: the type of `a` is expected to be `f32`, but got `bool`
+  ┌─ wgsl:8:21
+  │
+8 │                 let a: f32 = true;
+  │                     ^ definition of `a`
+
+"#,
+    );
+}
+
+#[test]
+fn recover_error_locations() {
+    let source = "fn f() { let a = ; }\nfn g() { let b = ; }\n";
+    let error = naga::front::wgsl::parse_str(source).expect_err("source ought to fail");
+    let lines: Vec<_> = error
+        .errors()
+        .map(|error| error.location(source).map(|location| location.line_number))
+        .collect();
+    assert_eq!(lines, [Some(1), Some(2)]);
+}
@@ -0,0 +1,189 @@
+/*!
+Tests for the WGSL formatter.
+*/
+
+#![cfg(feature = "wgsl-in")]
+
+use naga::front::wgsl;
+
+#[track_caller]
+fn check(source: &str, expected: &str) {
+    let formatted = wgsl::format_str(source).unwrap();
+    if formatted != expected {
+        println!("{formatted}");
+        panic!("formatted source doesn't match the expected output");
+    }
+    assert_eq!(wgsl::format_str(&formatted).unwrap(), formatted);
+}
+
+#[test]
+fn layout() {
+    check(
+        "
+struct   Light{color:vec3<f32>,@align(16) intensity :f32}
+@group(0)   @binding(0) var<uniform>light:Light;
+fn shade(n:vec3<f32>,l:vec3<f32>)->vec3<f32>{let d=max(dot(n,-l),0.0);
+if d>0.5{return light.color*d;}else if d<-0.5{return vec3(0.0);}else{
+for(var i=0;i<4;i++){}
+}
+return light.color*light.intensity;}
+",
+        "\
+struct Light {
+    color: vec3<f32>,
+    @align(16) intensity: f32
+}
+@group(0) @binding(0)
+var<uniform> light: Light;
+fn shade(n: vec3<f32>, l: vec3<f32>) -> vec3<f32> {
+    let d = max(dot(n, -l), 0.0);
+    if d > 0.5 {
+        return light.color * d;
+    } else if d < -0.5 {
+        return vec3(0.0);
+    } else {
+        for (var i = 0; i < 4; i++) {}
+    }
+    return light.color * light.intensity;
+}
+",
+    );
+}
+
+#[test]
+fn comments_and_blank_lines() {
+    check(
+        "// Header.
+
+
+const A = 1; // The first.
+/* The second. */ const B=array<i32,2>(A,   2);
+
+
+
+fn f(p: ptr<function, i32>) {
+
+    // Leading.
+    *p = A  <<  2u; /* trailing */
+
+    switch *p {
+        case 1, 2: {}
+        default { *p = -* p; }
+    }
+
+}
+",
+        "// Header.
+
+const A = 1; // The first.
+/* The second. */ const B = array<i32, 2>(A, 2);
+
+fn f(p: ptr<function, i32>) {
+    // Leading.
+    *p = A << 2u; /* trailing */
+
+    switch *p {
+        case 1, 2: {}
+        default {
+            *p = -*p;
+        }
+    }
+}
+",
+    );
+}
+
+#[test]
+fn continuation_lines() {
+    check(
+        "fn f() -> f32 {
+    let x = max(1.0,
+        min(2.0,
+    3.0)
+    );
+    return x +
+    1.0;
+}
+",
+        "fn f() -> f32 {
+    let x = max(1.0,
+        min(2.0,
+            3.0)
+    );
+    return x +
+        1.0;
+}
+",
+    );
+}
+
+#[test]
+fn nested_templates() {
+    check(
+        "var<private> a: array<vec2<u32>,2>;
+fn f() -> u32 { return a[0][1]>>1u; }
+",
+        "var<private> a: array<vec2<u32>, 2>;
+fn f() -> u32 {
+    return a[0][1] >> 1u;
+}
+",
+    );
+}
+
+#[test]
+fn syntax_error() {
+    assert!(wgsl::format_str("fn f( {}").is_err());
+}
+
+/// Formatting the snapshot inputs must not change what they mean, and must
+/// be idempotent.
+#[cfg(feature = "wgsl-out")]
+#[test]
+fn snapshot_inputs() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/in");
+    let mut count = 0;
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("wgsl") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        let formatted =
+            wgsl::format_str(&source).unwrap_or_else(|error| panic!("{}: {error}", path.display()));
+        assert_eq!(
+            wgsl::format_str(&formatted).unwrap(),
+            formatted,
+            "{}: formatting is not idempotent",
+            path.display()
+        );
+
+        let Ok(original) = wgsl::parse_str(&source) else {
+            continue;
+        };
+        let reformatted = wgsl::parse_str(&formatted).unwrap();
+        // Compare the modules by their WGSL output. Skip the ones that the
+        // WGSL backend can't write.
+        let write = |module: &naga::Module| {
+            let info = naga::valid::Validator::new(
+                naga::valid::ValidationFlags::empty(),
+                naga::valid::Capabilities::all(),
+            )
+            .validate(module)
+            .ok()?;
+            naga::back::wgsl::write_string(module, &info, naga::back::wgsl::WriterFlags::empty())
+                .ok()
+        };
+        let Some(expected) = write(&original) else {
+            continue;
+        };
+        assert_eq!(
+            Some(expected),
+            write(&reformatted),
+            "{}: formatting changed the module",
+            path.display()
+        );
+        count += 1;
+    }
+    assert!(count > 0);
+}
@@ -272,6 +272,18 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
             Action::DestroyShaderModule(id) => {
                 self.shader_module_drop::<A>(id);
             }
+            Action::CreatePipelineCache { id, desc } => {
+                // Traces don't record the cache contents, so there's no data
+                // to hand to the driver.
+                let (_, error) =
+                    unsafe { self.device_create_pipeline_cache::<A>(device, &desc, id) };
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyPipelineCache(id) => {
+                self.pipeline_cache_drop::<A>(id);
+            }
             Action::CreateComputePipeline {
                 id,
                 desc,
@@ -313,6 +325,25 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                     panic!("{e}");
                 }
             }
+            Action::CreateMeshPipeline {
+                id,
+                desc,
+                implicit_context,
+            } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let implicit_ids =
+                    implicit_context
+                        .as_ref()
+                        .map(|ic| wgc::device::ImplicitPipelineIds {
+                            root_id: ic.root_id,
+                            group_ids: &ic.group_ids,
+                        });
+                let (_, error) =
+                    self.device_create_mesh_pipeline::<A>(device, &desc, id, implicit_ids);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
             Action::DestroyRenderPipeline(id) => {
                 self.render_pipeline_drop::<A>(id);
             }
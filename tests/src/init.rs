@@ -11,7 +11,9 @@ pub fn init_logger() {
 }
 
 /// Initialize a wgpu instance with the options from the environment.
-pub fn initialize_instance() -> Instance {
+///
+/// `flags` are enabled on top of the debugging flags and those from the environment.
+pub fn initialize_instance(flags: wgpu::InstanceFlags) -> Instance {
     // We ignore `WGPU_BACKEND` for now, merely using test filtering to only run a single backend's tests.
     //
     // We can potentially work support back into the test runner in the future, but as the adapters are matched up
@@ -31,15 +33,18 @@ pub fn initialize_instance() -> Instance {
     let gles_minor_version = wgpu::util::gles_minor_version_from_env().unwrap_or_default();
     Instance::new(wgpu::InstanceDescriptor {
         backends,
-        flags: wgpu::InstanceFlags::debugging().with_env(),
+        flags: wgpu::InstanceFlags::debugging().with_env() | flags,
         dx12_shader_compiler,
         gles_minor_version,
     })
 }
 
 /// Initialize a wgpu adapter, taking the `n`th adapter from the instance.
-pub async fn initialize_adapter(adapter_index: usize) -> (Instance, Adapter, Option<SurfaceGuard>) {
-    let instance = initialize_instance();
+pub async fn initialize_adapter(
+    adapter_index: usize,
+    flags: wgpu::InstanceFlags,
+) -> (Instance, Adapter, Option<SurfaceGuard>) {
+    let instance = initialize_instance(flags);
     #[allow(unused_variables)]
     let _surface: wgpu::Surface;
     let surface_guard: Option<SurfaceGuard>;
@@ -24,6 +24,9 @@ pub struct TestParameters {
 
     /// Conditions under which this test should be run, but is expected to fail.
     pub failures: Vec<FailureCase>,
+
+    /// Instance flags to enable on top of the debugging flags.
+    pub instance_flags: wgpu::InstanceFlags,
 }
 
 impl Default for TestParameters {
@@ -34,6 +37,7 @@ impl Default for TestParameters {
             required_limits: Limits::downlevel_webgl2_defaults(),
             skips: Vec::new(),
             failures: Vec::new(),
+            instance_flags: wgpu::InstanceFlags::empty(),
         }
     }
 }
@@ -63,6 +67,12 @@ impl TestParameters {
         self
     }
 
+    /// Enable these instance flags when creating the instance.
+    pub fn instance_flags(mut self, flags: wgpu::InstanceFlags) -> Self {
+        self.instance_flags |= flags;
+        self
+    }
+
     /// Mark the test as always failing, but not to be skipped.
     pub fn expect_fail(mut self, when: FailureCase) -> Self {
         self.failures.push(when);
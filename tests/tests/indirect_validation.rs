@@ -0,0 +1,204 @@
+//! Tests for `InstanceFlags::VALIDATION_INDIRECT_CALL`.
+
+use wgpu::util::DeviceExt;
+
+use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
+
+const SHADER_SRC: &str = "
+@group(0) @binding(0)
+var<storage, read_write> counter: atomic<u32>;
+
+@compute @workgroup_size(1)
+fn main() {
+    atomicAdd(&counter, 1u);
+}
+";
+
+#[gpu_test]
+static SKIP_INVALID_DISPATCH: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .test_features_limits()
+            .downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION)
+            .instance_flags(wgpu::InstanceFlags::VALIDATION_INDIRECT_CALL)
+            // The validation is done by wgpu-core.
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                cache: None,
+            });
+
+        let counter = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("counter"),
+                contents: bytemuck::bytes_of(&0u32),
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            });
+        // A valid dispatch of 2 workgroups, followed by one exceeding
+        // `max_compute_workgroups_per_dimension`.
+        let indirect = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("indirect"),
+                contents: bytemuck::cast_slice(&[2u32, 1, 1, u32::MAX, 1, 1]),
+                usage: wgpu::BufferUsages::INDIRECT,
+            });
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: counter.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            cpass.set_pipeline(&pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.dispatch_workgroups_indirect(&indirect, 0);
+            cpass.dispatch_workgroups_indirect(&indirect, 12);
+        }
+        encoder.copy_buffer_to_buffer(&counter, 0, &readback, 0, 4);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, Result::unwrap);
+        // The rejected dispatch is reported once the submission is done.
+        fail(&ctx.device, || ctx.device.poll(wgpu::Maintain::Wait)).panic_on_timeout();
+
+        let count: u32 = bytemuck::pod_read_unaligned(&readback.slice(..).get_mapped_range());
+        assert_eq!(count, 2);
+    });
+
+const DRAW_SHADER_SRC: &str = "
+@vertex
+fn vs_main() -> @builtin(position) vec4<f32> {
+    return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+";
+
+// The pre-pass can't validate calls whose count is read from a buffer. Indirect
+// mesh task draws are rejected the same way, but can't be encoded through wgpu.
+#[gpu_test]
+static REJECT_UNVALIDATED_DRAW: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::MULTI_DRAW_INDIRECT_COUNT)
+            .downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION)
+            .instance_flags(wgpu::InstanceFlags::VALIDATION_INDIRECT_CALL)
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(DRAW_SHADER_SRC.into()),
+            });
+        let pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: None,
+                layout: None,
+                vertex: wgpu::VertexState {
+                    buffers: &[],
+                    entry_point: "vs_main",
+                    module: &module,
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    entry_point: "fs_main",
+                    module: &module,
+                    targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                }),
+                multiview: None,
+                cache: None,
+            });
+
+        let target = ctx
+            .device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some("target"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let indirect = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("indirect"),
+                contents: bytemuck::cast_slice(&[3u32, 1, 0, 0]),
+                usage: wgpu::BufferUsages::INDIRECT,
+            });
+        let count = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("count"),
+                contents: bytemuck::bytes_of(&1u32),
+                usage: wgpu::BufferUsages::INDIRECT,
+            });
+
+        fail(&ctx.device, || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: None,
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &target,
+                        resolve_target: None,
+                        ops: wgpu::Operations::default(),
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                rpass.set_pipeline(&pipeline);
+                rpass.multi_draw_indirect_count(&indirect, 0, &count, 0, 1);
+            }
+            encoder.finish()
+        });
+    });
@@ -1,4 +1,4 @@
-use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
+use wgpu_test::{fail, gpu_test, valid, FailureCase, GpuTestConfiguration, TestParameters};
 
 // Create an invalid shader and a compute pipeline that uses it
 // with a default bindgroup layout, and then ask for that layout.
@@ -28,8 +28,110 @@ static PIPELINE_DEFAULT_LAYOUT_BAD_MODULE: GpuTestConfiguration = GpuTestConfigu
                     layout: None,
                     module: &module,
                     entry_point: "doesn't exist",
+                    cache: None,
                 });
 
             pipeline.get_bind_group_layout(0);
         });
     });
+
+const LARGE_WORKGROUP_STORAGE_SHADER: &str = "
+    var<workgroup> data: array<vec4<f32>, 2048>;
+
+    @compute @workgroup_size(1)
+    fn main(@builtin(local_invocation_index) index: u32) {
+        data[index] = vec4(1.0);
+    }
+";
+
+// Workgroup storage above `max_compute_workgroup_storage_size` is a
+// validation error when creating the pipeline.
+#[gpu_test]
+static COMPUTE_PIPELINE_WORKGROUP_STORAGE_LIMIT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_sync(|ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(LARGE_WORKGROUP_STORAGE_SHADER.into()),
+            });
+
+        fail(&ctx.device, || {
+            ctx.device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: None,
+                    layout: None,
+                    module: &module,
+                    entry_point: "main",
+                    cache: None,
+                })
+        });
+    });
+
+const MANY_TARGETS_SHADER: &str = "
+    @vertex
+    fn vs_main() -> @builtin(position) vec4<f32> {
+        return vec4(0.0);
+    }
+
+    struct Targets {
+        @location(0) a: vec4<f32>,
+        @location(1) b: vec4<f32>,
+        @location(2) c: vec4<f32>,
+        @location(3) d: vec4<f32>,
+        @location(4) e: vec4<f32>,
+    }
+
+    @fragment
+    fn fs_main() -> Targets {
+        return Targets(vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0));
+    }
+";
+
+// Color targets taking up more than `max_color_attachment_bytes_per_sample`
+// are a validation error when creating the pipeline.
+#[gpu_test]
+static RENDER_PIPELINE_COLOR_ATTACHMENT_BYTES_LIMIT: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(MANY_TARGETS_SHADER.into()),
+            });
+
+        let create_pipeline = |count: usize| {
+            // `Rgba8Unorm` takes up 8 bytes per sample, so four targets fit
+            // in the default limit of 32, and five don't.
+            let target = wgpu::ColorTargetState::from(wgpu::TextureFormat::Rgba8Unorm);
+            let targets = vec![Some(target); count];
+            ctx.device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: None,
+                    layout: None,
+                    vertex: wgpu::VertexState {
+                        module: &module,
+                        entry_point: "vs_main",
+                        buffers: &[],
+                    },
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    fragment: Some(wgpu::FragmentState {
+                        module: &module,
+                        entry_point: "fs_main",
+                        targets: &targets,
+                    }),
+                    multiview: None,
+                    cache: None,
+                })
+        };
+
+        valid(&ctx.device, || create_pipeline(4));
+        fail(&ctx.device, || create_pipeline(5));
+    });
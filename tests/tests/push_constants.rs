@@ -103,6 +103,7 @@ async fn partial_update_test(ctx: TestingContext) {
             layout: Some(&pipeline_layout),
             module: &sm,
             entry_point: "main",
+            cache: None,
         });
 
     let mut encoder = ctx
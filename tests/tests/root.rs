@@ -16,6 +16,7 @@ mod device;
 mod encoder;
 mod external_texture;
 mod float32_filterable;
+mod indirect_validation;
 mod instance;
 mod life_cycle;
 mod mem_leaks;
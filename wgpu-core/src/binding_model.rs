@@ -95,6 +95,8 @@ pub enum CreateBindGroupError {
     BindingArrayLengthMismatch { actual: usize, expected: usize },
     #[error("Array binding provided zero elements")]
     BindingArrayZeroLength,
+    #[error("External texture at binding {binding} has {count} planes, but must have 1 to 3")]
+    WrongExternalTexturePlaneCount { binding: u32, count: usize },
     #[error("Bound buffer range {range:?} does not fit in buffer of size {size}")]
     BindingRangeTooLarge {
         buffer: BufferId,
@@ -754,6 +756,27 @@ pub enum BindingResource<'a> {
     SamplerArray(Cow<'a, [SamplerId]>),
     TextureView(TextureViewId),
     TextureViewArray(Cow<'a, [TextureViewId]>),
+    ExternalTexture(ExternalTextureBinding<'a>),
+}
+
+/// Resources to bind to a `texture_external` in a shader.
+///
+/// An external texture at binding `N` occupies bindings `N` through `N + 3` of
+/// the bind group layout: three sampled float textures for its planes, and a
+/// uniform buffer for its parameters. Naga lays them out this way in every
+/// backend, see [`naga::ResourceBinding::external_texture_bindings`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct ExternalTextureBinding<'a> {
+    /// Views of the texture's planes, between one and three of them.
+    ///
+    /// A single plane holds RGBA. With two planes, the first holds Y and the
+    /// second interleaved UV, as in NV12. With three, they hold Y, U and V.
+    pub planes: Cow<'a, [TextureViewId]>,
+    /// Buffer holding a `NagaExternalTextureParams` struct, with the YUV to
+    /// RGB conversion matrix, the size of the texture and the number of planes.
+    pub params: BufferBinding,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -385,6 +385,13 @@ impl RenderBundleEncoder {
                             .map_pass_err(scope);
                     }
 
+                    if pipeline.flags.contains(PipelineFlags::MESH) {
+                        return Err(RenderCommandError::Unimplemented(
+                            "mesh pipelines in render bundles",
+                        ))
+                        .map_pass_err(scope);
+                    }
+
                     let pipeline_state = PipelineState::new(pipeline);
 
                     commands.push(command);
@@ -575,6 +582,9 @@ impl RenderBundleEncoder {
                     device
                         .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
                         .map_pass_err(scope)?;
+                    if device.indirect_validation.is_some() {
+                        return Err(DrawError::UnvalidatedIndirectDraw).map_pass_err(scope);
+                    }
 
                     let pipeline = state.pipeline(scope)?;
                     let used_bind_groups = pipeline.used_bind_groups;
@@ -614,6 +624,9 @@ impl RenderBundleEncoder {
                     device
                         .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
                         .map_pass_err(scope)?;
+                    if device.indirect_validation.is_some() {
+                        return Err(DrawError::UnvalidatedIndirectDraw).map_pass_err(scope);
+                    }
 
                     let pipeline = state.pipeline(scope)?;
                     let used_bind_groups = pipeline.used_bind_groups;
@@ -647,6 +660,9 @@ impl RenderBundleEncoder {
                 }
                 RenderCommand::MultiDrawIndirect { .. }
                 | RenderCommand::MultiDrawIndirectCount { .. } => unimplemented!(),
+                RenderCommand::DrawMeshTasks { .. }
+                | RenderCommand::MultiDrawMeshTasksIndirect { .. }
+                | RenderCommand::MultiDrawMeshTasksIndirectCount { .. } => unimplemented!(),
                 RenderCommand::PushDebugGroup { color: _, len: _ } => unimplemented!(),
                 RenderCommand::InsertDebugMarker { color: _, len: _ } => unimplemented!(),
                 RenderCommand::PopDebugGroup => unimplemented!(),
@@ -967,6 +983,11 @@ impl<A: HalApi> RenderBundle<A> {
                 | RenderCommand::MultiDrawIndirectCount { .. } => {
                     return Err(ExecutionError::Unimplemented("multi-draw-indirect"))
                 }
+                RenderCommand::DrawMeshTasks { .. }
+                | RenderCommand::MultiDrawMeshTasksIndirect { .. }
+                | RenderCommand::MultiDrawMeshTasksIndirectCount { .. } => {
+                    return Err(ExecutionError::Unimplemented("mesh shader draws"))
+                }
                 RenderCommand::PushDebugGroup { .. }
                 | RenderCommand::InsertDebugMarker { .. }
                 | RenderCommand::PopDebugGroup => {
@@ -1418,7 +1439,7 @@ impl<A: HalApi> State<A> {
 
 /// Error encountered when finishing recording a render bundle.
 #[derive(Clone, Debug, Error)]
-pub(super) enum RenderBundleErrorInner {
+pub(crate) enum RenderBundleErrorInner {
     #[error("Resource is not valid to use with this render bundle because the resource and the bundle come from different devices")]
     NotValidToUse,
     #[error(transparent)]
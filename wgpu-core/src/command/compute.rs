@@ -19,13 +19,14 @@ use crate::{
     hal_label, id,
     id::DeviceId,
     identity::GlobalIdentityHandlerFactory,
+    indirect_validation::{CallKind, IndirectCalls},
     init_tracker::MemoryInitKind,
     pipeline,
     resource::{self},
     storage::Storage,
     track::{Tracker, UsageConflict, UsageScope},
     validation::{check_buffer_usage, MissingBufferUsageError},
-    Label,
+    FastHashSet, Label,
 };
 
 use hal::CommandEncoder as _;
@@ -209,6 +210,12 @@ pub enum ComputePassErrorInner {
         end_offset: u64,
         buffer_size: u64,
     },
+    #[error(
+        "Indirect buffer offset {0} is not a multiple of 4, so its arguments can't be validated"
+    )]
+    UnalignedIndirectBufferOffset(u64),
+    #[error("Indirect buffer {0:?} is written by an earlier dispatch of this pass, so its arguments can't be validated")]
+    IndirectBufferWrittenInPass(id::BufferId),
     #[error("Buffer {0:?} is invalid or destroyed")]
     InvalidBuffer(id::BufferId),
     #[error(transparent)]
@@ -238,7 +245,7 @@ impl PrettyError for ComputePassErrorInner {
             Self::InvalidPipeline(id) => {
                 fmt.compute_pipeline_label(&id);
             }
-            Self::InvalidIndirectBuffer(id) => {
+            Self::InvalidIndirectBuffer(id) | Self::IndirectBufferWrittenInPass(id) => {
                 fmt.buffer_label(&id);
             }
             Self::Dispatch(DispatchError::IncompatibleBindGroup { ref diff, .. }) => {
@@ -285,6 +292,9 @@ struct State<A: HalApi> {
     pipeline: Option<id::ComputePipelineId>,
     scope: UsageScope<A>,
     debug_scope_depth: u32,
+    /// Buffers written by the dispatches so far, if indirect calls are
+    /// validated.
+    written_buffers: Option<FastHashSet<id::BufferId>>,
 }
 
 impl<A: HalApi> State<A> {
@@ -321,6 +331,9 @@ impl<A: HalApi> State<A> {
             unsafe { self.scope.merge_bind_group(&bind_group_guard[id].used)? };
             // Note: stateless trackers are not merged: the lifetime reference
             // is held to the bind group itself.
+            if let Some(ref mut written_buffers) = self.written_buffers {
+                written_buffers.extend(bind_group_guard[id].used.buffers.written_ids());
+            }
         }
 
         for id in self.binder.list_active() {
@@ -398,6 +411,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let tracker = &mut cmd_buf_data.trackers;
         let buffer_memory_init_actions = &mut cmd_buf_data.buffer_memory_init_actions;
         let texture_memory_actions = &mut cmd_buf_data.texture_memory_actions;
+        let indirect_validation = &mut cmd_buf_data.indirect_validation;
 
         // We automatically keep extending command buffers over time, and because
         // we want to insert a command buffer _before_ what we're about to record,
@@ -413,11 +427,21 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let buffer_guard = hub.buffers.read();
         let texture_guard = hub.textures.read();
 
+        let indirect_size = base
+            .commands
+            .iter()
+            .filter(|command| matches!(command, ComputeCommand::DispatchIndirect { .. }))
+            .count() as u64
+            * CallKind::Dispatch.size();
+        let mut indirect_calls =
+            IndirectCalls::new(device, base.label, indirect_size).map_pass_err(pass_scope)?;
+
         let mut state = State {
             binder: Binder::new(),
             pipeline: None,
             scope: UsageScope::new(&*buffer_guard, &*texture_guard),
             debug_scope_depth: 0,
+            written_buffers: indirect_calls.as_ref().map(|_| FastHashSet::default()),
         };
         let mut temp_offsets = Vec::new();
         let mut dynamic_offset_count = 0;
@@ -757,6 +781,33 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         ),
                     );
 
+                    let (buf_raw, offset) = match indirect_calls.as_mut() {
+                        Some(calls) => {
+                            // The validation pre-pass can't see what earlier
+                            // dispatches of this pass wrote to the buffer.
+                            if state
+                                .written_buffers
+                                .as_ref()
+                                .map_or(true, |written| written.contains(&buffer_id))
+                            {
+                                return Err(ComputePassErrorInner::IndirectBufferWrittenInPass(
+                                    buffer_id,
+                                ))
+                                .map_pass_err(scope);
+                            }
+                            if offset % 4 != 0 {
+                                return Err(ComputePassErrorInner::UnalignedIndirectBufferOffset(
+                                    offset,
+                                ))
+                                .map_pass_err(scope);
+                            }
+                            let dst_offset =
+                                calls.push(indirect_buffer, CallKind::Dispatch, offset, 1, [0, 0]);
+                            (calls.dst(), dst_offset)
+                        }
+                        None => (buf_raw, offset),
+                    };
+
                     state
                         .flush_states(
                             raw,
@@ -877,6 +928,12 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             &mut tracker.textures,
             device,
         );
+        if let Some(indirect_calls) = indirect_calls {
+            let resources = indirect_calls
+                .encode(transit, &mut tracker.buffers, &snatch_guard)
+                .map_pass_err(pass_scope)?;
+            indirect_validation.push(resources);
+        }
         CommandBuffer::insert_barriers_from_tracker(
             transit,
             tracker,
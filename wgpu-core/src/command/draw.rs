@@ -50,6 +50,18 @@ pub enum DrawError {
     },
     #[error(transparent)]
     BindingSizeTooSmall(#[from] LateMinBufferBindingSizeMismatch),
+    #[error("Render pipeline uses mesh shaders, so it can only be used with `draw_mesh_tasks`")]
+    UnexpectedMeshPipeline,
+    #[error("`draw_mesh_tasks` requires a render pipeline that uses mesh shaders")]
+    MeshPipelineRequired,
+    #[error("Each mesh task group count dimension ({current:?}) must be less or equal to {limit}, and their product to {total_limit}")]
+    InvalidMeshTaskGroupCount {
+        current: [u32; 3],
+        limit: u32,
+        total_limit: u32,
+    },
+    #[error("Indirect draws in render bundles, `multi_draw_*indirect_count` and indirect mesh task draws can't be used while indirect call validation is enabled, as their arguments can't be validated")]
+    UnvalidatedIndirectDraw,
 }
 
 /// Error encountered when encoding a render command.
@@ -228,6 +240,24 @@ pub enum RenderCommand {
         max_count: u32,
         indexed: bool,
     },
+    DrawMeshTasks {
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    },
+    MultiDrawMeshTasksIndirect {
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        /// Count of `None` represents a non-multi call.
+        count: Option<NonZeroU32>,
+    },
+    MultiDrawMeshTasksIndirectCount {
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    },
     PushDebugGroup {
         color: u32,
         len: usize,
@@ -24,6 +24,7 @@ use crate::hub::Hub;
 use crate::id::CommandBufferId;
 use crate::snatch::SnatchGuard;
 
+use crate::indirect_validation::PassResources;
 use crate::init_tracker::BufferInitTrackerAction;
 use crate::resource::{Resource, ResourceInfo, ResourceType};
 use crate::track::{Tracker, UsageScope};
@@ -109,6 +110,7 @@ pub struct BakedCommands<A: HalApi> {
     pub(crate) trackers: Tracker<A>,
     buffer_memory_init_actions: Vec<BufferInitTrackerAction<A>>,
     texture_memory_actions: CommandBufferTextureMemoryActions<A>,
+    pub(crate) indirect_validation: Vec<PassResources<A>>,
 }
 
 pub(crate) struct DestroyedBufferError(pub id::BufferId);
@@ -121,6 +123,8 @@ pub struct CommandBufferMutable<A: HalApi> {
     buffer_memory_init_actions: Vec<BufferInitTrackerAction<A>>,
     texture_memory_actions: CommandBufferTextureMemoryActions<A>,
     pub(crate) pending_query_resets: QueryResetMap<A>,
+    /// Objects used by the indirect call validation of the recorded passes.
+    indirect_validation: Vec<PassResources<A>>,
     #[cfg(feature = "trace")]
     pub(crate) commands: Option<Vec<TraceCommand>>,
 }
@@ -190,6 +194,7 @@ impl<A: HalApi> CommandBuffer<A> {
                 buffer_memory_init_actions: Default::default(),
                 texture_memory_actions: Default::default(),
                 pending_query_resets: QueryResetMap::new(),
+                indirect_validation: Vec::new(),
                 #[cfg(feature = "trace")]
                 commands: if enable_tracing {
                     Some(Vec::new())
@@ -284,6 +289,7 @@ impl<A: HalApi> CommandBuffer<A> {
             trackers: data.trackers,
             buffer_memory_init_actions: data.buffer_memory_init_actions,
             texture_memory_actions: data.texture_memory_actions,
+            indirect_validation: data.indirect_validation,
         }
     }
 
@@ -679,6 +685,11 @@ pub enum PassErrorScope {
         indirect: bool,
         pipeline: Option<id::RenderPipelineId>,
     },
+    #[error("In a draw_mesh_tasks command, indirect:{indirect}")]
+    DrawMeshTasks {
+        indirect: bool,
+        pipeline: Option<id::RenderPipelineId>,
+    },
     #[error("While resetting queries after the renderpass was ran")]
     QueryReset,
     #[error("In a write_timestamp command")]
@@ -726,6 +737,9 @@ impl PrettyError for PassErrorScope {
             }
             Self::Draw {
                 pipeline: Some(id), ..
+            }
+            | Self::DrawMeshTasks {
+                pipeline: Some(id), ..
             } => {
                 fmt.render_pipeline_label(&id);
             }
@@ -20,6 +20,7 @@ use crate::{
     hal_api::HalApi,
     hal_label, id,
     identity::GlobalIdentityHandlerFactory,
+    indirect_validation::{CallKind, IndirectCalls},
     init_tracker::{MemoryInitKind, TextureInitRange, TextureInitTrackerAction},
     pipeline::{self, PipelineFlags},
     resource::{Buffer, QuerySet, Texture, TextureView, TextureViewNotRenderableReason},
@@ -430,7 +431,7 @@ struct State<A: HalApi> {
 }
 
 impl<A: HalApi> State<A> {
-    fn is_ready(&self, indexed: bool) -> Result<(), DrawError> {
+    fn is_ready(&self, indexed: bool, mesh: bool) -> Result<(), DrawError> {
         // Determine how many vertex buffers have already been bound
         let vertex_buffer_count = self.vertex.inputs.iter().take_while(|v| v.bound).count() as u32;
         // Compare with the needed quantity
@@ -451,6 +452,11 @@ impl<A: HalApi> State<A> {
         if self.pipeline.is_none() {
             return Err(DrawError::MissingPipeline);
         }
+        match (mesh, self.pipeline_flags.contains(PipelineFlags::MESH)) {
+            (false, true) => return Err(DrawError::UnexpectedMeshPipeline),
+            (true, false) => return Err(DrawError::MeshPipelineRequired),
+            _ => {}
+        }
         if self.blend_constant == OptionalState::Required {
             return Err(DrawError::MissingBlendConstant);
         }
@@ -600,6 +606,10 @@ pub enum RenderPassErrorInner {
         end_offset: u64,
         buffer_size: u64,
     },
+    #[error(
+        "Indirect buffer offset {0} is not a multiple of 4, so its arguments can't be validated"
+    )]
+    UnalignedIndirectBufferOffset(u64),
     #[error("Indirect draw uses bytes {begin_count_offset}..{end_count_offset} which overruns indirect buffer of size {count_buffer_size}")]
     IndirectCountBufferOverrun {
         begin_count_offset: u64,
@@ -1324,7 +1334,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let device = &cmd_buf.device;
         let snatch_guard = device.snatchable_lock.read();
 
-        let (scope, pending_discard_init_fixups) = {
+        let (scope, pending_discard_init_fixups, indirect_calls) = {
             let mut cmd_buf_data = cmd_buf.data.lock();
             let cmd_buf_data = cmd_buf_data.as_mut().unwrap();
 
@@ -1403,6 +1413,23 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
             let raw = &mut encoder.raw;
 
+            let indirect_size = base
+                .commands
+                .iter()
+                .map(|command| match *command {
+                    RenderCommand::MultiDrawIndirect { count, indexed, .. } => {
+                        let kind = match indexed {
+                            false => CallKind::Draw,
+                            true => CallKind::DrawIndexed,
+                        };
+                        kind.size() * count.map_or(1, |c| c.get()) as u64
+                    }
+                    _ => 0,
+                })
+                .sum();
+            let mut indirect_calls =
+                IndirectCalls::new(device, base.label, indirect_size).map_pass_err(pass_scope)?;
+
             let mut state = State {
                 pipeline_flags: PipelineFlags::empty(),
                 binder: Binder::new(),
@@ -1893,7 +1920,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             indirect: false,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        state.is_ready(indexed, false).map_pass_err(scope)?;
 
                         let last_vertex = first_vertex as u64 + vertex_count as u64;
                         let vertex_limit = state.vertex.vertex_limit;
@@ -1935,7 +1962,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             indirect: false,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        state.is_ready(indexed, false).map_pass_err(scope)?;
 
                         let last_index = first_index as u64 + index_count as u64;
                         let index_limit = state.index.limit;
@@ -1980,7 +2007,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             indirect: true,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        state.is_ready(indexed, false).map_pass_err(scope)?;
 
                         let stride = match indexed {
                             false => mem::size_of::<wgt::DrawIndirectArgs>(),
@@ -2030,6 +2057,30 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             ),
                         );
 
+                        let (kind, vertex_or_index_limit) = match indexed {
+                            false => (CallKind::Draw, state.vertex.vertex_limit),
+                            true => (CallKind::DrawIndexed, state.index.limit),
+                        };
+                        let (indirect_raw, offset) = match indirect_calls.as_mut() {
+                            Some(calls) => {
+                                if offset % 4 != 0 {
+                                    return Err(
+                                        RenderPassErrorInner::UnalignedIndirectBufferOffset(offset),
+                                    )
+                                    .map_pass_err(scope);
+                                }
+                                let dst_offset = calls.push(
+                                    indirect_buffer,
+                                    kind,
+                                    offset,
+                                    actual_count,
+                                    [vertex_or_index_limit, state.vertex.instance_limit],
+                                );
+                                (calls.dst(), dst_offset)
+                            }
+                            None => (indirect_raw, offset),
+                        };
+
                         match indexed {
                             false => unsafe {
                                 raw.draw_indirect(indirect_raw, offset, actual_count);
@@ -2054,7 +2105,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             indirect: true,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        state.is_ready(indexed, false).map_pass_err(scope)?;
 
                         let stride = match indexed {
                             false => mem::size_of::<wgt::DrawIndirectArgs>(),
@@ -2067,6 +2118,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         device
                             .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
                             .map_pass_err(scope)?;
+                        if device.indirect_validation.is_some() {
+                            return Err(DrawError::UnvalidatedIndirectDraw).map_pass_err(scope);
+                        }
 
                         let indirect_buffer = info
                             .usage_scope
@@ -2155,6 +2209,220 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             },
                         }
                     }
+                    RenderCommand::DrawMeshTasks {
+                        group_count_x,
+                        group_count_y,
+                        group_count_z,
+                    } => {
+                        api_log!(
+                            "RenderPass::draw_mesh_tasks {group_count_x} {group_count_y} {group_count_z}"
+                        );
+
+                        let scope = PassErrorScope::DrawMeshTasks {
+                            indirect: false,
+                            pipeline: state.pipeline,
+                        };
+                        state.is_ready(false, true).map_pass_err(scope)?;
+
+                        let (limit, total_limit) =
+                            if state.pipeline_flags.contains(PipelineFlags::TASK) {
+                                (
+                                    device.limits.max_task_workgroups_per_dimension,
+                                    device.limits.max_task_workgroup_total_count,
+                                )
+                            } else {
+                                (
+                                    device.limits.max_mesh_workgroups_per_dimension,
+                                    device.limits.max_mesh_workgroup_total_count,
+                                )
+                            };
+                        let current = [group_count_x, group_count_y, group_count_z];
+                        let total = current.iter().map(|&c| u64::from(c)).product::<u64>();
+                        if current.iter().any(|&c| c > limit) || total > u64::from(total_limit) {
+                            return Err(DrawError::InvalidMeshTaskGroupCount {
+                                current,
+                                limit,
+                                total_limit,
+                            })
+                            .map_pass_err(scope);
+                        }
+
+                        unsafe {
+                            raw.draw_mesh_tasks(group_count_x, group_count_y, group_count_z);
+                        }
+                    }
+                    RenderCommand::MultiDrawMeshTasksIndirect {
+                        buffer_id,
+                        offset,
+                        count,
+                    } => {
+                        api_log!(
+                            "RenderPass::draw_mesh_tasks_indirect {buffer_id:?} {offset} {count:?}"
+                        );
+
+                        let scope = PassErrorScope::DrawMeshTasks {
+                            indirect: true,
+                            pipeline: state.pipeline,
+                        };
+                        state.is_ready(false, true).map_pass_err(scope)?;
+
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+                            .map_pass_err(scope)?;
+                        if device.indirect_validation.is_some() {
+                            return Err(DrawError::UnvalidatedIndirectDraw).map_pass_err(scope);
+                        }
+
+                        let stride = mem::size_of::<wgt::DrawMeshTasksIndirectArgs>();
+
+                        if count.is_some() {
+                            device
+                                .require_features(wgt::Features::MULTI_DRAW_INDIRECT)
+                                .map_pass_err(scope)?;
+                        }
+
+                        let indirect_buffer = info
+                            .usage_scope
+                            .buffers
+                            .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(indirect_buffer.usage, BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let indirect_raw = indirect_buffer
+                            .raw
+                            .get(&snatch_guard)
+                            .ok_or(RenderCommandError::DestroyedBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+
+                        let actual_count = count.map_or(1, |c| c.get());
+
+                        let end_offset = offset + stride as u64 * actual_count as u64;
+                        if end_offset > indirect_buffer.size {
+                            return Err(RenderPassErrorInner::IndirectBufferOverrun {
+                                count,
+                                offset,
+                                end_offset,
+                                buffer_size: indirect_buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
+
+                        buffer_memory_init_actions.extend(
+                            indirect_buffer.initialization_status.read().create_action(
+                                indirect_buffer,
+                                offset..end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        unsafe {
+                            raw.draw_mesh_tasks_indirect(indirect_raw, offset, actual_count);
+                        }
+                    }
+                    RenderCommand::MultiDrawMeshTasksIndirectCount {
+                        buffer_id,
+                        offset,
+                        count_buffer_id,
+                        count_buffer_offset,
+                        max_count,
+                    } => {
+                        api_log!("RenderPass::multi_draw_mesh_tasks_indirect_count {buffer_id:?} {offset} {count_buffer_id:?} {count_buffer_offset:?} {max_count:?}");
+
+                        let scope = PassErrorScope::DrawMeshTasks {
+                            indirect: true,
+                            pipeline: state.pipeline,
+                        };
+                        state.is_ready(false, true).map_pass_err(scope)?;
+
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+                            .map_pass_err(scope)?;
+                        if device.indirect_validation.is_some() {
+                            return Err(DrawError::UnvalidatedIndirectDraw).map_pass_err(scope);
+                        }
+
+                        let stride = mem::size_of::<wgt::DrawMeshTasksIndirectArgs>() as u64;
+
+                        device
+                            .require_features(wgt::Features::MULTI_DRAW_INDIRECT_COUNT)
+                            .map_pass_err(scope)?;
+
+                        let indirect_buffer = info
+                            .usage_scope
+                            .buffers
+                            .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(indirect_buffer.usage, BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let indirect_raw = indirect_buffer
+                            .raw
+                            .get(&snatch_guard)
+                            .ok_or(RenderCommandError::DestroyedBuffer(buffer_id))
+                            .map_pass_err(scope)?;
+
+                        let count_buffer = info
+                            .usage_scope
+                            .buffers
+                            .merge_single(
+                                &*buffer_guard,
+                                count_buffer_id,
+                                hal::BufferUses::INDIRECT,
+                            )
+                            .map_pass_err(scope)?;
+                        check_buffer_usage(count_buffer.usage, BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                        let count_raw = count_buffer
+                            .raw
+                            .get(&snatch_guard)
+                            .ok_or(RenderCommandError::DestroyedBuffer(count_buffer_id))
+                            .map_pass_err(scope)?;
+
+                        let end_offset = offset + stride * max_count as u64;
+                        if end_offset > indirect_buffer.size {
+                            return Err(RenderPassErrorInner::IndirectBufferOverrun {
+                                count: None,
+                                offset,
+                                end_offset,
+                                buffer_size: indirect_buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
+                        buffer_memory_init_actions.extend(
+                            indirect_buffer.initialization_status.read().create_action(
+                                indirect_buffer,
+                                offset..end_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        let begin_count_offset = count_buffer_offset;
+                        let end_count_offset = count_buffer_offset + 4;
+                        if end_count_offset > count_buffer.size {
+                            return Err(RenderPassErrorInner::IndirectCountBufferOverrun {
+                                begin_count_offset,
+                                end_count_offset,
+                                count_buffer_size: count_buffer.size,
+                            })
+                            .map_pass_err(scope);
+                        }
+                        buffer_memory_init_actions.extend(
+                            count_buffer.initialization_status.read().create_action(
+                                count_buffer,
+                                count_buffer_offset..end_count_offset,
+                                MemoryInitKind::NeedsInitializedMemory,
+                            ),
+                        );
+
+                        unsafe {
+                            raw.draw_mesh_tasks_indirect_count(
+                                indirect_raw,
+                                offset,
+                                count_raw,
+                                count_buffer_offset,
+                                max_count,
+                            );
+                        }
+                    }
                     RenderCommand::PushDebugGroup { color: _, len } => {
                         state.debug_scope_depth += 1;
                         if !discard_hal_labels {
@@ -2369,7 +2637,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 info.finish(raw).map_pass_err(pass_scope)?;
 
             encoder.close().map_pass_err(pass_scope)?;
-            (trackers, pending_discard_init_fixups)
+            (trackers, pending_discard_init_fixups, indirect_calls)
         };
 
         let cmd_buf = hub.command_buffers.get(encoder_id).unwrap();
@@ -2402,6 +2670,13 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 .map_err(RenderCommandError::InvalidQuerySet)
                 .map_pass_err(PassErrorScope::QueryReset)?;
 
+            if let Some(indirect_calls) = indirect_calls {
+                let resources = indirect_calls
+                    .encode(transit, &mut tracker.buffers, &snatch_guard)
+                    .map_pass_err(pass_scope)?;
+                cmd_buf_data.indirect_validation.push(resources);
+            }
+
             super::CommandBuffer::insert_barriers_from_scope(
                 transit,
                 tracker,
@@ -2719,6 +2994,71 @@ pub mod render_ffi {
             });
     }
 
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_draw_mesh_tasks(
+        pass: &mut RenderPass,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+        pass.base.commands.push(RenderCommand::DrawMeshTasks {
+            group_count_x,
+            group_count_y,
+            group_count_z,
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_draw_mesh_tasks_indirect(
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::MultiDrawMeshTasksIndirect {
+                buffer_id,
+                offset,
+                count: None,
+            });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_multi_draw_mesh_tasks_indirect(
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count: u32,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::MultiDrawMeshTasksIndirect {
+                buffer_id,
+                offset,
+                count: NonZeroU32::new(count),
+            });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_render_pass_multi_draw_mesh_tasks_indirect_count(
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::MultiDrawMeshTasksIndirectCount {
+                buffer_id,
+                offset,
+                count_buffer_id,
+                count_buffer_offset,
+                max_count,
+            });
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given `label`
@@ -10,6 +10,7 @@ use crate::{
     hal_api::HalApi,
     id::{self, AdapterId, DeviceId, QueueId, SurfaceId},
     identity::{GlobalIdentityHandlerFactory, Input},
+    indirect_validation::IndirectValidationError,
     init_tracker::TextureInitTracker,
     instance::{self, Adapter, Surface},
     pipeline, present,
@@ -27,7 +28,7 @@ use wgt::{BufferAddress, TextureFormat};
 
 use std::{
     borrow::Cow,
-    iter,
+    iter, mem,
     ops::Range,
     ptr,
     sync::{atomic::Ordering, Arc},
@@ -1291,6 +1292,95 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         hub.shader_modules.unregister(shader_module_id);
     }
 
+    /// # Safety
+    ///
+    /// After checking that `desc.data` was produced by this version of wgpu
+    /// for the same adapter and driver, it is passed to the backend as-is. A
+    /// driver given data that was tampered with in a way the header doesn't
+    /// catch can misbehave, so it should come from a trusted source.
+    pub unsafe fn device_create_pipeline_cache<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &pipeline::PipelineCacheDescriptor<'_>,
+        id_in: Input<G, id::PipelineCacheId>,
+    ) -> (
+        id::PipelineCacheId,
+        Option<pipeline::CreatePipelineCacheError>,
+    ) {
+        profiling::scope!("Device::create_pipeline_cache");
+
+        let hub = A::hub(self);
+        let fid = hub.pipeline_caches.prepare::<G>(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                // The cache contents don't affect rendering, so leave them
+                // out of the trace.
+                trace.add(trace::Action::CreatePipelineCache {
+                    id: fid.id(),
+                    desc: pipeline::PipelineCacheDescriptor {
+                        data: None,
+                        ..desc.clone()
+                    },
+                });
+            }
+
+            let cache = match unsafe { device.create_pipeline_cache(desc) } {
+                Ok(cache) => cache,
+                Err(e) => break e,
+            };
+
+            let (id, _) = fid.assign(cache);
+            api_log!("Device::create_pipeline_cache -> {id:?}");
+            return (id, None);
+        };
+
+        log::error!("Device::create_pipeline_cache error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Get the data in `cache_id`, to pass to
+    /// [`Global::device_create_pipeline_cache`] in a later run.
+    ///
+    /// Returns `None` if the cache is invalid, or if the backend doesn't
+    /// support pipeline caches.
+    pub fn pipeline_cache_get_data<A: HalApi>(
+        &self,
+        cache_id: id::PipelineCacheId,
+    ) -> Option<Vec<u8>> {
+        profiling::scope!("PipelineCache::get_data");
+        api_log!("PipelineCache::get_data {cache_id:?}");
+
+        let hub = A::hub(self);
+        let cache = hub.pipeline_caches.get(cache_id).ok()?;
+        let device = &cache.device;
+        if !device.is_valid() {
+            return None;
+        }
+        let origin = device.pipeline_cache_origin()?;
+        let data = unsafe { device.raw().pipeline_cache_get_data(cache.raw()) }?;
+        Some(crate::pipeline_cache::add_cache_header(&data, origin))
+    }
+
+    pub fn pipeline_cache_drop<A: HalApi>(&self, pipeline_cache_id: id::PipelineCacheId) {
+        profiling::scope!("PipelineCache::drop");
+        api_log!("PipelineCache::drop {pipeline_cache_id:?}");
+
+        let hub = A::hub(self);
+        hub.pipeline_caches.unregister(pipeline_cache_id);
+    }
+
     pub fn device_create_command_encoder<A: HalApi>(
         &self,
         device_id: DeviceId,
@@ -1604,6 +1694,87 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// Create a render pipeline whose geometry comes from task and mesh
+    /// shaders instead of vertex buffers.
+    ///
+    /// Requires [`wgt::Features::MESH_SHADER`].
+    pub fn device_create_mesh_pipeline<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &pipeline::MeshPipelineDescriptor,
+        id_in: Input<G, id::RenderPipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<G>>,
+    ) -> (
+        id::RenderPipelineId,
+        Option<pipeline::CreateRenderPipelineError>,
+    ) {
+        profiling::scope!("Device::create_mesh_pipeline");
+
+        let hub = A::hub(self);
+
+        let fid = hub.render_pipelines.prepare::<G>(id_in);
+        let implicit_context = implicit_pipeline_ids.map(|ipi| ipi.prepare(hub));
+        let implicit_error_context = implicit_context.clone();
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateMeshPipeline {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                    implicit_context: implicit_context.clone(),
+                });
+            }
+
+            let pipeline =
+                match device.create_mesh_pipeline(&device.adapter, desc, implicit_context, hub) {
+                    Ok(pair) => pair,
+                    Err(e) => break e,
+                };
+
+            let (id, resource) = fid.assign(pipeline);
+            api_log!("Device::create_mesh_pipeline -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .render_pipelines
+                .insert_single(id, resource);
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+
+        // We also need to assign errors to the implicit pipeline layout and the
+        // implicit bind group layout. We have to remove any existing entries first.
+        let mut pipeline_layout_guard = hub.pipeline_layouts.write();
+        let mut bgl_guard = hub.bind_group_layouts.write();
+        if let Some(ref ids) = implicit_error_context {
+            if pipeline_layout_guard.contains(ids.root_id) {
+                pipeline_layout_guard.remove(ids.root_id);
+            }
+            pipeline_layout_guard.insert_error(ids.root_id, IMPLICIT_BIND_GROUP_LAYOUT_ERROR_LABEL);
+            for &bgl_id in ids.group_ids.iter() {
+                if bgl_guard.contains(bgl_id) {
+                    bgl_guard.remove(bgl_id);
+                }
+                bgl_guard.insert_error(bgl_id, IMPLICIT_BIND_GROUP_LAYOUT_ERROR_LABEL);
+            }
+        }
+
+        log::error!("Device::create_mesh_pipeline error: {error}");
+
+        (id, Some(error))
+    }
+
     /// Get an ID of one of the bind group layouts. The ID adds a refcount,
     /// which needs to be released by calling `bind_group_layout_drop`.
     pub fn render_pipeline_get_bind_group_layout<A: HalApi>(
@@ -2118,6 +2289,23 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(queue_empty)
     }
 
+    /// Take the indirect call validation failures of the submissions that
+    /// completed so far.
+    ///
+    /// Always empty unless the instance was created with
+    /// [`wgt::InstanceFlags::VALIDATION_INDIRECT_CALL`]. Submissions are
+    /// checked for completion by [`Self::device_poll`] and
+    /// [`Self::queue_submit`].
+    pub fn device_take_indirect_validation_errors<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+    ) -> Result<Vec<IndirectValidationError>, InvalidDevice> {
+        let hub = A::hub(self);
+        let device = hub.devices.get(device_id).map_err(|_| InvalidDevice)?;
+        let errors = mem::take(&mut device.lock_life().indirect_validation_errors);
+        Ok(errors)
+    }
+
     /// Poll all devices belonging to the backend `A`.
     ///
     /// If `force_wait` is true, block until all buffer mappings are done.
@@ -11,6 +11,7 @@ use crate::{
         QuerySetId, RenderBundleId, RenderPipelineId, SamplerId, StagingBufferId, TextureId,
         TextureViewId,
     },
+    indirect_validation::IndirectValidationError,
     pipeline::{ComputePipeline, RenderPipeline},
     resource::{
         self, Buffer, DestroyedBuffer, DestroyedTexture, QuerySet, Resource, Sampler,
@@ -244,6 +245,10 @@ pub(crate) struct LifetimeTracker<A: HalApi> {
     /// device.lose or by the UserCallbacks returned from maintain when the device
     /// has been destroyed and its queues are empty.
     pub device_lost_closure: Option<DeviceLostClosure>,
+
+    /// Indirect call validation failures of completed submissions, waiting
+    /// to be reported by `Global::device_take_indirect_validation_errors`.
+    pub indirect_validation_errors: Vec<IndirectValidationError>,
 }
 
 impl<A: HalApi> LifetimeTracker<A> {
@@ -257,6 +262,7 @@ impl<A: HalApi> LifetimeTracker<A> {
             ready_to_map: Vec::new(),
             work_done_closures: SmallVec::new(),
             device_lost_closure: None,
+            indirect_validation_errors: Vec::new(),
         }
     }
 
@@ -365,6 +371,12 @@ impl<A: HalApi> LifetimeTracker<A> {
             log::debug!("Active submission {} is done", a.index);
             self.ready_to_map.extend(a.mapped);
             for encoder in a.encoders {
+                self.indirect_validation_errors.extend(
+                    encoder
+                        .indirect_validation
+                        .iter()
+                        .filter_map(|resources| resources.error()),
+                );
                 let raw = unsafe { encoder.land() };
                 command_allocator.release_encoder(raw);
             }
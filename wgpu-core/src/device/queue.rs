@@ -14,6 +14,7 @@ use crate::{
     hal_label,
     id::{self, QueueId},
     identity::{GlobalIdentityHandlerFactory, Input},
+    indirect_validation::PassResources,
     init_tracker::{has_copy_partial_init_tracker_coverage, TextureInitRange},
     resource::{
         Buffer, BufferAccessError, BufferMapState, DestroyedBuffer, DestroyedTexture, Resource,
@@ -154,6 +155,9 @@ pub enum TempResource<A: HalApi> {
 pub(crate) struct EncoderInFlight<A: HalApi> {
     raw: A::CommandEncoder,
     cmd_buffers: Vec<A::CommandBuffer>,
+    /// Objects used by indirect call validation, destroyed once the
+    /// encoder has landed.
+    pub(crate) indirect_validation: Vec<PassResources<A>>,
 }
 
 impl<A: HalApi> EncoderInFlight<A> {
@@ -253,6 +257,7 @@ impl<A: HalApi> PendingWrites<A> {
             Some(EncoderInFlight {
                 raw: mem::replace(&mut self.command_encoder, new_encoder),
                 cmd_buffers: mem::take(&mut self.executing_command_buffers),
+                indirect_validation: Vec::new(),
             })
         } else {
             None
@@ -1391,6 +1396,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         active_executions.push(EncoderInFlight {
                             raw: baked.encoder,
                             cmd_buffers: baked.list,
+                            indirect_validation: baked.indirect_validation,
                         });
                     }
 
@@ -13,6 +13,7 @@ use crate::{
     hal_label,
     hub::Hub,
     id::{self, DeviceId, QueueId},
+    indirect_validation::{CreateIndirectValidationError, IndirectValidation},
     init_tracker::{
         BufferInitTracker, BufferInitTrackerAction, MemoryInitKind, TextureInitRange,
         TextureInitTracker, TextureInitTrackerAction,
@@ -128,6 +129,9 @@ pub struct Device<A: HalApi> {
     pub(crate) features: wgt::Features,
     pub(crate) downlevel: wgt::DownlevelCapabilities,
     pub(crate) instance_flags: wgt::InstanceFlags,
+    /// Pipeline validating indirect call arguments, if
+    /// [`wgt::InstanceFlags::VALIDATION_INDIRECT_CALL`] is enabled.
+    pub(crate) indirect_validation: Option<IndirectValidation<A>>,
     pub(crate) pending_writes: Mutex<Option<PendingWrites<A>>>,
     #[cfg(feature = "trace")]
     pub(crate) trace: Mutex<Option<trace::Trace>>,
@@ -151,6 +155,9 @@ impl<A: HalApi> Drop for Device<A> {
         let pending_writes = self.pending_writes.lock().take().unwrap();
         pending_writes.dispose(&raw);
         self.command_allocator.lock().take().unwrap().dispose(&raw);
+        if let Some(indirect_validation) = self.indirect_validation.take() {
+            indirect_validation.dispose(&raw);
+        }
         unsafe {
             raw.destroy_buffer(self.zero_buffer.take().unwrap());
             raw.destroy_fence(self.fence.write().take().unwrap());
@@ -160,12 +167,39 @@ impl<A: HalApi> Drop for Device<A> {
     }
 }
 
+/// How a render pipeline produces the primitives it rasterizes.
+enum GeometryState<'a, 'b> {
+    /// Vertex buffers and a vertex shader.
+    Vertex(&'b pipeline::VertexState<'a>),
+    /// An optional task shader and a mesh shader.
+    Mesh {
+        task: Option<&'b pipeline::ProgrammableStageDescriptor<'a>>,
+        mesh: &'b pipeline::ProgrammableStageDescriptor<'a>,
+    },
+}
+
+/// The parts of [`pipeline::RenderPipelineDescriptor`] and
+/// [`pipeline::MeshPipelineDescriptor`] that render pipeline creation shares.
+struct RenderPipelineParts<'a, 'b> {
+    label: &'b crate::Label<'a>,
+    layout: Option<id::PipelineLayoutId>,
+    geometry: GeometryState<'a, 'b>,
+    primitive: wgt::PrimitiveState,
+    depth_stencil: &'b Option<wgt::DepthStencilState>,
+    multisample: wgt::MultisampleState,
+    fragment: &'b Option<pipeline::FragmentState<'a>>,
+    multiview: Option<NonZeroU32>,
+    cache: Option<id::PipelineCacheId>,
+}
+
 #[derive(Clone, Debug, Error)]
 pub enum CreateDeviceError {
     #[error("Not enough memory left to create device")]
     OutOfMemory,
     #[error("Failed to create internal buffer for initializing textures")]
     FailedToCreateZeroBuffer(#[from] DeviceError),
+    #[error("Failed to create internal pipeline for validating indirect calls")]
+    FailedToCreateIndirectValidation(#[from] CreateIndirectValidationError),
 }
 
 impl<A: HalApi> Device<A> {
@@ -249,6 +283,19 @@ impl<A: HalApi> Device<A> {
         let alignments = adapter.raw.capabilities.alignments.clone();
         let downlevel = adapter.raw.capabilities.downlevel.clone();
 
+        let indirect_validation =
+            if instance_flags.contains(wgt::InstanceFlags::VALIDATION_INDIRECT_CALL) {
+                Some(IndirectValidation::new(
+                    &raw_device,
+                    &desc.required_limits,
+                    desc.required_features,
+                    &downlevel,
+                    instance_flags,
+                )?)
+            } else {
+                None
+            };
+
         Ok(Self {
             raw: Some(raw_device),
             adapter: adapter.clone(),
@@ -284,6 +331,7 @@ impl<A: HalApi> Device<A> {
             features: desc.required_features,
             downlevel,
             instance_flags,
+            indirect_validation,
             pending_writes: Mutex::new(Some(pending_writes)),
         })
     }
@@ -530,6 +578,11 @@ impl<A: HalApi> Device<A> {
             usage |= hal::BufferUses::COPY_DST;
         }
 
+        if self.indirect_validation.is_some() && desc.usage.contains(wgt::BufferUsages::INDIRECT) {
+            // The validation pre-pass reads indirect arguments from a storage binding.
+            usage |= hal::BufferUses::STORAGE_READ;
+        }
+
         let actual_size = if desc.size == 0 {
             wgt::COPY_BUFFER_ALIGNMENT
         } else if desc.usage.contains(wgt::BufferUsages::VERTEX) {
@@ -1416,22 +1469,41 @@ impl<A: HalApi> Device<A> {
                 None
             };
 
-        let info = naga::valid::Validator::new(naga::valid::ValidationFlags::all(), caps)
-            .validate(&module)
-            .map_err(|inner| {
-                pipeline::CreateShaderModuleError::Validation(pipeline::ShaderError {
-                    source,
-                    label: desc.label.as_ref().map(|l| l.to_string()),
-                    inner: Box::new(inner),
-                })
-            })?;
+        let mut validator = naga::valid::Validator::new(naga::valid::ValidationFlags::all(), caps);
+        let result = validator.validate(&module);
+        #[cfg(feature = "opt")]
+        let (module, result) = match desc.optimization {
+            // The passes may panic on invalid modules.
+            Some(ref options) if result.is_ok() => {
+                profiling::scope!("naga::opt::optimize");
+                let mut optimized = module.into_owned();
+                let result = naga::opt::optimize(&mut optimized, options, &mut validator);
+                (Cow::Owned(optimized), result)
+            }
+            _ => (module, result),
+        };
+        let info = result.map_err(|inner| {
+            pipeline::CreateShaderModuleError::Validation(pipeline::ShaderError {
+                source: source.clone(),
+                label: desc.label.as_ref().map(|l| l.to_string()),
+                inner: Box::new(inner),
+            })
+        })?;
+
+        // The backends key the shaders they translate the module to on its
+        // source, which an optimized module doesn't match anymore.
+        #[cfg(feature = "opt")]
+        let optimized = desc.optimization.is_some();
+        #[cfg(not(feature = "opt"))]
+        let optimized = false;
 
         let interface =
-            validation::Interface::new(&module, &info, self.limits.clone(), self.features);
+            validation::Interface::new(&module, &info, self.limits.clone(), self.features)?;
         let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
             module,
             info,
             debug_source,
+            source: (!source.is_empty() && !optimized).then_some(Cow::Owned(source)),
         });
         let hal_desc = hal::ShaderModuleDescriptor {
             label: desc.label.to_hal(self.instance_flags),
@@ -1507,6 +1579,60 @@ impl<A: HalApi> Device<A> {
         })
     }
 
+    /// # Safety
+    ///
+    /// Once validated, `desc.data` is passed to the backend as-is, see
+    /// [`Global::device_create_pipeline_cache`].
+    ///
+    /// [`Global::device_create_pipeline_cache`]: crate::global::Global::device_create_pipeline_cache
+    pub(crate) unsafe fn create_pipeline_cache(
+        self: &Arc<Self>,
+        desc: &pipeline::PipelineCacheDescriptor,
+    ) -> Result<pipeline::PipelineCache<A>, pipeline::CreatePipelineCacheError> {
+        let data = match desc.data.as_deref() {
+            Some(data) => {
+                let validated = match self.pipeline_cache_origin() {
+                    Some(origin) => crate::pipeline_cache::validate_cache_data(data, origin),
+                    None => Err(pipeline::PipelineCacheValidationError::Unsupported),
+                };
+                match validated {
+                    Ok(data) => Some(data),
+                    Err(error) if desc.fallback => {
+                        log::warn!("Ignoring pipeline cache data: {error}");
+                        None
+                    }
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            None => None,
+        };
+
+        let hal_desc = hal::PipelineCacheDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            data,
+        };
+        let raw =
+            unsafe { self.raw().create_pipeline_cache(&hal_desc) }.map_err(|err| match err {
+                hal::PipelineCacheError::Device(error) => DeviceError::from(error),
+            })?;
+
+        Ok(pipeline::PipelineCache {
+            raw: Some(raw),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default()),
+        })
+    }
+
+    /// Identify this device in the header of pipeline cache data, or `None`
+    /// if the backend doesn't support pipeline caches.
+    pub(crate) fn pipeline_cache_origin(&self) -> Option<crate::pipeline_cache::CacheOrigin> {
+        let driver_key = self.raw().pipeline_cache_validation_key()?;
+        Some(crate::pipeline_cache::CacheOrigin::new(
+            &self.adapter.raw.info,
+            driver_key,
+        ))
+    }
+
     /// Generate information about late-validated buffer bindings for pipelines.
     //TODO: should this be combined with `get_introspection_bind_group_layouts` in some way?
     pub(crate) fn make_late_sized_buffer_groups(
@@ -1677,6 +1803,12 @@ impl<A: HalApi> Device<A> {
                 );
             }
 
+            if entry
+                .visibility
+                .intersects(wgt::ShaderStages::TASK | wgt::ShaderStages::MESH)
+            {
+                required_features |= wgt::Features::MESH_SHADER;
+            }
             if entry.visibility.contains(wgt::ShaderStages::VERTEX) {
                 if writable_storage == WritableStorage::Yes {
                     required_features |= wgt::Features::VERTEX_WRITABLE_STORAGE;
@@ -1928,6 +2060,54 @@ impl<A: HalApi> Device<A> {
         Ok(())
     }
 
+    /// Replace each external texture entry with entries for its planes and
+    /// its parameters buffer, laid out as by
+    /// [`naga::ResourceBinding::external_texture_bindings`].
+    ///
+    /// Planes that the texture doesn't have are bound to its first plane, as
+    /// shaders never read them.
+    fn expand_external_textures<'a>(
+        entries: &'a [binding_model::BindGroupEntry<'a>],
+    ) -> Result<Cow<'a, [binding_model::BindGroupEntry<'a>]>, binding_model::CreateBindGroupError>
+    {
+        use crate::binding_model::{BindGroupEntry, BindingResource as Br};
+
+        if !entries
+            .iter()
+            .any(|entry| matches!(entry.resource, Br::ExternalTexture(_)))
+        {
+            return Ok(Cow::Borrowed(entries));
+        }
+
+        let mut expanded = Vec::with_capacity(entries.len() + 3);
+        for entry in entries {
+            let Br::ExternalTexture(ref external) = entry.resource else {
+                expanded.push(entry.clone());
+                continue;
+            };
+            let planes = &external.planes;
+            if planes.is_empty() || planes.len() > 3 {
+                return Err(
+                    binding_model::CreateBindGroupError::WrongExternalTexturePlaneCount {
+                        binding: entry.binding,
+                        count: planes.len(),
+                    },
+                );
+            }
+            for (binding, i) in (entry.binding..).zip(0..3) {
+                expanded.push(BindGroupEntry {
+                    binding,
+                    resource: Br::TextureView(*planes.get(i).unwrap_or(&planes[0])),
+                });
+            }
+            expanded.push(BindGroupEntry {
+                binding: entry.binding + 3,
+                resource: Br::Buffer(external.params.clone()),
+            });
+        }
+        Ok(Cow::Owned(expanded))
+    }
+
     // This function expects the provided bind group layout to be resolved
     // (not passing a duplicate) beforehand.
     pub(crate) fn create_bind_group(
@@ -1937,10 +2117,11 @@ impl<A: HalApi> Device<A> {
         hub: &Hub<A>,
     ) -> Result<binding_model::BindGroup<A>, binding_model::CreateBindGroupError> {
         use crate::binding_model::{BindingResource as Br, CreateBindGroupError as Error};
+        let entries = Self::expand_external_textures(&desc.entries)?;
         {
             // Check that the number of entries in the descriptor matches
             // the number of entries in the layout.
-            let actual = desc.entries.len();
+            let actual = entries.len();
             let expected = layout.entries.len();
             if actual != expected {
                 return Err(Error::BindingsNumMismatch { expected, actual });
@@ -1963,12 +2144,12 @@ impl<A: HalApi> Device<A> {
 
         let mut used_buffer_ranges = Vec::new();
         let mut used_texture_ranges = Vec::new();
-        let mut hal_entries = Vec::with_capacity(desc.entries.len());
+        let mut hal_entries = Vec::with_capacity(entries.len());
         let mut hal_buffers = Vec::new();
         let mut hal_samplers = Vec::new();
         let mut hal_textures = Vec::new();
         let snatch_guard = self.snatchable_lock.read();
-        for entry in desc.entries.iter() {
+        for entry in entries.iter() {
             let binding = entry.binding;
             // Find the corresponding declaration in the layout
             let decl = layout
@@ -2137,6 +2318,7 @@ impl<A: HalApi> Device<A> {
 
                     (res_index, num_bindings)
                 }
+                Br::ExternalTexture(_) => unreachable!("expanded above"),
             };
 
             hal_entries.push(hal::BindGroupEntry {
@@ -2549,6 +2731,22 @@ impl<A: HalApi> Device<A> {
             None => None,
         };
 
+        let cache = match desc.cache {
+            Some(cache_id) => {
+                let cache = hub
+                    .pipeline_caches
+                    .get(cache_id)
+                    .map_err(|_| pipeline::CreateComputePipelineError::InvalidCache)?;
+
+                if cache.device.as_info().id() != self.as_info().id() {
+                    return Err(DeviceError::WrongDevice.into());
+                }
+
+                Some(cache)
+            }
+            None => None,
+        };
+
         let mut binding_layout_source = match pipeline_layout {
             Some(ref pipeline_layout) => {
                 validation::BindingLayoutSource::Provided(pipeline_layout.get_binding_maps())
@@ -2596,6 +2794,7 @@ impl<A: HalApi> Device<A> {
                 entry_point: desc.stage.entry_point.as_ref(),
                 module: shader_module.raw(),
             },
+            cache: cache.as_ref().map(|cache| cache.raw()),
         };
 
         let raw = unsafe {
@@ -2633,6 +2832,51 @@ impl<A: HalApi> Device<A> {
         desc: &pipeline::RenderPipelineDescriptor,
         implicit_context: Option<ImplicitPipelineContext>,
         hub: &Hub<A>,
+    ) -> Result<pipeline::RenderPipeline<A>, pipeline::CreateRenderPipelineError> {
+        let parts = RenderPipelineParts {
+            label: &desc.label,
+            layout: desc.layout,
+            geometry: GeometryState::Vertex(&desc.vertex),
+            primitive: desc.primitive,
+            depth_stencil: &desc.depth_stencil,
+            multisample: desc.multisample,
+            fragment: &desc.fragment,
+            multiview: desc.multiview,
+            cache: desc.cache,
+        };
+        self.create_render_pipeline_impl(adapter, parts, implicit_context, hub)
+    }
+
+    pub(crate) fn create_mesh_pipeline(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: &pipeline::MeshPipelineDescriptor,
+        implicit_context: Option<ImplicitPipelineContext>,
+        hub: &Hub<A>,
+    ) -> Result<pipeline::RenderPipeline<A>, pipeline::CreateRenderPipelineError> {
+        let parts = RenderPipelineParts {
+            label: &desc.label,
+            layout: desc.layout,
+            geometry: GeometryState::Mesh {
+                task: desc.task.as_ref(),
+                mesh: &desc.mesh,
+            },
+            primitive: desc.primitive,
+            depth_stencil: &desc.depth_stencil,
+            multisample: desc.multisample,
+            fragment: &desc.fragment,
+            multiview: desc.multiview,
+            cache: desc.cache,
+        };
+        self.create_render_pipeline_impl(adapter, parts, implicit_context, hub)
+    }
+
+    fn create_render_pipeline_impl(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: RenderPipelineParts,
+        implicit_context: Option<ImplicitPipelineContext>,
+        hub: &Hub<A>,
     ) -> Result<pipeline::RenderPipeline<A>, pipeline::CreateRenderPipelineError> {
         use wgt::TextureFormatFeatureFlags as Tfff;
 
@@ -2648,6 +2892,14 @@ impl<A: HalApi> Device<A> {
             }
         }
 
+        let (geometry_stage, vertex_buffer_layouts) = match desc.geometry {
+            GeometryState::Vertex(vertex) => (wgt::ShaderStages::VERTEX, &vertex.buffers[..]),
+            GeometryState::Mesh { .. } => {
+                self.require_features(wgt::Features::MESH_SHADER)?;
+                (wgt::ShaderStages::MESH, &[][..])
+            }
+        };
+
         let mut shader_binding_sizes = FastHashMap::default();
 
         let num_attachments = desc.fragment.as_ref().map(|f| f.targets.len()).unwrap_or(0);
@@ -2681,12 +2933,12 @@ impl<A: HalApi> Device<A> {
         let mut io = validation::StageIo::default();
         let mut validated_stages = wgt::ShaderStages::empty();
 
-        let mut vertex_steps = Vec::with_capacity(desc.vertex.buffers.len());
-        let mut vertex_buffers = Vec::with_capacity(desc.vertex.buffers.len());
+        let mut vertex_steps = Vec::with_capacity(vertex_buffer_layouts.len());
+        let mut vertex_buffers = Vec::with_capacity(vertex_buffer_layouts.len());
         let mut total_attributes = 0;
         let mut shader_expects_dual_source_blending = false;
         let mut pipeline_expects_dual_source_blending = false;
-        for (i, vb_state) in desc.vertex.buffers.iter().enumerate() {
+        for (i, vb_state) in vertex_buffer_layouts.iter().enumerate() {
             vertex_steps.push(pipeline::VertexStep {
                 stride: vb_state.array_stride,
                 mode: vb_state.step_mode,
@@ -2760,10 +3012,15 @@ impl<A: HalApi> Device<A> {
             );
         }
 
-        if desc.primitive.strip_index_format.is_some() && !desc.primitive.topology.is_strip() {
+        // Mesh shaders declare their own output topology.
+        let strip_index_format = match desc.geometry {
+            GeometryState::Vertex(_) => desc.primitive.strip_index_format,
+            GeometryState::Mesh { .. } => None,
+        };
+        if strip_index_format.is_some() && !desc.primitive.topology.is_strip() {
             return Err(
                 pipeline::CreateRenderPipelineError::StripIndexFormatForNonStripTopology {
-                    strip_index_format: desc.primitive.strip_index_format,
+                    strip_index_format,
                     topology: desc.primitive.topology,
                 },
             );
@@ -2790,6 +3047,7 @@ impl<A: HalApi> Device<A> {
             );
         }
 
+        let mut target_bytes_per_sample = 0;
         for (i, cs) in color_targets.iter().enumerate() {
             if let Some(cs) = cs.as_ref() {
                 let error = loop {
@@ -2819,6 +3077,20 @@ impl<A: HalApi> Device<A> {
                     if !hal::FormatAspects::from(cs.format).contains(hal::FormatAspects::COLOR) {
                         break Some(pipeline::ColorStateError::FormatNotColor(cs.format));
                     }
+                    if let (Some(cost), Some(alignment)) = (
+                        cs.format.target_pixel_byte_cost(),
+                        cs.format.target_component_alignment(),
+                    ) {
+                        target_bytes_per_sample =
+                            wgt::math::align_to(target_bytes_per_sample, alignment) + cost;
+                        let limit = self.limits.max_color_attachment_bytes_per_sample;
+                        if target_bytes_per_sample > limit {
+                            break Some(pipeline::ColorStateError::TooManyBytesPerSample {
+                                total: target_bytes_per_sample,
+                                limit,
+                            });
+                        }
+                    }
                     if desc.multisample.count > 1
                         && !format_features
                             .flags
@@ -2932,6 +3204,22 @@ impl<A: HalApi> Device<A> {
             None => None,
         };
 
+        let cache = match desc.cache {
+            Some(cache_id) => {
+                let cache = hub
+                    .pipeline_caches
+                    .get(cache_id)
+                    .map_err(|_| pipeline::CreateRenderPipelineError::InvalidCache)?;
+
+                if cache.device.as_info().id() != self.as_info().id() {
+                    return Err(DeviceError::WrongDevice.into());
+                }
+
+                Some(cache)
+            }
+            None => None,
+        };
+
         let mut binding_layout_source = match pipeline_layout {
             Some(ref pipeline_layout) => {
                 validation::BindingLayoutSource::Provided(pipeline_layout.get_binding_maps())
@@ -2947,22 +3235,29 @@ impl<A: HalApi> Device<A> {
             sc
         };
 
-        let vertex_shader_module;
-        let vertex_stage = {
-            let stage_desc = &desc.vertex.stage;
-            let stage = wgt::ShaderStages::VERTEX;
-
-            vertex_shader_module = hub.shader_modules.get(stage_desc.module).map_err(|_| {
+        let geometry_stage_descs = match desc.geometry {
+            GeometryState::Vertex(vertex) => {
+                [None, Some((&vertex.stage, wgt::ShaderStages::VERTEX))]
+            }
+            GeometryState::Mesh { task, mesh } => [
+                task.map(|task| (task, wgt::ShaderStages::TASK)),
+                Some((mesh, wgt::ShaderStages::MESH)),
+            ],
+        };
+        let mut geometry_shader_modules = ArrayVec::<_, 2>::new();
+        let mut geometry_entry_points = ArrayVec::<_, 2>::new();
+        for (stage_desc, stage) in geometry_stage_descs.into_iter().flatten() {
+            let shader_module = hub.shader_modules.get(stage_desc.module).map_err(|_| {
                 pipeline::CreateRenderPipelineError::Stage {
                     stage,
                     error: validation::StageError::InvalidModule,
                 }
             })?;
-            if vertex_shader_module.device.as_info().id() != self.as_info().id() {
+            if shader_module.device.as_info().id() != self.as_info().id() {
                 return Err(DeviceError::WrongDevice.into());
             }
 
-            if let Some(ref interface) = vertex_shader_module.interface {
+            if let Some(ref interface) = shader_module.interface {
                 io = interface
                     .check_stage(
                         &mut binding_layout_source,
@@ -2976,11 +3271,16 @@ impl<A: HalApi> Device<A> {
                 validated_stages |= stage;
             }
 
-            hal::ProgrammableStage {
-                module: vertex_shader_module.raw(),
-                entry_point: stage_desc.entry_point.as_ref(),
-            }
-        };
+            geometry_shader_modules.push(shader_module);
+            geometry_entry_points.push(stage_desc.entry_point.as_ref());
+        }
+        let mut geometry_stages = geometry_shader_modules
+            .iter()
+            .zip(geometry_entry_points.iter())
+            .map(|(module, &entry_point)| hal::ProgrammableStage {
+                module: module.raw(),
+                entry_point,
+            });
 
         let mut fragment_shader_module = None;
         let fragment_stage = match desc.fragment {
@@ -2996,7 +3296,7 @@ impl<A: HalApi> Device<A> {
                         })?,
                 );
 
-                if validated_stages == wgt::ShaderStages::VERTEX {
+                if validated_stages.contains(geometry_stage) {
                     if let Some(ref interface) = shader_module.interface {
                         io = interface
                             .check_stage(
@@ -3073,7 +3373,7 @@ impl<A: HalApi> Device<A> {
         }
         let last_stage = match desc.fragment {
             Some(_) => wgt::ShaderStages::FRAGMENT,
-            None => wgt::ShaderStages::VERTEX,
+            None => geometry_stage,
         };
         if desc.layout.is_none() && !validated_stages.contains(last_stage) {
             return Err(pipeline::ImplicitLayoutError::ReflectionError(last_stage).into());
@@ -3116,23 +3416,49 @@ impl<A: HalApi> Device<A> {
         let late_sized_buffer_groups =
             Device::make_late_sized_buffer_groups(&shader_binding_sizes, &pipeline_layout);
 
-        let pipeline_desc = hal::RenderPipelineDescriptor {
-            label: desc.label.to_hal(self.instance_flags),
-            layout: pipeline_layout.raw(),
-            vertex_buffers: &vertex_buffers,
-            vertex_stage,
-            primitive: desc.primitive,
-            depth_stencil: desc.depth_stencil.clone(),
-            multisample: desc.multisample,
-            fragment_stage,
-            color_targets,
-            multiview: desc.multiview,
-        };
-        let raw = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .create_render_pipeline(&pipeline_desc)
+        let raw = match desc.geometry {
+            GeometryState::Vertex(_) => {
+                let pipeline_desc = hal::RenderPipelineDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    layout: pipeline_layout.raw(),
+                    vertex_buffers: &vertex_buffers,
+                    vertex_stage: geometry_stages.next().unwrap(),
+                    primitive: desc.primitive,
+                    depth_stencil: desc.depth_stencil.clone(),
+                    multisample: desc.multisample,
+                    fragment_stage,
+                    color_targets,
+                    multiview: desc.multiview,
+                    cache: cache.as_ref().map(|cache| cache.raw()),
+                };
+                unsafe {
+                    self.raw
+                        .as_ref()
+                        .unwrap()
+                        .create_render_pipeline(&pipeline_desc)
+                }
+            }
+            GeometryState::Mesh { task, .. } => {
+                let pipeline_desc = hal::MeshPipelineDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    layout: pipeline_layout.raw(),
+                    task_stage: task.and_then(|_| geometry_stages.next()),
+                    mesh_stage: geometry_stages.next().unwrap(),
+                    primitive: desc.primitive,
+                    depth_stencil: desc.depth_stencil.clone(),
+                    multisample: desc.multisample,
+                    fragment_stage,
+                    color_targets,
+                    multiview: desc.multiview,
+                    cache: cache.as_ref().map(|cache| cache.raw()),
+                };
+                unsafe {
+                    self.raw
+                        .as_ref()
+                        .unwrap()
+                        .create_mesh_pipeline(&pipeline_desc)
+                }
+            }
         }
         .map_err(|err| match err {
             hal::PipelineError::Device(error) => {
@@ -3163,6 +3489,12 @@ impl<A: HalApi> Device<A> {
         };
 
         let mut flags = pipeline::PipelineFlags::empty();
+        if let GeometryState::Mesh { task, .. } = desc.geometry {
+            flags |= pipeline::PipelineFlags::MESH;
+            if task.is_some() {
+                flags |= pipeline::PipelineFlags::TASK;
+            }
+        }
         for state in color_targets.iter().filter_map(|s| s.as_ref()) {
             if let Some(ref bs) = state.blend {
                 if bs.color.uses_constant() | bs.alpha.uses_constant() {
@@ -3184,7 +3516,7 @@ impl<A: HalApi> Device<A> {
 
         let shader_modules = {
             let mut shader_modules = ArrayVec::new();
-            shader_modules.push(vertex_shader_module);
+            shader_modules.extend(geometry_shader_modules);
             shader_modules.extend(fragment_shader_module);
             shader_modules
         };
@@ -3196,7 +3528,7 @@ impl<A: HalApi> Device<A> {
             pass_context,
             _shader_modules: shader_modules,
             flags,
-            strip_index_format: desc.primitive.strip_index_format,
+            strip_index_format,
             vertex_steps,
             late_sized_buffer_groups,
             info: ResourceInfo::new(desc.label.borrow_or_default()),
@@ -6,6 +6,7 @@ use crate::{gfx_select, global::Global, identity::IdentityManagerFactory};
 pub struct ErrorFormatter<'a> {
     writer: &'a mut dyn fmt::Write,
     global: &'a Global<IdentityManagerFactory>,
+    resources: Vec<wgt::ErrorResource>,
 }
 
 impl<'a> ErrorFormatter<'a> {
@@ -18,34 +19,46 @@ impl<'a> ErrorFormatter<'a> {
     }
 
     pub fn label(&mut self, label_key: &str, label_value: &String) {
-        if !label_key.is_empty() && !label_value.is_empty() {
-            self.note(&format!("{label_key} = `{label_value}`"));
+        self.resource(label_key, None, label_value);
+    }
+
+    fn resource(&mut self, kind: &str, id: Option<&dyn fmt::Debug>, label: &String) {
+        if kind.is_empty() {
+            return;
+        }
+        self.resources.push(wgt::ErrorResource {
+            kind: kind.to_string(),
+            id: id.map(|id| format!("{id:?}")),
+            label: label.clone(),
+        });
+        if !label.is_empty() {
+            self.note(&format!("{kind} = `{label}`"));
         }
     }
 
     pub fn bind_group_label(&mut self, id: &crate::id::BindGroupId) {
         let label: String = gfx_select!(id => self.global.bind_group_label(*id));
-        self.label("bind group", &label);
+        self.resource("bind group", Some(id), &label);
     }
 
     pub fn bind_group_layout_label(&mut self, id: &crate::id::BindGroupLayoutId) {
         let label: String = gfx_select!(id => self.global.bind_group_layout_label(*id));
-        self.label("bind group layout", &label);
+        self.resource("bind group layout", Some(id), &label);
     }
 
     pub fn render_pipeline_label(&mut self, id: &crate::id::RenderPipelineId) {
         let label: String = gfx_select!(id => self.global.render_pipeline_label(*id));
-        self.label("render pipeline", &label);
+        self.resource("render pipeline", Some(id), &label);
     }
 
     pub fn compute_pipeline_label(&mut self, id: &crate::id::ComputePipelineId) {
         let label: String = gfx_select!(id => self.global.compute_pipeline_label(*id));
-        self.label("compute pipeline", &label);
+        self.resource("compute pipeline", Some(id), &label);
     }
 
     pub fn buffer_label_with_key(&mut self, id: &crate::id::BufferId, key: &str) {
         let label: String = gfx_select!(id => self.global.buffer_label(*id));
-        self.label(key, &label);
+        self.resource(key, Some(id), &label);
     }
 
     pub fn buffer_label(&mut self, id: &crate::id::BufferId) {
@@ -54,7 +67,7 @@ impl<'a> ErrorFormatter<'a> {
 
     pub fn texture_label_with_key(&mut self, id: &crate::id::TextureId, key: &str) {
         let label: String = gfx_select!(id => self.global.texture_label(*id));
-        self.label(key, &label);
+        self.resource(key, Some(id), &label);
     }
 
     pub fn texture_label(&mut self, id: &crate::id::TextureId) {
@@ -63,7 +76,7 @@ impl<'a> ErrorFormatter<'a> {
 
     pub fn texture_view_label_with_key(&mut self, id: &crate::id::TextureViewId, key: &str) {
         let label: String = gfx_select!(id => self.global.texture_view_label(*id));
-        self.label(key, &label);
+        self.resource(key, Some(id), &label);
     }
 
     pub fn texture_view_label(&mut self, id: &crate::id::TextureViewId) {
@@ -72,17 +85,17 @@ impl<'a> ErrorFormatter<'a> {
 
     pub fn sampler_label(&mut self, id: &crate::id::SamplerId) {
         let label: String = gfx_select!(id => self.global.sampler_label(*id));
-        self.label("sampler", &label);
+        self.resource("sampler", Some(id), &label);
     }
 
     pub fn command_buffer_label(&mut self, id: &crate::id::CommandBufferId) {
         let label: String = gfx_select!(id => self.global.command_buffer_label(*id));
-        self.label("command buffer", &label);
+        self.resource("command buffer", Some(id), &label);
     }
 
     pub fn query_set_label(&mut self, id: &crate::id::QuerySetId) {
         let label: String = gfx_select!(id => self.global.query_set_label(*id));
-        self.label("query set", &label);
+        self.resource("query set", Some(id), &label);
     }
 }
 
@@ -97,58 +110,848 @@ pub fn format_pretty_any(
     global: &Global<IdentityManagerFactory>,
     error: &(dyn Error + 'static),
 ) {
-    let mut fmt = ErrorFormatter { writer, global };
+    let mut fmt = ErrorFormatter {
+        writer,
+        global,
+        resources: Vec::new(),
+    };
+    fmt_pretty_any(&mut fmt, error)
+}
+
+/// Build the structured form of `error` and the chain of errors that caused it.
+///
+/// The resources listed for each error are the ones [`format_pretty_any`]
+/// would add notes for.
+pub fn report_any(
+    global: &Global<IdentityManagerFactory>,
+    error: &(dyn Error + 'static),
+) -> wgt::ErrorReport {
+    let mut pretty = String::new();
+    let mut fmt = ErrorFormatter {
+        writer: &mut pretty,
+        global,
+        resources: Vec::new(),
+    };
+    fmt_pretty_any(&mut fmt, error);
+    let resources = fmt.resources;
+
+    wgt::ErrorReport {
+        code: error_code(error).to_string(),
+        message: error.to_string(),
+        resources,
+        source: error
+            .source()
+            .map(|source| Box::new(report_any(global, source))),
+    }
+}
 
+fn fmt_pretty_any(fmt: &mut ErrorFormatter, error: &(dyn Error + 'static)) {
     if let Some(pretty_err) = error.downcast_ref::<ContextError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
 
     if let Some(pretty_err) = error.downcast_ref::<crate::command::RenderCommandError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::binding_model::CreateBindGroupError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) =
         error.downcast_ref::<crate::binding_model::CreatePipelineLayoutError>()
     {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::ExecutionError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::RenderPassErrorInner>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::RenderPassError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::ComputePassErrorInner>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::ComputePassError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::RenderBundleError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::TransferError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::PassErrorScope>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::track::UsageConflict>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::QueryError>() {
-        return pretty_err.fmt_pretty(&mut fmt);
+        return pretty_err.fmt_pretty(fmt);
     }
 
     // default
     fmt.error(error)
 }
 
+/// Return a stable code for the kind of `error`.
+///
+/// For this crate's error types, this is their [`ErrorCode::code`]. Errors
+/// from other crates get the code `External`.
+pub fn error_code(error: &(dyn Error + 'static)) -> &'static str {
+    use crate::pipeline::ShaderError;
+
+    if let Some(code) = known_error_code(error) {
+        return code;
+    }
+    // `ShaderError` is generic, so `error_codes!` can't list it.
+    #[cfg(feature = "wgsl")]
+    if let Some(error) = error.downcast_ref::<ShaderError<naga::front::wgsl::ParseError>>() {
+        return error.code();
+    }
+    if let Some(error) =
+        error.downcast_ref::<ShaderError<naga::WithSpan<naga::valid::ValidationError>>>()
+    {
+        return error.code();
+    }
+    "External"
+}
+
+/// An error with a stable code, for [`wgt::ErrorReport::code`].
+///
+/// The code is the type's name, followed by the variant's for enums, like
+/// `CreateBufferError::UnalignedSize`. Codes are spelled out in a table in
+/// this module rather than taken from `Debug` or [`std::any::type_name`], so
+/// every new variant needs an entry and renaming one doesn't change its code
+/// unnoticed.
+pub trait ErrorCode: Error {
+    fn code(&self) -> &'static str;
+}
+
+impl<E> ErrorCode for crate::pipeline::ShaderError<E>
+where
+    crate::pipeline::ShaderError<E>: Error,
+{
+    fn code(&self) -> &'static str {
+        "ShaderError"
+    }
+}
+
+/// Implement [`ErrorCode`] for each listed type, and write `known_error_code`
+/// to find the code of any of them.
+///
+/// Structs are listed by name, and enums with all of their variants.
+macro_rules! error_codes {
+    ($($ty:ident $({ $($(#[$attr:meta])* $variant:ident,)* })?,)*) => {
+        $(
+            impl ErrorCode for $ty {
+                fn code(&self) -> &'static str {
+                    error_codes!(@code self $ty $({ $($(#[$attr])* $variant,)* })?)
+                }
+            }
+        )*
+
+        fn known_error_code(error: &(dyn Error + 'static)) -> Option<&'static str> {
+            $(
+                if let Some(error) = error.downcast_ref::<$ty>() {
+                    return Some(error.code());
+                }
+            )*
+            None
+        }
+    };
+    (@code $self:ident $ty:ident) => {
+        stringify!($ty)
+    };
+    (@code $self:ident $ty:ident { $($(#[$attr:meta])* $variant:ident,)* }) => {
+        match *$self {
+            $(
+                $(#[$attr])*
+                $ty::$variant { .. } => concat!(stringify!($ty), "::", stringify!($variant)),
+            )*
+        }
+    };
+}
+
+use crate::{
+    binding_model::{
+        BindError, BindGroupLayoutEntryError, BindingTypeMaxCountError, BindingZone,
+        CreateBindGroupError, CreateBindGroupLayoutError, CreatePipelineLayoutError,
+        GetBindGroupLayoutError, PushConstantUploadError,
+    },
+    command::{
+        ClearError, ColorAttachmentError, CommandEncoderError, ComputePassError,
+        ComputePassErrorInner, CopyError, CreateRenderBundleError, DispatchError, DrawError,
+        ExecutionError, PassErrorScope, QueryError, QueryUseError, RenderBundleError,
+        RenderBundleErrorInner, RenderCommandError, RenderPassError, RenderPassErrorInner,
+        ResolveError, TransferError,
+    },
+    device::queue::{InvalidQueue, QueueSubmitError, QueueWriteError},
+    device::resource::CreateDeviceError,
+    device::{
+        DeviceError, InvalidDevice, MissingDownlevelFlags, MissingFeatures,
+        RenderPassCompatibilityError, WaitIdleError,
+    },
+    indirect_validation::{CreateIndirectValidationError, IndirectValidationError},
+    instance::{
+        FailedLimit, GetSurfaceSupportError, InvalidAdapter, IsSurfaceSupportedError,
+        RequestAdapterError, RequestDeviceError,
+    },
+    pipeline::{
+        ColorStateError, CreateComputePipelineError, CreatePipelineCacheError,
+        CreateRenderPipelineError, CreateShaderModuleError, DepthStencilStateError,
+        ImplicitLayoutError, PipelineCacheValidationError,
+    },
+    present::{ConfigureSurfaceError, SurfaceError},
+    resource::{
+        BufferAccessError, CreateBufferError, CreateQuerySetError, CreateSamplerError,
+        CreateTextureError, CreateTextureViewError, DestroyError, TextureDimensionError,
+        TextureViewDestroyError, TextureViewNotRenderableReason,
+    },
+    track::UsageConflict,
+    validation::{
+        BindingError, FilteringError, InputError, MissingBufferUsageError,
+        MissingTextureUsageError, StageError,
+    },
+};
+
+error_codes! {
+    ContextError,
+    BindError {
+        MismatchedDynamicOffsetCount,
+        UnalignedDynamicBinding,
+        DynamicBindingOutOfBounds,
+    },
+    BindGroupLayoutEntryError {
+        StorageTextureCube,
+        StorageTextureReadWrite,
+        ArrayUnsupported,
+        SampleTypeFloatFilterableBindingMultisampled,
+        MissingFeatures,
+        MissingDownlevelFlags,
+    },
+    BindingError {
+        Missing,
+        Invisible,
+        WrongType,
+        WrongAddressSpace,
+        WrongBufferSize,
+        WrongTextureViewDimension,
+        WrongTextureClass,
+        WrongSamplerComparison,
+        InconsistentlyDerivedType,
+        BadStorageFormat,
+        UnsupportedTextureStorageAccess,
+        MissingExternalTextureParams,
+    },
+    BindingTypeMaxCountError,
+    BindingZone {
+        Stage,
+        Pipeline,
+    },
+    BufferAccessError {
+        Device,
+        Failed,
+        Invalid,
+        Destroyed,
+        AlreadyMapped,
+        MapAlreadyPending,
+        MissingBufferUsage,
+        NotMapped,
+        UnalignedRange,
+        UnalignedOffset,
+        UnalignedRangeSize,
+        OutOfBoundsUnderrun,
+        OutOfBoundsOverrun,
+        NegativeRange,
+        MapAborted,
+    },
+    ClearError {
+        MissingClearTextureFeature,
+        InvalidCommandEncoder,
+        InvalidDevice,
+        InvalidBuffer,
+        InvalidTexture,
+        NoValidTextureClearMode,
+        UnalignedFillSize,
+        UnalignedBufferOffset,
+        BufferOverrun,
+        MissingCopyDstUsageFlag,
+        MissingTextureAspect,
+        InvalidTextureLevelRange,
+        InvalidTextureLayerRange,
+        Device,
+    },
+    ColorAttachmentError {
+        InvalidFormat,
+        TooMany,
+    },
+    ColorStateError {
+        FormatNotRenderable,
+        FormatNotBlendable,
+        FormatNotColor,
+        InvalidSampleCount,
+        IncompatibleFormat,
+        InvalidMinMaxBlendFactors,
+        InvalidWriteMask,
+        TooManyBytesPerSample,
+    },
+    CommandEncoderError {
+        Invalid,
+        NotRecording,
+        Device,
+    },
+    ComputePassError,
+    ComputePassErrorInner {
+        Device,
+        Encoder,
+        InvalidBindGroup,
+        InvalidDevice,
+        BindGroupIndexOutOfRange,
+        InvalidPipeline,
+        InvalidQuerySet,
+        InvalidIndirectBuffer,
+        IndirectBufferOverrun,
+        UnalignedIndirectBufferOffset,
+        IndirectBufferWrittenInPass,
+        InvalidBuffer,
+        ResourceUsageConflict,
+        MissingBufferUsage,
+        InvalidPopDebugGroup,
+        Dispatch,
+        Bind,
+        PushConstants,
+        QueryUse,
+        MissingFeatures,
+        MissingDownlevelFlags,
+    },
+    ConfigureSurfaceError {
+        Device,
+        InvalidSurface,
+        InvalidViewFormat,
+        MissingDownlevelFlags,
+        PreviousOutputExists,
+        ZeroArea,
+        TooLarge,
+        UnsupportedQueueFamily,
+        UnsupportedFormat,
+        UnsupportedPresentMode,
+        UnsupportedAlphaMode,
+        UnsupportedUsage,
+        StuckGpu,
+    },
+    CopyError {
+        Encoder,
+        Transfer,
+    },
+    CreateBindGroupError {
+        Device,
+        InvalidLayout,
+        InvalidBuffer,
+        InvalidTextureView,
+        InvalidTexture,
+        InvalidSampler,
+        BindingArrayPartialLengthMismatch,
+        BindingArrayLengthMismatch,
+        BindingArrayZeroLength,
+        WrongExternalTexturePlaneCount,
+        BindingRangeTooLarge,
+        BindingSizeTooSmall,
+        BindingZeroSize,
+        BindingsNumMismatch,
+        DuplicateBinding,
+        MissingBindingDeclaration,
+        MissingBufferUsage,
+        MissingTextureUsage,
+        SingleBindingExpected,
+        UnalignedBufferOffset,
+        BufferRangeTooLarge,
+        WrongBindingType,
+        InvalidTextureMultisample,
+        InvalidTextureSampleType,
+        InvalidTextureDimension,
+        InvalidStorageTextureFormat,
+        InvalidStorageTextureMipLevelCount,
+        WrongSamplerComparison,
+        WrongSamplerFiltering,
+        DepthStencilAspect,
+        StorageReadNotSupported,
+        ResourceUsageConflict,
+    },
+    CreateBindGroupLayoutError {
+        Device,
+        ConflictBinding,
+        Entry,
+        TooManyBindings,
+        InvalidBindingIndex,
+        InvalidVisibility,
+    },
+    CreateBufferError {
+        Device,
+        AccessError,
+        UnalignedSize,
+        InvalidUsage,
+        UsageMismatch,
+        MaxBufferSize,
+        MissingDownlevelFlags,
+    },
+    CreateComputePipelineError {
+        Device,
+        InvalidLayout,
+        InvalidCache,
+        Implicit,
+        Stage,
+        Internal,
+        MissingDownlevelFlags,
+    },
+    CreateDeviceError {
+        OutOfMemory,
+        FailedToCreateZeroBuffer,
+        FailedToCreateIndirectValidation,
+    },
+    CreateIndirectValidationError {
+        Device,
+        Shader,
+        Pipeline,
+        Unsupported,
+    },
+    CreatePipelineCacheError {
+        Device,
+        Validation,
+    },
+    CreatePipelineLayoutError {
+        Device,
+        InvalidBindGroupLayout,
+        MisalignedPushConstantRange,
+        MissingFeatures,
+        MoreThanOnePushConstantRangePerStage,
+        PushConstantRangeTooLarge,
+        TooManyBindings,
+        TooManyGroups,
+    },
+    CreateQuerySetError {
+        Device,
+        ZeroCount,
+        TooManyQueries,
+        MissingFeatures,
+    },
+    CreateRenderBundleError {
+        ColorAttachment,
+        InvalidSampleCount,
+    },
+    CreateRenderPipelineError {
+        ColorAttachment,
+        Device,
+        InvalidLayout,
+        InvalidCache,
+        Implicit,
+        ColorState,
+        DepthStencilState,
+        InvalidSampleCount,
+        TooManyVertexBuffers,
+        TooManyVertexAttributes,
+        VertexStrideTooLarge,
+        UnalignedVertexStride,
+        InvalidVertexAttributeOffset,
+        ShaderLocationClash,
+        StripIndexFormatForNonStripTopology,
+        ConservativeRasterizationNonFillPolygonMode,
+        MissingFeatures,
+        MissingDownlevelFlags,
+        Stage,
+        Internal,
+        UnalignedShader,
+        BlendFactorOnUnsupportedTarget,
+        PipelineExpectsShaderToUseDualSourceBlending,
+        ShaderExpectsPipelineToUseDualSourceBlending,
+    },
+    CreateSamplerError {
+        Device,
+        InvalidLodMinClamp,
+        InvalidLodMaxClamp,
+        InvalidAnisotropy,
+        InvalidFilterModeWithAnisotropy,
+        TooManyObjects,
+        MissingFeatures,
+    },
+    CreateShaderModuleError {
+        #[cfg(feature = "wgsl")]
+        Parsing,
+        Generation,
+        Device,
+        Validation,
+        MissingFeatures,
+        InvalidGroupIndex,
+        Binding,
+    },
+    CreateTextureError {
+        Device,
+        CreateTextureView,
+        InvalidUsage,
+        InvalidDimension,
+        InvalidDepthDimension,
+        InvalidCompressedDimension,
+        InvalidMipLevelCount,
+        InvalidFormatUsages,
+        InvalidViewFormat,
+        InvalidDimensionUsages,
+        InvalidMultisampledStorageBinding,
+        InvalidMultisampledFormat,
+        InvalidSampleCount,
+        MultisampledNotRenderAttachment,
+        MissingFeatures,
+        MissingDownlevelFlags,
+    },
+    CreateTextureViewError {
+        InvalidTexture,
+        OutOfMemory,
+        InvalidTextureViewDimension,
+        InvalidMultisampledTextureViewDimension,
+        InvalidCubemapTextureDepth,
+        InvalidCubemapArrayTextureDepth,
+        InvalidCubeTextureViewSize,
+        ZeroMipLevelCount,
+        ZeroArrayLayerCount,
+        TooManyMipLevels,
+        TooManyArrayLayers,
+        InvalidArrayLayerCount,
+        InvalidAspect,
+        FormatReinterpretation,
+    },
+    DepthStencilStateError {
+        FormatNotRenderable,
+        FormatNotDepth,
+        FormatNotStencil,
+        InvalidSampleCount,
+    },
+    DestroyError {
+        Invalid,
+        AlreadyDestroyed,
+    },
+    DeviceError {
+        Invalid,
+        Lost,
+        OutOfMemory,
+        ResourceCreationFailed,
+        InvalidQueueId,
+        WrongDevice,
+    },
+    DispatchError {
+        MissingPipeline,
+        IncompatibleBindGroup,
+        InvalidGroupSize,
+        BindingSizeTooSmall,
+    },
+    DrawError {
+        MissingBlendConstant,
+        MissingPipeline,
+        MissingVertexBuffer,
+        MissingIndexBuffer,
+        IncompatibleBindGroup,
+        VertexBeyondLimit,
+        InstanceBeyondLimit,
+        IndexBeyondLimit,
+        UnmatchedIndexFormats,
+        BindingSizeTooSmall,
+        UnexpectedMeshPipeline,
+        MeshPipelineRequired,
+        InvalidMeshTaskGroupCount,
+        UnvalidatedIndirectDraw,
+    },
+    ExecutionError {
+        DestroyedBuffer,
+        InvalidBindGroup,
+        Unimplemented,
+    },
+    FailedLimit,
+    FilteringError {
+        Integer,
+        Float,
+    },
+    GetBindGroupLayoutError {
+        InvalidPipeline,
+        InvalidGroupIndex,
+    },
+    GetSurfaceSupportError {
+        InvalidAdapter,
+        InvalidSurface,
+        Unsupported,
+    },
+    ImplicitLayoutError {
+        MissingIds,
+        ReflectionError,
+        BindGroup,
+        Pipeline,
+    },
+    IndirectValidationError,
+    InputError {
+        Missing,
+        WrongType,
+        InterpolationMismatch,
+        SamplingMismatch,
+    },
+    InvalidAdapter,
+    InvalidDevice,
+    InvalidQueue,
+    IsSurfaceSupportedError {
+        InvalidAdapter,
+        InvalidSurface,
+    },
+    MissingBufferUsageError,
+    MissingDownlevelFlags,
+    MissingFeatures,
+    MissingTextureUsageError,
+    PassErrorScope {
+        Bundle,
+        Pass,
+        SetBindGroup,
+        SetPipelineRender,
+        SetPipelineCompute,
+        SetPushConstant,
+        SetVertexBuffer,
+        SetIndexBuffer,
+        SetViewport,
+        SetScissorRect,
+        Draw,
+        DrawMeshTasks,
+        QueryReset,
+        WriteTimestamp,
+        BeginOcclusionQuery,
+        EndOcclusionQuery,
+        BeginPipelineStatisticsQuery,
+        EndPipelineStatisticsQuery,
+        ExecuteBundle,
+        Dispatch,
+        PopDebugGroup,
+    },
+    PipelineCacheValidationError {
+        Truncated,
+        Extended,
+        Corrupted,
+        Outdated,
+        WrongDevice,
+        Unsupported,
+    },
+    PushConstantUploadError {
+        TooLarge,
+        PartialRangeMatch,
+        MissingStages,
+        UnmatchedStages,
+        Unaligned,
+    },
+    QueryError {
+        Device,
+        Encoder,
+        Use,
+        Resolve,
+        InvalidBuffer,
+        InvalidQuerySet,
+    },
+    QueryUseError {
+        OutOfBounds,
+        UsedTwiceInsideRenderpass,
+        AlreadyStarted,
+        AlreadyStopped,
+        IncompatibleType,
+    },
+    QueueSubmitError {
+        Queue,
+        DestroyedBuffer,
+        DestroyedTexture,
+        Unmap,
+        BufferStillMapped,
+        SurfaceOutputDropped,
+        SurfaceUnconfigured,
+        StuckGpu,
+    },
+    QueueWriteError {
+        Queue,
+        Transfer,
+        MemoryInitFailure,
+    },
+    RenderBundleError,
+    RenderBundleErrorInner {
+        NotValidToUse,
+        Device,
+        RenderCommand,
+        Draw,
+        MissingDownlevelFlags,
+    },
+    RenderCommandError {
+        InvalidBindGroup,
+        InvalidRenderBundle,
+        BindGroupIndexOutOfRange,
+        VertexBufferIndexOutOfRange,
+        UnalignedBufferOffset,
+        InvalidDynamicOffsetCount,
+        InvalidPipeline,
+        InvalidQuerySet,
+        IncompatiblePipelineTargets,
+        IncompatiblePipelineRods,
+        UsageConflict,
+        DestroyedBuffer,
+        MissingBufferUsage,
+        MissingTextureUsage,
+        PushConstants,
+        InvalidViewportRect,
+        InvalidViewportDepth,
+        InvalidScissorRect,
+        Unimplemented,
+    },
+    RenderPassCompatibilityError {
+        IncompatibleColorAttachment,
+        IncompatibleDepthStencilAttachment,
+        IncompatibleSampleCount,
+        IncompatibleMultiview,
+    },
+    RenderPassError,
+    RenderPassErrorInner {
+        Device,
+        ColorAttachment,
+        Encoder,
+        InvalidAttachment,
+        InvalidDepthStencilAttachmentFormat,
+        UnsupportedResolveTargetFormat,
+        MissingAttachments,
+        TextureViewIsNotRenderable,
+        AttachmentsDimensionMismatch,
+        AttachmentSampleCountMismatch,
+        InvalidResolveSampleCounts,
+        MismatchedResolveTextureFormat,
+        SurfaceTextureDropped,
+        OutOfMemory,
+        InvalidBindGroup,
+        InvalidDepthOps,
+        InvalidStencilOps,
+        InvalidValuesOffset,
+        MissingFeatures,
+        MissingDownlevelFlags,
+        IndirectBufferOverrun,
+        UnalignedIndirectBufferOffset,
+        IndirectCountBufferOverrun,
+        InvalidPopDebugGroup,
+        ResourceUsageConflict,
+        IncompatibleBundleTargets,
+        IncompatibleBundleReadOnlyDepthStencil,
+        RenderCommand,
+        Draw,
+        Bind,
+        QueryUse,
+        MultiViewMismatch,
+        MultiViewDimensionMismatch,
+        InvalidQuerySet,
+        MissingOcclusionQuerySet,
+    },
+    RequestAdapterError {
+        NotFound,
+        InvalidSurface,
+    },
+    RequestDeviceError {
+        InvalidAdapter,
+        DeviceLost,
+        Internal,
+        LimitsExceeded,
+        NoGraphicsQueue,
+        OutOfMemory,
+        UnsupportedFeature,
+    },
+    ResolveError {
+        MissingBufferUsage,
+        BufferOffsetAlignment,
+        QueryOverrun,
+        BufferOverrun,
+    },
+    StageError {
+        InvalidModule,
+        InvalidWorkgroupSize,
+        TooManyVaryings,
+        TooMuchWorkgroupStorage,
+        TooManyMeshOutputs,
+        MissingEntryPoint,
+        Binding,
+        Filtering,
+        Input,
+        InputNotConsumed,
+    },
+    SurfaceError {
+        Invalid,
+        NotConfigured,
+        Device,
+        AlreadyAcquired,
+        StillReferenced,
+    },
+    TextureDimensionError {
+        Zero,
+        LimitExceeded,
+        InvalidSampleCount,
+        NotMultipleOfBlockWidth,
+        NotMultipleOfBlockHeight,
+        WidthNotMultipleOf,
+        HeightNotMultipleOf,
+        MultisampledDepthOrArrayLayer,
+    },
+    TextureViewDestroyError {
+    },
+    TextureViewNotRenderableReason {
+        Usage,
+        Dimension,
+        MipLevelCount,
+        ArrayLayerCount,
+        Aspects,
+    },
+    TransferError {
+        InvalidDevice,
+        InvalidBuffer,
+        InvalidTexture,
+        SameSourceDestinationBuffer,
+        MissingCopySrcUsageFlag,
+        MissingCopyDstUsageFlag,
+        MissingRenderAttachmentUsageFlag,
+        BufferOverrun,
+        TextureOverrun,
+        InvalidTextureAspect,
+        InvalidTextureMipLevel,
+        InvalidDimensionExternal,
+        UnalignedBufferOffset,
+        UnalignedCopySize,
+        UnalignedCopyWidth,
+        UnalignedCopyHeight,
+        UnalignedCopyOriginX,
+        UnalignedCopyOriginY,
+        UnalignedBytesPerRow,
+        UnspecifiedBytesPerRow,
+        UnspecifiedRowsPerImage,
+        InvalidBytesPerRow,
+        InvalidCopySize,
+        InvalidRowsPerImage,
+        CopySrcMissingAspects,
+        CopyDstMissingAspects,
+        CopyAspectNotOne,
+        CopyFromForbiddenTextureFormat,
+        CopyToForbiddenTextureFormat,
+        ExternalCopyToForbiddenTextureFormat,
+        InvalidDepthTextureExtent,
+        TextureFormatsNotCopyCompatible,
+        MemoryInitFailure,
+        MissingDownlevelFlags,
+        InvalidSampleCount,
+        InvalidMipLevel,
+    },
+    UsageConflict {
+        BufferInvalid,
+        TextureInvalid,
+        Buffer,
+        Texture,
+    },
+    WaitIdleError {
+        Device,
+        WrongSubmissionIndex,
+        StuckGpu,
+    },
+}
+
 #[derive(Debug)]
 pub struct ContextError {
     pub string: &'static str,
@@ -178,3 +981,48 @@ impl Error for ContextError {
         Some(self.cause.as_ref())
     }
 }
+
+#[test]
+fn error_codes() {
+    use crate::{device::DeviceError, resource::CreateBufferError};
+
+    assert_eq!(
+        error_code(&CreateBufferError::UnalignedSize),
+        "CreateBufferError::UnalignedSize"
+    );
+    assert_eq!(
+        error_code(&CreateBufferError::InvalidUsage(wgt::BufferUsages::empty())),
+        "CreateBufferError::InvalidUsage"
+    );
+    assert_eq!(
+        error_code(&crate::device::MissingFeatures(wgt::Features::empty())),
+        "MissingFeatures"
+    );
+
+    let error = ContextError {
+        string: "Device::create_buffer",
+        cause: Box::new(CreateBufferError::Device(DeviceError::Lost)),
+        label_key: "buffer",
+        label: "vertices".to_string(),
+    };
+    assert_eq!(error_code(&error), "ContextError");
+    assert_eq!(
+        error_code(error.source().unwrap()),
+        "CreateBufferError::Device"
+    );
+
+    assert_eq!(
+        error_code(&crate::command::RenderBundleError::INVALID_DEVICE),
+        "RenderBundleError"
+    );
+    assert_eq!(
+        error_code(
+            crate::command::RenderBundleError::INVALID_DEVICE
+                .source()
+                .unwrap()
+        ),
+        "RenderBundleErrorInner::Device"
+    );
+
+    assert_eq!(error_code(&fmt::Error), "External");
+}
@@ -118,7 +118,7 @@ use crate::{
     id,
     identity::GlobalIdentityHandlerFactory,
     instance::{Adapter, HalSurface, Surface},
-    pipeline::{ComputePipeline, RenderPipeline, ShaderModule},
+    pipeline::{ComputePipeline, PipelineCache, RenderPipeline, ShaderModule},
     registry::{Registry, RegistryReport},
     resource::{Buffer, QuerySet, Sampler, StagingBuffer, Texture, TextureView},
     storage::{Element, Storage},
@@ -138,6 +138,7 @@ pub struct HubReport {
     pub render_bundles: RegistryReport,
     pub render_pipelines: RegistryReport,
     pub compute_pipelines: RegistryReport,
+    pub pipeline_caches: RegistryReport,
     pub query_sets: RegistryReport,
     pub buffers: RegistryReport,
     pub textures: RegistryReport,
@@ -188,6 +189,7 @@ pub struct Hub<A: HalApi> {
     pub render_bundles: Registry<id::RenderBundleId, RenderBundle<A>>,
     pub render_pipelines: Registry<id::RenderPipelineId, RenderPipeline<A>>,
     pub compute_pipelines: Registry<id::ComputePipelineId, ComputePipeline<A>>,
+    pub pipeline_caches: Registry<id::PipelineCacheId, PipelineCache<A>>,
     pub query_sets: Registry<id::QuerySetId, QuerySet<A>>,
     pub buffers: Registry<id::BufferId, Buffer<A>>,
     pub staging_buffers: Registry<id::StagingBufferId, StagingBuffer<A>>,
@@ -210,6 +212,7 @@ impl<A: HalApi> Hub<A> {
             render_bundles: Registry::new(A::VARIANT, factory),
             render_pipelines: Registry::new(A::VARIANT, factory),
             compute_pipelines: Registry::new(A::VARIANT, factory),
+            pipeline_caches: Registry::new(A::VARIANT, factory),
             query_sets: Registry::new(A::VARIANT, factory),
             buffers: Registry::new(A::VARIANT, factory),
             staging_buffers: Registry::new(A::VARIANT, factory),
@@ -247,6 +250,7 @@ impl<A: HalApi> Hub<A> {
         self.pipeline_layouts.write().map.clear();
         self.compute_pipelines.write().map.clear();
         self.render_pipelines.write().map.clear();
+        self.pipeline_caches.write().map.clear();
         self.query_sets.write().map.clear();
 
         for element in surface_guard.map.iter() {
@@ -292,6 +296,7 @@ impl<A: HalApi> Hub<A> {
             render_bundles: self.render_bundles.generate_report(),
             render_pipelines: self.render_pipelines.generate_report(),
             compute_pipelines: self.compute_pipelines.generate_report(),
+            pipeline_caches: self.pipeline_caches.generate_report(),
             query_sets: self.query_sets.generate_report(),
             buffers: self.buffers.generate_report(),
             textures: self.textures.generate_report(),
@@ -162,6 +162,7 @@ pub trait GlobalIdentityHandlerFactory:
     + IdentityHandlerFactory<id::RenderBundleId>
     + IdentityHandlerFactory<id::RenderPipelineId>
     + IdentityHandlerFactory<id::ComputePipelineId>
+    + IdentityHandlerFactory<id::PipelineCacheId>
     + IdentityHandlerFactory<id::QuerySetId>
     + IdentityHandlerFactory<id::BufferId>
     + IdentityHandlerFactory<id::StagingBufferId>
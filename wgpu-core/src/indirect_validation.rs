@@ -0,0 +1,787 @@
+/*! GPU-side validation of indirect draw and dispatch arguments.
+
+The buffer ranges of indirect calls are validated on the CPU, but the
+arguments stored in them are only known on the GPU. When
+[`wgt::InstanceFlags::VALIDATION_INDIRECT_CALL`] is set, every pass that
+issues indirect calls gets a compute pre-pass which copies the arguments
+of each call into an internal buffer, replacing invalid ones by zeros.
+The pass then reads its arguments from that internal buffer.
+
+Draws are checked against the vertex, index and instance ranges that
+were bound when the draw was recorded, and dispatches against
+`max_compute_workgroups_per_dimension`. The number of rejected calls is
+copied into a readback buffer, which is read when the submission has
+completed and turned into an [`IndirectValidationError`].
+
+The pre-pass runs before the pass, so it sees the indirect buffer as it
+was at the start of the pass. Render passes cannot write their indirect
+buffers, but compute passes can; a compute pass therefore rejects
+indirect dispatches reading a buffer that an earlier dispatch of the same
+pass could write to.
+
+Calls that can't go through the pre-pass are rejected while validation is
+enabled: indirect draws in render bundles, `multi_draw_*indirect_count`,
+indirect mesh task draws, and indirect buffer offsets that aren't a multiple
+of 4.
+ */
+
+use std::{iter, mem, num::NonZeroU64, ptr, sync::Arc};
+
+use hal::{CommandEncoder as _, Device as _};
+use thiserror::Error;
+
+use crate::{
+    device::{Device, DeviceError},
+    hal_api::HalApi,
+    hal_label,
+    resource::{Buffer, Resource},
+    snatch::SnatchGuard,
+    track::BufferTracker,
+};
+
+/// Number of calls validated by one workgroup of the validation shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Size in bytes of one entry of the `calls` array of the validation shader.
+const CALL_SIZE: u64 = 5 * mem::size_of::<u32>() as u64;
+
+#[cfg(feature = "wgsl")]
+const SHADER: &str = "
+struct Call {
+    kind: u32,
+    src: u32,
+    dst: u32,
+    limit_a: u32,
+    limit_b: u32,
+}
+
+@group(0) @binding(0) var<storage, read> calls: array<Call>;
+@group(0) @binding(1) var<storage, read> src: array<u32>;
+@group(0) @binding(2) var<storage, read_write> dst: array<u32>;
+@group(0) @binding(3) var<storage, read_write> violations: atomic<u32>;
+
+const MAX_WORKGROUPS: u32 = ${MAX_WORKGROUPS}u;
+const FIRST_INSTANCE: bool = ${FIRST_INSTANCE};
+
+fn fits(first: u32, count: u32, limit: u32) -> bool {
+    return count <= limit && first <= limit - count;
+}
+
+@compute @workgroup_size(${WORKGROUP_SIZE})
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= arrayLength(&calls) {
+        return;
+    }
+    let call = calls[id.x];
+    let s = call.src;
+
+    var len = 3u;
+    var valid = true;
+    switch call.kind {
+        case 0u: {
+            valid = src[s] <= MAX_WORKGROUPS
+                && src[s + 1u] <= MAX_WORKGROUPS
+                && src[s + 2u] <= MAX_WORKGROUPS;
+        }
+        case 1u: {
+            // vertex_count, instance_count, first_vertex, first_instance
+            len = 4u;
+            valid = fits(src[s + 2u], src[s], call.limit_a)
+                && fits(src[s + 3u], src[s + 1u], call.limit_b)
+                && (FIRST_INSTANCE || src[s + 3u] == 0u);
+        }
+        default: {
+            // index_count, instance_count, first_index, base_vertex, first_instance
+            len = 5u;
+            valid = fits(src[s + 2u], src[s], call.limit_a)
+                && fits(src[s + 4u], src[s + 1u], call.limit_b)
+                && (FIRST_INSTANCE || src[s + 4u] == 0u);
+        }
+    }
+
+    for (var i = 0u; i < len; i++) {
+        dst[call.dst + i] = select(0u, src[s + i], valid);
+    }
+    if !valid {
+        atomicAdd(&violations, 1u);
+    }
+}
+";
+
+/// Returns the source of the validation shader for a device with the given
+/// limits and features.
+#[cfg(feature = "wgsl")]
+fn shader_source(limits: &wgt::Limits, features: wgt::Features) -> String {
+    SHADER
+        .replace(
+            "${MAX_WORKGROUPS}",
+            &limits.max_compute_workgroups_per_dimension.to_string(),
+        )
+        .replace(
+            "${FIRST_INSTANCE}",
+            &features
+                .contains(wgt::Features::INDIRECT_FIRST_INSTANCE)
+                .to_string(),
+        )
+        .replace("${WORKGROUP_SIZE}", &WORKGROUP_SIZE.to_string())
+}
+
+/// Indirect calls of a pass whose arguments failed validation on the GPU.
+///
+/// The calls were replaced by empty draws or dispatches.
+#[derive(Clone, Debug, Error)]
+#[error("{count} indirect call(s) in pass {label:?} had invalid arguments and were skipped")]
+pub struct IndirectValidationError {
+    pub label: String,
+    pub count: u32,
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum CreateIndirectValidationError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Failed to compile the validation shader: {0}")]
+    Shader(String),
+    #[error("Failed to create the validation pipeline: {0}")]
+    Pipeline(#[from] hal::PipelineError),
+    #[error("Indirect call validation needs {0}")]
+    Unsupported(&'static str),
+}
+
+/// The kind of an indirect call, matching the `kind` field of the shader's `Call`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CallKind {
+    Dispatch = 0,
+    Draw = 1,
+    DrawIndexed = 2,
+}
+
+impl CallKind {
+    /// Size in bytes of the arguments of one call.
+    pub(crate) const fn size(self) -> u64 {
+        (match self {
+            Self::Dispatch => mem::size_of::<wgt::DispatchIndirectArgs>(),
+            Self::Draw => mem::size_of::<wgt::DrawIndirectArgs>(),
+            Self::DrawIndexed => mem::size_of::<wgt::DrawIndexedIndirectArgs>(),
+        }) as u64
+    }
+}
+
+/// The device-wide objects used to validate indirect calls.
+#[derive(Debug)]
+pub(crate) struct IndirectValidation<A: HalApi> {
+    module: A::ShaderModule,
+    bind_group_layout: A::BindGroupLayout,
+    pipeline_layout: A::PipelineLayout,
+    pipeline: A::ComputePipeline,
+}
+
+impl<A: HalApi> IndirectValidation<A> {
+    /// Creates the validation pipeline.
+    ///
+    /// Fails if the device can't run it, rather than leaving indirect calls
+    /// unvalidated.
+    pub(crate) fn new(
+        device: &A::Device,
+        limits: &wgt::Limits,
+        features: wgt::Features,
+        downlevel: &wgt::DownlevelCapabilities,
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<Self, CreateIndirectValidationError> {
+        if !downlevel.flags.contains(
+            wgt::DownlevelFlags::COMPUTE_SHADERS | wgt::DownlevelFlags::INDIRECT_EXECUTION,
+        ) || limits.max_storage_buffers_per_shader_stage < 4
+        {
+            return Err(CreateIndirectValidationError::Unsupported(
+                "compute shaders, indirect execution and 4 storage buffers per shader stage",
+            ));
+        }
+
+        let module = Self::create_module(device, limits, features, instance_flags)?;
+
+        let entries = [(0, true), (1, true), (2, false), (3, false)].map(|(binding, read_only)| {
+            wgt::BindGroupLayoutEntry {
+                binding,
+                visibility: wgt::ShaderStages::COMPUTE,
+                ty: wgt::BindingType::Buffer {
+                    ty: wgt::BufferBindingType::Storage { read_only },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }
+        });
+        let bind_group_layout = unsafe {
+            device.create_bind_group_layout(&hal::BindGroupLayoutDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) Indirect validation bind group layout"),
+                    instance_flags,
+                ),
+                flags: hal::BindGroupLayoutFlags::empty(),
+                entries: &entries,
+            })
+        }
+        .map_err(DeviceError::from)?;
+        let pipeline_layout = unsafe {
+            device.create_pipeline_layout(&hal::PipelineLayoutDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) Indirect validation pipeline layout"),
+                    instance_flags,
+                ),
+                flags: hal::PipelineLayoutFlags::empty(),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            })
+        }
+        .map_err(DeviceError::from)?;
+        let pipeline = unsafe {
+            device.create_compute_pipeline(&hal::ComputePipelineDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) Indirect validation pipeline"),
+                    instance_flags,
+                ),
+                layout: &pipeline_layout,
+                stage: hal::ProgrammableStage {
+                    module: &module,
+                    entry_point: "main",
+                },
+                cache: None,
+            })
+        }?;
+
+        Ok(Self {
+            module,
+            bind_group_layout,
+            pipeline_layout,
+            pipeline,
+        })
+    }
+
+    #[cfg(feature = "wgsl")]
+    fn create_module(
+        device: &A::Device,
+        limits: &wgt::Limits,
+        features: wgt::Features,
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<A::ShaderModule, CreateIndirectValidationError> {
+        let source = shader_source(limits, features);
+        let module = naga::front::wgsl::parse_str(&source)
+            .map_err(|err| CreateIndirectValidationError::Shader(err.emit_to_string(&source)))?;
+        let info = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .map_err(|err| CreateIndirectValidationError::Shader(err.emit_to_string(&source)))?;
+
+        let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
+            module: std::borrow::Cow::Owned(module),
+            info,
+            debug_source: None,
+            source: Some(std::borrow::Cow::Owned(source)),
+        });
+        let hal_desc = hal::ShaderModuleDescriptor {
+            label: hal_label(
+                Some("(wgpu internal) Indirect validation shader"),
+                instance_flags,
+            ),
+            runtime_checks: true,
+        };
+        unsafe { device.create_shader_module(&hal_desc, hal_shader) }.map_err(|err| match err {
+            hal::ShaderError::Device(err) => CreateIndirectValidationError::Device(err.into()),
+            hal::ShaderError::Compilation(msg) => CreateIndirectValidationError::Shader(msg),
+        })
+    }
+
+    #[cfg(not(feature = "wgsl"))]
+    fn create_module(
+        _device: &A::Device,
+        _limits: &wgt::Limits,
+        _features: wgt::Features,
+        _instance_flags: wgt::InstanceFlags,
+    ) -> Result<A::ShaderModule, CreateIndirectValidationError> {
+        Err(CreateIndirectValidationError::Unsupported(
+            "the `wgsl` feature of wgpu-core",
+        ))
+    }
+
+    pub(crate) fn dispose(self, device: &A::Device) {
+        unsafe {
+            device.destroy_compute_pipeline(self.pipeline);
+            device.destroy_pipeline_layout(self.pipeline_layout);
+            device.destroy_bind_group_layout(self.bind_group_layout);
+            device.destroy_shader_module(self.module);
+        }
+    }
+}
+
+/// Internal objects used by the validation of one pass.
+///
+/// They are destroyed when this is dropped, which must not happen before
+/// the commands using them have finished executing.
+#[derive(Debug)]
+pub(crate) struct PassResources<A: HalApi> {
+    device: Arc<Device<A>>,
+    label: String,
+    buffers: Vec<A::Buffer>,
+    bind_groups: Vec<A::BindGroup>,
+    readback: Option<A::Buffer>,
+}
+
+impl<A: HalApi> PassResources<A> {
+    /// Reads back the number of rejected calls, once the commands of the
+    /// pass have finished executing.
+    pub(crate) fn error(&self) -> Option<IndirectValidationError> {
+        let readback = self.readback.as_ref()?;
+        let raw = self.device.raw();
+        let size = mem::size_of::<u32>() as u64;
+        let count = unsafe {
+            let mapping = match raw.map_buffer(readback, 0..size) {
+                Ok(mapping) => mapping,
+                Err(err) => {
+                    log::error!("Failed to read back indirect validation results: {err}");
+                    return None;
+                }
+            };
+            if !mapping.is_coherent {
+                raw.invalidate_mapped_ranges(readback, iter::once(0..size));
+            }
+            let count = ptr::read_unaligned(mapping.ptr.as_ptr().cast::<u32>());
+            if let Err(err) = raw.unmap_buffer(readback) {
+                log::error!("Failed to unmap indirect validation results: {err}");
+            }
+            count
+        };
+        (count != 0).then(|| IndirectValidationError {
+            label: self.label.clone(),
+            count,
+        })
+    }
+}
+
+impl<A: HalApi> Drop for PassResources<A> {
+    fn drop(&mut self) {
+        let raw = self.device.raw();
+        unsafe {
+            for bind_group in self.bind_groups.drain(..) {
+                raw.destroy_bind_group(bind_group);
+            }
+            for buffer in self.buffers.drain(..).chain(self.readback.take()) {
+                raw.destroy_buffer(buffer);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Call<A: HalApi> {
+    buffer: Arc<Buffer<A>>,
+    kind: CallKind,
+    src_offset: u64,
+    dst_offset: u64,
+    limits: [u32; 2],
+}
+
+/// A contiguous run of calls reading from the same window of a buffer.
+struct Group {
+    calls: std::ops::Range<usize>,
+    window: std::ops::Range<u64>,
+    params_offset: u64,
+}
+
+/// The indirect calls recorded by a pass.
+///
+/// The validated arguments live in [`Self::dst`]; the pass must read them
+/// from the offsets returned by [`Self::push`].
+#[derive(Debug)]
+pub(crate) struct IndirectCalls<A: HalApi> {
+    resources: PassResources<A>,
+    calls: Vec<Call<A>>,
+    dst_size: u64,
+}
+
+impl<A: HalApi> IndirectCalls<A> {
+    /// Prepares the validation of a pass whose indirect calls need `size`
+    /// bytes of arguments in total.
+    ///
+    /// Returns `None` if validation is disabled or the pass has no
+    /// indirect calls.
+    pub(crate) fn new(
+        device: &Arc<Device<A>>,
+        label: Option<&str>,
+        size: u64,
+    ) -> Result<Option<Self>, DeviceError> {
+        if device.indirect_validation.is_none() || size == 0 {
+            return Ok(None);
+        }
+        let dst = unsafe {
+            device.raw().create_buffer(&hal::BufferDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) Validated indirect arguments"),
+                    device.instance_flags,
+                ),
+                size,
+                usage: hal::BufferUses::STORAGE_READ_WRITE | hal::BufferUses::INDIRECT,
+                memory_flags: hal::MemoryFlags::empty(),
+            })
+        }?;
+        Ok(Some(Self {
+            resources: PassResources {
+                device: device.clone(),
+                label: label.unwrap_or_default().to_string(),
+                buffers: vec![dst],
+                bind_groups: Vec::new(),
+                readback: None,
+            },
+            calls: Vec::new(),
+            dst_size: 0,
+        }))
+    }
+
+    /// The buffer holding the validated arguments.
+    pub(crate) fn dst(&self) -> &A::Buffer {
+        &self.resources.buffers[0]
+    }
+
+    /// Records `count` tightly packed calls reading their arguments from
+    /// `buffer` at `offset`, and returns where their validated arguments
+    /// will be in [`Self::dst`].
+    ///
+    /// `limits` are the vertex (or index) and instance limits of draws.
+    /// `offset` must be a multiple of 4, as the pre-pass reads the arguments
+    /// as `u32`s.
+    pub(crate) fn push(
+        &mut self,
+        buffer: &Arc<Buffer<A>>,
+        kind: CallKind,
+        offset: u64,
+        count: u32,
+        limits: [u64; 2],
+    ) -> u64 {
+        debug_assert_eq!(offset % mem::size_of::<u32>() as u64, 0);
+        let first_dst_offset = self.dst_size;
+        let limits = limits.map(|limit| limit.min(u32::MAX as u64) as u32);
+        for i in 0..count as u64 {
+            self.calls.push(Call {
+                buffer: buffer.clone(),
+                kind,
+                src_offset: offset + i * kind.size(),
+                dst_offset: self.dst_size,
+                limits,
+            });
+            self.dst_size += kind.size();
+        }
+        first_dst_offset
+    }
+
+    /// Records the validation pre-pass into `encoder`, which must execute
+    /// before the pass.
+    ///
+    /// The indirect buffers are transitioned through `tracker`, so any
+    /// barriers for the pass itself have to be recorded afterwards.
+    pub(crate) fn encode(
+        mut self,
+        encoder: &mut A::CommandEncoder,
+        tracker: &mut BufferTracker<A>,
+        snatch_guard: &SnatchGuard,
+    ) -> Result<PassResources<A>, DeviceError> {
+        if self.calls.is_empty() {
+            return Ok(self.resources);
+        }
+        let device = self.resources.device.clone();
+        let raw = device.raw();
+        let validation = device.indirect_validation.as_ref().unwrap();
+        let align = device.limits.min_storage_buffer_offset_alignment as u64;
+        let max_binding_size = device.limits.max_storage_buffer_binding_size as u64;
+
+        // Group the calls by buffer, in windows that fit in one binding.
+        self.calls
+            .sort_by_key(|call| (call.buffer.as_info().id(), call.src_offset));
+        let mut groups: Vec<Group> = Vec::new();
+        for (i, call) in self.calls.iter().enumerate() {
+            let end = call.src_offset + call.kind.size();
+            match groups.last_mut() {
+                Some(group)
+                    if Arc::ptr_eq(&self.calls[group.calls.start].buffer, &call.buffer)
+                        && end - group.window.start <= max_binding_size =>
+                {
+                    group.calls.end = i + 1;
+                    group.window.end = group.window.end.max(end);
+                }
+                _ => groups.push(Group {
+                    calls: i..i + 1,
+                    window: call.src_offset - call.src_offset % align..end,
+                    params_offset: 0,
+                }),
+            }
+        }
+
+        let word = mem::size_of::<u32>() as u64;
+        let mut params = Vec::new();
+        for group in groups.iter_mut() {
+            while (params.len() as u64 * word) % align != 0 {
+                params.push(0);
+            }
+            group.params_offset = params.len() as u64 * word;
+            for call in &self.calls[group.calls.clone()] {
+                params.extend([
+                    call.kind as u32,
+                    ((call.src_offset - group.window.start) / word) as u32,
+                    (call.dst_offset / word) as u32,
+                    call.limits[0],
+                    call.limits[1],
+                ]);
+            }
+        }
+        let params_size = params.len() as u64 * word;
+
+        let create_buffer = |label, size, usage| unsafe {
+            raw.create_buffer(&hal::BufferDescriptor {
+                label: hal_label(Some(label), device.instance_flags),
+                size,
+                usage,
+                memory_flags: hal::MemoryFlags::empty(),
+            })
+        };
+        self.resources.buffers.push(create_buffer(
+            "(wgpu internal) Indirect validation calls",
+            params_size,
+            hal::BufferUses::COPY_DST | hal::BufferUses::STORAGE_READ,
+        )?);
+        self.resources.buffers.push(create_buffer(
+            "(wgpu internal) Indirect validation violations",
+            word,
+            hal::BufferUses::COPY_SRC
+                | hal::BufferUses::COPY_DST
+                | hal::BufferUses::STORAGE_READ_WRITE,
+        )?);
+        self.resources.readback = Some(create_buffer(
+            "(wgpu internal) Indirect validation readback",
+            word,
+            hal::BufferUses::MAP_READ | hal::BufferUses::COPY_DST,
+        )?);
+        let staging = unsafe {
+            raw.create_buffer(&hal::BufferDescriptor {
+                label: hal_label(Some("(wgpu internal) Staging"), device.instance_flags),
+                size: params_size,
+                usage: hal::BufferUses::MAP_WRITE | hal::BufferUses::COPY_SRC,
+                memory_flags: hal::MemoryFlags::TRANSIENT,
+            })
+        }?;
+        self.resources.buffers.push(staging);
+
+        let [dst, calls, violations, staging] = &self.resources.buffers[..] else {
+            unreachable!()
+        };
+        let readback = self.resources.readback.as_ref().unwrap();
+
+        unsafe {
+            let mapping = raw.map_buffer(staging, 0..params_size)?;
+            ptr::copy_nonoverlapping(
+                params.as_ptr().cast::<u8>(),
+                mapping.ptr.as_ptr(),
+                params_size as usize,
+            );
+            if !mapping.is_coherent {
+                raw.flush_mapped_ranges(staging, iter::once(0..params_size));
+            }
+            raw.unmap_buffer(staging)?;
+        }
+
+        for group in groups.iter() {
+            let src = self.calls[group.calls.start]
+                .buffer
+                .raw
+                .get(snatch_guard)
+                .expect("Buffer is destroyed");
+            let buffers = [
+                hal::BufferBinding {
+                    buffer: calls,
+                    offset: group.params_offset,
+                    size: NonZeroU64::new(group.calls.len() as u64 * CALL_SIZE),
+                },
+                hal::BufferBinding {
+                    buffer: src,
+                    offset: group.window.start,
+                    size: NonZeroU64::new(group.window.end - group.window.start),
+                },
+                hal::BufferBinding {
+                    buffer: dst,
+                    offset: 0,
+                    size: None,
+                },
+                hal::BufferBinding {
+                    buffer: violations,
+                    offset: 0,
+                    size: None,
+                },
+            ];
+            let entries = [0, 1, 2, 3].map(|binding| hal::BindGroupEntry {
+                binding,
+                resource_index: binding,
+                count: 1,
+            });
+            let bind_group = unsafe {
+                raw.create_bind_group(&hal::BindGroupDescriptor {
+                    label: hal_label(
+                        Some("(wgpu internal) Indirect validation bind group"),
+                        device.instance_flags,
+                    ),
+                    layout: &validation.bind_group_layout,
+                    buffers: &buffers,
+                    samplers: &[],
+                    textures: &[],
+                    entries: &entries,
+                    acceleration_structures: &[],
+                })
+            }?;
+            self.resources.bind_groups.push(bind_group);
+        }
+
+        let src_transitions = groups
+            .iter()
+            .filter_map(|group| {
+                let buffer = &self.calls[group.calls.start].buffer;
+                let (buffer, pending) =
+                    tracker.set_single(buffer, hal::BufferUses::STORAGE_READ)?;
+                pending.map(|pending| (buffer, pending.usage))
+            })
+            .collect::<Vec<_>>();
+        let src_barriers = src_transitions
+            .iter()
+            .map(|(buffer, usage)| hal::BufferBarrier {
+                buffer: buffer.raw.get(snatch_guard).expect("Buffer is destroyed"),
+                usage: usage.clone(),
+            });
+
+        unsafe {
+            encoder.transition_buffers(src_barriers.chain([
+                hal::BufferBarrier {
+                    buffer: staging,
+                    usage: hal::BufferUses::MAP_WRITE..hal::BufferUses::COPY_SRC,
+                },
+                hal::BufferBarrier {
+                    buffer: calls,
+                    usage: hal::BufferUses::empty()..hal::BufferUses::COPY_DST,
+                },
+                hal::BufferBarrier {
+                    buffer: violations,
+                    usage: hal::BufferUses::empty()..hal::BufferUses::COPY_DST,
+                },
+                hal::BufferBarrier {
+                    buffer: dst,
+                    usage: hal::BufferUses::empty()..hal::BufferUses::STORAGE_READ_WRITE,
+                },
+            ]));
+            encoder.copy_buffer_to_buffer(
+                staging,
+                calls,
+                iter::once(hal::BufferCopy {
+                    src_offset: 0,
+                    dst_offset: 0,
+                    size: NonZeroU64::new(params_size).unwrap(),
+                }),
+            );
+            encoder.clear_buffer(violations, 0..word);
+            encoder.transition_buffers(
+                [
+                    hal::BufferBarrier {
+                        buffer: calls,
+                        usage: hal::BufferUses::COPY_DST..hal::BufferUses::STORAGE_READ,
+                    },
+                    hal::BufferBarrier {
+                        buffer: violations,
+                        usage: hal::BufferUses::COPY_DST..hal::BufferUses::STORAGE_READ_WRITE,
+                    },
+                ]
+                .into_iter(),
+            );
+
+            encoder.begin_compute_pass(&hal::ComputePassDescriptor {
+                label: hal_label(
+                    Some("(wgpu internal) Indirect validation"),
+                    device.instance_flags,
+                ),
+                timestamp_writes: None,
+            });
+            encoder.set_compute_pipeline(&validation.pipeline);
+            for (group, bind_group) in groups.iter().zip(self.resources.bind_groups.iter()) {
+                encoder.set_bind_group(&validation.pipeline_layout, 0, bind_group, &[]);
+                let count = group.calls.len() as u32;
+                encoder.dispatch([(count + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE, 1, 1]);
+            }
+            encoder.end_compute_pass();
+
+            encoder.transition_buffers(
+                [
+                    hal::BufferBarrier {
+                        buffer: dst,
+                        usage: hal::BufferUses::STORAGE_READ_WRITE..hal::BufferUses::INDIRECT,
+                    },
+                    hal::BufferBarrier {
+                        buffer: violations,
+                        usage: hal::BufferUses::STORAGE_READ_WRITE..hal::BufferUses::COPY_SRC,
+                    },
+                    hal::BufferBarrier {
+                        buffer: readback,
+                        usage: hal::BufferUses::empty()..hal::BufferUses::COPY_DST,
+                    },
+                ]
+                .into_iter(),
+            );
+            encoder.copy_buffer_to_buffer(
+                violations,
+                readback,
+                iter::once(hal::BufferCopy {
+                    src_offset: 0,
+                    dst_offset: 0,
+                    size: NonZeroU64::new(word).unwrap(),
+                }),
+            );
+            encoder.transition_buffers(iter::once(hal::BufferBarrier {
+                buffer: readback,
+                usage: hal::BufferUses::COPY_DST..hal::BufferUses::MAP_READ,
+            }));
+        }
+
+        Ok(self.resources)
+    }
+}
+
+#[cfg(all(test, feature = "wgsl"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shader_is_valid() {
+        for features in [
+            wgt::Features::empty(),
+            wgt::Features::INDIRECT_FIRST_INSTANCE,
+        ] {
+            let source = shader_source(&wgt::Limits::downlevel_defaults(), features);
+            let module = naga::front::wgsl::parse_str(&source)
+                .unwrap_or_else(|err| panic!("{}", err.emit_to_string(&source)));
+            naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::empty(),
+            )
+            .validate(&module)
+            .unwrap_or_else(|err| panic!("{}", err.emit_to_string(&source)));
+        }
+    }
+
+    #[test]
+    fn call_size_matches_shader() {
+        let source = shader_source(&wgt::Limits::default(), wgt::Features::empty());
+        let module = naga::front::wgsl::parse_str(&source).unwrap();
+        let (_, call) = module
+            .types
+            .iter()
+            .find(|(_, ty)| ty.name.as_deref() == Some("Call"))
+            .unwrap();
+        let naga::TypeInner::Struct { span, .. } = call.inner else {
+            panic!("`Call` is not a struct");
+        };
+        assert_eq!(span as u64, CALL_SIZE);
+    }
+}
@@ -88,9 +88,11 @@ mod hash_utils;
 pub mod hub;
 pub mod id;
 pub mod identity;
+pub mod indirect_validation;
 mod init_tracker;
 pub mod instance;
 pub mod pipeline;
+mod pipeline_cache;
 mod pool;
 pub mod present;
 pub mod registry;
@@ -5,7 +5,7 @@ use crate::{
     command::ColorAttachmentError,
     device::{Device, DeviceError, MissingDownlevelFlags, MissingFeatures, RenderPassContext},
     hal_api::HalApi,
-    id::{ComputePipelineId, PipelineLayoutId, RenderPipelineId, ShaderModuleId},
+    id::{ComputePipelineId, PipelineCacheId, PipelineLayoutId, RenderPipelineId, ShaderModuleId},
     resource::{Resource, ResourceInfo, ResourceType},
     resource_log, validation, Label,
 };
@@ -33,13 +33,19 @@ pub enum ShaderModuleSource<'a> {
     Dummy(PhantomData<&'a ()>),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
 #[cfg_attr(feature = "replay", derive(serde::Deserialize))]
 pub struct ShaderModuleDescriptor<'a> {
     pub label: Label<'a>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub shader_bound_checks: wgt::ShaderBoundChecks,
+    /// If not `None`, run Naga's optimization passes over the module before
+    /// passing it to the backend, which may help drivers that do little
+    /// optimization of their own.
+    #[cfg(feature = "opt")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub optimization: Option<naga::opt::Options>,
 }
 
 #[derive(Debug)]
@@ -122,6 +128,9 @@ impl fmt::Display for ShaderError<naga::WithSpan<naga::valid::ValidationError>>
                 .map(|&(span, ref desc)| {
                     Label::primary((), span.to_range().unwrap()).with_message(desc.to_owned())
                 })
+                .chain(self.inner.notes().map(|&(span, ref desc)| {
+                    Label::secondary((), span.to_range().unwrap()).with_message(desc.to_owned())
+                }))
                 .collect(),
         );
 
@@ -167,6 +176,8 @@ pub enum CreateShaderModuleError {
         group: u32,
         limit: u32,
     },
+    #[error("Shader global {0:?} can't be bound")]
+    Binding(naga::ResourceBinding, #[source] validation::BindingError),
 }
 
 impl CreateShaderModuleError {
@@ -218,6 +229,9 @@ pub struct ComputePipelineDescriptor<'a> {
     pub layout: Option<PipelineLayoutId>,
     /// The compiled compute stage and its entry point.
     pub stage: ProgrammableStageDescriptor<'a>,
+    /// The pipeline cache to use when creating this pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub cache: Option<PipelineCacheId>,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -227,6 +241,8 @@ pub enum CreateComputePipelineError {
     Device(#[from] DeviceError),
     #[error("Pipeline layout is invalid")]
     InvalidLayout,
+    #[error("Pipeline cache is invalid")]
+    InvalidCache,
     #[error("Unable to derive an implicit layout")]
     Implicit(#[from] ImplicitLayoutError),
     #[error("Error matching shader requirements against the pipeline")]
@@ -343,6 +359,42 @@ pub struct RenderPipelineDescriptor<'a> {
     /// If the pipeline will be used with a multiview render pass, this indicates how many array
     /// layers the attachments will have.
     pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub cache: Option<PipelineCacheId>,
+}
+
+/// Describes a mesh pipeline: a render pipeline whose geometry is produced by an optional task
+/// shader and a mesh shader, rather than by vertex buffers and a vertex shader.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct MeshPipelineDescriptor<'a> {
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    pub layout: Option<PipelineLayoutId>,
+    /// The compiled task stage and its entry point, if any.
+    pub task: Option<ProgrammableStageDescriptor<'a>>,
+    /// The compiled mesh stage and its entry point.
+    pub mesh: ProgrammableStageDescriptor<'a>,
+    /// The properties of the pipeline at the rasterization level. The topology is declared by
+    /// the mesh shader, so `topology` and `strip_index_format` are ignored.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub primitive: wgt::PrimitiveState,
+    /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub depth_stencil: Option<wgt::DepthStencilState>,
+    /// The multi-sampling properties of the pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub multisample: wgt::MultisampleState,
+    /// The fragment processing state for this pipeline.
+    pub fragment: Option<FragmentState<'a>>,
+    /// If the pipeline will be used with a multiview render pass, this indicates how many array
+    /// layers the attachments will have.
+    pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub cache: Option<PipelineCacheId>,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -365,6 +417,8 @@ pub enum ColorStateError {
     InvalidMinMaxBlendFactors(wgt::BlendComponent),
     #[error("Invalid write mask {0:?}")]
     InvalidWriteMask(wgt::ColorWrites),
+    #[error("The color targets up to and including this one take up {total} bytes per sample, above the limit of {limit}")]
+    TooManyBytesPerSample { total: u32, limit: u32 },
 }
 
 #[derive(Clone, Debug, Error)]
@@ -389,6 +443,8 @@ pub enum CreateRenderPipelineError {
     Device(#[from] DeviceError),
     #[error("Pipeline layout is invalid")]
     InvalidLayout,
+    #[error("Pipeline cache is invalid")]
+    InvalidCache,
     #[error("Unable to derive an implicit layout")]
     Implicit(#[from] ImplicitLayoutError),
     #[error("Color state [{0}] is invalid")]
@@ -450,6 +506,93 @@ pub enum CreateRenderPipelineError {
     ShaderExpectsPipelineToUseDualSourceBlending,
 }
 
+/// Describes a pipeline cache.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct PipelineCacheDescriptor<'a> {
+    pub label: Label<'a>,
+    /// Data previously returned by [`Global::pipeline_cache_get_data`] for a
+    /// cache on the same adapter, used to populate the new cache.
+    ///
+    /// [`Global::pipeline_cache_get_data`]: crate::global::Global::pipeline_cache_get_data
+    pub data: Option<Cow<'a, [u8]>>,
+    /// If `data` can't be used, for example because it was produced by a
+    /// different driver version, create an empty cache instead of failing.
+    pub fallback: bool,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PipelineCacheValidationError {
+    #[error("The pipeline cache data was truncated")]
+    Truncated,
+    #[error("The pipeline cache data was longer than recorded")]
+    Extended,
+    #[error("The pipeline cache data was corrupted")]
+    Corrupted,
+    #[error(
+        "The pipeline cache data was produced by a different version of wgpu, naga or the driver"
+    )]
+    Outdated,
+    #[error("The pipeline cache data was produced by a different adapter")]
+    WrongDevice,
+    #[error("Pipeline caches are not supported on this device")]
+    Unsupported,
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreatePipelineCacheError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Pipeline cache data can't be used")]
+    Validation(#[from] PipelineCacheValidationError),
+}
+
+#[derive(Debug)]
+pub struct PipelineCache<A: HalApi> {
+    pub(crate) raw: Option<A::PipelineCache>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<PipelineCacheId>,
+}
+
+impl<A: HalApi> Drop for PipelineCache<A> {
+    fn drop(&mut self) {
+        if let Some(raw) = self.raw.take() {
+            resource_log!("Destroy raw PipelineCache {:?}", self.info.label());
+
+            #[cfg(feature = "trace")]
+            if let Some(t) = self.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyPipelineCache(self.info.id()));
+            }
+
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_pipeline_cache(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource<PipelineCacheId> for PipelineCache<A> {
+    const TYPE: ResourceType = "PipelineCache";
+
+    fn as_info(&self) -> &ResourceInfo<PipelineCacheId> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<PipelineCacheId> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> PipelineCache<A> {
+    pub(crate) fn raw(&self) -> &A::PipelineCache {
+        self.raw.as_ref().unwrap()
+    }
+}
+
 bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -458,6 +601,12 @@ bitflags::bitflags! {
         const STENCIL_REFERENCE = 1 << 1;
         const WRITES_DEPTH = 1 << 2;
         const WRITES_STENCIL = 1 << 3;
+        /// The pipeline was created with `create_mesh_pipeline`, and can only be used with
+        /// `draw_mesh_tasks` and its indirect variants.
+        const MESH = 1 << 4;
+        /// The mesh pipeline has a task shader, so the group counts of its draws are bounded
+        /// by the task shader limits instead of the mesh shader ones.
+        const TASK = 1 << 5;
     }
 }
 
@@ -0,0 +1,260 @@
+//! The header wgpu prepends to pipeline cache data.
+//!
+//! Backends only know how to interpret data produced by the same driver, and
+//! some drivers don't defend against being handed anything else. So the data
+//! returned by [`Global::pipeline_cache_get_data`] starts with a header
+//! identifying the wgpu and naga versions, adapter and driver that produced
+//! it, along
+//! with the size and hash of the payload, and we check all of these before
+//! giving the payload to the backend.
+//!
+//! [`Global::pipeline_cache_get_data`]: crate::global::Global::pipeline_cache_get_data
+
+use crate::pipeline::PipelineCacheValidationError;
+
+const MAGIC: [u8; 8] = *b"WGPUPLCH";
+/// Bump this whenever the layout of the header changes.
+const HEADER_VERSION: u32 = 2;
+const WGPU_VERSION: &str = env!("CARGO_PKG_VERSION");
+const VERSION_LEN: usize = 16;
+
+/// The identity of the device that produced some pipeline cache data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct CacheOrigin {
+    pub backend: wgt::Backend,
+    pub vendor: u32,
+    pub device: u32,
+    /// The value of [`hal::Device::pipeline_cache_validation_key`].
+    pub driver_key: [u8; 16],
+}
+
+impl CacheOrigin {
+    pub(crate) fn new(info: &wgt::AdapterInfo, driver_key: [u8; 16]) -> Self {
+        Self {
+            backend: info.backend,
+            vendor: info.vendor,
+            device: info.device,
+            driver_key,
+        }
+    }
+}
+
+struct Header {
+    header_version: u32,
+    wgpu_version: [u8; VERSION_LEN],
+    naga_version: [u8; VERSION_LEN],
+    backend: u32,
+    vendor: u32,
+    device: u32,
+    driver_key: [u8; 16],
+    data_size: u64,
+    data_hash: u64,
+}
+
+const HEADER_SIZE: usize = 8 + 4 + VERSION_LEN + VERSION_LEN + 4 + 4 + 4 + 16 + 8 + 8;
+
+fn encode_version(version: &str) -> [u8; VERSION_LEN] {
+    let mut encoded = [0; VERSION_LEN];
+    let len = version.len().min(VERSION_LEN);
+    encoded[..len].copy_from_slice(&version.as_bytes()[..len]);
+    encoded
+}
+
+fn wgpu_version() -> [u8; VERSION_LEN] {
+    encode_version(WGPU_VERSION)
+}
+
+/// Backends may cache shaders translated by naga, so data from a different
+/// naga is stale even if wgpu itself is unchanged.
+fn naga_version() -> [u8; VERSION_LEN] {
+    encode_version(naga::VERSION)
+}
+
+/// 64-bit FNV-1a, which unlike the hashers in `std` is guaranteed to give the
+/// same result across runs and platforms.
+fn hash_data(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn backend_to_u32(backend: wgt::Backend) -> u32 {
+    backend as u32
+}
+
+/// Prepend the header for `origin` to the backend's cache `data`.
+pub(crate) fn add_cache_header(data: &[u8], origin: CacheOrigin) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_SIZE + data.len());
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&HEADER_VERSION.to_le_bytes());
+    out.extend_from_slice(&wgpu_version());
+    out.extend_from_slice(&naga_version());
+    out.extend_from_slice(&backend_to_u32(origin.backend).to_le_bytes());
+    out.extend_from_slice(&origin.vendor.to_le_bytes());
+    out.extend_from_slice(&origin.device.to_le_bytes());
+    out.extend_from_slice(&origin.driver_key);
+    out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    out.extend_from_slice(&hash_data(data).to_le_bytes());
+    debug_assert_eq!(out.len(), HEADER_SIZE);
+    out.extend_from_slice(data);
+    out
+}
+
+/// Check the header of `data` against `origin`, returning the backend's part
+/// of the data if it can safely be used.
+pub(crate) fn validate_cache_data(
+    data: &[u8],
+    origin: CacheOrigin,
+) -> Result<&[u8], PipelineCacheValidationError> {
+    if data.len() < HEADER_SIZE {
+        return Err(PipelineCacheValidationError::Truncated);
+    }
+    let (header, payload) = data.split_at(HEADER_SIZE);
+    let header = read_header(header).ok_or(PipelineCacheValidationError::Corrupted)?;
+
+    if header.header_version != HEADER_VERSION
+        || header.wgpu_version != wgpu_version()
+        || header.naga_version != naga_version()
+    {
+        return Err(PipelineCacheValidationError::Outdated);
+    }
+    if header.backend != backend_to_u32(origin.backend)
+        || header.vendor != origin.vendor
+        || header.device != origin.device
+    {
+        return Err(PipelineCacheValidationError::WrongDevice);
+    }
+    if header.driver_key != origin.driver_key {
+        return Err(PipelineCacheValidationError::Outdated);
+    }
+    match (payload.len() as u64).cmp(&header.data_size) {
+        std::cmp::Ordering::Less => return Err(PipelineCacheValidationError::Truncated),
+        std::cmp::Ordering::Greater => return Err(PipelineCacheValidationError::Extended),
+        std::cmp::Ordering::Equal => {}
+    }
+    if hash_data(payload) != header.data_hash {
+        return Err(PipelineCacheValidationError::Corrupted);
+    }
+    Ok(payload)
+}
+
+fn read_header(mut data: &[u8]) -> Option<Header> {
+    fn take<const N: usize>(data: &mut &[u8]) -> [u8; N] {
+        let (bytes, rest) = data.split_at(N);
+        *data = rest;
+        bytes.try_into().unwrap()
+    }
+    let u32_le = |data: &mut &[u8]| u32::from_le_bytes(take(data));
+    let u64_le = |data: &mut &[u8]| u64::from_le_bytes(take(data));
+
+    if take::<8>(&mut data) != MAGIC {
+        return None;
+    }
+    let header_version = u32_le(&mut data);
+    let wgpu_version = take(&mut data);
+    let naga_version = take(&mut data);
+    let backend = u32_le(&mut data);
+    let vendor = u32_le(&mut data);
+    let device = u32_le(&mut data);
+    let driver_key = take(&mut data);
+    let data_size = u64_le(&mut data);
+    let data_hash = u64_le(&mut data);
+    Some(Header {
+        header_version,
+        wgpu_version,
+        naga_version,
+        backend,
+        vendor,
+        device,
+        driver_key,
+        data_size,
+        data_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGIN: CacheOrigin = CacheOrigin {
+        backend: wgt::Backend::Vulkan,
+        vendor: 0x10de,
+        device: 0x2204,
+        driver_key: [7; 16],
+    };
+
+    #[test]
+    fn round_trip() {
+        let data = add_cache_header(b"driver data", ORIGIN);
+        assert_eq!(validate_cache_data(&data, ORIGIN), Ok(&b"driver data"[..]));
+        assert_eq!(
+            validate_cache_data(&add_cache_header(&[], ORIGIN), ORIGIN),
+            Ok(&[][..])
+        );
+    }
+
+    #[test]
+    fn rejects_stale_data() {
+        let data = add_cache_header(b"driver data", ORIGIN);
+        let other_driver = CacheOrigin {
+            driver_key: [8; 16],
+            ..ORIGIN
+        };
+        assert_eq!(
+            validate_cache_data(&data, other_driver),
+            Err(PipelineCacheValidationError::Outdated)
+        );
+        let other_device = CacheOrigin {
+            device: 0x2206,
+            ..ORIGIN
+        };
+        assert_eq!(
+            validate_cache_data(&data, other_device),
+            Err(PipelineCacheValidationError::WrongDevice)
+        );
+
+        let mut old_version = data.clone();
+        old_version[8 + 4] ^= 1;
+        assert_eq!(
+            validate_cache_data(&old_version, ORIGIN),
+            Err(PipelineCacheValidationError::Outdated)
+        );
+        let mut old_naga = data.clone();
+        old_naga[8 + 4 + VERSION_LEN] ^= 1;
+        assert_eq!(
+            validate_cache_data(&old_naga, ORIGIN),
+            Err(PipelineCacheValidationError::Outdated)
+        );
+    }
+
+    #[test]
+    fn rejects_damaged_data() {
+        let data = add_cache_header(b"driver data", ORIGIN);
+        assert_eq!(
+            validate_cache_data(&data[..HEADER_SIZE - 1], ORIGIN),
+            Err(PipelineCacheValidationError::Truncated)
+        );
+        assert_eq!(
+            validate_cache_data(&data[..data.len() - 1], ORIGIN),
+            Err(PipelineCacheValidationError::Truncated)
+        );
+        let mut extended = data.clone();
+        extended.push(0);
+        assert_eq!(
+            validate_cache_data(&extended, ORIGIN),
+            Err(PipelineCacheValidationError::Extended)
+        );
+        let mut flipped = data.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            validate_cache_data(&flipped, ORIGIN),
+            Err(PipelineCacheValidationError::Corrupted)
+        );
+        let mut bad_magic = data;
+        bad_magic[0] = b'X';
+        assert_eq!(
+            validate_cache_data(&bad_magic, ORIGIN),
+            Err(PipelineCacheValidationError::Corrupted)
+        );
+    }
+}
@@ -79,6 +79,18 @@ impl<A: HalApi> BufferBindGroupState<A> {
             .into_iter()
     }
 
+    /// Returns a list of all buffers the bind group can write to. May contain duplicates.
+    #[allow(clippy::pattern_type_mismatch)]
+    pub fn written_ids(&self) -> impl Iterator<Item = BufferId> + '_ {
+        let buffers = self.buffers.lock();
+        buffers
+            .iter()
+            .filter(|(_, usage)| usage.intersects(BufferUses::EXCLUSIVE))
+            .map(|(ref b, _)| b.as_info().id())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     /// Returns a list of all buffers tracked. May contain duplicates.
     pub fn drain_resources(&self) -> impl Iterator<Item = Arc<Buffer<A>>> + '_ {
         let mut buffers = self.buffers.lock();
@@ -1,10 +1,10 @@
-use crate::{device::bgl, FastHashMap, FastHashSet};
+use crate::{device::bgl, pipeline::CreateShaderModuleError, FastHashMap, FastHashSet};
 use arrayvec::ArrayVec;
 use std::{collections::hash_map::Entry, fmt};
 use thiserror::Error;
 use wgt::{BindGroupLayoutEntry, BindingType};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum ResourceType {
     Buffer {
         size: wgt::BufferSize,
@@ -122,6 +122,11 @@ struct EntryPoint {
     spec_constants: Vec<SpecializationConstant>,
     sampling_pairs: FastHashSet<(naga::Handle<Resource>, naga::Handle<Resource>)>,
     workgroup_size: [u32; 3],
+    /// Total size in bytes of the `var<workgroup>` globals the entry point
+    /// uses, each rounded up to a multiple of 16.
+    workgroup_storage_size: u64,
+    /// The `max_vertices` and `max_primitives` a mesh entry point declares.
+    mesh_output_counts: Option<(u32, u32)>,
     dual_source_blending: bool,
 }
 
@@ -210,6 +215,8 @@ pub enum BindingError {
         "Storage texture with access {0:?} doesn't have a matching supported `StorageTextureAccess`"
     )]
     UnsupportedTextureStorageAccess(naga::StorageAccess),
+    #[error("External texture is used without the type of its parameters in the module")]
+    MissingExternalTextureParams,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -251,6 +258,15 @@ pub enum StageError {
     },
     #[error("Shader uses {used} inter-stage components above the limit of {limit}")]
     TooManyVaryings { used: u32, limit: u32 },
+    #[error("Shader uses {used} bytes of workgroup storage above the limit of {limit}")]
+    TooMuchWorkgroupStorage { used: u64, limit: u32 },
+    #[error("Mesh shader outputs up to {vertices} vertices and {primitives} primitives, above the limits of {vertex_limit} vertices and {primitive_limit} primitives")]
+    TooManyMeshOutputs {
+        vertices: u32,
+        primitives: u32,
+        vertex_limit: u32,
+        primitive_limit: u32,
+    },
     #[error("Unable to find entry point '{0}'")]
     MissingEntryPoint(String),
     #[error("Shader global {0:?} is not available in the pipeline layout")]
@@ -592,6 +608,8 @@ impl Resource {
                             f
                         },
                     },
+                    // Replaced by its planes in `Interface::new`.
+                    naga::ImageClass::External => unreachable!(),
                 }
             }
         })
@@ -866,9 +884,10 @@ impl Interface {
         info: &naga::valid::ModuleInfo,
         limits: wgt::Limits,
         features: wgt::Features,
-    ) -> Self {
+    ) -> Result<Self, CreateShaderModuleError> {
         let mut resources = naga::Arena::new();
         let mut resource_mapping = FastHashMap::default();
+        let mut external_resources = FastHashMap::default();
         for (var_handle, var) in module.global_variables.iter() {
             let bind = match var.binding {
                 Some(ref br) => br.clone(),
@@ -882,6 +901,58 @@ impl Interface {
             };
 
             let ty = match *inner_ty {
+                // External textures are bound as three planes and a uniform
+                // buffer of conversion parameters, see
+                // `naga::ResourceBinding::external_texture_bindings`.
+                naga::TypeInner::Image {
+                    class: naga::ImageClass::External,
+                    ..
+                } => {
+                    let plane = ResourceType::Texture {
+                        dim: naga::ImageDimension::D2,
+                        arrayed: false,
+                        class: naga::ImageClass::Sampled {
+                            kind: naga::ScalarKind::Float,
+                            multi: false,
+                        },
+                    };
+                    let params_size = module
+                        .special_types
+                        .external_texture_params
+                        .and_then(|ty| {
+                            let size = module.types[ty].inner.size(module.to_ctx());
+                            wgt::BufferSize::new(size as u64)
+                        })
+                        .ok_or_else(|| {
+                            CreateShaderModuleError::Binding(
+                                bind.clone(),
+                                BindingError::MissingExternalTextureParams,
+                            )
+                        })?;
+                    let [plane1, plane2, params] = bind.external_texture_bindings();
+                    let extra = [
+                        (plane1, plane.clone(), naga::AddressSpace::Handle),
+                        (plane2, plane.clone(), naga::AddressSpace::Handle),
+                        (
+                            params,
+                            ResourceType::Buffer { size: params_size },
+                            naga::AddressSpace::Uniform,
+                        ),
+                    ]
+                    .map(|(bind, ty, class)| {
+                        resources.append(
+                            Resource {
+                                name: var.name.clone(),
+                                bind,
+                                ty,
+                                class,
+                            },
+                            Default::default(),
+                        )
+                    });
+                    external_resources.insert(var_handle, extra);
+                    plane
+                }
                 naga::TypeInner::Image {
                     dim,
                     arrayed,
@@ -911,6 +982,10 @@ impl Interface {
             resource_mapping.insert(var_handle, handle);
         }
 
+        // The module has been validated, so laying out its types can't fail.
+        let mut layouter = naga::proc::Layouter::default();
+        layouter.update(module.to_ctx()).unwrap();
+
         let mut entry_points = FastHashMap::default();
         entry_points.reserve(module.entry_points.len());
         for (index, entry_point) in module.entry_points.iter().enumerate() {
@@ -927,11 +1002,28 @@ impl Interface {
                     &module.types,
                 );
             }
+            // Mesh shaders write their vertices instead of returning them.
+            if let Some(ref mesh_info) = entry_point.mesh_info {
+                Self::populate(
+                    &mut ep.outputs,
+                    None,
+                    mesh_info.vertex_output_type,
+                    &module.types,
+                );
+                ep.mesh_output_counts = Some((mesh_info.max_vertices, mesh_info.max_primitives));
+            }
 
             for (var_handle, var) in module.global_variables.iter() {
                 let usage = info[var_handle];
                 if !usage.is_empty() && var.binding.is_some() {
                     ep.resources.push(resource_mapping[&var_handle]);
+                    if let Some(extra) = external_resources.get(&var_handle) {
+                        ep.resources.extend_from_slice(extra);
+                    }
+                }
+                if !usage.is_empty() && var.space == naga::AddressSpace::WorkGroup {
+                    let size = u64::from(layouter[var.ty].size);
+                    ep.workgroup_storage_size += wgt::math::align_to(size, 16);
                 }
             }
 
@@ -945,12 +1037,12 @@ impl Interface {
             entry_points.insert((entry_point.stage, entry_point.name.clone()), ep);
         }
 
-        Self {
+        Ok(Self {
             limits,
             features,
             resources,
             entry_points,
-        }
+        })
     }
 
     pub fn check_stage(
@@ -968,6 +1060,8 @@ impl Interface {
             wgt::ShaderStages::VERTEX => naga::ShaderStage::Vertex,
             wgt::ShaderStages::FRAGMENT => naga::ShaderStage::Fragment,
             wgt::ShaderStages::COMPUTE => naga::ShaderStage::Compute,
+            wgt::ShaderStages::TASK => naga::ShaderStage::Task,
+            wgt::ShaderStages::MESH => naga::ShaderStage::Mesh,
             _ => unreachable!(),
         };
         let pair = (shader_stage, entry_point_name.to_string());
@@ -1089,16 +1183,30 @@ impl Interface {
         }
 
         // check workgroup size limits
-        if shader_stage == naga::ShaderStage::Compute {
-            let max_workgroup_size_limits = [
-                self.limits.max_compute_workgroup_size_x,
-                self.limits.max_compute_workgroup_size_y,
-                self.limits.max_compute_workgroup_size_z,
-            ];
+        let workgroup_size_limits = match shader_stage {
+            naga::ShaderStage::Compute => Some((
+                [
+                    self.limits.max_compute_workgroup_size_x,
+                    self.limits.max_compute_workgroup_size_y,
+                    self.limits.max_compute_workgroup_size_z,
+                ],
+                self.limits.max_compute_invocations_per_workgroup,
+            )),
+            naga::ShaderStage::Task => {
+                let total = self.limits.max_task_invocations_per_workgroup;
+                Some(([total; 3], total))
+            }
+            naga::ShaderStage::Mesh => {
+                let total = self.limits.max_mesh_invocations_per_workgroup;
+                Some(([total; 3], total))
+            }
+            naga::ShaderStage::Vertex | naga::ShaderStage::Fragment => None,
+        };
+        if let Some((max_workgroup_size_limits, max_invocations)) = workgroup_size_limits {
             let total_invocations = entry_point.workgroup_size.iter().product::<u32>();
 
             if entry_point.workgroup_size.iter().any(|&s| s == 0)
-                || total_invocations > self.limits.max_compute_invocations_per_workgroup
+                || total_invocations > max_invocations
                 || entry_point.workgroup_size[0] > max_workgroup_size_limits[0]
                 || entry_point.workgroup_size[1] > max_workgroup_size_limits[1]
                 || entry_point.workgroup_size[2] > max_workgroup_size_limits[2]
@@ -1107,7 +1215,30 @@ impl Interface {
                     current: entry_point.workgroup_size,
                     current_total: total_invocations,
                     limit: max_workgroup_size_limits,
-                    total: self.limits.max_compute_invocations_per_workgroup,
+                    total: max_invocations,
+                });
+            }
+        }
+
+        if let Some((max_vertices, max_primitives)) = entry_point.mesh_output_counts {
+            let vertex_limit = self.limits.max_mesh_output_vertices;
+            let primitive_limit = self.limits.max_mesh_output_primitives;
+            if max_vertices > vertex_limit || max_primitives > primitive_limit {
+                return Err(StageError::TooManyMeshOutputs {
+                    vertices: max_vertices,
+                    primitives: max_primitives,
+                    vertex_limit,
+                    primitive_limit,
+                });
+            }
+        }
+
+        if shader_stage == naga::ShaderStage::Compute {
+            let limit = self.limits.max_compute_workgroup_storage_size;
+            if entry_point.workgroup_storage_size > u64::from(limit) {
+                return Err(StageError::TooMuchWorkgroupStorage {
+                    used: entry_point.workgroup_storage_size,
+                    limit,
                 });
             }
         }
@@ -1146,7 +1277,9 @@ impl Interface {
                                             iv.ty.dim.num_components(),
                                         )
                                     }
-                                    naga::ShaderStage::Compute => (false, 0),
+                                    naga::ShaderStage::Compute
+                                    | naga::ShaderStage::Task
+                                    | naga::ShaderStage::Mesh => (false, 0),
                                 };
                                 if compatible {
                                     Ok(num_components)
@@ -1192,7 +1325,7 @@ impl Interface {
             }
         }
 
-        if shader_stage == naga::ShaderStage::Vertex {
+        if let naga::ShaderStage::Vertex | naga::ShaderStage::Mesh = shader_stage {
             for output in entry_point.outputs.iter() {
                 //TODO: count builtins towards the limit?
                 inter_stage_components += match *output {
@@ -168,6 +168,7 @@ impl<A: hal::Api> Example<A> {
                 module: Cow::Owned(module),
                 info,
                 debug_source: None,
+                source: None,
             }
         };
         let shader_desc = hal::ShaderModuleDescriptor {
@@ -269,6 +270,7 @@ impl<A: hal::Api> Example<A> {
                 write_mask: wgt::ColorWrites::default(),
             })],
             multiview: None,
+            cache: None,
         };
         let pipeline = unsafe { device.create_render_pipeline(&pipeline_desc).unwrap() };
 
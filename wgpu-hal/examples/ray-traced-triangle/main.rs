@@ -340,6 +340,7 @@ impl<A: hal::Api> Example<A> {
                 module: Cow::Owned(module),
                 info,
                 debug_source: None,
+                source: None,
             }
         };
         let shader_desc = hal::ShaderModuleDescriptor {
@@ -372,6 +373,7 @@ impl<A: hal::Api> Example<A> {
                     module: &shader_module,
                     entry_point: "main",
                 },
+                cache: None,
             })
         }
         .unwrap();
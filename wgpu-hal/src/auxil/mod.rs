@@ -46,11 +46,17 @@ pub mod db {
 /// offset at some intermediate point, internally, as i32.
 pub const MAX_I32_BINDING_SIZE: u32 = 1 << 31;
 
+/// Bytes per sample of color attachments for APIs that don't limit it beyond
+/// the number of attachments: every attachment at the largest pixel byte cost.
+pub const MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE: u32 = crate::MAX_COLOR_ATTACHMENTS as u32 * 16;
+
 pub fn map_naga_stage(stage: naga::ShaderStage) -> wgt::ShaderStages {
     match stage {
         naga::ShaderStage::Vertex => wgt::ShaderStages::VERTEX,
         naga::ShaderStage::Fragment => wgt::ShaderStages::FRAGMENT,
         naga::ShaderStage::Compute => wgt::ShaderStages::COMPUTE,
+        naga::ShaderStage::Task => wgt::ShaderStages::TASK,
+        naga::ShaderStage::Mesh => wgt::ShaderStages::MESH,
     }
 }
 
@@ -116,6 +122,109 @@ impl crate::TextureCopy {
     }
 }
 
+/// Key of a pipeline cache entry.
+///
+/// This is a SHA-256 digest of everything the entry was derived from, so
+/// distinct inputs can't end up sharing an entry.
+#[cfg(any(vulkan, gles))]
+pub(crate) type CacheKey = [u8; 32];
+
+/// Computes a [`CacheKey`].
+///
+/// As a [`Hasher`](std::hash::Hasher), this accepts anything that implements
+/// `Hash`, but [`Hasher::finish`](std::hash::Hasher::finish) only returns 64
+/// bits of the digest. Use [`CacheKeyHasher::key`] to get the whole key.
+#[cfg(any(vulkan, gles))]
+#[derive(Clone, Default)]
+pub(crate) struct CacheKeyHasher(sha2::Sha256);
+
+#[cfg(any(vulkan, gles))]
+impl CacheKeyHasher {
+    pub fn key(self) -> CacheKey {
+        sha2::Digest::finalize(self.0).into()
+    }
+}
+
+#[cfg(any(vulkan, gles))]
+impl std::hash::Hasher for CacheKeyHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        sha2::Digest::update(&mut self.0, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let key = self.clone().key();
+        u64::from_le_bytes(key[..8].try_into().unwrap())
+    }
+}
+
+/// Hash the [`source`](crate::NagaShader::source) of a shader, to key its
+/// translations on.
+///
+/// This covers the crate version too, as the same source may be translated
+/// differently by another release.
+#[cfg(any(vulkan, gles))]
+pub(crate) fn shader_source_hash(source: &str) -> CacheKey {
+    use std::hash::Hash as _;
+    let mut hasher = CacheKeyHasher::default();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    source.hash(&mut hasher);
+    hasher.key()
+}
+
+/// Append `value` to pipeline cache data.
+#[cfg(any(vulkan, gles))]
+pub(crate) fn write_cache_u64(data: &mut Vec<u8>, value: u64) {
+    data.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Append a length-prefixed byte string to pipeline cache data.
+#[cfg(any(vulkan, gles))]
+pub(crate) fn write_cache_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+    write_cache_u64(data, bytes.len() as u64);
+    data.extend_from_slice(bytes);
+}
+
+/// Append `key` to pipeline cache data.
+#[cfg(any(vulkan, gles))]
+pub(crate) fn write_cache_key(data: &mut Vec<u8>, key: &CacheKey) {
+    data.extend_from_slice(key);
+}
+
+/// Read a value written by [`write_cache_u64`], advancing `data` past it.
+#[cfg(any(vulkan, gles))]
+pub(crate) fn read_cache_u64(data: &mut &[u8]) -> Option<u64> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (value, rest) = data.split_at(8);
+    *data = rest;
+    Some(u64::from_le_bytes(value.try_into().unwrap()))
+}
+
+/// Read a byte string written by [`write_cache_bytes`], advancing `data`
+/// past it.
+#[cfg(any(vulkan, gles))]
+pub(crate) fn read_cache_bytes<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = usize::try_from(read_cache_u64(data)?).ok()?;
+    if data.len() < len {
+        return None;
+    }
+    let (bytes, rest) = data.split_at(len);
+    *data = rest;
+    Some(bytes)
+}
+
+/// Read a key written by [`write_cache_key`], advancing `data` past it.
+#[cfg(any(vulkan, gles))]
+pub(crate) fn read_cache_key(data: &mut &[u8]) -> Option<CacheKey> {
+    let key = data
+        .get(..std::mem::size_of::<CacheKey>())?
+        .try_into()
+        .ok()?;
+    *data = &data[std::mem::size_of::<CacheKey>()..];
+    Some(key)
+}
+
 /// Construct a `CStr` from a byte slice, up to the first zero byte.
 ///
 /// Return a `CStr` extending from the start of `bytes` up to and
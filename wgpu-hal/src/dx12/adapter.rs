@@ -377,6 +377,8 @@ impl super::Adapter {
                         d3d12_ty::D3D12_CONSTANT_BUFFER_DATA_PLACEMENT_ALIGNMENT,
                     min_storage_buffer_offset_alignment: 4,
                     max_inter_stage_shader_components: base.max_inter_stage_shader_components,
+                    max_color_attachment_bytes_per_sample:
+                        crate::auxil::MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE,
                     max_compute_workgroup_storage_size: base.max_compute_workgroup_storage_size, //TODO?
                     max_compute_invocations_per_workgroup:
                         d3d12_ty::D3D12_CS_4_X_THREAD_GROUP_MAX_THREADS_PER_GROUP,
@@ -385,6 +387,14 @@ impl super::Adapter {
                     max_compute_workgroup_size_z: d3d12_ty::D3D12_CS_THREAD_GROUP_MAX_Z,
                     max_compute_workgroups_per_dimension:
                         d3d12_ty::D3D12_CS_DISPATCH_MAX_THREAD_GROUPS_PER_DIMENSION,
+                    max_task_workgroups_per_dimension: 0,
+                    max_task_workgroup_total_count: 0,
+                    max_task_invocations_per_workgroup: 0,
+                    max_mesh_workgroups_per_dimension: 0,
+                    max_mesh_workgroup_total_count: 0,
+                    max_mesh_invocations_per_workgroup: 0,
+                    max_mesh_output_vertices: 0,
+                    max_mesh_output_primitives: 0,
                     // Dx12 does not expose a maximum buffer size in the API.
                     // This limit is chosen to avoid potential issues with drivers should they internally
                     // store buffer sizes using 32 bit ints (a situation we have already encountered with vulkan).
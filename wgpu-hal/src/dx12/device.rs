@@ -1068,6 +1068,7 @@ impl crate::Device<super::Api> for super::Device {
                 special_constants_binding,
                 push_constants_target,
                 zero_initialize_workgroup_memory: true,
+                descriptor_heap_map: hlsl::DescriptorHeapMap::default(),
             },
         })
     }
@@ -1451,6 +1452,16 @@ impl crate::Device<super::Api> for super::Device {
     }
     unsafe fn destroy_render_pipeline(&self, _pipeline: super::RenderPipeline) {}
 
+    unsafe fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        // `Features::MESH_SHADER` is not supported on this backend.
+        Err(crate::PipelineError::Device(
+            crate::DeviceError::ResourceCreationFailed,
+        ))
+    }
+
     unsafe fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<super::Api>,
@@ -1486,6 +1497,20 @@ impl crate::Device<super::Api> for super::Device {
     }
     unsafe fn destroy_compute_pipeline(&self, _pipeline: super::ComputePipeline) {}
 
+    unsafe fn create_pipeline_cache(
+        &self,
+        _desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<(), crate::PipelineCacheError> {
+        Ok(())
+    }
+    unsafe fn destroy_pipeline_cache(&self, _cache: ()) {}
+    unsafe fn pipeline_cache_get_data(&self, _cache: &()) -> Option<Vec<u8>> {
+        None
+    }
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]> {
+        None
+    }
+
     unsafe fn create_query_set(
         &self,
         desc: &wgt::QuerySetDescriptor<crate::Label>,
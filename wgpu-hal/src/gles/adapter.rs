@@ -191,6 +191,7 @@ impl super::Adapter {
 
     pub(super) unsafe fn expose(
         context: super::AdapterContext,
+        program_binary: Option<super::ProgramBinaryFns>,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         let gl = context.lock();
         let extensions = gl.supported_extensions();
@@ -722,6 +723,8 @@ impl super::Adapter {
             max_inter_stage_shader_components: unsafe {
                 gl.get_parameter_i32(glow::MAX_VARYING_COMPONENTS)
             } as u32,
+            max_color_attachment_bytes_per_sample:
+                crate::auxil::MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE,
             max_compute_workgroup_storage_size: if supports_work_group_params {
                 (unsafe { gl.get_parameter_i32(glow::MAX_COMPUTE_SHARED_MEMORY_SIZE) } as u32)
             } else {
@@ -751,6 +754,14 @@ impl super::Adapter {
                 0
             },
             max_compute_workgroups_per_dimension,
+            max_task_workgroups_per_dimension: 0,
+            max_task_workgroup_total_count: 0,
+            max_task_invocations_per_workgroup: 0,
+            max_mesh_workgroups_per_dimension: 0,
+            max_mesh_workgroup_total_count: 0,
+            max_mesh_invocations_per_workgroup: 0,
+            max_mesh_output_vertices: 0,
+            max_mesh_output_primitives: 0,
             max_buffer_size: i32::MAX as u64,
             max_non_sampler_bindings: std::u32::MAX,
         };
@@ -780,6 +791,19 @@ impl super::Adapter {
 
         let downlevel_defaults = wgt::DownlevelLimits {};
 
+        let program_binary = program_binary
+            .filter(|_| unsafe { gl.get_parameter_i32(glow::NUM_PROGRAM_BINARY_FORMATS) } > 0);
+        // Program binaries are only guaranteed to be accepted by the exact
+        // driver that produced them.
+        let program_binary_key = program_binary.map(|_| {
+            use std::hash::Hash as _;
+            let mut hasher = crate::auxil::CacheKeyHasher::default();
+            vendor.hash(&mut hasher);
+            renderer.hash(&mut hasher);
+            version.hash(&mut hasher);
+            hasher.key()[..16].try_into().unwrap()
+        });
+
         // Drop the GL guard so we can move the context into AdapterShared
         // ( on Wasm the gl handle is just a ref so we tell clippy to allow
         // dropping the ref )
@@ -796,6 +820,8 @@ impl super::Adapter {
                     shading_language_version,
                     next_shader_id: Default::default(),
                     program_cache: Default::default(),
+                    program_binary,
+                    program_binary_key,
                     es: es_ver.is_some(),
                 }),
             },
@@ -1122,6 +1122,32 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) {
         unreachable!()
     }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        _group_count_x: u32,
+        _group_count_y: u32,
+        _group_count_z: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _draw_count: u32,
+    ) {
+        unreachable!()
+    }
+    unsafe fn draw_mesh_tasks_indirect_count(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+        _count_buffer: &super::Buffer,
+        _count_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        unreachable!()
+    }
 
     // compute
 
@@ -1175,13 +1201,13 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         super::Api: 'a,
         T: IntoIterator<Item = crate::BuildAccelerationStructureDescriptor<'a, super::Api>>,
     {
-        unimplemented!()
+        unreachable!()
     }
 
     unsafe fn place_acceleration_structure_barrier(
         &mut self,
         _barriers: crate::AccelerationStructureBarrier,
     ) {
-        unimplemented!()
+        unreachable!()
     }
 }
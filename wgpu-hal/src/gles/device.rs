@@ -41,14 +41,27 @@ impl CompilationContext<'_> {
             if ep_info[handle].is_empty() {
                 continue;
             }
-            let register = match var.space {
-                naga::AddressSpace::Uniform => super::BindingRegister::UniformBuffers,
-                naga::AddressSpace::Storage { .. } => super::BindingRegister::StorageBuffers,
+            let br = var.binding.as_ref().unwrap();
+            let (register, br) = match var.space {
+                naga::AddressSpace::Uniform => (super::BindingRegister::UniformBuffers, br.clone()),
+                naga::AddressSpace::Storage { .. } => {
+                    (super::BindingRegister::StorageBuffers, br.clone())
+                }
+                // The parameters of external textures are in a uniform block.
+                naga::AddressSpace::Handle => match module.types[var.ty].inner {
+                    naga::TypeInner::Image {
+                        class: naga::ImageClass::External,
+                        ..
+                    } => {
+                        let [_, _, params] = br.external_texture_bindings();
+                        (super::BindingRegister::UniformBuffers, params)
+                    }
+                    _ => continue,
+                },
                 _ => continue,
             };
 
-            let br = var.binding.as_ref().unwrap();
-            let slot = self.layout.get_slot(br);
+            let slot = self.layout.get_slot(&br);
 
             let name = match reflection_info.uniforms.get(&handle) {
                 Some(name) => name.clone(),
@@ -74,8 +87,10 @@ impl CompilationContext<'_> {
                 _ => super::BindingRegister::Textures,
             };
 
-            let tex_br = var.binding.as_ref().unwrap();
-            let texture_linear_index = self.layout.get_slot(tex_br);
+            // The other planes of external textures follow their binding.
+            let mut tex_br = var.binding.clone().unwrap();
+            tex_br.binding += mapping.plane;
+            let texture_linear_index = self.layout.get_slot(&tex_br);
 
             self.name_binding_map
                 .insert(name, (register, texture_linear_index));
@@ -100,6 +115,7 @@ impl CompilationContext<'_> {
                     unsafe { gl.bind_frag_data_location(program, location.location, &name) }
                 }
                 naga::ShaderStage::Compute => {}
+                naga::ShaderStage::Task | naga::ShaderStage::Mesh => unreachable!(),
             }
         }
 
@@ -107,6 +123,99 @@ impl CompilationContext<'_> {
     }
 }
 
+/// What translating the stages of a program yields besides their GLSL.
+struct ProgramReflection {
+    name_binding_map: NameBindingMap,
+    sampler_map: super::SamplerBindMap,
+    push_constant_items:
+        ArrayVec<Vec<naga::back::glsl::PushConstantItem>, { crate::MAX_CONCURRENT_SHADER_STAGES }>,
+}
+
+impl Default for ProgramReflection {
+    fn default() -> Self {
+        Self {
+            name_binding_map: NameBindingMap::default(),
+            sampler_map: [None; super::MAX_TEXTURE_SLOTS],
+            push_constant_items: ArrayVec::new(),
+        }
+    }
+}
+
+impl ProgramReflection {
+    /// Append `self` to pipeline cache data.
+    #[cfg(native)]
+    fn write(&self, data: &mut Vec<u8>) {
+        use crate::auxil::{write_cache_bytes, write_cache_u64};
+        write_cache_u64(data, self.name_binding_map.len() as u64);
+        for (name, &(register, slot)) in self.name_binding_map.iter() {
+            write_cache_bytes(data, name.as_bytes());
+            write_cache_u64(data, register as u64);
+            write_cache_u64(data, slot.into());
+        }
+        for &sampler in self.sampler_map.iter() {
+            write_cache_u64(data, sampler.map_or(u64::MAX, u64::from));
+        }
+        write_cache_u64(data, self.push_constant_items.len() as u64);
+        for items in self.push_constant_items.iter() {
+            write_cache_u64(data, items.len() as u64);
+            for item in items {
+                write_cache_bytes(data, item.access_path.as_bytes());
+                write_cache_u64(data, item.ty.index() as u64);
+                write_cache_u64(data, item.offset.into());
+            }
+        }
+    }
+
+    /// Read the reflection written by [`ProgramReflection::write`] for a
+    /// program made of `shaders`.
+    #[cfg(native)]
+    fn read(mut data: &[u8], shaders: &[ShaderStage]) -> Option<Self> {
+        use crate::auxil::{read_cache_bytes, read_cache_u64};
+        let read_string =
+            |data: &mut &[u8]| String::from_utf8(read_cache_bytes(data)?.to_vec()).ok();
+
+        let mut reflection = Self::default();
+        for _ in 0..read_cache_u64(&mut data)? {
+            let name = read_string(&mut data)?;
+            let register = match read_cache_u64(&mut data)? {
+                0 => super::BindingRegister::UniformBuffers,
+                1 => super::BindingRegister::StorageBuffers,
+                2 => super::BindingRegister::Textures,
+                3 => super::BindingRegister::Images,
+                _ => return None,
+            };
+            let slot = read_cache_u64(&mut data)?.try_into().ok()?;
+            reflection.name_binding_map.insert(name, (register, slot));
+        }
+        for sampler in reflection.sampler_map.iter_mut() {
+            *sampler = match read_cache_u64(&mut data)? {
+                u64::MAX => None,
+                slot => Some(slot.try_into().ok()?),
+            };
+        }
+        if read_cache_u64(&mut data)? != shaders.len() as u64 {
+            return None;
+        }
+        for &(_, stage) in shaders {
+            let types = &stage.module.naga.module.types;
+            let mut items = Vec::new();
+            for _ in 0..read_cache_u64(&mut data)? {
+                let access_path = read_string(&mut data)?;
+                let index = usize::try_from(read_cache_u64(&mut data)?).ok()?;
+                let (ty, _) = types.iter().nth(index)?;
+                let offset = read_cache_u64(&mut data)?.try_into().ok()?;
+                items.push(naga::back::glsl::PushConstantItem {
+                    access_path,
+                    ty,
+                    offset,
+                });
+            }
+            reflection.push_constant_items.push(items);
+        }
+        data.is_empty().then_some(reflection)
+    }
+}
+
 impl super::Device {
     /// # Safety
     ///
@@ -173,6 +282,7 @@ impl super::Device {
             naga::ShaderStage::Vertex => glow::VERTEX_SHADER,
             naga::ShaderStage::Fragment => glow::FRAGMENT_SHADER,
             naga::ShaderStage::Compute => glow::COMPUTE_SHADER,
+            naga::ShaderStage::Task | naga::ShaderStage::Mesh => unreachable!(),
         };
 
         let raw = unsafe { gl.create_shader(target) }.unwrap();
@@ -206,13 +316,13 @@ impl super::Device {
         }
     }
 
-    fn create_shader(
+    fn translate_shader(
         gl: &glow::Context,
         naga_stage: naga::ShaderStage,
         stage: &crate::ProgrammableStage<super::Api>,
         context: CompilationContext,
         program: glow::Program,
-    ) -> Result<glow::Shader, crate::PipelineError> {
+    ) -> Result<String, crate::PipelineError> {
         use naga::back::glsl;
         let pipeline_options = glsl::PipelineOptions {
             shader_stage: naga_stage,
@@ -276,7 +386,82 @@ impl super::Device {
             program,
         );
 
-        unsafe { Self::compile_shader(gl, &output, naga_stage, stage.module.label.as_deref()) }
+        Ok(output)
+    }
+
+    /// Tries to link `program` made of `shaders` from the binary stored in
+    /// `cache` under `key`.
+    ///
+    /// Returns the reflection stored along with the binary, or `None` if there
+    /// is no such binary or the driver rejected it.
+    #[cfg(native)]
+    unsafe fn load_program_binary(
+        gl: &glow::Context,
+        program: glow::Program,
+        fns: super::ProgramBinaryFns,
+        cache: &super::PipelineCache,
+        key: crate::auxil::CacheKey,
+        shaders: &[ShaderStage],
+    ) -> Option<ProgramReflection> {
+        let binaries = cache.binaries.lock();
+        let cached = binaries.get(&key)?;
+        let reflection = ProgramReflection::read(&cached.reflection, shaders)?;
+        unsafe {
+            (fns.program_binary)(
+                program.0.get(),
+                cached.format,
+                cached.binary.as_ptr().cast(),
+                cached.binary.len() as i32,
+            )
+        };
+        let linked_ok = unsafe { gl.get_program_link_status(program) };
+        if !linked_ok {
+            log::info!("\tCached program binary was rejected, recompiling");
+            return None;
+        }
+        Some(reflection)
+    }
+
+    /// Stores the binary of the linked `program` in `cache` under `key`,
+    /// along with its `reflection`.
+    #[cfg(native)]
+    unsafe fn store_program_binary(
+        program: glow::Program,
+        fns: super::ProgramBinaryFns,
+        cache: &super::PipelineCache,
+        key: crate::auxil::CacheKey,
+        reflection: &ProgramReflection,
+    ) {
+        let mut length = 0;
+        unsafe { (fns.get_program_iv)(program.0.get(), glow::PROGRAM_BINARY_LENGTH, &mut length) };
+        if length <= 0 {
+            return;
+        }
+        let mut binary = vec![0u8; length as usize];
+        let mut format = 0;
+        let mut written = 0;
+        unsafe {
+            (fns.get_program_binary)(
+                program.0.get(),
+                length,
+                &mut written,
+                &mut format,
+                binary.as_mut_ptr().cast(),
+            )
+        };
+        binary.truncate(written.max(0) as usize);
+        if !binary.is_empty() {
+            let mut reflection_data = Vec::new();
+            reflection.write(&mut reflection_data);
+            cache.binaries.lock().insert(
+                key,
+                super::ProgramBinary {
+                    format,
+                    binary,
+                    reflection: reflection_data,
+                },
+            );
+        }
     }
 
     unsafe fn create_pipeline<'a>(
@@ -286,6 +471,7 @@ impl super::Device {
         layout: &super::PipelineLayout,
         #[cfg_attr(target_arch = "wasm32", allow(unused))] label: Option<&str>,
         multiview: Option<std::num::NonZeroU32>,
+        cache: Option<&super::PipelineCache>,
     ) -> Result<Arc<super::PipelineInner>, crate::PipelineError> {
         let mut program_stages = ArrayVec::new();
         let mut group_to_binding_to_slot = Vec::with_capacity(layout.group_infos.len());
@@ -312,15 +498,7 @@ impl super::Device {
                 group_to_binding_to_slot: group_to_binding_to_slot.into_boxed_slice(),
             })
             .or_insert_with(|| unsafe {
-                Self::create_program(
-                    gl,
-                    shaders,
-                    layout,
-                    label,
-                    multiview,
-                    self.shared.shading_language_version,
-                    self.shared.private_caps,
-                )
+                Self::create_program(gl, shaders, layout, label, multiview, &self.shared, cache)
             })
             .to_owned()?;
         drop(guard);
@@ -334,10 +512,11 @@ impl super::Device {
         layout: &super::PipelineLayout,
         #[cfg_attr(target_arch = "wasm32", allow(unused))] label: Option<&str>,
         multiview: Option<std::num::NonZeroU32>,
-        glsl_version: naga::back::glsl::Version,
-        private_caps: PrivateCapabilities,
+        shared: &super::AdapterShared,
+        cache: Option<&super::PipelineCache>,
     ) -> Result<Arc<super::PipelineInner>, crate::PipelineError> {
-        let glsl_version = match glsl_version {
+        let private_caps = shared.private_caps;
+        let glsl_version = match shared.shading_language_version {
             naga::back::glsl::Version::Embedded { version, .. } => format!("{version} es"),
             naga::back::glsl::Version::Desktop(version) => format!("{version}"),
         };
@@ -350,70 +529,142 @@ impl super::Device {
             }
         }
 
-        let mut name_binding_map = NameBindingMap::default();
-        let mut push_constant_items = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
-        let mut sampler_map = [None; super::MAX_TEXTURE_SLOTS];
         let mut has_stages = wgt::ShaderStages::empty();
-        let mut shaders_to_delete = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
-
-        for &(naga_stage, stage) in &shaders {
+        for &(naga_stage, _) in &shaders {
             has_stages |= map_naga_stage(naga_stage);
-            let pc_item = {
-                push_constant_items.push(Vec::new());
-                push_constant_items.last_mut().unwrap()
-            };
-            let context = CompilationContext {
-                layout,
-                sampler_map: &mut sampler_map,
-                name_binding_map: &mut name_binding_map,
-                push_constant_items: pc_item,
-                multiview,
-            };
-
-            let shader = Self::create_shader(gl, naga_stage, stage, context, program)?;
-            shaders_to_delete.push(shader);
         }
 
-        // Create empty fragment shader if only vertex shader is present
-        if has_stages == wgt::ShaderStages::VERTEX {
-            let shader_src = format!("#version {glsl_version}\n void main(void) {{}}",);
-            log::info!("Only vertex shader is present. Creating an empty fragment shader",);
-            let shader = unsafe {
-                Self::compile_shader(
-                    gl,
-                    &shader_src,
+        // Program binaries are keyed by the sources their stages were parsed
+        // from and the options they are translated with, so that loading one
+        // skips the translation. The bounds check policies depend on the GL
+        // version, which the validation key of the cache already covers.
+        let binary_cache = cache
+            .filter(|_| shared.program_binary.is_some())
+            .and_then(|cache| {
+                use std::hash::Hash as _;
+                let mut hasher = crate::auxil::CacheKeyHasher::default();
+                for &(naga_stage, stage) in &shaders {
+                    stage.module.source_hash?.hash(&mut hasher);
+                    naga_stage.hash(&mut hasher);
+                    stage.entry_point.hash(&mut hasher);
+                }
+                multiview.hash(&mut hasher);
+                let options = &layout.naga_options;
+                match options.version {
+                    naga::back::glsl::Version::Desktop(version) => (0u8, version).hash(&mut hasher),
+                    naga::back::glsl::Version::Embedded { version, is_webgl } => {
+                        (1u8, version, is_webgl).hash(&mut hasher)
+                    }
+                }
+                options.writer_flags.bits().hash(&mut hasher);
+                options.binding_map.hash(&mut hasher);
+                options.zero_initialize_workgroup_memory.hash(&mut hasher);
+                Some((cache, hasher.key()))
+            });
+
+        #[cfg(native)]
+        let cached_reflection = match (shared.program_binary, binary_cache) {
+            (Some(fns), Some((cache, key))) => unsafe {
+                Self::load_program_binary(gl, program, fns, cache, key, &shaders)
+            },
+            _ => None,
+        };
+        #[cfg(not(native))]
+        let cached_reflection = None;
+
+        let reflection = if let Some(reflection) = cached_reflection {
+            log::debug!("\tLoaded program {:?} from binary", program);
+            reflection
+        } else {
+            let mut reflection = ProgramReflection::default();
+            let mut sources = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+
+            for &(naga_stage, stage) in &shaders {
+                let pc_item = {
+                    reflection.push_constant_items.push(Vec::new());
+                    reflection.push_constant_items.last_mut().unwrap()
+                };
+                let context = CompilationContext {
+                    layout,
+                    sampler_map: &mut reflection.sampler_map,
+                    name_binding_map: &mut reflection.name_binding_map,
+                    push_constant_items: pc_item,
+                    multiview,
+                };
+
+                let source = Self::translate_shader(gl, naga_stage, stage, context, program)?;
+                sources.push((naga_stage, source, stage.module.label.as_deref()));
+            }
+
+            // Create empty fragment shader if only vertex shader is present
+            if has_stages == wgt::ShaderStages::VERTEX {
+                let shader_src = format!("#version {glsl_version}\n void main(void) {{}}",);
+                log::info!("Only vertex shader is present. Creating an empty fragment shader",);
+                sources.push((
                     naga::ShaderStage::Fragment,
+                    shader_src,
                     Some("(wgpu internal) dummy fragment shader"),
-                )
-            }?;
-            shaders_to_delete.push(shader);
-        }
+                ));
+            }
 
-        for &shader in shaders_to_delete.iter() {
-            unsafe { gl.attach_shader(program, shader) };
-        }
-        unsafe { gl.link_program(program) };
+            let mut shaders_to_delete =
+                ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+            for &(naga_stage, ref source, label) in sources.iter() {
+                let shader = unsafe { Self::compile_shader(gl, source, naga_stage, label) };
+                match shader {
+                    Ok(shader) => shaders_to_delete.push(shader),
+                    Err(e) => {
+                        for shader in shaders_to_delete {
+                            unsafe { gl.delete_shader(shader) };
+                        }
+                        return Err(e);
+                    }
+                }
+            }
 
-        for shader in shaders_to_delete {
-            unsafe { gl.delete_shader(shader) };
-        }
+            for &shader in shaders_to_delete.iter() {
+                unsafe { gl.attach_shader(program, shader) };
+            }
+            #[cfg(native)]
+            if let (Some(fns), Some(_)) = (shared.program_binary, binary_cache) {
+                unsafe {
+                    (fns.program_parameter_i)(
+                        program.0.get(),
+                        glow::PROGRAM_BINARY_RETRIEVABLE_HINT,
+                        glow::TRUE as i32,
+                    )
+                };
+            }
+            unsafe { gl.link_program(program) };
 
-        log::debug!("\tLinked program {:?}", program);
+            for shader in shaders_to_delete {
+                unsafe { gl.delete_shader(shader) };
+            }
 
-        let linked_ok = unsafe { gl.get_program_link_status(program) };
-        let msg = unsafe { gl.get_program_info_log(program) };
-        if !linked_ok {
-            return Err(crate::PipelineError::Linkage(has_stages, msg));
-        }
-        if !msg.is_empty() {
-            log::warn!("\tLink: {}", msg);
-        }
+            log::debug!("\tLinked program {:?}", program);
+
+            let linked_ok = unsafe { gl.get_program_link_status(program) };
+            let msg = unsafe { gl.get_program_info_log(program) };
+            if !linked_ok {
+                return Err(crate::PipelineError::Linkage(has_stages, msg));
+            }
+            if !msg.is_empty() {
+                log::warn!("\tLink: {}", msg);
+            }
+
+            #[cfg(native)]
+            if let (Some(fns), Some((cache, key))) = (shared.program_binary, binary_cache) {
+                unsafe { Self::store_program_binary(program, fns, cache, key, &reflection) };
+            }
+
+            reflection
+        };
 
         if !private_caps.contains(super::PrivateCapabilities::SHADER_BINDING_LAYOUT) {
             // This remapping is only needed if we aren't able to put the binding layout
             // in the shader. We can't remap storage buffers this way.
             unsafe { gl.use_program(Some(program)) };
-            for (ref name, (register, slot)) in name_binding_map {
+            for (ref name, (register, slot)) in reflection.name_binding_map {
                 log::trace!("Get binding {:?} from program {:?}", name, program);
                 match register {
                     super::BindingRegister::UniformBuffers => {
@@ -441,7 +692,7 @@ impl super::Device {
 
         let mut uniforms = ArrayVec::new();
 
-        for (stage_idx, stage_items) in push_constant_items.into_iter().enumerate() {
+        for (stage_idx, stage_items) in reflection.push_constant_items.into_iter().enumerate() {
             for item in stage_items {
                 let naga_module = &shaders[stage_idx].1.module.naga.module;
                 let type_inner = &naga_module.types[item.ty].inner;
@@ -476,7 +727,7 @@ impl super::Device {
 
         Ok(Arc::new(super::PipelineInner {
             program,
-            sampler_map,
+            sampler_map: reflection.sampler_map,
             first_instance_location,
             push_constant_descs: uniforms,
         }))
@@ -1244,15 +1495,21 @@ impl crate::Device<super::Api> for super::Device {
         desc: &crate::ShaderModuleDescriptor,
         shader: crate::ShaderInput,
     ) -> Result<super::ShaderModule, crate::ShaderError> {
+        let mut naga = match shader {
+            crate::ShaderInput::SpirV(_) => {
+                panic!("`Features::SPIRV_SHADER_PASSTHROUGH` is not enabled")
+            }
+            crate::ShaderInput::Naga(naga) => naga,
+        };
+        let source_hash = naga
+            .source
+            .take()
+            .map(|source| crate::auxil::shader_source_hash(&source));
         Ok(super::ShaderModule {
-            naga: match shader {
-                crate::ShaderInput::SpirV(_) => {
-                    panic!("`Features::SPIRV_SHADER_PASSTHROUGH` is not enabled")
-                }
-                crate::ShaderInput::Naga(naga) => naga,
-            },
+            naga,
             label: desc.label.map(|str| str.to_string()),
             id: self.shared.next_shader_id.fetch_add(1, Ordering::Relaxed),
+            source_hash,
         })
     }
     unsafe fn destroy_shader_module(&self, _module: super::ShaderModule) {}
@@ -1267,8 +1524,16 @@ impl crate::Device<super::Api> for super::Device {
         if let Some(ref fs) = desc.fragment_stage {
             shaders.push((naga::ShaderStage::Fragment, fs));
         }
-        let inner =
-            unsafe { self.create_pipeline(gl, shaders, desc.layout, desc.label, desc.multiview) }?;
+        let inner = unsafe {
+            self.create_pipeline(
+                gl,
+                shaders,
+                desc.layout,
+                desc.label,
+                desc.multiview,
+                desc.cache,
+            )
+        }?;
 
         let (vertex_buffers, vertex_attributes) = {
             let mut buffers = Vec::new();
@@ -1342,6 +1607,16 @@ impl crate::Device<super::Api> for super::Device {
         }
     }
 
+    unsafe fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        // `Features::MESH_SHADER` is not supported on this backend.
+        Err(crate::PipelineError::Device(
+            crate::DeviceError::ResourceCreationFailed,
+        ))
+    }
+
     unsafe fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<super::Api>,
@@ -1349,7 +1624,9 @@ impl crate::Device<super::Api> for super::Device {
         let gl = &self.shared.context.lock();
         let mut shaders = ArrayVec::new();
         shaders.push((naga::ShaderStage::Compute, &desc.stage));
-        let inner = unsafe { self.create_pipeline(gl, shaders, desc.layout, desc.label, None) }?;
+        let inner = unsafe {
+            self.create_pipeline(gl, shaders, desc.layout, desc.label, None, desc.cache)
+        }?;
 
         Ok(super::ComputePipeline { inner })
     }
@@ -1369,6 +1646,56 @@ impl crate::Device<super::Api> for super::Device {
         }
     }
 
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
+        let mut binaries = naga::FastHashMap::default();
+        if self.shared.program_binary.is_some() {
+            let mut data = desc.data.unwrap_or_default();
+            while let Some(key) = crate::auxil::read_cache_key(&mut data) {
+                let Some(format) = crate::auxil::read_cache_u64(&mut data) else {
+                    break;
+                };
+                let Some(binary) = crate::auxil::read_cache_bytes(&mut data) else {
+                    break;
+                };
+                let Some(reflection) = crate::auxil::read_cache_bytes(&mut data) else {
+                    break;
+                };
+                binaries.insert(
+                    key,
+                    super::ProgramBinary {
+                        format: format as u32,
+                        binary: binary.to_vec(),
+                        reflection: reflection.to_vec(),
+                    },
+                );
+            }
+            if !data.is_empty() {
+                log::warn!("Ignoring malformed pipeline cache data");
+            }
+        }
+        Ok(super::PipelineCache {
+            binaries: parking_lot::Mutex::new(binaries),
+        })
+    }
+    unsafe fn destroy_pipeline_cache(&self, _cache: super::PipelineCache) {}
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        self.shared.program_binary?;
+        let mut data = Vec::new();
+        for (key, cached) in cache.binaries.lock().iter() {
+            crate::auxil::write_cache_key(&mut data, key);
+            crate::auxil::write_cache_u64(&mut data, cached.format.into());
+            crate::auxil::write_cache_bytes(&mut data, &cached.binary);
+            crate::auxil::write_cache_bytes(&mut data, &cached.reflection);
+        }
+        Some(data)
+    }
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]> {
+        self.shared.program_binary_key
+    }
+
     #[cfg_attr(target_arch = "wasm32", allow(unused))]
     unsafe fn create_query_set(
         &self,
@@ -944,15 +944,15 @@ impl crate::Instance<super::Api> for Instance {
         let inner = self.inner.lock();
         inner.egl.make_current();
 
-        let mut gl = unsafe {
-            glow::Context::from_loader_function(|name| {
-                inner
-                    .egl
-                    .instance
-                    .get_proc_address(name)
-                    .map_or(ptr::null(), |p| p as *const _)
-            })
+        let loader = |name: &str| {
+            inner
+                .egl
+                .instance
+                .get_proc_address(name)
+                .map_or(ptr::null(), |p| p as *const ffi::c_void)
         };
+        let mut gl = unsafe { glow::Context::from_loader_function(loader) };
+        let program_binary = unsafe { super::ProgramBinaryFns::load(loader) };
 
         if self.flags.contains(wgt::InstanceFlags::DEBUG) && gl.supports_debug() {
             log::debug!("Max label length: {}", unsafe {
@@ -969,10 +969,13 @@ impl crate::Instance<super::Api> for Instance {
         inner.egl.unmake_current();
 
         unsafe {
-            super::Adapter::expose(AdapterContext {
-                glow: Mutex::new(gl),
-                egl: Some(inner.egl.clone()),
-            })
+            super::Adapter::expose(
+                AdapterContext {
+                    glow: Mutex::new(gl),
+                    egl: Some(inner.egl.clone()),
+                },
+                program_binary,
+            )
         }
         .into_iter()
         .collect()
@@ -988,14 +991,18 @@ impl super::Adapter {
     /// - The underlying OpenGL ES context must be current when interfacing with any objects returned by
     ///   wgpu-hal from this adapter.
     pub unsafe fn new_external(
-        fun: impl FnMut(&str) -> *const ffi::c_void,
+        mut fun: impl FnMut(&str) -> *const ffi::c_void,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
-        let context = unsafe { glow::Context::from_loader_function(fun) };
+        let context = unsafe { glow::Context::from_loader_function(&mut fun) };
+        let program_binary = unsafe { super::ProgramBinaryFns::load(fun) };
         unsafe {
-            Self::expose(AdapterContext {
-                glow: Mutex::new(context),
-                egl: None,
-            })
+            Self::expose(
+                AdapterContext {
+                    glow: Mutex::new(context),
+                    egl: None,
+                },
+                program_binary,
+            )
         }
     }
 
@@ -161,6 +161,7 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type PipelineCache = PipelineCache;
 }
 
 bitflags::bitflags! {
@@ -250,9 +251,65 @@ struct AdapterShared {
     shading_language_version: naga::back::glsl::Version,
     next_shader_id: AtomicU32,
     program_cache: Mutex<ProgramCache>,
+    /// Entry points for program binaries, if the context supports at least
+    /// one binary format.
+    program_binary: Option<ProgramBinaryFns>,
+    /// Identifies the driver that produced program binaries, see
+    /// [`crate::Device::pipeline_cache_validation_key`].
+    program_binary_key: Option<[u8; 16]>,
     es: bool,
 }
 
+/// The `glGetProgramBinary` family of functions, which glow doesn't expose.
+#[derive(Clone, Copy)]
+#[cfg_attr(not(native), allow(dead_code))]
+struct ProgramBinaryFns {
+    get_program_iv: unsafe extern "system" fn(program: u32, pname: u32, params: *mut i32),
+    get_program_binary: unsafe extern "system" fn(
+        program: u32,
+        buf_size: i32,
+        length: *mut i32,
+        binary_format: *mut u32,
+        binary: *mut std::ffi::c_void,
+    ),
+    program_binary: unsafe extern "system" fn(
+        program: u32,
+        binary_format: u32,
+        binary: *const std::ffi::c_void,
+        length: i32,
+    ),
+    program_parameter_i: unsafe extern "system" fn(program: u32, pname: u32, value: i32),
+}
+
+impl ProgramBinaryFns {
+    /// Loads the functions, returning `None` if any of them is missing.
+    #[cfg_attr(webgl, allow(dead_code))]
+    unsafe fn load(mut loader: impl FnMut(&str) -> *const std::ffi::c_void) -> Option<Self> {
+        let mut load = |name: &str| {
+            let ptr = loader(name);
+            (!ptr.is_null()).then_some(ptr)
+        };
+        let get_program_iv = load("glGetProgramiv")?;
+        let get_program_binary = load("glGetProgramBinary")?;
+        let program_binary = load("glProgramBinary")?;
+        let program_parameter_i = load("glProgramParameteri")?;
+        Some(unsafe {
+            Self {
+                get_program_iv: std::mem::transmute(get_program_iv),
+                get_program_binary: std::mem::transmute(get_program_binary),
+                program_binary: std::mem::transmute(program_binary),
+                program_parameter_i: std::mem::transmute(program_parameter_i),
+            }
+        })
+    }
+}
+
+impl fmt::Debug for ProgramBinaryFns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgramBinaryFns").finish_non_exhaustive()
+    }
+}
+
 pub struct Adapter {
     shared: Arc<AdapterShared>,
 }
@@ -424,7 +481,7 @@ impl PipelineLayout {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 enum BindingRegister {
     UniformBuffers,
     StorageBuffers,
@@ -462,6 +519,8 @@ pub struct ShaderModule {
     naga: crate::NagaShader,
     label: Option<String>,
     id: ShaderId,
+    /// Hash of the source `naga` was parsed from, if it was given.
+    source_hash: Option<crate::auxil::CacheKey>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -588,6 +647,23 @@ pub struct ComputePipeline {
     inner: Arc<PipelineInner>,
 }
 
+#[derive(Debug)]
+pub struct PipelineCache {
+    /// Program binaries keyed by a hash of the sources and options their
+    /// stages were translated with.
+    binaries: Mutex<FastHashMap<crate::auxil::CacheKey, ProgramBinary>>,
+}
+
+/// A linked program, as stored in a [`PipelineCache`].
+#[derive(Debug)]
+struct ProgramBinary {
+    format: u32,
+    binary: Vec<u8>,
+    /// What translating the stages of the program yielded besides their GLSL,
+    /// so that loading the binary doesn't need to translate them again.
+    reflection: Vec<u8>,
+}
+
 #[cfg(send_sync)]
 unsafe impl Sync for ComputePipeline {}
 #[cfg(send_sync)]
@@ -131,7 +131,7 @@ impl crate::Instance<super::Api> for Instance {
             None => return Vec::new(),
         };
 
-        unsafe { super::Adapter::expose(AdapterContext { glow_context: gl }) }
+        unsafe { super::Adapter::expose(AdapterContext { glow_context: gl }, None) }
             .into_iter()
             .collect()
     }
@@ -138,6 +138,7 @@ unsafe impl Sync for WglContext {}
 
 struct Inner {
     gl: glow::Context,
+    program_binary: Option<super::ProgramBinaryFns>,
     device: InstanceDevice,
     context: WglContext,
 }
@@ -474,6 +475,9 @@ impl crate::Instance<super::Api> for Instance {
         let mut gl = unsafe {
             glow::Context::from_loader_function(|name| load_gl_func(name, Some(opengl_module)))
         };
+        let program_binary = unsafe {
+            super::ProgramBinaryFns::load(|name| load_gl_func(name, Some(opengl_module)))
+        };
 
         let extra = Wgl::load_with(|name| load_gl_func(name, None));
         let extentions = extensions(&extra, dc);
@@ -505,6 +509,7 @@ impl crate::Instance<super::Api> for Instance {
             inner: Arc::new(Mutex::new(Inner {
                 device,
                 gl,
+                program_binary,
                 context,
             })),
             srgb_capable,
@@ -534,10 +539,14 @@ impl crate::Instance<super::Api> for Instance {
     unsafe fn destroy_surface(&self, _surface: Surface) {}
 
     unsafe fn enumerate_adapters(&self) -> Vec<crate::ExposedAdapter<super::Api>> {
+        let program_binary = self.inner.lock().program_binary;
         unsafe {
-            super::Adapter::expose(AdapterContext {
-                inner: self.inner.clone(),
-            })
+            super::Adapter::expose(
+                AdapterContext {
+                    inner: self.inner.clone(),
+                },
+                program_binary,
+            )
         }
         .into_iter()
         .collect()
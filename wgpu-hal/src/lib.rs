@@ -95,7 +95,8 @@ use wgt::WasmNotSendSync;
 
 // - Vertex + Fragment
 // - Compute
-pub const MAX_CONCURRENT_SHADER_STAGES: usize = 2;
+// - Task + Mesh + Fragment
+pub const MAX_CONCURRENT_SHADER_STAGES: usize = 3;
 pub const MAX_ANISOTROPY: u8 = 16;
 pub const MAX_BIND_GROUPS: usize = 8;
 pub const MAX_VERTEX_BUFFERS: usize = 16;
@@ -139,6 +140,12 @@ pub enum PipelineError {
     Device(#[from] DeviceError),
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum PipelineCacheError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Error)]
 pub enum SurfaceError {
     #[error("Surface is lost")]
@@ -212,6 +219,7 @@ pub trait Api: Clone + fmt::Debug + Sized {
     type ShaderModule: fmt::Debug + WasmNotSendSync;
     type RenderPipeline: fmt::Debug + WasmNotSendSync;
     type ComputePipeline: fmt::Debug + WasmNotSendSync;
+    type PipelineCache: fmt::Debug + WasmNotSendSync;
 
     type AccelerationStructure: fmt::Debug + WasmNotSendSync + 'static;
 }
@@ -363,12 +371,40 @@ pub trait Device<A: Api>: WasmNotSendSync {
         desc: &RenderPipelineDescriptor<A>,
     ) -> Result<A::RenderPipeline, PipelineError>;
     unsafe fn destroy_render_pipeline(&self, pipeline: A::RenderPipeline);
+    /// Create a render pipeline whose geometry comes from task and mesh shaders.
+    ///
+    /// The result is destroyed with [`Device::destroy_render_pipeline`].
+    unsafe fn create_mesh_pipeline(
+        &self,
+        desc: &MeshPipelineDescriptor<A>,
+    ) -> Result<A::RenderPipeline, PipelineError>;
     unsafe fn create_compute_pipeline(
         &self,
         desc: &ComputePipelineDescriptor<A>,
     ) -> Result<A::ComputePipeline, PipelineError>;
     unsafe fn destroy_compute_pipeline(&self, pipeline: A::ComputePipeline);
 
+    /// Creates a pipeline cache, seeded with `desc.data` if there is any.
+    ///
+    /// # Safety
+    ///
+    /// - `desc.data` must have been returned by [`Device::pipeline_cache_get_data`]
+    ///   on a device with the same [`Device::pipeline_cache_validation_key`].
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> Result<A::PipelineCache, PipelineCacheError>;
+    unsafe fn destroy_pipeline_cache(&self, cache: A::PipelineCache);
+    /// Serializes the contents of `cache`, or returns `None` if the backend
+    /// has nothing it can persist.
+    unsafe fn pipeline_cache_get_data(&self, cache: &A::PipelineCache) -> Option<Vec<u8>>;
+    /// Identifies the driver that produced pipeline cache data.
+    ///
+    /// Data returned by [`Device::pipeline_cache_get_data`] can only be
+    /// loaded by a device with the same key. Returns `None` if the backend
+    /// doesn't support pipeline caches.
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]>;
+
     unsafe fn create_query_set(
         &self,
         desc: &wgt::QuerySetDescriptor<Label>,
@@ -622,6 +658,29 @@ pub trait CommandEncoder<A: Api>: WasmNotSendSync + fmt::Debug {
         max_count: u32,
     );
 
+    /// Launch a grid of task shader workgroups, or of mesh shader
+    /// workgroups if the pipeline has no task shader.
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    );
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        buffer: &A::Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    );
+    unsafe fn draw_mesh_tasks_indirect_count(
+        &mut self,
+        buffer: &A::Buffer,
+        offset: wgt::BufferAddress,
+        count_buffer: &A::Buffer,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    );
+
     // compute passes
 
     // Begins a compute pass, clears all active bindings.
@@ -1159,6 +1218,12 @@ pub struct NagaShader {
     pub info: naga::valid::ModuleInfo,
     /// Source codes for debug
     pub debug_source: Option<DebugSource>,
+    /// Source code `module` was parsed from, without any later changes to it.
+    ///
+    /// Backends key the shaders they translate `module` to on a hash of it, as
+    /// hashing the module itself would be costly. They don't cache translations
+    /// of modules without it.
+    pub source: Option<Cow<'static, str>>,
 }
 
 // Custom implementation avoids the need to generate Debug impl code
@@ -1215,6 +1280,16 @@ pub struct ComputePipelineDescriptor<'a, A: Api> {
     pub layout: &'a A::PipelineLayout,
     /// The compiled compute stage and its entry point.
     pub stage: ProgrammableStage<'a, A>,
+    /// The cache to look the pipeline up in and add it to, if any.
+    pub cache: Option<&'a A::PipelineCache>,
+}
+
+/// Describes a pipeline cache.
+#[derive(Clone, Debug)]
+pub struct PipelineCacheDescriptor<'a> {
+    pub label: Label<'a>,
+    /// Data previously returned by [`Device::pipeline_cache_get_data`], if any.
+    pub data: Option<&'a [u8]>,
 }
 
 /// Describes how the vertex buffer is interpreted.
@@ -1251,6 +1326,38 @@ pub struct RenderPipelineDescriptor<'a, A: Api> {
     /// If the pipeline will be used with a multiview render pass, this indicates how many array
     /// layers the attachments will have.
     pub multiview: Option<NonZeroU32>,
+    /// The cache to look the pipeline up in and add it to, if any.
+    pub cache: Option<&'a A::PipelineCache>,
+}
+
+/// Describes a mesh pipeline: a render pipeline whose geometry is produced
+/// by task and mesh shaders rather than by vertex fetch and a vertex shader.
+#[derive(Clone, Debug)]
+pub struct MeshPipelineDescriptor<'a, A: Api> {
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    pub layout: &'a A::PipelineLayout,
+    /// The task stage for this pipeline, if any.
+    pub task_stage: Option<ProgrammableStage<'a, A>>,
+    /// The mesh stage for this pipeline.
+    pub mesh_stage: ProgrammableStage<'a, A>,
+    /// The properties of the pipeline at the rasterization level.
+    ///
+    /// The topology is ignored: it is declared by the mesh shader.
+    pub primitive: wgt::PrimitiveState,
+    /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
+    pub depth_stencil: Option<wgt::DepthStencilState>,
+    /// The multi-sampling properties of the pipeline.
+    pub multisample: wgt::MultisampleState,
+    /// The fragment stage for this pipeline.
+    pub fragment_stage: Option<ProgrammableStage<'a, A>>,
+    /// The effect of draw calls on the color aspect of the output target.
+    pub color_targets: &'a [Option<wgt::ColorTargetState>],
+    /// If the pipeline will be used with a multiview render pass, this indicates how many array
+    /// layers the attachments will have.
+    pub multiview: Option<NonZeroU32>,
+    /// The cache to look the pipeline up in and add it to, if any.
+    pub cache: Option<&'a A::PipelineCache>,
 }
 
 #[derive(Debug, Clone)]
@@ -738,6 +738,15 @@ impl super::PrivateCapabilities {
             } else {
                 60
             },
+            max_color_attachment_bytes_per_sample: if device
+                .supports_feature_set(MTLFeatureSet::macOS_GPUFamily1_v1)
+            {
+                crate::auxil::MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE
+            } else if family_check && device.supports_family(MTLGPUFamily::Apple4) {
+                64
+            } else {
+                32
+            },
             max_threads_per_group: if Self::supports_any(
                 device,
                 &[
@@ -940,12 +949,21 @@ impl super::PrivateCapabilities {
                 min_uniform_buffer_offset_alignment: self.buffer_alignment as u32,
                 min_storage_buffer_offset_alignment: self.buffer_alignment as u32,
                 max_inter_stage_shader_components: self.max_varying_components,
+                max_color_attachment_bytes_per_sample: self.max_color_attachment_bytes_per_sample,
                 max_compute_workgroup_storage_size: self.max_total_threadgroup_memory,
                 max_compute_invocations_per_workgroup: self.max_threads_per_group,
                 max_compute_workgroup_size_x: self.max_threads_per_group,
                 max_compute_workgroup_size_y: self.max_threads_per_group,
                 max_compute_workgroup_size_z: self.max_threads_per_group,
                 max_compute_workgroups_per_dimension: 0xFFFF,
+                max_task_workgroups_per_dimension: 0,
+                max_task_workgroup_total_count: 0,
+                max_task_invocations_per_workgroup: 0,
+                max_mesh_workgroups_per_dimension: 0,
+                max_mesh_workgroup_total_count: 0,
+                max_mesh_invocations_per_workgroup: 0,
+                max_mesh_output_vertices: 0,
+                max_mesh_output_primitives: 0,
                 max_buffer_size: self.max_buffer_size,
                 max_non_sampler_bindings: std::u32::MAX,
             },
@@ -686,6 +686,7 @@ impl crate::Device<super::Api> for super::Device {
                 .sizes_buffer
                 .map(|buffer_index| buffer_index as naga::back::msl::Slot),
             resources: info.resources,
+            argument_buffers: Default::default(),
         });
 
         Ok(super::PipelineLayout {
@@ -1029,6 +1030,16 @@ impl crate::Device<super::Api> for super::Device {
     }
     unsafe fn destroy_render_pipeline(&self, _pipeline: super::RenderPipeline) {}
 
+    unsafe fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        // `Features::MESH_SHADER` is not supported on this backend.
+        Err(crate::PipelineError::Device(
+            crate::DeviceError::ResourceCreationFailed,
+        ))
+    }
+
     unsafe fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<super::Api>,
@@ -1084,6 +1095,20 @@ impl crate::Device<super::Api> for super::Device {
     }
     unsafe fn destroy_compute_pipeline(&self, _pipeline: super::ComputePipeline) {}
 
+    unsafe fn create_pipeline_cache(
+        &self,
+        _desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<(), crate::PipelineCacheError> {
+        Ok(())
+    }
+    unsafe fn destroy_pipeline_cache(&self, _cache: ()) {}
+    unsafe fn pipeline_cache_get_data(&self, _cache: &()) -> Option<Vec<u8>> {
+        None
+    }
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]> {
+        None
+    }
+
     unsafe fn create_query_set(
         &self,
         desc: &wgt::QuerySetDescriptor<crate::Label>,
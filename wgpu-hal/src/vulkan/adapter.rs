@@ -1,6 +1,9 @@
 use super::conv;
 
-use ash::{extensions::khr, vk};
+use ash::{
+    extensions::{ext, khr},
+    vk,
+};
 use parking_lot::Mutex;
 
 use std::{
@@ -39,6 +42,7 @@ pub struct PhysicalDeviceFeatures {
     acceleration_structure: Option<vk::PhysicalDeviceAccelerationStructureFeaturesKHR>,
     buffer_device_address: Option<vk::PhysicalDeviceBufferDeviceAddressFeaturesKHR>,
     ray_query: Option<vk::PhysicalDeviceRayQueryFeaturesKHR>,
+    mesh_shader: Option<vk::PhysicalDeviceMeshShaderFeaturesEXT>,
     zero_initialize_workgroup_memory:
         Option<vk::PhysicalDeviceZeroInitializeWorkgroupMemoryFeatures>,
 }
@@ -88,6 +92,9 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.ray_query {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.mesh_shader {
+            info = info.push_next(feature);
+        }
         info
     }
 
@@ -338,6 +345,16 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            mesh_shader: if enabled_extensions.contains(&vk::ExtMeshShaderFn::name()) {
+                Some(
+                    vk::PhysicalDeviceMeshShaderFeaturesEXT::builder()
+                        .task_shader(true)
+                        .mesh_shader(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
             zero_initialize_workgroup_memory: if device_api_version >= vk::API_VERSION_1_3
                 || enabled_extensions.contains(&vk::KhrZeroInitializeWorkgroupMemoryFn::name())
             {
@@ -586,6 +603,15 @@ impl PhysicalDeviceFeatures {
             caps.supports_extension(vk::KhrRayQueryFn::name()),
         );
 
+        // `VK_EXT_mesh_shader` consumes SPIR-V 1.4, which needs Vulkan 1.2 or `VK_KHR_spirv_1_4`.
+        features.set(
+            F::MESH_SHADER,
+            self.mesh_shader
+                .is_some_and(|f| f.task_shader != 0 && f.mesh_shader != 0)
+                && (caps.device_api_version >= vk::API_VERSION_1_2
+                    || caps.supports_extension(vk::KhrSpirv14Fn::name())),
+        );
+
         let rg11b10ufloat_renderable = supports_format(
             instance,
             phd,
@@ -646,6 +672,7 @@ pub struct PhysicalDeviceCapabilities {
     maintenance_3: Option<vk::PhysicalDeviceMaintenance3Properties>,
     descriptor_indexing: Option<vk::PhysicalDeviceDescriptorIndexingPropertiesEXT>,
     acceleration_structure: Option<vk::PhysicalDeviceAccelerationStructurePropertiesKHR>,
+    mesh_shader: Option<vk::PhysicalDeviceMeshShaderPropertiesEXT>,
     driver: Option<vk::PhysicalDeviceDriverPropertiesKHR>,
     /// The device API version.
     ///
@@ -793,6 +820,16 @@ impl PhysicalDeviceCapabilities {
             extensions.push(vk::KhrRayQueryFn::name());
         }
 
+        // Require `VK_EXT_mesh_shader` if the associated feature was requested, along with
+        // `VK_KHR_spirv_1_4` and its dependency `VK_KHR_shader_float_controls` before Vulkan 1.2
+        if requested_features.contains(wgt::Features::MESH_SHADER) {
+            extensions.push(vk::ExtMeshShaderFn::name());
+            if self.device_api_version < vk::API_VERSION_1_2 {
+                extensions.push(vk::KhrSpirv14Fn::name());
+                extensions.push(vk::KhrShaderFloatControlsFn::name());
+            }
+        }
+
         // Require `VK_EXT_conservative_rasterization` if the associated feature was requested
         if requested_features.contains(wgt::Features::CONSERVATIVE_RASTERIZATION) {
             extensions.push(vk::ExtConservativeRasterizationFn::name());
@@ -818,6 +855,11 @@ impl PhysicalDeviceCapabilities {
             .min(limits.max_compute_work_group_count[1])
             .min(limits.max_compute_work_group_count[2]);
 
+        // Without `VK_EXT_mesh_shader`, every mesh shader limit is left at 0. The per-dimension
+        // workgroup sizes aren't exposed separately, so they bound the invocation counts.
+        let mesh_shader = self.mesh_shader.unwrap_or_default();
+        let min_of = |values: [u32; 3]| values[0].min(values[1]).min(values[2]);
+
         // Prevent very large buffers on mesa and most android devices.
         let is_nvidia = self.properties.vendor_id == crate::auxil::db::nvidia::VENDOR;
         let max_buffer_size =
@@ -862,12 +904,26 @@ impl PhysicalDeviceCapabilities {
             max_inter_stage_shader_components: limits
                 .max_vertex_output_components
                 .min(limits.max_fragment_input_components),
+            max_color_attachment_bytes_per_sample:
+                crate::auxil::MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE,
             max_compute_workgroup_storage_size: limits.max_compute_shared_memory_size,
             max_compute_invocations_per_workgroup: limits.max_compute_work_group_invocations,
             max_compute_workgroup_size_x: max_compute_workgroup_sizes[0],
             max_compute_workgroup_size_y: max_compute_workgroup_sizes[1],
             max_compute_workgroup_size_z: max_compute_workgroup_sizes[2],
             max_compute_workgroups_per_dimension,
+            max_task_workgroups_per_dimension: min_of(mesh_shader.max_task_work_group_count),
+            max_task_workgroup_total_count: mesh_shader.max_task_work_group_total_count,
+            max_task_invocations_per_workgroup: mesh_shader
+                .max_task_work_group_invocations
+                .min(min_of(mesh_shader.max_task_work_group_size)),
+            max_mesh_workgroups_per_dimension: min_of(mesh_shader.max_mesh_work_group_count),
+            max_mesh_workgroup_total_count: mesh_shader.max_mesh_work_group_total_count,
+            max_mesh_invocations_per_workgroup: mesh_shader
+                .max_mesh_work_group_invocations
+                .min(min_of(mesh_shader.max_mesh_work_group_size)),
+            max_mesh_output_vertices: mesh_shader.max_mesh_output_vertices,
+            max_mesh_output_primitives: mesh_shader.max_mesh_output_primitives,
             max_buffer_size,
             max_non_sampler_bindings: std::u32::MAX,
         }
@@ -911,6 +967,9 @@ impl super::InstanceShared {
                 let supports_acceleration_structure =
                     capabilities.supports_extension(vk::KhrAccelerationStructureFn::name());
 
+                let supports_mesh_shader =
+                    capabilities.supports_extension(vk::ExtMeshShaderFn::name());
+
                 let mut builder = vk::PhysicalDeviceProperties2KHR::builder();
                 if supports_maintenance3 {
                     capabilities.maintenance_3 =
@@ -932,6 +991,13 @@ impl super::InstanceShared {
                     builder = builder.push_next(next);
                 }
 
+                if supports_mesh_shader {
+                    let next = capabilities
+                        .mesh_shader
+                        .insert(vk::PhysicalDeviceMeshShaderPropertiesEXT::default());
+                    builder = builder.push_next(next);
+                }
+
                 if supports_driver_properties {
                     let next = capabilities
                         .driver
@@ -1041,6 +1107,12 @@ impl super::InstanceShared {
                     .insert(vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default());
                 builder = builder.push_next(next);
             }
+            if capabilities.supports_extension(vk::ExtMeshShaderFn::name()) {
+                let next = features
+                    .mesh_shader
+                    .insert(vk::PhysicalDeviceMeshShaderFeaturesEXT::default());
+                builder = builder.push_next(next);
+            }
 
             // `VK_KHR_zero_initialize_workgroup_memory` is promoted to 1.3
             if capabilities.device_api_version >= vk::API_VERSION_1_3
@@ -1376,6 +1448,11 @@ impl super::Adapter {
         } else {
             None
         };
+        let mesh_shading_fn = if enabled_extensions.contains(&ext::MeshShader::name()) {
+            Some(ext::MeshShader::new(&self.instance.raw, &raw_device))
+        } else {
+            None
+        };
         let ray_tracing_fns = if enabled_extensions.contains(&khr::AccelerationStructure::name())
             && enabled_extensions.contains(&khr::BufferDeviceAddress::name())
         {
@@ -1444,6 +1521,10 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::RayQueryKHR);
             }
 
+            if features.contains(wgt::Features::MESH_SHADER) {
+                capabilities.push(spv::Capability::MeshShadingEXT);
+            }
+
             let mut flags = spv::WriterFlags::empty();
             flags.set(
                 spv::WriterFlags::DEBUG,
@@ -1512,8 +1593,10 @@ impl super::Adapter {
                 draw_indirect_count: indirect_count_fn,
                 timeline_semaphore: timeline_semaphore_fn,
                 ray_tracing: ray_tracing_fns,
+                mesh_shading: mesh_shading_fn,
             },
             vendor_id: self.phd_capabilities.properties.vendor_id,
+            pipeline_cache_uuid: self.phd_capabilities.properties.pipeline_cache_uuid,
             timestamp_period: self.phd_capabilities.properties.limits.timestamp_period,
             private_caps: self.private_caps.clone(),
             workarounds: self.workarounds,
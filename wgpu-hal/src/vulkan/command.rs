@@ -1077,6 +1077,69 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             None => panic!("Feature `DRAW_INDIRECT_COUNT` not enabled"),
         }
     }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) {
+        match self.device.extension_fns.mesh_shading {
+            Some(ref t) => {
+                unsafe {
+                    t.cmd_draw_mesh_tasks(self.active, group_count_x, group_count_y, group_count_z)
+                };
+            }
+            None => panic!("Feature `MESH_SHADER` not enabled"),
+        }
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+        let stride = mem::size_of::<wgt::DrawMeshTasksIndirectArgs>() as u32;
+        match self.device.extension_fns.mesh_shading {
+            Some(ref t) => {
+                unsafe {
+                    t.cmd_draw_mesh_tasks_indirect(
+                        self.active,
+                        buffer.raw,
+                        offset,
+                        draw_count,
+                        stride,
+                    )
+                };
+            }
+            None => panic!("Feature `MESH_SHADER` not enabled"),
+        }
+    }
+    unsafe fn draw_mesh_tasks_indirect_count(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+        count_buffer: &super::Buffer,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
+    ) {
+        let stride = mem::size_of::<wgt::DrawMeshTasksIndirectArgs>() as u32;
+        match self.device.extension_fns.mesh_shading {
+            Some(ref t) => {
+                unsafe {
+                    t.cmd_draw_mesh_tasks_indirect_count(
+                        self.active,
+                        buffer.raw,
+                        offset,
+                        count_buffer.raw,
+                        count_offset,
+                        max_count,
+                        stride,
+                    )
+                };
+            }
+            None => panic!("Feature `MESH_SHADER` not enabled"),
+        }
+    }
 
     // compute
 
@@ -709,11 +709,204 @@ impl super::Device {
         Ok(raw)
     }
 
+    /// Create a graphics pipeline from already compiled shader `stages`.
+    ///
+    /// Render pipelines pass their `vertex_state`, while mesh pipelines have
+    /// neither vertex input nor input assembly.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn create_graphics_pipeline(
+        &self,
+        label: crate::Label,
+        layout: &super::PipelineLayout,
+        stages: &[vk::PipelineShaderStageCreateInfo],
+        vertex_state: Option<(
+            &vk::PipelineVertexInputStateCreateInfo,
+            &vk::PipelineInputAssemblyStateCreateInfo,
+        )>,
+        primitive: &wgt::PrimitiveState,
+        depth_stencil: Option<&wgt::DepthStencilState>,
+        multisample: &wgt::MultisampleState,
+        color_targets: &[Option<wgt::ColorTargetState>],
+        multiview: Option<NonZeroU32>,
+        cache: Option<&super::PipelineCache>,
+    ) -> Result<vk::Pipeline, crate::PipelineError> {
+        let dynamic_states = [
+            vk::DynamicState::VIEWPORT,
+            vk::DynamicState::SCISSOR,
+            vk::DynamicState::BLEND_CONSTANTS,
+            vk::DynamicState::STENCIL_REFERENCE,
+        ];
+        let mut compatible_rp_key = super::RenderPassKey {
+            sample_count: multisample.count,
+            multiview,
+            ..Default::default()
+        };
+
+        let mut vk_rasterization = vk::PipelineRasterizationStateCreateInfo::builder()
+            .polygon_mode(conv::map_polygon_mode(primitive.polygon_mode))
+            .front_face(conv::map_front_face(primitive.front_face))
+            .line_width(1.0)
+            .depth_clamp_enable(primitive.unclipped_depth);
+        if let Some(face) = primitive.cull_mode {
+            vk_rasterization = vk_rasterization.cull_mode(conv::map_cull_face(face))
+        }
+        let mut vk_rasterization_conservative_state =
+            vk::PipelineRasterizationConservativeStateCreateInfoEXT::builder()
+                .conservative_rasterization_mode(vk::ConservativeRasterizationModeEXT::OVERESTIMATE)
+                .build();
+        if primitive.conservative {
+            vk_rasterization = vk_rasterization.push_next(&mut vk_rasterization_conservative_state);
+        }
+
+        let mut vk_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::builder();
+        if let Some(ds) = depth_stencil {
+            let vk_format = self.shared.private_caps.map_texture_format(ds.format);
+            let vk_layout = if ds.is_read_only(primitive.cull_mode) {
+                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
+            } else {
+                vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL
+            };
+            compatible_rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
+                base: super::AttachmentKey::compatible(vk_format, vk_layout),
+                stencil_ops: crate::AttachmentOps::all(),
+            });
+
+            if ds.is_depth_enabled() {
+                vk_depth_stencil = vk_depth_stencil
+                    .depth_test_enable(true)
+                    .depth_write_enable(ds.depth_write_enabled)
+                    .depth_compare_op(conv::map_comparison(ds.depth_compare));
+            }
+            if ds.stencil.is_enabled() {
+                let s = &ds.stencil;
+                let front = conv::map_stencil_face(&s.front, s.read_mask, s.write_mask);
+                let back = conv::map_stencil_face(&s.back, s.read_mask, s.write_mask);
+                vk_depth_stencil = vk_depth_stencil
+                    .stencil_test_enable(true)
+                    .front(front)
+                    .back(back);
+            }
+
+            if ds.bias.is_enabled() {
+                vk_rasterization = vk_rasterization
+                    .depth_bias_enable(true)
+                    .depth_bias_constant_factor(ds.bias.constant as f32)
+                    .depth_bias_clamp(ds.bias.clamp)
+                    .depth_bias_slope_factor(ds.bias.slope_scale);
+            }
+        }
+
+        let vk_viewport = vk::PipelineViewportStateCreateInfo::builder()
+            .flags(vk::PipelineViewportStateCreateFlags::empty())
+            .scissor_count(1)
+            .viewport_count(1)
+            .build();
+
+        let vk_sample_mask = [multisample.mask as u32, (multisample.mask >> 32) as u32];
+        let vk_multisample = vk::PipelineMultisampleStateCreateInfo::builder()
+            .rasterization_samples(vk::SampleCountFlags::from_raw(multisample.count))
+            .alpha_to_coverage_enable(multisample.alpha_to_coverage_enabled)
+            .sample_mask(&vk_sample_mask)
+            .build();
+
+        let mut vk_attachments = Vec::with_capacity(color_targets.len());
+        for cat in color_targets {
+            let (key, attarchment) = if let Some(cat) = cat.as_ref() {
+                let mut vk_attachment = vk::PipelineColorBlendAttachmentState::builder()
+                    .color_write_mask(vk::ColorComponentFlags::from_raw(cat.write_mask.bits()));
+                if let Some(ref blend) = cat.blend {
+                    let (color_op, color_src, color_dst) = conv::map_blend_component(&blend.color);
+                    let (alpha_op, alpha_src, alpha_dst) = conv::map_blend_component(&blend.alpha);
+                    vk_attachment = vk_attachment
+                        .blend_enable(true)
+                        .color_blend_op(color_op)
+                        .src_color_blend_factor(color_src)
+                        .dst_color_blend_factor(color_dst)
+                        .alpha_blend_op(alpha_op)
+                        .src_alpha_blend_factor(alpha_src)
+                        .dst_alpha_blend_factor(alpha_dst);
+                }
+
+                let vk_format = self.shared.private_caps.map_texture_format(cat.format);
+                (
+                    Some(super::ColorAttachmentKey {
+                        base: super::AttachmentKey::compatible(
+                            vk_format,
+                            vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+                        ),
+                        resolve: None,
+                    }),
+                    vk_attachment.build(),
+                )
+            } else {
+                (None, vk::PipelineColorBlendAttachmentState::default())
+            };
+
+            compatible_rp_key.colors.push(key);
+            vk_attachments.push(attarchment);
+        }
+
+        let vk_color_blend = vk::PipelineColorBlendStateCreateInfo::builder()
+            .attachments(&vk_attachments)
+            .build();
+
+        let vk_dynamic_state = vk::PipelineDynamicStateCreateInfo::builder()
+            .dynamic_states(&dynamic_states)
+            .build();
+
+        let raw_pass = self
+            .shared
+            .make_render_pass(compatible_rp_key)
+            .map_err(crate::DeviceError::from)?;
+
+        let mut vk_info = vk::GraphicsPipelineCreateInfo::builder()
+            .layout(layout.raw)
+            .stages(stages)
+            .rasterization_state(&vk_rasterization)
+            .viewport_state(&vk_viewport)
+            .multisample_state(&vk_multisample)
+            .depth_stencil_state(&vk_depth_stencil)
+            .color_blend_state(&vk_color_blend)
+            .dynamic_state(&vk_dynamic_state)
+            .render_pass(raw_pass);
+        if let Some((vk_vertex_input, vk_input_assembly)) = vertex_state {
+            vk_info = vk_info
+                .vertex_input_state(vk_vertex_input)
+                .input_assembly_state(vk_input_assembly);
+        }
+        let vk_infos = [vk_info.build()];
+
+        let mut raw_vec = {
+            profiling::scope!("vkCreateGraphicsPipelines");
+            unsafe {
+                self.shared
+                    .raw
+                    .create_graphics_pipelines(
+                        cache.map_or(vk::PipelineCache::null(), |cache| cache.raw),
+                        &vk_infos,
+                        None,
+                    )
+                    .map_err(|(_, e)| crate::DeviceError::from(e))
+            }?
+        };
+
+        let raw = raw_vec.pop().unwrap();
+        if let Some(label) = label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::PIPELINE, raw, label)
+            };
+        }
+
+        Ok(raw)
+    }
+
     fn compile_stage(
         &self,
         stage: &crate::ProgrammableStage<super::Api>,
         naga_stage: naga::ShaderStage,
         binding_map: &naga::back::spv::BindingMap,
+        cache: Option<&super::PipelineCache>,
     ) -> Result<CompiledStage, crate::PipelineError> {
         let stage_flags = crate::auxil::map_naga_stage(naga_stage);
         let vk_module = match *stage.module {
@@ -721,17 +914,27 @@ impl super::Device {
             super::ShaderModule::Intermediate {
                 ref naga_shader,
                 runtime_checks,
+                ref source_hash,
             } => {
                 let pipeline_options = naga::back::spv::PipelineOptions {
                     entry_point: stage.entry_point.to_string(),
                     shader_stage: naga_stage,
                 };
+                // `SPV_EXT_mesh_shader` needs SPIR-V 1.4
+                let is_mesh_stage = matches!(
+                    naga_stage,
+                    naga::ShaderStage::Task | naga::ShaderStage::Mesh
+                );
                 let needs_temp_options = !runtime_checks
                     || !binding_map.is_empty()
-                    || naga_shader.debug_source.is_some();
+                    || naga_shader.debug_source.is_some()
+                    || is_mesh_stage;
                 let mut temp_options;
                 let options = if needs_temp_options {
                     temp_options = self.naga_options.clone();
+                    if is_mesh_stage {
+                        temp_options.lang_version = (1, 4);
+                    }
                     if !runtime_checks {
                         temp_options.bounds_check_policies = naga::proc::BoundsCheckPolicies {
                             index: naga::proc::BoundsCheckPolicy::Unchecked,
@@ -756,16 +959,56 @@ impl super::Device {
                 } else {
                     &self.naga_options
                 };
-                let spv = {
-                    profiling::scope!("naga::spv::write_vec");
-                    naga::back::spv::write_vec(
-                        &naga_shader.module,
-                        &naga_shader.info,
-                        options,
-                        Some(&pipeline_options),
-                    )
-                }
-                .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))?;
+                let spirv_key = cache.and(source_hash.as_ref()).map(|source_hash| {
+                    use std::hash::{Hash as _, Hasher as _};
+                    let mut hasher = crate::auxil::CacheKeyHasher::default();
+                    hasher.write(source_hash);
+                    pipeline_options.hash(&mut hasher);
+                    options.lang_version.hash(&mut hasher);
+                    options.flags.bits().hash(&mut hasher);
+                    options.binding_map.hash(&mut hasher);
+                    options
+                        .capabilities
+                        .as_ref()
+                        .map(|capabilities| {
+                            let mut capabilities = capabilities.iter().collect::<Vec<_>>();
+                            capabilities.sort();
+                            capabilities
+                        })
+                        .hash(&mut hasher);
+                    options.bounds_check_policies.hash(&mut hasher);
+                    (options.zero_initialize_workgroup_memory as u8).hash(&mut hasher);
+                    // The source code is the one `source_hash` covers.
+                    options
+                        .debug_info
+                        .as_ref()
+                        .map(|debug_info| debug_info.file_name)
+                        .hash(&mut hasher);
+                    hasher.key()
+                });
+                let cached_spv = match (cache, spirv_key) {
+                    (Some(cache), Some(key)) => cache.spirv.lock().get(&key).cloned(),
+                    _ => None,
+                };
+                let spv = match cached_spv {
+                    Some(spv) => spv,
+                    None => {
+                        let spv = {
+                            profiling::scope!("naga::spv::write_vec");
+                            naga::back::spv::write_vec(
+                                &naga_shader.module,
+                                &naga_shader.info,
+                                options,
+                                Some(&pipeline_options),
+                            )
+                        }
+                        .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))?;
+                        if let (Some(cache), Some(key)) = (cache, spirv_key) {
+                            cache.spirv.lock().insert(key, spv.clone());
+                        }
+                        spv
+                    }
+                };
                 self.create_shader_module_impl(&spv)?
             }
         };
@@ -1571,15 +1814,20 @@ impl crate::Device<super::Api> for super::Device {
         shader: crate::ShaderInput,
     ) -> Result<super::ShaderModule, crate::ShaderError> {
         let spv = match shader {
-            crate::ShaderInput::Naga(naga_shader) => {
+            crate::ShaderInput::Naga(mut naga_shader) => {
                 if self
                     .shared
                     .workarounds
                     .contains(super::Workarounds::SEPARATE_ENTRY_POINTS)
                 {
+                    let source_hash = naga_shader
+                        .source
+                        .take()
+                        .map(|source| crate::auxil::shader_source_hash(&source));
                     return Ok(super::ShaderModule::Intermediate {
                         naga_shader,
                         runtime_checks: desc.runtime_checks,
+                        source_hash,
                     });
                 }
                 let mut naga_options = self.naga_options.clone();
@@ -1637,17 +1885,6 @@ impl crate::Device<super::Api> for super::Device {
         &self,
         desc: &crate::RenderPipelineDescriptor<super::Api>,
     ) -> Result<super::RenderPipeline, crate::PipelineError> {
-        let dynamic_states = [
-            vk::DynamicState::VIEWPORT,
-            vk::DynamicState::SCISSOR,
-            vk::DynamicState::BLEND_CONSTANTS,
-            vk::DynamicState::STENCIL_REFERENCE,
-        ];
-        let mut compatible_rp_key = super::RenderPassKey {
-            sample_count: desc.multisample.count,
-            multiview: desc.multiview,
-            ..Default::default()
-        };
         let mut stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
         let mut vertex_buffers = Vec::with_capacity(desc.vertex_buffers.len());
         let mut vertex_attributes = Vec::new();
@@ -1685,6 +1922,7 @@ impl crate::Device<super::Api> for super::Device {
             &desc.vertex_stage,
             naga::ShaderStage::Vertex,
             &desc.layout.binding_arrays,
+            desc.cache,
         )?;
         stages.push(compiled_vs.create_info);
         let compiled_fs = match desc.fragment_stage {
@@ -1693,6 +1931,7 @@ impl crate::Device<super::Api> for super::Device {
                     stage,
                     naga::ShaderStage::Fragment,
                     &desc.layout.binding_arrays,
+                    desc.cache,
                 )?;
                 stages.push(compiled.create_info);
                 Some(compiled)
@@ -1700,159 +1939,20 @@ impl crate::Device<super::Api> for super::Device {
             None => None,
         };
 
-        let mut vk_rasterization = vk::PipelineRasterizationStateCreateInfo::builder()
-            .polygon_mode(conv::map_polygon_mode(desc.primitive.polygon_mode))
-            .front_face(conv::map_front_face(desc.primitive.front_face))
-            .line_width(1.0)
-            .depth_clamp_enable(desc.primitive.unclipped_depth);
-        if let Some(face) = desc.primitive.cull_mode {
-            vk_rasterization = vk_rasterization.cull_mode(conv::map_cull_face(face))
-        }
-        let mut vk_rasterization_conservative_state =
-            vk::PipelineRasterizationConservativeStateCreateInfoEXT::builder()
-                .conservative_rasterization_mode(vk::ConservativeRasterizationModeEXT::OVERESTIMATE)
-                .build();
-        if desc.primitive.conservative {
-            vk_rasterization = vk_rasterization.push_next(&mut vk_rasterization_conservative_state);
-        }
-
-        let mut vk_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::builder();
-        if let Some(ref ds) = desc.depth_stencil {
-            let vk_format = self.shared.private_caps.map_texture_format(ds.format);
-            let vk_layout = if ds.is_read_only(desc.primitive.cull_mode) {
-                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
-            } else {
-                vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL
-            };
-            compatible_rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
-                base: super::AttachmentKey::compatible(vk_format, vk_layout),
-                stencil_ops: crate::AttachmentOps::all(),
-            });
-
-            if ds.is_depth_enabled() {
-                vk_depth_stencil = vk_depth_stencil
-                    .depth_test_enable(true)
-                    .depth_write_enable(ds.depth_write_enabled)
-                    .depth_compare_op(conv::map_comparison(ds.depth_compare));
-            }
-            if ds.stencil.is_enabled() {
-                let s = &ds.stencil;
-                let front = conv::map_stencil_face(&s.front, s.read_mask, s.write_mask);
-                let back = conv::map_stencil_face(&s.back, s.read_mask, s.write_mask);
-                vk_depth_stencil = vk_depth_stencil
-                    .stencil_test_enable(true)
-                    .front(front)
-                    .back(back);
-            }
-
-            if ds.bias.is_enabled() {
-                vk_rasterization = vk_rasterization
-                    .depth_bias_enable(true)
-                    .depth_bias_constant_factor(ds.bias.constant as f32)
-                    .depth_bias_clamp(ds.bias.clamp)
-                    .depth_bias_slope_factor(ds.bias.slope_scale);
-            }
-        }
-
-        let vk_viewport = vk::PipelineViewportStateCreateInfo::builder()
-            .flags(vk::PipelineViewportStateCreateFlags::empty())
-            .scissor_count(1)
-            .viewport_count(1)
-            .build();
-
-        let vk_sample_mask = [
-            desc.multisample.mask as u32,
-            (desc.multisample.mask >> 32) as u32,
-        ];
-        let vk_multisample = vk::PipelineMultisampleStateCreateInfo::builder()
-            .rasterization_samples(vk::SampleCountFlags::from_raw(desc.multisample.count))
-            .alpha_to_coverage_enable(desc.multisample.alpha_to_coverage_enabled)
-            .sample_mask(&vk_sample_mask)
-            .build();
-
-        let mut vk_attachments = Vec::with_capacity(desc.color_targets.len());
-        for cat in desc.color_targets {
-            let (key, attarchment) = if let Some(cat) = cat.as_ref() {
-                let mut vk_attachment = vk::PipelineColorBlendAttachmentState::builder()
-                    .color_write_mask(vk::ColorComponentFlags::from_raw(cat.write_mask.bits()));
-                if let Some(ref blend) = cat.blend {
-                    let (color_op, color_src, color_dst) = conv::map_blend_component(&blend.color);
-                    let (alpha_op, alpha_src, alpha_dst) = conv::map_blend_component(&blend.alpha);
-                    vk_attachment = vk_attachment
-                        .blend_enable(true)
-                        .color_blend_op(color_op)
-                        .src_color_blend_factor(color_src)
-                        .dst_color_blend_factor(color_dst)
-                        .alpha_blend_op(alpha_op)
-                        .src_alpha_blend_factor(alpha_src)
-                        .dst_alpha_blend_factor(alpha_dst);
-                }
-
-                let vk_format = self.shared.private_caps.map_texture_format(cat.format);
-                (
-                    Some(super::ColorAttachmentKey {
-                        base: super::AttachmentKey::compatible(
-                            vk_format,
-                            vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
-                        ),
-                        resolve: None,
-                    }),
-                    vk_attachment.build(),
-                )
-            } else {
-                (None, vk::PipelineColorBlendAttachmentState::default())
-            };
-
-            compatible_rp_key.colors.push(key);
-            vk_attachments.push(attarchment);
-        }
-
-        let vk_color_blend = vk::PipelineColorBlendStateCreateInfo::builder()
-            .attachments(&vk_attachments)
-            .build();
-
-        let vk_dynamic_state = vk::PipelineDynamicStateCreateInfo::builder()
-            .dynamic_states(&dynamic_states)
-            .build();
-
-        let raw_pass = self
-            .shared
-            .make_render_pass(compatible_rp_key)
-            .map_err(crate::DeviceError::from)?;
-
-        let vk_infos = [{
-            vk::GraphicsPipelineCreateInfo::builder()
-                .layout(desc.layout.raw)
-                .stages(&stages)
-                .vertex_input_state(&vk_vertex_input)
-                .input_assembly_state(&vk_input_assembly)
-                .rasterization_state(&vk_rasterization)
-                .viewport_state(&vk_viewport)
-                .multisample_state(&vk_multisample)
-                .depth_stencil_state(&vk_depth_stencil)
-                .color_blend_state(&vk_color_blend)
-                .dynamic_state(&vk_dynamic_state)
-                .render_pass(raw_pass)
-                .build()
-        }];
-
-        let mut raw_vec = {
-            profiling::scope!("vkCreateGraphicsPipelines");
-            unsafe {
-                self.shared
-                    .raw
-                    .create_graphics_pipelines(vk::PipelineCache::null(), &vk_infos, None)
-                    .map_err(|(_, e)| crate::DeviceError::from(e))
-            }?
-        };
-
-        let raw = raw_vec.pop().unwrap();
-        if let Some(label) = desc.label {
-            unsafe {
-                self.shared
-                    .set_object_name(vk::ObjectType::PIPELINE, raw, label)
-            };
-        }
+        let raw = unsafe {
+            self.create_graphics_pipeline(
+                desc.label,
+                desc.layout,
+                &stages,
+                Some((&vk_vertex_input, &vk_input_assembly)),
+                &desc.primitive,
+                desc.depth_stencil.as_ref(),
+                &desc.multisample,
+                desc.color_targets,
+                desc.multiview,
+                desc.cache,
+            )
+        }?;
 
         if let Some(raw_module) = compiled_vs.temp_raw_module {
             unsafe { self.shared.raw.destroy_shader_module(raw_module, None) };
@@ -1871,6 +1971,51 @@ impl crate::Device<super::Api> for super::Device {
         unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
     }
 
+    unsafe fn create_mesh_pipeline(
+        &self,
+        desc: &crate::MeshPipelineDescriptor<super::Api>,
+    ) -> Result<super::RenderPipeline, crate::PipelineError> {
+        let mut stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+        let mut compiled_stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
+
+        let programmable_stages = [
+            (desc.task_stage.as_ref(), naga::ShaderStage::Task),
+            (Some(&desc.mesh_stage), naga::ShaderStage::Mesh),
+            (desc.fragment_stage.as_ref(), naga::ShaderStage::Fragment),
+        ];
+        for (stage, naga_stage) in programmable_stages {
+            if let Some(stage) = stage {
+                let compiled =
+                    self.compile_stage(stage, naga_stage, &desc.layout.binding_arrays, desc.cache)?;
+                stages.push(compiled.create_info);
+                compiled_stages.push(compiled);
+            }
+        }
+
+        let raw = unsafe {
+            self.create_graphics_pipeline(
+                desc.label,
+                desc.layout,
+                &stages,
+                None,
+                &desc.primitive,
+                desc.depth_stencil.as_ref(),
+                &desc.multisample,
+                desc.color_targets,
+                desc.multiview,
+                desc.cache,
+            )
+        }?;
+
+        for compiled in compiled_stages {
+            if let Some(raw_module) = compiled.temp_raw_module {
+                unsafe { self.shared.raw.destroy_shader_module(raw_module, None) };
+            }
+        }
+
+        Ok(super::RenderPipeline { raw })
+    }
+
     unsafe fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<super::Api>,
@@ -1879,6 +2024,7 @@ impl crate::Device<super::Api> for super::Device {
             &desc.stage,
             naga::ShaderStage::Compute,
             &desc.layout.binding_arrays,
+            desc.cache,
         )?;
 
         let vk_infos = [{
@@ -1893,7 +2039,12 @@ impl crate::Device<super::Api> for super::Device {
             unsafe {
                 self.shared
                     .raw
-                    .create_compute_pipelines(vk::PipelineCache::null(), &vk_infos, None)
+                    .create_compute_pipelines(
+                        desc.cache
+                            .map_or(vk::PipelineCache::null(), |cache| cache.raw),
+                        &vk_infos,
+                        None,
+                    )
                     .map_err(|(_, e)| crate::DeviceError::from(e))
             }?
         };
@@ -1916,6 +2067,68 @@ impl crate::Device<super::Api> for super::Device {
         unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
     }
 
+    unsafe fn create_pipeline_cache(
+        &self,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
+        // The data is the `VkPipelineCache` contents followed by the SPIR-V
+        // entries, see `pipeline_cache_get_data`.
+        let mut data = desc.data.unwrap_or_default();
+        let raw_data = crate::auxil::read_cache_bytes(&mut data).unwrap_or_default();
+        let mut spirv = rustc_hash::FxHashMap::default();
+        while let Some(key) = crate::auxil::read_cache_key(&mut data) {
+            let Some(bytes) = crate::auxil::read_cache_bytes(&mut data) else {
+                break;
+            };
+            let words = bytes
+                .chunks_exact(4)
+                .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+                .collect();
+            spirv.insert(key, words);
+        }
+        if !data.is_empty() {
+            log::warn!("Ignoring malformed pipeline cache data");
+        }
+
+        let info = vk::PipelineCacheCreateInfo::builder().initial_data(raw_data);
+        let raw = {
+            profiling::scope!("vkCreatePipelineCache");
+            unsafe { self.shared.raw.create_pipeline_cache(&info, None) }
+                .map_err(crate::DeviceError::from)?
+        };
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::PIPELINE_CACHE, raw, label)
+            };
+        }
+
+        Ok(super::PipelineCache {
+            raw,
+            spirv: Mutex::new(spirv),
+        })
+    }
+    unsafe fn destroy_pipeline_cache(&self, cache: super::PipelineCache) {
+        unsafe { self.shared.raw.destroy_pipeline_cache(cache.raw, None) }
+    }
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        let raw_data = unsafe { self.shared.raw.get_pipeline_cache_data(cache.raw) }.ok()?;
+        let mut data = Vec::with_capacity(raw_data.len() + 8);
+        crate::auxil::write_cache_bytes(&mut data, &raw_data);
+        for (key, words) in cache.spirv.lock().iter() {
+            let bytes = words
+                .iter()
+                .flat_map(|word| word.to_le_bytes())
+                .collect::<Vec<_>>();
+            crate::auxil::write_cache_key(&mut data, key);
+            crate::auxil::write_cache_bytes(&mut data, &bytes);
+        }
+        Some(data)
+    }
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]> {
+        Some(self.shared.pipeline_cache_uuid)
+    }
+
     unsafe fn create_query_set(
         &self,
         desc: &wgt::QuerySetDescriptor<crate::Label>,
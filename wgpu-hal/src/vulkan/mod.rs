@@ -80,6 +80,7 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type PipelineCache = PipelineCache;
 }
 
 struct DebugUtils {
@@ -195,6 +196,7 @@ struct DeviceExtensionFunctions {
     draw_indirect_count: Option<khr::DrawIndirectCount>,
     timeline_semaphore: Option<ExtensionFn<khr::TimelineSemaphore>>,
     ray_tracing: Option<RayTracingDeviceExtensionFunctions>,
+    mesh_shading: Option<ext::MeshShader>,
 }
 
 struct RayTracingDeviceExtensionFunctions {
@@ -327,6 +329,7 @@ struct DeviceShared {
     enabled_extensions: Vec<&'static CStr>,
     extension_fns: DeviceExtensionFunctions,
     vendor_id: u32,
+    pipeline_cache_uuid: [u8; 16],
     timestamp_period: f32,
     private_caps: PrivateCapabilities,
     workarounds: Workarounds,
@@ -488,6 +491,8 @@ pub enum ShaderModule {
     Intermediate {
         naga_shader: crate::NagaShader,
         runtime_checks: bool,
+        /// Hash of the source `naga_shader` was parsed from, if it was given.
+        source_hash: Option<crate::auxil::CacheKey>,
     },
 }
 
@@ -496,6 +501,15 @@ pub struct RenderPipeline {
     raw: vk::Pipeline,
 }
 
+#[derive(Debug)]
+pub struct PipelineCache {
+    raw: vk::PipelineCache,
+    /// SPIR-V that naga generated for individual pipeline stages of
+    /// [`ShaderModule::Intermediate`] modules, keyed by a hash of their source
+    /// and the options it was translated with.
+    spirv: Mutex<rustc_hash::FxHashMap<crate::auxil::CacheKey, Vec<u32>>>,
+}
+
 #[derive(Debug)]
 pub struct ComputePipeline {
     raw: vk::Pipeline,
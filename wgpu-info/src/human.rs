@@ -147,12 +147,21 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
         min_uniform_buffer_offset_alignment,
         min_storage_buffer_offset_alignment,
         max_inter_stage_shader_components,
+        max_color_attachment_bytes_per_sample,
         max_compute_workgroup_storage_size,
         max_compute_invocations_per_workgroup,
         max_compute_workgroup_size_x,
         max_compute_workgroup_size_y,
         max_compute_workgroup_size_z,
         max_compute_workgroups_per_dimension,
+        max_task_workgroups_per_dimension,
+        max_task_workgroup_total_count,
+        max_task_invocations_per_workgroup,
+        max_mesh_workgroups_per_dimension,
+        max_mesh_workgroup_total_count,
+        max_mesh_invocations_per_workgroup,
+        max_mesh_output_vertices,
+        max_mesh_output_primitives,
         max_non_sampler_bindings,
     } = limits;
     writeln!(output, "\t\t                        Max Texture Dimension 1d: {max_texture_dimension_1d}")?;
@@ -178,12 +187,21 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
     writeln!(output, "\t\t             Min Uniform Buffer Offset Alignment: {min_uniform_buffer_offset_alignment}")?;
     writeln!(output, "\t\t             Min Storage Buffer Offset Alignment: {min_storage_buffer_offset_alignment}")?;
     writeln!(output, "\t\t                Max Inter-Stage Shader Component: {max_inter_stage_shader_components}")?;
+    writeln!(output, "\t\t           Max Color Attachment Bytes Per Sample: {max_color_attachment_bytes_per_sample}")?;
     writeln!(output, "\t\t              Max Compute Workgroup Storage Size: {max_compute_workgroup_storage_size}")?;
     writeln!(output, "\t\t           Max Compute Invocations Per Workgroup: {max_compute_invocations_per_workgroup}")?;
     writeln!(output, "\t\t                    Max Compute Workgroup Size X: {max_compute_workgroup_size_x}")?;
     writeln!(output, "\t\t                    Max Compute Workgroup Size Y: {max_compute_workgroup_size_y}")?;
     writeln!(output, "\t\t                    Max Compute Workgroup Size Z: {max_compute_workgroup_size_z}")?;
     writeln!(output, "\t\t            Max Compute Workgroups Per Dimension: {max_compute_workgroups_per_dimension}")?;
+    writeln!(output, "\t\t               Max Task Workgroups Per Dimension: {max_task_workgroups_per_dimension}")?;
+    writeln!(output, "\t\t                  Max Task Workgroup Total Count: {max_task_workgroup_total_count}")?;
+    writeln!(output, "\t\t              Max Task Invocations Per Workgroup: {max_task_invocations_per_workgroup}")?;
+    writeln!(output, "\t\t               Max Mesh Workgroups Per Dimension: {max_mesh_workgroups_per_dimension}")?;
+    writeln!(output, "\t\t                  Max Mesh Workgroup Total Count: {max_mesh_workgroup_total_count}")?;
+    writeln!(output, "\t\t              Max Mesh Invocations Per Workgroup: {max_mesh_invocations_per_workgroup}")?;
+    writeln!(output, "\t\t                        Max Mesh Output Vertices: {max_mesh_output_vertices}")?;
+    writeln!(output, "\t\t                      Max Mesh Output Primitives: {max_mesh_output_primitives}")?;
 
     // This one reflects more of a wgpu implementation limitations than a hardware limit
     // so don't show it here.
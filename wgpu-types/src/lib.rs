@@ -133,7 +133,10 @@ impl Backend {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum PowerPreference {
     #[default]
     /// Power usage is not considered when choosing an adapter.
@@ -798,7 +801,15 @@ bitflags::bitflags! {
         /// This is a native-only feature.
         const RAY_TRACING_ACCELERATION_STRUCTURE = 1 << 56;
 
-        // 57 available
+        /// Allows for the creation of mesh pipelines, made of an optional task shader and a
+        /// mesh shader, and drawing with them using `draw_mesh_tasks` and its indirect
+        /// variants.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with VK_EXT_mesh_shader)
+        ///
+        /// This is a native only feature.
+        const MESH_SHADER = 1 << 57;
 
         // Shader:
 
@@ -900,6 +911,24 @@ bitflags::bitflags! {
         /// This mainly applies to a Vulkan driver's compliance version. If the major compliance version
         /// is `0`, then the driver is ignored. This flag allows that driver to be enabled for testing.
         const ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER = 1 << 3;
+        /// Validate the arguments of indirect draws and dispatches on the GPU.
+        ///
+        /// Before each pass that issues `draw_indirect`, `draw_indexed_indirect`,
+        /// `multi_draw_*indirect` or `dispatch_workgroups_indirect`, wgpu runs a small
+        /// compute pre-pass that checks the arguments against the bound vertex, index and
+        /// instance ranges and against `max_compute_workgroups_per_dimension`. Calls with
+        /// invalid arguments are turned into empty draws and dispatches, and are reported as
+        /// validation errors once the GPU has finished the submission.
+        ///
+        /// This has a cost for every pass using indirect calls and is meant for running
+        /// untrusted or unproven content. Calls whose arguments can't be validated are
+        /// rejected with a validation error instead: indirect draws in render bundles,
+        /// `multi_draw_*indirect_count`, `draw_mesh_tasks_indirect` and its multi-draw
+        /// variants, indirect buffer offsets that aren't a multiple of 4, and dispatches
+        /// reading a buffer written earlier in the same compute pass.
+        /// Device creation fails if the device can't run the pre-pass, or if wgpu-core was
+        /// built without its `wgsl` feature.
+        const VALIDATION_INDIRECT_CALL = 1 << 4;
     }
 }
 
@@ -955,6 +984,9 @@ impl InstanceFlags {
         if let Some(bit) = env("WGPU_ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER") {
             self.set(Self::ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER, bit);
         }
+        if let Some(bit) = env("WGPU_VALIDATION_INDIRECT_CALL") {
+            self.set(Self::VALIDATION_INDIRECT_CALL, bit);
+        }
 
         self
     }
@@ -1063,6 +1095,10 @@ pub struct Limits {
     /// inter-stage communication (vertex outputs to fragment inputs). Defaults to 60.
     /// Higher is "better".
     pub max_inter_stage_shader_components: u32,
+    /// The maximum number of bytes a render pipeline's color targets may take up in a single
+    /// sample, as computed with [`TextureFormat::target_pixel_byte_cost`] and
+    /// [`TextureFormat::target_component_alignment`]. Defaults to 32. Higher is "better".
+    pub max_color_attachment_bytes_per_sample: u32,
     /// Maximum number of bytes used for workgroup memory in a compute entry point. Defaults to
     /// 16352. Higher is "better".
     pub max_compute_workgroup_storage_size: u32,
@@ -1081,6 +1117,32 @@ pub struct Limits {
     /// The maximum value for each dimension of a `ComputePass::dispatch(x, y, z)` operation.
     /// Defaults to 65535. Higher is "better".
     pub max_compute_workgroups_per_dimension: u32,
+    /// The maximum value for each dimension of a `RenderPass::draw_mesh_tasks(x, y, z)`
+    /// operation with a pipeline that has a task shader. Defaults to 0. Higher is "better".
+    /// Requesting more than 0 during device creation requires [`Features::MESH_SHADER`] to be enabled.
+    pub max_task_workgroups_per_dimension: u32,
+    /// The maximum value of the product of the dimensions of a `RenderPass::draw_mesh_tasks`
+    /// operation with a pipeline that has a task shader. Defaults to 0. Higher is "better".
+    pub max_task_workgroup_total_count: u32,
+    /// Maximum value of the product of the `workgroup_size` dimensions for a task entry-point.
+    /// Each dimension is bounded by it as well. Defaults to 0. Higher is "better".
+    pub max_task_invocations_per_workgroup: u32,
+    /// The maximum value for each dimension of a `RenderPass::draw_mesh_tasks(x, y, z)`
+    /// operation with a pipeline that has no task shader, or of the mesh shader workgroups
+    /// launched by a task shader. Defaults to 0. Higher is "better".
+    pub max_mesh_workgroups_per_dimension: u32,
+    /// The maximum value of the product of the dimensions of the mesh shader workgroups
+    /// launched by a draw or a task shader. Defaults to 0. Higher is "better".
+    pub max_mesh_workgroup_total_count: u32,
+    /// Maximum value of the product of the `workgroup_size` dimensions for a mesh entry-point.
+    /// Each dimension is bounded by it as well. Defaults to 0. Higher is "better".
+    pub max_mesh_invocations_per_workgroup: u32,
+    /// Maximum number of vertices a mesh entry-point may declare as its output.
+    /// Defaults to 0. Higher is "better".
+    pub max_mesh_output_vertices: u32,
+    /// Maximum number of primitives a mesh entry-point may declare as its output.
+    /// Defaults to 0. Higher is "better".
+    pub max_mesh_output_primitives: u32,
     /// Amount of storage available for push constants in bytes. Defaults to 0. Higher is "better".
     /// Requesting more than 0 during device creation requires [`Features::PUSH_CONSTANTS`] to be enabled.
     ///
@@ -1124,12 +1186,21 @@ impl Default for Limits {
             min_uniform_buffer_offset_alignment: 256,
             min_storage_buffer_offset_alignment: 256,
             max_inter_stage_shader_components: 60,
+            max_color_attachment_bytes_per_sample: 32,
             max_compute_workgroup_storage_size: 16384,
             max_compute_invocations_per_workgroup: 256,
             max_compute_workgroup_size_x: 256,
             max_compute_workgroup_size_y: 256,
             max_compute_workgroup_size_z: 64,
             max_compute_workgroups_per_dimension: 65535,
+            max_task_workgroups_per_dimension: 0,
+            max_task_workgroup_total_count: 0,
+            max_task_invocations_per_workgroup: 0,
+            max_mesh_workgroups_per_dimension: 0,
+            max_mesh_workgroup_total_count: 0,
+            max_mesh_invocations_per_workgroup: 0,
+            max_mesh_output_vertices: 0,
+            max_mesh_output_primitives: 0,
             max_push_constant_size: 0,
             max_non_sampler_bindings: 1_000_000,
         }
@@ -1165,12 +1236,21 @@ impl Limits {
     ///     min_uniform_buffer_offset_alignment: 256,
     ///     min_storage_buffer_offset_alignment: 256,
     ///     max_inter_stage_shader_components: 60,
+    ///     max_color_attachment_bytes_per_sample: 32,
     ///     max_compute_workgroup_storage_size: 16352,
     ///     max_compute_invocations_per_workgroup: 256,
     ///     max_compute_workgroup_size_x: 256,
     ///     max_compute_workgroup_size_y: 256,
     ///     max_compute_workgroup_size_z: 64,
     ///     max_compute_workgroups_per_dimension: 65535,
+    ///     max_task_workgroups_per_dimension: 0,
+    ///     max_task_workgroup_total_count: 0,
+    ///     max_task_invocations_per_workgroup: 0,
+    ///     max_mesh_workgroups_per_dimension: 0,
+    ///     max_mesh_workgroup_total_count: 0,
+    ///     max_mesh_invocations_per_workgroup: 0,
+    ///     max_mesh_output_vertices: 0,
+    ///     max_mesh_output_primitives: 0,
     ///     max_buffer_size: 256 << 20, // (256 MiB)
     ///     max_non_sampler_bindings: 1_000_000,
     /// });
@@ -1199,12 +1279,21 @@ impl Limits {
             min_uniform_buffer_offset_alignment: 256,
             min_storage_buffer_offset_alignment: 256,
             max_inter_stage_shader_components: 60,
+            max_color_attachment_bytes_per_sample: 32,
             max_compute_workgroup_storage_size: 16352,
             max_compute_invocations_per_workgroup: 256,
             max_compute_workgroup_size_x: 256,
             max_compute_workgroup_size_y: 256,
             max_compute_workgroup_size_z: 64,
             max_compute_workgroups_per_dimension: 65535,
+            max_task_workgroups_per_dimension: 0,
+            max_task_workgroup_total_count: 0,
+            max_task_invocations_per_workgroup: 0,
+            max_mesh_workgroups_per_dimension: 0,
+            max_mesh_workgroup_total_count: 0,
+            max_mesh_invocations_per_workgroup: 0,
+            max_mesh_output_vertices: 0,
+            max_mesh_output_primitives: 0,
             max_buffer_size: 256 << 20,
             max_non_sampler_bindings: 1_000_000,
         }
@@ -1239,12 +1328,21 @@ impl Limits {
     ///     min_uniform_buffer_offset_alignment: 256,
     ///     min_storage_buffer_offset_alignment: 256,
     ///     max_inter_stage_shader_components: 31,
+    ///     max_color_attachment_bytes_per_sample: 32,
     ///     max_compute_workgroup_storage_size: 0, // +
     ///     max_compute_invocations_per_workgroup: 0, // +
     ///     max_compute_workgroup_size_x: 0, // +
     ///     max_compute_workgroup_size_y: 0, // +
     ///     max_compute_workgroup_size_z: 0, // +
     ///     max_compute_workgroups_per_dimension: 0, // +
+    ///     max_task_workgroups_per_dimension: 0,
+    ///     max_task_workgroup_total_count: 0,
+    ///     max_task_invocations_per_workgroup: 0,
+    ///     max_mesh_workgroups_per_dimension: 0,
+    ///     max_mesh_workgroup_total_count: 0,
+    ///     max_mesh_invocations_per_workgroup: 0,
+    ///     max_mesh_output_vertices: 0,
+    ///     max_mesh_output_primitives: 0,
     ///     max_buffer_size: 256 << 20, // (256 MiB),
     ///     max_non_sampler_bindings: 1_000_000,
     /// });
@@ -1359,12 +1457,21 @@ impl Limits {
         compare!(min_uniform_buffer_offset_alignment, Greater);
         compare!(min_storage_buffer_offset_alignment, Greater);
         compare!(max_inter_stage_shader_components, Less);
+        compare!(max_color_attachment_bytes_per_sample, Less);
         compare!(max_compute_workgroup_storage_size, Less);
         compare!(max_compute_invocations_per_workgroup, Less);
         compare!(max_compute_workgroup_size_x, Less);
         compare!(max_compute_workgroup_size_y, Less);
         compare!(max_compute_workgroup_size_z, Less);
         compare!(max_compute_workgroups_per_dimension, Less);
+        compare!(max_task_workgroups_per_dimension, Less);
+        compare!(max_task_workgroup_total_count, Less);
+        compare!(max_task_invocations_per_workgroup, Less);
+        compare!(max_mesh_workgroups_per_dimension, Less);
+        compare!(max_mesh_workgroup_total_count, Less);
+        compare!(max_mesh_invocations_per_workgroup, Less);
+        compare!(max_mesh_output_vertices, Less);
+        compare!(max_mesh_output_primitives, Less);
         compare!(max_buffer_size, Less);
         compare!(max_non_sampler_bindings, Less);
     }
@@ -1719,6 +1826,10 @@ bitflags::bitflags! {
         const FRAGMENT = 1 << 1;
         /// Binding is visible from the compute shader of a compute pipeline.
         const COMPUTE = 1 << 2;
+        /// Binding is visible from the task shader of a mesh pipeline.
+        const TASK = 1 << 3;
+        /// Binding is visible from the mesh shader of a mesh pipeline.
+        const MESH = 1 << 4;
         /// Binding is visible from the vertex and fragment shaders of a render pipeline.
         const VERTEX_FRAGMENT = Self::VERTEX.bits() | Self::FRAGMENT.bits();
     }
@@ -1736,23 +1847,26 @@ impl_bitflags!(ShaderStages);
 #[cfg_attr(feature = "replay", derive(Deserialize))]
 pub enum TextureViewDimension {
     /// A one dimensional texture. `texture_1d` in WGSL and `texture1D` in GLSL.
-    #[cfg_attr(feature = "serde", serde(rename = "1d"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "1d"))]
     D1,
     /// A two dimensional texture. `texture_2d` in WGSL and `texture2D` in GLSL.
-    #[cfg_attr(feature = "serde", serde(rename = "2d"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "2d"))]
     #[default]
     D2,
     /// A two dimensional array texture. `texture_2d_array` in WGSL and `texture2DArray` in GLSL.
-    #[cfg_attr(feature = "serde", serde(rename = "2d-array"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "2d-array"))]
     D2Array,
     /// A cubemap texture. `texture_cube` in WGSL and `textureCube` in GLSL.
-    #[cfg_attr(feature = "serde", serde(rename = "cube"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "cube"))]
     Cube,
     /// A cubemap array texture. `texture_cube_array` in WGSL and `textureCubeArray` in GLSL.
-    #[cfg_attr(feature = "serde", serde(rename = "cube-array"))]
+    #[cfg_attr(
+        any(feature = "trace", feature = "replay"),
+        serde(rename = "cube-array")
+    )]
     CubeArray,
     /// A three dimensional texture. `texture_3d` in WGSL and `texture3D` in GLSL.
-    #[cfg_attr(feature = "serde", serde(rename = "3d"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "3d"))]
     D3,
 }
 
@@ -1779,7 +1893,10 @@ impl TextureViewDimension {
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum BlendFactor {
     /// 0.0
     Zero = 0,
@@ -1842,7 +1959,10 @@ impl BlendFactor {
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum BlendOperation {
     /// Src + Dst
     #[default]
@@ -1865,7 +1985,10 @@ pub enum BlendOperation {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct BlendComponent {
     /// Multiplier for the source, which is produced by the fragment shader.
     pub src_factor: BlendFactor,
@@ -1921,7 +2044,10 @@ impl Default for BlendComponent {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct BlendState {
     /// Color equation.
     pub color: BlendComponent,
@@ -1961,7 +2087,10 @@ impl BlendState {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct ColorTargetState {
     /// The [`TextureFormat`] of the image that this pipeline will render to. Must match the format
     /// of the corresponding color attachment in [`CommandEncoder::begin_render_pass`][CEbrp]
@@ -1969,10 +2098,10 @@ pub struct ColorTargetState {
     /// [CEbrp]: ../wgpu/struct.CommandEncoder.html#method.begin_render_pass
     pub format: TextureFormat,
     /// The blending that is used for this pipeline.
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
     pub blend: Option<BlendState>,
     /// Mask which enables/disables writes to different color/alpha channel.
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
     pub write_mask: ColorWrites,
 }
 
@@ -1994,7 +2123,10 @@ impl From<TextureFormat> for ColorTargetState {
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum PrimitiveTopology {
     /// Vertex data is a list of points. Each vertex is a new point.
     PointList = 0,
@@ -2035,7 +2167,10 @@ impl PrimitiveTopology {
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum FrontFace {
     /// Triangles with vertices in counter clockwise order are considered the front face.
     ///
@@ -2057,7 +2192,10 @@ pub enum FrontFace {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum Face {
     /// Front face
     Front = 0,
@@ -2070,7 +2208,10 @@ pub enum Face {
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum PolygonMode {
     /// Polygons are filled
     #[default]
@@ -2089,31 +2230,34 @@ pub enum PolygonMode {
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct PrimitiveState {
     /// The primitive topology used to interpret vertices.
     pub topology: PrimitiveTopology,
     /// When drawing strip topologies with indices, this is the required format for the index buffer.
     /// This has no effect on non-indexed or non-strip draws.
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
     pub strip_index_format: Option<IndexFormat>,
     /// The face to consider the front for the purpose of culling and stencil operations.
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
     pub front_face: FrontFace,
     /// The face culling mode.
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
     pub cull_mode: Option<Face>,
     /// If set to true, the polygon depth is not clipped to 0-1 before rasterization.
     ///
     /// Enabling this requires `Features::DEPTH_CLIP_CONTROL` to be enabled.
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
     pub unclipped_depth: bool,
     /// Controls the way each polygon is rasterized. Can be either `Fill` (default), `Line` or `Point`
     ///
     /// Setting this to `Line` requires `Features::POLYGON_MODE_LINE` to be enabled.
     ///
     /// Setting this to `Point` requires `Features::POLYGON_MODE_POINT` to be enabled.
-    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
     pub polygon_mode: PolygonMode,
     /// If set to true, the primitives are rendered with conservative overestimation. I.e. any rastered pixel touched by it is filled.
     /// Only valid for PolygonMode::Fill!
@@ -2130,7 +2274,10 @@ pub struct PrimitiveState {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct MultisampleState {
     /// The number of samples calculated per pixel (for MSAA). For non-multisampled textures,
     /// this should be `1`
@@ -3520,6 +3667,135 @@ impl TextureFormat {
         }
     }
 
+    /// The number of bytes a pixel of this format takes up in a color attachment, if it can be
+    /// rendered to.
+    ///
+    /// Known as the [render target pixel byte cost](https://gpuweb.github.io/gpuweb/#render-target-pixel-byte-cost).
+    /// This is counted against [`Limits::max_color_attachment_bytes_per_sample`].
+    pub fn target_pixel_byte_cost(&self) -> Option<u32> {
+        match *self {
+            Self::R8Unorm | Self::R8Snorm | Self::R8Uint | Self::R8Sint => Some(1),
+
+            Self::Rg8Unorm | Self::Rg8Snorm | Self::Rg8Uint | Self::Rg8Sint => Some(2),
+            Self::R16Unorm | Self::R16Snorm | Self::R16Uint | Self::R16Sint | Self::R16Float => {
+                Some(2)
+            }
+
+            Self::Rgba8Uint | Self::Rgba8Sint => Some(4),
+            Self::Rg16Unorm
+            | Self::Rg16Snorm
+            | Self::Rg16Uint
+            | Self::Rg16Sint
+            | Self::Rg16Float => Some(4),
+            Self::R32Uint | Self::R32Sint | Self::R32Float => Some(4),
+
+            // Normalized 8-bit formats are stored at a higher precision for blending.
+            Self::Rgba8Unorm
+            | Self::Rgba8UnormSrgb
+            | Self::Rgba8Snorm
+            | Self::Bgra8Unorm
+            | Self::Bgra8UnormSrgb => Some(8),
+            Self::Rgb10a2Uint | Self::Rgb10a2Unorm | Self::Rg11b10Float => Some(8),
+            Self::Rgba16Unorm
+            | Self::Rgba16Snorm
+            | Self::Rgba16Uint
+            | Self::Rgba16Sint
+            | Self::Rgba16Float => Some(8),
+            Self::Rg32Uint | Self::Rg32Sint | Self::Rg32Float => Some(8),
+
+            Self::Rgba32Uint | Self::Rgba32Sint | Self::Rgba32Float => Some(16),
+
+            Self::Rgb9e5Ufloat
+            | Self::Stencil8
+            | Self::Depth16Unorm
+            | Self::Depth24Plus
+            | Self::Depth24PlusStencil8
+            | Self::Depth32Float
+            | Self::Depth32FloatStencil8
+            | Self::NV12
+            | Self::Bc1RgbaUnorm
+            | Self::Bc1RgbaUnormSrgb
+            | Self::Bc2RgbaUnorm
+            | Self::Bc2RgbaUnormSrgb
+            | Self::Bc3RgbaUnorm
+            | Self::Bc3RgbaUnormSrgb
+            | Self::Bc4RUnorm
+            | Self::Bc4RSnorm
+            | Self::Bc5RgUnorm
+            | Self::Bc5RgSnorm
+            | Self::Bc6hRgbUfloat
+            | Self::Bc6hRgbFloat
+            | Self::Bc7RgbaUnorm
+            | Self::Bc7RgbaUnormSrgb
+            | Self::Etc2Rgb8Unorm
+            | Self::Etc2Rgb8UnormSrgb
+            | Self::Etc2Rgb8A1Unorm
+            | Self::Etc2Rgb8A1UnormSrgb
+            | Self::Etc2Rgba8Unorm
+            | Self::Etc2Rgba8UnormSrgb
+            | Self::EacR11Unorm
+            | Self::EacR11Snorm
+            | Self::EacRg11Unorm
+            | Self::EacRg11Snorm
+            | Self::Astc { .. } => None,
+        }
+    }
+
+    /// The alignment in bytes of a pixel of this format in a color attachment, if it can be
+    /// rendered to.
+    ///
+    /// Known as the [render target component alignment](https://gpuweb.github.io/gpuweb/#render-target-component-alignment).
+    pub fn target_component_alignment(&self) -> Option<u32> {
+        match *self {
+            Self::R8Unorm
+            | Self::R8Snorm
+            | Self::R8Uint
+            | Self::R8Sint
+            | Self::Rg8Unorm
+            | Self::Rg8Snorm
+            | Self::Rg8Uint
+            | Self::Rg8Sint
+            | Self::Rgba8Unorm
+            | Self::Rgba8UnormSrgb
+            | Self::Rgba8Snorm
+            | Self::Rgba8Uint
+            | Self::Rgba8Sint
+            | Self::Bgra8Unorm
+            | Self::Bgra8UnormSrgb => Some(1),
+
+            Self::R16Unorm
+            | Self::R16Snorm
+            | Self::R16Uint
+            | Self::R16Sint
+            | Self::R16Float
+            | Self::Rg16Unorm
+            | Self::Rg16Snorm
+            | Self::Rg16Uint
+            | Self::Rg16Sint
+            | Self::Rg16Float
+            | Self::Rgba16Unorm
+            | Self::Rgba16Snorm
+            | Self::Rgba16Uint
+            | Self::Rgba16Sint
+            | Self::Rgba16Float => Some(2),
+
+            Self::R32Uint
+            | Self::R32Sint
+            | Self::R32Float
+            | Self::Rg32Uint
+            | Self::Rg32Sint
+            | Self::Rg32Float
+            | Self::Rgba32Uint
+            | Self::Rgba32Sint
+            | Self::Rgba32Float
+            | Self::Rgb10a2Uint
+            | Self::Rgb10a2Unorm
+            | Self::Rg11b10Float => Some(4),
+
+            _ => None,
+        }
+    }
+
     /// Returns the number of components this format has.
     pub fn components(&self) -> u8 {
         self.components_with_aspect(TextureAspect::All)
@@ -4527,7 +4803,10 @@ pub enum IndexFormat {
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum StencilOperation {
     /// Keep stencil value unchanged.
     #[default]
@@ -4561,7 +4840,10 @@ pub enum StencilOperation {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct StencilFaceState {
     /// Comparison function that determines if the fail_op or pass_op is used on the stencil buffer.
     pub compare: CompareFunction,
@@ -4612,7 +4894,10 @@ impl Default for StencilFaceState {
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum CompareFunction {
     /// Function never passes
     Never = 1,
@@ -4707,7 +4992,10 @@ impl CompareFunction {
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum VertexStepMode {
     /// Vertex data is advanced every vertex.
     #[default]
@@ -4729,7 +5017,10 @@ pub enum VertexStepMode {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct VertexAttribute {
     /// Format of the input
     pub format: VertexFormat,
@@ -4747,7 +5038,10 @@ pub struct VertexAttribute {
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "lowercase")
+)]
 pub enum VertexFormat {
     /// Two unsigned bytes (u8). `vec2<u32>` in shaders.
     Uint8x2 = 0,
@@ -5041,7 +5335,10 @@ pub enum PresentMode {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "lowercase")
+)]
 pub enum CompositeAlphaMode {
     /// Chooses either `Opaque` or `Inherit` automatically，depending on the
     /// `alpha_mode` that the current surface can support.
@@ -5329,13 +5626,13 @@ impl Color {
 #[cfg_attr(feature = "replay", derive(Deserialize))]
 pub enum TextureDimension {
     /// 1D texture
-    #[cfg_attr(feature = "serde", serde(rename = "1d"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "1d"))]
     D1,
     /// 2D texture
-    #[cfg_attr(feature = "serde", serde(rename = "2d"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "2d"))]
     D2,
     /// 3D texture
-    #[cfg_attr(feature = "serde", serde(rename = "3d"))]
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(rename = "3d"))]
     D3,
 }
 
@@ -5347,7 +5644,10 @@ pub enum TextureDimension {
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct Origin2d {
     ///
     pub x: u32,
@@ -5383,7 +5683,10 @@ impl std::fmt::Debug for Origin2d {
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct Origin3d {
     /// X position of the origin
     pub x: u32,
@@ -5426,14 +5729,20 @@ impl std::fmt::Debug for Origin3d {
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct Extent3d {
     /// Width of the extent
     pub width: u32,
     /// Height of the extent
     pub height: u32,
     /// The depth of the extent or the number of array layers
-    #[cfg_attr(feature = "serde", serde(default = "default_depth"))]
+    #[cfg_attr(
+        any(feature = "trace", feature = "replay"),
+        serde(default = "default_depth")
+    )]
     pub depth_or_array_layers: u32,
 }
 
@@ -5443,7 +5752,7 @@ impl std::fmt::Debug for Extent3d {
     }
 }
 
-#[cfg(feature = "serde")]
+#[cfg(any(feature = "trace", feature = "replay"))]
 fn default_depth() -> u32 {
     1
 }
@@ -5758,7 +6067,10 @@ impl<L, V> TextureDescriptor<L, V> {
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum TextureAspect {
     /// Depth, Stencil, and Color.
     #[default]
@@ -5783,7 +6095,10 @@ pub enum TextureAspect {
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum AddressMode {
     /// Clamp the value to the edge of the texture
     ///
@@ -5817,7 +6132,10 @@ pub enum AddressMode {
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum FilterMode {
     /// Nearest neighbor sampling.
     ///
@@ -6128,7 +6446,10 @@ impl Default for TextureSampleType {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum StorageTextureAccess {
     /// The texture can only be written in the shader and it:
     /// - may or may not be annotated with `write` (WGSL).
@@ -6191,7 +6512,10 @@ pub enum StorageTextureAccess {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "trace", derive(Serialize))]
 #[cfg_attr(feature = "replay", derive(Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum SamplerBindingType {
     /// The sampling result is produced based on more than a single color sample from a texture,
     /// e.g. when bilinear interpolation is enabled.
@@ -6535,7 +6859,10 @@ unsafe impl Sync for ExternalImageSource {}
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
 #[cfg_attr(feature = "replay", derive(serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "kebab-case")
+)]
 pub enum PredefinedColorSpace {
     /// sRGB color space
     Srgb,
@@ -6583,7 +6910,10 @@ impl<T: Copy> ImageCopyTextureTagged<T> {
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
 #[cfg_attr(feature = "replay", derive(serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(feature = "trace", feature = "replay"),
+    serde(rename_all = "camelCase")
+)]
 pub struct ImageSubresourceRange {
     /// Aspect of the texture. Color textures must be [`TextureAspect::All`][TAA].
     ///
@@ -6879,6 +7209,31 @@ impl DispatchIndirectArgs {
     }
 }
 
+/// Argument buffer layout for draw_mesh_tasks_indirect commands.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DrawMeshTasksIndirectArgs {
+    /// The number of task shader (or mesh shader, if there is no task shader) work groups in X
+    /// dimension.
+    pub group_count_x: u32,
+    /// The number of work groups in Y dimension.
+    pub group_count_y: u32,
+    /// The number of work groups in Z dimension.
+    pub group_count_z: u32,
+}
+
+impl DrawMeshTasksIndirectArgs {
+    /// Returns the bytes representation of the struct, ready to be written into a buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            std::mem::transmute(std::slice::from_raw_parts(
+                self as *const _ as *const u8,
+                std::mem::size_of::<Self>(),
+            ))
+        }
+    }
+}
+
 /// Describes how shader bound checks should be performed.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
@@ -7105,3 +7460,84 @@ pub enum DeviceLostReason {
     /// After Device::destroy
     Destroyed = 1,
 }
+
+/// The structured form of an error, for grouping errors by kind and by the
+/// resources involved.
+///
+/// Unlike the message, the code doesn't depend on how the error is worded or
+/// on the values it mentions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    any(feature = "serde", test),
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ErrorReport {
+    /// A stable code for the kind of error, like
+    /// `"CreateBufferError::UnalignedSize"`.
+    pub code: String,
+    /// The error's message.
+    pub message: String,
+    /// The resources involved in the error.
+    pub resources: Vec<ErrorResource>,
+    /// The error that caused this one, if any.
+    pub source: Option<Box<ErrorReport>>,
+}
+
+impl ErrorReport {
+    /// Iterates over this report and the reports of the errors that caused
+    /// it, outermost first.
+    pub fn chain(&self) -> impl Iterator<Item = &ErrorReport> {
+        std::iter::successors(Some(self), |report| report.source.as_deref())
+    }
+}
+
+/// A resource involved in an error, as listed by [`ErrorReport::resources`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    any(feature = "serde", test),
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ErrorResource {
+    /// The kind of resource, like `"buffer"` or `"render pipeline"`.
+    pub kind: String,
+    /// The id of the resource, if known, formatted as
+    /// `Id(index,epoch,backend)`.
+    pub id: Option<String>,
+    /// The label of the resource, which is empty if it has none.
+    pub label: String,
+}
+
+#[test]
+fn error_report_serialize() {
+    let report = ErrorReport {
+        code: "ContextError".to_string(),
+        message: "In Device::create_bind_group".to_string(),
+        resources: vec![ErrorResource {
+            kind: "bind group".to_string(),
+            id: None,
+            label: "lights".to_string(),
+        }],
+        source: Some(Box::new(ErrorReport {
+            code: "CreateBindGroupError::InvalidLayout".to_string(),
+            message: "Bind group layout is invalid".to_string(),
+            resources: Vec::new(),
+            source: None,
+        })),
+    };
+    assert_eq!(
+        serde_json::to_string(&report).unwrap(),
+        concat!(
+            r#"{"code":"ContextError","message":"In Device::create_bind_group","#,
+            r#""resources":[{"kind":"bind group","id":null,"label":"lights"}],"#,
+            r#""source":{"code":"CreateBindGroupError::InvalidLayout","#,
+            r#""message":"Bind group layout is invalid","resources":[],"source":null}}"#,
+        )
+    );
+    assert_eq!(
+        report.chain().map(|r| r.code.as_str()).collect::<Vec<_>>(),
+        ["ContextError", "CreateBindGroupError::InvalidLayout"]
+    );
+    let round_trip: ErrorReport =
+        serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+    assert_eq!(round_trip, report);
+}
@@ -60,6 +60,8 @@ impl crate::Context for ContextWasiWebgpu {
     type RenderPipelineData = webgpu::GpuRenderPipeline;
     type ComputePipelineId = ();
     type ComputePipelineData = webgpu::GpuComputePipeline;
+    type PipelineCacheId = ();
+    type PipelineCacheData = ();
     type CommandEncoderId = ();
     // Option so that command_encoder_finish can take ownership.
     type CommandEncoderData = Option<webgpu::GpuCommandEncoder>;
@@ -384,6 +386,16 @@ impl crate::Context for ContextWasiWebgpu {
         todo!()
     }
 
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> (Self::PipelineCacheId, Self::PipelineCacheData) {
+        // WebGPU has no pipeline caches.
+        ((), ())
+    }
+
     fn device_create_command_encoder(
         &self,
         _device: &Self::DeviceId,
@@ -555,6 +567,22 @@ impl crate::Context for ContextWasiWebgpu {
         // Dropped automatically
     }
 
+    fn pipeline_cache_get_data(
+        &self,
+        _cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn pipeline_cache_drop(
+        &self,
+        _cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) {
+        // Dropped automatically
+    }
+
     fn bind_group_drop(
         &self,
         _bind_group: &Self::BindGroupId,
@@ -1704,6 +1732,7 @@ fn map_wgt_limits(limits: webgpu::GpuSupportedLimits) -> wgt::Limits {
         min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment(),
         min_storage_buffer_offset_alignment: limits.min_storage_buffer_offset_alignment(),
         max_inter_stage_shader_components: 0, // Removed from spec,
+        max_color_attachment_bytes_per_sample: limits.max_color_attachment_bytes_per_sample(),
         max_compute_workgroup_storage_size: limits.max_compute_workgroup_storage_size(),
         max_compute_invocations_per_workgroup: limits.max_compute_invocations_per_workgroup(),
         max_compute_workgroup_size_x: limits.max_compute_workgroup_size_x(),
@@ -1712,6 +1741,14 @@ fn map_wgt_limits(limits: webgpu::GpuSupportedLimits) -> wgt::Limits {
         max_compute_workgroups_per_dimension: limits.max_compute_workgroups_per_dimension(),
         // The following are not part of WebGPU
         max_push_constant_size: wgt::Limits::default().max_push_constant_size,
+        max_task_workgroups_per_dimension: wgt::Limits::default().max_task_workgroups_per_dimension,
+        max_task_workgroup_total_count: wgt::Limits::default().max_task_workgroup_total_count,
+        max_task_invocations_per_workgroup: wgt::Limits::default().max_task_invocations_per_workgroup,
+        max_mesh_workgroups_per_dimension: wgt::Limits::default().max_mesh_workgroups_per_dimension,
+        max_mesh_workgroup_total_count: wgt::Limits::default().max_mesh_workgroup_total_count,
+        max_mesh_invocations_per_workgroup: wgt::Limits::default().max_mesh_invocations_per_workgroup,
+        max_mesh_output_vertices: wgt::Limits::default().max_mesh_output_vertices,
+        max_mesh_output_primitives: wgt::Limits::default().max_mesh_output_primitives,
         max_non_sampler_bindings: wgt::Limits::default().max_non_sampler_bindings,
     }
 }
@@ -2478,9 +2515,11 @@ impl<'a> From<&crate::BindGroupEntry<'a>> for webgpu::GpuBindGroupEntry<'a> {
 
 impl<'a> From<&crate::BindGroupLayoutEntry> for webgpu::GpuBindGroupLayoutEntry {
     fn from(entry: &crate::BindGroupLayoutEntry) -> Self {
+        // WebGPU has no task or mesh shaders.
+        let visibility = entry.visibility - (wgt::ShaderStages::TASK | wgt::ShaderStages::MESH);
         let mut mapped_entry = webgpu::GpuBindGroupLayoutEntry {
             binding: entry.binding,
-            visibility: entry.visibility.bits(),
+            visibility: visibility.bits(),
             buffer: None,
             sampler: None,
             texture: None,
@@ -91,12 +91,25 @@ impl crate::Error {
     fn from_js(js_error: js_sys::Object) -> Self {
         let source = Box::<dyn std::error::Error + Send + Sync>::from("<WebGPU Error>");
         if let Some(js_error) = js_error.dyn_ref::<web_sys::GpuValidationError>() {
+            let description = js_error.message();
             crate::Error::Validation {
                 source,
-                description: js_error.message(),
+                report: crate::ErrorReport {
+                    code: "GPUValidationError".to_string(),
+                    message: description.clone(),
+                    ..Default::default()
+                },
+                description,
             }
         } else if js_error.has_type::<web_sys::GpuOutOfMemoryError>() {
-            crate::Error::OutOfMemory { source }
+            crate::Error::OutOfMemory {
+                source,
+                report: crate::ErrorReport {
+                    code: "GPUOutOfMemoryError".to_string(),
+                    message: "Out of Memory".to_string(),
+                    ..Default::default()
+                },
+            }
         } else {
             panic!("Unexpected error");
         }
@@ -724,6 +737,7 @@ fn map_wgt_limits(limits: web_sys::GpuSupportedLimits) -> wgt::Limits {
         min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment(),
         min_storage_buffer_offset_alignment: limits.min_storage_buffer_offset_alignment(),
         max_inter_stage_shader_components: limits.max_inter_stage_shader_components(),
+        max_color_attachment_bytes_per_sample: limits.max_color_attachment_bytes_per_sample(),
         max_compute_workgroup_storage_size: limits.max_compute_workgroup_storage_size(),
         max_compute_invocations_per_workgroup: limits.max_compute_invocations_per_workgroup(),
         max_compute_workgroup_size_x: limits.max_compute_workgroup_size_x(),
@@ -732,6 +746,14 @@ fn map_wgt_limits(limits: web_sys::GpuSupportedLimits) -> wgt::Limits {
         max_compute_workgroups_per_dimension: limits.max_compute_workgroups_per_dimension(),
         // The following are not part of WebGPU
         max_push_constant_size: wgt::Limits::default().max_push_constant_size,
+        max_task_workgroups_per_dimension: wgt::Limits::default().max_task_workgroups_per_dimension,
+        max_task_workgroup_total_count: wgt::Limits::default().max_task_workgroup_total_count,
+        max_task_invocations_per_workgroup: wgt::Limits::default().max_task_invocations_per_workgroup,
+        max_mesh_workgroups_per_dimension: wgt::Limits::default().max_mesh_workgroups_per_dimension,
+        max_mesh_workgroup_total_count: wgt::Limits::default().max_mesh_workgroup_total_count,
+        max_mesh_invocations_per_workgroup: wgt::Limits::default().max_mesh_invocations_per_workgroup,
+        max_mesh_output_vertices: wgt::Limits::default().max_mesh_output_vertices,
+        max_mesh_output_primitives: wgt::Limits::default().max_mesh_output_primitives,
         max_non_sampler_bindings: wgt::Limits::default().max_non_sampler_bindings,
     }
 }
@@ -780,6 +802,7 @@ fn map_js_sys_limits(limits: &wgt::Limits) -> js_sys::Object {
         (maxVertexAttributes, max_vertex_attributes),
         (maxVertexBufferArrayStride, max_vertex_buffer_array_stride),
         (maxInterStageShaderComponents, max_inter_stage_shader_components),
+        (maxColorAttachmentBytesPerSample, max_color_attachment_bytes_per_sample),
         (maxComputeWorkgroupStorageSize, max_compute_workgroup_storage_size),
         (maxComputeInvocationsPerWorkgroup, max_compute_invocations_per_workgroup),
         (maxComputeWorkgroupSizeX, max_compute_workgroup_size_x),
@@ -1017,6 +1040,8 @@ impl crate::context::Context for ContextWebGpu {
     type RenderPipelineData = Sendable<web_sys::GpuRenderPipeline>;
     type ComputePipelineId = Identified<web_sys::GpuComputePipeline>;
     type ComputePipelineData = Sendable<web_sys::GpuComputePipeline>;
+    type PipelineCacheId = Identified<()>;
+    type PipelineCacheData = Sendable<()>;
     type CommandEncoderId = Identified<web_sys::GpuCommandEncoder>;
     type CommandEncoderData = Sendable<web_sys::GpuCommandEncoder>;
     type ComputePassId = Identified<web_sys::GpuComputePassEncoder>;
@@ -1509,8 +1534,11 @@ impl crate::context::Context for ContextWebGpu {
             .entries
             .iter()
             .map(|bind| {
+                // WebGPU has no task or mesh shaders.
+                let visibility =
+                    bind.visibility - (wgt::ShaderStages::TASK | wgt::ShaderStages::MESH);
                 let mut mapped_entry =
-                    web_sys::GpuBindGroupLayoutEntry::new(bind.binding, bind.visibility.bits());
+                    web_sys::GpuBindGroupLayoutEntry::new(bind.binding, visibility.bits());
 
                 match bind.ty {
                     wgt::BindingType::Buffer {
@@ -1897,6 +1925,16 @@ impl crate::context::Context for ContextWebGpu {
         create_identified(device_data.0.create_query_set(&mapped_desc))
     }
 
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> (Self::PipelineCacheId, Self::PipelineCacheData) {
+        // WebGPU has no pipeline caches.
+        create_identified(())
+    }
+
     fn device_create_command_encoder(
         &self,
         _device: &Self::DeviceId,
@@ -2130,6 +2168,22 @@ impl crate::context::Context for ContextWebGpu {
         // Dropped automatically
     }
 
+    fn pipeline_cache_get_data(
+        &self,
+        _cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn pipeline_cache_drop(
+        &self,
+        _cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) {
+        // Dropped automatically
+    }
+
     fn bind_group_drop(
         &self,
         _bind_group: &Self::BindGroupId,
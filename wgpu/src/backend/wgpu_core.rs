@@ -252,6 +252,7 @@ impl ContextWgpuCore {
                 source.downcast_ref::<wgc::device::DeviceError>()
             {
                 return sink.handle_error(crate::Error::OutOfMemory {
+                    report: wgc::error::report_any(self.global(), &error),
                     source: Box::new(error),
                 });
             }
@@ -261,6 +262,7 @@ impl ContextWgpuCore {
         // Otherwise, it is a validation error
         sink.handle_error(crate::Error::Validation {
             description: self.format_error(&error),
+            report: wgc::error::report_any(self.global(), &error),
             source: Box::new(error),
         });
     }
@@ -274,6 +276,20 @@ impl ContextWgpuCore {
         self.handle_error(sink_mutex, cause, "", None, string)
     }
 
+    /// Forward the indirect call validation failures found on the GPU to `sink_mutex`.
+    fn report_indirect_validation_errors(
+        &self,
+        device: wgc::id::DeviceId,
+        sink_mutex: &Mutex<ErrorSinkRaw>,
+        string: &'static str,
+    ) {
+        let errors =
+            wgc::gfx_select!(device => self.0.device_take_indirect_validation_errors(device));
+        for error in errors.into_iter().flatten() {
+            self.handle_error_nolabel(sink_mutex, error, string);
+        }
+    }
+
     #[track_caller]
     fn handle_error_fatal(
         &self,
@@ -473,6 +489,8 @@ impl crate::Context for ContextWgpuCore {
     type RenderPipelineData = ();
     type ComputePipelineId = wgc::id::ComputePipelineId;
     type ComputePipelineData = ();
+    type PipelineCacheId = wgc::id::PipelineCacheId;
+    type PipelineCacheData = ();
     type CommandEncoderId = wgc::id::CommandEncoderId;
     type CommandEncoderData = CommandEncoder;
     type ComputePassId = Unused;
@@ -834,6 +852,7 @@ impl crate::Context for ContextWgpuCore {
         let descriptor = wgc::pipeline::ShaderModuleDescriptor {
             label: desc.label.map(Borrowed),
             shader_bound_checks,
+            ..Default::default()
         };
         let source = match desc.source {
             #[cfg(feature = "spirv")]
@@ -896,6 +915,7 @@ impl crate::Context for ContextWgpuCore {
             // Doesn't matter the value since spirv shaders aren't mutated to include
             // runtime checks
             shader_bound_checks: unsafe { wgt::ShaderBoundChecks::unchecked() },
+            ..Default::default()
         };
         let (id, error) = wgc::gfx_select!(
             device => self.0.device_create_shader_module_spirv(*device, &descriptor, Borrowed(&desc.source), ())
@@ -1139,6 +1159,7 @@ impl crate::Context for ContextWgpuCore {
                 targets: Borrowed(frag.targets),
             }),
             multiview: desc.multiview,
+            cache: desc.cache.map(|c| c.id.into()),
         };
 
         let (id, error) = wgc::gfx_select!(device => self.0.device_create_render_pipeline(
@@ -1184,6 +1205,7 @@ impl crate::Context for ContextWgpuCore {
                 module: desc.module.id.into(),
                 entry_point: Borrowed(desc.entry_point),
             },
+            cache: desc.cache.map(|c| c.id.into()),
         };
 
         let (id, error) = wgc::gfx_select!(device => self.0.device_create_compute_pipeline(
@@ -1322,6 +1344,33 @@ impl crate::Context for ContextWgpuCore {
         }
         (id, ())
     }
+    unsafe fn device_create_pipeline_cache(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> (Self::PipelineCacheId, Self::PipelineCacheData) {
+        use wgc::pipeline as pipe;
+
+        let descriptor = pipe::PipelineCacheDescriptor {
+            label: desc.label.map(Borrowed),
+            data: desc.data.map(Borrowed),
+            fallback: desc.fallback,
+        };
+        let (id, error) = wgc::gfx_select!(
+            device => self.0.device_create_pipeline_cache(*device, &descriptor, ())
+        );
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_pipeline_cache",
+            );
+        }
+        (id, ())
+    }
     fn device_create_command_encoder(
         &self,
         device: &Self::DeviceId,
@@ -1408,11 +1457,11 @@ impl crate::Context for ContextWgpuCore {
     fn device_poll(
         &self,
         device: &Self::DeviceId,
-        _device_data: &Self::DeviceData,
+        device_data: &Self::DeviceData,
         maintain: crate::Maintain,
     ) -> wgt::MaintainResult {
         let maintain_inner = maintain.map_index(|i| *i.1.as_ref().downcast_ref().unwrap());
-        match wgc::gfx_select!(device => self.0.device_poll(
+        let result = match wgc::gfx_select!(device => self.0.device_poll(
             *device,
             maintain_inner
         )) {
@@ -1421,7 +1470,9 @@ impl crate::Context for ContextWgpuCore {
                 false => wgt::MaintainResult::Ok,
             },
             Err(err) => self.handle_error_fatal(err, "Device::poll"),
-        }
+        };
+        self.report_indirect_validation_errors(*device, &device_data.error_sink, "Device::poll");
+        result
     }
     fn device_on_uncaptured_error(
         &self,
@@ -1586,6 +1637,22 @@ impl crate::Context for ContextWgpuCore {
         wgc::gfx_select!(*query_set => self.0.query_set_drop(*query_set))
     }
 
+    fn pipeline_cache_get_data(
+        &self,
+        cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) -> Option<Vec<u8>> {
+        wgc::gfx_select!(*cache => self.0.pipeline_cache_get_data(*cache))
+    }
+
+    fn pipeline_cache_drop(
+        &self,
+        cache: &Self::PipelineCacheId,
+        _cache_data: &Self::PipelineCacheData,
+    ) {
+        wgc::gfx_select!(*cache => self.0.pipeline_cache_drop(*cache))
+    }
+
     fn bind_group_drop(
         &self,
         bind_group: &Self::BindGroupId,
@@ -2215,7 +2282,7 @@ impl crate::Context for ContextWgpuCore {
     fn queue_submit<I: Iterator<Item = (Self::CommandBufferId, Self::CommandBufferData)>>(
         &self,
         queue: &Self::QueueId,
-        _queue_data: &Self::QueueData,
+        queue_data: &Self::QueueData,
         command_buffers: I,
     ) -> (Self::SubmissionIndex, Self::SubmissionIndexData) {
         let temp_command_buffers = command_buffers
@@ -2227,6 +2294,7 @@ impl crate::Context for ContextWgpuCore {
             Ok(index) => index,
             Err(err) => self.handle_error_fatal(err, "Queue::submit"),
         };
+        self.report_indirect_validation_errors(*queue, &queue_data.error_sink, "Queue::submit");
 
         (Unused, index)
     }
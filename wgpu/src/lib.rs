@@ -96,17 +96,17 @@ pub use wgt::{
     BufferBindingType, BufferSize, BufferUsages, Color, ColorTargetState, ColorWrites,
     CommandBufferDescriptor, CompareFunction, CompositeAlphaMode, DepthBiasState,
     DepthStencilState, DeviceLostReason, DeviceType, DownlevelCapabilities, DownlevelFlags,
-    Dx12Compiler, DynamicOffset, Extent3d, Face, Features, FilterMode, FrontFace,
-    Gles3MinorVersion, ImageDataLayout, ImageSubresourceRange, IndexFormat, InstanceDescriptor,
-    InstanceFlags, Limits, MaintainResult, MultisampleState, Origin2d, Origin3d,
-    PipelineStatisticsTypes, PolygonMode, PowerPreference, PredefinedColorSpace, PresentMode,
-    PresentationTimestamp, PrimitiveState, PrimitiveTopology, PushConstantRange, QueryType,
-    RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor, ShaderLocation, ShaderModel,
-    ShaderStages, StencilFaceState, StencilOperation, StencilState, StorageTextureAccess,
-    SurfaceCapabilities, SurfaceStatus, TextureAspect, TextureDimension, TextureFormat,
-    TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType, TextureUsages,
-    TextureViewDimension, VertexAttribute, VertexFormat, VertexStepMode, WasmNotSend,
-    WasmNotSendSync, WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT,
+    Dx12Compiler, DynamicOffset, ErrorReport, ErrorResource, Extent3d, Face, Features, FilterMode,
+    FrontFace, Gles3MinorVersion, ImageDataLayout, ImageSubresourceRange, IndexFormat,
+    InstanceDescriptor, InstanceFlags, Limits, MaintainResult, MultisampleState, Origin2d,
+    Origin3d, PipelineStatisticsTypes, PolygonMode, PowerPreference, PredefinedColorSpace,
+    PresentMode, PresentationTimestamp, PrimitiveState, PrimitiveTopology, PushConstantRange,
+    QueryType, RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor, ShaderLocation,
+    ShaderModel, ShaderStages, StencilFaceState, StencilOperation, StencilState,
+    StorageTextureAccess, SurfaceCapabilities, SurfaceStatus, TextureAspect, TextureDimension,
+    TextureFormat, TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType,
+    TextureUsages, TextureViewDimension, VertexAttribute, VertexFormat, VertexStepMode,
+    WasmNotSend, WasmNotSendSync, WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT,
     MAP_ALIGNMENT, PUSH_CONSTANT_ALIGNMENT, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES,
     QUERY_SIZE, VERTEX_STRIDE_ALIGNMENT,
 };
@@ -842,6 +842,53 @@ impl ComputePipeline {
     }
 }
 
+/// Handle to a pipeline cache, which is used to accelerate
+/// creating [`RenderPipeline`]s and [`ComputePipeline`]s
+/// in later runs of the program.
+///
+/// It can be created with [`Device::create_pipeline_cache`], and passed to
+/// pipeline creation through [`RenderPipelineDescriptor::cache`] and
+/// [`ComputePipelineDescriptor::cache`]. Once pipelines have been created,
+/// its contents can be retrieved with [`PipelineCache::get_data`] and
+/// stored, for example on disk, to create a populated cache next time.
+///
+/// Backends without pipeline caches (currently everything except Vulkan and
+/// GLES with program binaries) accept a `PipelineCache`, but never return any
+/// data from it.
+///
+/// This type is unique to the Rust API of `wgpu`.
+#[derive(Debug)]
+pub struct PipelineCache {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(PipelineCache: Send, Sync);
+
+impl PipelineCache {
+    /// Get the data associated with this pipeline cache.
+    ///
+    /// The data includes a header identifying the version of `wgpu`, the adapter
+    /// and the driver that produced it, and [`Device::create_pipeline_cache`]
+    /// checks this header before using the data.
+    ///
+    /// Returns `None` if the backend doesn't support pipeline caches.
+    pub fn get_data(&self) -> Option<Vec<u8>> {
+        self.context
+            .pipeline_cache_get_data(&self.id, self.data.as_ref())
+    }
+}
+
+impl Drop for PipelineCache {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context
+                .pipeline_cache_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
 /// Handle to a command buffer on the GPU.
 ///
 /// A `CommandBuffer` represents a complete sequence of commands that may be submitted to a command
@@ -1564,6 +1611,8 @@ pub struct RenderPipelineDescriptor<'a> {
     /// If the pipeline will be used with a multiview render pass, this indicates how many array
     /// layers the attachments will have.
     pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<&'a PipelineCache>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPipelineDescriptor<'_>: Send, Sync);
@@ -1622,10 +1671,33 @@ pub struct ComputePipelineDescriptor<'a> {
     /// The name of the entry point in the compiled shader. There must be a function with this name
     /// and no return value in the shader.
     pub entry_point: &'a str,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<&'a PipelineCache>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(ComputePipelineDescriptor<'_>: Send, Sync);
 
+/// Describes a [`PipelineCache`].
+///
+/// For use with [`Device::create_pipeline_cache`].
+///
+/// This type is unique to the Rust API of `wgpu`.
+#[derive(Clone, Debug)]
+pub struct PipelineCacheDescriptor<'a> {
+    /// Debug label of the pipeline cache. This will show up in graphics debuggers for easy identification.
+    pub label: Label<'a>,
+    /// Data returned by [`PipelineCache::get_data`] in a previous run, used to populate the
+    /// new cache.
+    pub data: Option<&'a [u8]>,
+    /// If `data` was produced by a different version of `wgpu`, adapter or driver, or was
+    /// corrupted, create an empty cache instead of returning an error.
+    ///
+    /// Data is routinely invalidated by driver updates, so this should usually be `true`.
+    pub fallback: bool,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(PipelineCacheDescriptor<'_>: Send, Sync);
+
 pub use wgt::ImageCopyBuffer as ImageCopyBufferBase;
 /// View of a buffer which can be used to copy to/from a texture.
 ///
@@ -2513,6 +2585,36 @@ impl Device {
         }
     }
 
+    /// Creates a [`PipelineCache`] with initial data from a previous run.
+    ///
+    /// If `desc.data` can't be used and `desc.fallback` is `false`, an error is reported
+    /// through the device's error handling, and the returned cache is invalid.
+    ///
+    /// # Safety
+    ///
+    /// `desc.data` is checked against a header recording the `wgpu` version, adapter and
+    /// driver it came from, and a hash of its contents. Beyond that, it is passed to the
+    /// driver as-is, so it must come from a trusted source: a driver handed maliciously
+    /// crafted data can misbehave.
+    pub unsafe fn create_pipeline_cache(
+        &self,
+        desc: &PipelineCacheDescriptor<'_>,
+    ) -> PipelineCache {
+        let (id, data) = unsafe {
+            DynContext::device_create_pipeline_cache(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                desc,
+            )
+        };
+        PipelineCache {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &BufferDescriptor<'_>) -> Buffer {
         let mut map_context = MapContext::new(desc.size);
@@ -5135,6 +5237,8 @@ pub enum Error {
         #[cfg(not(send_sync))]
         #[cfg_attr(docsrs, doc(cfg(all())))]
         source: Box<dyn error::Error + 'static>,
+        /// Structured form of the error.
+        report: ErrorReport,
     },
     /// Validation error, signifying a bug in code or data
     Validation {
@@ -5148,6 +5252,8 @@ pub enum Error {
         source: Box<dyn error::Error + 'static>,
         /// Description of the validation error.
         description: String,
+        /// Structured form of the error.
+        report: ErrorReport,
     },
 }
 #[cfg(send_sync)]
@@ -5156,7 +5262,7 @@ static_assertions::assert_impl_all!(Error: Send);
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
-            Error::OutOfMemory { source } => Some(source.as_ref()),
+            Error::OutOfMemory { source, .. } => Some(source.as_ref()),
             Error::Validation { source, .. } => Some(source.as_ref()),
         }
     }
@@ -5171,6 +5277,24 @@ impl fmt::Display for Error {
     }
 }
 
+impl Error {
+    /// Returns the structured form of the error.
+    ///
+    /// This has a stable code for each error and its sources, and the
+    /// resources involved, for grouping errors without parsing messages.
+    pub fn report(&self) -> &ErrorReport {
+        match self {
+            Error::OutOfMemory { report, .. } | Error::Validation { report, .. } => report,
+        }
+    }
+
+    /// Returns the structured form of the error as JSON, for logging.
+    #[cfg(feature = "error-json")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self.report()).expect("Error reports always serialize")
+    }
+}
+
 use send_sync::*;
 
 mod send_sync {
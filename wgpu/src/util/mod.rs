@@ -7,6 +7,8 @@ mod belt;
 mod device;
 mod encoder;
 mod init;
+#[cfg(any(wgpu_core, naga))]
+mod reflect;
 
 use std::sync::Arc;
 use std::{
@@ -19,6 +21,8 @@ pub use belt::StagingBelt;
 pub use device::{BufferInitDescriptor, DeviceExt, TextureDataOrder};
 pub use encoder::RenderEncoder;
 pub use init::*;
+#[cfg(any(wgpu_core, naga))]
+pub use reflect::bind_group_layout_entries;
 pub use wgt::{math::*, DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs};
 
 /// Treat the given byte slice as a SPIR-V module.
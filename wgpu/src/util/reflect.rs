@@ -0,0 +1,224 @@
+use crate::naga::{self, reflect};
+use crate::{
+    BindGroupLayoutEntry, BindingType, BufferBindingType, BufferSize, SamplerBindingType,
+    ShaderStages, StorageTextureAccess, TextureFormat, TextureSampleType, TextureViewDimension,
+};
+
+/// Build the [`BindGroupLayoutEntry`]s for bind group `group` from the
+/// reflection of the entry points that will share the layout.
+///
+/// Each resource bound in `group` by any of `entry_points` gets an entry,
+/// visible to the stages of the entry points that use it. The entries are
+/// sorted by binding number. If several entry points use the same buffer,
+/// its minimum binding size is the largest any of them needs, and it is only
+/// read-only if all of them only read it.
+///
+/// Texture sample types are derived as filterable, buffers have no dynamic
+/// offsets, and external textures are expanded into the planes and parameter
+/// buffer they are lowered to. Edit the result to change these choices.
+///
+/// Use [`naga::reflect::reflect`] to get the reflection of a module's entry
+/// points.
+pub fn bind_group_layout_entries(
+    entry_points: &[reflect::EntryPoint],
+    group: u32,
+) -> Vec<BindGroupLayoutEntry> {
+    let mut entries: Vec<BindGroupLayoutEntry> = Vec::new();
+    for entry_point in entry_points {
+        let visibility = match entry_point.stage {
+            naga::ShaderStage::Vertex => ShaderStages::VERTEX,
+            naga::ShaderStage::Fragment => ShaderStages::FRAGMENT,
+            naga::ShaderStage::Compute => ShaderStages::COMPUTE,
+            naga::ShaderStage::Task => ShaderStages::TASK,
+            naga::ShaderStage::Mesh => ShaderStages::MESH,
+        };
+        for resource in entry_point.resources.iter() {
+            if resource.binding.group != group {
+                continue;
+            }
+            for (binding, ty) in binding_types(resource) {
+                let entry = BindGroupLayoutEntry {
+                    binding,
+                    visibility,
+                    ty,
+                    count: resource.count,
+                };
+                match entries.iter_mut().find(|e| e.binding == binding) {
+                    Some(existing) => merge(existing, &entry),
+                    None => entries.push(entry),
+                }
+            }
+        }
+    }
+    entries.sort_by_key(|entry| entry.binding);
+    entries
+}
+
+/// Combine `new`, the entry another entry point needs, into `existing`.
+fn merge(existing: &mut BindGroupLayoutEntry, new: &BindGroupLayoutEntry) {
+    existing.visibility |= new.visibility;
+    if let (
+        BindingType::Buffer {
+            ty,
+            min_binding_size,
+            ..
+        },
+        BindingType::Buffer {
+            ty: new_ty,
+            min_binding_size: new_min_binding_size,
+            ..
+        },
+    ) = (&mut existing.ty, new.ty)
+    {
+        if let (
+            BufferBindingType::Storage { read_only },
+            BufferBindingType::Storage {
+                read_only: new_read_only,
+            },
+        ) = (ty, new_ty)
+        {
+            *read_only &= new_read_only;
+        }
+        *min_binding_size = (*min_binding_size).max(new_min_binding_size);
+    }
+}
+
+/// Return the bindings and binding types `resource` occupies.
+fn binding_types(resource: &reflect::Resource) -> Vec<(u32, BindingType)> {
+    let binding = resource.binding.binding;
+    let ty = match resource.ty {
+        reflect::ResourceType::Buffer { space, min_size } => BindingType::Buffer {
+            ty: match space {
+                naga::AddressSpace::Storage { access } => BufferBindingType::Storage {
+                    read_only: !access.contains(naga::StorageAccess::STORE),
+                },
+                _ => BufferBindingType::Uniform,
+            },
+            has_dynamic_offset: false,
+            min_binding_size: BufferSize::new(min_size.into()),
+        },
+        reflect::ResourceType::Sampler { comparison } => BindingType::Sampler(if comparison {
+            SamplerBindingType::Comparison
+        } else {
+            SamplerBindingType::Filtering
+        }),
+        reflect::ResourceType::AccelerationStructure => BindingType::AccelerationStructure,
+        reflect::ResourceType::Image {
+            dim,
+            arrayed,
+            class,
+        } => {
+            let view_dimension = match dim {
+                naga::ImageDimension::D1 => TextureViewDimension::D1,
+                naga::ImageDimension::D2 if arrayed => TextureViewDimension::D2Array,
+                naga::ImageDimension::D2 => TextureViewDimension::D2,
+                naga::ImageDimension::D3 => TextureViewDimension::D3,
+                naga::ImageDimension::Cube if arrayed => TextureViewDimension::CubeArray,
+                naga::ImageDimension::Cube => TextureViewDimension::Cube,
+            };
+            match class {
+                naga::ImageClass::Sampled { kind, multi } => BindingType::Texture {
+                    sample_type: match kind {
+                        naga::ScalarKind::Sint => TextureSampleType::Sint,
+                        naga::ScalarKind::Uint => TextureSampleType::Uint,
+                        _ => TextureSampleType::Float { filterable: true },
+                    },
+                    view_dimension,
+                    multisampled: multi,
+                },
+                naga::ImageClass::Depth { multi } => BindingType::Texture {
+                    sample_type: TextureSampleType::Depth,
+                    view_dimension,
+                    multisampled: multi,
+                },
+                naga::ImageClass::Storage { format, access } => BindingType::StorageTexture {
+                    access: if !access.contains(naga::StorageAccess::STORE) {
+                        StorageTextureAccess::ReadOnly
+                    } else if !access.contains(naga::StorageAccess::LOAD) {
+                        StorageTextureAccess::WriteOnly
+                    } else {
+                        StorageTextureAccess::ReadWrite
+                    },
+                    format: map_storage_format(format),
+                    view_dimension,
+                },
+                naga::ImageClass::External => {
+                    let plane = BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    };
+                    let [plane1, plane2, params] = resource.binding.external_texture_bindings();
+                    return vec![
+                        (binding, plane),
+                        (plane1.binding, plane),
+                        (plane2.binding, plane),
+                        (
+                            params.binding,
+                            BindingType::Buffer {
+                                ty: BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                        ),
+                    ];
+                }
+            }
+        }
+    };
+    vec![(binding, ty)]
+}
+
+fn map_storage_format(format: naga::StorageFormat) -> TextureFormat {
+    use naga::StorageFormat as Sf;
+    use TextureFormat as Tf;
+
+    match format {
+        Sf::R8Unorm => Tf::R8Unorm,
+        Sf::R8Snorm => Tf::R8Snorm,
+        Sf::R8Uint => Tf::R8Uint,
+        Sf::R8Sint => Tf::R8Sint,
+
+        Sf::R16Uint => Tf::R16Uint,
+        Sf::R16Sint => Tf::R16Sint,
+        Sf::R16Float => Tf::R16Float,
+        Sf::Rg8Unorm => Tf::Rg8Unorm,
+        Sf::Rg8Snorm => Tf::Rg8Snorm,
+        Sf::Rg8Uint => Tf::Rg8Uint,
+        Sf::Rg8Sint => Tf::Rg8Sint,
+
+        Sf::R32Uint => Tf::R32Uint,
+        Sf::R32Sint => Tf::R32Sint,
+        Sf::R32Float => Tf::R32Float,
+        Sf::Rg16Uint => Tf::Rg16Uint,
+        Sf::Rg16Sint => Tf::Rg16Sint,
+        Sf::Rg16Float => Tf::Rg16Float,
+        Sf::Rgba8Unorm => Tf::Rgba8Unorm,
+        Sf::Rgba8Snorm => Tf::Rgba8Snorm,
+        Sf::Rgba8Uint => Tf::Rgba8Uint,
+        Sf::Rgba8Sint => Tf::Rgba8Sint,
+        Sf::Bgra8Unorm => Tf::Bgra8Unorm,
+
+        Sf::Rgb10a2Uint => Tf::Rgb10a2Uint,
+        Sf::Rgb10a2Unorm => Tf::Rgb10a2Unorm,
+        Sf::Rg11b10Float => Tf::Rg11b10Float,
+
+        Sf::Rg32Uint => Tf::Rg32Uint,
+        Sf::Rg32Sint => Tf::Rg32Sint,
+        Sf::Rg32Float => Tf::Rg32Float,
+        Sf::Rgba16Uint => Tf::Rgba16Uint,
+        Sf::Rgba16Sint => Tf::Rgba16Sint,
+        Sf::Rgba16Float => Tf::Rgba16Float,
+
+        Sf::Rgba32Uint => Tf::Rgba32Uint,
+        Sf::Rgba32Sint => Tf::Rgba32Sint,
+        Sf::Rgba32Float => Tf::Rgba32Float,
+
+        Sf::R16Unorm => Tf::R16Unorm,
+        Sf::R16Snorm => Tf::R16Snorm,
+        Sf::Rg16Unorm => Tf::Rg16Unorm,
+        Sf::Rg16Snorm => Tf::Rg16Snorm,
+        Sf::Rgba16Unorm => Tf::Rgba16Unorm,
+        Sf::Rgba16Snorm => Tf::Rgba16Snorm,
+    }
+}